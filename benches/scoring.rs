@@ -0,0 +1,210 @@
+//! BM25 评分、结果标准化、URL 去重与结果聚合的性能测试
+//!
+//! 使用固定种子生成 1k 与 10k 条合成结果，不同提交之间的结果可以直接比较。
+//! 基于 criterion：输出带置信区间的统计，并可保存基线用于回归对比：
+//!
+//! ```bash
+//! cargo bench --bench scoring
+//! cargo bench --bench scoring -- aggregate
+//! cargo bench --bench scoring -- --save-baseline main
+//! cargo bench --bench scoring -- --baseline main
+//! ```
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use seesea_core::derive::{ResultType, SearchQuery, SearchResult, SearchResultItem};
+use seesea_core::search::{
+    deduplicate_by_url, score_and_sort_results, score_results, standardize_results, AggregationStrategy,
+    SearchAggregator, SortBy,
+};
+
+/// 测试的结果集大小
+const SIZES: &[usize] = &[1_000, 10_000];
+/// 合成结果分布的引擎
+const ENGINES: &[&str] = &["bing", "baidu", "yandex", "sogou", "github", "stackoverflow"];
+/// 标题与摘要使用的词表
+const WORDS: &[&str] = &[
+    "rust", "programming", "language", "memory", "safety", "async", "tokio", "search", "engine", "privacy",
+    "搜索", "引擎", "编程", "语言", "performance", "tutorial", "guide", "crate", "compiler", "ownership",
+];
+/// 生成合成数据的随机种子
+const SEED: u64 = 42;
+
+/// 由词表随机组成的句子
+fn sentence(rng: &mut fastrand::Rng, words: std::ops::Range<usize>) -> String {
+    (0..rng.usize(words)).map(|_| WORDS[rng.usize(..WORDS.len())]).collect::<Vec<_>>().join(" ")
+}
+
+/// 合成结果条目
+///
+/// 约四分之一的 URL 重复，约三分之一的文本带有多余空白与 HTML 实体，与抓取到的摘要相近
+fn items(rng: &mut fastrand::Rng, count: usize) -> Vec<SearchResultItem> {
+    (0..count)
+        .map(|i| {
+            let id = if rng.u8(..4) == 0 { rng.usize(..count.max(1)) } else { i };
+            let (title, content) = if rng.u8(..3) == 0 {
+                (
+                    format!("\n  {} &amp; {} ", sentence(rng, 1..5), sentence(rng, 2..5)),
+                    format!("{}\t\t{} &lt;b&gt;", sentence(rng, 5..20), sentence(rng, 10..20)),
+                )
+            } else {
+                (sentence(rng, 3..10), sentence(rng, 15..40))
+            };
+            SearchResultItem {
+                title,
+                url: format!("https://example{}.com/page/{}", id % 97, id),
+                content,
+                display_url: None,
+                site_name: None,
+                score: 0.0,
+                result_type: ResultType::Web,
+                thumbnail: None,
+                published_date: None,
+                template: None,
+                metadata: HashMap::new(),
+            }
+        })
+        .collect()
+}
+
+/// 按引擎拆分、共 `count` 条的合成搜索结果
+fn results(rng: &mut fastrand::Rng, count: usize) -> Vec<SearchResult> {
+    let per_engine = count / ENGINES.len();
+    ENGINES
+        .iter()
+        .map(|engine| SearchResult {
+            engine_name: engine.to_string(),
+            total_results: Some(per_engine),
+            elapsed_ms: 0,
+            items: items(rng, per_engine),
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        })
+        .collect()
+}
+
+/// 将全部条目包装为单个引擎的结果
+fn single(items: &[SearchResultItem]) -> SearchResult {
+    SearchResult {
+        engine_name: "bing".to_string(),
+        total_results: Some(items.len()),
+        elapsed_ms: 0,
+        items: items.to_vec(),
+        pagination: None,
+        suggestions: Vec::new(),
+        metadata: HashMap::new(),
+    }
+}
+
+/// 测试使用的查询
+fn query() -> SearchQuery {
+    SearchQuery {
+        query: "rust programming language".to_string(),
+        ..Default::default()
+    }
+}
+
+/// 每种大小的合成条目与按引擎拆分的结果（同一大小下各测试使用相同数据）
+fn inputs() -> Vec<(usize, Vec<SearchResultItem>, Vec<SearchResult>)> {
+    SIZES
+        .iter()
+        .map(|&size| {
+            let mut rng = fastrand::Rng::with_seed(SEED);
+            let items = items(&mut rng, size);
+            let results = results(&mut rng, size);
+            (size, items, results)
+        })
+        .collect()
+}
+
+/// BM25 评分与评分后排序
+fn bench_bm25(c: &mut Criterion) {
+    let query = query();
+    let mut group = c.benchmark_group("bm25");
+    for (size, items, _) in inputs() {
+        group.bench_with_input(BenchmarkId::new("score_results", size), &items, |b, items| {
+            b.iter_batched(
+                || items.clone(),
+                |mut items| {
+                    score_results(&mut items, &query, "bing", None, None);
+                    items
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("score_and_sort", size), &items, |b, items| {
+            b.iter_batched(
+                || items.clone(),
+                |mut items| {
+                    score_and_sort_results(&mut items, &query, "bing", None);
+                    items
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// 结果标准化与 URL 去重
+fn bench_standardize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("standardize");
+    for (size, items, _) in inputs() {
+        group.bench_with_input(BenchmarkId::new("results", size), &items, |b, items| {
+            b.iter_batched(
+                || single(items),
+                |mut result| {
+                    standardize_results(&mut result);
+                    result
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("dedup_by_url", size), &items, |b, items| {
+            b.iter_batched(
+                || items.clone(),
+                |mut items| {
+                    deduplicate_by_url(&mut items);
+                    items
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// 各聚合策略
+fn bench_aggregate(c: &mut Criterion) {
+    let query = query();
+    let merged = SearchAggregator::new(AggregationStrategy::Merged, SortBy::Relevance);
+    let round_robin = SearchAggregator::new(AggregationStrategy::RoundRobin, SortBy::Relevance);
+
+    let mut group = c.benchmark_group("aggregate");
+    for (size, _, results) in inputs() {
+        group.bench_with_input(BenchmarkId::new("merged", size), &results, |b, results| {
+            b.iter_batched(|| results.clone(), |results| merged.aggregate(black_box(results)), BatchSize::LargeInput)
+        });
+        group.bench_with_input(BenchmarkId::new("round_robin", size), &results, |b, results| {
+            b.iter_batched(
+                || results.clone(),
+                |results| round_robin.aggregate(black_box(results)),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("with_scoring", size), &results, |b, results| {
+            b.iter_batched(
+                || results.clone(),
+                |results| merged.aggregate_with_scoring(black_box(results), &query),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_bm25, bench_standardize, bench_aggregate);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 构建脚本
+//!
+//! 启用 `grpc` 特性时编译 `proto/` 下的服务定义（使用纯 Rust 的 protox，不依赖 protoc）
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "grpc")]
+    compile_protos();
+}
+
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    println!("cargo:rerun-if-changed=proto");
+
+    let descriptors = protox::compile(["proto/seesea.proto"], ["proto"])
+        .unwrap_or_else(|e| panic!("failed to compile proto/seesea.proto: {}", e));
+    tonic_prost_build::configure()
+        .compile_fds(descriptors)
+        .unwrap_or_else(|e| panic!("failed to generate gRPC code: {}", e));
+}
@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use seesea_core::search::{clean_text, highlight_terms, highlight_text, HighlightMarkers};
+
+fuzz_target!(|data: &[u8]| {
+    // 首字节作为长度上限，其余作为文本
+    let Some((&max_length, rest)) = data.split_first() else {
+        return;
+    };
+    let text = String::from_utf8_lossy(rest);
+    let cleaned = clean_text(&text, max_length as usize);
+    assert!(cleaned.chars().count() <= max_length as usize);
+
+    let terms = highlight_terms(&text);
+    let _ = highlight_text(&cleaned, &terms, &HighlightMarkers::default());
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use seesea_core::search::QueryParser;
+
+fuzz_target!(|data: &[u8]| {
+    let query = String::from_utf8_lossy(data);
+    let parser = QueryParser::new().with_pii_scrubbing(true);
+    let parsed = parser.parse(&query);
+    assert_eq!(parsed.normalized, parsed.normalized.trim());
+});
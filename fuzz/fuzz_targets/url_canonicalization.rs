@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use seesea_core::api::handlers::redirect::strip_tracking_params;
+use seesea_core::derive::MagnetLink;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(url) = std::str::from_utf8(data) else {
+        return;
+    };
+    let rules = vec!["utm_*".to_string(), "fbclid".to_string()];
+    if let Some(stripped) = strip_tracking_params(url, &rules) {
+        assert_eq!(strip_tracking_params(&stripped, &rules).as_ref(), Some(&stripped));
+    }
+    if let Some(magnet) = MagnetLink::parse(url) {
+        assert_eq!(MagnetLink::parse(&magnet.to_uri()), Some(magnet));
+    }
+});
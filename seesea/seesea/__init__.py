@@ -63,11 +63,13 @@ __author__ = "SeeSea Team"
 try:
     from seesea_core import (
         PySearchClient,
+        PyAsyncSearchClient,
         PyApiServer,
         PyConfig,
         PyCacheStats,
         PyCacheInterface,
         PyRssClient,
+        PyHotClient,
         PyBrowserConfig,
         PyBrowserEngineClient,
         # 引擎注册函数（不再是类）
@@ -75,28 +77,39 @@ try:
         unregister_engine,
         list_engines,
         has_engine,
+        # 结果处理器注册函数
+        register_result_processor,
+        unregister_result_processor,
+        list_result_processors,
     )
 except ImportError as e:
     import warnings
     warnings.warn(f"Failed to import Rust core module: {e}. Please install seesea_core with 'pip install seesea_core'")
     PySearchClient = None
+    PyAsyncSearchClient = None
     PyApiServer = None
     PyConfig = None
     PyCacheStats = None
     PyCacheInterface = None
     PyRssClient = None
+    PyHotClient = None
     PyBrowserConfig = None
     PyBrowserEngineClient = None
     register_engine = None
     unregister_engine = None
     list_engines = None
     has_engine = None
+    register_result_processor = None
+    unregister_result_processor = None
+    list_result_processors = None
 
 # Python 高层接口
-from .search import SearchClient
+from .search import SearchClient, AsyncSearchClient
 from .api import ApiServer
 from .config import Config
 from .rss import RssClient
+from .hot import HotClient
+from . import hot
 from .browser import (
     BrowserEngineClient,
     BrowserConfig,
@@ -112,6 +125,7 @@ from .cli import cli as cli_main
 from .types import (
     SearchResponse,
     SearchResultItem,
+    EngineTiming,
     EngineState,
     CacheInfo,
     SearchStats,
@@ -170,7 +184,8 @@ def _auto_register_engines():
                     engine_type=engine_type,
                     description=description,
                     categories=categories,
-                    callback=callback
+                    callback=callback,
+                    capabilities=getattr(module, 'ENGINE_CAPABILITIES', None),
                 )
                 registered_count += 1
             else:
@@ -193,7 +208,8 @@ def _auto_register_engines():
                                 engine_type=engine_type,
                                 description=description,
                                 categories=categories,
-                                callback=callback
+                                callback=callback,
+                                capabilities=getattr(module, 'ENGINE_CAPABILITIES', None),
                             )
                             registered_count += 1
                             break
@@ -232,7 +248,10 @@ except Exception as e:
 __all__ = [
     # 主要类
     'SearchClient',
+    'AsyncSearchClient',
     'RssClient',
+    'HotClient',
+    'hot',
     'BrowserEngineClient',
     'BrowserConfig',
     'BrowserEngine',
@@ -244,6 +263,7 @@ __all__ = [
     # 类型定义（类型安全）
     'SearchResponse',
     'SearchResultItem',
+    'EngineTiming',
     'EngineState',
     'CacheInfo',
     'SearchStats',
@@ -251,7 +271,9 @@ __all__ = [
     
     # Rust 核心类（高级用户）
     'PySearchClient',
+    'PyAsyncSearchClient',
     'PyRssClient',
+    'PyHotClient',
     'PyBrowserConfig',
     'PyBrowserEngineClient',
     'PyApiServer',
@@ -264,6 +286,9 @@ __all__ = [
     'unregister_engine',
     'list_engines',
     'has_engine',
+    'register_result_processor',
+    'unregister_result_processor',
+    'list_result_processors',
     
     # 工具函数
     'format_results',
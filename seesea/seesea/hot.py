@@ -0,0 +1,107 @@
+# Copyright 2025 nostalgiatan
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+
+"""
+SeeSea Hot Client - 热榜客户端
+
+获取微博、知乎、Hacker News、Reddit 等平台的热门话题榜单
+"""
+
+from typing import Any, Dict, List, Optional
+from seesea_core import PyHotClient
+
+
+class HotClient:
+    """
+    SeeSea 热榜客户端
+
+    热榜在进程内缓存（默认 10 分钟），`force=True` 时忽略缓存。
+
+    示例:
+        >>> client = HotClient()
+        >>> for platform in client.list_platforms():
+        ...     print(platform['id'], platform['name'])
+        >>> hot = client.fetch_platform("weibo")
+        >>> for item in hot['items'][:10]:
+        ...     print(item['rank'], item['title'], item['heat'])
+    """
+
+    def __init__(self):
+        """初始化热榜客户端"""
+        self._client = PyHotClient()
+
+    def list_platforms(self) -> List[Dict[str, Any]]:
+        """
+        列出可用的热榜平台
+
+        Returns:
+            平台列表，每项包含 id、name、format、url
+        """
+        return self._client.list_platforms()
+
+    def fetch_platform(self, platform: str, force: bool = False) -> Dict[str, Any]:
+        """
+        获取一个平台的热榜
+
+        Args:
+            platform: 平台 ID（如 weibo、zhihu、hackernews、reddit）
+            force: 是否忽略缓存
+
+        Returns:
+            热榜字典，包含：
+            - platform: 平台 ID
+            - name: 平台名称
+            - fetched_at: 获取时间（Unix 时间戳）
+            - cached: 是否来自缓存
+            - items: 条目列表（rank、title、url、description、heat、label）
+
+        Raises:
+            RuntimeError: 平台未知或获取失败时抛出
+        """
+        return self._client.fetch_platform(platform, force)
+
+    def fetch_all(self, force: bool = False) -> List[Dict[str, Any]]:
+        """
+        获取所有平台的热榜（获取失败的平台被跳过）
+
+        Args:
+            force: 是否忽略缓存
+
+        Returns:
+            热榜字典列表
+        """
+        return self._client.fetch_all(force)
+
+
+_default_client: Optional[HotClient] = None
+
+
+def _client() -> HotClient:
+    global _default_client
+    if _default_client is None:
+        _default_client = HotClient()
+    return _default_client
+
+
+def list_platforms() -> List[Dict[str, Any]]:
+    """列出可用的热榜平台（使用共享客户端）"""
+    return _client().list_platforms()
+
+
+def fetch_platform(platform: str, force: bool = False) -> Dict[str, Any]:
+    """获取一个平台的热榜（使用共享客户端）"""
+    return _client().fetch_platform(platform, force)
+
+
+__all__ = ['HotClient', 'list_platforms', 'fetch_platform']
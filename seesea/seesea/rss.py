@@ -18,7 +18,8 @@ SeeSea RSS Client - RSS 订阅客户端
 提供简单易用的 RSS feed 获取和解析接口
 """
 
-from typing import Dict, List, Optional, Any
+from datetime import datetime
+from typing import Dict, Iterator, List, Optional, Any, Union
 from seesea_core import PyRssClient
 
 
@@ -203,5 +204,108 @@ class RssClient:
             max_results,
         )
     
+    def subscribe(self, url: str, update_interval: Optional[int] = None) -> Optional[Dict[str, Any]]:
+        """
+        订阅 RSS feed（立即获取并持久化）
+        
+        Args:
+            url: RSS feed URL
+            update_interval: 更新间隔（秒，默认 3600）
+        
+        Returns:
+            订阅信息（url、name、last_updated、update_interval、item_count）
+        
+        Raises:
+            RuntimeError: 获取失败时抛出
+        """
+        return self._client.subscribe(url, update_interval)
+    
+    def unsubscribe(self, url: str) -> bool:
+        """取消订阅并删除缓存的 feed，返回订阅是否存在"""
+        return self._client.unsubscribe(url)
+    
+    def list_subscriptions(self) -> List[Dict[str, Any]]:
+        """列出订阅"""
+        return self._client.list_subscriptions()
+    
+    def fetch_now(self, url: Optional[str] = None) -> Dict[str, int]:
+        """
+        立即刷新订阅，忽略更新间隔
+        
+        Args:
+            url: 只刷新该 feed（未指定时刷新所有订阅）
+        
+        Returns:
+            字典，键为 feed URL，值为条目数；刷新全部时失败的 feed 不包含在内
+        """
+        return self._client.fetch_now(url)
+    
+    def iter_items(
+        self,
+        categories: Optional[List[str]] = None,
+        since: Optional[Union[datetime, int]] = None,
+        until: Optional[Union[datetime, int]] = None,
+        feed_url: Optional[str] = None,
+        unread_for: Optional[str] = None,
+    ) -> Iterator[Dict[str, Any]]:
+        """
+        遍历订阅中缓存的条目（按发布时间从新到旧，不发起网络请求）
+        
+        Args:
+            categories: 只包含带有其中任一分类的条目（忽略大小写）
+            since: 发布时间下限（datetime 或 Unix 时间戳）
+            until: 发布时间上限（datetime 或 Unix 时间戳）
+            feed_url: 只包含该 feed 的条目
+            unread_for: 只包含该客户端令牌未读的条目
+        
+        Returns:
+            条目迭代器，条目字段同 fetch_feed，另含 feed_url 和 feed_title；
+            指定时间范围时跳过没有发布时间的条目
+        
+        示例:
+            >>> from datetime import datetime, timedelta
+            >>> for item in client.iter_items(categories=["tech"], since=datetime.now() - timedelta(days=1)):
+            ...     print(item['feed_title'], item['title'])
+        """
+        return self._client.iter_items(
+            categories,
+            _timestamp(since),
+            _timestamp(until),
+            feed_url,
+            unread_for,
+        )
+    
+    def mark_read(self, token: str, url: str, item_keys: List[str], read: bool = True) -> int:
+        """
+        标记条目为已读（read=False 时标记为未读）
+        
+        已读状态按客户端令牌分别保存，同一条目在多次刷新之间按 GUID 去重
+        
+        Args:
+            token: 客户端令牌（不能为空或包含 ':'）
+            url: RSS feed URL
+            item_keys: 条目的 guid（没有 guid 时使用 link）
+            read: 是否标记为已读
+        
+        Returns:
+            状态发生变化的条目数
+        """
+        return self._client.mark_read(token, url, item_keys, read)
+    
+    def mark_feed_read(self, token: str, url: str) -> int:
+        """将 feed 中缓存的全部条目标记为已读，返回新标记为已读的条目数"""
+        return self._client.mark_feed_read(token, url)
+    
+    def unread_counts(self, token: str) -> Dict[str, int]:
+        """统计每个订阅 feed 中的未读条目数，返回 {feed URL: 未读数}"""
+        return self._client.unread_counts(token)
+    
     def __repr__(self) -> str:
         return f"<RssClient>"
+
+
+def _timestamp(value: Optional[Union[datetime, int]]) -> Optional[int]:
+    """datetime 转换为 Unix 时间戳"""
+    if isinstance(value, datetime):
+        return int(value.timestamp())
+    return value
@@ -18,8 +18,9 @@ SeeSea Search Client - 搜索客户端
 提供简单易用的搜索接口
 """
 
+import json
 from typing import Dict, List, Optional, Any
-from seesea_core import PySearchClient
+from seesea_core import PySearchClient, PyAsyncSearchClient
 from .types import (
     SearchResponse,
     SearchResultItem,
@@ -57,6 +58,7 @@ class SearchClient:
         engines: Optional[List[str]] = None,
         force: Optional[bool] = False,
         cache_timeline: Optional[int] = None,
+        session: Optional[str] = None,
     ) -> SearchResponse:
         """
         执行搜索
@@ -70,6 +72,7 @@ class SearchClient:
             engines: 指定使用的搜索引擎列表（如 ["yandex", "bing"]）
             force: 强制搜索，绕过缓存（默认 False）
             cache_timeline: 缓存刷新时间线（秒），超过此时间强制刷新（默认 3600）
+            session: 搜索会话令牌（见 create_session），相同令牌的查询共享上下文
 
         Returns:
             SearchResponse 对象，包含：
@@ -79,6 +82,8 @@ class SearchClient:
             - cached: 是否来自缓存
             - query_time_ms: 查询耗时（毫秒）
             - engines_used: 使用的引擎列表
+            - per_engine: 各引擎的执行明细（耗时、结果数、缓存命中、错误与重试）
+            - related_queries: 搜索会话中之前的查询（最近的在前）
         
         Raises:
             RuntimeError: 搜索失败时抛出
@@ -99,6 +104,7 @@ class SearchClient:
             engines,
             force,
             cache_timeline,
+            session,
         )
         return SearchResponse.from_dict(result_dict)
     
@@ -119,6 +125,16 @@ class SearchClient:
         """
         return self._client.list_engines()
     
+    def engine_catalog(self) -> List[Dict[str, Any]]:
+        """
+        引擎能力目录（包括 Python 端注册的引擎）
+        
+        Returns:
+            每个引擎一项：id（engines 参数中使用的名称）、name、engine_type、categories、
+            capabilities（结果类型、分页、时间范围、语言/地区过滤等）、about、source、global 与 available
+        """
+        return json.loads(self._client.engine_catalog())
+    
     def health_check(self) -> Dict[str, bool]:
         """
         检查所有引擎的健康状态
@@ -299,5 +315,195 @@ class SearchClient:
             return None
         return PrivacyStats.from_dict(stats_dict)
     
+    def save_search(
+        self,
+        query: str,
+        interval_secs: int = 3600,
+        engines: Optional[List[str]] = None,
+        delivery: str = "store",
+    ) -> Dict[str, Any]:
+        """
+        保存搜索，按间隔重新执行，新结果产生告警
+        
+        Args:
+            query: 搜索查询
+            interval_secs: 执行间隔（秒，最小 60）
+            engines: 指定使用的引擎列表
+            delivery: 新结果的投递方式（store/webhook/both）
+            
+        Returns:
+            保存的搜索（包含 id、query、interval_secs 等字段）
+            
+        示例:
+            >>> saved = client.save_search("rust release", interval_secs=1800)
+            >>> print(saved["id"])
+        """
+        return self._client.save_search(query, interval_secs, engines, delivery)
+    
+    def list_saved_searches(self) -> List[Dict[str, Any]]:
+        """列出保存的搜索"""
+        return self._client.list_saved_searches()
+    
+    def delete_saved_search(self, saved_search_id: str) -> bool:
+        """删除保存的搜索及其告警，返回是否存在"""
+        return self._client.delete_saved_search(saved_search_id)
+    
+    def list_alerts(self, saved_search_id: Optional[str] = None) -> List[Dict[str, Any]]:
+        """
+        列出告警（最新的在前）
+        
+        Args:
+            saved_search_id: 只列出该保存的搜索的告警
+        """
+        return self._client.list_alerts(saved_search_id)
+    
+    def delete_alert(self, alert_id: str) -> bool:
+        """删除告警，返回是否存在"""
+        return self._client.delete_alert(alert_id)
+    
+    def run_saved_searches(self) -> int:
+        """立即执行所有到期的保存的搜索，返回产生的告警数"""
+        return self._client.run_saved_searches()
+    
+    def create_session(self) -> str:
+        """
+        创建搜索会话，返回会话令牌
+        
+        同一会话中之前查询的词用于提升相关结果，之前的查询作为 related_queries 返回
+        
+        示例:
+            >>> session = client.create_session()
+            >>> client.search("tokio", session=session)
+            >>> client.search("error handling", session=session).related_queries
+            ['tokio']
+        """
+        return self._client.create_session()
+    
+    def get_session(self, token: str) -> Optional[List[str]]:
+        """读取会话中的查询（最早的在前），会话不存在或已过期时返回 None"""
+        return self._client.get_session(token)
+    
+    def end_session(self, token: str) -> bool:
+        """结束搜索会话，返回是否存在"""
+        return self._client.end_session(token)
+    
     def __repr__(self) -> str:
         return f"<SearchClient>"
+
+
+class AsyncSearchClient:
+    """
+    SeeSea 异步搜索客户端
+    
+    方法返回协程，可在 asyncio 事件循环中并发执行而不阻塞。
+    
+    示例:
+        >>> client = AsyncSearchClient()
+        >>> response = await client.search("rust programming")
+        >>> responses = await asyncio.gather(
+        ...     client.search("rust"),
+        ...     client.search("python"),
+        ... )
+    """
+    
+    def __init__(self):
+        """初始化异步搜索客户端"""
+        self._client = PyAsyncSearchClient()
+    
+    async def search(
+        self,
+        query: str,
+        page: Optional[int] = 1,
+        page_size: Optional[int] = 10,
+        language: Optional[str] = None,
+        region: Optional[str] = None,
+        engines: Optional[List[str]] = None,
+        force: Optional[bool] = False,
+        session: Optional[str] = None,
+    ) -> SearchResponse:
+        """
+        执行搜索
+
+        Args:
+            query: 搜索关键词
+            page: 页码（从1开始）
+            page_size: 每页结果数
+            language: 语言过滤（如 "zh", "en"）
+            region: 地区过滤（如 "cn", "us"）
+            engines: 指定使用的搜索引擎列表
+            force: 强制搜索，绕过缓存（默认 False）
+            session: 搜索会话令牌，相同令牌的查询共享上下文
+
+        Returns:
+            SearchResponse 对象
+        
+        Raises:
+            RuntimeError: 搜索失败时抛出
+        """
+        result_dict = await self._client.search(
+            query,
+            page,
+            page_size,
+            language,
+            region,
+            engines,
+            force,
+            session,
+        )
+        return SearchResponse.from_dict(result_dict)
+    
+    async def search_streaming(
+        self,
+        query: str,
+        callback,
+        page: Optional[int] = 1,
+        page_size: Optional[int] = 10,
+        engines: Optional[List[str]] = None,
+    ) -> Dict[str, Any]:
+        """
+        流式搜索 - 每个引擎完成时立即调用回调函数
+        
+        回调在 Rust 运行时线程上调用，应尽快返回；
+        需要与事件循环交互时使用 loop.call_soon_threadsafe。
+        
+        Args:
+            query: 搜索关键词
+            callback: 回调函数，签名为 callback(result_dict)
+            page: 页码
+            page_size: 每页大小
+            engines: 指定引擎列表
+            
+        Returns:
+            最终聚合的搜索结果
+        """
+        return await self._client.search_streaming(
+            query,
+            callback,
+            page,
+            page_size,
+            engines,
+        )
+    
+    async def suggest(self, query: str) -> List[str]:
+        """
+        获取查询建议（拼写纠正）
+        
+        Args:
+            query: 搜索关键词
+            
+        Returns:
+            建议的查询列表
+        """
+        return await self._client.suggest(query)
+    
+    def list_engines(self) -> List[str]:
+        """
+        列出所有可用的搜索引擎
+        
+        Returns:
+            引擎名称列表
+        """
+        return self._client.list_engines()
+    
+    def __repr__(self) -> str:
+        return f"<AsyncSearchClient>"
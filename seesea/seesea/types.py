@@ -58,6 +58,39 @@ class SearchResultItem:
         return f"<SearchResultItem title='{self.title[:50]}...' url='{self.url}' score={self.score:.2f}>"
 
 
+@dataclass
+class EngineTiming:
+    """
+    单个引擎的执行明细
+    
+    Attributes:
+        engine: 引擎名称
+        elapsed_ms: 耗时（毫秒）
+        result_count: 返回的结果数
+        cache_hit: 是否完全由页面缓存提供
+        error: 错误信息（失败、超时或被跳过时）
+        retries: 重复发送相同请求的次数
+    """
+    engine: str
+    elapsed_ms: int = 0
+    result_count: int = 0
+    cache_hit: bool = False
+    error: Optional[str] = None
+    retries: int = 0
+    
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> 'EngineTiming':
+        """从字典创建引擎执行明细"""
+        return cls(
+            engine=data.get('engine', ''),
+            elapsed_ms=data.get('elapsed_ms', 0),
+            result_count=data.get('result_count', 0),
+            cache_hit=data.get('cache_hit', False),
+            error=data.get('error'),
+            retries=data.get('retries', 0),
+        )
+
+
 @dataclass
 class SearchResponse:
     """
@@ -70,6 +103,8 @@ class SearchResponse:
         cached: 是否来自缓存
         query_time_ms: 查询耗时（毫秒）
         engines_used: 使用的引擎列表
+        per_engine: 各引擎的执行明细
+        related_queries: 搜索会话中之前的查询（最近的在前）
     """
     query: str
     results: List[SearchResultItem]
@@ -77,6 +112,8 @@ class SearchResponse:
     cached: bool
     query_time_ms: int
     engines_used: List[str] = field(default_factory=list)
+    per_engine: List[EngineTiming] = field(default_factory=list)
+    related_queries: List[str] = field(default_factory=list)
     
     @classmethod
     def from_dict(cls, data: Dict[str, Any]) -> 'SearchResponse':
@@ -93,6 +130,11 @@ class SearchResponse:
             cached=data.get('cached', False),
             query_time_ms=data.get('query_time_ms', 0),
             engines_used=data.get('engines_used', []),
+            per_engine=[
+                EngineTiming.from_dict(timing)
+                for timing in data.get('per_engine', [])
+            ],
+            related_queries=data.get('related_queries', []),
         )
     
     def __repr__(self) -> str:
@@ -0,0 +1,227 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 结果格式化
+//!
+//! 将 API 搜索响应渲染为 RSS 2.0 或 Atom 1.0 feed，供订阅阅读器使用
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::types::ApiSearchResponse;
+use crate::web::escape_html;
+
+/// Feed 格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedFormat {
+    /// RSS 2.0
+    #[default]
+    Rss,
+    /// Atom 1.0
+    Atom,
+}
+
+impl FeedFormat {
+    /// 响应的 Content-Type
+    pub fn content_type(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+        }
+    }
+}
+
+/// Feed 的频道信息
+#[derive(Debug, Clone)]
+pub struct FeedInfo<'a> {
+    /// 标题
+    pub title: &'a str,
+    /// 对应的网页链接（绝对地址）
+    pub link: &'a str,
+    /// feed 自身的地址（绝对地址）
+    pub self_link: &'a str,
+    /// 站点根地址，用于补全结果中的站内相对链接（如跳转链接）
+    pub base_url: &'a str,
+    /// 更新时间
+    pub updated: DateTime<Utc>,
+}
+
+impl FeedInfo<'_> {
+    /// 将站内相对链接补全为绝对地址
+    fn absolute(&self, url: &str) -> String {
+        if url.starts_with('/') && !url.starts_with("//") {
+            format!("{}{}", self.base_url.trim_end_matches('/'), url)
+        } else {
+            url.to_string()
+        }
+    }
+}
+
+/// 将搜索响应渲染为 feed
+///
+/// # 参数
+///
+/// * `format` - Feed 格式
+/// * `info` - 频道信息
+/// * `response` - 搜索响应
+///
+/// # 返回值
+///
+/// 返回 XML 文本，所有插入的文本都经过转义
+pub fn render_feed(format: FeedFormat, info: &FeedInfo<'_>, response: &ApiSearchResponse) -> String {
+    match format {
+        FeedFormat::Rss => render_rss(info, response),
+        FeedFormat::Atom => render_atom(info, response),
+    }
+}
+
+/// 渲染 RSS 2.0
+fn render_rss(info: &FeedInfo<'_>, response: &ApiSearchResponse) -> String {
+    let updated = info.updated.to_rfc2822();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\"><channel>");
+    xml.push_str(&format!(
+        "<title>{}</title><link>{}</link><description>{}</description>",
+        escape_html(info.title),
+        escape_html(info.link),
+        escape_html(&format!("SeeSea: {}", response.query)),
+    ));
+    xml.push_str(&format!(
+        "<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>",
+        escape_html(info.self_link)
+    ));
+    xml.push_str(&format!("<lastBuildDate>{}</lastBuildDate><generator>SeeSea</generator>", updated));
+
+    for item in &response.results {
+        let url = info.absolute(&item.url);
+        xml.push_str(&format!(
+            "<item><title>{}</title><link>{}</link><guid isPermaLink=\"true\">{}</guid><category>{}</category>",
+            escape_html(&item.title),
+            escape_html(&url),
+            escape_html(&url),
+            escape_html(&item.engine),
+        ));
+        if let Some(description) = item.description.as_deref().filter(|d| !d.is_empty()) {
+            xml.push_str(&format!("<description>{}</description>", escape_html(description)));
+        }
+        xml.push_str(&format!("<pubDate>{}</pubDate></item>", updated));
+    }
+
+    xml.push_str("</channel></rss>\n");
+    xml
+}
+
+/// 渲染 Atom 1.0
+fn render_atom(info: &FeedInfo<'_>, response: &ApiSearchResponse) -> String {
+    let updated = info.updated.to_rfc3339();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">");
+    xml.push_str(&format!(
+        "<id>{}</id><title>{}</title><updated>{}</updated><generator>SeeSea</generator>",
+        escape_html(info.self_link),
+        escape_html(info.title),
+        updated,
+    ));
+    xml.push_str(&format!(
+        "<link rel=\"self\" href=\"{}\"/><link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>",
+        escape_html(info.self_link),
+        escape_html(info.link),
+    ));
+
+    for item in &response.results {
+        let url = info.absolute(&item.url);
+        xml.push_str(&format!(
+            "<entry><id>{}</id><title>{}</title><link href=\"{}\"/><updated>{}</updated><author><name>{}</name></author>",
+            escape_html(&url),
+            escape_html(&item.title),
+            escape_html(&url),
+            updated,
+            escape_html(&item.engine),
+        ));
+        if let Some(description) = item.description.as_deref().filter(|d| !d.is_empty()) {
+            xml.push_str(&format!("<summary>{}</summary>", escape_html(description)));
+        }
+        xml.push_str("</entry>");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::ApiSearchResultItem;
+
+    fn response() -> ApiSearchResponse {
+        ApiSearchResponse {
+            query: "rust & go".to_string(),
+            results: vec![ApiSearchResultItem {
+                title: "Rust <1.80>".to_string(),
+                url: "/redirect?url=https%3A%2F%2Frust-lang.org&sig=abc".to_string(),
+                description: Some("A \"systems\" language".to_string()),
+                thumbnail: None,
+                engine: "bing".to_string(),
+                score: Some(1.0),
+                explain: None,
+            }],
+            total_count: 1,
+            page: 1,
+            page_size: 10,
+            engines_used: vec!["bing".to_string()],
+            query_time_ms: 0,
+            cached: false,
+            warnings: Vec::new(),
+            answer: None,
+            suggestions: Vec::new(),
+            related_queries: Vec::new(),
+            debug: None,
+            per_engine: Vec::new(),
+            partial: false,
+            request_id: None,
+        }
+    }
+
+    fn info() -> FeedInfo<'static> {
+        FeedInfo {
+            title: "SeeSea: rust & go",
+            link: "https://search.example/?q=rust+%26+go",
+            self_link: "https://search.example/feeds/search?q=rust+%26+go",
+            base_url: "https://search.example",
+            updated: DateTime::parse_from_rfc3339("2025-06-10T08:00:00Z").unwrap().with_timezone(&Utc),
+        }
+    }
+
+    #[test]
+    fn test_render_rss() {
+        let xml = render_feed(FeedFormat::Rss, &info(), &response());
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<title>Rust &lt;1.80&gt;</title>"));
+        assert!(xml.contains("<link>https://search.example/redirect?url=https%3A%2F%2Frust-lang.org&amp;sig=abc</link>"));
+        assert!(xml.contains("<description>A &quot;systems&quot; language</description>"));
+        assert!(xml.contains("<pubDate>Tue, 10 Jun 2025 08:00:00 +0000</pubDate>"));
+        assert!(xml.contains("rel=\"self\""));
+    }
+
+    #[test]
+    fn test_render_atom() {
+        let xml = render_feed(FeedFormat::Atom, &info(), &response());
+        assert!(xml.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+        assert!(xml.contains("<updated>2025-06-10T08:00:00+00:00</updated>"));
+        assert!(xml.contains("<entry><id>https://search.example/redirect?url=https%3A%2F%2Frust-lang.org&amp;sig=abc</id>"));
+        assert!(xml.contains("<author><name>bing</name></author>"));
+        assert_eq!(FeedFormat::Atom.content_type(), "application/atom+xml; charset=utf-8");
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! gRPC 服务接口
+//!
+//! 基于 tonic 提供搜索、查询建议与 RSS 获取，供服务间集成使用；
+//! `SearchStream` 在每个引擎返回时推送一次结果。服务定义见 `proto/seesea.proto`
+
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tokio_stream::Stream;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tonic::{Request, Response, Status};
+
+use crate::derive::rss::{RssFeed, RssFeedItem, RssFeedQuery};
+use crate::derive::{SearchQuery, SearchResult, SearchResultItem};
+use crate::rss::RssInterface;
+use crate::search::{EngineTiming, SearchInterface, SearchRequest, SearchResponse};
+
+/// 由 `proto/seesea.proto` 生成的消息与服务代码
+pub mod proto {
+    tonic::include_proto!("seesea.v1");
+}
+
+use proto::see_sea_server::{SeeSea, SeeSeaServer};
+
+/// gRPC 服务
+#[derive(Clone)]
+pub struct GrpcService {
+    /// 搜索接口
+    search: Arc<SearchInterface>,
+    /// RSS 接口
+    rss: Arc<RssInterface>,
+}
+
+impl GrpcService {
+    /// 创建 gRPC 服务
+    ///
+    /// # Arguments
+    ///
+    /// * `search` - 搜索接口（与 HTTP API 共享时两者共用引擎状态与缓存）
+    /// * `rss` - RSS 接口
+    pub fn new(search: Arc<SearchInterface>, rss: Arc<RssInterface>) -> Self {
+        Self { search, rss }
+    }
+
+    /// 使用搜索接口的 HTTP 客户端创建不带缓存的 RSS 接口
+    pub fn with_search(search: Arc<SearchInterface>) -> Self {
+        let rss = Arc::new(RssInterface::new(Arc::clone(search.http_client())));
+        Self::new(search, rss)
+    }
+
+    /// 包装为 tonic 服务
+    pub fn into_server(self) -> SeeSeaServer<Self> {
+        SeeSeaServer::new(self)
+    }
+
+    /// 在已绑定的监听器上启动 gRPC 服务器，并在 `shutdown` 完成时优雅停机
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - 已绑定的 TCP 监听器
+    /// * `shutdown` - 停机信号
+    pub async fn serve_with_shutdown<F>(
+        self,
+        listener: tokio::net::TcpListener,
+        shutdown: F,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        tonic::transport::Server::builder()
+            .add_service(self.into_server())
+            .serve_with_incoming_shutdown(tonic::transport::server::TcpIncoming::from(listener), shutdown)
+            .await?;
+        Ok(())
+    }
+
+    /// 绑定地址并启动 gRPC 服务器
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        self.serve_with_shutdown(listener, std::future::pending()).await
+    }
+}
+
+/// 流式搜索的事件流
+type SearchEventStream = Pin<Box<dyn Stream<Item = Result<proto::SearchEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl SeeSea for GrpcService {
+    async fn search(
+        &self,
+        request: Request<proto::SearchRequest>,
+    ) -> Result<Response<proto::SearchResponse>, Status> {
+        let request = to_search_request(request.into_inner())?;
+        let response = self
+            .search
+            .search(&request)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(from_search_response(&response)))
+    }
+
+    type SearchStreamStream = SearchEventStream;
+
+    async fn search_stream(
+        &self,
+        request: Request<proto::SearchRequest>,
+    ) -> Result<Response<Self::SearchStreamStream>, Status> {
+        let request = to_search_request(request.into_inner())?;
+        let search = Arc::clone(&self.search);
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let engine_sender = sender.clone();
+            let result = search
+                .search_streaming(&request, move |result, engine| {
+                    let event = proto::search_event::Event::EngineResult(from_engine_result(&engine, &result));
+                    let _ = engine_sender.send(Ok(proto::SearchEvent { event: Some(event) }));
+                })
+                .await;
+            let message = match result {
+                Ok(response) => Ok(proto::SearchEvent {
+                    event: Some(proto::search_event::Event::Summary(from_search_response(&response))),
+                }),
+                Err(e) => Err(Status::internal(e.to_string())),
+            };
+            let _ = sender.send(message);
+        });
+
+        Ok(Response::new(Box::pin(UnboundedReceiverStream::new(receiver))))
+    }
+
+    async fn suggest(
+        &self,
+        request: Request<proto::SuggestRequest>,
+    ) -> Result<Response<proto::SuggestResponse>, Status> {
+        let suggestions = self.search.suggest(&request.into_inner().query);
+        Ok(Response::new(proto::SuggestResponse { suggestions }))
+    }
+
+    async fn fetch_feed(
+        &self,
+        request: Request<proto::FetchFeedRequest>,
+    ) -> Result<Response<proto::Feed>, Status> {
+        let request = request.into_inner();
+        if request.url.trim().is_empty() {
+            return Err(Status::invalid_argument("url must not be empty"));
+        }
+        let query = RssFeedQuery {
+            url: request.url,
+            max_items: request.max_items.map(|max| max as usize),
+            filter_keywords: request.filter_keywords,
+            after_date: None,
+        };
+        let feed = self
+            .rss
+            .fetch(&query)
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+        Ok(Response::new(from_feed(feed)))
+    }
+}
+
+/// 转换 gRPC 搜索请求
+fn to_search_request(request: proto::SearchRequest) -> Result<SearchRequest, Status> {
+    if request.query.trim().is_empty() {
+        return Err(Status::invalid_argument("query must not be empty"));
+    }
+
+    let mut query = SearchQuery {
+        query: request.query,
+        language: request.language,
+        region: request.region,
+        ..Default::default()
+    };
+    if request.page > 0 {
+        query.page = request.page as usize;
+    }
+    if request.page_size > 0 {
+        query.page_size = request.page_size as usize;
+    }
+
+    Ok(SearchRequest {
+        query,
+        engines: request.engines,
+        force: request.force,
+        ..Default::default()
+    })
+}
+
+/// 转换结果条目
+fn from_item(item: &SearchResultItem) -> proto::ResultItem {
+    proto::ResultItem {
+        title: item.title.clone(),
+        url: item.url.clone(),
+        content: item.content.clone(),
+        score: item.score,
+        display_url: item.display_url.clone(),
+        thumbnail: item.thumbnail.clone(),
+        published_date: item.published_date.map(|date| date.to_rfc3339()),
+    }
+}
+
+/// 转换单个引擎的结果
+fn from_engine_result(engine: &str, result: &SearchResult) -> proto::EngineResult {
+    proto::EngineResult {
+        engine: engine.to_string(),
+        items: result.items.iter().map(from_item).collect(),
+        elapsed_ms: result.elapsed_ms,
+    }
+}
+
+/// 转换搜索响应
+fn from_search_response(response: &SearchResponse) -> proto::SearchResponse {
+    proto::SearchResponse {
+        results: response
+            .results
+            .iter()
+            .map(|result| from_engine_result(&result.engine_name, result))
+            .collect(),
+        engines_used: response.engines_used.clone(),
+        total_count: response.total_count as u64,
+        query_time_ms: response.query_time_ms,
+        cached: response.cached,
+        suggestions: response.suggestions.clone(),
+        per_engine: response.per_engine.iter().map(from_engine_timing).collect(),
+    }
+}
+
+/// 转换单个引擎的执行明细
+fn from_engine_timing(timing: &EngineTiming) -> proto::EngineTiming {
+    proto::EngineTiming {
+        engine: timing.engine.clone(),
+        elapsed_ms: timing.elapsed_ms,
+        result_count: timing.result_count as u64,
+        cache_hit: timing.cache_hit,
+        error: timing.error.clone(),
+        retries: timing.retries,
+    }
+}
+
+/// 转换 feed 条目
+fn from_feed_item(item: RssFeedItem) -> proto::FeedItem {
+    proto::FeedItem {
+        title: item.title,
+        link: item.link,
+        description: item.description,
+        author: item.author,
+        pub_date: item.pub_date,
+        categories: item.categories,
+    }
+}
+
+/// 转换 feed
+fn from_feed(feed: RssFeed) -> proto::Feed {
+    proto::Feed {
+        title: feed.meta.title,
+        link: feed.meta.link,
+        description: feed.meta.description,
+        items: feed.items.into_iter().map(from_feed_item).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::SearchConfig;
+
+    #[test]
+    fn test_search_request_conversion() {
+        let request = proto::SearchRequest {
+            query: "rust".to_string(),
+            engines: vec!["bing".to_string()],
+            page: 2,
+            page_size: 0,
+            language: Some("en".to_string()),
+            region: None,
+            force: true,
+        };
+        let converted = to_search_request(request).unwrap();
+        assert_eq!(converted.query.page, 2);
+        assert_eq!(converted.query.page_size, SearchQuery::default().page_size);
+        assert_eq!(converted.engines, vec!["bing"]);
+        assert!(converted.force);
+
+        let empty = proto::SearchRequest {
+            query: "  ".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(to_search_request(empty).unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_roundtrip() {
+        let config = SearchConfig {
+            enable_cache: false,
+            ..Default::default()
+        };
+        let search = Arc::new(SearchInterface::new(config).unwrap());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(GrpcService::with_search(search).serve_with_shutdown(listener, async {
+            let _ = stopped.await;
+        }));
+
+        let mut client = proto::see_sea_client::SeeSeaClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+        let response = client
+            .suggest(proto::SuggestRequest { query: "rust".to_string() })
+            .await
+            .unwrap();
+        assert!(response.into_inner().suggestions.is_empty());
+
+        let status = client
+            .fetch_feed(proto::FetchFeedRequest::default())
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let _ = stop.send(());
+        server.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 保存的搜索与告警 API 处理器
+//!
+//! 注册、列出与删除保存的搜索，查看与删除其产生的告警
+
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use crate::api::on::ApiState;
+use crate::api::types::ApiErrorResponse;
+use crate::cache::{AlertDelivery, SavedSearch};
+
+/// 注册保存的搜索请求
+#[derive(Debug, Deserialize)]
+pub struct SavedSearchCreateRequest {
+    /// 查询文本
+    pub query: String,
+    /// 使用的引擎（为空时使用默认引擎）
+    #[serde(default)]
+    pub engines: Vec<String>,
+    /// 执行间隔（秒）
+    #[serde(default = "default_interval")]
+    pub interval_secs: u64,
+    /// 新结果的投递方式
+    #[serde(default)]
+    pub delivery: AlertDelivery,
+}
+
+fn default_interval() -> u64 {
+    3600
+}
+
+/// 告警列表查询参数
+#[derive(Debug, Deserialize)]
+pub struct AlertListQuery {
+    /// 只列出该保存的搜索的告警
+    pub saved_search_id: Option<String>,
+}
+
+/// 错误响应
+fn error_response(status: StatusCode, code: &str, message: &str, details: Option<String>) -> Response {
+    let error = ApiErrorResponse {
+        code: code.to_string(),
+        message: message.to_string(),
+        details,
+    };
+    (status, Json(error)).into_response()
+}
+
+/// 未设置告警存储时的响应
+fn alerts_disabled() -> Response {
+    error_response(StatusCode::SERVICE_UNAVAILABLE, "ALERTS_DISABLED", "未启用保存的搜索", None)
+}
+
+/// 存储操作失败的响应
+fn storage_error(e: impl std::fmt::Display) -> Response {
+    error_response(StatusCode::INTERNAL_SERVER_ERROR, "ALERT_STORAGE_ERROR", "读写告警存储失败", Some(e.to_string()))
+}
+
+/// 处理列出保存的搜索请求
+pub async fn handle_saved_searches_list(State(state): State<ApiState>) -> Response {
+    let Some(store) = state.alerts.as_ref() else {
+        return alerts_disabled();
+    };
+    match store.list_searches_async().await {
+        Ok(searches) => (StatusCode::OK, Json(searches)).into_response(),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理注册保存的搜索请求
+pub async fn handle_saved_search_create(
+    State(state): State<ApiState>,
+    Json(request): Json<SavedSearchCreateRequest>,
+) -> Response {
+    let Some(store) = state.alerts.as_ref() else {
+        return alerts_disabled();
+    };
+    if request.query.trim().is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "INVALID_QUERY", "查询不能为空", None);
+    }
+
+    let search = SavedSearch::new(&request.query, request.engines, request.interval_secs, request.delivery);
+    match store.save_search_async(search.clone()).await {
+        Ok(()) => (StatusCode::CREATED, Json(search)).into_response(),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理删除保存的搜索请求（同时删除其告警）
+pub async fn handle_saved_search_delete(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(store) = state.alerts.as_ref() else {
+        return alerts_disabled();
+    };
+    match store.delete_search_async(&id).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => error_response(StatusCode::NOT_FOUND, "NOT_FOUND", "保存的搜索不存在", None),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理列出告警请求
+pub async fn handle_alerts_list(
+    State(state): State<ApiState>,
+    Query(query): Query<AlertListQuery>,
+) -> Response {
+    let Some(store) = state.alerts.as_ref() else {
+        return alerts_disabled();
+    };
+    match store.list_alerts_async(query.saved_search_id.as_deref()).await {
+        Ok(alerts) => (StatusCode::OK, Json(alerts)).into_response(),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理删除告警请求
+pub async fn handle_alert_delete(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(store) = state.alerts.as_ref() else {
+        return alerts_disabled();
+    };
+    match store.delete_alert_async(&id).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => error_response(StatusCode::NOT_FOUND, "NOT_FOUND", "告警不存在", None),
+        Err(e) => storage_error(e),
+    }
+}
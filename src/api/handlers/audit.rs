@@ -0,0 +1,82 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 审计日志 API 处理器
+//!
+//! 查询审计日志，仅限具有 `admin` 权限的 API 密钥
+
+use axum::{
+    extract::{Extension, Json, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use crate::api::middleware::ApiClient;
+use crate::api::on::ApiState;
+use crate::api::types::ApiErrorResponse;
+use crate::cache::AuditEvent;
+
+/// 审计日志路径
+pub const AUDIT_PATH: &str = "/api/audit";
+
+/// 默认返回的记录数
+const DEFAULT_LIMIT: usize = 100;
+
+/// 单次最多返回的记录数
+const MAX_LIMIT: usize = 1000;
+
+/// 审计日志查询参数
+#[derive(Debug, Deserialize)]
+pub struct AuditListQuery {
+    /// 只返回该类型的记录
+    pub event: Option<AuditEvent>,
+    /// 只返回不早于该时间（Unix 时间戳）的记录
+    pub since: Option<u64>,
+    /// 最多返回的记录数
+    pub limit: Option<usize>,
+}
+
+/// 错误响应
+fn error_response(status: StatusCode, code: &str, message: &str, details: Option<String>) -> Response {
+    let error = ApiErrorResponse {
+        code: code.to_string(),
+        message: message.to_string(),
+        details,
+    };
+    (status, Json(error)).into_response()
+}
+
+/// 处理查询审计日志请求
+pub async fn handle_audit_list(
+    State(state): State<ApiState>,
+    Extension(client): Extension<ApiClient>,
+    Query(query): Query<AuditListQuery>,
+) -> Response {
+    if !client.admin {
+        return error_response(StatusCode::FORBIDDEN, "FORBIDDEN", "需要管理员权限", None);
+    }
+    let Some(audit) = state.audit.as_ref() else {
+        return error_response(StatusCode::SERVICE_UNAVAILABLE, "AUDIT_DISABLED", "未启用审计日志", None);
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    match audit.list_async(query.event, query.since, limit).await {
+        Ok(entries) => (StatusCode::OK, Json(entries)).into_response(),
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "AUDIT_STORAGE_ERROR",
+            "读取审计日志失败",
+            Some(e.to_string()),
+        ),
+    }
+}
@@ -17,13 +17,15 @@
 //! 处理缓存管理相关的 API 请求
 
 use axum::{
-    extract::State,
+    extract::{Extension, State},
     response::{IntoResponse, Response},
     http::StatusCode,
     Json,
 };
 use serde::Serialize;
+use crate::api::middleware::{ApiClient, RequestId};
 use crate::api::on::ApiState;
+use crate::cache::{AuditEntry, AuditEvent};
 
 /// 缓存统计响应
 #[derive(Debug, Serialize)]
@@ -70,10 +72,20 @@ pub async fn handle_cache_stats(
     (StatusCode::OK, Json(stats)).into_response()
 }
 
+/// 记录缓存管理操作的审计日志
+fn audit_admin_action(state: &ApiState, client: &ApiClient, request_id: &RequestId, action: &str) {
+    state.audit(
+        AuditEntry::new(AuditEvent::AdminAction, client.principal(), action).with_request_id(Some(&request_id.0)),
+    );
+}
+
 /// 处理清除所有缓存请求
 pub async fn handle_cache_clear(
-    State(_state): State<ApiState>,
+    State(state): State<ApiState>,
+    Extension(client): Extension<ApiClient>,
+    Extension(request_id): Extension<RequestId>,
 ) -> Response {
+    audit_admin_action(&state, &client, &request_id, "cache.clear");
     // TODO: 实现缓存清理
     let response = CacheClearResponse {
         success: true,
@@ -86,8 +98,11 @@ pub async fn handle_cache_clear(
 
 /// 处理清理过期缓存请求
 pub async fn handle_cache_cleanup(
-    State(_state): State<ApiState>,
+    State(state): State<ApiState>,
+    Extension(client): Extension<ApiClient>,
+    Extension(request_id): Extension<RequestId>,
 ) -> Response {
+    audit_admin_action(&state, &client, &request_id, "cache.cleanup");
     // TODO: 实现过期缓存清理
     let response = CacheClearResponse {
         success: true,
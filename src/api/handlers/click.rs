@@ -0,0 +1,99 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! 结果点击 API 处理器
+//!
+//! 启用点击统计后结果链接经 `/click?e=<引擎>&u=<链接>&s=<签名>` 跳转，
+//! 只累计引擎与目标域名的点击数，随后与 `/r` 一样校验签名、去除追踪参数并跳转
+
+use axum::{
+    extract::{Query, State},
+    response::Response,
+};
+use serde::Deserialize;
+
+use crate::api::handlers::redirect;
+use crate::api::on::ApiState;
+use crate::api::signing::{LinkSigner, SIGNATURE_PARAM};
+use crate::search::clicks::click_domain;
+
+/// 点击端点路径
+pub const CLICK_PATH: &str = "/click";
+
+/// 点击请求参数
+#[derive(Debug, Deserialize)]
+pub struct ClickParams {
+    /// 结果来源引擎
+    pub e: Option<String>,
+    /// 目标 URL
+    pub u: String,
+    /// 目标 URL 的签名
+    pub s: Option<String>,
+}
+
+/// 将结果链接改写为经点击端点跳转的链接
+///
+/// # Arguments
+///
+/// * `target` - 原始链接
+/// * `engine` - 结果来源引擎
+/// * `base_url` - 端点的外部访问前缀，为空则使用相对路径
+/// * `signer` - 链接签名器（签名附加在 `s` 参数中）
+pub fn click_url(target: &str, engine: &str, base_url: Option<&str>, signer: &LinkSigner) -> String {
+    let base = base_url.map(|b| b.trim_end_matches('/')).unwrap_or("");
+    format!(
+        "{}{}?e={}&u={}&{}={}",
+        base,
+        CLICK_PATH,
+        urlencoding::encode(engine),
+        urlencoding::encode(target),
+        SIGNATURE_PARAM,
+        signer.sign(target)
+    )
+}
+
+/// 处理结果点击
+///
+/// 未启用点击统计时只跳转不计数；签名无效的链接既不计数也不跳转
+pub async fn handle_click(
+    State(state): State<ApiState>,
+    Query(params): Query<ClickParams>,
+) -> Response {
+    if let Some(response) = redirect::reject_unsigned(&state, &params.u, params.s.as_deref()) {
+        return response;
+    }
+    if state.click_tracking.enabled
+        && let Some(domain) = click_domain(&params.u)
+    {
+        state.search.record_click(params.e.as_deref(), &domain).await;
+    }
+    redirect::follow_link(&state, &params.u).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_click_url() {
+        let signer = LinkSigner::new(b"test");
+        assert_eq!(
+            click_url("https://a.com/?x=1", "bing", None, &signer),
+            format!("/click?e=bing&u=https%3A%2F%2Fa.com%2F%3Fx%3D1&s={}", signer.sign("https://a.com/?x=1"))
+        );
+        assert!(
+            click_url("https://a.com", "bing", Some("https://search.example.com/"), &signer)
+                .starts_with("https://search.example.com/click?e=bing&u=")
+        );
+    }
+}
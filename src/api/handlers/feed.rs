@@ -0,0 +1,181 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 搜索订阅 feed 处理器
+//!
+//! `/feeds/search` 将一个查询发布为 RSS/Atom feed：每次请求重新执行搜索（命中缓存时直接使用缓存结果），
+//! 使任意订阅阅读器都可以跟踪该查询。`token` 为 `/api/preferences` 创建的偏好令牌，
+//! 用于补全未指定的语言、引擎与每页结果数（阅读器无法携带网页界面的 Cookie）
+
+use axum::{
+    extract::{Extension, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+
+use crate::api::formatter::{render_feed, FeedFormat, FeedInfo};
+use crate::api::middleware::ApiClient;
+use crate::api::on::{build_api_response, build_search_request, ApiState};
+use crate::api::types::{ApiErrorResponse, ApiSearchRequest};
+use crate::web::apply_preferences;
+
+/// 搜索订阅 feed 路径
+pub const FEED_SEARCH_PATH: &str = "/feeds/search";
+
+/// Feed 专用参数（其余参数同 `/api/search`）
+#[derive(Debug, Default, Deserialize)]
+pub struct FeedParams {
+    /// Feed 格式（`rss` 或 `atom`，默认 rss）
+    #[serde(default)]
+    pub format: FeedFormat,
+    /// 偏好令牌
+    pub token: Option<String>,
+}
+
+/// 错误响应
+fn error_response(status: StatusCode, code: &str, message: &str, details: Option<String>) -> Response {
+    let error = ApiErrorResponse {
+        code: code.to_string(),
+        message: message.to_string(),
+        details,
+    };
+    (status, Json(error)).into_response()
+}
+
+/// 根据请求头推断站点根地址（经反向代理时读取 `X-Forwarded-Proto`/`X-Forwarded-Host`）
+///
+/// 缺少 Host 时返回空字符串，链接保持为站内相对地址
+pub fn request_base_url(headers: &HeaderMap) -> String {
+    let header_value = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    let Some(host) = header_value("x-forwarded-host").or_else(|| header_value(header::HOST.as_str())) else {
+        return String::new();
+    };
+    let scheme = header_value("x-forwarded-proto").unwrap_or("http");
+    format!("{}://{}", scheme, host)
+}
+
+/// 构建 feed 自身与对应网页的链接（不包含偏好令牌）
+fn feed_links(base_url: &str, query: &str, params: &ApiSearchRequest, format: FeedFormat) -> (String, String) {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    serializer.append_pair("q", query);
+    if let Some(language) = params.language.as_deref() {
+        serializer.append_pair("language", language);
+    }
+    if let Some(engines) = params.engines.as_deref() {
+        serializer.append_pair("engines", engines);
+    }
+    let search = serializer.finish();
+
+    let self_link = match format {
+        FeedFormat::Rss => format!("{}{}?{}", base_url, FEED_SEARCH_PATH, search),
+        FeedFormat::Atom => format!("{}{}?{}&format=atom", base_url, FEED_SEARCH_PATH, search),
+    };
+    (format!("{}/?{}", base_url, search), self_link)
+}
+
+/// 处理搜索订阅 feed 请求
+pub async fn handle_search_feed(
+    State(state): State<ApiState>,
+    Extension(client): Extension<ApiClient>,
+    headers: HeaderMap,
+    Query(params): Query<ApiSearchRequest>,
+    Query(feed): Query<FeedParams>,
+) -> Response {
+    let params = match feed.token.as_deref() {
+        Some(token) => {
+            let Some(store) = state.preferences.as_ref() else {
+                return error_response(StatusCode::SERVICE_UNAVAILABLE, "PREFERENCES_DISABLED", "未启用用户偏好存储", None);
+            };
+            match store.get_async(token).await {
+                Ok(Some(preferences)) => apply_preferences(params, &preferences),
+                Ok(None) => return error_response(StatusCode::NOT_FOUND, "NOT_FOUND", "偏好令牌不存在", None),
+                Err(e) => {
+                    return error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "PREFERENCE_STORAGE_ERROR",
+                        "读写偏好存储失败",
+                        Some(e.to_string()),
+                    );
+                }
+            }
+        }
+        None => params,
+    };
+
+    let request = match build_search_request(&params, &client) {
+        Ok(request) => request,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, "INVALID_PARAMS", "参数错误", Some(e.to_string())),
+    };
+
+    // 阅读器会定期轮询，feed 请求不记录搜索历史
+    let start_time = std::time::Instant::now();
+    let response = match state.search.search(&request).await {
+        Ok(response) => response,
+        Err(e) => {
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "SEARCH_ERROR", "搜索失败", Some(e.to_string()));
+        }
+    };
+    let response = build_api_response(&state, &client, &params, response, start_time.elapsed().as_millis() as u64);
+
+    let base_url = request_base_url(&headers);
+    let (link, self_link) = feed_links(&base_url, &response.query, &params, feed.format);
+    let title = format!("{}: {}", state.web.title, response.query);
+    let info = FeedInfo {
+        title: &title,
+        link: &link,
+        self_link: &self_link,
+        base_url: &base_url,
+        updated: chrono::Utc::now(),
+    };
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, feed.format.content_type())],
+        render_feed(feed.format, &info, &response),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_params_and_links() {
+        let uri: axum::http::Uri = "/feeds/search?q=rust&format=atom&token=abc&engines=bing".parse().unwrap();
+        let Query(feed) = Query::<FeedParams>::try_from_uri(&uri).unwrap();
+        assert_eq!(feed.format, FeedFormat::Atom);
+        assert_eq!(feed.token.as_deref(), Some("abc"));
+        let Query(params) = Query::<ApiSearchRequest>::try_from_uri(&uri).unwrap();
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(request_base_url(&headers), "");
+        headers.insert(header::HOST, "search.example".parse().unwrap());
+        headers.insert("x-forwarded-proto", "https".parse().unwrap());
+        let base_url = request_base_url(&headers);
+        assert_eq!(base_url, "https://search.example");
+
+        let (link, self_link) = feed_links(&base_url, "rust", &params, feed.format);
+        assert_eq!(link, "https://search.example/?q=rust&engines=bing");
+        assert_eq!(self_link, "https://search.example/feeds/search?q=rust&engines=bing&format=atom");
+    }
+}
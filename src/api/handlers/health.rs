@@ -14,7 +14,242 @@
 
 //! 健康检查处理器
 //!
-//! 处理健康检查相关的 API 请求
+//! `/healthz` 与 `/readyz` 逐项检查服务依赖，返回编排平台可直接消费的结构化 JSON：
+//! 任一检查失败时返回 `503`，否则返回 `200`（警告不影响状态码）。
+//!
+//! - `cache`：缓存数据库已打开且可写
+//! - `engines`：至少一个引擎可用（未被禁用或临时禁用）
+//! - `config`：配置校验无错误
+//! - `doh`：DoH 服务器可达（仅 `/readyz`，未启用 DoH 时跳过）
+
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+use crate::api::on::ApiState;
+use crate::api::types::{ApiHealthCheck, ApiHealthReport, ApiHealthStatus};
+use crate::cache::CacheInterface;
+use crate::config::SeeSeaConfig;
+use crate::net::types::DohConfig;
+
+/// 存活检查端点路径
+pub const HEALTHZ_PATH: &str = "/healthz";
+
+/// 就绪检查端点路径
+pub const READYZ_PATH: &str = "/readyz";
+
+/// DoH 检查解析的主机名
+const DOH_PROBE_HOST: &str = "example.com";
+
+/// DoH 检查超时
+const DOH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 处理存活检查请求
+pub async fn handle_healthz(State(state): State<ApiState>) -> Response {
+    let checks = vec![
+        check_cache(state.cache.as_ref()).await,
+        check_engines(&state).await,
+        check_config(state.app_config.as_deref()),
+    ];
+    report_response(&state.version, checks)
+}
+
+/// 处理就绪检查请求
+///
+/// 在存活检查的基础上额外探测 DoH 服务器
+pub async fn handle_readyz(State(state): State<ApiState>) -> Response {
+    let checks = vec![
+        check_cache(state.cache.as_ref()).await,
+        check_engines(&state).await,
+        check_config(state.app_config.as_deref()),
+        check_doh(&state.search.http_client().config().doh).await,
+    ];
+    report_response(&state.version, checks)
+}
+
+/// 汇总检查结果并生成响应
+fn report_response(version: &str, checks: Vec<ApiHealthCheck>) -> Response {
+    let report = build_report(version, checks);
+    let status = if report.status == ApiHealthStatus::Fail {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (status, Json(report)).into_response()
+}
+
+/// 汇总检查结果：任一失败为 `fail`，否则有警告为 `warn`，其余为 `pass`
+pub fn build_report(version: &str, checks: Vec<ApiHealthCheck>) -> ApiHealthReport {
+    let status = if checks.iter().any(|c| c.status == ApiHealthStatus::Fail) {
+        ApiHealthStatus::Fail
+    } else if checks.iter().any(|c| c.status == ApiHealthStatus::Warn) {
+        ApiHealthStatus::Warn
+    } else {
+        ApiHealthStatus::Pass
+    };
+    ApiHealthReport {
+        status,
+        version: version.to_string(),
+        checks,
+    }
+}
+
+fn check_result(name: &str, start: Instant, status: ApiHealthStatus, message: Option<String>) -> ApiHealthCheck {
+    ApiHealthCheck {
+        name: name.to_string(),
+        status,
+        message,
+        elapsed_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+/// 检查缓存数据库可写
+pub async fn check_cache(cache: Option<&CacheInterface>) -> ApiHealthCheck {
+    let start = Instant::now();
+    let Some(cache) = cache else {
+        return check_result("cache", start, ApiHealthStatus::Skip, Some("缓存未配置".to_string()));
+    };
+    match cache.check_writable().await {
+        Ok(()) => check_result("cache", start, ApiHealthStatus::Pass, None),
+        Err(e) => check_result("cache", start, ApiHealthStatus::Fail, Some(e.to_string())),
+    }
+}
+
+/// 检查至少一个引擎可用
+async fn check_engines(state: &ApiState) -> ApiHealthCheck {
+    let start = Instant::now();
+    let engines = state.search.list_engines();
+    let unavailable: Vec<String> = state
+        .search
+        .get_engine_states()
+        .await
+        .into_iter()
+        .filter(|(_, (enabled, temporarily_disabled, _))| !enabled || *temporarily_disabled)
+        .map(|(name, _)| name)
+        .collect();
+    let available = engines
+        .iter()
+        .filter(|name| !unavailable.contains(name) && state.search.inactive_reason(name).is_none())
+        .count();
+    engines_result(start, available, engines.len())
+}
+
+fn engines_result(start: Instant, available: usize, total: usize) -> ApiHealthCheck {
+    let message = Some(format!("{}/{} 个引擎可用", available, total));
+    let status = if available == 0 {
+        ApiHealthStatus::Fail
+    } else if available < total {
+        ApiHealthStatus::Warn
+    } else {
+        ApiHealthStatus::Pass
+    };
+    check_result("engines", start, status, message)
+}
+
+/// 检查配置有效性：有错误为 `fail`，仅有警告为 `warn`
+pub fn check_config(config: Option<&SeeSeaConfig>) -> ApiHealthCheck {
+    let start = Instant::now();
+    let Some(config) = config else {
+        return check_result("config", start, ApiHealthStatus::Skip, Some("未加载应用配置".to_string()));
+    };
+    let validation = config.validate();
+    if !validation.errors.is_empty() {
+        check_result("config", start, ApiHealthStatus::Fail, Some(validation.errors.join("; ")))
+    } else if !validation.warnings.is_empty() {
+        check_result("config", start, ApiHealthStatus::Warn, Some(validation.warnings.join("; ")))
+    } else {
+        check_result("config", start, ApiHealthStatus::Pass, None)
+    }
+}
+
+/// 检查 DoH 服务器可达
+///
+/// 未启用 DoH 时跳过；解析失败时若允许回退系统 DNS 仅为 `warn`，否则为 `fail`
+pub async fn check_doh(config: &DohConfig) -> ApiHealthCheck {
+    let start = Instant::now();
+    if !config.enabled {
+        return check_result("doh", start, ApiHealthStatus::Skip, Some("DoH 未启用".to_string()));
+    }
+    let error = match tokio::time::timeout(
+        DOH_TIMEOUT,
+        crate::net::resolver::doh::resolve_via_doh(DOH_PROBE_HOST, config),
+    )
+    .await
+    {
+        Ok(Ok(_)) => return check_result("doh", start, ApiHealthStatus::Pass, None),
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => format!("DoH 解析超时（{} 秒）", DOH_TIMEOUT.as_secs()),
+    };
+    let status = if config.fallback_to_system {
+        ApiHealthStatus::Warn
+    } else {
+        ApiHealthStatus::Fail
+    };
+    check_result("doh", start, status, Some(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheImplConfig;
+
+    fn check(name: &str, status: ApiHealthStatus) -> ApiHealthCheck {
+        check_result(name, Instant::now(), status, None)
+    }
+
+    #[test]
+    fn test_build_report_status() {
+        let report = build_report("1.0", vec![check("a", ApiHealthStatus::Pass), check("b", ApiHealthStatus::Skip)]);
+        assert_eq!(report.status, ApiHealthStatus::Pass);
+
+        let report = build_report("1.0", vec![check("a", ApiHealthStatus::Warn), check("b", ApiHealthStatus::Pass)]);
+        assert_eq!(report.status, ApiHealthStatus::Warn);
+
+        let report = build_report("1.0", vec![check("a", ApiHealthStatus::Warn), check("b", ApiHealthStatus::Fail)]);
+        assert_eq!(report.status, ApiHealthStatus::Fail);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["status"], "fail");
+        assert_eq!(json["checks"][0]["name"], "a");
+    }
+
+    #[test]
+    fn test_engines_result() {
+        assert_eq!(engines_result(Instant::now(), 0, 3).status, ApiHealthStatus::Fail);
+        assert_eq!(engines_result(Instant::now(), 2, 3).status, ApiHealthStatus::Warn);
+        assert_eq!(engines_result(Instant::now(), 3, 3).status, ApiHealthStatus::Pass);
+    }
+
+    #[tokio::test]
+    async fn test_check_cache() {
+        assert_eq!(check_cache(None).await.status, ApiHealthStatus::Skip);
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = CacheImplConfig {
+            db_path: dir.path().join("cache.db").to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let cache = CacheInterface::new(config).unwrap();
+        assert_eq!(check_cache(Some(&cache)).await.status, ApiHealthStatus::Pass);
+    }
+
+    #[tokio::test]
+    async fn test_check_doh_disabled() {
+        let config = DohConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(check_doh(&config).await.status, ApiHealthStatus::Skip);
 
-// Placeholder for future health check handlers
-// Currently handled in on.rs
+        let config = DohConfig {
+            enabled: true,
+            servers: Vec::new(),
+            fallback_to_system: true,
+        };
+        assert_eq!(check_doh(&config).await.status, ApiHealthStatus::Warn);
+    }
+}
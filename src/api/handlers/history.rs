@@ -0,0 +1,185 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 搜索历史 API 处理器
+//!
+//! 列出、搜索、记录与清除加密保存的搜索历史（需在配置中启用 `privacy.history`）
+
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::json;
+use crate::api::on::ApiState;
+use crate::api::types::ApiErrorResponse;
+use crate::cache::{HistoryEntry, HistoryKind};
+
+/// 默认返回的记录数
+const DEFAULT_LIMIT: usize = 50;
+
+/// 单次最多返回的记录数
+const MAX_LIMIT: usize = 1000;
+
+/// 历史列表查询参数
+#[derive(Debug, Deserialize)]
+pub struct HistoryListQuery {
+    /// 只返回查询、标题或链接包含该文本的记录
+    #[serde(alias = "query")]
+    pub q: Option<String>,
+    /// 最多返回的记录数
+    pub limit: Option<usize>,
+}
+
+/// 清除历史查询参数
+#[derive(Debug, Deserialize)]
+pub struct HistoryPurgeQuery {
+    /// 只清除早于该时间（Unix 时间戳）的记录
+    pub before: Option<u64>,
+}
+
+/// 记录历史请求
+#[derive(Debug, Deserialize)]
+pub struct HistoryRecordRequest {
+    /// 记录类型
+    pub kind: HistoryKind,
+    /// 查询文本
+    #[serde(default)]
+    pub query: String,
+    /// 点击的结果链接（点击记录必填）
+    pub url: Option<String>,
+    /// 点击的结果标题
+    pub title: Option<String>,
+    /// 使用的引擎
+    #[serde(default)]
+    pub engines: Vec<String>,
+}
+
+impl HistoryRecordRequest {
+    /// 转换为历史记录
+    pub fn into_entry(self) -> Result<HistoryEntry, String> {
+        match self.kind {
+            HistoryKind::Query if self.query.trim().is_empty() => Err("查询不能为空".to_string()),
+            HistoryKind::Query => Ok(HistoryEntry::query(&self.query, self.engines)),
+            HistoryKind::Click => {
+                let url = self.url.filter(|u| !u.trim().is_empty()).ok_or("点击记录需要结果链接")?;
+                Ok(HistoryEntry::click(&self.query, &url, self.title.as_deref()))
+            }
+        }
+    }
+}
+
+/// 错误响应
+fn error_response(status: StatusCode, code: &str, message: &str, details: Option<String>) -> Response {
+    let error = ApiErrorResponse {
+        code: code.to_string(),
+        message: message.to_string(),
+        details,
+    };
+    (status, Json(error)).into_response()
+}
+
+/// 未启用搜索历史时的响应
+fn history_disabled() -> Response {
+    error_response(StatusCode::SERVICE_UNAVAILABLE, "HISTORY_DISABLED", "未启用搜索历史", None)
+}
+
+/// 存储操作失败的响应
+fn storage_error(e: impl std::fmt::Display) -> Response {
+    error_response(StatusCode::INTERNAL_SERVER_ERROR, "HISTORY_STORAGE_ERROR", "读写搜索历史失败", Some(e.to_string()))
+}
+
+/// 处理列出（搜索）历史请求
+pub async fn handle_history_list(
+    State(state): State<ApiState>,
+    Query(query): Query<HistoryListQuery>,
+) -> Response {
+    let Some(store) = state.history.as_ref() else {
+        return history_disabled();
+    };
+    let filter = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    match store.list_async(filter, limit).await {
+        Ok(entries) => (StatusCode::OK, Json(entries)).into_response(),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理记录历史请求
+pub async fn handle_history_record(
+    State(state): State<ApiState>,
+    Json(request): Json<HistoryRecordRequest>,
+) -> Response {
+    let Some(store) = state.history.as_ref() else {
+        return history_disabled();
+    };
+    let entry = match request.into_entry() {
+        Ok(entry) => entry,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, "INVALID_HISTORY_ENTRY", "历史记录无效", Some(e)),
+    };
+    match store.record_async(entry.clone()).await {
+        Ok(()) => (StatusCode::CREATED, Json(entry)).into_response(),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理清除历史请求
+pub async fn handle_history_purge(
+    State(state): State<ApiState>,
+    Query(query): Query<HistoryPurgeQuery>,
+) -> Response {
+    let Some(store) = state.history.as_ref() else {
+        return history_disabled();
+    };
+    match store.purge_async(query.before).await {
+        Ok(purged) => (StatusCode::OK, Json(json!({ "purged": purged }))).into_response(),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理删除单条历史请求
+pub async fn handle_history_delete(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(store) = state.history.as_ref() else {
+        return history_disabled();
+    };
+    match store.delete_async(&id).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => error_response(StatusCode::NOT_FOUND, "NOT_FOUND", "历史记录不存在", None),
+        Err(e) => storage_error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_request_into_entry() {
+        let request: HistoryRecordRequest =
+            serde_json::from_str(r#"{"kind": "click", "query": "rust", "url": "https://rust-lang.org"}"#).unwrap();
+        let entry = request.into_entry().unwrap();
+        assert_eq!(entry.kind, HistoryKind::Click);
+        assert_eq!(entry.url.as_deref(), Some("https://rust-lang.org"));
+
+        let request: HistoryRecordRequest = serde_json::from_str(r#"{"kind": "click", "query": "rust"}"#).unwrap();
+        assert!(request.into_entry().is_err());
+
+        let request: HistoryRecordRequest = serde_json::from_str(r#"{"kind": "query", "query": "  "}"#).unwrap();
+        assert!(request.into_entry().is_err());
+    }
+}
@@ -0,0 +1,493 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 缩略图代理 API 处理器
+//!
+//! 服务器代为获取第三方缩略图，用户浏览器只与 SeeSea 通信；
+//! 启用预取后搜索完成时在后台预先下载靠前结果的缩略图。
+//! 代理只接受服务器签名的链接，且只连接公网地址（每一跳重定向都会检查），
+//! 防止端点被当作开放代理或用来探测内网
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use futures::StreamExt;
+use serde::Deserialize;
+
+use crate::api::middleware::ApiClient;
+use crate::api::on::ApiState;
+use crate::api::signing::{LinkSigner, SIGNATURE_PARAM};
+use crate::api::types::ApiErrorResponse;
+use crate::cache::image::{CachedImage, ImageCache};
+use crate::cache::manager::run_blocking;
+use crate::config::privacy::{ImagePrefetchConfig, ImageProxyConfig};
+use crate::derive::SearchResult;
+use crate::net::client::public::{get_public, PublicFetchError};
+
+/// 缩略图代理端点路径
+pub const IMAGE_PROXY_PATH: &str = "/proxy/image";
+
+/// 允许代理的图片类型
+///
+/// 只允许位图格式；SVG 可以携带脚本，与 Web 界面同源提供会造成 XSS
+const ALLOWED_IMAGE_TYPES: &[&str] = &["image/png", "image/jpeg", "image/gif", "image/webp", "image/avif"];
+
+/// 代理响应的内容安全策略（禁止图片作为文档执行任何内容）
+const IMAGE_CSP: &str = "default-src 'none'; sandbox";
+
+/// 缩略图代理请求参数
+#[derive(Debug, Deserialize)]
+pub struct ImageProxyParams {
+    /// 原始图片 URL
+    pub url: String,
+    /// 原始图片 URL 的签名
+    pub s: Option<String>,
+}
+
+/// 将缩略图链接改写为代理链接
+///
+/// 未启用代理或链接不是 http/https 时原样返回
+///
+/// # Arguments
+///
+/// * `target` - 原始图片链接
+/// * `config` - 缩略图代理配置
+/// * `signer` - 链接签名器（签名附加在 `s` 参数中）
+pub fn proxy_image_url(target: &str, config: &ImageProxyConfig, signer: &LinkSigner) -> String {
+    if !config.enabled || !is_proxyable(target) {
+        return target.to_string();
+    }
+
+    let base = config
+        .base_url
+        .as_deref()
+        .map(|b| b.trim_end_matches('/'))
+        .unwrap_or("");
+    format!(
+        "{}{}?url={}&{}={}",
+        base,
+        IMAGE_PROXY_PATH,
+        urlencoding::encode(target),
+        SIGNATURE_PARAM,
+        signer.sign(target)
+    )
+}
+
+/// 是否为可代理的链接
+fn is_proxyable(target: &str) -> bool {
+    match url::Url::parse(target) {
+        Ok(parsed) => parsed.scheme() == "http" || parsed.scheme() == "https",
+        Err(_) => false,
+    }
+}
+
+/// 是否为允许代理的位图类型（忽略参数与大小写）
+fn is_allowed_image_type(content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or("").trim();
+    ALLOWED_IMAGE_TYPES
+        .iter()
+        .any(|allowed| essence.eq_ignore_ascii_case(allowed))
+}
+
+/// 构建拒绝访问非公网地址的响应
+fn forbidden_target(host: &str) -> Response {
+    error_response(
+        StatusCode::FORBIDDEN,
+        "FORBIDDEN_TARGET",
+        "不允许代理访问该地址",
+        Some(format!("{} 不是公网地址", host)),
+    )
+}
+
+/// 构建错误响应
+fn error_response(status: StatusCode, code: &str, message: &str, details: Option<String>) -> Response {
+    let error = ApiErrorResponse {
+        code: code.to_string(),
+        message: message.to_string(),
+        details,
+    };
+    (status, Json(error)).into_response()
+}
+
+/// 构建图片响应
+fn image_response(image: CachedImage, ttl_secs: u64) -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, image.content_type),
+            (header::CACHE_CONTROL, format!("public, max-age={}", ttl_secs)),
+            (header::REFERRER_POLICY, "no-referrer".to_string()),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            (header::CONTENT_SECURITY_POLICY, IMAGE_CSP.to_string()),
+        ],
+        image.data,
+    )
+        .into_response()
+}
+
+/// 请求远程图片
+///
+/// 只连接公网地址，每一跳重定向都会检查，重定向到内网地址与直接请求内网地址一样被拒绝
+async fn send_checked(state: &ApiState, target: &str) -> Result<reqwest::Response, Response> {
+    let timeout = Duration::from_secs(state.image_proxy.timeout_secs);
+    let privacy = state.search.http_client().privacy_manager().map(|p| p.as_ref());
+    get_public(target, timeout, privacy).await.map_err(|e| match e {
+        PublicFetchError::Blocked(host) => forbidden_target(&host),
+        PublicFetchError::Request(details) => {
+            error_response(StatusCode::BAD_GATEWAY, "UPSTREAM_ERROR", "获取图片失败", Some(details))
+        }
+    })
+}
+
+/// 获取远程图片
+///
+/// 目标不是公网地址、超过大小限制或不是图片时返回对应的错误响应
+async fn fetch_image(state: &ApiState, target: &str) -> Result<CachedImage, Response> {
+    let config = &state.image_proxy;
+    let mut response = send_checked(state, target).await?;
+
+    if !response.status().is_success() {
+        return Err(error_response(
+            StatusCode::BAD_GATEWAY,
+            "UPSTREAM_ERROR",
+            "获取图片失败",
+            Some(format!("上游返回状态码 {}", response.status())),
+        ));
+    }
+
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    if !is_allowed_image_type(&content_type) {
+        return Err(error_response(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "NOT_AN_IMAGE",
+            "目标不是图片",
+            Some(content_type),
+        ));
+    }
+
+    let too_large = || {
+        error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "IMAGE_TOO_LARGE",
+            "图片超过大小限制",
+            Some(format!("最大 {} 字节", config.max_size_bytes)),
+        )
+    };
+
+    if let Some(length) = response.content_length()
+        && length as usize > config.max_size_bytes
+    {
+        return Err(too_large());
+    }
+
+    // 分块读取，防止未声明长度的响应超出限制
+    let mut data = Vec::new();
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) => {
+                if data.len() + chunk.len() > config.max_size_bytes {
+                    return Err(too_large());
+                }
+                data.extend_from_slice(&chunk);
+            }
+            Ok(None) => break,
+            Err(e) => {
+                return Err(error_response(
+                    StatusCode::BAD_GATEWAY,
+                    "UPSTREAM_ERROR",
+                    "读取图片失败",
+                    Some(e.to_string()),
+                ));
+            }
+        }
+    }
+
+    Ok(CachedImage { content_type, data })
+}
+
+/// 处理缩略图代理请求
+pub async fn handle_image_proxy(
+    State(state): State<ApiState>,
+    Query(params): Query<ImageProxyParams>,
+) -> Response {
+    let config = &state.image_proxy;
+    if !config.enabled && !state.profiles.image_proxy_enabled() {
+        return error_response(StatusCode::NOT_FOUND, "IMAGE_PROXY_DISABLED", "缩略图代理未启用", None);
+    }
+
+    if !is_proxyable(&params.url) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "INVALID_URL",
+            "图片地址无效",
+            Some("仅支持 http/https 链接".to_string()),
+        );
+    }
+
+    if !params.s.as_deref().is_some_and(|s| state.link_signer.verify(&params.url, s)) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "INVALID_SIGNATURE",
+            "图片地址签名无效",
+            Some("只接受搜索结果中由服务器签名的链接".to_string()),
+        );
+    }
+
+    // 先查缓存
+    if let Some(cache) = state.image_cache.clone() {
+        let url = params.url.clone();
+        match run_blocking(move || cache.get(&url)).await {
+            // 早先缓存的非位图图片不再提供，重新获取时会被拒绝
+            Ok(Some(image)) if is_allowed_image_type(&image.content_type) => {
+                return image_response(image, config.cache_ttl_secs);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to read image cache: {}", e),
+        }
+    }
+
+    match fetch_image(&state, &params.url).await {
+        Ok(image) => {
+            if let Some(cache) = state.image_cache.clone() {
+                let (url, cached) = (params.url.clone(), image.clone());
+                let ttl = Duration::from_secs(config.cache_ttl_secs);
+                if let Err(e) = run_blocking(move || cache.set(&url, &cached, Some(ttl))).await {
+                    tracing::warn!("Failed to write image cache: {}", e);
+                }
+            }
+            image_response(image, config.cache_ttl_secs)
+        }
+        Err(response) => response,
+    }
+}
+
+/// 在后台预取搜索结果的缩略图
+///
+/// 调用方的缩略图代理与预取均启用且设置了图片缓存时，按评分取前 N 条指定类型结果的
+/// 缩略图下载并写入缓存，之后按磁盘上限淘汰旧图片；已缓存的图片不会重复下载
+pub fn spawn_prefetch(state: &ApiState, client: &ApiClient, results: &[SearchResult]) {
+    let prefetch = &state.image_proxy.prefetch;
+    if !prefetch.enabled || !client.image_proxy(&state.image_proxy).enabled {
+        return;
+    }
+    let Some(cache) = state.image_cache.clone() else {
+        return;
+    };
+
+    let targets = prefetch_targets(results, prefetch);
+    if targets.is_empty() {
+        return;
+    }
+
+    let state = state.clone();
+    tokio::spawn(async move { prefetch_images(state, cache, targets).await });
+}
+
+/// 选出需要预取的缩略图链接（按结果评分从高到低，去重后取前 N 条）
+fn prefetch_targets(results: &[SearchResult], config: &ImagePrefetchConfig) -> Vec<String> {
+    let mut items: Vec<_> = results
+        .iter()
+        .flat_map(|result| &result.items)
+        .filter(|item| config.result_types.contains(&item.result_type))
+        .collect();
+    items.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let mut seen = HashSet::new();
+    items
+        .into_iter()
+        .filter_map(|item| item.thumbnail.as_deref())
+        .filter(|target| is_proxyable(target) && seen.insert(*target))
+        .take(config.top_n)
+        .map(str::to_string)
+        .collect()
+}
+
+/// 下载缩略图并写入缓存，完成后按磁盘上限淘汰
+async fn prefetch_images(state: ApiState, cache: ImageCache, targets: Vec<String>) {
+    let config = &state.image_proxy;
+    let ttl = Duration::from_secs(config.cache_ttl_secs);
+
+    let stored = futures::stream::iter(targets)
+        .map(|target| {
+            let state = &state;
+            let cache = &cache;
+            async move {
+                let (lookup, key) = (cache.clone(), target.clone());
+                if run_blocking(move || lookup.contains(&key)).await.unwrap_or(false) {
+                    return false;
+                }
+                match fetch_image(state, &target).await {
+                    Ok(image) => match run_blocking({
+                        let (cache, key) = (cache.clone(), target.clone());
+                        move || cache.set(&key, &image, Some(ttl))
+                    })
+                    .await
+                    {
+                        Ok(()) => true,
+                        Err(e) => {
+                            tracing::warn!("Failed to write image cache: {}", e);
+                            false
+                        }
+                    },
+                    Err(response) => {
+                        tracing::debug!("预取缩略图 {} 失败: {}", target, response.status());
+                        false
+                    }
+                }
+            }
+        })
+        .buffer_unordered(config.prefetch.concurrency.max(1))
+        .filter(|stored| std::future::ready(*stored))
+        .count()
+        .await;
+
+    if stored == 0 {
+        return;
+    }
+    tracing::debug!("已预取 {} 张缩略图", stored);
+
+    let quota = config.prefetch.disk_quota_bytes;
+    match run_blocking(move || cache.enforce_quota(quota)).await {
+        Ok(0) => {}
+        Ok(evicted) => tracing::debug!("图片缓存超出磁盘上限，已淘汰 {} 张图片", evicted),
+        Err(e) => tracing::warn!("图片缓存配额清理失败: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derive::types::{ResultType, SearchResultItem};
+    use std::collections::HashMap;
+
+    fn item(thumbnail: &str, result_type: ResultType, score: f64) -> SearchResultItem {
+        SearchResultItem {
+            title: "t".to_string(),
+            url: "https://example.com".to_string(),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score,
+            result_type,
+            thumbnail: Some(thumbnail.to_string()),
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_prefetch_targets() {
+        let result = SearchResult {
+            engine_name: "test".to_string(),
+            total_results: None,
+            elapsed_ms: 0,
+            items: vec![
+                item("https://i.example.com/low.jpg", ResultType::Image, 0.2),
+                item("https://i.example.com/web.jpg", ResultType::Web, 0.9),
+                item("https://i.example.com/high.jpg", ResultType::News, 0.8),
+                item("https://i.example.com/high.jpg", ResultType::Image, 0.7),
+                item("data:image/png;base64,AAAA", ResultType::Image, 0.6),
+                item("https://i.example.com/mid.jpg", ResultType::Image, 0.5),
+            ],
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        let config = ImagePrefetchConfig {
+            top_n: 2,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            prefetch_targets(&[result], &config),
+            vec!["https://i.example.com/high.jpg", "https://i.example.com/mid.jpg"]
+        );
+    }
+
+    #[test]
+    fn test_proxy_image_url_disabled() {
+        let config = ImageProxyConfig::default();
+        assert_eq!(
+            proxy_image_url("https://i.example.com/a.jpg", &config, &LinkSigner::random()),
+            "https://i.example.com/a.jpg"
+        );
+    }
+
+    #[test]
+    fn test_proxy_image_url_enabled() {
+        let config = ImageProxyConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let signer = LinkSigner::new(b"test");
+        assert_eq!(
+            proxy_image_url("https://i.example.com/a.jpg", &config, &signer),
+            format!(
+                "/proxy/image?url=https%3A%2F%2Fi.example.com%2Fa.jpg&s={}",
+                signer.sign("https://i.example.com/a.jpg")
+            )
+        );
+        // data URI 等非 http 链接保持不变
+        assert_eq!(
+            proxy_image_url("data:image/png;base64,AAAA", &config, &signer),
+            "data:image/png;base64,AAAA"
+        );
+    }
+
+    #[test]
+    fn test_proxy_image_url_with_base() {
+        let config = ImageProxyConfig {
+            enabled: true,
+            base_url: Some("https://search.example.com/".to_string()),
+            ..Default::default()
+        };
+        assert!(proxy_image_url("http://i.example.com/a.jpg", &config, &LinkSigner::random())
+            .starts_with("https://search.example.com/proxy/image?url="));
+    }
+
+    #[test]
+    fn test_is_allowed_image_type() {
+        assert!(is_allowed_image_type("image/png"));
+        assert!(is_allowed_image_type("image/JPEG; charset=binary"));
+        assert!(is_allowed_image_type("image/avif"));
+        assert!(!is_allowed_image_type("image/svg+xml"));
+        assert!(!is_allowed_image_type("image/svg+xml; charset=utf-8"));
+        assert!(!is_allowed_image_type("text/html"));
+        assert!(!is_allowed_image_type(""));
+    }
+
+    #[test]
+    fn test_image_response_headers() {
+        let image = CachedImage {
+            content_type: "image/png".to_string(),
+            data: vec![1, 2, 3],
+        };
+        let response = image_response(image, 60);
+        let headers = response.headers();
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(headers[header::CONTENT_SECURITY_POLICY], IMAGE_CSP);
+        assert_eq!(headers[header::CONTENT_TYPE], "image/png");
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 地图搜索 API 处理器
+//!
+//! `/api/search/map` 以 GeoJSON FeatureCollection 返回带坐标的结果
+
+use axum::{
+    extract::{Extension, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::api::middleware::ApiClient;
+use crate::api::on::ApiState;
+use crate::api::types::ApiErrorResponse;
+use crate::derive::{
+    BoundingBox, SearchQuery, SearchResultItem, LATITUDE_METADATA_KEY, LONGITUDE_METADATA_KEY,
+};
+use crate::search::SearchRequest;
+
+/// 地图搜索端点路径
+pub const MAP_SEARCH_PATH: &str = "/api/search/map";
+
+/// 默认使用的地图引擎
+const DEFAULT_MAP_ENGINES: &[&str] = &["nominatim"];
+
+/// 地图搜索请求参数
+#[derive(Debug, Deserialize)]
+pub struct MapSearchParams {
+    /// 查询关键词
+    #[serde(alias = "query")]
+    pub q: String,
+    /// 中心点纬度
+    pub lat: Option<f64>,
+    /// 中心点经度
+    pub lon: Option<f64>,
+    /// 限定范围：`min_lon,min_lat,max_lon,max_lat`
+    pub bbox: Option<String>,
+    /// 语言
+    pub language: Option<String>,
+    /// 指定引擎（逗号分隔，默认 nominatim）
+    pub engines: Option<String>,
+}
+
+impl MapSearchParams {
+    /// 转换为内部搜索查询
+    pub fn to_search_query(&self) -> Result<SearchQuery, String> {
+        if self.q.trim().is_empty() {
+            return Err("查询参数 'q' 是必需的".to_string());
+        }
+        if self.lat.is_some() != self.lon.is_some() {
+            return Err("lat 与 lon 需要同时提供".to_string());
+        }
+        if let Some(lat) = self.lat
+            && !(-90.0..=90.0).contains(&lat)
+        {
+            return Err(format!("纬度越界: {}", lat));
+        }
+        if let Some(lon) = self.lon
+            && !(-180.0..=180.0).contains(&lon)
+        {
+            return Err(format!("经度越界: {}", lon));
+        }
+
+        let bbox = self.bbox.as_deref().map(BoundingBox::parse).transpose()?;
+        Ok(SearchQuery {
+            query: self.q.clone(),
+            language: self.language.clone(),
+            lat: self.lat,
+            lon: self.lon,
+            bbox,
+            ..Default::default()
+        })
+    }
+
+    /// 获取引擎列表
+    pub fn engines(&self) -> Vec<String> {
+        match self.engines {
+            Some(ref engines) => engines
+                .split(',')
+                .map(|e| e.trim().to_string())
+                .filter(|e| !e.is_empty())
+                .collect(),
+            None => DEFAULT_MAP_ENGINES.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+}
+
+/// 读取结果坐标
+fn item_coordinates(item: &SearchResultItem) -> Option<(f64, f64)> {
+    let lat = item.metadata.get(LATITUDE_METADATA_KEY)?.parse().ok()?;
+    let lon = item.metadata.get(LONGITUDE_METADATA_KEY)?.parse().ok()?;
+    Some((lat, lon))
+}
+
+/// 将结果转换为 GeoJSON Feature，无坐标的结果返回 None
+pub fn item_to_feature(item: &SearchResultItem) -> Option<Value> {
+    let (lat, lon) = item_coordinates(item)?;
+
+    let mut properties = Map::new();
+    properties.insert("title".to_string(), json!(item.title));
+    properties.insert("url".to_string(), json!(item.url));
+    properties.insert("description".to_string(), json!(item.content));
+    for (key, value) in &item.metadata {
+        if key != LATITUDE_METADATA_KEY && key != LONGITUDE_METADATA_KEY {
+            properties.insert(key.clone(), json!(value));
+        }
+    }
+
+    // GeoJSON 坐标顺序为 [经度, 纬度]
+    Some(json!({
+        "type": "Feature",
+        "geometry": { "type": "Point", "coordinates": [lon, lat] },
+        "properties": properties,
+    }))
+}
+
+/// 构建 GeoJSON FeatureCollection
+///
+/// 指定 `bbox` 时过滤掉范围外的结果
+pub fn to_feature_collection<'a, I>(items: I, bbox: Option<&BoundingBox>) -> Value
+where
+    I: IntoIterator<Item = &'a SearchResultItem>,
+{
+    let features: Vec<Value> = items
+        .into_iter()
+        .filter(|item| match (bbox, item_coordinates(item)) {
+            (Some(bbox), Some((lat, lon))) => bbox.contains(lat, lon),
+            _ => true,
+        })
+        .filter_map(item_to_feature)
+        .collect();
+
+    let mut collection = json!({
+        "type": "FeatureCollection",
+        "features": features,
+    });
+    if let Some(bbox) = bbox {
+        collection["bbox"] = json!([bbox.min_lon, bbox.min_lat, bbox.max_lon, bbox.max_lat]);
+    }
+    collection
+}
+
+/// 处理地图搜索请求
+pub async fn handle_map_search(
+    State(state): State<ApiState>,
+    Extension(client): Extension<ApiClient>,
+    Query(params): Query<MapSearchParams>,
+) -> Response {
+    let query = params
+        .to_search_query()
+        .and_then(|mut query| {
+            client.apply_safe_search(&mut query);
+            let engines = client.restrict_engines(params.engines(), params.engines.is_some())?;
+            Ok((query, engines))
+        });
+    let (query, engines) = match query {
+        Ok(query) => query,
+        Err(e) => {
+            let error = ApiErrorResponse {
+                code: "INVALID_PARAMS".to_string(),
+                message: "参数错误".to_string(),
+                details: Some(e),
+            };
+            return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+        }
+    };
+
+    let bbox = query.bbox;
+    let request = SearchRequest {
+        query,
+        engines,
+        timeout: None,
+        max_results: None,
+        force: false,
+        cache_timeline: Some(3600),
+        translate_to: None,
+        highlight: false,
+        debug_capture: false,
+        max_latency_ms: None,
+        explain: false,
+        session: None,
+    };
+
+    match state.search.search(&request).await {
+        Ok(response) => {
+            let items = response.results.iter().flat_map(|r| r.items.iter());
+            let collection = to_feature_collection(items, bbox.as_ref());
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/geo+json")],
+                collection.to_string(),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            let error = ApiErrorResponse {
+                code: "SEARCH_ERROR".to_string(),
+                message: "搜索失败".to_string(),
+                details: Some(e.to_string()),
+            };
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derive::ResultType;
+    use std::collections::HashMap;
+
+    fn place(title: &str, lat: f64, lon: f64) -> SearchResultItem {
+        let mut metadata = HashMap::new();
+        metadata.insert(LATITUDE_METADATA_KEY.to_string(), lat.to_string());
+        metadata.insert(LONGITUDE_METADATA_KEY.to_string(), lon.to_string());
+        metadata.insert("place_type".to_string(), "cafe".to_string());
+        SearchResultItem {
+            title: title.to_string(),
+            url: format!("https://www.openstreetmap.org/node/{}", title.len()),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score: 1.0,
+            result_type: ResultType::Map,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata,
+        }
+    }
+
+    #[test]
+    fn test_params_validation() {
+        let params = MapSearchParams {
+            q: "cafe".to_string(),
+            lat: Some(48.85),
+            lon: None,
+            bbox: None,
+            language: None,
+            engines: None,
+        };
+        assert!(params.to_search_query().is_err());
+
+        let params = MapSearchParams {
+            lon: Some(2.35),
+            bbox: Some("2.2,48.8,2.4,48.9".to_string()),
+            ..params
+        };
+        let query = params.to_search_query().unwrap();
+        assert_eq!(query.bbox.unwrap().max_lat, 48.9);
+        assert_eq!(params.engines(), vec!["nominatim"]);
+    }
+
+    #[test]
+    fn test_feature_collection() {
+        let inside = place("inside", 48.85, 2.35);
+        let outside = place("outside", 40.0, 2.35);
+        let mut no_coords = place("none", 0.0, 0.0);
+        no_coords.metadata.clear();
+
+        let bbox = BoundingBox::parse("2.2,48.8,2.4,48.9").unwrap();
+        let collection = to_feature_collection([&inside, &outside, &no_coords], Some(&bbox));
+
+        assert_eq!(collection["type"], "FeatureCollection");
+        let features = collection["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0]["geometry"]["coordinates"], json!([2.35, 48.85]));
+        assert_eq!(features[0]["properties"]["place_type"], "cafe");
+        assert!(features[0]["properties"].get(LATITUDE_METADATA_KEY).is_none());
+        assert_eq!(collection["bbox"], json!([2.2, 48.8, 2.4, 48.9]));
+    }
+}
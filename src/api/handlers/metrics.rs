@@ -14,7 +14,226 @@
 
 //! 指标处理器
 //!
-//! 处理指标和统计相关的 API 请求
+//! 以 Prometheus 文本格式（0.0.4）导出引擎与缓存指标，供 Prometheus 抓取后在 Grafana 中展示。
+//! 端点路径取自 `api.metrics.path`，仅在 `api.metrics.enabled = true` 时注册。
+//!
+//! - `seesea_engine_*`：按 `engine` 标签区分的请求数、失败数、结果数与最近延迟分位数
+//! - `seesea_cache_*`：命中、未命中、写入、删除、过期清理计数，条目数与磁盘占用，
+//!   以及写入值大小、读取与写入延迟的直方图
+
+use std::fmt::Write;
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::api::on::ApiState;
+use crate::cache::{CacheStats, Histogram};
+use crate::search::{EngineReport, EngineReportEntry};
+
+/// 指标名前缀
+pub const METRIC_PREFIX: &str = "seesea_";
+
+/// Prometheus 文本格式的 Content-Type
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// 处理指标抓取请求
+pub async fn handle_metrics(State(state): State<ApiState>) -> Response {
+    let mut writer = PrometheusWriter::new();
+    write_engine_metrics(&mut writer, &state.search.engine_report().await);
+    if let Some(ref cache) = state.cache {
+        write_cache_metrics(&mut writer, &cache.manager().stats());
+    }
+    ([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], writer.finish()).into_response()
+}
+
+/// 写入引擎指标
+pub fn write_engine_metrics(writer: &mut PrometheusWriter, report: &EngineReport) {
+    type Field = fn(&EngineReportEntry) -> Option<f64>;
+    let families: [(&str, &str, &str, Field); 6] = [
+        ("engine_requests_total", "counter", "引擎请求总数", |e| Some(e.stats.total_requests as f64)),
+        ("engine_failures_total", "counter", "引擎失败（错误或超时）总数", |e| Some(e.stats.failed_requests as f64)),
+        ("engine_zero_results_total", "counter", "引擎零结果请求总数", |e| Some(e.stats.zero_result_requests as f64)),
+        ("engine_results_total", "counter", "引擎返回的结果总数", |e| Some(e.stats.total_results as f64)),
+        ("engine_latency_p50_seconds", "gauge", "引擎最近成功请求延迟的中位数（秒）", |e| {
+            e.p50_latency_ms.map(|ms| ms as f64 / 1000.0)
+        }),
+        ("engine_latency_p95_seconds", "gauge", "引擎最近成功请求延迟的 95 分位数（秒）", |e| {
+            e.p95_latency_ms.map(|ms| ms as f64 / 1000.0)
+        }),
+    ];
+    for (name, kind, help, field) in families {
+        writer.family(name, kind, help);
+        for entry in &report.engines {
+            if let Some(value) = field(entry) {
+                writer.sample(name, &[("engine", &entry.stats.engine)], value);
+            }
+        }
+    }
+}
+
+/// 写入缓存指标
+pub fn write_cache_metrics(writer: &mut PrometheusWriter, stats: &CacheStats) {
+    writer.metric("cache_hits_total", "counter", "缓存命中总数", stats.hits as f64);
+    writer.metric("cache_misses_total", "counter", "缓存未命中总数", stats.misses as f64);
+    writer.metric("cache_writes_total", "counter", "缓存写入总数", stats.writes as f64);
+    writer.metric("cache_deletes_total", "counter", "缓存删除总数", stats.deletes as f64);
+    writer.metric("cache_evictions_total", "counter", "过期清理的条目总数", stats.evictions as f64);
+    writer.metric("cache_keys", "gauge", "缓存条目数", stats.total_keys as f64);
+    writer.metric("cache_size_bytes", "gauge", "缓存数据库的磁盘占用（字节）", stats.estimated_size_bytes as f64);
+    writer.histogram("cache_value_size_bytes", "写入缓存的值大小（字节）", &stats.value_size_bytes);
+    writer.histogram("cache_get_duration_seconds", "缓存读取耗时（秒）", &stats.get_latency_seconds);
+    writer.histogram("cache_set_duration_seconds", "缓存写入耗时（秒）", &stats.set_latency_seconds);
+}
+
+/// Prometheus 文本格式写入器
+///
+/// 指标名自动加上 [`METRIC_PREFIX`]
+#[derive(Debug, Default)]
+pub struct PrometheusWriter {
+    out: String,
+}
+
+impl PrometheusWriter {
+    /// 创建空的写入器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 写入指标族的 `HELP` 与 `TYPE` 行
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 指标名（不含前缀）
+    /// * `kind` - 指标类型（`counter`、`gauge`、`histogram`）
+    /// * `help` - 说明
+    pub fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+        let _ = writeln!(self.out, "# HELP {}{} {}", METRIC_PREFIX, name, help);
+        let _ = writeln!(self.out, "# TYPE {}{} {}", METRIC_PREFIX, name, kind);
+    }
+
+    /// 写入一个样本
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 指标名（不含前缀）
+    /// * `labels` - 标签
+    /// * `value` - 样本值
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        let _ = write!(self.out, "{}{}", METRIC_PREFIX, name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+                .collect();
+            let _ = write!(self.out, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.out, " {}", format_value(value));
+    }
+
+    /// 写入不带标签的单值指标
+    pub fn metric(&mut self, name: &str, kind: &str, help: &str, value: f64) {
+        self.family(name, kind, help);
+        self.sample(name, &[], value);
+    }
+
+    /// 写入直方图（`_bucket`、`_sum` 与 `_count` 样本）
+    pub fn histogram(&mut self, name: &str, help: &str, histogram: &Histogram) {
+        self.family(name, "histogram", help);
+        let bucket = format!("{}_bucket", name);
+        for (bound, count) in histogram.bounds.iter().zip(&histogram.buckets) {
+            self.sample(&bucket, &[("le", &format_value(*bound))], *count as f64);
+        }
+        self.sample(&bucket, &[("le", "+Inf")], histogram.count as f64);
+        self.sample(&format!("{}_sum", name), &[], histogram.sum);
+        self.sample(&format!("{}_count", name), &[], histogram.count as f64);
+    }
+
+    /// 返回写入的文本
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// 转义标签值中的反斜杠、双引号与换行
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// 按 Prometheus 文本格式格式化样本值
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::EngineStatsRecord;
+
+    #[test]
+    fn test_histogram_output() {
+        let histogram = Histogram {
+            bounds: vec![0.001, 0.01],
+            buckets: vec![1, 3],
+            count: 4,
+            sum: 0.5,
+        };
+        let mut writer = PrometheusWriter::new();
+        writer.histogram("cache_get_duration_seconds", "读取耗时", &histogram);
+        let text = writer.finish();
+        assert!(text.contains("# TYPE seesea_cache_get_duration_seconds histogram\n"));
+        assert!(text.contains("seesea_cache_get_duration_seconds_bucket{le=\"0.001\"} 1\n"));
+        assert!(text.contains("seesea_cache_get_duration_seconds_bucket{le=\"0.01\"} 3\n"));
+        assert!(text.contains("seesea_cache_get_duration_seconds_bucket{le=\"+Inf\"} 4\n"));
+        assert!(text.contains("seesea_cache_get_duration_seconds_sum 0.5\n"));
+        assert!(text.contains("seesea_cache_get_duration_seconds_count 4\n"));
+    }
+
+    #[test]
+    fn test_cache_and_engine_metrics() {
+        let stats = CacheStats {
+            hits: 3,
+            misses: 1,
+            ..Default::default()
+        };
+        let mut record = EngineStatsRecord::new("bi\"ng");
+        record.record_success(250, 10);
+        let report = EngineReport {
+            generated_at: 0,
+            total_requests: 1,
+            total_failures: 0,
+            engines: vec![EngineReportEntry {
+                success_rate: None,
+                avg_results: 10.0,
+                p50_latency_ms: record.p50_latency_ms(),
+                p95_latency_ms: None,
+                bandwidth: None,
+                degraded: false,
+                clicks: 0,
+                click_through_rate: None,
+                stats: record,
+            }],
+        };
 
-// Placeholder for future metrics handlers
-// Currently handled in on.rs
+        let mut writer = PrometheusWriter::new();
+        write_engine_metrics(&mut writer, &report);
+        write_cache_metrics(&mut writer, &stats);
+        let text = writer.finish();
+        assert!(text.contains("seesea_cache_hits_total 3\n"));
+        assert!(text.contains("seesea_cache_misses_total 1\n"));
+        assert!(text.contains("seesea_engine_requests_total{engine=\"bi\\\"ng\"} 1\n"));
+        assert!(text.contains("seesea_engine_latency_p50_seconds{engine=\"bi\\\"ng\"} 0.25\n"));
+        // 没有样本的分位数不输出
+        assert!(!text.contains("seesea_engine_latency_p95_seconds{"));
+        assert!(text.contains("seesea_cache_value_size_bytes_count 0\n"));
+    }
+}
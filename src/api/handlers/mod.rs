@@ -22,3 +22,14 @@ pub mod config;
 pub mod metrics;
 pub mod rss;
 pub mod cache;
+pub mod redirect;
+pub mod click;
+pub mod image_proxy;
+pub mod map;
+pub mod feed;
+pub mod alerts;
+pub mod preferences;
+pub mod sessions;
+pub mod history;
+pub mod audit;
+pub mod ws;
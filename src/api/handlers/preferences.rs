@@ -0,0 +1,144 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 用户偏好 API 处理器
+//!
+//! 创建偏好时返回不透明令牌，之后凭令牌读取、更新或删除；不涉及账户与身份信息
+
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use crate::api::on::ApiState;
+use crate::api::types::ApiErrorResponse;
+use crate::cache::UserPreferences;
+
+/// 创建偏好的响应
+#[derive(Debug, Serialize)]
+pub struct PreferencesCreatedResponse {
+    /// 偏好令牌（仅在创建时返回，请由客户端妥善保存）
+    pub token: String,
+    /// 保存的偏好
+    pub preferences: UserPreferences,
+}
+
+/// 错误响应
+fn error_response(status: StatusCode, code: &str, message: &str, details: Option<String>) -> Response {
+    let error = ApiErrorResponse {
+        code: code.to_string(),
+        message: message.to_string(),
+        details,
+    };
+    (status, Json(error)).into_response()
+}
+
+/// 未设置偏好存储时的响应
+fn preferences_disabled() -> Response {
+    error_response(StatusCode::SERVICE_UNAVAILABLE, "PREFERENCES_DISABLED", "未启用用户偏好存储", None)
+}
+
+/// 令牌不存在时的响应
+fn not_found() -> Response {
+    error_response(StatusCode::NOT_FOUND, "NOT_FOUND", "偏好令牌不存在", None)
+}
+
+/// 存储操作失败的响应
+fn storage_error(e: impl std::fmt::Display) -> Response {
+    error_response(StatusCode::INTERNAL_SERVER_ERROR, "PREFERENCE_STORAGE_ERROR", "读写偏好存储失败", Some(e.to_string()))
+}
+
+/// 偏好取值无效的响应
+fn invalid_preferences(e: String) -> Response {
+    error_response(StatusCode::BAD_REQUEST, "INVALID_PREFERENCES", "偏好取值无效", Some(e))
+}
+
+/// 处理创建偏好请求
+pub async fn handle_preferences_create(
+    State(state): State<ApiState>,
+    Json(preferences): Json<UserPreferences>,
+) -> Response {
+    let Some(store) = state.preferences.as_ref() else {
+        return preferences_disabled();
+    };
+    if let Err(e) = preferences.validate() {
+        return invalid_preferences(e);
+    }
+
+    match store.create_async(preferences).await {
+        Ok(token) => match store.get_async(&token).await {
+            Ok(Some(preferences)) => {
+                (StatusCode::CREATED, Json(PreferencesCreatedResponse { token, preferences })).into_response()
+            }
+            Ok(None) => not_found(),
+            Err(e) => storage_error(e),
+        },
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理读取偏好请求
+pub async fn handle_preferences_get(
+    State(state): State<ApiState>,
+    Path(token): Path<String>,
+) -> Response {
+    let Some(store) = state.preferences.as_ref() else {
+        return preferences_disabled();
+    };
+    match store.get_async(&token).await {
+        Ok(Some(preferences)) => (StatusCode::OK, Json(preferences)).into_response(),
+        Ok(None) => not_found(),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理更新偏好请求（整体替换）
+pub async fn handle_preferences_update(
+    State(state): State<ApiState>,
+    Path(token): Path<String>,
+    Json(preferences): Json<UserPreferences>,
+) -> Response {
+    let Some(store) = state.preferences.as_ref() else {
+        return preferences_disabled();
+    };
+    if let Err(e) = preferences.validate() {
+        return invalid_preferences(e);
+    }
+
+    match store.update_async(&token, preferences).await {
+        Ok(true) => match store.get_async(&token).await {
+            Ok(Some(preferences)) => (StatusCode::OK, Json(preferences)).into_response(),
+            Ok(None) => not_found(),
+            Err(e) => storage_error(e),
+        },
+        Ok(false) => not_found(),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理删除偏好请求
+pub async fn handle_preferences_delete(
+    State(state): State<ApiState>,
+    Path(token): Path<String>,
+) -> Response {
+    let Some(store) = state.preferences.as_ref() else {
+        return preferences_disabled();
+    };
+    match store.delete_async(&token).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => not_found(),
+        Err(e) => storage_error(e),
+    }
+}
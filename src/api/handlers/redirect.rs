@@ -0,0 +1,220 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 跳转 API 处理器
+//!
+//! 结果链接经 `/r?u=...&s=...` 跳转，去除追踪参数并禁止发送 Referer；
+//! 目标须带有服务器签名，防止端点被当作开放跳转使用
+
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+use url::Url;
+
+use crate::api::on::ApiState;
+use crate::api::signing::{LinkSigner, SIGNATURE_PARAM};
+use crate::api::types::ApiErrorResponse;
+use crate::cache::HistoryEntry;
+use crate::config::privacy::UrlRewriteConfig;
+
+/// 跳转端点路径
+pub const REDIRECT_PATH: &str = "/r";
+
+/// 跳转请求参数
+#[derive(Debug, Deserialize)]
+pub struct RedirectParams {
+    /// 目标 URL
+    pub u: String,
+    /// 目标 URL 的签名
+    pub s: Option<String>,
+}
+
+/// 判断参数名是否在移除列表中
+///
+/// 以 `*` 结尾的规则按前缀匹配，其余规则按名称精确匹配（忽略大小写）
+fn is_tracking_param(name: &str, rules: &[String]) -> bool {
+    let name = name.to_ascii_lowercase();
+    rules.iter().any(|rule| {
+        let rule = rule.to_ascii_lowercase();
+        match rule.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == rule,
+        }
+    })
+}
+
+/// 移除 URL 中的追踪参数
+///
+/// # Arguments
+///
+/// * `target` - 原始 URL
+/// * `rules` - 需要移除的参数规则
+///
+/// # Returns
+///
+/// 仅接受 http/https 链接，其他协议或无效 URL 返回 None
+pub fn strip_tracking_params(target: &str, rules: &[String]) -> Option<String> {
+    let mut parsed = Url::parse(target).ok()?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return None;
+    }
+
+    let kept: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(name, _)| !is_tracking_param(name, rules))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+
+    if kept.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(kept);
+    }
+
+    Some(parsed.to_string())
+}
+
+/// 将结果链接改写为本地跳转链接
+///
+/// 未启用重写时原样返回
+///
+/// # Arguments
+///
+/// * `target` - 原始链接
+/// * `config` - 链接重写配置
+/// * `signer` - 链接签名器（签名附加在 `s` 参数中）
+pub fn rewrite_result_url(target: &str, config: &UrlRewriteConfig, signer: &LinkSigner) -> String {
+    if !config.enabled {
+        return target.to_string();
+    }
+
+    let base = config
+        .base_url
+        .as_deref()
+        .map(|b| b.trim_end_matches('/'))
+        .unwrap_or("");
+    format!(
+        "{}{}?u={}&{}={}",
+        base,
+        REDIRECT_PATH,
+        urlencoding::encode(target),
+        SIGNATURE_PARAM,
+        signer.sign(target)
+    )
+}
+
+/// 处理跳转请求
+pub async fn handle_redirect(
+    State(state): State<ApiState>,
+    Query(params): Query<RedirectParams>,
+) -> Response {
+    if let Some(response) = reject_unsigned(&state, &params.u, params.s.as_deref()) {
+        return response;
+    }
+    follow_link(&state, &params.u).await
+}
+
+/// 校验跳转目标的签名
+///
+/// 签名有效时返回 None，缺少签名或签名与目标不匹配时返回 400 响应
+pub(crate) fn reject_unsigned(state: &ApiState, target: &str, signature: Option<&str>) -> Option<Response> {
+    match signature {
+        Some(signature) if state.link_signer.verify(target, signature) => None,
+        _ => {
+            let error = ApiErrorResponse {
+                code: "INVALID_SIGNATURE".to_string(),
+                message: "跳转目标签名无效".to_string(),
+                details: Some("只接受搜索结果中由服务器签名的链接".to_string()),
+            };
+            Some((StatusCode::BAD_REQUEST, Json(error)).into_response())
+        }
+    }
+}
+
+/// 去除追踪参数后跳转到目标链接（启用搜索历史时记录点击）
+pub(crate) async fn follow_link(state: &ApiState, target: &str) -> Response {
+    match strip_tracking_params(target, &state.url_rewrite.strip_params) {
+        Some(location) => {
+            if let Some(history) = state.history.as_ref()
+                && let Err(e) = history.record_async(HistoryEntry::click("", &location, None)).await
+            {
+                tracing::warn!("记录点击历史失败: {}", e);
+            }
+            (
+                StatusCode::FOUND,
+                [
+                    (header::LOCATION, location),
+                    (header::REFERRER_POLICY, "no-referrer".to_string()),
+                    (header::CACHE_CONTROL, "no-store".to_string()),
+                ],
+            )
+                .into_response()
+        }
+        None => {
+            let error = ApiErrorResponse {
+                code: "INVALID_URL".to_string(),
+                message: "跳转目标无效".to_string(),
+                details: Some("仅支持 http/https 链接".to_string()),
+            };
+            (StatusCode::BAD_REQUEST, Json(error)).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_rules() -> Vec<String> {
+        UrlRewriteConfig::default().strip_params
+    }
+
+    #[test]
+    fn test_strip_tracking_params() {
+        let url = "https://example.com/page?id=7&utm_source=x&UTM_Medium=y&fbclid=abc&gclid=def";
+        let stripped = strip_tracking_params(url, &default_rules()).unwrap();
+        assert_eq!(stripped, "https://example.com/page?id=7");
+    }
+
+    #[test]
+    fn test_strip_all_params_removes_query() {
+        let stripped = strip_tracking_params("https://example.com/?utm_campaign=z", &default_rules()).unwrap();
+        assert_eq!(stripped, "https://example.com/");
+    }
+
+    #[test]
+    fn test_strip_rejects_non_http() {
+        assert!(strip_tracking_params("javascript:alert(1)", &default_rules()).is_none());
+        assert!(strip_tracking_params("not a url", &default_rules()).is_none());
+    }
+
+    #[test]
+    fn test_rewrite_result_url() {
+        let signer = LinkSigner::new(b"test");
+        let mut config = UrlRewriteConfig::default();
+        assert_eq!(rewrite_result_url("https://a.com/?x=1", &config, &signer), "https://a.com/?x=1");
+
+        config.enabled = true;
+        assert_eq!(
+            rewrite_result_url("https://a.com/?x=1", &config, &signer),
+            format!("/r?u=https%3A%2F%2Fa.com%2F%3Fx%3D1&s={}", signer.sign("https://a.com/?x=1"))
+        );
+
+        config.base_url = Some("https://search.example.com/".to_string());
+        assert!(rewrite_result_url("https://a.com", &config, &signer).starts_with("https://search.example.com/r?u="));
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 搜索会话 API 处理器
+//!
+//! 创建会话时返回不透明令牌，搜索请求携带 `session=<令牌>` 时查询记入该会话；
+//! 可凭令牌查看会话中的查询或提前结束会话
+
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use crate::api::on::ApiState;
+use crate::api::types::ApiErrorResponse;
+use crate::cache::{validate_session_token, SearchSession};
+
+/// 创建会话的响应
+#[derive(Debug, Serialize)]
+pub struct SessionCreatedResponse {
+    /// 会话令牌（搜索时通过 `session` 参数传入）
+    pub token: String,
+    /// 会话有效期（秒，自最后一次查询起）
+    pub ttl_secs: u64,
+}
+
+/// 错误响应
+fn error_response(status: StatusCode, code: &str, message: &str, details: Option<String>) -> Response {
+    let error = ApiErrorResponse {
+        code: code.to_string(),
+        message: message.to_string(),
+        details,
+    };
+    (status, Json(error)).into_response()
+}
+
+/// 未启用搜索会话时的响应
+fn sessions_disabled() -> Response {
+    error_response(StatusCode::SERVICE_UNAVAILABLE, "SESSIONS_DISABLED", "未启用搜索会话（需启用缓存）", None)
+}
+
+/// 会话不存在或已过期时的响应
+fn not_found() -> Response {
+    error_response(StatusCode::NOT_FOUND, "NOT_FOUND", "搜索会话不存在或已过期", None)
+}
+
+/// 存储操作失败的响应
+fn storage_error(e: impl std::fmt::Display) -> Response {
+    error_response(StatusCode::INTERNAL_SERVER_ERROR, "SESSION_STORAGE_ERROR", "读写搜索会话失败", Some(e.to_string()))
+}
+
+/// 令牌无效时的响应
+fn invalid_token(token: &str) -> Option<Response> {
+    validate_session_token(token)
+        .err()
+        .map(|e| error_response(StatusCode::BAD_REQUEST, "INVALID_SESSION_TOKEN", "会话令牌无效", Some(e)))
+}
+
+/// 处理创建会话请求
+pub async fn handle_session_create(State(state): State<ApiState>) -> Response {
+    let Some(store) = state.search.sessions() else {
+        return sessions_disabled();
+    };
+    match store.create_async().await {
+        Ok(token) => {
+            let response = SessionCreatedResponse { token, ttl_secs: store.ttl().as_secs() };
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理读取会话请求
+pub async fn handle_session_get(
+    State(state): State<ApiState>,
+    Path(token): Path<String>,
+) -> Response {
+    let Some(store) = state.search.sessions() else {
+        return sessions_disabled();
+    };
+    if let Some(response) = invalid_token(&token) {
+        return response;
+    }
+    match store.get_async(&token).await {
+        Ok(Some(session)) => (StatusCode::OK, Json::<SearchSession>(session)).into_response(),
+        Ok(None) => not_found(),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// 处理删除会话请求
+pub async fn handle_session_delete(
+    State(state): State<ApiState>,
+    Path(token): Path<String>,
+) -> Response {
+    let Some(store) = state.search.sessions() else {
+        return sessions_disabled();
+    };
+    if let Some(response) = invalid_token(&token) {
+        return response;
+    }
+    match store.delete_async(&token).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => not_found(),
+        Err(e) => storage_error(e),
+    }
+}
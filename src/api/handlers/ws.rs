@@ -0,0 +1,248 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WebSocket API 处理器
+//!
+//! 客户端通过 `/ws` 在同一连接上提交多个查询，服务端在每个引擎返回时推送
+//! 该引擎的结果与进度统计，全部完成后推送聚合结果。所有帧均为 JSON 文本，
+//! 以 `type` 字段区分，以客户端提供的 `id` 关联查询
+
+use axum::{
+    extract::{
+        Extension, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::Response,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+use crate::api::middleware::ApiClient;
+use crate::api::on::{build_api_items, build_api_response, build_search_request, ApiState};
+use crate::api::types::{ApiSearchRequest, ApiSearchResponse, ApiSearchResultItem};
+
+/// WebSocket 端点路径
+pub const WS_PATH: &str = "/ws";
+
+/// 单个连接同时进行的查询上限
+const MAX_CONCURRENT_SEARCHES: usize = 4;
+
+/// 客户端帧
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientFrame {
+    /// 提交查询（参数与 `/api/search` 相同）
+    Search {
+        /// 查询 ID（原样出现在该查询的所有服务端帧中）
+        #[serde(default)]
+        id: Option<String>,
+        /// 搜索参数
+        #[serde(flatten)]
+        params: ApiSearchRequest,
+    },
+}
+
+/// 服务端帧
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerFrame {
+    /// 某个引擎返回的结果
+    EngineResult {
+        /// 查询 ID
+        id: Option<String>,
+        /// 引擎名称
+        engine: String,
+        /// 该引擎的结果
+        results: Vec<ApiSearchResultItem>,
+        /// 引擎耗时（毫秒）
+        elapsed_ms: u64,
+    },
+    /// 查询进度
+    Stats {
+        /// 查询 ID
+        id: Option<String>,
+        /// 已返回的引擎数
+        engines_completed: usize,
+        /// 已收到的结果数
+        results_received: usize,
+        /// 查询开始以来的耗时（毫秒）
+        elapsed_ms: u64,
+    },
+    /// 全部引擎完成后的聚合结果
+    Done {
+        /// 查询 ID
+        id: Option<String>,
+        /// 聚合结果（与 `/api/search` 响应相同）
+        response: Box<ApiSearchResponse>,
+    },
+    /// 错误
+    Error {
+        /// 查询 ID（无法解析的帧为 None）
+        id: Option<String>,
+        /// 错误消息
+        message: String,
+    },
+}
+
+/// 处理 WebSocket 升级请求
+pub async fn handle_ws(
+    State(state): State<ApiState>,
+    Extension(client): Extension<ApiClient>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| handle_socket(socket, state, client))
+}
+
+/// 处理一个 WebSocket 连接
+///
+/// 读取循环解析客户端帧并为每个查询启动任务，写入任务将各查询的帧依次发出；
+/// 连接关闭时取消所有进行中的查询
+async fn handle_socket(socket: WebSocket, state: ApiState, client: ApiClient) {
+    let (mut sink, mut stream) = socket.split();
+    let (sender, mut receiver) = mpsc::unbounded_channel::<ServerFrame>();
+
+    let writer = tokio::spawn(async move {
+        while let Some(frame) = receiver.recv().await {
+            let text = match serde_json::to_string(&frame) {
+                Ok(text) => text,
+                Err(e) => {
+                    tracing::warn!("序列化 WebSocket 帧失败: {}", e);
+                    continue;
+                }
+            };
+            if sink.send(Message::Text(text.into())).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut searches = JoinSet::new();
+    while let Some(Ok(message)) = stream.next().await {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        // 回收已结束的查询
+        while searches.try_join_next().is_some() {}
+
+        match serde_json::from_str::<ClientFrame>(&text) {
+            Ok(ClientFrame::Search { id, params }) => {
+                if searches.len() >= MAX_CONCURRENT_SEARCHES {
+                    let message = format!("同时进行的查询不能超过 {} 个", MAX_CONCURRENT_SEARCHES);
+                    let _ = sender.send(ServerFrame::Error { id, message });
+                    continue;
+                }
+                searches.spawn(run_search(state.clone(), client.clone(), id, params, sender.clone()));
+            }
+            Err(e) => {
+                let _ = sender.send(ServerFrame::Error {
+                    id: None,
+                    message: format!("无效的消息: {}", e),
+                });
+            }
+        }
+    }
+
+    searches.abort_all();
+    drop(sender);
+    let _ = writer.await;
+}
+
+/// 执行一个查询，引擎结果、进度与聚合结果依次写入 `sender`
+async fn run_search(
+    state: ApiState,
+    client: ApiClient,
+    id: Option<String>,
+    params: ApiSearchRequest,
+    sender: mpsc::UnboundedSender<ServerFrame>,
+) {
+    let start_time = std::time::Instant::now();
+    let request = match build_search_request(&params, &client) {
+        Ok(request) => request,
+        Err(e) => {
+            let _ = sender.send(ServerFrame::Error { id, message: e.to_string() });
+            return;
+        }
+    };
+
+    let mut engines_completed = 0;
+    let mut results_received = 0;
+    let callback = {
+        let (state, client, id, sender) = (state.clone(), client.clone(), id.clone(), sender.clone());
+        move |result: crate::derive::SearchResult, engine: String| {
+            let results = build_api_items(&state, &client, &result);
+            engines_completed += 1;
+            results_received += results.len();
+            let _ = sender.send(ServerFrame::EngineResult {
+                id: id.clone(),
+                engine,
+                results,
+                elapsed_ms: result.elapsed_ms,
+            });
+            let _ = sender.send(ServerFrame::Stats {
+                id: id.clone(),
+                engines_completed,
+                results_received,
+                elapsed_ms: start_time.elapsed().as_millis() as u64,
+            });
+        }
+    };
+
+    let frame = match state.search.search_streaming(&request, callback).await {
+        Ok(response) => {
+            let elapsed_ms = start_time.elapsed().as_millis() as u64;
+            ServerFrame::Done {
+                id,
+                response: Box::new(build_api_response(&state, &client, &params, response, elapsed_ms)),
+            }
+        }
+        Err(e) => ServerFrame::Error { id, message: e.to_string() },
+    };
+    let _ = sender.send(frame);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_frame_parsing() {
+        let frame: ClientFrame =
+            serde_json::from_str(r#"{"type":"search","id":"1","q":"rust","page":2}"#).unwrap();
+        let ClientFrame::Search { id, params } = frame;
+        assert_eq!(id.as_deref(), Some("1"));
+        assert_eq!(params.get_query().unwrap(), "rust");
+        assert_eq!(params.page, 2);
+
+        assert!(serde_json::from_str::<ClientFrame>(r#"{"type":"unknown"}"#).is_err());
+    }
+
+    #[test]
+    fn test_server_frame_serialization() {
+        let frame = ServerFrame::Stats {
+            id: Some("1".to_string()),
+            engines_completed: 2,
+            results_received: 15,
+            elapsed_ms: 120,
+        };
+        let json = serde_json::to_value(&frame).unwrap();
+        assert_eq!(json["type"], "stats");
+        assert_eq!(json["id"], "1");
+        assert_eq!(json["engines_completed"], 2);
+    }
+}
@@ -0,0 +1,195 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API 服务器监听器
+//!
+//! 支持 TCP 地址、Unix 域套接字与 systemd 套接字激活传入的已绑定监听
+
+use super::on::ServerConfig;
+use std::io;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+
+/// systemd 传入的第一个文件描述符（`SD_LISTEN_FDS_START`）
+#[cfg(unix)]
+const SD_LISTEN_FDS_START: i32 = 3;
+
+/// API 服务器监听器
+#[derive(Debug)]
+pub enum ApiListener {
+    /// TCP 监听
+    Tcp(tokio::net::TcpListener),
+    /// Unix 域套接字监听
+    #[cfg(unix)]
+    Unix {
+        /// 监听器
+        listener: tokio::net::UnixListener,
+        /// 由本进程创建的套接字文件（停机后删除，套接字激活时为 None）
+        path: Option<PathBuf>,
+    },
+}
+
+impl ApiListener {
+    /// 按服务器配置创建监听器
+    ///
+    /// 优先级：systemd 套接字激活 > Unix 域套接字 > `host:port`
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - 服务器配置
+    ///
+    /// # Returns
+    ///
+    /// 返回已绑定的监听器或绑定错误
+    pub async fn bind(config: &ServerConfig) -> io::Result<Self> {
+        if config.socket_activation {
+            return Self::from_systemd();
+        }
+        if let Some(ref path) = config.unix_socket {
+            return Self::bind_unix(path, config.unix_socket_mode);
+        }
+        let addr = format!("{}:{}", config.host, config.port);
+        Ok(Self::Tcp(tokio::net::TcpListener::bind(&addr).await?))
+    }
+
+    /// 绑定 Unix 域套接字
+    ///
+    /// 路径上残留的套接字文件（上次未正常停机）会先被删除；路径被其他类型的文件占用时返回错误
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - 套接字路径
+    /// * `mode` - 套接字文件权限（None 时沿用 umask）
+    #[cfg(unix)]
+    pub fn bind_unix(path: &Path, mode: Option<u32>) -> io::Result<Self> {
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+        match std::fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)?,
+            Ok(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("{} exists and is not a socket", path.display()),
+                ));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let listener = tokio::net::UnixListener::bind(path)?;
+        if let Some(mode) = mode {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+        }
+        Ok(Self::Unix {
+            listener,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// 绑定 Unix 域套接字（非 Unix 平台不支持）
+    #[cfg(not(unix))]
+    pub fn bind_unix(_path: &std::path::Path, _mode: Option<u32>) -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "unix sockets are only supported on unix"))
+    }
+
+    /// 接管 systemd 套接字激活传入的监听
+    ///
+    /// 要求 `LISTEN_PID` 为本进程且 `LISTEN_FDS` 至少为 1，使用第一个描述符；
+    /// 描述符可以是 TCP 或 Unix 域套接字
+    #[cfg(unix)]
+    pub fn from_systemd() -> io::Result<Self> {
+        use std::os::fd::FromRawFd;
+
+        let listen_pid = std::env::var("LISTEN_PID").ok().and_then(|pid| pid.parse::<u32>().ok());
+        let listen_fds = std::env::var("LISTEN_FDS").ok().and_then(|fds| fds.parse::<u32>().ok());
+        if listen_pid != Some(std::process::id()) || listen_fds.unwrap_or(0) == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "socket activation requested but no listening socket was passed (LISTEN_PID/LISTEN_FDS)",
+            ));
+        }
+
+        // SAFETY: systemd 约定 LISTEN_PID 为本进程时，从 SD_LISTEN_FDS_START 起的描述符是传给本进程的
+        // 已绑定监听套接字，且只在这里接管一次
+        let tcp = unsafe { std::net::TcpListener::from_raw_fd(SD_LISTEN_FDS_START) };
+        match tcp.local_addr() {
+            Ok(_) => {
+                tcp.set_nonblocking(true)?;
+                Ok(Self::Tcp(tokio::net::TcpListener::from_std(tcp)?))
+            }
+            // 非 IP 地址族的套接字按 Unix 域套接字接管
+            Err(_) => {
+                let unix = std::os::unix::net::UnixListener::from(std::os::fd::OwnedFd::from(tcp));
+                unix.set_nonblocking(true)?;
+                Ok(Self::Unix {
+                    listener: tokio::net::UnixListener::from_std(unix)?,
+                    path: None,
+                })
+            }
+        }
+    }
+
+    /// 接管 systemd 套接字激活传入的监听（非 Unix 平台不支持）
+    #[cfg(not(unix))]
+    pub fn from_systemd() -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "socket activation is only supported on unix"))
+    }
+
+    /// 监听地址的可读描述（如 `http://127.0.0.1:8080`、`unix:/run/seesea.sock`）
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Tcp(listener) => match listener.local_addr() {
+                Ok(addr) => format!("http://{}", addr),
+                Err(_) => "tcp".to_string(),
+            },
+            #[cfg(unix)]
+            Self::Unix { listener, .. } => match listener.local_addr().ok().and_then(|addr| addr.as_pathname().map(Path::to_path_buf)) {
+                Some(path) => format!("unix:{}", path.display()),
+                None => "unix".to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bind_unix_replaces_stale_socket() {
+        let path = std::env::temp_dir().join(format!("seesea_listener_{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let first = ApiListener::bind_unix(&path, Some(0o660)).unwrap();
+        assert_eq!(first.describe(), format!("unix:{}", path.display()));
+        drop(first);
+
+        // 残留的套接字文件不妨碍重新绑定
+        let second = ApiListener::bind_unix(&path, None).unwrap();
+        drop(second);
+        std::fs::remove_file(&path).unwrap();
+
+        // 普通文件不会被覆盖
+        std::fs::write(&path, b"data").unwrap();
+        let err = ApiListener::bind_unix(&path, None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_from_systemd_without_activation() {
+        let err = ApiListener::from_systemd().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -14,10 +14,51 @@
 
 //! 认证中间件
 //!
-//! 提供 API 认证功能
+//! 提供 API 认证功能：按 API 密钥识别调用方并绑定租户配置档
+//! （允许的引擎、强制安全搜索级别、隐私设置与限流）
 
-// Placeholder for future authentication implementation
-// Will support API keys, JWT, etc.
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::api::on::ApiState;
+use crate::api::middleware::RequestId;
+use crate::api::types::ApiErrorResponse;
+use crate::cache::{principal_id, AuditEntry, AuditEvent};
+use crate::config::api::{ApiConfig, ProfileConfig};
+use crate::config::common::AuthType;
+use crate::config::privacy::{ImageProxyConfig, UrlRewriteConfig};
+use crate::derive::SearchQuery;
+
+/// 无需认证的路径（健康检查，以及嵌入结果中的跳转与缩略图链接，后者只接受服务器签名的目标）
+const PUBLIC_PATHS: &[&str] = &[
+    "/health",
+    "/api/health",
+    crate::api::handlers::health::HEALTHZ_PATH,
+    crate::api::handlers::health::READYZ_PATH,
+    crate::api::handlers::redirect::REDIRECT_PATH,
+    crate::api::handlers::click::CLICK_PATH,
+    crate::api::handlers::image_proxy::IMAGE_PROXY_PATH,
+];
+
+/// 无法识别客户端地址的匿名请求的限流标识
+const ANONYMOUS_CLIENT: &str = "anonymous";
+
+/// 反向代理传递客户端地址的请求头
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// 管理员权限（API 密钥的 `permissions` 包含该项时可访问审计日志等管理端点）
+pub const ADMIN_PERMISSION: &str = "admin";
 
 /// 认证配置
 #[derive(Debug, Clone)]
@@ -38,9 +79,506 @@ impl Default for AuthConfig {
     }
 }
 
+/// 计算 API 密钥的存储哈希（SHA-256 小写十六进制，对应 `key_hash` 字段）
+pub fn hash_api_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 已识别的调用方，由 [`authenticate`] 写入请求扩展
+#[derive(Debug, Clone, Default)]
+pub struct ApiClient {
+    /// API 密钥名称（匿名请求为 None）
+    pub key_name: Option<String>,
+    /// 绑定的配置档名称
+    pub profile_name: Option<String>,
+    /// 绑定的配置档（未绑定时不做限制）
+    pub profile: Option<Arc<ProfileConfig>>,
+    /// 是否具有管理员权限
+    pub admin: bool,
+    /// 客户端地址（匿名请求按该地址限流）
+    pub address: Option<IpAddr>,
+}
+
+impl ApiClient {
+    /// 限流标识
+    ///
+    /// 携带密钥的请求按密钥名称计数，匿名请求按客户端地址计数
+    /// （IPv6 地址按 /64 前缀，避免同一网段轮换地址绕过限流）
+    fn rate_limit_key(&self) -> Cow<'_, str> {
+        match (&self.key_name, self.address) {
+            (Some(name), _) => Cow::Borrowed(name),
+            (None, Some(IpAddr::V6(ip))) => {
+                let prefix = u128::from(ip) & !((1u128 << 64) - 1);
+                Cow::Owned(format!("ip:{}/64", Ipv6Addr::from(prefix)))
+            }
+            (None, Some(ip)) => Cow::Owned(format!("ip:{}", ip)),
+            (None, None) => Cow::Borrowed(ANONYMOUS_CLIENT),
+        }
+    }
+
+    /// 审计记录中的调用方标识（密钥名称或客户端地址的哈希）
+    pub fn principal(&self) -> String {
+        principal_id(&self.rate_limit_key())
+    }
+
+    /// 按配置档限制引擎列表
+    ///
+    /// # Arguments
+    ///
+    /// * `requested` - 请求的引擎列表
+    /// * `explicit` - 请求是否显式指定了引擎（否则使用配置档的引擎列表）
+    ///
+    /// # Returns
+    ///
+    /// 返回允许使用的引擎，全部被拒绝时返回错误
+    pub fn restrict_engines(&self, requested: Vec<String>, explicit: bool) -> Result<Vec<String>, String> {
+        let Some(profile) = self.profile.as_ref().filter(|p| !p.engines.is_empty()) else {
+            return Ok(requested);
+        };
+        if !explicit {
+            return Ok(profile.engines.clone());
+        }
+
+        let allowed: Vec<String> = requested.into_iter().filter(|e| profile.engines.contains(e)).collect();
+        if allowed.is_empty() {
+            return Err(format!(
+                "配置档 '{}' 不允许使用所请求的引擎",
+                self.profile_name.as_deref().unwrap_or_default()
+            ));
+        }
+        Ok(allowed)
+    }
+
+    /// 应用配置档强制的安全搜索级别
+    pub fn apply_safe_search(&self, query: &mut SearchQuery) {
+        if let Some(level) = self.profile.as_ref().and_then(|p| p.safe_search.clone()) {
+            query.safe_search = level;
+        }
+    }
+
+    /// 该调用方生效的链接重写配置
+    pub fn url_rewrite<'a>(&self, base: &'a UrlRewriteConfig) -> Cow<'a, UrlRewriteConfig> {
+        match self.profile.as_ref().and_then(|p| p.url_rewrite) {
+            Some(enabled) if enabled != base.enabled => Cow::Owned(UrlRewriteConfig {
+                enabled,
+                ..base.clone()
+            }),
+            _ => Cow::Borrowed(base),
+        }
+    }
+
+    /// 该调用方生效的缩略图代理配置
+    pub fn image_proxy<'a>(&self, base: &'a ImageProxyConfig) -> Cow<'a, ImageProxyConfig> {
+        match self.profile.as_ref().and_then(|p| p.image_proxy) {
+            Some(enabled) if enabled != base.enabled => Cow::Owned(ImageProxyConfig {
+                enabled,
+                ..base.clone()
+            }),
+            _ => Cow::Borrowed(base),
+        }
+    }
+}
+
+/// 认证失败原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// 需要 API 密钥但未提供
+    MissingKey,
+    /// 密钥无效、已禁用或已过期
+    InvalidKey,
+}
+
+/// 已注册的 API 密钥
+#[derive(Debug, Clone)]
+struct ApiKeyEntry {
+    client: ApiClient,
+    enabled: bool,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// API 密钥与租户配置档注册表
+#[derive(Debug, Clone)]
+pub struct ProfileRegistry {
+    /// 密钥哈希 → 密钥信息
+    keys: HashMap<String, ApiKeyEntry>,
+    /// 匿名请求对应的调用方
+    anonymous: ApiClient,
+    /// 是否要求所有请求携带密钥
+    require_key: bool,
+    /// 任一配置档启用了缩略图代理
+    any_image_proxy: bool,
+    /// 密钥请求头名称
+    header_name: String,
+    /// 密钥查询参数名称
+    query_param: String,
+    /// 受信任的反向代理地址
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl Default for ProfileRegistry {
+    fn default() -> Self {
+        Self {
+            keys: HashMap::new(),
+            anonymous: ApiClient::default(),
+            require_key: false,
+            any_image_proxy: false,
+            header_name: "X-API-Key".to_string(),
+            query_param: "api_key".to_string(),
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+impl ProfileRegistry {
+    /// 从 API 配置构建注册表
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - API 配置（`auth.api_key` 与 `profiles` 段）
+    ///
+    /// # Returns
+    ///
+    /// 返回注册表，密钥或默认配置档引用了不存在的配置档时返回错误
+    pub fn from_config(config: &ApiConfig) -> Result<Self, String> {
+        let profiles: HashMap<&str, Arc<ProfileConfig>> = config
+            .profiles
+            .iter()
+            .map(|(name, profile)| (name.as_str(), Arc::new(profile.clone())))
+            .collect();
+        let client_for = |key_name: Option<&str>, profile_name: Option<&str>| -> Result<ApiClient, String> {
+            let profile = profile_name
+                .map(|name| profiles.get(name).cloned().ok_or_else(|| format!("配置档 '{}' 不存在", name)))
+                .transpose()?;
+            Ok(ApiClient {
+                key_name: key_name.map(str::to_string),
+                profile_name: profile_name.map(str::to_string),
+                profile,
+                admin: false,
+                address: None,
+            })
+        };
+
+        let api_key = &config.auth.api_key;
+        let mut keys = HashMap::new();
+        for key in &api_key.api_keys {
+            let expires_at = key
+                .expires_at
+                .as_deref()
+                .map(|t| {
+                    DateTime::parse_from_rfc3339(t)
+                        .map(|t| t.with_timezone(&Utc))
+                        .map_err(|e| format!("API 密钥 '{}' 的过期时间无效: {}", key.name, e))
+                })
+                .transpose()?;
+            let entry = ApiKeyEntry {
+                client: ApiClient {
+                    admin: key.permissions.iter().any(|p| p == ADMIN_PERMISSION),
+                    ..client_for(Some(&key.name), key.profile.as_deref())?
+                },
+                enabled: key.enabled,
+                expires_at,
+            };
+            keys.insert(key.key_hash.to_ascii_lowercase(), entry);
+        }
+
+        Ok(Self {
+            keys,
+            anonymous: client_for(None, config.default_profile.as_deref())?,
+            require_key: config.auth.enabled && matches!(config.auth.auth_type, AuthType::ApiKey),
+            any_image_proxy: config.profiles.values().any(|p| p.image_proxy == Some(true)),
+            header_name: api_key.header_name.clone(),
+            query_param: api_key.query_param.clone(),
+            trusted_proxies: config.trusted_proxies.clone(),
+        })
+    }
+
+    /// 是否有配置档启用了缩略图代理（即使全局未启用，代理端点也需提供服务）
+    pub fn image_proxy_enabled(&self) -> bool {
+        self.any_image_proxy
+    }
+
+    /// 从请求头或查询参数中读取 API 密钥
+    pub fn extract_key(&self, headers: &HeaderMap, query: Option<&str>) -> Option<String> {
+        if let Some(key) = headers.get(self.header_name.as_str()).and_then(|v| v.to_str().ok()) {
+            return Some(key.trim().to_string());
+        }
+        url::form_urlencoded::parse(query?.as_bytes())
+            .find(|(name, _)| name == self.query_param.as_str())
+            .map(|(_, key)| key.into_owned())
+    }
+
+    /// 识别请求的客户端地址
+    ///
+    /// # Arguments
+    ///
+    /// * `peer` - 连接的对端地址（Unix 域套接字连接为 None）
+    /// * `headers` - 请求头
+    ///
+    /// # Returns
+    ///
+    /// 对端是受信任的反向代理或 Unix 域套接字（只有本机进程能连接）时，
+    /// 取 `X-Forwarded-For` 中从右往左第一个不受信任的地址，否则返回对端地址
+    pub fn client_address(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+        if peer.is_some_and(|ip| !self.trusted_proxies.contains(&ip)) {
+            return peer;
+        }
+        let forwarded: Vec<IpAddr> = headers
+            .get_all(X_FORWARDED_FOR)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .filter_map(|v| v.trim().parse().ok())
+            .collect();
+        forwarded
+            .into_iter()
+            .rev()
+            .find(|ip| !self.trusted_proxies.contains(ip))
+            .or(peer)
+    }
+
+    /// 根据 API 密钥识别调用方
+    ///
+    /// 未提供密钥时返回匿名调用方（绑定默认配置档），除非配置要求必须认证
+    pub fn resolve(&self, key: Option<&str>) -> Result<ApiClient, AuthError> {
+        let Some(key) = key.filter(|k| !k.is_empty()) else {
+            if self.require_key {
+                return Err(AuthError::MissingKey);
+            }
+            return Ok(self.anonymous.clone());
+        };
+
+        match self.keys.get(&hash_api_key(key)) {
+            Some(entry) if entry.enabled && entry.expires_at.is_none_or(|t| t > Utc::now()) => {
+                Ok(entry.client.clone())
+            }
+            _ => Err(AuthError::InvalidKey),
+        }
+    }
+}
+
+/// 认证与限流中间件
+///
+/// 识别调用方并写入请求扩展 [`ApiClient`]；密钥无效返回 `401`，
+/// 超出配置档的每分钟请求数返回 `429`，两者都写入审计日志
+pub async fn authenticate(State(state): State<ApiState>, mut request: Request, next: Next) -> Response {
+    if PUBLIC_PATHS.contains(&request.uri().path()) {
+        request.extensions_mut().insert(ApiClient::default());
+        return next.run(request).await;
+    }
+
+    let key = state.profiles.extract_key(request.headers(), request.uri().query());
+    let peer = request.extensions().get::<ConnectInfo<SocketAddr>>().map(|info| info.0.ip());
+    let address = state.profiles.client_address(peer, request.headers());
+    let client = match state.profiles.resolve(key.as_deref()) {
+        Ok(client) => ApiClient { address, ..client },
+        Err(e) => {
+            let (details, reason) = match e {
+                AuthError::MissingKey => ("缺少 API 密钥", "missing_key"),
+                AuthError::InvalidKey => ("API 密钥无效或已过期", "invalid_key"),
+            };
+            // 无效密钥以其哈希标识，同一密钥的反复尝试可以关联
+            let principal = principal_id(key.as_deref().unwrap_or(ANONYMOUS_CLIENT));
+            state.audit(audit_entry(&request, AuditEvent::AuthFailure, principal, reason));
+            let error = ApiErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "认证失败".to_string(),
+                details: Some(details.to_string()),
+            };
+            return (StatusCode::UNAUTHORIZED, Json(error)).into_response();
+        }
+    };
+
+    if let Some(limit) = client.profile.as_ref().and_then(|p| p.requests_per_minute)
+        && let Err(retry_after) = state.rate_limiter.check(&client.rate_limit_key(), limit)
+    {
+        state.audit(audit_entry(&request, AuditEvent::RateLimited, client.principal(), "requests_per_minute"));
+        let error = ApiErrorResponse {
+            code: "RATE_LIMITED".to_string(),
+            message: "请求过于频繁".to_string(),
+            details: Some(format!("每分钟最多 {} 次请求", limit)),
+        };
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.as_secs().max(1).to_string())],
+            Json(error),
+        )
+            .into_response();
+    }
+
+    request.extensions_mut().insert(client);
+    next.run(request).await
+}
+
+/// 创建带有请求路径与请求 ID 的审计记录
+fn audit_entry(request: &Request, event: AuditEvent, principal: String, action: &str) -> AuditEntry {
+    AuditEntry::new(event, principal, action)
+        .with_path(request.uri().path())
+        .with_request_id(request.extensions().get::<RequestId>().map(|id| id.0.as_str()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::api::ApiKeyInfo;
+    use crate::config::common::SafeSearchLevel;
+    use crate::api::middleware::ClientRateLimiter;
+
+    fn api_key(name: &str, key: &str, profile: Option<&str>) -> ApiKeyInfo {
+        ApiKeyInfo {
+            name: name.to_string(),
+            key_hash: hash_api_key(key),
+            permissions: Vec::new(),
+            enabled: true,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            expires_at: None,
+            usage_limits: None,
+            profile: profile.map(str::to_string),
+        }
+    }
+
+    fn config() -> ApiConfig {
+        let mut config = ApiConfig::default();
+        config.profiles.insert(
+            "kids".to_string(),
+            ProfileConfig {
+                engines: vec!["bing".to_string(), "wikipedia".to_string()],
+                safe_search: Some(SafeSearchLevel::Strict),
+                url_rewrite: Some(true),
+                ..Default::default()
+            },
+        );
+        config.profiles.insert("research".to_string(), ProfileConfig::default());
+        config.auth.api_key.api_keys = vec![
+            api_key("family", "sk_kids", Some("kids")),
+            api_key("lab", "sk_lab", Some("research")),
+            ApiKeyInfo {
+                expires_at: Some("2000-01-01T00:00:00Z".to_string()),
+                ..api_key("old", "sk_old", None)
+            },
+            ApiKeyInfo {
+                permissions: vec![ADMIN_PERMISSION.to_string()],
+                ..api_key("ops", "sk_ops", None)
+            },
+        ];
+        config
+    }
+
+    #[test]
+    fn test_resolve_api_keys() {
+        let registry = ProfileRegistry::from_config(&config()).unwrap();
+
+        let kids = registry.resolve(Some("sk_kids")).unwrap();
+        assert_eq!(kids.key_name.as_deref(), Some("family"));
+        assert_eq!(kids.profile_name.as_deref(), Some("kids"));
+        assert_eq!(registry.resolve(Some("sk_lab")).unwrap().profile_name.as_deref(), Some("research"));
+        assert!(!kids.admin);
+        assert!(registry.resolve(Some("sk_ops")).unwrap().admin);
+        assert_ne!(kids.principal(), registry.resolve(Some("sk_ops")).unwrap().principal());
+
+        assert_eq!(registry.resolve(Some("sk_wrong")).unwrap_err(), AuthError::InvalidKey);
+        assert_eq!(registry.resolve(Some("sk_old")).unwrap_err(), AuthError::InvalidKey);
+
+        // 未要求认证时匿名请求不受限制
+        assert!(registry.resolve(None).unwrap().profile.is_none());
+    }
+
+    #[test]
+    fn test_require_key_and_default_profile() {
+        let mut config = config();
+        config.default_profile = Some("kids".to_string());
+        let registry = ProfileRegistry::from_config(&config).unwrap();
+        assert_eq!(registry.resolve(None).unwrap().profile_name.as_deref(), Some("kids"));
+
+        config.auth.enabled = true;
+        config.auth.auth_type = AuthType::ApiKey;
+        let registry = ProfileRegistry::from_config(&config).unwrap();
+        assert_eq!(registry.resolve(None).unwrap_err(), AuthError::MissingKey);
+
+        config.default_profile = Some("missing".to_string());
+        assert!(ProfileRegistry::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_extract_key() {
+        let registry = ProfileRegistry::default();
+        let mut headers = HeaderMap::new();
+        assert_eq!(registry.extract_key(&headers, Some("q=rust&api_key=sk_1")).as_deref(), Some("sk_1"));
+        headers.insert("x-api-key", "sk_2".parse().unwrap());
+        assert_eq!(registry.extract_key(&headers, Some("api_key=sk_1")).as_deref(), Some("sk_2"));
+        assert_eq!(registry.extract_key(&HeaderMap::new(), None), None);
+    }
+
+    #[test]
+    fn test_profile_restrictions() {
+        let registry = ProfileRegistry::from_config(&config()).unwrap();
+        let kids = registry.resolve(Some("sk_kids")).unwrap();
+
+        let engines = vec!["google".to_string(), "bing".to_string()];
+        assert_eq!(kids.restrict_engines(engines.clone(), true).unwrap(), vec!["bing".to_string()]);
+        assert_eq!(kids.restrict_engines(engines.clone(), false).unwrap(), vec!["bing", "wikipedia"]);
+        assert!(kids.restrict_engines(vec!["google".to_string()], true).is_err());
+
+        let mut query = SearchQuery::default();
+        kids.apply_safe_search(&mut query);
+        assert!(matches!(query.safe_search, SafeSearchLevel::Strict));
+
+        assert!(kids.url_rewrite(&UrlRewriteConfig::default()).enabled);
+        assert!(!kids.image_proxy(&ImageProxyConfig::default()).enabled);
+
+        let research = registry.resolve(Some("sk_lab")).unwrap();
+        assert_eq!(research.restrict_engines(engines.clone(), true).unwrap(), engines);
+    }
+
+    #[test]
+    fn test_client_address() {
+        let mut config = config();
+        config.trusted_proxies = vec!["10.0.0.1".parse().unwrap()];
+        let registry = ProfileRegistry::from_config(&config).unwrap();
+        let ip = |s: &str| Some(s.parse::<IpAddr>().unwrap());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(X_FORWARDED_FOR, "6.6.6.6, 203.0.113.7, 10.0.0.1".parse().unwrap());
+
+        // 直连的客户端不能通过请求头冒充其他地址
+        assert_eq!(registry.client_address(ip("198.51.100.2"), &headers), ip("198.51.100.2"));
+        // 受信任的代理：取最右侧不受信任的地址
+        assert_eq!(registry.client_address(ip("10.0.0.1"), &headers), ip("203.0.113.7"));
+        // Unix 域套接字
+        assert_eq!(registry.client_address(None, &headers), ip("203.0.113.7"));
+        assert_eq!(registry.client_address(ip("10.0.0.1"), &HeaderMap::new()), ip("10.0.0.1"));
+        assert_eq!(registry.client_address(None, &HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_anonymous_rate_limit_by_address() {
+        let limiter = ClientRateLimiter::new();
+        let client = |address: &str| ApiClient {
+            address: Some(address.parse().unwrap()),
+            ..ApiClient::default()
+        };
+
+        let first = client("203.0.113.7");
+        assert!(limiter.check(&first.rate_limit_key(), 1).is_ok());
+        assert!(limiter.check(&first.rate_limit_key(), 1).is_err());
+        // 其他匿名调用方不受影响
+        assert!(limiter.check(&client("203.0.113.8").rate_limit_key(), 1).is_ok());
+
+        // 同一 /64 网段的 IPv6 地址共享限额
+        assert!(limiter.check(&client("2001:db8:1:2::1").rate_limit_key(), 1).is_ok());
+        assert!(limiter.check(&client("2001:db8:1:2::ffff").rate_limit_key(), 1).is_err());
+        assert!(limiter.check(&client("2001:db8:1:3::1").rate_limit_key(), 1).is_ok());
+
+        // 携带密钥的请求按密钥计数
+        let keyed = ApiClient {
+            key_name: Some("family".to_string()),
+            ..first
+        };
+        assert_eq!(keyed.rate_limit_key(), "family");
+        assert_eq!(ApiClient::default().rate_limit_key(), ANONYMOUS_CLIENT);
+    }
 
     #[test]
     fn test_auth_config_default() {
@@ -20,8 +20,10 @@ pub mod cors;
 pub mod ratelimit;
 pub mod logging;
 pub mod auth;
+pub mod request_id;
 
 pub use cors::*;
 pub use ratelimit::*;
 pub use logging::*;
 pub use auth::*;
+pub use request_id::*;
@@ -16,8 +16,15 @@
 //!
 //! 提供 API 请求速率限制功能
 
-// Placeholder for future rate limiting implementation
-// Will use governor or similar crate for production use
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 限流窗口长度
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// 超过该数量的调用方时清理过期窗口
+const PRUNE_THRESHOLD: usize = 1024;
 
 /// 限流配置
 #[derive(Debug, Clone)]
@@ -38,10 +45,67 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// 按调用方计数的每分钟固定窗口限流器
+///
+/// 调用方由 API 密钥名称标识，上限来自其绑定的配置档
+#[derive(Debug, Default)]
+pub struct ClientRateLimiter {
+    /// 调用方 → (窗口起点, 窗口内请求数)
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl ClientRateLimiter {
+    /// 创建限流器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次请求
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - 调用方标识
+    /// * `per_minute` - 每分钟请求数上限
+    ///
+    /// # Returns
+    ///
+    /// 未超限返回 `Ok`，超限时返回距窗口重置的剩余时间
+    pub fn check(&self, client: &str, per_minute: u32) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        if windows.len() > PRUNE_THRESHOLD {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < WINDOW);
+        }
+
+        let (start, count) = windows.entry(client.to_string()).or_insert((now, 0));
+        if now.duration_since(*start) >= WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= per_minute {
+            return Err(WINDOW.saturating_sub(now.duration_since(*start)));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_client_rate_limiter() {
+        let limiter = ClientRateLimiter::new();
+        assert!(limiter.check("kids", 2).is_ok());
+        assert!(limiter.check("kids", 2).is_ok());
+        let retry_after = limiter.check("kids", 2).unwrap_err();
+        assert!(retry_after <= WINDOW);
+
+        // 不同调用方独立计数
+        assert!(limiter.check("research", 2).is_ok());
+    }
+
     #[test]
     fn test_rate_limit_config_default() {
         let config = RateLimitConfig::default();
@@ -0,0 +1,66 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 请求 ID 中间件
+//!
+//! 为每个请求分配请求 ID（优先采用调用方的 `X-Request-Id` 头），在携带该 ID 的
+//! tracing span 中处理请求，并通过响应头返回
+
+use axum::{
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+
+use crate::api::on::ApiState;
+use crate::search::{accept_request_id, generate_request_id, request_span, REQUEST_ID_HEADER};
+
+/// 当前请求的请求 ID（由 [`assign_request_id`] 放入请求扩展）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// 分配请求 ID 的中间件
+///
+/// 调用方提供的合法 ID 原样沿用，否则生成新 ID；关闭 `include_request_id` 时
+/// 日志仍带有请求 ID，但响应中不返回
+///
+/// # Arguments
+///
+/// * `state` - API 状态
+/// * `request` - HTTP 请求
+/// * `next` - 下一个中间件
+///
+/// # Returns
+///
+/// 返回 HTTP 响应
+pub async fn assign_request_id(State(state): State<ApiState>, mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(accept_request_id)
+        .unwrap_or_else(generate_request_id);
+    request.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let span = request_span(&request_id);
+    let mut response = next.run(request).instrument(span).await;
+    if state.include_request_id
+        && let Ok(value) = HeaderValue::from_str(&request_id)
+    {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
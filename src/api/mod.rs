@@ -16,11 +16,18 @@
 //!
 //! 提供高层次的外部 HTTP API 接口，用于公开搜索引擎的功能。
 //! 所有 API 都是经过高度封装的，便于外部集成。
+//! 启用 `grpc` 特性时另外提供 gRPC 服务接口。
 
 pub mod types;
+pub mod formatter;
 pub mod on;
+pub mod listener;
 pub mod handlers;
 pub mod middleware;
+pub mod signing;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 
 pub use types::*;
 pub use on::*;
+pub use listener::ApiListener;
@@ -20,19 +20,27 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use axum::{
     Router,
-    routing::{get, post},
-    extract::{State, Query, Json},
+    routing::{delete, get, post},
+    extract::{Extension, State, Query, Json},
     response::{IntoResponse, Response},
     http::StatusCode,
 };
 use serde_json::json;
 
 use crate::cache::CacheInterface;
+use crate::cache::{AlertStore, AuditEntry, AuditLog, HistoryEntry, HistoryStore, ImageCache, PreferenceStore};
+use crate::config::SeeSeaConfig;
+use crate::config::privacy::{ClickTrackingConfig, ImageProxyConfig, UrlRewriteConfig};
+use crate::config::api::MetricsConfig;
+use crate::config::web::WebConfig;
+use crate::web::{Templates, TEMPLATE_DIRECTORY};
 use crate::net::NetworkInterface;
 use crate::search::{SearchInterface, SearchRequest};
 use super::types::*;
-use super::handlers::{rss, cache};
-use super::middleware::cors;
+use super::handlers::{rss, cache, redirect, click, image_proxy, map, feed, alerts, preferences, sessions, history, audit, ws, health, metrics};
+use super::middleware::{auth, cors, request_id, ApiClient, ClientRateLimiter, ProfileRegistry, RequestId};
+use super::listener::ApiListener;
+use super::signing::LinkSigner;
 
 /// 服务器配置
 #[derive(Debug, Clone)]
@@ -45,6 +53,12 @@ pub struct ServerConfig {
     pub cors_origins: Vec<String>,
     /// 是否启用日志
     pub enable_logging: bool,
+    /// Unix 域套接字路径（设置后替代 host/port 监听）
+    pub unix_socket: Option<std::path::PathBuf>,
+    /// Unix 域套接字文件权限（None 时沿用 umask）
+    pub unix_socket_mode: Option<u32>,
+    /// 使用 systemd 套接字激活传入的已绑定监听
+    pub socket_activation: bool,
 }
 
 impl Default for ServerConfig {
@@ -54,10 +68,39 @@ impl Default for ServerConfig {
             port: 8080,
             cors_origins: vec!["*".to_string()],
             enable_logging: true,
+            unix_socket: None,
+            unix_socket_mode: None,
+            socket_activation: false,
         }
     }
 }
 
+impl ServerConfig {
+    /// 从应用主配置构建服务器配置
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - 加载后的 SeeSea 主配置
+    ///
+    /// # Returns
+    ///
+    /// 返回监听方式与 CORS 源取自配置文件的服务器配置
+    pub fn from_app_config(config: &SeeSeaConfig) -> Self {
+        Self {
+            host: config.server.bind_address.clone(),
+            port: config.server.port,
+            cors_origins: config.api.cors.allowed_origins.clone(),
+            enable_logging: true,
+            unix_socket: config.server.unix_socket.clone(),
+            unix_socket_mode: config.server.unix_socket_mode,
+            socket_activation: config.server.socket_activation,
+        }
+    }
+}
+
+/// `/api/stats` 中返回的点击最多域名数
+const STATS_TOP_DOMAINS: usize = 20;
+
 /// API 服务状态
 #[derive(Clone)]
 pub struct ApiState {
@@ -65,6 +108,54 @@ pub struct ApiState {
     pub search: Arc<SearchInterface>,
     /// 版本信息
     pub version: String,
+    /// 结果链接重写配置
+    pub url_rewrite: Arc<UrlRewriteConfig>,
+    /// 缩略图代理配置
+    pub image_proxy: Arc<ImageProxyConfig>,
+    /// 跳转、点击与缩略图代理链接的签名器
+    pub link_signer: Arc<LinkSigner>,
+    /// 结果点击统计配置
+    pub click_tracking: Arc<ClickTrackingConfig>,
+    /// 缩略图缓存（可选）
+    pub image_cache: Option<ImageCache>,
+    /// 保存的搜索与告警存储（未设置时相关端点返回 503）
+    pub alerts: Option<AlertStore>,
+    /// 用户偏好存储（未设置时相关端点返回 503）
+    pub preferences: Option<PreferenceStore>,
+    /// 加密的搜索历史（未启用时不记录，相关端点返回 503）
+    pub history: Option<HistoryStore>,
+    /// 审计日志（未设置时不记录，审计端点返回 503）
+    pub audit: Option<AuditLog>,
+    /// 缓存接口（用于健康检查，未设置时跳过缓存检查）
+    pub cache: Option<CacheInterface>,
+    /// 应用主配置（用于健康检查，未设置时跳过配置检查）
+    pub app_config: Option<Arc<SeeSeaConfig>>,
+    /// API 密钥与租户配置档
+    pub profiles: Arc<ProfileRegistry>,
+    /// 按调用方的限流器
+    pub rate_limiter: Arc<ClientRateLimiter>,
+    /// 内置网页界面配置
+    pub web: Arc<WebConfig>,
+    /// 内置网页界面模板
+    pub web_templates: Arc<Templates>,
+    /// Prometheus 指标端点配置
+    pub metrics: Arc<MetricsConfig>,
+    /// 是否在响应中返回请求 ID（对应配置 `api.response_format.include_request_id`）
+    pub include_request_id: bool,
+}
+
+impl ApiState {
+    /// 在后台追加一条审计记录（未设置审计日志时忽略，写入失败只记录日志）
+    pub fn audit(&self, entry: AuditEntry) {
+        let Some(audit) = self.audit.clone() else {
+            return;
+        };
+        tokio::spawn(async move {
+            if let Err(e) = audit.record_async(entry).await {
+                tracing::warn!("写入审计记录失败: {}", e);
+            }
+        });
+    }
 }
 
 /// API 接口
@@ -89,10 +180,151 @@ impl ApiInterface {
             state: ApiState {
                 search,
                 version,
+                url_rewrite: Arc::new(UrlRewriteConfig::default()),
+                image_proxy: Arc::new(ImageProxyConfig::default()),
+                link_signer: Arc::new(LinkSigner::random()),
+                click_tracking: Arc::new(ClickTrackingConfig::default()),
+                image_cache: None,
+                alerts: None,
+                preferences: None,
+                history: None,
+                audit: None,
+                cache: None,
+                app_config: None,
+                profiles: Arc::new(ProfileRegistry::default()),
+                rate_limiter: Arc::new(ClientRateLimiter::new()),
+                web: Arc::new(WebConfig::default()),
+                web_templates: Arc::new(Templates::builtin()),
+                metrics: Arc::new(MetricsConfig::default()),
+                include_request_id: true,
             },
         }
     }
 
+    /// 设置结果链接重写配置
+    ///
+    /// 启用后搜索结果中的链接会改写为经 `/r` 端点跳转
+    pub fn with_url_rewrite(mut self, config: UrlRewriteConfig) -> Self {
+        self.state.url_rewrite = Arc::new(config);
+        self
+    }
+
+    /// 设置缩略图代理
+    ///
+    /// 启用后结果缩略图改写为经 `/proxy/image` 获取，`cache` 用于缓存已获取的图片
+    pub fn with_image_proxy(mut self, config: ImageProxyConfig, cache: Option<ImageCache>) -> Self {
+        self.state.image_proxy = Arc::new(config);
+        self.state.image_cache = cache;
+        self
+    }
+
+    /// 设置链接签名器
+    ///
+    /// `/r`、`/click` 与 `/proxy/image` 只接受由该签名器签名的目标；未设置时使用随机密钥
+    pub fn with_link_signer(mut self, signer: LinkSigner) -> Self {
+        self.state.link_signer = Arc::new(signer);
+        self
+    }
+
+    /// 设置结果点击统计
+    ///
+    /// 启用后结果链接改写为经 `/click` 端点跳转，并累计各引擎与目标域名的点击数
+    pub fn with_click_tracking(mut self, config: ClickTrackingConfig) -> Self {
+        self.state.click_tracking = Arc::new(config);
+        self
+    }
+
+    /// 设置保存的搜索与告警存储
+    ///
+    /// 启用 `/api/saved-searches` 与 `/api/alerts` 管理端点
+    pub fn with_alerts(mut self, store: AlertStore) -> Self {
+        self.state.alerts = Some(store);
+        self
+    }
+
+    /// 设置用户偏好存储
+    ///
+    /// 启用 `/api/preferences` 端点
+    pub fn with_preferences(mut self, store: PreferenceStore) -> Self {
+        self.state.preferences = Some(store);
+        self
+    }
+
+    /// 设置搜索历史存储
+    ///
+    /// 启用后搜索查询与经 `/r` 的点击会被加密记录，并启用 `/api/history` 端点
+    pub fn with_history(mut self, store: HistoryStore) -> Self {
+        self.state.history = Some(store);
+        self
+    }
+
+    /// 设置审计日志
+    ///
+    /// 记录认证失败、超出限流与管理操作，管理员密钥可通过 `/api/audit` 查询
+    pub fn with_audit(mut self, audit: AuditLog) -> Self {
+        self.state.audit = Some(audit);
+        self
+    }
+
+    /// 设置缓存接口
+    ///
+    /// `/healthz` 与 `/readyz` 据此检查缓存数据库是否可写
+    pub fn with_cache(mut self, cache: CacheInterface) -> Self {
+        self.state.cache = Some(cache);
+        self
+    }
+
+    /// 设置应用主配置
+    ///
+    /// `/healthz` 与 `/readyz` 据此检查配置有效性
+    pub fn with_config(mut self, config: SeeSeaConfig) -> Self {
+        self.state.app_config = Some(Arc::new(config));
+        self
+    }
+
+    /// 设置 API 密钥与租户配置档
+    ///
+    /// 请求按携带的 API 密钥绑定配置档，限制可用引擎、安全搜索级别、隐私设置与请求频率
+    pub fn with_profiles(mut self, profiles: ProfileRegistry) -> Self {
+        self.state.profiles = Arc::new(profiles);
+        self
+    }
+
+    /// 设置内置网页界面
+    ///
+    /// 启用时在 `/` 提供内置搜索页面
+    pub fn with_web(mut self, config: WebConfig) -> Self {
+        self.state.web = Arc::new(config);
+        self
+    }
+
+    /// 设置内置网页界面模板
+    pub fn with_web_templates(mut self, templates: Templates) -> Self {
+        self.state.web_templates = Arc::new(templates);
+        self
+    }
+
+    /// 设置 Prometheus 指标端点
+    ///
+    /// 启用时在 `config.path` 以 Prometheus 文本格式导出引擎与缓存指标
+    pub fn with_metrics(mut self, config: MetricsConfig) -> Self {
+        self.state.metrics = Arc::new(config);
+        self
+    }
+
+    /// 设置是否在响应中返回请求 ID
+    ///
+    /// 启用时搜索响应带有 `request_id` 字段，所有响应带有 `X-Request-Id` 头；关闭时日志仍记录请求 ID
+    pub fn with_request_id(mut self, include: bool) -> Self {
+        self.state.include_request_id = include;
+        self
+    }
+
+    /// 获取搜索接口
+    pub fn search(&self) -> &Arc<SearchInterface> {
+        &self.state.search
+    }
+
     /// 从配置创建 API 接口
     ///
     /// # Arguments
@@ -113,19 +345,58 @@ impl ApiInterface {
         Ok(Self::new(search, env!("CARGO_PKG_VERSION").to_string()))
     }
 
+    /// 从应用主配置创建 API 接口
+    ///
+    /// 搜索配置由 [`SearchConfig::from_app_config`](crate::search::SearchConfig::from_app_config) 生成，
+    /// 链接重写与缩略图代理取自 `privacy` 段，内置网页界面取自 `web` 段，
+    /// 网页模板从配置目录下的 `templates/` 加载，指标端点取自 `api.metrics` 段
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - 加载后的 SeeSea 主配置
+    /// * `cache` - 缓存接口（用于缓存代理的缩略图，可选）
+    ///
+    /// # Returns
+    ///
+    /// 返回 API 接口实例或错误
+    pub fn from_app_config(
+        config: &SeeSeaConfig,
+        cache: Option<&CacheInterface>,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let search_config = crate::search::SearchConfig::from_app_config(config);
+        let search = Arc::new(SearchInterface::new(search_config)?);
+        let api = Self::new(search, env!("CARGO_PKG_VERSION").to_string())
+            .with_profiles(ProfileRegistry::from_config(&config.api)?)
+            .with_url_rewrite(config.privacy.url_rewrite.clone())
+            .with_image_proxy(config.privacy.image_proxy.clone(), cache.map(CacheInterface::images))
+            .with_click_tracking(config.privacy.click_tracking.clone())
+            .with_link_signer(LinkSigner::from_secret(config.privacy.link_signing_key.as_deref()))
+            .with_web(config.web.clone())
+            .with_web_templates(Templates::load(&config.general.config_directory.join(TEMPLATE_DIRECTORY)))
+            .with_metrics(config.api.metrics.clone())
+            .with_request_id(config.api.response_format.include_request_id)
+            .with_config(config.clone());
+        Ok(match cache {
+            Some(cache) => api.with_cache(cache.clone()),
+            None => api,
+        })
+    }
+
     /// 构建 Axum 路由器
     ///
     /// # Returns
     ///
     /// 返回配置好的 Axum Router
     pub fn build_router(&self) -> Router {
-        Router::new()
+        let router = Router::new()
             // 搜索相关路由
             .route("/api/search", get(handle_search))
             .route("/api/search", post(handle_search_post))
             
             // 引擎信息路由
             .route("/api/engines", get(handle_engines_list))
+            .route("/engines", get(handle_engine_catalog))
+            .route("/api/engines/stats", get(handle_engines_stats))
             
             // RSS 相关路由
             .route("/api/rss/feeds", get(rss::handle_rss_feeds_list))
@@ -137,6 +408,41 @@ impl ApiInterface {
             .route("/api/cache/stats", get(cache::handle_cache_stats))
             .route("/api/cache/clear", post(cache::handle_cache_clear))
             .route("/api/cache/cleanup", post(cache::handle_cache_cleanup))
+
+            // 保存的搜索与告警路由
+            .route("/api/saved-searches", get(alerts::handle_saved_searches_list))
+            .route("/api/saved-searches", post(alerts::handle_saved_search_create))
+            .route("/api/saved-searches/{id}", delete(alerts::handle_saved_search_delete))
+            .route("/api/alerts", get(alerts::handle_alerts_list))
+            .route("/api/alerts/{id}", delete(alerts::handle_alert_delete))
+
+            // 搜索历史路由
+            .route(
+                "/api/history",
+                get(history::handle_history_list)
+                    .post(history::handle_history_record)
+                    .delete(history::handle_history_purge),
+            )
+            .route("/api/history/{id}", delete(history::handle_history_delete))
+
+            // 审计日志路由（仅限管理员密钥）
+            .route(audit::AUDIT_PATH, get(audit::handle_audit_list))
+
+            // 用户偏好路由
+            .route("/api/preferences", post(preferences::handle_preferences_create))
+            .route(
+                "/api/preferences/{token}",
+                get(preferences::handle_preferences_get)
+                    .put(preferences::handle_preferences_update)
+                    .delete(preferences::handle_preferences_delete),
+            )
+
+            // 搜索会话路由
+            .route("/api/sessions", post(sessions::handle_session_create))
+            .route(
+                "/api/sessions/{token}",
+                get(sessions::handle_session_get).delete(sessions::handle_session_delete),
+            )
             
             // 统计信息路由
             .route("/api/stats", get(handle_stats))
@@ -144,10 +450,49 @@ impl ApiInterface {
             // 健康检查路由
             .route("/api/health", get(handle_health))
             .route("/health", get(handle_health))
+            .route(health::HEALTHZ_PATH, get(health::handle_healthz))
+            .route(health::READYZ_PATH, get(health::handle_readyz))
             
             // 版本信息路由
             .route("/api/version", get(handle_version))
-            
+
+            // 结果链接跳转路由
+            .route(redirect::REDIRECT_PATH, get(redirect::handle_redirect))
+            .route(click::CLICK_PATH, get(click::handle_click))
+
+            // 缩略图代理路由
+            .route(image_proxy::IMAGE_PROXY_PATH, get(image_proxy::handle_image_proxy))
+
+            // 地图搜索路由（GeoJSON）
+            .route(map::MAP_SEARCH_PATH, get(map::handle_map_search))
+
+            // 搜索订阅 feed（RSS/Atom）
+            .route(feed::FEED_SEARCH_PATH, get(feed::handle_search_feed))
+
+            // WebSocket 流式搜索路由
+            .route(ws::WS_PATH, get(ws::handle_ws));
+
+        // Prometheus 指标
+        let router = if self.state.metrics.enabled {
+            router.route(&self.state.metrics.path, get(metrics::handle_metrics))
+        } else {
+            router
+        };
+
+        // 内置网页搜索界面
+        let router = if self.state.web.enabled {
+            router.merge(crate::web::router())
+        } else {
+            router
+        };
+
+        router
+            // 识别 API 密钥并应用租户配置档的限流
+            .layer(axum::middleware::from_fn_with_state(self.state.clone(), auth::authenticate))
+
+            // 分配请求 ID，请求在携带该 ID 的 tracing span 中处理
+            .layer(axum::middleware::from_fn_with_state(self.state.clone(), request_id::assign_request_id))
+
             // 应用 CORS 中间件
             .layer(cors::create_cors_layer())
             
@@ -164,11 +509,75 @@ impl ApiInterface {
     ///
     /// 返回结果
     pub async fn serve(&self, config: ServerConfig) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let listener = ApiListener::bind(&config).await?;
+        self.serve_listener(listener, std::future::pending()).await
+    }
+
+    /// 在已绑定的监听器上启动服务器，并在 `shutdown` 完成时优雅停机
+    ///
+    /// 停机信号到达后不再接受新连接，等待进行中的请求处理完毕再返回
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - 已绑定的 TCP 监听器（调用方可先读取实际绑定地址）
+    /// * `shutdown` - 停机信号
+    ///
+    /// # Returns
+    ///
+    /// 返回结果
+    pub async fn serve_with_shutdown<F>(
+        &self,
+        listener: tokio::net::TcpListener,
+        shutdown: F,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.serve_listener(ApiListener::Tcp(listener), shutdown).await
+    }
+
+    /// 在 TCP 或 Unix 域套接字监听器上启动服务器，并在 `shutdown` 完成时优雅停机
+    ///
+    /// 停机后删除本进程创建的 Unix 域套接字文件
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - 已绑定的监听器
+    /// * `shutdown` - 停机信号
+    ///
+    /// # Returns
+    ///
+    /// 返回结果
+    pub async fn serve_listener<F>(
+        &self,
+        listener: ApiListener,
+        shutdown: F,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
         let app = self.build_router();
-        let addr = format!("{}:{}", config.host, config.port);
-        let listener = tokio::net::TcpListener::bind(&addr).await?;
-        axum::serve(listener, app).await?;
-        
+        match listener {
+            ApiListener::Tcp(listener) => {
+                // 记录对端地址，供匿名请求按客户端地址限流
+                axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>())
+                    .with_graceful_shutdown(shutdown)
+                    .await?;
+            }
+            #[cfg(unix)]
+            ApiListener::Unix { listener, path } => {
+                let served = axum::serve(listener, app)
+                    .with_graceful_shutdown(shutdown)
+                    .await;
+                if let Some(path) = path
+                    && let Err(e) = std::fs::remove_file(&path)
+                {
+                    tracing::warn!("Failed to remove unix socket {}: {}", path.display(), e);
+                }
+                served?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -176,10 +585,12 @@ impl ApiInterface {
 /// 处理 GET 搜索请求
 async fn handle_search(
     State(state): State<ApiState>,
+    Extension(client): Extension<ApiClient>,
+    Extension(request_id): Extension<RequestId>,
     Query(params): Query<ApiSearchRequest>,
 ) -> Response {
-    match execute_search(&state, params).await {
-        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+    match execute_search(&state, &client, params).await {
+        Ok(response) => (StatusCode::OK, Json(with_request_id(&state, response, request_id))).into_response(),
         Err(e) => {
             let error = ApiErrorResponse {
                 code: "SEARCH_ERROR".to_string(),
@@ -194,10 +605,12 @@ async fn handle_search(
 /// 处理 POST 搜索请求
 async fn handle_search_post(
     State(state): State<ApiState>,
+    Extension(client): Extension<ApiClient>,
+    Extension(request_id): Extension<RequestId>,
     Json(params): Json<ApiSearchRequest>,
 ) -> Response {
-    match execute_search(&state, params).await {
-        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+    match execute_search(&state, &client, params).await {
+        Ok(response) => (StatusCode::OK, Json(with_request_id(&state, response, request_id))).into_response(),
         Err(e) => {
             let error = ApiErrorResponse {
                 code: "SEARCH_ERROR".to_string(),
@@ -209,62 +622,149 @@ async fn handle_search_post(
     }
 }
 
+/// 按配置在搜索响应中附上请求 ID
+fn with_request_id(state: &ApiState, mut response: ApiSearchResponse, request_id: RequestId) -> ApiSearchResponse {
+    if state.include_request_id {
+        response.request_id = Some(request_id.0);
+    }
+    response
+}
+
 /// 执行搜索
-async fn execute_search(
+pub(crate) async fn execute_search(
     state: &ApiState,
+    client: &ApiClient,
     params: ApiSearchRequest,
 ) -> Result<ApiSearchResponse, Box<dyn std::error::Error + Send + Sync>> {
     let start_time = std::time::Instant::now();
+    let request = build_search_request(&params, client)?;
 
+    // 执行搜索
+    let response = state.search.search(&request).await?;
+
+    if let Some(history) = state.history.as_ref() {
+        let entry = HistoryEntry::query(&request.query.query, response.engines_used.clone());
+        if let Err(e) = history.record_async(entry).await {
+            tracing::warn!("记录搜索历史失败: {}", e);
+        }
+    }
+
+    Ok(build_api_response(state, client, &params, response, start_time.elapsed().as_millis() as u64))
+}
+
+/// 将 API 搜索参数转换为内部搜索请求（按调用方的配置档限制引擎与安全搜索级别）
+pub(crate) fn build_search_request(
+    params: &ApiSearchRequest,
+    client: &ApiClient,
+) -> Result<SearchRequest, Box<dyn std::error::Error + Send + Sync>> {
     // 转换为内部搜索查询
-    let search_query = params.to_search_query()
+    let mut search_query = params.to_search_query()
         .map_err(|e| format!("参数错误: {}", e))?;
+    client.apply_safe_search(&mut search_query);
 
     // 获取引擎列表
-    let engines = params.get_engines();
+    let explicit = params.china_mode || params.engines.is_some();
+    let engines = client.restrict_engines(params.get_engines(), explicit)?;
+
+    if let Some(token) = &params.session {
+        crate::cache::validate_session_token(token).map_err(|e| format!("参数错误: {}", e))?;
+    }
 
     // 创建搜索请求
-    let request = SearchRequest {
+    Ok(SearchRequest {
         query: search_query,
         engines,
         timeout: None,
         max_results: None,
         force: false,
         cache_timeline: Some(3600),
-    };
+        translate_to: params.translate_to.clone(),
+        highlight: params.highlight,
+        debug_capture: params.debug_capture,
+        max_latency_ms: params.max_latency_ms,
+        explain: params.explain,
+        session: params.session.clone(),
+    })
+}
 
-    // 执行搜索
-    let response = state.search.search(&request).await?;
-    
+/// 转换单个引擎的结果条目（按配置与调用方的配置档改写链接与缩略图）
+pub(crate) fn build_api_items(
+    state: &ApiState,
+    client: &ApiClient,
+    search_result: &crate::derive::SearchResult,
+) -> Vec<ApiSearchResultItem> {
+    let url_rewrite = client.url_rewrite(&state.url_rewrite);
+    let image_proxy = client.image_proxy(&state.image_proxy);
+    search_result
+        .items
+        .iter()
+        .map(|item| ApiSearchResultItem {
+            title: item.title.clone(),
+            url: if state.click_tracking.enabled {
+                let engine = item
+                    .metadata
+                    .get(crate::search::ENGINE_METADATA_KEY)
+                    .unwrap_or(&search_result.engine_name);
+                click::click_url(&item.url, engine, url_rewrite.base_url.as_deref(), &state.link_signer)
+            } else {
+                redirect::rewrite_result_url(&item.url, &url_rewrite, &state.link_signer)
+            },
+            description: Some(item.content.clone()),
+            thumbnail: item
+                .thumbnail
+                .as_deref()
+                .map(|t| image_proxy::proxy_image_url(t, &image_proxy, &state.link_signer)),
+            engine: search_result.engine_name.clone(),
+            score: Some(item.score),
+            explain: crate::search::explanation(item),
+        })
+        .collect()
+}
+
+/// 将内部搜索响应转换为 API 响应
+pub(crate) fn build_api_response(
+    state: &ApiState,
+    client: &ApiClient,
+    params: &ApiSearchRequest,
+    response: crate::search::SearchResponse,
+    elapsed_ms: u64,
+) -> ApiSearchResponse {
     // 转换结果
     let mut results = Vec::new();
+    let mut warnings = Vec::new();
+    let mut debug = None;
     for search_result in &response.results {
-        for item in &search_result.items {
-            results.push(ApiSearchResultItem {
-                title: item.title.clone(),
-                url: item.url.clone(),
-                description: Some(item.content.clone()),
-                engine: search_result.engine_name.clone(),
-                score: Some(item.score),
-            });
+        if let Some(warning) = search_result.metadata.get(crate::search::QUERY_WARNING_KEY) {
+            warnings.push(warning.clone());
+        }
+        if params.debug_capture {
+            debug = debug.or_else(|| crate::search::debug_capture::debug_captures(search_result));
         }
+        results.extend(build_api_items(state, client, search_result));
     }
-    
-    let elapsed = start_time.elapsed().as_millis() as u64;
+    image_proxy::spawn_prefetch(state, client, &response.results);
 
     // 获取实际的查询字符串
     let query_text = params.get_query().unwrap_or_default();
 
-    Ok(ApiSearchResponse {
+    ApiSearchResponse {
         query: query_text,
         results,
         total_count: response.total_count,
         page: params.page,
         page_size: params.page_size,
         engines_used: response.engines_used,
-        query_time_ms: elapsed,
+        query_time_ms: elapsed_ms,
         cached: response.cached,
-    })
+        warnings,
+        answer: response.answer,
+        suggestions: response.suggestions,
+        related_queries: response.related_queries,
+        debug,
+        per_engine: response.per_engine,
+        partial: response.partial,
+        request_id: response.request_id,
+    }
 }
 
 /// 处理引擎列表请求
@@ -275,24 +775,47 @@ async fn handle_engines_list(
     
     let engine_infos: Vec<ApiEngineInfo> = engines
         .into_iter()
-        .map(|name| ApiEngineInfo {
-            name: name.clone(),
-            description: format!("{} 搜索引擎", name),
-            engine_type: "general".to_string(),
-            enabled: true,
-            capabilities: vec!["web".to_string()],
+        .map(|name| {
+            let inactive_reason = state.search.inactive_reason(&name).map(str::to_string);
+            ApiEngineInfo {
+                description: format!("{} 搜索引擎", name),
+                engine_type: "general".to_string(),
+                enabled: inactive_reason.is_none(),
+                inactive_reason,
+                capabilities: vec!["web".to_string()],
+                name,
+            }
         })
         .collect();
     
     (StatusCode::OK, Json(engine_infos)).into_response()
 }
 
+/// 处理引擎能力目录请求（供前端动态构建引擎选择器）
+async fn handle_engine_catalog(
+    State(state): State<ApiState>,
+) -> Response {
+    let catalog = state.search.engine_catalog().await;
+    (StatusCode::OK, Json(catalog)).into_response()
+}
+
+/// 处理引擎统计报告请求
+async fn handle_engines_stats(
+    State(state): State<ApiState>,
+) -> Response {
+    let report = state.search.engine_report().await;
+    (StatusCode::OK, Json(report)).into_response()
+}
+
 /// 处理统计信息请求
 async fn handle_stats(
     State(state): State<ApiState>,
 ) -> Response {
     let stats = state.search.get_stats().await;
-    let api_stats = ApiStatsResponse::from_search_stats(&stats);
+    let mut api_stats = ApiStatsResponse::from_search_stats(&stats);
+    if state.click_tracking.enabled {
+        api_stats.clicks = Some(state.search.click_report(STATS_TOP_DOMAINS).await);
+    }
 
     (StatusCode::OK, Json(api_stats)).into_response()
 }
@@ -333,6 +856,32 @@ mod tests {
     use crate::net::types::NetworkConfig;
     use crate::cache::types::CacheImplConfig;
 
+    #[tokio::test]
+    async fn test_request_id_header() {
+        use crate::search::REQUEST_ID_HEADER;
+
+        let search = Arc::new(SearchInterface::new(SearchConfig::default()).unwrap());
+        let api = ApiInterface::new(search, "test".to_string());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/health", listener.local_addr().unwrap());
+        let (shutdown, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            api.serve_with_shutdown(listener, async {
+                let _ = stopped.await;
+            })
+            .await
+        });
+
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let response = client.get(&url).header(REQUEST_ID_HEADER, "trace-42").send().await.unwrap();
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "trace-42");
+        let response = client.get(&url).header(REQUEST_ID_HEADER, "bad id").send().await.unwrap();
+        assert_eq!(response.headers()[REQUEST_ID_HEADER].len(), 16);
+
+        shutdown.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+
     #[tokio::test]
     async fn test_api_interface_creation() {
         let search_config = SearchConfig::default();
@@ -355,4 +904,119 @@ mod tests {
         let _router = api.build_router();
         // Router is built successfully
     }
+
+    #[test]
+    fn test_api_with_url_rewrite() {
+        let search = Arc::new(
+            SearchInterface::new(SearchConfig::default()).unwrap()
+        );
+        let rewrite = UrlRewriteConfig {
+            enabled: true,
+            ..Default::default()
+        };
+
+        let api = ApiInterface::new(search, "0.1.0".to_string()).with_url_rewrite(rewrite);
+        assert!(api.state.url_rewrite.enabled);
+    }
+
+    #[tokio::test]
+    async fn test_redirect_requires_signature() {
+        use axum::extract::{Query, State};
+        use super::redirect::{handle_redirect, RedirectParams};
+        use super::click::{handle_click, ClickParams};
+
+        let search = Arc::new(
+            SearchInterface::new(SearchConfig::default()).unwrap()
+        );
+        let api = ApiInterface::new(search, "0.1.0".to_string()).with_link_signer(LinkSigner::new(b"test"));
+        let target = "https://a.com/?utm_source=x";
+        let signature = api.state.link_signer.sign(target);
+        let redirect = |u: &str, s: Option<&str>| {
+            handle_redirect(
+                State(api.state.clone()),
+                Query(RedirectParams { u: u.to_string(), s: s.map(str::to_string) }),
+            )
+        };
+
+        let response = redirect(target, Some(&signature)).await;
+        assert_eq!(response.status(), StatusCode::FOUND);
+        assert_eq!(response.headers()[axum::http::header::LOCATION], "https://a.com/");
+
+        // 缺少签名、签名被篡改或目标被替换时拒绝跳转
+        assert_eq!(redirect(target, None).await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(redirect(target, Some("AAAA")).await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(redirect("https://evil.example/", Some(&signature)).await.status(), StatusCode::BAD_REQUEST);
+
+        let click = |s: Option<String>| {
+            handle_click(
+                State(api.state.clone()),
+                Query(ClickParams { e: Some("bing".to_string()), u: target.to_string(), s }),
+            )
+        };
+        assert_eq!(click(Some(signature.clone())).await.status(), StatusCode::FOUND);
+        assert_eq!(click(None).await.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_api_with_image_proxy() {
+        let search = Arc::new(
+            SearchInterface::new(SearchConfig::default()).unwrap()
+        );
+        let proxy = ImageProxyConfig {
+            enabled: true,
+            ..Default::default()
+        };
+
+        let api = ApiInterface::new(search, "0.1.0".to_string()).with_image_proxy(proxy, None);
+        assert!(api.state.image_proxy.enabled);
+        assert!(api.state.image_cache.is_none());
+        let _router = api.build_router();
+    }
+
+    #[tokio::test]
+    async fn test_image_proxy_rejects_unsigned_and_private_targets() {
+        use axum::extract::{Query, State};
+        use super::image_proxy::{handle_image_proxy, ImageProxyParams};
+
+        let search = Arc::new(
+            SearchInterface::new(SearchConfig::default()).unwrap()
+        );
+        let proxy = ImageProxyConfig {
+            enabled: true,
+            timeout_secs: 2,
+            ..Default::default()
+        };
+        let api = ApiInterface::new(search, "0.1.0".to_string()).with_image_proxy(proxy, None);
+        let fetch = |url: &str, s: Option<String>| {
+            handle_image_proxy(
+                State(api.state.clone()),
+                Query(ImageProxyParams { url: url.to_string(), s }),
+            )
+        };
+        let signed = |url: &str| fetch(url, Some(api.state.link_signer.sign(url)));
+
+        // 未签名或签名与地址不匹配
+        let target = "https://i.example.com/a.jpg";
+        assert_eq!(fetch(target, None).await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            fetch("http://169.254.169.254/", Some(api.state.link_signer.sign(target))).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        // 签名有效但目标是内网地址：IP 直接拒绝，域名在解析后拒绝
+        assert_eq!(signed("http://169.254.169.254/latest/meta-data/").await.status(), StatusCode::FORBIDDEN);
+        assert_eq!(signed("http://[::1]:8080/a.png").await.status(), StatusCode::FORBIDDEN);
+        assert_eq!(signed("http://localhost:9/a.png").await.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_api_with_click_tracking() {
+        let search = Arc::new(
+            SearchInterface::new(SearchConfig::default()).unwrap()
+        );
+        let api = ApiInterface::new(search, "0.1.0".to_string())
+            .with_click_tracking(ClickTrackingConfig { enabled: true });
+        assert!(api.state.click_tracking.enabled);
+        let _router = api.build_router();
+    }
 }
@@ -0,0 +1,120 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 结果链接签名
+//!
+//! 跳转、点击与缩略图代理端点无需认证，为避免被当作开放跳转或开放代理使用，
+//! 服务器改写链接时以 HMAC-SHA256 对目标地址签名，端点只接受签名有效的目标
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+
+/// 签名查询参数名
+pub const SIGNATURE_PARAM: &str = "s";
+
+/// 随机生成的签名密钥长度（字节）
+const RANDOM_KEY_LEN: usize = 32;
+
+/// 链接签名器
+///
+/// 未配置密钥时每次启动随机生成，重启后旧链接失效
+#[derive(Clone)]
+pub struct LinkSigner {
+    /// HMAC 密钥
+    key: Vec<u8>,
+}
+
+impl std::fmt::Debug for LinkSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinkSigner").finish_non_exhaustive()
+    }
+}
+
+impl LinkSigner {
+    /// 使用指定密钥创建签名器
+    pub fn new(secret: &[u8]) -> Self {
+        Self { key: secret.to_vec() }
+    }
+
+    /// 使用随机密钥创建签名器
+    pub fn random() -> Self {
+        Self::new(&rand::random::<[u8; RANDOM_KEY_LEN]>())
+    }
+
+    /// 按配置创建签名器（未配置密钥时随机生成）
+    pub fn from_secret(secret: Option<&str>) -> Self {
+        match secret.filter(|s| !s.is_empty()) {
+            Some(secret) => Self::new(secret.as_bytes()),
+            None => Self::random(),
+        }
+    }
+
+    fn mac(&self, target: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(target.as_bytes());
+        mac
+    }
+
+    /// 计算目标地址的签名
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - 目标地址
+    ///
+    /// # Returns
+    ///
+    /// HMAC-SHA256 的 URL 安全 Base64 表示（无填充）
+    pub fn sign(&self, target: &str) -> String {
+        URL_SAFE_NO_PAD.encode(self.mac(target).finalize().into_bytes())
+    }
+
+    /// 校验目标地址的签名（常量时间比较）
+    pub fn verify(&self, target: &str, signature: &str) -> bool {
+        match URL_SAFE_NO_PAD.decode(signature) {
+            Ok(bytes) => self.mac(target).verify_slice(&bytes).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+impl Default for LinkSigner {
+    fn default() -> Self {
+        Self::random()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = LinkSigner::new(b"secret");
+        let signature = signer.sign("https://a.com/");
+        assert!(signer.verify("https://a.com/", &signature));
+        assert!(!signer.verify("https://b.com/", &signature));
+        assert!(!signer.verify("https://a.com/", ""));
+        assert!(!signer.verify("https://a.com/", "not base64!"));
+    }
+
+    #[test]
+    fn test_keys_do_not_share_signatures() {
+        let signature = LinkSigner::new(b"one").sign("https://a.com/");
+        assert!(!LinkSigner::new(b"two").verify("https://a.com/", &signature));
+        assert!(!LinkSigner::random().verify("https://a.com/", &signature));
+        assert_eq!(LinkSigner::from_secret(Some("one")).sign("https://a.com/"), signature);
+    }
+}
@@ -18,6 +18,7 @@
 
 use serde::{Deserialize, Serialize};
 use crate::derive::SearchQuery;
+use crate::search::ScoreExplanation;
 
 /// API 搜索请求
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +62,28 @@ pub struct ApiSearchRequest {
     /// 指定搜索引擎（可选，逗号分隔）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub engines: Option<String>,
+
+    /// 排序方式（可选：relevance、time、source、price）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    /// 将结果翻译为该语言（可选，如 `en`、`zh`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translate_to: Option<String>,
+    /// 用 `<em>` 高亮摘要中的查询词
+    #[serde(default)]
+    pub highlight: bool,
+    /// 在响应中附带各引擎的原始响应与解析警告（调试选择器用）
+    #[serde(default)]
+    pub debug_capture: bool,
+    /// 延迟预算（毫秒）：到期时返回已到达的结果并在响应中标记 `partial`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_latency_ms: Option<u64>,
+    /// 在每条结果中附带评分解释（BM25 分项、引擎权威度、时效性与去重合并）
+    #[serde(default)]
+    pub explain: bool,
+    /// 搜索会话令牌（见 `POST /api/sessions`）：相同令牌的查询共享上下文
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
 }
 
 fn default_page() -> u32 {
@@ -104,6 +127,10 @@ impl ApiSearchRequest {
             query.region = Some(region.clone());
         }
 
+        if let Some(ref sort) = self.sort {
+            query.params.insert(crate::search::SORT_PARAM_KEY.to_string(), sort.clone());
+        }
+
         Ok(query)
     }
 
@@ -176,6 +203,31 @@ pub struct ApiSearchResponse {
     
     /// 是否来自缓存
     pub cached: bool,
+
+    /// 警告信息（如查询中的敏感信息已被移除）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// 即时答案（显示在网页结果之前）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<crate::search::InstantAnswer>,
+    /// 拼写纠正建议（"您是不是要找"）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+    /// 搜索会话中之前的查询（最近的在前）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_queries: Vec<String>,
+    /// 各引擎的调试捕获（请求 `debug_capture` 时返回）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debug: Option<std::collections::BTreeMap<String, crate::search::EngineDebugCapture>>,
+    /// 各引擎的执行明细（耗时、结果数、缓存命中、错误与重试）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub per_engine: Vec<crate::search::EngineTiming>,
+    /// 延迟预算到期时仍有引擎未完成，结果只包含已到达的部分
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+    /// 请求 ID（与响应头 `X-Request-Id` 相同，关闭 `include_request_id` 时省略）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 /// API 搜索结果项
@@ -190,6 +242,10 @@ pub struct ApiSearchResultItem {
     /// 结果描述/摘要
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// 缩略图URL（启用缩略图代理时为代理地址）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
     
     /// 来源引擎
     pub engine: String,
@@ -197,6 +253,10 @@ pub struct ApiSearchResultItem {
     /// 评分（用于排序）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub score: Option<f64>,
+
+    /// 评分解释（仅在请求 `explain` 时附带）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explain: Option<ScoreExplanation>,
 }
 
 /// API 错误响应
@@ -229,6 +289,50 @@ pub struct ApiHealthResponse {
     pub total_engines: usize,
 }
 
+/// 单项依赖检查状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiHealthStatus {
+    /// 检查通过
+    Pass,
+    /// 可降级运行
+    Warn,
+    /// 检查失败
+    Fail,
+    /// 未配置，跳过
+    Skip,
+}
+
+/// 单项依赖检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiHealthCheck {
+    /// 检查项名称
+    pub name: String,
+
+    /// 检查状态
+    pub status: ApiHealthStatus,
+
+    /// 说明或失败原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// 检查耗时（毫秒）
+    pub elapsed_ms: u64,
+}
+
+/// `/healthz` 与 `/readyz` 响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiHealthReport {
+    /// 总体状态：任一检查失败为 `fail`，否则有警告为 `warn`
+    pub status: ApiHealthStatus,
+
+    /// 版本号
+    pub version: String,
+
+    /// 各项检查结果
+    pub checks: Vec<ApiHealthCheck>,
+}
+
 /// API 引擎信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiEngineInfo {
@@ -244,6 +348,10 @@ pub struct ApiEngineInfo {
     /// 是否可用
     pub enabled: bool,
     
+    /// 不可用原因（依赖不满足时）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inactive_reason: Option<String>,
+    
     /// 支持的功能
     pub capabilities: Vec<String>,
 }
@@ -268,6 +376,13 @@ pub struct ApiStatsResponse {
     
     /// 超时次数
     pub timeouts: u64,
+    
+    /// 合并到其他进行中请求的次数
+    pub coalesced_requests: u64,
+
+    /// 结果点击统计（仅在启用点击统计时返回）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clicks: Option<crate::search::ClickReport>,
 }
 
 impl ApiStatsResponse {
@@ -287,6 +402,8 @@ impl ApiStatsResponse {
             cache_hit_rate: hit_rate,
             engine_failures: stats.engine_failures,
             timeouts: stats.timeouts,
+            coalesced_requests: stats.coalesced_requests,
+            clicks: None,
         }
     }
 }
@@ -339,6 +456,13 @@ mod tests {
             safe_search: None,
             time_range: None,
             engines: None,
+            sort: Some("price".to_string()),
+            translate_to: None,
+            highlight: false,
+            debug_capture: false,
+            max_latency_ms: None,
+            explain: false,
+            session: None,
         };
 
         let query = request.to_search_query().unwrap();
@@ -346,6 +470,7 @@ mod tests {
         assert_eq!(query.page, 2);
         assert_eq!(query.page_size, 20);
         assert_eq!(query.language, Some("en".to_string()));
+        assert_eq!(query.params.get(crate::search::SORT_PARAM_KEY).map(String::as_str), Some("price"));
     }
 
     #[test]
@@ -358,6 +483,7 @@ mod tests {
             cache_misses: 40,
             engine_failures: 5,
             timeouts: 2,
+            coalesced_requests: 0,
         };
         
         let api_stats = ApiStatsResponse::from_search_stats(&stats);
@@ -16,15 +16,29 @@
 //!
 //! 提供命令行交互式搜索功能
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::Instrument;
 
-use seesea_core::derive::{SearchQuery, SearchResultItem};
-use seesea_core::search::{SearchInterface, SearchConfig, SearchRequest};
+use seesea_core::api::{ApiInterface, ApiListener, ServerConfig as ApiServerConfig};
+use seesea_core::cache::{AlertDelivery, CacheImplConfig, CacheInterface, SavedSearch};
+use seesea_core::config::{ConfigLoadResult, ConfigLoader, ConfigValidator, Environment, SeeSeaConfig};
+use seesea_core::config::loader::ConfigSource;
+use seesea_core::derive::{RssFeed, RssFeedQuery, SearchQuery, SearchResultItem};
+use seesea_core::net::{HttpClient, NetworkConfig};
+use seesea_core::lifecycle::TaskManager;
+use seesea_core::rss::RssInterface;
+use seesea_core::search::{generate_request_id, request_span, HighlightMarkers, SavedSearchRunner, SearchInterface, SearchConfig, SearchRequest, SearchResponse, ENGINE_METADATA_KEY};
 use seesea_core::search::engine_config::EngineMode;
+use seesea_core::search::debug_capture::debug_captures;
+use seesea_core::search::explain::explanation;
+use seesea_core::text::{pad_to_width, truncate_width};
 
 /// SeeSea 命令行应用
 #[derive(Parser)]
@@ -58,6 +72,22 @@ enum Commands {
         /// 调试模式 - 显示详细的引擎响应信息
         #[arg(long)]
         debug: bool,
+
+        /// 高亮摘要中的查询词
+        #[arg(long)]
+        highlight: bool,
+
+        /// 显示每条结果的评分解释（BM25 分项、引擎权威度、去重合并等）
+        #[arg(long)]
+        explain: bool,
+
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// 安静模式 - 只输出结果，不显示横幅、进度与统计
+        #[arg(short, long)]
+        quiet: bool,
     },
     
     /// 列出所有可用的搜索引擎
@@ -73,6 +103,276 @@ enum Commands {
         #[arg(short, long)]
         global: bool,
     },
+
+    /// 启动 HTTP API 服务器
+    Serve {
+        /// 配置文件或所在目录
+        #[arg(short, long, default_value = "./config")]
+        config: PathBuf,
+
+        /// 覆盖配置中的监听地址
+        #[arg(long)]
+        host: Option<String>,
+
+        /// 覆盖配置中的端口
+        #[arg(short, long)]
+        port: Option<u16>,
+
+        /// 在 Unix 域套接字上监听（替代监听地址与端口）
+        #[arg(long)]
+        unix_socket: Option<PathBuf>,
+
+        /// 同时在该地址启动 gRPC 服务（需以 grpc 特性编译）
+        #[arg(long)]
+        grpc: Option<std::net::SocketAddr>,
+    },
+
+    /// 管理 RSS 订阅
+    Rss {
+        #[command(subcommand)]
+        action: RssCommands,
+    },
+
+    /// 管理本地缓存
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommands,
+    },
+
+    /// 管理保存的搜索与告警
+    Alerts {
+        #[command(subcommand)]
+        action: AlertCommands,
+    },
+
+    /// 校验或生成配置文件
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum RssCommands {
+    /// 订阅 RSS feed（立即获取一次并持久化）
+    Add {
+        /// Feed URL
+        url: String,
+
+        /// 自动更新间隔（秒）
+        #[arg(short, long, default_value_t = DEFAULT_RSS_UPDATE_INTERVAL)]
+        interval: u64,
+    },
+
+    /// 取消订阅
+    Remove {
+        /// Feed URL
+        url: String,
+    },
+
+    /// 列出所有订阅
+    List,
+
+    /// 获取最新条目（未指定 URL 时获取所有订阅）
+    Fetch {
+        /// Feed URL
+        url: Option<String>,
+
+        /// 每个 feed 显示的条目数
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// 显示缓存统计信息
+    Stats,
+
+    /// 清空所有缓存
+    Clear {
+        /// 跳过确认
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// 清理过期条目
+    Cleanup {
+        /// 同时删除旧版本格式的搜索结果缓存键
+        #[arg(long)]
+        legacy: bool,
+    },
+
+    /// 导出缓存的搜索结果为 JSONL 文件
+    Export {
+        /// 输出文件
+        file: PathBuf,
+
+        /// 同时导出已过期的结果
+        #[arg(long)]
+        include_stale: bool,
+    },
+
+    /// 从 JSONL 文件导入搜索结果
+    Import {
+        /// 输入文件
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum AlertCommands {
+    /// 保存搜索（按间隔重新执行，新结果产生告警）
+    Add {
+        /// 搜索查询
+        query: String,
+
+        /// 执行间隔（秒）
+        #[arg(short, long, default_value_t = DEFAULT_SAVED_SEARCH_INTERVAL)]
+        interval: u64,
+
+        /// 指定使用的引擎（逗号分隔）
+        #[arg(short, long)]
+        engines: Option<String>,
+
+        /// 新结果的投递方式（store/webhook/both）
+        #[arg(short, long, default_value = "store")]
+        delivery: AlertDelivery,
+    },
+
+    /// 删除保存的搜索及其告警
+    Remove {
+        /// 保存的搜索 ID
+        id: String,
+    },
+
+    /// 列出保存的搜索
+    List,
+
+    /// 查看告警
+    Show {
+        /// 只显示该保存的搜索的告警
+        #[arg(short, long)]
+        search: Option<String>,
+    },
+
+    /// 删除告警
+    Dismiss {
+        /// 告警 ID
+        id: String,
+    },
+
+    /// 立即执行所有到期的保存的搜索
+    Run,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// 校验配置文件并输出错误、警告与评分
+    Validate {
+        /// 配置文件
+        file: PathBuf,
+    },
+
+    /// 生成带注释的默认配置文件
+    Init {
+        /// 目标运行环境（development/testing/staging/production）
+        #[arg(long, default_value = "development")]
+        env: Environment,
+
+        /// 输出文件
+        #[arg(short, long, default_value = "./config/seesea.toml")]
+        output: PathBuf,
+
+        /// 覆盖已存在的文件
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+/// 文本输出中结果摘要的最大显示宽度（全角字符占两列）
+const SNIPPET_WIDTH: usize = 200;
+
+/// 服务模式下清理过期缓存的间隔
+const CACHE_CLEANUP_INTERVAL: Duration = Duration::from_secs(600);
+
+/// 停机时等待后台任务结束的最长时间
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// RSS 订阅默认更新间隔（秒）
+const DEFAULT_RSS_UPDATE_INTERVAL: u64 = seesea_core::rss::DEFAULT_UPDATE_INTERVAL;
+
+/// 服务模式下删除超过保留期的搜索历史的间隔
+const HISTORY_EXPIRY_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// 服务模式下重载结果过滤规则并检查到期过滤列表的间隔（各列表按自身的刷新间隔获取）
+const FILTER_LIST_TICK: Duration = Duration::from_secs(300);
+
+/// 保存的搜索默认执行间隔（秒）
+const DEFAULT_SAVED_SEARCH_INTERVAL: u64 = 3600;
+
+/// 服务模式下检查到期的保存的搜索的间隔
+const SAVED_SEARCH_TICK: Duration = Duration::from_secs(60);
+
+/// 搜索结果输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+enum OutputFormat {
+    /// 彩色文本（默认）
+    #[default]
+    Text,
+    /// 无颜色文本
+    Plain,
+    /// 完整的 JSON 响应（含元数据）
+    Json,
+    /// CSV 表格
+    Csv,
+    /// Markdown 列表
+    Markdown,
+}
+
+impl OutputFormat {
+    /// 是否为供脚本解析的格式（只向标准输出写结果，错误返回非零退出码）
+    fn is_machine_readable(self) -> bool {
+        matches!(self, Self::Json | Self::Csv | Self::Markdown)
+    }
+
+    /// 是否显示横幅与进度
+    fn shows_banner(self) -> bool {
+        !self.is_machine_readable()
+    }
+
+    /// 该格式使用的查询词高亮标记
+    fn highlight_markers(self) -> HighlightMarkers {
+        match self {
+            Self::Text => HighlightMarkers::new("\x1b[1;33m", "\x1b[0m\x1b[90m"),
+            Self::Plain => HighlightMarkers::new("*", "*"),
+            Self::Markdown => HighlightMarkers::new("**", "**"),
+            Self::Json | Self::Csv => HighlightMarkers::default(),
+        }
+    }
+}
+
+/// 搜索命令选项
+#[derive(Debug, Clone, Default)]
+struct SearchOptions {
+    /// 使用全局模式
+    global: bool,
+    /// 指定引擎（逗号分隔）
+    engines: Option<String>,
+    /// 详细输出
+    verbose: bool,
+    /// 调试模式
+    debug: bool,
+    /// 高亮查询词
+    highlight: bool,
+    /// 显示评分解释
+    explain: bool,
+    /// 输出格式
+    format: OutputFormat,
+    /// 安静模式
+    quiet: bool,
+    /// 页码（默认第 1 页）
+    page: Option<usize>,
 }
 
 #[tokio::main]
@@ -80,8 +380,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
     
     match cli.command {
-        Some(Commands::Search { query, global, engines, verbose, debug }) => {
-            execute_search(query, global, engines, verbose, debug).await?;
+        Some(Commands::Search { query, global, engines, verbose, debug, highlight, explain, format, quiet }) => {
+            let options = SearchOptions { global, engines, verbose, debug, highlight, explain, format, quiet, page: None };
+            execute_search(query, options).await?;
         }
         Some(Commands::ListEngines { stats }) => {
             list_engines(stats).await?;
@@ -89,6 +390,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(Commands::Interactive { global }) => {
             interactive_mode(global).await?;
         }
+        Some(Commands::Serve { config, host, port, unix_socket, grpc }) => {
+            serve(config, host, port, unix_socket, grpc).await.map_err(|e| e as Box<dyn std::error::Error>)?;
+        }
+        Some(Commands::Rss { action }) => {
+            run_rss_command(action).await.map_err(|e| e as Box<dyn std::error::Error>)?;
+        }
+        Some(Commands::Cache { action }) => {
+            run_cache_command(action).map_err(|e| e as Box<dyn std::error::Error>)?;
+        }
+        Some(Commands::Alerts { action }) => {
+            run_alert_command(action).await.map_err(|e| e as Box<dyn std::error::Error>)?;
+        }
+        Some(Commands::Config { action }) => {
+            run_config_command(action).await.map_err(|e| e as Box<dyn std::error::Error>)?;
+        }
         None => {
             // 默认进入交互模式
             interactive_mode(false).await?;
@@ -99,20 +415,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 /// 执行搜索
-async fn execute_search(
-    query_str: String,
-    use_global: bool,
-    engines_str: Option<String>,
-    verbose: bool,
-    debug: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    println!("{}", "🌊 SeeSea 搜索".bright_cyan().bold());
-    println!("{}", "━".repeat(60).bright_black());
+///
+/// 搜索成功时返回响应，供交互模式记录会话状态
+async fn execute_search(query_str: String, options: SearchOptions) -> Result<Option<SearchResponse>, Box<dyn std::error::Error>> {
+    // 纯文本与机器可读格式不输出颜色；机器可读格式只向标准输出写结果
+    if options.format != OutputFormat::Text {
+        colored::control::set_override(false);
+    }
+    let show_banner = options.format.shows_banner() && !options.quiet;
+
+    if show_banner {
+        println!("{}", "🌊 SeeSea 搜索".bright_cyan().bold());
+        println!("{}", "━".repeat(60).bright_black());
+    }
 
     // 确定运行模式和引擎列表
-    let (mode, configured_engines) = if use_global {
+    let (mode, configured_engines) = if options.global {
         (EngineMode::Global, vec![])
-    } else if let Some(engines) = engines_str {
+    } else if let Some(ref engines) = options.engines {
         let engine_list: Vec<String> = engines
             .split(',')
             .map(|s| s.trim().to_string())
@@ -123,40 +443,51 @@ async fn execute_search(
         (EngineMode::Global, vec![])
     };
 
-    println!("📌 查询: {}", query_str.bright_white().bold());
-    println!("⚙️  模式: {}",
-        match mode {
-            EngineMode::Global => "全局模式（所有引擎）".bright_green(),
-            EngineMode::Custom(_) => "配置模式".bright_yellow(),
-        }
-    );
+    if show_banner {
+        println!("📌 查询: {}", query_str.bright_white().bold());
+        println!("⚙️  模式: {}",
+            match mode {
+                EngineMode::Global => "全局模式（所有引擎）".bright_green(),
+                EngineMode::Custom(_) => "配置模式".bright_yellow(),
+                EngineMode::Category(_) => "分类模式".bright_cyan(),
+            }
+        );
+    }
 
-    // 创建搜索接口
-    let search_config = SearchConfig::default();
+    // 创建搜索接口（按输出格式选择查询词高亮标记）
+    let search_config = SearchConfig {
+        highlight_markers: options.format.highlight_markers(),
+        ..Default::default()
+    };
     let search_interface = std::sync::Arc::new(
         SearchInterface::new(search_config)
             .map_err(|e| format!("Failed to create search interface: {}", e))?
     );
 
-    // 显示要使用的引擎
-    println!("🔍 使用引擎: {}",
-        if configured_engines.is_empty() {
-            match mode {
-                EngineMode::Global => search_interface.list_global_engines().join(", "),
-                _ => "默认引擎".to_string(),
-            }
-        } else {
-            configured_engines.join(", ")
-        }.bright_blue()
-    );
+    if show_banner {
+        // 显示要使用的引擎
+        println!("🔍 使用引擎: {}",
+            if configured_engines.is_empty() {
+                match mode {
+                    EngineMode::Global => search_interface.list_global_engines().join(", "),
+                    _ => "默认引擎".to_string(),
+                }
+            } else {
+                configured_engines.join(", ")
+            }.bright_blue()
+        );
 
-    // 检查是否使用了缓存
-    println!("🗄️  缓存: {}", "已启用".bright_green());
-    println!();
+        // 检查是否使用了缓存
+        println!("🗄️  缓存: {}", "已启用".bright_green());
+        println!();
+    }
 
     // 创建搜索查询
     let mut query = SearchQuery::default();
     query.query = query_str;
+    if let Some(page) = options.page {
+        query.page = page;
+    }
 
     // 创建搜索请求
     let search_request = SearchRequest {
@@ -166,146 +497,947 @@ async fn execute_search(
         max_results: Some(100),
         force: false,
         cache_timeline: Some(3600),
+        translate_to: None,
+        highlight: options.highlight,
+        debug_capture: options.debug,
+        max_latency_ms: None,
+        explain: options.explain,
+        session: None,
     };
 
     // 执行搜索
-    println!("{}", "正在搜索...".bright_yellow());
-
-    // 创建进度条
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] {msg}")
-            .unwrap()
-            .progress_chars("=>-")
-    );
-    progress_bar.set_message("正在搜索...");
-    progress_bar.enable_steady_tick(Duration::from_millis(120));
+    let progress_bar = if show_banner {
+        println!("{}", "正在搜索...".bright_yellow());
 
-    let search_result = if let EngineMode::Custom(_) = mode {
-        // 配置模式，使用指定引擎
-        search_interface.search(&search_request).await
+        // 创建进度条
+        let progress_bar = ProgressBar::new_spinner();
+        progress_bar.set_style(
+            ProgressStyle::default_bar()
+                .template("{spinner:.green} [{elapsed_precise}] {msg}")
+                .unwrap()
+                .progress_chars("=>-")
+        );
+        progress_bar.set_message("正在搜索...");
+        progress_bar.enable_steady_tick(Duration::from_millis(120));
+        Some(progress_bar)
     } else {
-        // 全局或中国模式，使用模式搜索
-        search_interface.search_with_mode(&search_request, mode).await
+        None
     };
 
+    // 搜索在携带请求 ID 的 span 中执行，日志据此关联
+    let request_id = generate_request_id();
+    let search_result = async {
+        if let EngineMode::Custom(_) = mode {
+            // 配置模式，使用指定引擎
+            search_interface.search(&search_request).await
+        } else {
+            // 全局或中国模式，使用模式搜索
+            search_interface.search_with_mode(&search_request, mode).await
+        }
+    }
+    .instrument(request_span(&request_id))
+    .await;
+
     // 完成进度条
-    progress_bar.finish_with_message("搜索完成！");
+    if let Some(progress_bar) = progress_bar {
+        progress_bar.finish_with_message("搜索完成！");
+        println!();
+    }
+
+    // 处理搜索结果
+    let response = match search_result {
+        Ok(mut response) => {
+            response.request_id = Some(request_id);
+            match options.format {
+                OutputFormat::Text | OutputFormat::Plain => {
+                    print_text_results(&response, options.verbose, options.quiet);
+                    if options.verbose && !options.quiet {
+                        print_engine_breakdown(&response);
+                        if let Some(ref request_id) = response.request_id {
+                            println!("{} {}", "请求 ID:".bright_black(), request_id);
+                        }
+                    }
+                    if options.debug {
+                        print_debug_captures(&response);
+                    }
+                    if options.explain {
+                        print_explanations(&response);
+                    }
+                }
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&response)?),
+                OutputFormat::Csv => print!("{}", format_csv(&response)),
+                OutputFormat::Markdown => print!("{}", format_markdown(&response)),
+            }
+            Some(response)
+        }
+        Err(e) if options.format.is_machine_readable() => {
+            return Err(format!("搜索失败 (请求 ID {}): {}", request_id, e).into());
+        }
+        Err(e) => {
+            println!("❌ 搜索失败: {} (请求 ID {})", format!("{}", e).bright_red(), request_id);
+            if options.debug {
+                println!("🔍 详细错误: {:?}", e);
+            }
+            None
+        }
+    };
+
+    // 显示统计信息
+    if options.verbose && show_banner {
+        println!();
+        print_search_stats(&search_interface).await;
+    }
+
+    Ok(response)
+}
+
+/// 输出各引擎的执行明细，耗时最长的引擎排在最前
+fn print_engine_breakdown(response: &SearchResponse) {
+    if response.per_engine.is_empty() {
+        return;
+    }
+
+    let mut timings: Vec<_> = response.per_engine.iter().collect();
+    timings.sort_by_key(|timing| std::cmp::Reverse(timing.elapsed_ms));
 
     println!();
+    println!("{}", "⏱️  引擎耗时".bright_cyan().bold());
+    println!("{}", "━".repeat(60).bright_black());
+    for timing in timings {
+        let mut line = format!(
+            "{} {:>6} ms · {} 条结果",
+            pad_to_width(&timing.engine, 16),
+            timing.elapsed_ms,
+            timing.result_count
+        );
+        if timing.cache_hit {
+            line.push_str(" · 缓存");
+        }
+        if timing.retries > 0 {
+            line.push_str(&format!(" · 重试 {} 次", timing.retries));
+        }
+        match &timing.error {
+            Some(error) => println!("{} {}", line.bright_red(), error.bright_black()),
+            None => println!("{}", line),
+        }
+    }
+}
 
-    // 处理搜索结果
-    match search_result {
-        Ok(response) => {
-            println!("{}", "🔍 搜索结果".bright_cyan().bold());
-            println!("{}", "━".repeat(60).bright_black());
+/// 输出各引擎的调试捕获（状态、原始响应大小、解析结果数与警告）
+fn print_debug_captures(response: &SearchResponse) {
+    let Some(captures) = response.results.iter().find_map(debug_captures) else {
+        return;
+    };
 
-            if response.cached {
-                println!("🗄️  {} 从缓存获取", "结果".bright_green());
-                println!();
+    println!();
+    println!("{}", "🐞 引擎调试信息".bright_cyan().bold());
+    println!("{}", "━".repeat(60).bright_black());
+    for (engine, capture) in captures {
+        let status = capture.status.map_or_else(|| "-".to_string(), |status| status.to_string());
+        println!(
+            "{} HTTP {} · {} 字节 · {} 条结果",
+            engine.bright_blue().bold(),
+            status,
+            capture.raw_bytes,
+            capture.item_count
+        );
+        if let Some(url) = &capture.url {
+            println!("   URL: {}", url.bright_black());
+        }
+        if let Some(error) = &capture.error {
+            println!("   {} {}", "错误:".bright_red(), error);
+        }
+        for warning in &capture.parse_warnings {
+            println!("   {} {}", "⚠".bright_yellow(), warning);
+        }
+        if let Some(file) = &capture.capture_file {
+            println!("   已保存: {}", file);
+        }
+    }
+}
+
+/// 输出各结果的评分解释（按最终排名）
+fn print_explanations(response: &SearchResponse) {
+    let mut explained: Vec<_> = response
+        .results
+        .iter()
+        .flat_map(|r| r.items.iter())
+        .filter_map(|item| explanation(item).map(|explanation| (item, explanation)))
+        .collect();
+    if explained.is_empty() {
+        return;
+    }
+    explained.sort_by_key(|(_, explanation)| explanation.rank);
+
+    println!();
+    println!("{}", "📐 评分解释".bright_cyan().bold());
+    println!("{}", "━".repeat(60).bright_black());
+    for (item, explanation) in explained {
+        let breakdown = &explanation.breakdown;
+        println!(
+            "{} {} · {:.3}",
+            format!("#{}", explanation.rank).bright_blue().bold(),
+            truncate_width(&item.title, 48),
+            explanation.final_score
+        );
+        println!(
+            "   BM25 标题 {:.3} · 摘要 {:.3} · 精确匹配 {:.3}/{:.3} · URL {:.3}",
+            breakdown.title_bm25,
+            breakdown.content_bm25,
+            breakdown.title_exact,
+            breakdown.content_exact,
+            breakdown.url_match
+        );
+        println!(
+            "   权威度 {:.2} · 位置 {:.3} · 时效 {:.3} · 相关性 {:.3}{}",
+            breakdown.engine_authority,
+            breakdown.position,
+            breakdown.recency,
+            breakdown.score,
+            if breakdown.exact_title_boost { " · 标题完全匹配" } else { "" }
+        );
+        if !explanation.merged_duplicates.is_empty() {
+            println!("   {} {}", "合并重复:".bright_black(), explanation.merged_duplicates.join(", "));
+        }
+    }
+}
+
+/// 以彩色文本输出搜索结果
+///
+/// 安静模式下只输出答案、纠正建议与结果列表
+fn print_text_results(response: &SearchResponse, verbose: bool, quiet: bool) {
+    if !quiet {
+        println!("{}", "🔍 搜索结果".bright_cyan().bold());
+        println!("{}", "━".repeat(60).bright_black());
+
+        if response.cached {
+            println!("🗄️  {} 从缓存获取", "结果".bright_green());
+            println!();
+        }
+
+        // 显示使用的引擎
+        println!("🔧 实际使用的引擎: {}", response.engines_used.join(", ").bright_blue());
+        println!("📊 总结果数: {}", response.total_count.to_string().bright_white().bold());
+        println!("⏱️  查询时间: {} ms", response.query_time_ms.to_string().bright_yellow());
+        println!();
+    }
+
+    // 即时答案显示在网页结果之前
+    if let Some(ref answer) = response.answer {
+        println!("💡 {}", answer.answer.bright_green().bold());
+        if let Some(ref url) = answer.url {
+            println!("   {}", url.bright_blue());
+        }
+        println!();
+    }
+
+    // 结果较少时提示拼写纠正
+    if let Some(suggestion) = response.suggestions.first() {
+        println!("🔤 您是不是要找: {}", suggestion.bright_cyan().bold());
+        println!();
+    }
+
+    // 收集所有结果
+    let mut all_results: Vec<(String, SearchResultItem)> = Vec::new();
+
+    for search_result in &response.results {
+        for item in &search_result.items {
+            all_results.push((search_result.engine_name.clone(), item.clone()));
+        }
+    }
+
+    // 注意：结果已经在SearchInterface中通过BM25评分排序，这里不需要重复排序
+    // SearchInterface的aggregate_with_scoring已经处理了评分和排序
+
+    // 显示前20个结果
+    let results_to_show = if verbose {
+        all_results.len().min(50)
+    } else {
+        all_results.len().min(20)
+    };
+
+    if all_results.is_empty() {
+        println!("❌ {}", "没有找到结果".bright_red());
+    } else {
+        for (i, (engine_name, item)) in all_results.iter().take(results_to_show).enumerate() {
+            println!("{}. {}", i + 1, item.title.bright_white().bold());
+            println!("   {}", item.url.bright_blue());
+
+            // 显示内容摘要
+            if !item.content.is_empty() {
+                // 按显示宽度截断，不切断多字节字符与高亮颜色码
+                let content = truncate_width(&item.content, SNIPPET_WIDTH);
+                println!("   {}", content.bright_black());
+            }
+
+            // 显示显示URL（如果与URL不同）
+            if let Some(display_url) = &item.display_url
+                && display_url != &item.url
+            {
+                println!("   {}", format!("🔗 {}", display_url).bright_black());
+            }
+
+            // 显示来源引擎
+            println!("   📌 来源: {}", engine_name.bright_green());
+
+            // 显示发布时间（如果有）
+            if let Some(published_date) = &item.published_date {
+                println!("   {}", format!("📅 {}", published_date.format("%Y-%m-%d")).bright_black());
+            }
+
+            // 显示评分（如果大于0）
+            if item.score > 0.0 {
+                println!("   {}", format!("⭐ 评分: {:.2}", item.score).bright_black());
             }
 
-            // 显示使用的引擎
-            println!("🔧 实际使用的引擎: {}", response.engines_used.join(", ").bright_blue());
-            println!("📊 总结果数: {}", response.total_count.to_string().bright_white().bold());
-            println!("⏱️  查询时间: {} ms", response.query_time_ms.to_string().bright_yellow());
             println!();
+        }
 
-            // 收集所有结果
-            let mut all_results: Vec<(String, SearchResultItem)> = Vec::new();
+        if all_results.len() > results_to_show {
+            println!("{}", format!("... 还有 {} 个结果（使用 --verbose 查看更多）",
+                all_results.len() - results_to_show).bright_yellow());
+        }
+    }
 
-            for search_result in &response.results {
-                for item in &search_result.items {
-                    all_results.push((search_result.engine_name.clone(), item.clone()));
-                }
+    if !quiet {
+        println!();
+        println!("{}", "━".repeat(60).bright_black());
+        println!("📊 搜索完成: {} 个引擎, {} 个结果",
+            response.engines_used.len().to_string().bright_green(),
+            response.total_count.to_string().bright_white().bold()
+        );
+    }
+}
+
+/// 结果项的来源引擎（聚合后以元数据记录）
+fn item_engine<'a>(engine_name: &'a str, item: &'a SearchResultItem) -> &'a str {
+    item.metadata.get(ENGINE_METADATA_KEY).map(String::as_str).unwrap_or(engine_name)
+}
+
+/// CSV 字段转义
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 以 CSV 格式输出搜索结果（首行为表头）
+fn format_csv(response: &SearchResponse) -> String {
+    let mut output = String::from("rank,title,url,engine,score,published_date,content\n");
+    let items = response.results.iter().flat_map(|r| r.items.iter().map(move |item| (r.engine_name.as_str(), item)));
+    for (rank, (engine_name, item)) in items.enumerate() {
+        let published = item.published_date.map(|d| d.to_rfc3339()).unwrap_or_default();
+        let fields = [
+            (rank + 1).to_string(),
+            csv_field(&item.title),
+            csv_field(&item.url),
+            csv_field(item_engine(engine_name, item)),
+            format!("{:.4}", item.score),
+            published,
+            csv_field(&item.content),
+        ];
+        output.push_str(&fields.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+/// Markdown 文本转义
+///
+/// 转义 Markdown 元字符（含表格分隔符 `|`），换行折叠为空格，避免标题或摘要打断列表项
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        if !escaped.is_empty() {
+            escaped.push(' ');
+        }
+        for c in word.chars() {
+            if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '(' | ')' | '<' | '>' | '#' | '|' | '!' | '~') {
+                escaped.push('\\');
             }
+            escaped.push(c);
+        }
+    }
+    escaped
+}
 
-            // 注意：结果已经在SearchInterface中通过BM25评分排序，这里不需要重复排序
-            // SearchInterface的aggregate_with_scoring已经处理了评分和排序
+/// Markdown 链接目标转义（括号、空白与 `|` 改为百分号编码）
+fn markdown_url(url: &str) -> String {
+    url.trim()
+        .replace('(', "%28")
+        .replace(')', "%29")
+        .replace('|', "%7C")
+        .replace(char::is_whitespace, "%20")
+}
 
-            // 显示前20个结果
-            let results_to_show = if verbose {
-                all_results.len().min(50)
-            } else {
-                all_results.len().min(20)
-            };
+/// 以 Markdown 列表输出搜索结果
+fn format_markdown(response: &SearchResponse) -> String {
+    let mut output = format!("# {}\n\n", markdown_escape(&response.query.query));
+    if let Some(ref answer) = response.answer {
+        output.push_str(&format!("> {}\n\n", markdown_escape(&answer.answer)));
+    }
+    if let Some(suggestion) = response.suggestions.first() {
+        output.push_str(&format!("您是不是要找：*{}*\n\n", markdown_escape(suggestion)));
+    }
 
-            if all_results.is_empty() {
-                println!("❌ {}", "没有找到结果".bright_red());
-            } else {
-                for (i, (engine_name, item)) in all_results.iter().take(results_to_show).enumerate() {
-                    println!("{}", format!("{}. {}", i + 1, item.title.bright_white().bold()));
-                    println!("   {}", item.url.bright_blue());
-
-                    // 显示内容摘要
-                    if !item.content.is_empty() {
-                        let content = if item.content.len() > 200 {
-                            // 安全地截断文本，避免在UTF-8字符中间截断
-                            let mut end = 200;
-                            while !item.content.is_char_boundary(end) {
-                                end -= 1;
-                            }
-                            format!("{}...", &item.content[..end])
-                        } else {
-                            item.content.clone()
-                        };
-                        println!("   {}", content.bright_black());
-                    }
+    let items = response.results.iter().flat_map(|r| r.items.iter().map(move |item| (r.engine_name.as_str(), item)));
+    for (rank, (engine_name, item)) in items.enumerate() {
+        output.push_str(&format!(
+            "{}. [{}]({}) — {}\n",
+            rank + 1,
+            markdown_escape(&item.title),
+            markdown_url(&item.url),
+            markdown_escape(item_engine(engine_name, item))
+        ));
+        if !item.content.trim().is_empty() {
+            output.push_str(&format!("   {}\n", markdown_escape(&item.content)));
+        }
+    }
+    output
+}
 
-                    // 显示显示URL（如果与URL不同）
-                    if let Some(display_url) = &item.display_url {
-                        if display_url != &item.url {
-                            println!("   {}", format!("🔗 {}", display_url).bright_black());
+/// 加载服务配置
+///
+/// `path` 为目录时在其中自动发现配置文件；找不到配置文件时使用默认配置，
+/// 两种情况都会叠加环境变量覆盖
+async fn load_server_config(path: &Path) -> Result<ConfigLoadResult, Box<dyn std::error::Error + Send + Sync>> {
+    let config_file = if path.is_dir() {
+        ConfigLoader::new().with_search_paths(&[path]).find_config_file().await?
+    } else {
+        path.to_path_buf()
+    };
+
+    let loader = ConfigLoader::new();
+    let sources = if config_file.exists() {
+        vec![ConfigSource::Defaults, ConfigSource::File(config_file), ConfigSource::Environment]
+    } else {
+        println!("{}", format!("⚠️  未找到配置文件: {}，使用默认配置", config_file.display()).bright_yellow());
+        vec![ConfigSource::Defaults, ConfigSource::Environment]
+    };
+    Ok(loader.load_from_sources(&sources).await?)
+}
+
+/// 启动 API 服务器，收到 SIGINT/SIGTERM 后优雅停机
+async fn serve(
+    config_path: PathBuf,
+    host: Option<String>,
+    port: Option<u16>,
+    unix_socket: Option<PathBuf>,
+    grpc: Option<std::net::SocketAddr>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    #[cfg(not(feature = "grpc"))]
+    if grpc.is_some() {
+        return Err("gRPC 服务需要以 grpc 特性编译".into());
+    }
+
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::INFO)
+        .init();
+
+    println!("{}", "🌊 SeeSea API 服务器".bright_cyan().bold());
+    println!("{}", "━".repeat(60).bright_black());
+
+    let load_result = load_server_config(&config_path).await?;
+    if !load_result.file_path.is_empty() {
+        println!("📄 配置文件: {}", load_result.file_path.bright_white());
+    }
+    for warning in &load_result.warnings {
+        println!("{}", format!("⚠️  {}", warning).bright_yellow());
+    }
+    let config_file = (!load_result.file_path.is_empty()).then(|| PathBuf::from(&load_result.file_path));
+    let app_config = load_result.config;
+
+    let mut server_config = ApiServerConfig::from_app_config(&app_config);
+    if let Some(host) = host {
+        server_config.host = host;
+    }
+    if let Some(port) = port {
+        server_config.port = port;
+    }
+    if let Some(unix_socket) = unix_socket {
+        server_config.unix_socket = Some(unix_socket);
+    }
+
+    let cache = Arc::new(
+        CacheInterface::new(CacheImplConfig::default())
+            .map_err(|e| format!("Failed to open cache: {}", e))?
+    );
+    let alerts = cache.alerts().map_err(|e| format!("Failed to open alert store: {}", e))?;
+    let preferences = cache.preferences().map_err(|e| format!("Failed to open preference store: {}", e))?;
+    let audit = cache.audit().map_err(|e| format!("Failed to open audit log: {}", e))?;
+    let mut api = ApiInterface::from_app_config(&app_config, Some(&cache))?
+        .with_alerts(alerts.clone())
+        .with_preferences(preferences)
+        .with_audit(audit);
+    let history = if app_config.privacy.history.enabled {
+        let history = cache.history(&app_config.privacy.history)
+            .map_err(|e| format!("Failed to open history store: {}", e))?;
+        api = api.with_history(history.clone());
+        Some(history)
+    } else {
+        None
+    };
+
+    // 后台任务：定期清理过期缓存，停机时刷新缓存到磁盘
+    let lifecycle = TaskManager::new();
+    let janitor_cache = Arc::clone(&cache);
+    lifecycle.spawn_interval("cache-janitor", CACHE_CLEANUP_INTERVAL, move || {
+        let cache = Arc::clone(&janitor_cache);
+        async move {
+            let result = tokio::task::spawn_blocking(move || cache.cleanup()).await;
+            if let Ok(Err(e)) = result {
+                tracing::warn!("清理过期缓存失败: {}", e);
+            }
+        }
+    });
+    if let Some(history) = history {
+        lifecycle.spawn_interval("history-retention", HISTORY_EXPIRY_INTERVAL, move || {
+            let history = history.clone();
+            async move {
+                if let Err(e) = history.expire_async().await {
+                    tracing::warn!("删除过期搜索历史失败: {}", e);
+                }
+            }
+        });
+    }
+    // 结果过滤：启动时获取远程列表，之后定期获取到期的列表，并从配置文件热重载过滤规则
+    if !app_config.engines.result_filtering.remote_lists.is_empty() {
+        let search = Arc::clone(api.search());
+        lifecycle.spawn("filter-lists-initial", async move {
+            search.refresh_filter_lists().await;
+        });
+    }
+    if config_file.is_some() || !app_config.engines.result_filtering.remote_lists.is_empty() {
+        let search = Arc::clone(api.search());
+        lifecycle.spawn_interval("result-filter", FILTER_LIST_TICK, move || {
+            let search = Arc::clone(&search);
+            let config_file = config_file.clone();
+            async move {
+                if let Some(path) = config_file {
+                    match load_server_config(&path).await {
+                        Ok(loaded) => {
+                            if let Err(e) = search.reload_result_filter(&loaded.config.engines.result_filtering) {
+                                tracing::warn!("重载结果过滤规则失败: {}", e);
+                            }
+                            if let Err(e) = search.set_scoring_profile(loaded.config.search.scoring.clone()) {
+                                tracing::warn!("重载评分参数失败: {}", e);
+                            }
                         }
+                        Err(e) => tracing::warn!("重新读取配置文件失败: {}", e),
                     }
+                }
+                search.refresh_filter_lists().await;
+            }
+        });
+    }
+    let runner = Arc::new(SavedSearchRunner::new(Arc::clone(api.search()), alerts));
+    lifecycle.spawn_interval("saved-searches", SAVED_SEARCH_TICK, move || {
+        let runner = Arc::clone(&runner);
+        async move {
+            runner.run_due().await;
+        }
+    });
+    let flush_cache = Arc::clone(&cache);
+    lifecycle.on_shutdown("cache-flush", move || {
+        if let Err(e) = flush_cache.flush() {
+            eprintln!("刷新缓存失败: {}", e);
+        }
+    });
 
-                    // 显示来源引擎
-                    println!("   {}", format!("📌 来源: {}", engine_name.bright_green()));
+    let listener = ApiListener::bind(&server_config)
+        .await
+        .map_err(|e| format!("Failed to bind API listener: {}", e))?;
+    println!("🚀 监听地址: {}", listener.describe().bright_green().bold());
 
-                    // 显示发布时间（如果有）
-                    if let Some(published_date) = &item.published_date {
-                        println!("   {}", format!("📅 {}", published_date.format("%Y-%m-%d")).bright_black());
-                    }
+    // gRPC 服务与 HTTP API 共享搜索接口，随后台任务一起停机
+    #[cfg(feature = "grpc")]
+    if let Some(grpc_addr) = grpc {
+        let grpc_listener = tokio::net::TcpListener::bind(grpc_addr)
+            .await
+            .map_err(|e| format!("Failed to bind {}: {}", grpc_addr, e))?;
+        println!("🚀 gRPC 地址: {}", grpc_listener.local_addr()?.to_string().bright_green().bold());
+        let service = seesea_core::api::grpc::GrpcService::with_search(Arc::clone(api.search()));
+        lifecycle.spawn_with_token(move |token| async move {
+            if let Err(e) = service.serve_with_shutdown(grpc_listener, token.cancelled_owned()).await {
+                tracing::warn!("gRPC 服务异常退出: {}", e);
+            }
+        });
+    }
+    println!("{}", "按 Ctrl+C 停止".bright_black());
 
-                    // 显示评分（如果大于0）
-                    if item.score > 0.0 {
-                        println!("   {}", format!("⭐ 评分: {:.2}", item.score).bright_black());
-                    }
+    api.serve_listener(listener, shutdown_signal()).await?;
+
+    let report = lifecycle.shutdown(SHUTDOWN_TIMEOUT).await;
+    if !report.completed {
+        println!("{}", format!("⚠️  {} 个后台任务未能按时结束", report.remaining_tasks).bright_yellow());
+    }
+    println!("👋 服务器已停止");
+    Ok(())
+}
+
+/// 等待 SIGINT（Ctrl+C）或 SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            eprintln!("无法监听 Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                eprintln!("无法监听 SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
 
-                    println!();
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    println!("\n{}", "⏹️  收到停止信号，等待进行中的请求完成...".bright_yellow());
+}
+
+/// 执行保存的搜索与告警管理命令
+async fn run_alert_command(action: AlertCommands) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let cache = CacheInterface::new(CacheImplConfig::default())
+        .map_err(|e| format!("Failed to open cache: {}", e))?;
+    let store = cache.alerts()
+        .map_err(|e| format!("Failed to open alert store: {}", e))?;
+
+    match action {
+        AlertCommands::Add { query, interval, engines, delivery } => {
+            let engines = engines
+                .map(|list| list.split(',').map(|e| e.trim().to_string()).filter(|e| !e.is_empty()).collect())
+                .unwrap_or_default();
+            let search = SavedSearch::new(&query, engines, interval, delivery);
+            store.save_search(&search)
+                .map_err(|e| format!("Failed to save search: {}", e))?;
+            println!("✅ 已保存搜索: {}", search.query.bright_white().bold());
+            println!("   ID: {}  间隔: {} 秒", search.id.bright_blue(), search.interval_secs);
+        }
+        AlertCommands::Remove { id } => {
+            if store.delete_search(&id).map_err(|e| format!("Failed to delete search: {}", e))? {
+                println!("✅ 已删除保存的搜索: {}", id.bright_blue());
+            } else {
+                println!("❌ {}", format!("保存的搜索不存在: {}", id).bright_red());
+            }
+        }
+        AlertCommands::List => {
+            let searches = store.list_searches()
+                .map_err(|e| format!("Failed to read saved searches: {}", e))?;
+            if searches.is_empty() {
+                println!("{}", "暂无保存的搜索（使用 `alerts add <query>` 添加）".bright_yellow());
+            }
+            for search in searches {
+                let last_run = search.last_run
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0))
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "从未".to_string());
+                println!("{} {}", search.id.bright_blue(), search.query.bright_white().bold());
+                println!("   间隔: {} 秒  上次执行: {}", search.interval_secs, last_run.bright_black());
+            }
+        }
+        AlertCommands::Show { search } => {
+            let alerts = store.list_alerts(search.as_deref())
+                .map_err(|e| format!("Failed to read alerts: {}", e))?;
+            if alerts.is_empty() {
+                println!("{}", "暂无告警".bright_yellow());
+            }
+            for alert in alerts {
+                println!("🔔 {} {} ({} 条新结果)", alert.id.bright_blue(), alert.query.bright_white().bold(), alert.results.len());
+                for result in &alert.results {
+                    println!("   • {}", result.title);
+                    println!("     {}", result.url.bright_black());
                 }
+            }
+        }
+        AlertCommands::Dismiss { id } => {
+            if store.delete_alert(&id).map_err(|e| format!("Failed to delete alert: {}", e))? {
+                println!("✅ 已删除告警: {}", id.bright_blue());
+            } else {
+                println!("❌ {}", format!("告警不存在: {}", id).bright_red());
+            }
+        }
+        AlertCommands::Run => {
+            let search = Arc::new(
+                SearchInterface::new(SearchConfig::default())
+                    .map_err(|e| format!("Failed to create search interface: {}", e))?
+            );
+            let runner = SavedSearchRunner::new(search, store);
+            let count = runner.run_due().await;
+            println!("✅ 产生 {} 条告警", count.to_string().bright_green());
+        }
+    }
+    Ok(())
+}
 
-                if all_results.len() > results_to_show {
-                    println!("{}", format!("... 还有 {} 个结果（使用 --verbose 查看更多）",
-                        all_results.len() - results_to_show).bright_yellow());
+/// 执行 RSS 订阅管理命令
+async fn run_rss_command(action: RssCommands) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let cache = CacheInterface::new(CacheImplConfig::default())
+        .map_err(|e| format!("Failed to open cache: {}", e))?;
+    let client = Arc::new(
+        HttpClient::new(NetworkConfig::default())
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+    );
+    let rss = RssInterface::with_cache(client, Arc::new(RwLock::new(cache.rss())));
+
+    match action {
+        RssCommands::Add { url, interval } => {
+            let feed = rss.fetch_persistent(&url, interval).await?;
+            println!("✅ 已订阅: {} ({} 个条目)", feed.meta.title.bright_white().bold(), feed.items.len());
+            println!("   {}", url.bright_blue());
+        }
+        RssCommands::Remove { url } => {
+            if rss.unsubscribe(&url).await? {
+                println!("✅ 已取消订阅: {}", url.bright_blue());
+            } else {
+                println!("❌ {}", format!("未订阅: {}", url).bright_red());
+            }
+        }
+        RssCommands::List => {
+            let subscriptions = rss.subscriptions().await?;
+            if subscriptions.is_empty() {
+                println!("{}", "暂无订阅（使用 `rss add <url>` 添加）".bright_yellow());
+            }
+            for meta in subscriptions {
+                let updated = chrono::DateTime::from_timestamp(meta.last_updated as i64, 0)
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                println!("📰 {}", meta.name.as_deref().unwrap_or(&meta.url).bright_white().bold());
+                println!("   {}", meta.url.bright_blue());
+                println!("   {}", format!(
+                    "{} 个条目 · 更新于 {} · 每 {} 秒更新",
+                    meta.item_count,
+                    updated,
+                    meta.update_interval.unwrap_or(DEFAULT_RSS_UPDATE_INTERVAL)
+                ).bright_black());
+            }
+        }
+        RssCommands::Fetch { url, limit } => {
+            // 已订阅的 feed 按各自的间隔刷新，未订阅的 URL 临时获取
+            let subscriptions = rss.subscriptions().await?;
+            let targets: Vec<(String, Option<u64>)> = match url {
+                Some(url) => {
+                    let interval = subscriptions.iter().find(|m| m.url == url).map(|m| m.update_interval);
+                    vec![(url, interval.map(|i| i.unwrap_or(DEFAULT_RSS_UPDATE_INTERVAL)))]
                 }
+                None => subscriptions
+                    .into_iter()
+                    .map(|m| (m.url, Some(m.update_interval.unwrap_or(DEFAULT_RSS_UPDATE_INTERVAL))))
+                    .collect(),
+            };
+            if targets.is_empty() {
+                println!("{}", "暂无订阅（使用 `rss add <url>` 添加）".bright_yellow());
             }
 
-            println!();
+            for (url, interval) in targets {
+                let feed = match interval {
+                    Some(interval) => rss.fetch_persistent(&url, interval).await,
+                    None => rss.fetch(&RssFeedQuery { url: url.clone(), ..Default::default() }).await,
+                };
+                match feed {
+                    Ok(feed) => print_feed(&feed, limit),
+                    Err(e) => println!("❌ {}: {}", url, format!("{}", e).bright_red()),
+                }
+            }
+        }
+    }
+
+    cache.flush().map_err(|e| format!("Failed to flush cache: {}", e))?;
+    Ok(())
+}
+
+/// 打印 feed 的最新条目
+fn print_feed(feed: &RssFeed, limit: usize) {
+    println!("{}", format!("📰 {}", feed.meta.title).bright_cyan().bold());
+    println!("{}", "━".repeat(60).bright_black());
+    for item in feed.items.iter().take(limit) {
+        println!("• {}", item.title.bright_white().bold());
+        println!("  {}", item.link.bright_blue());
+        if let Some(ref date) = item.pub_date {
+            println!("  {}", format!("📅 {}", date).bright_black());
+        }
+    }
+    if feed.items.len() > limit {
+        println!("{}", format!("... 还有 {} 个条目（使用 --limit 查看更多）", feed.items.len() - limit).bright_yellow());
+    }
+    println!();
+}
+
+/// 执行缓存管理命令
+fn run_cache_command(action: CacheCommands) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let cache = CacheInterface::new(CacheImplConfig::default())
+        .map_err(|e| format!("Failed to open cache: {}", e))?;
+
+    match action {
+        CacheCommands::Stats => {
+            let stats = cache.manager().stats();
+            println!("{}", "🗄️  缓存统计信息".bright_cyan().bold());
             println!("{}", "━".repeat(60).bright_black());
-            println!("📊 搜索完成: {} 个引擎, {} 个结果",
-                response.engines_used.len().to_string().bright_green(),
-                response.total_count.to_string().bright_white().bold()
+            println!("  {} {}",
+                pad_to_width("总键数", 20).bright_white().bold(),
+                stats.total_keys.to_string().bright_white()
+            );
+            println!("  {} {}",
+                pad_to_width("磁盘占用", 20).bright_white().bold(),
+                format!("{:.2} MB", stats.estimated_size_bytes as f64 / 1024.0 / 1024.0).bright_white()
             );
+
+            let counts = cache.namespace_counts()
+                .map_err(|e| format!("Failed to read cache: {}", e))?;
+            if !counts.is_empty() {
+                println!();
+                println!("{}", "📂 按命名空间".bright_cyan().bold());
+                for (namespace, count) in counts {
+                    let namespace = if namespace.is_empty() { "(无)".to_string() } else { namespace };
+                    println!("  {} {}",
+                        pad_to_width(&namespace, 20).bright_white().bold(),
+                        count.to_string().bright_green()
+                    );
+                }
+            }
+
+            let engines = cache.results().all_engine_stats()
+                .map_err(|e| format!("Failed to read cache: {}", e))?;
+            if !engines.is_empty() {
+                println!();
+                println!("{}", "🔍 按引擎".bright_cyan().bold());
+                for (engine, stats) in engines {
+                    println!("  {} {} {}",
+                        pad_to_width(&engine, 20).bright_white().bold(),
+                        format!("{} 条结果（{} 条过期）", stats.entries, stats.expired).bright_green(),
+                        format!("{:.2} KB · {} 条负面结果", stats.size_bytes as f64 / 1024.0, stats.negative_entries).bright_black()
+                    );
+                }
+            }
         }
-        Err(e) => {
-            println!("❌ 搜索失败: {}", format!("{}", e).bright_red());
-            if debug {
-                println!("🔍 详细错误: {:?}", e);
+        CacheCommands::Clear { yes } => {
+            if !yes {
+                print!("确定要清空所有缓存吗？(y/N): ");
+                io::stdout().flush()?;
+                let mut answer = String::new();
+                io::stdin().read_line(&mut answer)?;
+                if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+                    println!("{}", "已取消".bright_yellow());
+                    return Ok(());
+                }
             }
+            cache.clear_all().map_err(|e| format!("Failed to clear cache: {}", e))?;
+            println!("✅ {}", "缓存已清空".bright_green());
+        }
+        CacheCommands::Cleanup { legacy } => {
+            let removed = cache.cleanup().map_err(|e| format!("Failed to clean up cache: {}", e))?;
+            println!("✅ 已清理 {} 个过期条目", removed.to_string().bright_green());
+            if legacy {
+                let purged = cache.results().purge_legacy_keys()
+                    .map_err(|e| format!("Failed to purge legacy cache keys: {}", e))?;
+                println!("✅ 已删除 {} 个旧版本缓存键", purged.to_string().bright_green());
+            }
+        }
+        CacheCommands::Export { file, include_stale } => {
+            let mut writer = io::BufWriter::new(std::fs::File::create(&file)?);
+            let count = cache.results().export_jsonl(&mut writer, include_stale)
+                .map_err(|e| format!("Failed to export cache: {}", e))?;
+            writer.flush()?;
+            println!("✅ 已导出 {} 条搜索结果到 {}", count.to_string().bright_green(), file.display().to_string().bright_blue());
+        }
+        CacheCommands::Import { file } => {
+            let reader = io::BufReader::new(std::fs::File::open(&file)?);
+            let count = cache.results().import_jsonl(reader)
+                .map_err(|e| format!("Failed to import cache: {}", e))?;
+            println!("✅ 已从 {} 导入 {} 条搜索结果", file.display().to_string().bright_blue(), count.to_string().bright_green());
         }
     }
 
-    // 显示统计信息
-    if verbose {
-        println!();
-        print_search_stats(&search_interface).await;
+    cache.flush().map_err(|e| format!("Failed to flush cache: {}", e))?;
+    Ok(())
+}
+
+/// 执行配置管理命令
+async fn run_config_command(action: ConfigCommands) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match action {
+        ConfigCommands::Validate { file } => {
+            let config = ConfigLoader::new().load_from_file(&file).await?;
+            let report = ConfigValidator::new().generate_report(&config);
+
+            println!("{}", "🔎 配置校验报告".bright_cyan().bold());
+            println!("{}", "━".repeat(60).bright_black());
+            println!("📄 配置文件: {}", file.display().to_string().bright_white());
+            println!("⚙️  运行环境: {}", config.general.environment.to_string().bright_blue());
+            println!();
+
+            for error in &report.errors {
+                println!("❌ {}", error.bright_red());
+            }
+            for warning in &report.warnings {
+                println!("⚠️  {}", warning.bright_yellow());
+            }
+            for recommendation in &report.recommendations {
+                println!("💡 {}", recommendation.bright_black());
+            }
+            if !report.errors.is_empty() || !report.warnings.is_empty() || !report.recommendations.is_empty() {
+                println!();
+            }
+
+            let summary = &report.summary;
+            println!("  {} {}",
+                pad_to_width("规则通过", 20).bright_white().bold(),
+                format!("{}/{}", summary.passed_rules, summary.total_rules).bright_white()
+            );
+            println!("  {} {}",
+                pad_to_width("安全评分", 20).bright_white().bold(),
+                format!("{}/100", summary.security_score).bright_green()
+            );
+            println!("  {} {}",
+                pad_to_width("性能评分", 20).bright_white().bold(),
+                format!("{}/100", summary.performance_score).bright_green()
+            );
+            println!();
+
+            if !report.is_valid {
+                return Err(format!("配置无效: {} 个错误", report.errors.len()).into());
+            }
+            println!("✅ {}", "配置有效".bright_green());
+        }
+        ConfigCommands::Init { env, output, force } => {
+            if output.exists() && !force {
+                return Err(format!("{} 已存在（使用 --force 覆盖）", output.display()).into());
+            }
+
+            let mut config = SeeSeaConfig::for_environment(env);
+            if env == Environment::Production {
+                config.server.secret_key = generate_secret_key();
+            }
+            let content = config.to_commented_toml()?;
+
+            if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&output, content)?;
+            println!("✅ 已生成 {} 环境配置: {}", env.to_string().bright_blue(), output.display().to_string().bright_green());
+        }
     }
 
     Ok(())
 }
 
+/// 生成 64 位十六进制随机密钥
+fn generate_secret_key() -> String {
+    (0..32).map(|_| format!("{:02x}", rand::random::<u8>())).collect()
+}
+
 /// 打印搜索统计信息
 async fn print_search_stats(search_interface: &SearchInterface) {
     println!("{}", "📊 搜索统计信息".bright_cyan().bold());
@@ -314,31 +1446,35 @@ async fn print_search_stats(search_interface: &SearchInterface) {
     let stats = search_interface.get_stats().await;
 
     println!("  {} {}",
-        format!("{:20}", "总搜索次数").bright_white().bold(),
+        pad_to_width("总搜索次数", 20).bright_white().bold(),
         stats.total_searches.to_string().bright_white()
     );
     println!("  {} {}",
-        format!("{:20}", "缓存命中").bright_white().bold(),
+        pad_to_width("缓存命中", 20).bright_white().bold(),
         stats.cache_hits.to_string().bright_green()
     );
     println!("  {} {}",
-        format!("{:20}", "缓存未命中").bright_white().bold(),
+        pad_to_width("缓存未命中", 20).bright_white().bold(),
         stats.cache_misses.to_string().bright_yellow()
     );
     println!("  {} {}",
-        format!("{:20}", "引擎失败").bright_white().bold(),
+        pad_to_width("引擎失败", 20).bright_white().bold(),
         stats.engine_failures.to_string().bright_red()
     );
     println!("  {} {}",
-        format!("{:20}", "超时次数").bright_white().bold(),
+        pad_to_width("超时次数", 20).bright_white().bold(),
         stats.timeouts.to_string().bright_red()
     );
+    println!("  {} {}",
+        pad_to_width("合并请求", 20).bright_white().bold(),
+        stats.coalesced_requests.to_string().bright_green()
+    );
 
     let total_requests = stats.cache_hits + stats.cache_misses;
     if total_requests > 0 {
         let cache_hit_rate = (stats.cache_hits as f64 / total_requests as f64 * 100.0) as u32;
         println!("  {} {}",
-            format!("{:20}", "缓存命中率").bright_white().bold(),
+            pad_to_width("缓存命中率", 20).bright_white().bold(),
             format!("{}%", cache_hit_rate).bright_green()
         );
     }
@@ -370,14 +1506,87 @@ async fn list_engines(show_stats: bool) -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
+/// 交互模式的会话状态
+#[derive(Debug, Default)]
+struct InteractiveSession {
+    /// 当前使用的引擎（为空时使用全局模式）
+    engines: Vec<String>,
+    /// 上一次搜索的查询
+    last_query: Option<String>,
+    /// 上一次搜索的页码
+    page: usize,
+    /// 上一次显示的结果（按显示编号排列）
+    results: Vec<SearchResultItem>,
+}
+
+impl InteractiveSession {
+    /// 当前引擎模式
+    fn mode(&self) -> EngineMode {
+        if self.engines.is_empty() {
+            EngineMode::Global
+        } else {
+            EngineMode::Custom(self.engines.clone())
+        }
+    }
+
+    /// 按当前引擎选择生成搜索选项
+    fn search_options(&self, page: usize) -> SearchOptions {
+        match self.mode() {
+            EngineMode::Global | EngineMode::Category(_) => {
+                SearchOptions { global: true, page: Some(page), ..Default::default() }
+            }
+            EngineMode::Custom(engines) => SearchOptions {
+                engines: Some(engines.join(",")),
+                page: Some(page),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// 记录一次搜索的结果
+    fn record(&mut self, query: String, page: usize, response: Option<SearchResponse>) {
+        self.last_query = Some(query);
+        self.page = page;
+        self.results = response
+            .map(|r| r.results.into_iter().flat_map(|result| result.items).collect())
+            .unwrap_or_default();
+    }
+}
+
+/// 在系统默认浏览器中打开 URL
+fn open_in_browser(url: &str) -> io::Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    command
+        .arg(url)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
 /// 交互式搜索模式
-async fn interactive_mode(use_global: bool) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// 会话默认使用全局模式，`--global` 仅为兼容保留
+async fn interactive_mode(_use_global: bool) -> Result<(), Box<dyn std::error::Error>> {
     println!("{}", "🌊 SeeSea 交互式搜索".bright_cyan().bold());
     println!("{}", "━".repeat(60).bright_black());
     println!("输入查询来搜索，输入 'quit' 或 'exit' 退出");
     println!("输入 'engines' 列出所有引擎");
     println!("输入 'stats' 查看引擎统计信息");
     println!("输入 'mode' 切换运行模式");
+    println!("输入 'use <引擎,...>' 选择引擎，'use all' 恢复全局模式");
+    println!("输入 'open <编号>' 在浏览器中打开结果");
+    println!("输入 'more' 查看下一页");
     println!("{}", "━".repeat(60).bright_black());
     println!();
 
@@ -388,17 +1597,7 @@ async fn interactive_mode(use_global: bool) -> Result<(), Box<dyn std::error::Er
             .map_err(|e| format!("Failed to create search interface: {}", e))?
     );
 
-    let mut mode = if use_global {
-        EngineMode::Global
-    } else {
-        EngineMode::Global
-    };
-
-    let mut configured_engines = if use_global {
-        vec![]
-    } else {
-        vec![]
-    };
+    let mut session = InteractiveSession::default();
 
     loop {
         print!("🔍 > ");
@@ -412,18 +1611,21 @@ async fn interactive_mode(use_global: bool) -> Result<(), Box<dyn std::error::Er
             continue;
         }
 
-        match input.to_lowercase().as_str() {
-            "quit" | "exit" => {
+        let (command, args) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+        let args = args.trim();
+
+        match command.to_lowercase().as_str() {
+            "quit" | "exit" if args.is_empty() => {
                 println!("{}", "👋 再见！".bright_cyan());
                 break;
             }
-            "engines" => {
+            "engines" if args.is_empty() => {
                 list_engines(false).await?;
             }
-            "stats" => {
+            "stats" if args.is_empty() => {
                 print_search_stats(&search_interface).await;
             }
-            "mode" => {
+            "mode" if args.is_empty() => {
                 println!("{}", "\n🔄 选择运行模式:".bright_cyan().bold());
                 println!("1. 全局模式 (所有引擎)");
                 println!("2. 配置模式 (自定义引擎)");
@@ -436,29 +1638,58 @@ async fn interactive_mode(use_global: bool) -> Result<(), Box<dyn std::error::Er
                 match choice.trim() {
                     "1" => {
                         println!("{}", "✅ 切换到全局模式".bright_green());
-                        mode = EngineMode::Global;
-                        configured_engines = vec![];
+                        session.engines.clear();
                     }
                     "2" => {
-                        println!("{}", "✅ 切换到配置模式".bright_yellow());
-                        mode = EngineMode::Custom(configured_engines.clone());
+                        print!("请输入引擎（逗号分隔）: ");
+                        io::stdout().flush()?;
+                        let mut engines = String::new();
+                        io::stdin().read_line(&mut engines)?;
+                        select_engines(&mut session, &search_interface, engines.trim());
                     }
                     _ => {
                         println!("{}", "❌ 无效选择，保持当前模式".bright_red());
                     }
                 };
             }
-            _ => {
-                // 根据当前模式执行搜索
-                match mode {
-                    EngineMode::Global => {
-                        execute_search(input.to_string(), true, None, false, false).await?;
+            "use" if !args.is_empty() => {
+                select_engines(&mut session, &search_interface, args);
+            }
+            "open" if !args.is_empty() => {
+                let item = args
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| n.checked_sub(1))
+                    .and_then(|i| session.results.get(i));
+                match item {
+                    Some(item) => match open_in_browser(&item.url) {
+                        Ok(()) => println!("🌐 已打开: {}", item.url.bright_blue()),
+                        Err(e) => println!("❌ {}", format!("无法打开浏览器: {}", e).bright_red()),
+                    },
+                    None if session.results.is_empty() => {
+                        println!("{}", "❌ 还没有可打开的结果，请先搜索".bright_red());
                     }
-                    EngineMode::Custom(ref engines) => {
-                        execute_search(input.to_string(), false, Some(engines.join(",")), false, false).await?;
+                    None => {
+                        println!("{}", format!("❌ 请输入 1-{} 之间的编号", session.results.len()).bright_red());
+                    }
+                }
+            }
+            "more" if args.is_empty() => {
+                match session.last_query.clone() {
+                    Some(query) => {
+                        let page = session.page + 1;
+                        println!("{}", format!("📄 第 {} 页", page).bright_cyan());
+                        let response = execute_search(query.clone(), session.search_options(page)).await?;
+                        session.record(query, page, response);
                     }
+                    None => println!("{}", "❌ 还没有搜索记录，请先搜索".bright_red()),
                 }
             }
+            _ => {
+                // 使用当前选择的引擎执行新搜索
+                let response = execute_search(input.to_string(), session.search_options(1)).await?;
+                session.record(input.to_string(), 1, response);
+            }
         }
 
         println!();
@@ -467,3 +1698,121 @@ async fn interactive_mode(use_global: bool) -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+/// 设置交互会话使用的引擎
+///
+/// `all` 恢复全局模式，未知的引擎名会被忽略并提示
+fn select_engines(session: &mut InteractiveSession, search_interface: &SearchInterface, spec: &str) {
+    if spec.eq_ignore_ascii_case("all") {
+        session.engines.clear();
+        println!("{}", "✅ 切换到全局模式".bright_green());
+        return;
+    }
+
+    let available = search_interface.list_engines();
+    let (known, unknown): (Vec<String>, Vec<String>) = spec
+        .split(',')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .partition(|name| available.iter().any(|engine| engine == name));
+
+    if !unknown.is_empty() {
+        println!("{}", format!("⚠️  未知引擎: {}", unknown.join(", ")).bright_yellow());
+    }
+    if known.is_empty() {
+        println!("{}", "❌ 没有可用的引擎，保持当前选择".bright_red());
+        return;
+    }
+
+    println!("✅ 使用引擎: {}", known.join(", ").bright_blue());
+    session.engines = known;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seesea_core::derive::{ResultType, SearchResult};
+    use std::collections::HashMap;
+
+    fn response_with(title: &str, url: &str, content: &str) -> SearchResponse {
+        let item = SearchResultItem {
+            title: title.to_string(),
+            url: url.to_string(),
+            content: content.to_string(),
+            display_url: None,
+            site_name: None,
+            score: 0.5,
+            result_type: ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        };
+        SearchResponse {
+            results: vec![SearchResult {
+                engine_name: "bing".to_string(),
+                total_results: None,
+                elapsed_ms: 0,
+                items: vec![item],
+                pagination: None,
+                suggestions: Vec::new(),
+                metadata: HashMap::new(),
+            }],
+            engines_used: vec!["bing".to_string()],
+            total_count: 1,
+            query_time_ms: 0,
+            query: SearchQuery {
+                query: "rust | async".to_string(),
+                ..Default::default()
+            },
+            cached: false,
+            answer: None,
+            suggestions: Vec::new(),
+            per_engine: Vec::new(),
+            partial: false,
+            related_queries: Vec::new(),
+            request_id: None,
+        }
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("line1\nline2"), "\"line1\nline2\"");
+        assert_eq!(csv_field("cr\r"), "\"cr\r\"");
+    }
+
+    #[test]
+    fn test_format_csv_rows() {
+        let response = response_with("Hello, \"World\"", "https://example.com/?a=1,2", "first\nsecond");
+        let csv = format_csv(&response);
+        let mut lines = csv.splitn(2, '\n');
+        assert_eq!(lines.next(), Some("rank,title,url,engine,score,published_date,content"));
+        assert_eq!(
+            lines.next(),
+            Some("1,\"Hello, \"\"World\"\"\",\"https://example.com/?a=1,2\",bing,0.5000,,\"first\nsecond\"\n")
+        );
+    }
+
+    #[test]
+    fn test_markdown_escape() {
+        assert_eq!(markdown_escape("a | b"), "a \\| b");
+        assert_eq!(markdown_escape("[x](y)"), "\\[x\\]\\(y\\)");
+        assert_eq!(markdown_escape("*bold* _it_ `code`"), "\\*bold\\* \\_it\\_ \\`code\\`");
+        assert_eq!(markdown_escape("line1\n\nline2\r\n"), "line1 line2");
+        assert_eq!(markdown_escape("# 标题"), "\\# 标题");
+    }
+
+    #[test]
+    fn test_format_markdown_keeps_items_on_one_line() {
+        let response = response_with("Rust | [Book]", "https://example.com/a (b)", "multi\nline | snippet");
+        let markdown = format_markdown(&response);
+        assert_eq!(
+            markdown,
+            "# rust \\| async\n\n\
+             1. [Rust \\| \\[Book\\]](https://example.com/a%20%28b%29) — bing\n   \
+             multi line \\| snippet\n"
+        );
+    }
+}
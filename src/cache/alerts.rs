@@ -0,0 +1,424 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 保存的搜索与告警存储
+//!
+//! 保存的搜索按计划重新执行，与上次运行时见过的结果链接比较得出新结果，
+//! 新结果作为告警保存。两者存放在缓存数据库的独立树中，不受缓存清空与过期清理影响
+
+use crate::cache::manager::{run_blocking, CacheError, CacheManager, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 保存的搜索所在的树
+const SAVED_SEARCH_TREE: &str = "saved_searches";
+
+/// 告警所在的树
+const ALERT_TREE: &str = "alerts";
+
+/// 每个保存的搜索最多记住的结果链接数（超出时丢弃最早的）
+const MAX_SEEN_URLS: usize = 1000;
+
+/// 保存的搜索的最小执行间隔（秒）
+pub const MIN_INTERVAL_SECS: u64 = 60;
+
+/// 新结果的投递方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertDelivery {
+    /// 保存到告警树
+    #[default]
+    Store,
+    /// 以 Webhook 事件投递
+    Webhook,
+    /// 同时保存与投递
+    Both,
+}
+
+impl AlertDelivery {
+    /// 是否保存到告警树
+    pub fn stores(&self) -> bool {
+        matches!(self, Self::Store | Self::Both)
+    }
+
+    /// 是否以 Webhook 投递
+    pub fn notifies(&self) -> bool {
+        matches!(self, Self::Webhook | Self::Both)
+    }
+}
+
+impl std::str::FromStr for AlertDelivery {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "store" => Ok(Self::Store),
+            "webhook" => Ok(Self::Webhook),
+            "both" => Ok(Self::Both),
+            other => Err(format!("未知的投递方式: {}（可选 store/webhook/both）", other)),
+        }
+    }
+}
+
+/// 保存的搜索
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearch {
+    /// ID
+    pub id: String,
+    /// 查询文本
+    pub query: String,
+    /// 使用的引擎（为空时使用默认引擎）
+    pub engines: Vec<String>,
+    /// 执行间隔（秒）
+    pub interval_secs: u64,
+    /// 新结果的投递方式
+    pub delivery: AlertDelivery,
+    /// 创建时间（Unix 时间戳）
+    pub created_at: u64,
+    /// 上次执行时间（Unix 时间戳，从未执行时为 None）
+    pub last_run: Option<u64>,
+    /// 上次执行为止见过的结果链接
+    pub seen_urls: Vec<String>,
+}
+
+/// 告警中的一条新结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertResult {
+    /// 标题
+    pub title: String,
+    /// 链接
+    pub url: String,
+    /// 摘要
+    pub content: String,
+}
+
+/// 告警：保存的搜索一次执行中出现的新结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    /// ID（按创建时间排序）
+    pub id: String,
+    /// 所属保存的搜索 ID
+    pub saved_search_id: String,
+    /// 查询文本
+    pub query: String,
+    /// 创建时间（Unix 时间戳）
+    pub created_at: u64,
+    /// 新结果
+    pub results: Vec<AlertResult>,
+}
+
+impl SavedSearch {
+    /// 创建保存的搜索
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 查询文本
+    /// * `engines` - 使用的引擎（为空时使用默认引擎）
+    /// * `interval_secs` - 执行间隔（秒，小于最小间隔时取最小间隔）
+    /// * `delivery` - 新结果的投递方式
+    pub fn new(query: &str, engines: Vec<String>, interval_secs: u64, delivery: AlertDelivery) -> Self {
+        Self {
+            id: format!("{:016x}", rand::random::<u64>()),
+            query: query.trim().to_string(),
+            engines,
+            interval_secs: interval_secs.max(MIN_INTERVAL_SECS),
+            delivery,
+            created_at: now_secs(),
+            last_run: None,
+            seen_urls: Vec::new(),
+        }
+    }
+
+    /// 在 `now` 时刻是否需要执行
+    pub fn is_due(&self, now: u64) -> bool {
+        self.last_run
+            .is_none_or(|last_run| now.saturating_sub(last_run) >= self.interval_secs)
+    }
+
+    /// 记录一次执行的结果
+    ///
+    /// 首次执行只建立基线，不产生新结果
+    ///
+    /// # 参数
+    ///
+    /// * `results` - 本次执行得到的结果
+    /// * `now` - 执行时间（Unix 时间戳）
+    ///
+    /// # 返回值
+    ///
+    /// 上次执行以来新出现的结果
+    pub fn record_run(&mut self, results: Vec<AlertResult>, now: u64) -> Vec<AlertResult> {
+        let first_run = self.last_run.is_none();
+        self.last_run = Some(now);
+
+        let mut seen: HashSet<String> = self.seen_urls.iter().cloned().collect();
+        let fresh: Vec<_> = results
+            .into_iter()
+            .filter(|result| seen.insert(result.url.clone()))
+            .collect();
+
+        self.seen_urls.extend(fresh.iter().map(|result| result.url.clone()));
+        if self.seen_urls.len() > MAX_SEEN_URLS {
+            let excess = self.seen_urls.len() - MAX_SEEN_URLS;
+            self.seen_urls.drain(..excess);
+        }
+
+        if first_run { Vec::new() } else { fresh }
+    }
+}
+
+impl Alert {
+    /// 为保存的搜索的新结果创建告警
+    pub fn new(search: &SavedSearch, results: Vec<AlertResult>) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            id: format!("{:012x}{:08x}", now.as_millis(), rand::random::<u32>()),
+            saved_search_id: search.id.clone(),
+            query: search.query.clone(),
+            created_at: now.as_secs(),
+            results,
+        }
+    }
+}
+
+/// 保存的搜索与告警存储
+#[derive(Clone)]
+pub struct AlertStore {
+    /// 保存的搜索（ID → 记录）
+    searches: sled::Tree,
+    /// 告警（ID → 记录）
+    alerts: sled::Tree,
+}
+
+impl AlertStore {
+    /// 在缓存数据库中打开存储
+    ///
+    /// # 参数
+    ///
+    /// * `manager` - 缓存管理器
+    pub fn new(manager: &CacheManager) -> Result<Self> {
+        Ok(Self {
+            searches: manager.open_tree(SAVED_SEARCH_TREE)?,
+            alerts: manager.open_tree(ALERT_TREE)?,
+        })
+    }
+
+    /// 保存（新建或更新）保存的搜索
+    pub fn save_search(&self, search: &SavedSearch) -> Result<()> {
+        insert(&self.searches, &search.id, search)
+    }
+
+    /// 获取保存的搜索
+    pub fn get_search(&self, id: &str) -> Result<Option<SavedSearch>> {
+        match self.searches.get(id.as_bytes()) {
+            Ok(Some(data)) => decode(&data).map(Some),
+            Ok(None) => Ok(None),
+            Err(e) => Err(CacheError::DatabaseError(format!("读取保存的搜索失败: {}", e))),
+        }
+    }
+
+    /// 列出所有保存的搜索（按创建时间排序）
+    pub fn list_searches(&self) -> Result<Vec<SavedSearch>> {
+        let mut searches: Vec<SavedSearch> = list(&self.searches)?;
+        searches.sort_by_key(|search| search.created_at);
+        Ok(searches)
+    }
+
+    /// 删除保存的搜索及其告警
+    ///
+    /// # 返回值
+    ///
+    /// 保存的搜索存在时返回 true
+    pub fn delete_search(&self, id: &str) -> Result<bool> {
+        let removed = self
+            .searches
+            .remove(id.as_bytes())
+            .map_err(|e| CacheError::DatabaseError(format!("删除保存的搜索失败: {}", e)))?
+            .is_some();
+
+        for alert in self.list_alerts(Some(id))? {
+            self.delete_alert(&alert.id)?;
+        }
+        Ok(removed)
+    }
+
+    /// 保存告警
+    pub fn push_alert(&self, alert: &Alert) -> Result<()> {
+        insert(&self.alerts, &alert.id, alert)
+    }
+
+    /// 列出告警（最新的在前）
+    ///
+    /// # 参数
+    ///
+    /// * `saved_search_id` - 只列出该保存的搜索的告警（None 时列出全部）
+    pub fn list_alerts(&self, saved_search_id: Option<&str>) -> Result<Vec<Alert>> {
+        let mut alerts: Vec<Alert> = list(&self.alerts)?;
+        alerts.retain(|alert| saved_search_id.is_none_or(|id| alert.saved_search_id == id));
+        alerts.reverse();
+        Ok(alerts)
+    }
+
+    /// 删除告警
+    ///
+    /// # 返回值
+    ///
+    /// 告警存在时返回 true
+    pub fn delete_alert(&self, id: &str) -> Result<bool> {
+        self.alerts
+            .remove(id.as_bytes())
+            .map(|old| old.is_some())
+            .map_err(|e| CacheError::DatabaseError(format!("删除告警失败: {}", e)))
+    }
+
+    /// 异步保存保存的搜索（在阻塞线程池中执行）
+    pub async fn save_search_async(&self, search: SavedSearch) -> Result<()> {
+        let store = self.clone();
+        run_blocking(move || store.save_search(&search)).await
+    }
+
+    /// 异步列出所有保存的搜索（在阻塞线程池中执行）
+    pub async fn list_searches_async(&self) -> Result<Vec<SavedSearch>> {
+        let store = self.clone();
+        run_blocking(move || store.list_searches()).await
+    }
+
+    /// 异步删除保存的搜索及其告警（在阻塞线程池中执行）
+    pub async fn delete_search_async(&self, id: &str) -> Result<bool> {
+        let (store, id) = (self.clone(), id.to_string());
+        run_blocking(move || store.delete_search(&id)).await
+    }
+
+    /// 异步保存告警（在阻塞线程池中执行）
+    pub async fn push_alert_async(&self, alert: Alert) -> Result<()> {
+        let store = self.clone();
+        run_blocking(move || store.push_alert(&alert)).await
+    }
+
+    /// 异步列出告警（在阻塞线程池中执行）
+    pub async fn list_alerts_async(&self, saved_search_id: Option<&str>) -> Result<Vec<Alert>> {
+        let (store, id) = (self.clone(), saved_search_id.map(str::to_string));
+        run_blocking(move || store.list_alerts(id.as_deref())).await
+    }
+
+    /// 异步删除告警（在阻塞线程池中执行）
+    pub async fn delete_alert_async(&self, id: &str) -> Result<bool> {
+        let (store, id) = (self.clone(), id.to_string());
+        run_blocking(move || store.delete_alert(&id)).await
+    }
+}
+
+/// 当前 Unix 时间戳（秒）
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 序列化并写入记录
+fn insert<T: Serialize>(tree: &sled::Tree, key: &str, value: &T) -> Result<()> {
+    let data = bincode::serde::encode_to_vec(value, bincode::config::standard())
+        .map_err(|e| CacheError::SerializationError(format!("序列化失败: {}", e)))?;
+    tree.insert(key.as_bytes(), data)
+        .map_err(|e| CacheError::DatabaseError(format!("写入失败: {}", e)))?;
+    Ok(())
+}
+
+/// 反序列化记录
+fn decode<T: for<'de> Deserialize<'de>>(data: &[u8]) -> Result<T> {
+    bincode::serde::decode_from_slice(data, bincode::config::standard())
+        .map(|(value, _)| value)
+        .map_err(|e| CacheError::SerializationError(format!("反序列化失败: {}", e)))
+}
+
+/// 按键顺序读取树中所有记录（跳过损坏的数据）
+fn list<T: for<'de> Deserialize<'de>>(tree: &sled::Tree) -> Result<Vec<T>> {
+    let mut values = Vec::new();
+    for item in tree.iter() {
+        let (_, data) = item.map_err(|e| CacheError::DatabaseError(format!("遍历失败: {}", e)))?;
+        if let Ok(value) = decode(&data) {
+            values.push(value);
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::CacheImplConfig;
+
+    fn result(url: &str) -> AlertResult {
+        AlertResult {
+            title: url.to_string(),
+            url: url.to_string(),
+            content: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_run_diffs_against_seen_urls() {
+        let mut search = SavedSearch::new("rust", Vec::new(), 10, AlertDelivery::Store);
+        assert_eq!(search.interval_secs, MIN_INTERVAL_SECS);
+        assert!(search.is_due(0));
+
+        // 首次执行只建立基线
+        assert!(search.record_run(vec![result("a"), result("b")], 100).is_empty());
+        assert!(!search.is_due(120));
+        assert!(search.is_due(160));
+
+        let fresh = search.record_run(vec![result("b"), result("c"), result("c")], 160);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].url, "c");
+        assert_eq!(search.seen_urls, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_alert_store_roundtrip() {
+        let db_path = std::env::temp_dir().join(format!("test_alert_store_{}", std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let manager = CacheManager::instance(config).unwrap();
+        let store = AlertStore::new(&manager).unwrap();
+
+        let search = SavedSearch::new("rust", vec!["bing".to_string()], 300, AlertDelivery::Both);
+        store.save_search(&search).unwrap();
+        assert_eq!(store.list_searches().unwrap().len(), 1);
+
+        let first = Alert::new(&search, vec![result("a")]);
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = Alert::new(&search, vec![result("b")]);
+        store.push_alert(&first).unwrap();
+        store.push_alert(&second).unwrap();
+
+        let alerts = store.list_alerts(Some(&search.id)).unwrap();
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].id, second.id);
+        assert!(store.list_alerts(Some("other")).unwrap().is_empty());
+
+        assert!(store.delete_alert(&first.id).unwrap());
+        assert!(store.delete_search(&search.id).unwrap());
+        assert!(store.list_alerts(None).unwrap().is_empty());
+        assert!(store.get_search(&search.id).unwrap().is_none());
+
+        CacheManager::release(&db_path.to_string_lossy());
+        let _ = std::fs::remove_dir_all(&db_path);
+    }
+}
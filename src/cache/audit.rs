@@ -0,0 +1,244 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 审计日志
+//!
+//! 只追加的审计记录：认证失败、API 密钥使用异常（超出限流）与管理操作（如清除缓存）。
+//! 记录中的调用方只保存加盐哈希后的标识，不保存 API 密钥或密钥名称本身；
+//! 记录键由创建时间与随机数组成，按时间排序，写入后不提供修改或删除接口
+
+use crate::cache::manager::{run_blocking, CacheError, CacheManager, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 审计记录所在的树
+const AUDIT_TREE: &str = "audit";
+
+/// 调用方标识哈希的盐
+const PRINCIPAL_SALT: &[u8] = b"seesea-audit-principal";
+
+/// 调用方标识哈希保留的十六进制位数
+const PRINCIPAL_HASH_LEN: usize = 16;
+
+/// 审计事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// 认证失败（缺少密钥、密钥无效或已过期）
+    AuthFailure,
+    /// 超出配置档的请求频率
+    RateLimited,
+    /// 管理操作
+    AdminAction,
+}
+
+impl AuditEvent {
+    /// 事件名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEvent::AuthFailure => "auth_failure",
+            AuditEvent::RateLimited => "rate_limited",
+            AuditEvent::AdminAction => "admin_action",
+        }
+    }
+}
+
+/// 一条审计记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// ID（按创建时间排序）
+    pub id: String,
+    /// 事件类型
+    pub event: AuditEvent,
+    /// 调用方标识的哈希（见 [`principal_id`]）
+    pub principal: String,
+    /// 操作或失败原因（如 `cache.clear`、`invalid_key`）
+    pub action: String,
+    /// 请求路径
+    pub path: Option<String>,
+    /// 请求 ID
+    pub request_id: Option<String>,
+    /// 创建时间（Unix 时间戳）
+    pub timestamp: u64,
+}
+
+impl AuditEntry {
+    /// 创建审计记录
+    ///
+    /// # 参数
+    ///
+    /// * `event` - 事件类型
+    /// * `principal` - 调用方标识（已哈希，见 [`principal_id`]）
+    /// * `action` - 操作或失败原因
+    pub fn new(event: AuditEvent, principal: String, action: &str) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            id: format!("{:012x}{:08x}", now.as_millis(), fastrand::u32(..)),
+            event,
+            principal,
+            action: action.to_string(),
+            path: None,
+            request_id: None,
+            timestamp: now.as_secs(),
+        }
+    }
+
+    /// 设置请求路径
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// 设置请求 ID
+    pub fn with_request_id(mut self, request_id: Option<&str>) -> Self {
+        self.request_id = request_id.map(str::to_string);
+        self
+    }
+}
+
+/// 计算调用方标识的哈希（加盐 SHA-256 的前 16 位十六进制）
+///
+/// 同一调用方的记录可以关联，但无法从记录还原密钥或密钥名称
+pub fn principal_id(identity: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(PRINCIPAL_SALT)
+        .chain_update(identity.as_bytes())
+        .finalize();
+    digest
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>()[..PRINCIPAL_HASH_LEN]
+        .to_string()
+}
+
+/// 只追加的审计日志
+#[derive(Clone)]
+pub struct AuditLog {
+    /// 记录（ID → JSON）
+    tree: sled::Tree,
+}
+
+impl AuditLog {
+    /// 在缓存数据库中打开审计日志
+    ///
+    /// # 参数
+    ///
+    /// * `manager` - 缓存管理器
+    pub fn new(manager: &CacheManager) -> Result<Self> {
+        Ok(Self {
+            tree: manager.open_tree(AUDIT_TREE)?,
+        })
+    }
+
+    /// 追加一条记录（ID 已存在时不覆盖）
+    pub fn record(&self, entry: &AuditEntry) -> Result<()> {
+        let data = serde_json::to_vec(entry)
+            .map_err(|e| CacheError::SerializationError(format!("序列化失败: {}", e)))?;
+        self.tree
+            .compare_and_swap(entry.id.as_bytes(), None as Option<&[u8]>, Some(data))
+            .map_err(|e| CacheError::DatabaseError(format!("写入审计记录失败: {}", e)))?
+            .map_err(|_| CacheError::DatabaseError(format!("审计记录 {} 已存在", entry.id)))
+    }
+
+    /// 列出审计记录（最新的在前）
+    ///
+    /// # 参数
+    ///
+    /// * `event` - 只返回该类型的记录（None 时不过滤）
+    /// * `since` - 只返回不早于该时间（Unix 时间戳）的记录
+    /// * `limit` - 最多返回的记录数
+    pub fn list(&self, event: Option<AuditEvent>, since: Option<u64>, limit: usize) -> Result<Vec<AuditEntry>> {
+        let mut entries = Vec::new();
+        for item in self.tree.iter().values().rev() {
+            if entries.len() >= limit {
+                break;
+            }
+            let data = item.map_err(|e| CacheError::DatabaseError(format!("遍历失败: {}", e)))?;
+            let Ok(entry) = serde_json::from_slice::<AuditEntry>(&data) else {
+                continue;
+            };
+            if since.is_some_and(|since| entry.timestamp < since) {
+                break;
+            }
+            if event.is_none_or(|event| entry.event == event) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 记录数
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// 是否没有记录
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// 异步追加一条记录（在阻塞线程池中执行）
+    pub async fn record_async(&self, entry: AuditEntry) -> Result<()> {
+        let log = self.clone();
+        run_blocking(move || log.record(&entry)).await
+    }
+
+    /// 异步列出审计记录（在阻塞线程池中执行）
+    pub async fn list_async(&self, event: Option<AuditEvent>, since: Option<u64>, limit: usize) -> Result<Vec<AuditEntry>> {
+        let log = self.clone();
+        run_blocking(move || log.list(event, since, limit)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::CacheImplConfig;
+    use std::sync::Arc;
+
+    fn manager(name: &str) -> Arc<CacheManager> {
+        let db_path = std::env::temp_dir().join(format!("test_audit_{}_{}", name, std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        CacheManager::instance(config).unwrap()
+    }
+
+    #[test]
+    fn test_audit_log_append_and_list() {
+        let log = AuditLog::new(&manager("list")).unwrap();
+        let failure = AuditEntry::new(AuditEvent::AuthFailure, principal_id("secret-key"), "invalid_key")
+            .with_path("/api/search");
+        let clear = AuditEntry::new(AuditEvent::AdminAction, principal_id("ops"), "cache.clear");
+        log.record(&failure).unwrap();
+        log.record(&clear).unwrap();
+        assert!(log.record(&clear).is_err());
+        assert_eq!(log.len(), 2);
+
+        let entries = log.list(None, None, 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        let admin = log.list(Some(AuditEvent::AdminAction), None, 10).unwrap();
+        assert_eq!(admin, vec![clear]);
+        assert!(log.list(None, Some(u64::MAX), 10).unwrap().is_empty());
+
+        // 记录中不含密钥明文
+        assert_eq!(failure.principal.len(), PRINCIPAL_HASH_LEN);
+        assert!(!failure.principal.contains("secret"));
+        assert_eq!(failure.principal, principal_id("secret-key"));
+    }
+}
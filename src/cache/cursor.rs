@@ -0,0 +1,132 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 引擎续页令牌缓存
+//!
+//! 使用游标/偏移分页的引擎在返回第 N 页时给出第 N+1 页的令牌，
+//! 这里按 (查询, 引擎, 页码) 保存，请求下一页时取回
+
+use crate::cache::manager::{CacheManager, CacheError};
+use crate::derive::types::SearchQuery;
+use std::sync::Arc;
+use std::time::Duration;
+
+type Result<T> = std::result::Result<T, CacheError>;
+
+/// 续页令牌缓存键前缀
+const CURSOR_KEY_PREFIX: &str = "cursor:";
+
+/// 续页令牌默认有效期（多数引擎的游标在数分钟到数小时后失效）
+const DEFAULT_CURSOR_TTL: Duration = Duration::from_secs(3600);
+
+/// 续页令牌缓存
+pub struct CursorCache {
+    manager: Arc<CacheManager>,
+}
+
+impl CursorCache {
+    /// 创建续页令牌缓存实例
+    ///
+    /// # 参数
+    ///
+    /// * `manager` - 缓存管理器（Arc包装）
+    pub fn new(manager: Arc<CacheManager>) -> Self {
+        Self { manager }
+    }
+
+    /// 生成续页令牌缓存键
+    ///
+    /// 与结果缓存键相同的查询字段参与哈希，但不含页码以外的分页参数
+    pub fn generate_key(query: &SearchQuery, engine_name: &str, page: usize) -> String {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        query.query.hash(&mut hasher);
+        query.page_size.hash(&mut hasher);
+        query.language.hash(&mut hasher);
+        query.region.hash(&mut hasher);
+        query.time_range.hash(&mut hasher);
+        engine_name.hash(&mut hasher);
+
+        format!("{}{:x}:{}", CURSOR_KEY_PREFIX, hasher.finish(), page)
+    }
+
+    /// 获取请求指定页所需的续页令牌
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 搜索查询
+    /// * `engine_name` - 引擎名称
+    /// * `page` - 要请求的页码
+    ///
+    /// # 返回值
+    ///
+    /// 返回续页令牌，不存在或已过期时返回 None
+    pub fn get(&self, query: &SearchQuery, engine_name: &str, page: usize) -> Result<Option<String>> {
+        let key = Self::generate_key(query, engine_name, page);
+        Ok(self
+            .manager
+            .get(&key)?
+            .map(|data| String::from_utf8_lossy(&data).into_owned()))
+    }
+
+    /// 保存请求指定页所需的续页令牌
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 搜索查询
+    /// * `engine_name` - 引擎名称
+    /// * `page` - 令牌对应的页码（通常为当前页 + 1）
+    /// * `cursor` - 续页令牌
+    pub fn set(&self, query: &SearchQuery, engine_name: &str, page: usize, cursor: &str) -> Result<()> {
+        let key = Self::generate_key(query, engine_name, page);
+        self.manager.set(key, cursor.as_bytes().to_vec(), Some(DEFAULT_CURSOR_TTL))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::{CacheImplConfig, CacheMode};
+    use serial_test::serial;
+
+    fn temp_cursor_cache() -> CursorCache {
+        let db_path = std::env::temp_dir().join(format!("test_cursor_cache_{}", std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            default_ttl_secs: 10,
+            max_size_bytes: 1024 * 1024,
+            enabled: true,
+            compression: false,
+            mode: CacheMode::HighThroughput,
+        };
+        CursorCache::new(CacheManager::instance(config).expect("Failed to create cache manager"))
+    }
+
+    #[test]
+    #[serial]
+    fn test_cursor_set_and_get() {
+        let cache = temp_cursor_cache();
+        let query = SearchQuery {
+            query: "cursor pagination test".to_string(),
+            ..Default::default()
+        };
+
+        cache.set(&query, "crossref", 2, "token-2").expect("保存续页令牌失败");
+        assert_eq!(cache.get(&query, "crossref", 2).unwrap().as_deref(), Some("token-2"));
+        assert_eq!(cache.get(&query, "crossref", 3).unwrap(), None);
+        assert_eq!(cache.get(&query, "semantic_scholar", 2).unwrap(), None);
+    }
+}
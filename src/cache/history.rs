@@ -0,0 +1,472 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 加密的搜索历史存储
+//!
+//! 默认关闭，需在配置中显式启用并提供加密口令。记录的查询与点击结果整体加密后保存：
+//! 密钥由口令与存储专属的随机盐经 Argon2id 派生，每条记录以随机 96 位 nonce 做
+//! ChaCha20-Poly1305 认证加密（记录 ID 作为附加数据），口令错误或数据被篡改时记录无法读出。
+//! 记录键只含创建时间与随机数，用于按时间排序与保留期过期清理，不含任何明文内容
+
+use crate::cache::manager::{run_blocking, CacheError, CacheManager, Result};
+use crate::config::privacy::{HistoryConfig, MIN_HISTORY_KEY_CHARS};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 历史记录所在的树
+const HISTORY_TREE: &str = "history";
+
+/// 存储参数（密钥派生盐）所在的树，清除记录时保留
+const HISTORY_META_TREE: &str = "history_meta";
+
+/// 密钥派生盐的键
+const SALT_KEY: &[u8] = b"salt";
+
+/// 密钥派生盐长度
+const SALT_LEN: usize = 16;
+
+/// 密文格式版本
+const FORMAT_VERSION: u8 = 2;
+
+/// nonce 长度
+const NONCE_LEN: usize = 12;
+
+/// 认证标签长度
+const TAG_LEN: usize = 16;
+
+/// 记录 ID 中时间戳部分的长度（毫秒的十六进制）
+const ID_TIME_LEN: usize = 12;
+
+/// 历史记录类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryKind {
+    /// 执行的查询
+    Query,
+    /// 点击的结果
+    Click,
+}
+
+/// 一条历史记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// ID（按创建时间排序）
+    pub id: String,
+    /// 记录类型
+    pub kind: HistoryKind,
+    /// 查询文本（点击记录可能为空）
+    pub query: String,
+    /// 点击的结果链接
+    pub url: Option<String>,
+    /// 点击的结果标题
+    pub title: Option<String>,
+    /// 使用的引擎
+    pub engines: Vec<String>,
+    /// 创建时间（Unix 时间戳）
+    pub timestamp: u64,
+}
+
+impl HistoryEntry {
+    /// 创建查询记录
+    pub fn query(query: &str, engines: Vec<String>) -> Self {
+        Self::new(HistoryKind::Query, query, None, None, engines)
+    }
+
+    /// 创建点击记录
+    pub fn click(query: &str, url: &str, title: Option<&str>) -> Self {
+        Self::new(
+            HistoryKind::Click,
+            query,
+            Some(url.to_string()),
+            title.map(str::to_string),
+            Vec::new(),
+        )
+    }
+
+    fn new(kind: HistoryKind, query: &str, url: Option<String>, title: Option<String>, engines: Vec<String>) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            id: format!("{:012x}{:08x}", now.as_millis(), rand::random::<u32>()),
+            kind,
+            query: query.trim().to_string(),
+            url,
+            title,
+            engines,
+            timestamp: now.as_secs(),
+        }
+    }
+
+    /// 是否匹配搜索文本（查询、标题或链接包含该文本，不区分大小写）
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        [Some(&self.query), self.title.as_ref(), self.url.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|field| field.to_lowercase().contains(&text))
+    }
+}
+
+/// 从记录 ID 读取创建时间（毫秒）
+fn id_millis(id: &[u8]) -> Option<u64> {
+    let prefix = std::str::from_utf8(id.get(..ID_TIME_LEN)?).ok()?;
+    u64::from_str_radix(prefix, 16).ok()
+}
+
+/// 记录加密器
+///
+/// 密钥由口令与存储的随机盐经 Argon2id 派生，离线暴力破解口令需要为每个候选口令重复派生
+struct HistoryCipher {
+    aead: ChaCha20Poly1305,
+}
+
+impl HistoryCipher {
+    fn new(passphrase: &str, salt: &[u8]) -> Result<Self> {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| CacheError::EncryptionError(format!("派生历史记录密钥失败: {}", e)))?;
+        Ok(Self {
+            aead: ChaCha20Poly1305::new(&key),
+        })
+    }
+
+    /// 加密：`版本 || nonce || 密文与认证标签`，版本与记录 ID 作为附加数据参与认证
+    fn seal(&self, id: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = rand::random::<[u8; NONCE_LEN]>();
+        let aad = [&[FORMAT_VERSION], id].concat();
+        let ciphertext = self
+            .aead
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: &aad })
+            .map_err(|_| CacheError::EncryptionError("加密历史记录失败".to_string()))?;
+
+        let mut sealed = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+        sealed.push(FORMAT_VERSION);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// 校验并解密，口令错误、数据损坏或记录被移到其他 ID 时返回错误
+    fn open(&self, id: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+        if sealed.len() < 1 + NONCE_LEN + TAG_LEN || sealed[0] != FORMAT_VERSION {
+            return Err(CacheError::EncryptionError("历史记录格式无效".to_string()));
+        }
+        let (nonce, ciphertext) = sealed[1..].split_at(NONCE_LEN);
+        let aad = [&[FORMAT_VERSION], id].concat();
+        self.aead
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: &aad })
+            .map_err(|_| CacheError::EncryptionError("历史记录认证失败（口令错误或数据损坏）".to_string()))
+    }
+}
+
+/// 读取存储的密钥派生盐，首次打开时生成并保存
+fn store_salt(meta: &sled::Tree) -> Result<Vec<u8>> {
+    let salt = rand::random::<[u8; SALT_LEN]>();
+    // 并发首次打开时只有一个盐被保存，其余调用读取已保存的盐
+    let _ = meta
+        .compare_and_swap(SALT_KEY, None as Option<&[u8]>, Some(&salt[..]))
+        .map_err(|e| CacheError::DatabaseError(format!("保存密钥派生盐失败: {}", e)))?;
+    meta.get(SALT_KEY)
+        .map_err(|e| CacheError::DatabaseError(format!("读取密钥派生盐失败: {}", e)))?
+        .map(|salt| salt.to_vec())
+        .ok_or_else(|| CacheError::DatabaseError("密钥派生盐丢失".to_string()))
+}
+
+/// 加密的搜索历史存储
+#[derive(Clone)]
+pub struct HistoryStore {
+    /// 记录（ID → 密文）
+    tree: sled::Tree,
+    /// 加密器
+    cipher: Arc<HistoryCipher>,
+    /// 保留时长（秒）
+    retention_secs: u64,
+}
+
+impl HistoryStore {
+    /// 在缓存数据库中打开存储
+    ///
+    /// # 参数
+    ///
+    /// * `manager` - 缓存管理器
+    /// * `config` - 搜索历史配置（需提供加密口令）
+    pub fn new(manager: &CacheManager, config: &HistoryConfig) -> Result<Self> {
+        let passphrase = config
+            .encryption_key
+            .as_deref()
+            .filter(|key| key.chars().count() >= MIN_HISTORY_KEY_CHARS)
+            .ok_or_else(|| {
+                CacheError::EncryptionError(format!("搜索历史需要至少 {} 个字符的加密口令", MIN_HISTORY_KEY_CHARS))
+            })?;
+        let salt = store_salt(&manager.open_tree(HISTORY_META_TREE)?)?;
+        Ok(Self {
+            tree: manager.open_tree(HISTORY_TREE)?,
+            cipher: Arc::new(HistoryCipher::new(passphrase, &salt)?),
+            retention_secs: u64::from(config.retention_days) * 86400,
+        })
+    }
+
+    /// 保留期起点（毫秒），早于该时间的记录视为过期
+    fn cutoff_millis(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        now.saturating_sub(self.retention_secs * 1000)
+    }
+
+    /// 加密并保存一条记录
+    pub fn record(&self, entry: &HistoryEntry) -> Result<()> {
+        let data = bincode::serde::encode_to_vec(entry, bincode::config::standard())
+            .map_err(|e| CacheError::SerializationError(format!("序列化失败: {}", e)))?;
+        self.tree
+            .insert(entry.id.as_bytes(), self.cipher.seal(entry.id.as_bytes(), &data)?)
+            .map_err(|e| CacheError::DatabaseError(format!("写入历史记录失败: {}", e)))?;
+        Ok(())
+    }
+
+    /// 列出历史记录（最新的在前，跳过过期与无法解密的记录）
+    ///
+    /// # 参数
+    ///
+    /// * `filter` - 只保留匹配该文本的记录（None 时不过滤）
+    /// * `limit` - 最多返回的记录数
+    pub fn list(&self, filter: Option<&str>, limit: usize) -> Result<Vec<HistoryEntry>> {
+        let cutoff = self.cutoff_millis();
+        let mut entries = Vec::new();
+        for item in self.tree.iter().rev() {
+            if entries.len() >= limit {
+                break;
+            }
+            let (key, sealed) = item.map_err(|e| CacheError::DatabaseError(format!("遍历失败: {}", e)))?;
+            if id_millis(&key).is_none_or(|millis| millis < cutoff) {
+                break;
+            }
+            let Ok(data) = self.cipher.open(&key, &sealed) else {
+                continue;
+            };
+            let Ok((entry, _)) =
+                bincode::serde::decode_from_slice::<HistoryEntry, _>(&data, bincode::config::standard())
+            else {
+                continue;
+            };
+            if filter.is_none_or(|text| entry.matches(text)) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 删除一条记录
+    ///
+    /// # 返回值
+    ///
+    /// 记录存在时返回 true
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        self.tree
+            .remove(id.as_bytes())
+            .map(|old| old.is_some())
+            .map_err(|e| CacheError::DatabaseError(format!("删除历史记录失败: {}", e)))
+    }
+
+    /// 清除记录
+    ///
+    /// # 参数
+    ///
+    /// * `before` - 只清除早于该时间（Unix 时间戳）的记录，None 时清除全部
+    ///
+    /// # 返回值
+    ///
+    /// 清除的记录数
+    pub fn purge(&self, before: Option<u64>) -> Result<usize> {
+        let Some(before) = before else {
+            let count = self.tree.len();
+            self.tree
+                .clear()
+                .map_err(|e| CacheError::DatabaseError(format!("清除历史记录失败: {}", e)))?;
+            return Ok(count);
+        };
+        self.remove_before(before.saturating_mul(1000))
+    }
+
+    /// 删除超过保留期的记录
+    ///
+    /// # 返回值
+    ///
+    /// 删除的记录数
+    pub fn expire(&self) -> Result<usize> {
+        self.remove_before(self.cutoff_millis())
+    }
+
+    /// 删除早于 `cutoff`（毫秒）的记录；记录键按时间排序，遇到更新的记录即停止
+    fn remove_before(&self, cutoff: u64) -> Result<usize> {
+        let mut removed = 0;
+        for key in self.tree.iter().keys() {
+            let key = key.map_err(|e| CacheError::DatabaseError(format!("遍历失败: {}", e)))?;
+            if id_millis(&key).is_some_and(|millis| millis >= cutoff) {
+                break;
+            }
+            self.tree
+                .remove(&key)
+                .map_err(|e| CacheError::DatabaseError(format!("删除历史记录失败: {}", e)))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// 异步保存一条记录（在阻塞线程池中执行）
+    pub async fn record_async(&self, entry: HistoryEntry) -> Result<()> {
+        let store = self.clone();
+        run_blocking(move || store.record(&entry)).await
+    }
+
+    /// 异步列出历史记录（在阻塞线程池中执行）
+    pub async fn list_async(&self, filter: Option<&str>, limit: usize) -> Result<Vec<HistoryEntry>> {
+        let (store, filter) = (self.clone(), filter.map(str::to_string));
+        run_blocking(move || store.list(filter.as_deref(), limit)).await
+    }
+
+    /// 异步删除一条记录（在阻塞线程池中执行）
+    pub async fn delete_async(&self, id: &str) -> Result<bool> {
+        let (store, id) = (self.clone(), id.to_string());
+        run_blocking(move || store.delete(&id)).await
+    }
+
+    /// 异步清除记录（在阻塞线程池中执行）
+    pub async fn purge_async(&self, before: Option<u64>) -> Result<usize> {
+        let store = self.clone();
+        run_blocking(move || store.purge(before)).await
+    }
+
+    /// 异步删除超过保留期的记录（在阻塞线程池中执行）
+    pub async fn expire_async(&self) -> Result<usize> {
+        let store = self.clone();
+        run_blocking(move || store.expire()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::CacheImplConfig;
+
+    fn config(key: &str) -> HistoryConfig {
+        HistoryConfig {
+            enabled: true,
+            encryption_key: Some(key.to_string()),
+            retention_days: 30,
+        }
+    }
+
+    fn manager(name: &str) -> Arc<CacheManager> {
+        let db_path = std::env::temp_dir().join(format!("test_history_{}_{}", name, std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        CacheManager::instance(config).unwrap()
+    }
+
+    #[test]
+    fn test_cipher_roundtrip_and_tamper() {
+        let salt = [7u8; SALT_LEN];
+        let cipher = HistoryCipher::new("correct horse battery staple", &salt).unwrap();
+        let plaintext = "rust 异步运行时".repeat(20);
+        let sealed = cipher.seal(b"id-1", plaintext.as_bytes()).unwrap();
+        assert!(!sealed.windows(4).any(|w| w == b"rust"));
+        assert_eq!(sealed.len(), 1 + NONCE_LEN + plaintext.len() + TAG_LEN);
+        assert_eq!(cipher.open(b"id-1", &sealed).unwrap(), plaintext.as_bytes());
+
+        // 相同明文每次加密结果不同
+        assert_ne!(cipher.seal(b"id-1", plaintext.as_bytes()).unwrap(), sealed);
+
+        let mut tampered = sealed.clone();
+        tampered[20] ^= 1;
+        assert!(cipher.open(b"id-1", &tampered).is_err());
+        // 密文不能移到其他记录 ID 下
+        assert!(cipher.open(b"id-2", &sealed).is_err());
+        assert!(cipher.open(b"id-1", &sealed[..10]).is_err());
+
+        // 口令或盐不同时派生出不同的密钥
+        let other = HistoryCipher::new("another passphrase!!", &salt).unwrap();
+        assert!(other.open(b"id-1", &sealed).is_err());
+        let other_salt = HistoryCipher::new("correct horse battery staple", &[8u8; SALT_LEN]).unwrap();
+        assert!(other_salt.open(b"id-1", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_store_salt_is_random_and_persistent() {
+        let meta = manager("salt").open_tree(HISTORY_META_TREE).unwrap();
+        let salt = store_salt(&meta).unwrap();
+        assert_eq!(salt.len(), SALT_LEN);
+        assert_eq!(store_salt(&meta).unwrap(), salt);
+
+        // 不同的存储使用不同的盐
+        let other = manager("salt_other").open_tree(HISTORY_META_TREE).unwrap();
+        assert_ne!(store_salt(&other).unwrap(), salt);
+    }
+
+    #[test]
+    fn test_history_store() {
+        let manager = manager("store");
+        assert!(HistoryStore::new(&manager, &config("short")).is_err());
+        let store = HistoryStore::new(&manager, &config("a sufficiently long key")).unwrap();
+        store.purge(None).unwrap();
+
+        let query = HistoryEntry::query("rust async", vec!["bing".to_string()]);
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let click = HistoryEntry::click("rust async", "https://tokio.rs/", Some("Tokio"));
+        store.record(&query).unwrap();
+        store.record(&click).unwrap();
+
+        let entries = store.list(None, 10).unwrap();
+        assert_eq!(entries, vec![click.clone(), query.clone()]);
+        assert_eq!(store.list(None, 1).unwrap().len(), 1);
+        assert_eq!(store.list(Some("TOKIO"), 10).unwrap(), vec![click.clone()]);
+        assert!(store.list(Some("python"), 10).unwrap().is_empty());
+
+        // 口令不同时无法读出
+        let other = HistoryStore::new(&manager, &config("a different long key")).unwrap();
+        assert!(other.list(None, 10).unwrap().is_empty());
+
+        assert!(store.delete(&query.id).unwrap());
+        assert_eq!(store.purge(None).unwrap(), 1);
+        assert!(store.list(None, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_history_expiry() {
+        let manager = manager("expiry");
+        let store = HistoryStore::new(&manager, &config("a sufficiently long key")).unwrap();
+        store.purge(None).unwrap();
+
+        let mut old = HistoryEntry::query("old", Vec::new());
+        old.id = format!("{:012x}{:08x}", 1_000u64, 0);
+        old.timestamp = 1;
+        let recent = HistoryEntry::query("recent", Vec::new());
+        store.record(&old).unwrap();
+        store.record(&recent).unwrap();
+
+        assert_eq!(store.list(None, 10).unwrap(), vec![recent.clone()]);
+        assert_eq!(store.expire().unwrap(), 1);
+        assert_eq!(store.purge(Some(2)).unwrap(), 0);
+        assert_eq!(store.list(None, 10).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 图片缓存
+//!
+//! 为缩略图代理缓存已获取的图片内容
+
+use crate::cache::manager::{CacheManager, CacheError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+type Result<T> = std::result::Result<T, CacheError>;
+
+/// 图片缓存键前缀
+const IMAGE_KEY_PREFIX: &str = "image:";
+
+/// 缓存的图片
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CachedImage {
+    /// Content-Type
+    pub content_type: String,
+    /// 图片数据
+    pub data: Vec<u8>,
+}
+
+/// 图片缓存
+///
+/// 封装 CacheManager，按原始图片 URL 存取图片内容
+#[derive(Clone)]
+pub struct ImageCache {
+    manager: Arc<CacheManager>,
+}
+
+impl ImageCache {
+    /// 创建图片缓存实例
+    pub fn new(manager: Arc<CacheManager>) -> Self {
+        Self { manager }
+    }
+
+    /// 生成图片缓存键
+    pub fn generate_key(url: &str) -> String {
+        format!("{}{}", IMAGE_KEY_PREFIX, url)
+    }
+
+    /// 存储图片
+    pub fn set(&self, url: &str, image: &CachedImage, ttl: Option<Duration>) -> Result<()> {
+        let bytes = bincode::serde::encode_to_vec(image, bincode::config::standard())
+            .map_err(|e| CacheError::SerializationError(format!("Failed to serialize image: {}", e)))?;
+        self.manager.set(Self::generate_key(url), bytes, ttl)
+    }
+
+    /// 获取图片
+    pub fn get(&self, url: &str) -> Result<Option<CachedImage>> {
+        match self.manager.get(&Self::generate_key(url))? {
+            Some(bytes) => {
+                let (image, _) = bincode::serde::decode_from_slice(&bytes, bincode::config::standard())
+                    .map_err(|e| CacheError::SerializationError(format!("Failed to deserialize image: {}", e)))?;
+                Ok(Some(image))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// 删除图片
+    pub fn delete(&self, url: &str) -> Result<bool> {
+        self.manager.delete(&Self::generate_key(url))
+    }
+
+    /// 是否已缓存未过期的图片（不读取图片内容）
+    pub fn contains(&self, url: &str) -> Result<bool> {
+        Ok(self
+            .manager
+            .get_metadata(&Self::generate_key(url))?
+            .is_some_and(|meta| !meta.is_expired()))
+    }
+
+    /// 图片缓存占用的字节数（含已过期但尚未清理的图片）
+    pub fn usage(&self) -> Result<u64> {
+        Ok(self.entries()?.iter().map(|entry| entry.size_bytes).sum())
+    }
+
+    /// 将图片缓存占用控制在磁盘上限内
+    ///
+    /// 超出上限时先淘汰已过期的图片，再按最后访问时间从旧到新淘汰
+    ///
+    /// # Arguments
+    ///
+    /// * `quota_bytes` - 磁盘上限（字节）
+    ///
+    /// # Returns
+    ///
+    /// 返回淘汰的图片数
+    pub fn enforce_quota(&self, quota_bytes: u64) -> Result<usize> {
+        let mut entries = self.entries()?;
+        let mut usage: u64 = entries.iter().map(|entry| entry.size_bytes).sum();
+        if usage <= quota_bytes {
+            return Ok(0);
+        }
+
+        entries.sort_by_key(|entry| (!entry.expired, entry.last_accessed_at));
+        let mut evicted = 0;
+        for entry in entries {
+            if usage <= quota_bytes {
+                break;
+            }
+            if self.manager.delete(&entry.key)? {
+                evicted += 1;
+            }
+            usage = usage.saturating_sub(entry.size_bytes);
+        }
+        Ok(evicted)
+    }
+
+    /// 列出所有图片条目的元数据
+    fn entries(&self) -> Result<Vec<ImageEntry>> {
+        let mut entries = Vec::new();
+        for item in self.manager.scan_prefix(IMAGE_KEY_PREFIX) {
+            let (key, _) = item.map_err(|e| CacheError::DatabaseError(format!("遍历图片缓存失败: {}", e)))?;
+            let key = String::from_utf8_lossy(&key).into_owned();
+            if let Some(meta) = self.manager.get_metadata(&key)? {
+                entries.push(ImageEntry {
+                    expired: meta.is_expired(),
+                    last_accessed_at: meta.last_accessed_at,
+                    size_bytes: meta.size_bytes as u64,
+                    key,
+                });
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// 配额淘汰时使用的图片条目信息
+struct ImageEntry {
+    key: String,
+    expired: bool,
+    last_accessed_at: u64,
+    size_bytes: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::{CacheImplConfig, CacheMode};
+    use serial_test::serial;
+
+    fn temp_image_cache() -> ImageCache {
+        let db_path = std::env::temp_dir().join(format!("test_image_cache_{}", std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            default_ttl_secs: 3600,
+            max_size_bytes: 1024 * 1024,
+            enabled: true,
+            compression: false,
+            mode: CacheMode::HighThroughput,
+        };
+
+        let manager = CacheManager::instance(config).expect("Failed to create cache manager");
+        ImageCache::new(manager)
+    }
+
+    #[test]
+    fn test_generate_key() {
+        assert_eq!(ImageCache::generate_key("https://a.com/x.png"), "image:https://a.com/x.png");
+    }
+
+    #[test]
+    #[serial]
+    fn test_set_get_delete() {
+        let cache = temp_image_cache();
+        let url = "https://img.example.com/thumb-image-cache-test.png";
+        let image = CachedImage {
+            content_type: "image/png".to_string(),
+            data: vec![0x89, 0x50, 0x4e, 0x47],
+        };
+
+        cache.set(url, &image, Some(Duration::from_secs(60))).unwrap();
+        assert_eq!(cache.get(url).unwrap(), Some(image));
+        assert!(cache.delete(url).unwrap());
+        assert_eq!(cache.get(url).unwrap(), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_enforce_quota() {
+        let cache = temp_image_cache();
+        let urls: Vec<String> = (0..3)
+            .map(|i| format!("https://img.example.com/quota-test-{}.png", i))
+            .collect();
+        let image = CachedImage {
+            content_type: "image/png".to_string(),
+            data: vec![0; 1000],
+        };
+        for url in &urls {
+            cache.set(url, &image, Some(Duration::from_secs(60))).unwrap();
+        }
+        assert!(cache.contains(&urls[0]).unwrap());
+
+        // 上限以内不淘汰
+        let usage = cache.usage().unwrap();
+        assert!(usage >= 3000);
+        assert_eq!(cache.enforce_quota(usage).unwrap(), 0);
+
+        // 超出上限时淘汰到上限以内
+        assert!(cache.enforce_quota(usage - 1).unwrap() >= 1);
+        assert!(cache.usage().unwrap() < usage);
+
+        for url in &urls {
+            cache.delete(url).unwrap();
+        }
+        assert!(!cache.contains(&urls[0]).unwrap());
+    }
+}
@@ -18,8 +18,10 @@
 
 use crate::cache::types::*;
 use once_cell::sync::Lazy;
+use sled::transaction::{TransactionError, Transactional};
 use sled::Db;
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -50,30 +52,70 @@ pub enum CacheError {
     /// 条目已过期
     #[error("缓存条目已过期")]
     EntryExpired,
+
+    /// 加密或解密失败
+    #[error("加密错误: {0}")]
+    EncryptionError(String),
+
+    /// 键格式无效
+    #[error("无效的键: {0}")]
+    InvalidKey(String),
 }
 
 /// 缓存结果类型
 pub type Result<T> = std::result::Result<T, CacheError>;
 
-/// 全局单例实例（使用Lazy和Mutex确保线程安全）
-/// 
-/// ## 单例模式实现
-/// 
-/// 使用`Lazy`和`Mutex`实现线程安全的单例模式：
-/// - `Lazy`: 提供线程安全的延迟初始化
-/// - `Mutex`: 允许独占访问，提供内部可变性
-/// 
-/// 这种实现确保：
-/// 1. 全局只有一个CacheManager实例
-/// 2. 线程安全的访问
-/// 3. 延迟初始化（首次调用时创建）
-/// 4. 避免重复初始化
-/// 5. 无需手动管理内存（没有unsafe代码）
-static GLOBAL_CACHE_MANAGER: Lazy<Mutex<Option<Arc<CacheManager>>>> = Lazy::new(|| Mutex::new(None));
+/// 在阻塞线程池中执行缓存操作
+///
+/// sled 的读写可能因磁盘 I/O 阻塞，异步上下文中的缓存操作通过它移出 tokio 工作线程
+pub(crate) async fn run_blocking<T, F>(op: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(op)
+        .await
+        .map_err(|e| CacheError::DatabaseError(format!("缓存任务执行失败: {}", e)))?
+}
+
+/// 健康检查探测键所在的树
+const HEALTH_TREE: &str = "health";
+
+/// 健康检查探测键
+const HEALTH_PROBE_KEY: &[u8] = b"probe";
+
+/// 全局缓存管理器注册表（数据库路径 → 管理器）
+///
+/// ## 按路径共享实例
+///
+/// sled 不允许同一进程重复打开同一个数据库，因此每个路径只创建一个管理器：
+/// 1. 相同路径的调用共享同一个实例
+/// 2. 不同路径得到相互独立的实例，同一进程可以嵌入多个使用不同缓存库的 SeeSea
+/// 3. 延迟初始化（首次使用某路径时创建）
+/// 4. 实例在调用 [`CacheManager::release`] 前一直保持打开
+static CACHE_MANAGERS: Lazy<Mutex<HashMap<PathBuf, Arc<CacheManager>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 注册表使用的路径键（相对路径按当前目录解析）
+fn registry_key(db_path: &str) -> PathBuf {
+    std::path::absolute(db_path).unwrap_or_else(|_| PathBuf::from(db_path))
+}
+
+/// 序列化后的写入操作（元数据已编码，可在 sled 事务中重试）
+enum EncodedOp {
+    Set {
+        key: CacheKey,
+        value: CacheValue,
+        metadata: Vec<u8>,
+    },
+    Delete {
+        key: CacheKey,
+    },
+}
 
 /// 缓存管理器
 ///
-/// 基于 sled 实现的高性能缓存管理器（单例模式）
+/// 基于 sled 实现的高性能缓存管理器（每个数据库路径一个实例）
 pub struct CacheManager {
     /// sled 数据库实例
     db: Db,
@@ -94,14 +136,23 @@ pub struct CacheManager {
     deletes: Arc<AtomicU64>,
     /// 过期清理计数器（原子操作）
     evictions: Arc<AtomicU64>,
+    /// 写入值大小分布
+    value_sizes: HistogramRecorder,
+    /// 读取延迟分布
+    get_latency: HistogramRecorder,
+    /// 写入延迟分布
+    set_latency: HistogramRecorder,
 }
 
 impl CacheManager {
-    /// 获取全局缓存管理器实例（单例模式）
+    /// 获取缓存管理器实例
+    ///
+    /// 按 `config.db_path` 共享实例：路径首次使用时按配置创建，
+    /// 之后相同路径的调用返回同一实例（其余配置项被忽略）
     ///
     /// # 参数
     ///
-    /// * `config` - 缓存配置（仅在第一次调用时使用）
+    /// * `config` - 缓存配置
     ///
     /// # 返回值
     ///
@@ -117,19 +168,36 @@ impl CacheManager {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn instance(config: CacheImplConfig) -> Result<Arc<Self>> {
-        let mut guard = GLOBAL_CACHE_MANAGER.lock()
+        let mut managers = CACHE_MANAGERS.lock()
             .map_err(|e| CacheError::DatabaseError(format!("Lock poisoned: {}", e)))?;
-        
-        if let Some(manager) = guard.as_ref() {
-            // Already initialized, return clone
-            Ok(Arc::clone(manager))
-        } else {
-            // Initialize for the first time
-            let manager = Self::create_internal(config)?;
-            let arc_manager = Arc::new(manager);
-            *guard = Some(Arc::clone(&arc_manager));
-            Ok(arc_manager)
+
+        let key = registry_key(&config.db_path);
+        if let Some(manager) = managers.get(&key) {
+            return Ok(Arc::clone(manager));
         }
+
+        let manager = Arc::new(Self::create_internal(config)?);
+        managers.insert(key, Arc::clone(&manager));
+        Ok(manager)
+    }
+
+    /// 从注册表移除指定路径的实例
+    ///
+    /// 已持有的实例仍可继续使用，最后一个引用释放后数据库关闭；
+    /// 之后再次调用 [`instance`](Self::instance) 会重新打开该路径
+    ///
+    /// # 参数
+    ///
+    /// * `db_path` - 数据库路径
+    ///
+    /// # 返回值
+    ///
+    /// 路径存在于注册表时返回 true
+    pub fn release(db_path: &str) -> bool {
+        CACHE_MANAGERS
+            .lock()
+            .map(|mut managers| managers.remove(&registry_key(db_path)).is_some())
+            .unwrap_or(false)
     }
 
     /// 创建新的缓存管理器（内部方法）
@@ -175,11 +243,14 @@ impl CacheManager {
             writes: Arc::new(AtomicU64::new(0)),
             deletes: Arc::new(AtomicU64::new(0)),
             evictions: Arc::new(AtomicU64::new(0)),
+            value_sizes: HistogramRecorder::new(VALUE_SIZE_BUCKETS),
+            get_latency: HistogramRecorder::new(LATENCY_BUCKETS),
+            set_latency: HistogramRecorder::new(LATENCY_BUCKETS),
         })
     }
 
     /// 创建新的缓存管理器（已弃用，使用instance替代）
-    #[deprecated(since = "0.1.0", note = "请使用 instance() 方法获取单例实例")]
+    #[deprecated(since = "0.1.0", note = "请使用 instance() 方法获取按路径共享的实例")]
     pub fn new(config: CacheImplConfig) -> Result<Self> {
         Self::create_internal(config)
     }
@@ -197,6 +268,7 @@ impl CacheManager {
         if !self.config.enabled {
             return Err(CacheError::CacheDisabled);
         }
+        let _timer = self.get_latency.start_timer();
 
         // 获取元数据
         let metadata = match self.get_metadata(key)? {
@@ -254,6 +326,7 @@ impl CacheManager {
         if !self.config.enabled {
             return Err(CacheError::CacheDisabled);
         }
+        let _timer = self.get_latency.start_timer();
 
         // 获取元数据
         let metadata = match self.get_metadata(key)? {
@@ -305,6 +378,7 @@ impl CacheManager {
         if !self.config.enabled {
             return Err(CacheError::CacheDisabled);
         }
+        let _timer = self.set_latency.start_timer();
 
         // 检查缓存大小限制
         let value_size = value.len();
@@ -317,24 +391,19 @@ impl CacheManager {
             }
         }
 
-        // 创建元数据
-        let ttl_duration = ttl.or_else(|| Some(Duration::from_secs(self.config.default_ttl_secs)));
-        let metadata = CacheEntryMetadata::new(ttl_duration, value_size);
-
-        // 写入数据
-        self.db.insert(key.as_bytes(), value.as_slice()).map_err(|e| {
-            CacheError::DatabaseError(format!("写入缓存失败: {}", e))
-        })?;
-
-        // 写入元数据
-        self.set_metadata(&key, &metadata)?;
+        // 数据与元数据在同一事务中写入，避免崩溃后留下孤立的元数据
+        let metadata = self.encode_metadata(&self.entry_metadata(ttl, value_size))?;
+        self.write_atomic(&[EncodedOp::Set { key, value, metadata }])?;
 
         self.writes.fetch_add(1, Ordering::Relaxed);
+        self.value_sizes.observe(value_size as f64);
         Ok(())
     }
 
     /// 删除缓存项
     ///
+    /// 数据与元数据在同一事务中删除
+    ///
     /// # 参数
     ///
     /// * `key` - 缓存键
@@ -343,18 +412,71 @@ impl CacheManager {
             return Err(CacheError::CacheDisabled);
         }
 
-        let existed = self.db.remove(key.as_bytes()).map_err(|e| {
-            CacheError::DatabaseError(format!("删除缓存失败: {}", e))
-        })?.is_some();
-
+        let existed = self.write_atomic(&[EncodedOp::Delete { key: key.to_string() }])? > 0;
         if existed {
-            let _ = self.metadata_tree.remove(key.as_bytes());
             self.deletes.fetch_add(1, Ordering::Relaxed);
         }
 
         Ok(existed)
     }
 
+    /// 原子地应用批量写入
+    ///
+    /// 用于聚合结果等需要同时写入多个键的场景，所有操作要么全部生效，要么全部不生效
+    ///
+    /// # 参数
+    ///
+    /// * `batch` - 批量写入
+    ///
+    /// # 返回值
+    ///
+    /// 返回删除操作实际删除的条目数
+    pub fn apply_batch(&self, batch: CacheBatch) -> Result<usize> {
+        if !self.config.enabled {
+            return Err(CacheError::CacheDisabled);
+        }
+        if batch.is_empty() {
+            return Ok(0);
+        }
+
+        let total_size: usize = batch
+            .ops
+            .iter()
+            .map(|op| match op {
+                BatchOp::Set { value, .. } => value.len(),
+                BatchOp::Delete { .. } => 0,
+            })
+            .sum();
+        if self.is_cache_full(total_size)? {
+            self.cleanup_expired()?;
+            if self.is_cache_full(total_size)? {
+                return Err(CacheError::CacheFull);
+            }
+        }
+
+        let mut value_sizes = Vec::new();
+        let ops = batch
+            .ops
+            .into_iter()
+            .map(|op| match op {
+                BatchOp::Set { key, value, ttl } => {
+                    value_sizes.push(value.len());
+                    let metadata = self.encode_metadata(&self.entry_metadata(ttl, value.len()))?;
+                    Ok(EncodedOp::Set { key, value, metadata })
+                }
+                BatchOp::Delete { key } => Ok(EncodedOp::Delete { key }),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let deleted = self.write_atomic(&ops)?;
+        self.writes.fetch_add(value_sizes.len() as u64, Ordering::Relaxed);
+        self.deletes.fetch_add(deleted as u64, Ordering::Relaxed);
+        for size in value_sizes {
+            self.value_sizes.observe(size as f64);
+        }
+        Ok(deleted)
+    }
+
     /// 清空所有缓存
     pub fn clear(&self) -> Result<()> {
         if !self.config.enabled {
@@ -411,6 +533,9 @@ impl CacheManager {
             total_keys: self.db.len() as u64,
             estimated_size_bytes: self.db.size_on_disk().unwrap_or(0),
             evictions: self.evictions.load(Ordering::Relaxed),
+            value_size_bytes: self.value_sizes.snapshot(),
+            get_latency_seconds: self.get_latency.snapshot(),
+            set_latency_seconds: self.set_latency.snapshot(),
         }
     }
 
@@ -422,6 +547,74 @@ impl CacheManager {
         Ok(())
     }
 
+    /// 异步获取缓存值（在阻塞线程池中执行）
+    pub async fn get_async(self: &Arc<Self>, key: &str) -> Result<Option<CacheValue>> {
+        let manager = Arc::clone(self);
+        let key = key.to_string();
+        run_blocking(move || manager.get(&key)).await
+    }
+
+    /// 异步设置缓存值（在阻塞线程池中执行）
+    pub async fn set_async(self: &Arc<Self>, key: String, value: CacheValue, ttl: Option<Duration>) -> Result<()> {
+        let manager = Arc::clone(self);
+        run_blocking(move || manager.set(key, value, ttl)).await
+    }
+
+    /// 异步应用批量写入（在阻塞线程池中执行）
+    pub async fn apply_batch_async(self: &Arc<Self>, batch: CacheBatch) -> Result<usize> {
+        let manager = Arc::clone(self);
+        run_blocking(move || manager.apply_batch(batch)).await
+    }
+
+    /// 异步删除缓存值（在阻塞线程池中执行）
+    pub async fn delete_async(self: &Arc<Self>, key: &str) -> Result<bool> {
+        let manager = Arc::clone(self);
+        let key = key.to_string();
+        run_blocking(move || manager.delete(&key)).await
+    }
+
+    /// 异步清理过期条目（在阻塞线程池中执行）
+    pub async fn cleanup_expired_async(self: &Arc<Self>) -> Result<usize> {
+        let manager = Arc::clone(self);
+        run_blocking(move || manager.cleanup_expired()).await
+    }
+
+    /// 检查数据库可读写：在独立的健康检查树中写入、读回并删除一个探测键
+    pub fn check_writable(&self) -> Result<()> {
+        let tree = self.open_tree(HEALTH_TREE)?;
+        let probe = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+            .to_be_bytes();
+
+        tree.insert(HEALTH_PROBE_KEY, &probe)
+            .map_err(|e| CacheError::DatabaseError(format!("写入探测键失败: {}", e)))?;
+        let read = tree
+            .get(HEALTH_PROBE_KEY)
+            .map_err(|e| CacheError::DatabaseError(format!("读取探测键失败: {}", e)))?;
+        if read.as_deref() != Some(&probe[..]) {
+            return Err(CacheError::DatabaseError("探测键读回的值与写入不一致".to_string()));
+        }
+        tree.remove(HEALTH_PROBE_KEY)
+            .map_err(|e| CacheError::DatabaseError(format!("删除探测键失败: {}", e)))?;
+        Ok(())
+    }
+
+    /// 异步检查数据库可读写（在阻塞线程池中执行）
+    pub async fn check_writable_async(self: &Arc<Self>) -> Result<()> {
+        let manager = Arc::clone(self);
+        run_blocking(move || manager.check_writable()).await
+    }
+
+    /// 异步刷新到磁盘
+    pub async fn flush_async(&self) -> Result<()> {
+        self.db.flush_async().await.map_err(|e| {
+            CacheError::DatabaseError(format!("刷新缓存失败: {}", e))
+        })?;
+        Ok(())
+    }
+
     /// 获取数据库迭代器
     ///
     /// 用于遍历所有缓存条目
@@ -433,6 +626,28 @@ impl CacheManager {
         self.db.iter()
     }
 
+    /// 获取指定前缀的数据库迭代器
+    ///
+    /// # 参数
+    ///
+    /// * `prefix` - 缓存键前缀
+    pub fn scan_prefix(&self, prefix: &str) -> sled::Iter {
+        self.db.scan_prefix(prefix.as_bytes())
+    }
+
+    /// 打开数据库中的独立树
+    ///
+    /// 独立树中的数据不受 [`clear`](Self::clear) 与过期清理影响
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 树名称
+    pub fn open_tree(&self, name: &str) -> Result<sled::Tree> {
+        self.db
+            .open_tree(name)
+            .map_err(|e| CacheError::DatabaseError(format!("打开树 {} 失败: {}", name, e)))
+    }
+
     // 私有辅助方法
 
     pub fn get_metadata(&self, key: &str) -> Result<Option<CacheEntryMetadata>> {
@@ -450,10 +665,51 @@ impl CacheManager {
         }
     }
 
-    fn set_metadata(&self, key: &str, metadata: &CacheEntryMetadata) -> Result<()> {
-        let data = bincode::serde::encode_to_vec(metadata, bincode::config::standard()).map_err(|e| {
+    /// 按配置的默认 TTL 创建条目元数据
+    fn entry_metadata(&self, ttl: Option<Duration>, size_bytes: usize) -> CacheEntryMetadata {
+        let ttl = ttl.unwrap_or(Duration::from_secs(self.config.default_ttl_secs));
+        CacheEntryMetadata::new(Some(ttl), size_bytes)
+    }
+
+    fn encode_metadata(&self, metadata: &CacheEntryMetadata) -> Result<Vec<u8>> {
+        bincode::serde::encode_to_vec(metadata, bincode::config::standard()).map_err(|e| {
             CacheError::SerializationError(format!("序列化元数据失败: {}", e))
-        })?;
+        })
+    }
+
+    /// 在一个 sled 事务中同时写入数据树与元数据树
+    ///
+    /// # 返回值
+    ///
+    /// 返回删除操作实际删除的条目数
+    fn write_atomic(&self, ops: &[EncodedOp]) -> Result<usize> {
+        let result: std::result::Result<usize, TransactionError<()>> =
+            (&*self.db, &self.metadata_tree).transaction(|(data, meta)| {
+                let mut deleted = 0;
+                for op in ops {
+                    match op {
+                        EncodedOp::Set { key, value, metadata } => {
+                            data.insert(key.as_bytes(), value.as_slice())?;
+                            meta.insert(key.as_bytes(), metadata.as_slice())?;
+                        }
+                        EncodedOp::Delete { key } => {
+                            if data.remove(key.as_bytes())?.is_some() {
+                                deleted += 1;
+                            }
+                            meta.remove(key.as_bytes())?;
+                        }
+                    }
+                }
+                Ok(deleted)
+            });
+        result.map_err(|e| match e {
+            TransactionError::Storage(e) => CacheError::DatabaseError(format!("缓存写入事务失败: {}", e)),
+            TransactionError::Abort(()) => CacheError::DatabaseError("缓存写入事务中止".to_string()),
+        })
+    }
+
+    fn set_metadata(&self, key: &str, metadata: &CacheEntryMetadata) -> Result<()> {
+        let data = self.encode_metadata(metadata)?;
 
         self.metadata_tree.insert(key.as_bytes(), data.as_slice()).map_err(|e| {
             CacheError::DatabaseError(format!("写入元数据失败: {}", e))
@@ -511,6 +767,25 @@ mod tests {
         assert!(manager.is_ok());
     }
 
+    #[test]
+    #[serial]
+    fn test_instance_per_path() {
+        let config_a = temp_cache_config();
+        let config_b = temp_cache_config();
+
+        let a = CacheManager::instance(config_a.clone()).unwrap();
+        let b = CacheManager::instance(config_b.clone()).unwrap();
+        assert!(!Arc::ptr_eq(&a, &b));
+        assert!(Arc::ptr_eq(&a, &CacheManager::instance(config_a.clone()).unwrap()));
+
+        a.set("key".to_string(), b"a".to_vec(), None).unwrap();
+        assert!(b.get("key").unwrap().is_none());
+
+        assert!(CacheManager::release(&config_a.db_path));
+        assert!(!CacheManager::release(&config_a.db_path));
+        assert!(CacheManager::release(&config_b.db_path));
+    }
+
     #[test]
     #[serial]
     fn test_cache_set_and_get() {
@@ -578,6 +853,29 @@ mod tests {
         assert!(manager.get(&key).unwrap_or(None).is_none());
     }
 
+    #[test]
+    #[serial]
+    fn test_apply_batch() {
+        let manager = CacheManager::instance(temp_cache_config()).unwrap();
+        manager.set("old".to_string(), b"old".to_vec(), None).unwrap();
+
+        let mut batch = CacheBatch::new();
+        batch
+            .set("a", b"1".to_vec(), None)
+            .set("b", b"2".to_vec(), Some(Duration::from_secs(60)))
+            .delete("old")
+            .delete("missing");
+        assert_eq!(manager.apply_batch(batch).unwrap(), 1);
+
+        assert_eq!(manager.get("a").unwrap(), Some(b"1".to_vec()));
+        assert_eq!(manager.get("b").unwrap(), Some(b"2".to_vec()));
+        assert!(manager.get("old").unwrap().is_none());
+        // 删除时元数据一并移除
+        assert!(manager.get_metadata("old").unwrap().is_none());
+        assert_eq!(manager.stats().writes, 3);
+        assert_eq!(manager.stats().deletes, 1);
+    }
+
     #[test]
     #[serial]
     fn test_cache_stats() {
@@ -608,5 +906,12 @@ mod tests {
         let _ = manager.get(&key);
         let stats = manager.stats();
         assert_eq!(stats.hits, 1);
+
+        // 直方图：两次读取、一次 11 字节的写入
+        assert_eq!(stats.get_latency_seconds.count, 2);
+        assert_eq!(stats.set_latency_seconds.count, 1);
+        assert_eq!(stats.value_size_bytes.count, 1);
+        assert_eq!(stats.value_size_bytes.sum, 11.0);
+        assert_eq!(stats.value_size_bytes.buckets[0], 1);
     }
 }
@@ -16,7 +16,7 @@
 //!
 //! 提供引擎元数据和配置的缓存功能
 
-use crate::cache::manager::{CacheError, CacheManager, Result};
+use crate::cache::manager::{run_blocking, CacheError, CacheManager, Result};
 use crate::derive::types::EngineInfo;
 use std::sync::Arc;
 use std::time::Duration;
@@ -30,6 +30,7 @@ const ENGINE_INFO_PREFIX: &str = "engine_info:";
 /// 元数据缓存
 ///
 /// 封装 CacheManager，提供元数据专用的缓存接口
+#[derive(Clone)]
 pub struct MetadataCache {
     manager: Arc<CacheManager>,
 }
@@ -165,6 +166,40 @@ impl MetadataCache {
     pub fn manager(&self) -> &CacheManager {
         &self.manager
     }
+
+    /// 异步缓存引擎信息（在阻塞线程池中执行）
+    pub async fn set_engine_info_async(
+        &self,
+        engine_name: &str,
+        info: &EngineInfo,
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        let cache = self.clone();
+        let engine_name = engine_name.to_string();
+        let info = info.clone();
+        run_blocking(move || cache.set_engine_info(&engine_name, &info, ttl)).await
+    }
+
+    /// 异步获取引擎信息（在阻塞线程池中执行）
+    pub async fn get_engine_info_async(&self, engine_name: &str) -> Result<Option<EngineInfo>> {
+        let cache = self.clone();
+        let engine_name = engine_name.to_string();
+        run_blocking(move || cache.get_engine_info(&engine_name)).await
+    }
+
+    /// 异步缓存通用元数据（在阻塞线程池中执行）
+    pub async fn set_metadata_async(&self, key: &str, data: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+        let cache = self.clone();
+        let key = key.to_string();
+        run_blocking(move || cache.set_metadata(&key, data, ttl)).await
+    }
+
+    /// 异步获取通用元数据（在阻塞线程池中执行）
+    pub async fn get_metadata_async(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let cache = self.clone();
+        let key = key.to_string();
+        run_blocking(move || cache.get_metadata(&key)).await
+    }
 }
 
 #[cfg(test)]
@@ -18,6 +18,10 @@
 //! - 搜索结果缓存
 //! - 引擎元数据缓存
 //! - RSS feed 缓存
+//! - 页面缓存（按 URL 共享获取到的原始页面）
+//! - 引擎会话（Cookie 与粘性请求头，重启后恢复）
+//! - 搜索会话（同一会话中的相关查询）
+//! - 保存的搜索与告警
 //! - 语义相似度缓存
 //! - 通用键值缓存
 //!
@@ -70,17 +74,35 @@ pub mod types;
 pub mod manager;
 pub mod result;
 pub mod metadata;
+pub mod cursor;
 pub mod rss;
+pub mod alerts;
+pub mod audit;
+pub mod preferences;
+pub mod history;
+pub mod image;
+pub mod page;
+pub mod session;
+pub mod search_session;
 pub mod semantic;
 pub mod semantic_cache;
 pub mod on;
 
 // 重新导出主要类型
-pub use types::{CacheImplConfig, CacheMode, CacheStats, CacheEntryMetadata};
+pub use types::{CacheBatch, CacheImplConfig, CacheMode, CacheStats, CacheEntryMetadata, Histogram};
 pub use manager::{CacheManager, CacheError, Result};
-pub use result::ResultCache;
+pub use result::{EngineCacheStats, ExportedResult, NegativeOutcome, ResultCache, RESULT_KEY_VERSION};
 pub use metadata::MetadataCache;
+pub use cursor::CursorCache;
 pub use rss::RssCache;
+pub use alerts::{Alert, AlertDelivery, AlertResult, AlertStore, SavedSearch};
+pub use audit::{principal_id, AuditEntry, AuditEvent, AuditLog};
+pub use preferences::{PreferenceStore, Theme, UserPreferences};
+pub use history::{HistoryEntry, HistoryKind, HistoryStore};
+pub use image::{ImageCache, CachedImage};
+pub use page::{PageCache, CachedPage, DEFAULT_PAGE_TTL};
+pub use session::{EngineSessionData, EngineSessionStore, SessionCookie};
+pub use search_session::{validate_session_token, SearchSession, SearchSessionStore, SessionQuery, MAX_SESSION_TOKEN_LEN};
 pub use semantic::{SimpleVectorizer, QueryVector};
 pub use semantic_cache::{SemanticCache, SemanticCacheConfig};
 pub use on::CacheInterface;
@@ -16,17 +16,27 @@
 //!
 //! 提供缓存模块的公共 API 接口
 
-use crate::cache::manager::{CacheManager, Result};
+use crate::cache::manager::{CacheError, CacheManager, Result};
 use crate::cache::metadata::MetadataCache;
+use crate::cache::cursor::CursorCache;
 use crate::cache::result::ResultCache;
 use crate::cache::rss::RssCache;
+use crate::cache::alerts::AlertStore;
+use crate::cache::audit::AuditLog;
+use crate::cache::preferences::PreferenceStore;
+use crate::cache::history::HistoryStore;
+use crate::config::privacy::HistoryConfig;
+use crate::cache::image::ImageCache;
+use crate::cache::page::PageCache;
 use crate::cache::semantic_cache::{SemanticCache, SemanticCacheConfig};
 use crate::cache::types::CacheImplConfig;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 /// 统一的缓存接口
 ///
 /// 提供对所有缓存功能的统一访问
+#[derive(Clone)]
 pub struct CacheInterface {
     /// 缓存管理器
     manager: Arc<CacheManager>,
@@ -37,6 +47,8 @@ pub struct CacheInterface {
 impl CacheInterface {
     /// 创建缓存接口
     ///
+    /// 相同 `db_path` 的接口共享同一个缓存管理器，不同路径的接口相互独立
+    ///
     /// # 参数
     ///
     /// * `config` - 缓存配置
@@ -79,11 +91,48 @@ impl CacheInterface {
         MetadataCache::new(Arc::clone(&self.manager))
     }
 
+    /// 获取引擎续页令牌缓存
+    pub fn cursors(&self) -> CursorCache {
+        CursorCache::new(Arc::clone(&self.manager))
+    }
+
     /// 获取 RSS 缓存
     pub fn rss(&self) -> RssCache {
         RssCache::new(Arc::clone(&self.manager))
     }
 
+    /// 获取保存的搜索与告警存储
+    pub fn alerts(&self) -> Result<AlertStore> {
+        AlertStore::new(&self.manager)
+    }
+
+    /// 获取审计日志
+    pub fn audit(&self) -> Result<AuditLog> {
+        AuditLog::new(&self.manager)
+    }
+
+    /// 获取用户偏好存储
+    pub fn preferences(&self) -> Result<PreferenceStore> {
+        PreferenceStore::new(&self.manager)
+    }
+
+    /// 获取加密的搜索历史存储
+    ///
+    /// `config` 需提供加密口令；是否启用由调用方根据 `config.enabled` 决定
+    pub fn history(&self, config: &HistoryConfig) -> Result<HistoryStore> {
+        HistoryStore::new(&self.manager, config)
+    }
+
+    /// 获取图片缓存
+    pub fn images(&self) -> ImageCache {
+        ImageCache::new(Arc::clone(&self.manager))
+    }
+
+    /// 获取页面缓存
+    pub fn pages(&self) -> Result<PageCache> {
+        PageCache::new(&self.manager)
+    }
+
     /// 获取语义缓存
     pub fn semantic(&self) -> SemanticCache {
         SemanticCache::new(Arc::clone(&self.manager), self.semantic_config.clone())
@@ -94,9 +143,10 @@ impl CacheInterface {
         &self.manager
     }
 
-    /// 清空所有缓存
+    /// 清空所有缓存（包括页面缓存，保存的搜索与告警不受影响）
     pub fn clear_all(&self) -> Result<()> {
-        self.manager.clear()
+        self.manager.clear()?;
+        self.pages()?.clear()
     }
 
     /// 刷新缓存到磁盘
@@ -104,9 +154,35 @@ impl CacheInterface {
         self.manager.flush()
     }
 
-    /// 清理过期条目
+    /// 检查缓存数据库已打开且可写（用于健康检查）
+    pub async fn check_writable(&self) -> Result<()> {
+        self.manager.check_writable_async().await
+    }
+
+    /// 清理过期条目（包括过期页面）
     pub fn cleanup(&self) -> Result<usize> {
-        self.manager.cleanup_expired()
+        Ok(self.manager.cleanup_expired()? + self.pages()?.cleanup()?)
+    }
+
+    /// 按命名空间统计条目数
+    ///
+    /// 命名空间为缓存键中第一个 `:` 之前的部分（如 `result`、`rss_feed`），
+    /// 不含 `:` 的键计入空字符串
+    ///
+    /// # 返回值
+    ///
+    /// 返回按命名空间排序的条目数
+    pub fn namespace_counts(&self) -> Result<BTreeMap<String, usize>> {
+        let mut counts = BTreeMap::new();
+        for item in self.manager.iter() {
+            let (key, _) = item.map_err(|e| {
+                CacheError::DatabaseError(format!("遍历缓存失败: {}", e))
+            })?;
+            let key = String::from_utf8_lossy(&key);
+            let namespace = key.split_once(':').map(|(ns, _)| ns).unwrap_or_default();
+            *counts.entry(namespace.to_string()).or_insert(0) += 1;
+        }
+        Ok(counts)
     }
 }
 
@@ -0,0 +1,250 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 页面缓存
+//!
+//! 按 URL 缓存获取到的原始页面（状态码、Content-Type 与响应体），
+//! 供搜索引擎、浏览器引擎与内容提取共享，避免重复下载同一文档。
+//! 页面存放在缓存数据库的独立树中，每个条目带有自己的过期时间
+
+use crate::cache::alerts::now_secs;
+use crate::cache::manager::{run_blocking, CacheError, CacheManager, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 页面所在的树
+const PAGE_TREE: &str = "pages";
+
+/// 页面的默认缓存时间
+pub const DEFAULT_PAGE_TTL: Duration = Duration::from_secs(3600);
+
+/// 缓存的页面
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedPage {
+    /// 页面 URL
+    pub url: String,
+    /// HTTP 状态码
+    pub status: u16,
+    /// Content-Type
+    pub content_type: Option<String>,
+    /// 原始响应体（已解压）
+    pub body: Vec<u8>,
+    /// 获取时间（Unix 时间戳）
+    pub fetched_at: u64,
+    /// 过期时间（Unix 时间戳）
+    pub expires_at: u64,
+}
+
+impl CachedPage {
+    /// 创建页面记录
+    ///
+    /// # 参数
+    ///
+    /// * `url` - 页面 URL
+    /// * `status` - HTTP 状态码
+    /// * `content_type` - Content-Type
+    /// * `body` - 原始响应体
+    /// * `ttl` - 缓存时间
+    pub fn new(url: &str, status: u16, content_type: Option<String>, body: Vec<u8>, ttl: Duration) -> Self {
+        let fetched_at = now_secs();
+        Self {
+            url: url.to_string(),
+            status,
+            content_type,
+            body,
+            fetched_at,
+            expires_at: fetched_at.saturating_add(ttl.as_secs()),
+        }
+    }
+
+    /// 是否已过期
+    pub fn is_expired(&self) -> bool {
+        now_secs() >= self.expires_at
+    }
+
+    /// 按 Content-Type 中的字符集解码为文本
+    pub fn text(&self) -> String {
+        crate::net::client::body::decode_text(self.body.clone(), self.content_type.as_deref())
+    }
+}
+
+/// 页面缓存
+#[derive(Clone)]
+pub struct PageCache {
+    /// 页面（URL → 记录）
+    pages: sled::Tree,
+}
+
+impl PageCache {
+    /// 在缓存数据库中打开页面缓存
+    ///
+    /// # 参数
+    ///
+    /// * `manager` - 缓存管理器
+    pub fn new(manager: &CacheManager) -> Result<Self> {
+        Ok(Self {
+            pages: manager.open_tree(PAGE_TREE)?,
+        })
+    }
+
+    /// 生成页面缓存键（去掉片段标识，片段不影响服务器返回的内容）
+    pub fn generate_key(url: &str) -> &str {
+        url.split('#').next().unwrap_or(url)
+    }
+
+    /// 存储页面
+    pub fn put(&self, page: &CachedPage) -> Result<()> {
+        let data = bincode::serde::encode_to_vec(page, bincode::config::standard())
+            .map_err(|e| CacheError::SerializationError(format!("序列化页面失败: {}", e)))?;
+        self.pages
+            .insert(Self::generate_key(&page.url).as_bytes(), data)
+            .map_err(|e| CacheError::DatabaseError(format!("写入页面失败: {}", e)))?;
+        Ok(())
+    }
+
+    /// 获取未过期的页面（过期的页面会被删除）
+    pub fn get(&self, url: &str) -> Result<Option<CachedPage>> {
+        let key = Self::generate_key(url);
+        let data = match self.pages.get(key.as_bytes()) {
+            Ok(Some(data)) => data,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(CacheError::DatabaseError(format!("读取页面失败: {}", e))),
+        };
+
+        match bincode::serde::decode_from_slice::<CachedPage, _>(&data, bincode::config::standard()) {
+            Ok((page, _)) if !page.is_expired() => Ok(Some(page)),
+            // 过期或损坏的记录直接删除
+            _ => {
+                self.delete(url)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// 删除页面
+    ///
+    /// # 返回值
+    ///
+    /// 页面存在时返回 true
+    pub fn delete(&self, url: &str) -> Result<bool> {
+        self.pages
+            .remove(Self::generate_key(url).as_bytes())
+            .map(|old| old.is_some())
+            .map_err(|e| CacheError::DatabaseError(format!("删除页面失败: {}", e)))
+    }
+
+    /// 缓存的页面数（含尚未清理的过期页面）
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// 是否没有缓存页面
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// 清理过期页面
+    ///
+    /// # 返回值
+    ///
+    /// 返回删除的页面数
+    pub fn cleanup(&self) -> Result<usize> {
+        let now = now_secs();
+        let mut removed = 0;
+        for item in self.pages.iter() {
+            let (key, data) = item.map_err(|e| CacheError::DatabaseError(format!("遍历页面失败: {}", e)))?;
+            let expired = bincode::serde::decode_from_slice::<CachedPage, _>(&data, bincode::config::standard())
+                .map_or(true, |(page, _)| now >= page.expires_at);
+            if expired {
+                self.pages
+                    .remove(key)
+                    .map_err(|e| CacheError::DatabaseError(format!("删除页面失败: {}", e)))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// 清空页面缓存
+    pub fn clear(&self) -> Result<()> {
+        self.pages
+            .clear()
+            .map_err(|e| CacheError::DatabaseError(format!("清空页面缓存失败: {}", e)))
+    }
+
+    /// 异步获取页面（在阻塞线程池中执行）
+    pub async fn get_async(&self, url: &str) -> Result<Option<CachedPage>> {
+        let (cache, url) = (self.clone(), url.to_string());
+        run_blocking(move || cache.get(&url)).await
+    }
+
+    /// 异步存储页面（在阻塞线程池中执行）
+    pub async fn put_async(&self, page: CachedPage) -> Result<()> {
+        let cache = self.clone();
+        run_blocking(move || cache.put(&page)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::CacheImplConfig;
+
+    fn temp_page_cache(name: &str) -> PageCache {
+        let db_path = std::env::temp_dir().join(format!("test_page_cache_{}_{}", name, std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let manager = CacheManager::instance(config).expect("Failed to create cache manager");
+        let cache = PageCache::new(&manager).unwrap();
+        cache.clear().unwrap();
+        cache
+    }
+
+    #[test]
+    fn test_put_get_ignores_fragment() {
+        let cache = temp_page_cache("roundtrip");
+        let page = CachedPage::new(
+            "https://example.com/article",
+            200,
+            Some("text/html; charset=utf-8".to_string()),
+            "<p>正文</p>".as_bytes().to_vec(),
+            Duration::from_secs(60),
+        );
+        cache.put(&page).unwrap();
+
+        let cached = cache.get("https://example.com/article#section").unwrap().unwrap();
+        assert_eq!(cached, page);
+        assert_eq!(cached.text(), "<p>正文</p>");
+        assert!(cache.delete("https://example.com/article").unwrap());
+        assert!(cache.get("https://example.com/article").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_expired_pages_are_dropped() {
+        let cache = temp_page_cache("expiry");
+        let expired = CachedPage::new("https://example.com/old", 200, None, b"old".to_vec(), Duration::ZERO);
+        let fresh = CachedPage::new("https://example.com/new", 200, None, b"new".to_vec(), Duration::from_secs(60));
+        cache.put(&expired).unwrap();
+        cache.put(&fresh).unwrap();
+
+        assert_eq!(cache.cleanup().unwrap(), 1);
+        assert_eq!(cache.len(), 1);
+
+        cache.put(&expired).unwrap();
+        assert!(cache.get("https://example.com/old").unwrap().is_none());
+        assert_eq!(cache.len(), 1);
+    }
+}
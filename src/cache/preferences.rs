@@ -0,0 +1,288 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 用户偏好存储
+//!
+//! 不设账户：创建偏好时生成随机的不透明令牌，客户端凭令牌读写自己的偏好
+//! （语言、引擎、每页结果数与主题）。数据库中只保存令牌的 SHA-256 哈希，
+//! 记录不含任何身份信息，存放在缓存数据库的独立树中，不受缓存清空与过期清理影响
+
+use crate::cache::manager::{run_blocking, CacheError, CacheManager, Result};
+use crate::cache::alerts::now_secs;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 偏好所在的树
+const PREFERENCE_TREE: &str = "preferences";
+
+/// 最多保存的偏好引擎数
+const MAX_ENGINES: usize = 64;
+
+/// 每页结果数上限
+pub const MAX_RESULTS_PER_PAGE: u32 = 100;
+
+/// 界面主题
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// 跟随系统
+    #[default]
+    Auto,
+    /// 浅色
+    Light,
+    /// 深色
+    Dark,
+}
+
+/// 用户偏好
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserPreferences {
+    /// 偏好语言（如 `zh`、`en`）
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 偏好引擎（为空时使用默认引擎）
+    #[serde(default)]
+    pub engines: Vec<String>,
+    /// 每页结果数
+    #[serde(default)]
+    pub results_per_page: Option<u32>,
+    /// 界面主题
+    #[serde(default)]
+    pub theme: Theme,
+    /// 最后更新时间（Unix 时间戳，由存储写入）
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+impl UserPreferences {
+    /// 校验偏好取值
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if let Some(language) = &self.language
+            && (language.is_empty()
+                || language.len() > 16
+                || !language.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        {
+            return Err(format!("无效的语言代码: {}", language));
+        }
+        if self.engines.len() > MAX_ENGINES {
+            return Err(format!("偏好引擎最多 {} 个", MAX_ENGINES));
+        }
+        if let Some(engine) = self.engines.iter().find(|e| e.trim().is_empty()) {
+            return Err(format!("无效的引擎名称: {:?}", engine));
+        }
+        if let Some(n) = self.results_per_page
+            && !(1..=MAX_RESULTS_PER_PAGE).contains(&n)
+        {
+            return Err(format!("每页结果数需在 1 到 {} 之间", MAX_RESULTS_PER_PAGE));
+        }
+        Ok(())
+    }
+}
+
+/// 生成新的偏好令牌（128 位随机数的十六进制）
+pub fn new_token() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// 令牌在数据库中的键
+fn token_key(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+/// 用户偏好存储
+#[derive(Clone)]
+pub struct PreferenceStore {
+    /// 令牌哈希 → 偏好
+    tree: sled::Tree,
+}
+
+impl PreferenceStore {
+    /// 在缓存数据库中打开存储
+    ///
+    /// # 参数
+    ///
+    /// * `manager` - 缓存管理器
+    pub fn new(manager: &CacheManager) -> Result<Self> {
+        Ok(Self {
+            tree: manager.open_tree(PREFERENCE_TREE)?,
+        })
+    }
+
+    /// 保存一组新偏好
+    ///
+    /// # 返回值
+    ///
+    /// 新生成的令牌（仅此一次返回，存储中只保留其哈希）
+    pub fn create(&self, preferences: &UserPreferences) -> Result<String> {
+        let token = new_token();
+        self.write(&token, preferences)?;
+        Ok(token)
+    }
+
+    /// 读取令牌对应的偏好
+    pub fn get(&self, token: &str) -> Result<Option<UserPreferences>> {
+        match self.tree.get(token_key(token)) {
+            Ok(Some(data)) => bincode::serde::decode_from_slice(&data, bincode::config::standard())
+                .map(|(value, _)| Some(value))
+                .map_err(|e| CacheError::SerializationError(format!("反序列化失败: {}", e))),
+            Ok(None) => Ok(None),
+            Err(e) => Err(CacheError::DatabaseError(format!("读取偏好失败: {}", e))),
+        }
+    }
+
+    /// 更新令牌对应的偏好
+    ///
+    /// # 返回值
+    ///
+    /// 令牌存在时返回 true（不存在时不会创建新记录）
+    pub fn update(&self, token: &str, preferences: &UserPreferences) -> Result<bool> {
+        let exists = self
+            .tree
+            .contains_key(token_key(token))
+            .map_err(|e| CacheError::DatabaseError(format!("读取偏好失败: {}", e)))?;
+        if exists {
+            self.write(token, preferences)?;
+        }
+        Ok(exists)
+    }
+
+    /// 删除令牌对应的偏好
+    ///
+    /// # 返回值
+    ///
+    /// 令牌存在时返回 true
+    pub fn delete(&self, token: &str) -> Result<bool> {
+        self.tree
+            .remove(token_key(token))
+            .map(|old| old.is_some())
+            .map_err(|e| CacheError::DatabaseError(format!("删除偏好失败: {}", e)))
+    }
+
+    /// 偏好记录数
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// 是否没有偏好记录
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// 写入偏好（更新时间戳）
+    fn write(&self, token: &str, preferences: &UserPreferences) -> Result<()> {
+        let record = UserPreferences {
+            updated_at: now_secs(),
+            ..preferences.clone()
+        };
+        let data = bincode::serde::encode_to_vec(&record, bincode::config::standard())
+            .map_err(|e| CacheError::SerializationError(format!("序列化失败: {}", e)))?;
+        self.tree
+            .insert(token_key(token), data)
+            .map_err(|e| CacheError::DatabaseError(format!("写入偏好失败: {}", e)))?;
+        Ok(())
+    }
+
+    /// 异步保存一组新偏好（在阻塞线程池中执行）
+    pub async fn create_async(&self, preferences: UserPreferences) -> Result<String> {
+        let store = self.clone();
+        run_blocking(move || store.create(&preferences)).await
+    }
+
+    /// 异步读取偏好（在阻塞线程池中执行）
+    pub async fn get_async(&self, token: &str) -> Result<Option<UserPreferences>> {
+        let (store, token) = (self.clone(), token.to_string());
+        run_blocking(move || store.get(&token)).await
+    }
+
+    /// 异步更新偏好（在阻塞线程池中执行）
+    pub async fn update_async(&self, token: &str, preferences: UserPreferences) -> Result<bool> {
+        let (store, token) = (self.clone(), token.to_string());
+        run_blocking(move || store.update(&token, &preferences)).await
+    }
+
+    /// 异步删除偏好（在阻塞线程池中执行）
+    pub async fn delete_async(&self, token: &str) -> Result<bool> {
+        let (store, token) = (self.clone(), token.to_string());
+        run_blocking(move || store.delete(&token)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::CacheImplConfig;
+
+    #[test]
+    fn test_validate_preferences() {
+        assert!(UserPreferences::default().validate().is_ok());
+
+        let preferences = UserPreferences {
+            language: Some("zh-CN".to_string()),
+            engines: vec!["bing".to_string()],
+            results_per_page: Some(20),
+            theme: Theme::Dark,
+            updated_at: 0,
+        };
+        assert!(preferences.validate().is_ok());
+
+        let invalid = [
+            UserPreferences { language: Some("zh CN".to_string()), ..Default::default() },
+            UserPreferences { results_per_page: Some(0), ..Default::default() },
+            UserPreferences { results_per_page: Some(MAX_RESULTS_PER_PAGE + 1), ..Default::default() },
+            UserPreferences { engines: vec![" ".to_string()], ..Default::default() },
+        ];
+        for preferences in invalid {
+            assert!(preferences.validate().is_err(), "{:?}", preferences);
+        }
+    }
+
+    #[test]
+    fn test_preference_store_roundtrip() {
+        let db_path = std::env::temp_dir().join(format!("test_preference_store_{}", std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let manager = CacheManager::instance(config).unwrap();
+        let store = PreferenceStore::new(&manager).unwrap();
+
+        let preferences = UserPreferences {
+            language: Some("en".to_string()),
+            theme: Theme::Light,
+            ..Default::default()
+        };
+        let token = store.create(&preferences).unwrap();
+        assert_eq!(token.len(), 32);
+
+        // 数据库中不保存令牌原文
+        let keys: Vec<_> = store.tree.iter().keys().map(|k| k.unwrap()).collect();
+        assert!(keys.iter().all(|k| k.as_ref() != token.as_bytes()));
+
+        let stored = store.get(&token).unwrap().unwrap();
+        assert_eq!(stored.language.as_deref(), Some("en"));
+        assert!(stored.updated_at > 0);
+
+        let updated = UserPreferences {
+            results_per_page: Some(50),
+            ..stored
+        };
+        assert!(store.update(&token, &updated).unwrap());
+        assert_eq!(store.get(&token).unwrap().unwrap().results_per_page, Some(50));
+        assert!(!store.update("unknown", &updated).unwrap());
+        assert!(store.get("unknown").unwrap().is_none());
+
+        assert!(store.delete(&token).unwrap());
+        assert!(!store.delete(&token).unwrap());
+    }
+}
@@ -16,19 +16,127 @@
 //!
 //! 提供搜索结果的专门缓存功能
 
-use crate::cache::manager::{CacheManager, CacheError};
+use crate::cache::manager::{run_blocking, CacheManager, CacheError};
+use crate::cache::types::CacheBatch;
 use crate::derive::types::{SearchQuery, SearchResult};
+use crate::search::chinese::{ChineseMatching, KeywordMatcher};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use xxhash_rust::xxh3::Xxh3;
 
 type Result<T> = std::result::Result<T, CacheError>;
 
 /// 搜索结果缓存键前缀
 const RESULT_KEY_PREFIX: &str = "result:";
 
+/// 负面结果缓存键前缀（键的其余部分与搜索结果缓存键相同）
+const NEGATIVE_KEY_PREFIX: &str = "negative:";
+
+/// 搜索结果缓存键格式版本
+///
+/// 键的组成字段或哈希算法变化时递增；版本 1 为无版本标记的 `DefaultHasher` 键，
+/// 版本 2 不含引擎命名空间
+pub const RESULT_KEY_VERSION: u32 = 3;
+
+/// 不含引擎命名空间的版本 2 缓存键的版本号
+const UNSCOPED_KEY_VERSION: u32 = 2;
+
+/// 稳定的缓存键哈希器
+///
+/// 字段以固定字节序和长度前缀写入 XXH3-128，结果不随 Rust 版本变化
+struct StableKeyHasher(Xxh3);
+
+impl StableKeyHasher {
+    fn new() -> Self {
+        Self(Xxh3::new())
+    }
+
+    /// 写入 Option 是否有值的标记
+    fn write_tag(&mut self, present: bool) {
+        self.0.update(&[present as u8]);
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.0.update(&value.to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.0.update(value.as_bytes());
+    }
+
+    fn write_opt_str(&mut self, value: Option<&str>) {
+        self.write_tag(value.is_some());
+        if let Some(value) = value {
+            self.write_str(value);
+        }
+    }
+
+    fn write_opt_f64(&mut self, value: Option<f64>) {
+        self.write_tag(value.is_some());
+        if let Some(value) = value {
+            self.write_u64(value.to_bits());
+        }
+    }
+
+    fn finish(&self) -> u128 {
+        self.0.digest128()
+    }
+}
+
+/// 引擎对某一查询的负面结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NegativeOutcome {
+    /// 返回零结果
+    ZeroResults,
+    /// 被目标站点拒绝（验证码、HTTP 403/429）
+    Blocked,
+}
+
+impl NegativeOutcome {
+    /// 负面结果的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ZeroResults => "zero_results",
+            Self::Blocked => "blocked",
+        }
+    }
+}
+
+/// 单个引擎在搜索结果缓存中的统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineCacheStats {
+    /// 搜索结果条目数（含已过期条目）
+    pub entries: usize,
+    /// 已过期但尚未清理的条目数
+    pub expired: usize,
+    /// 负面结果条目数
+    pub negative_entries: usize,
+    /// 搜索结果数据大小（字节）
+    pub size_bytes: u64,
+}
+
+/// 导出的搜索结果缓存条目（JSONL 文件中的一行）
+///
+/// 缓存键由查询哈希得出，无法从结果反推，因此随条目一起导出
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedResult {
+    /// 缓存键
+    pub key: String,
+    /// 过期时间（Unix 时间戳）
+    pub expires_at: Option<u64>,
+    /// 搜索结果
+    pub result: SearchResult,
+}
+
 /// 搜索结果缓存
 ///
 /// 封装 CacheManager，提供搜索结果专用的缓存接口
+#[derive(Clone)]
 pub struct ResultCache {
     manager: Arc<CacheManager>,
 }
@@ -45,6 +153,9 @@ impl ResultCache {
 
     /// 生成搜索结果缓存键
     ///
+    /// 键格式为 `result:v{版本}:{引擎名}:{XXH3-128}`，跨 Rust 版本与平台保持不变；
+    /// 同一引擎的条目共享键前缀，可以按前缀扫描而无需遍历整个缓存
+    ///
     /// # 参数
     ///
     /// * `query` - 搜索查询
@@ -54,7 +165,79 @@ impl ResultCache {
     ///
     /// 返回唯一的缓存键字符串
     pub fn generate_key(query: &SearchQuery, engine_name: &str) -> String {
-        // 使用 hash 生成唯一键，避免键过长
+        format!("{}{:032x}", Self::engine_prefix(engine_name), Self::key_hash(query, engine_name))
+    }
+
+    /// 引擎命名空间的键前缀
+    ///
+    /// # 参数
+    ///
+    /// * `engine_name` - 引擎名称
+    ///
+    /// # 返回值
+    ///
+    /// 返回 `result:v{版本}:{引擎名}:`
+    pub fn engine_prefix(engine_name: &str) -> String {
+        format!("{}v{}:{}:", RESULT_KEY_PREFIX, RESULT_KEY_VERSION, engine_name)
+    }
+
+    /// 从当前版本的缓存键中解析引擎名
+    ///
+    /// 哈希部分固定为 32 位十六进制，引擎名本身可以包含 `:`
+    fn engine_of_key(key: &str) -> Option<&str> {
+        let rest = key.strip_prefix(RESULT_KEY_PREFIX)?;
+        let rest = rest.strip_prefix(&format!("v{}:", RESULT_KEY_VERSION))?;
+        let (engine, hash) = rest.rsplit_once(':')?;
+        (hash.len() == 32).then_some(engine)
+    }
+
+    /// 计算查询与引擎的稳定哈希（版本 2 与当前版本共用）
+    fn key_hash(query: &SearchQuery, engine_name: &str) -> u128 {
+        let mut hasher = StableKeyHasher::new();
+        hasher.write_str(&query.query);
+        hasher.write_u64(query.page as u64);
+        hasher.write_u64(query.page_size as u64);
+        hasher.write_opt_str(query.language.as_deref());
+        hasher.write_opt_str(query.region.as_deref());
+        hasher.write_str(engine_name);
+        hasher.write_opt_f64(query.lat);
+        hasher.write_opt_f64(query.lon);
+        hasher.write_tag(query.bbox.is_some());
+        if let Some(b) = query.bbox {
+            for value in [b.min_lon, b.min_lat, b.max_lon, b.max_lat] {
+                hasher.write_u64(value.to_bits());
+            }
+        }
+
+        hasher.finish()
+    }
+
+    /// 是否为当前版本的缓存键
+    fn is_current_key(key: &[u8]) -> bool {
+        let current = format!("{}v{}:", RESULT_KEY_PREFIX, RESULT_KEY_VERSION);
+        key.starts_with(current.as_bytes())
+    }
+
+    /// 生成不含引擎命名空间的版本 2 缓存键（仅用于迁移旧条目）
+    fn unscoped_key(query: &SearchQuery, engine_name: &str) -> String {
+        format!(
+            "{}v{}:{:032x}",
+            RESULT_KEY_PREFIX,
+            UNSCOPED_KEY_VERSION,
+            Self::key_hash(query, engine_name)
+        )
+    }
+
+    /// 旧版本的缓存键，按从新到旧的顺序
+    fn previous_keys(query: &SearchQuery, engine_name: &str) -> [String; 2] {
+        [Self::unscoped_key(query, engine_name), Self::legacy_key(query, engine_name)]
+    }
+
+    /// 生成版本 1 的缓存键（仅用于迁移旧条目）
+    ///
+    /// `DefaultHasher` 的输出只在同一 Rust 版本内稳定，
+    /// 升级工具链后旧条目将无法再被找到
+    fn legacy_key(query: &SearchQuery, engine_name: &str) -> String {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
@@ -65,10 +248,79 @@ impl ResultCache {
         query.language.hash(&mut hasher);
         query.region.hash(&mut hasher);
         engine_name.hash(&mut hasher);
+        // 地图搜索的位置参数（仅在设置时参与哈希，保持普通查询的键不变）
+        if query.lat.is_some() || query.lon.is_some() || query.bbox.is_some() {
+            query.lat.map(f64::to_bits).hash(&mut hasher);
+            query.lon.map(f64::to_bits).hash(&mut hasher);
+            query
+                .bbox
+                .map(|b| [b.min_lon, b.min_lat, b.max_lon, b.max_lat].map(f64::to_bits))
+                .hash(&mut hasher);
+        }
 
         format!("{}{:x}", RESULT_KEY_PREFIX, hasher.finish())
     }
 
+    /// 把旧版本的条目迁移到当前版本的键
+    ///
+    /// 旧键无法从结果反推查询与引擎，因此在按查询读取时迁移：
+    /// 新键未命中时依次查找旧键，命中则按剩余有效期写入新键并删除旧键
+    ///
+    /// # 返回值
+    ///
+    /// 返回迁移的数据，旧键都不存在时返回 None
+    fn migrate_legacy_entry(&self, query: &SearchQuery, engine_name: &str, key: &str) -> Result<Option<Vec<u8>>> {
+        for legacy in Self::previous_keys(query, engine_name) {
+            if let Some(data) = self.manager.get(&legacy)? {
+                return self.migrate_entry(&legacy, key, data).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// 按剩余有效期把条目从旧键移动到新键
+    fn migrate_entry(&self, legacy: &str, key: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let ttl = self
+            .manager
+            .get_metadata(legacy)?
+            .and_then(|meta| meta.expires_at)
+            .map(|expires_at| Duration::from_secs(expires_at.saturating_sub(now)));
+        let mut batch = CacheBatch::new();
+        batch.set(key, data.clone(), ttl).delete(legacy);
+        self.manager.apply_batch(batch)?;
+        tracing::debug!("迁移旧版本搜索结果缓存键 {} -> {}", legacy, key);
+        Ok(data)
+    }
+
+    /// 删除所有未迁移的旧版本缓存键
+    ///
+    /// 升级 Rust 工具链后旧键的哈希无法再复现，这些条目只会占用空间直到过期
+    ///
+    /// # 返回值
+    ///
+    /// 返回删除的条目数
+    pub fn purge_legacy_keys(&self) -> Result<usize> {
+        let mut legacy = Vec::new();
+        for item in self.manager.iter() {
+            let (key, _) = item.map_err(|e| {
+                CacheError::DatabaseError(format!("遍历缓存失败: {}", e))
+            })?;
+            if key.starts_with(RESULT_KEY_PREFIX.as_bytes()) && !Self::is_current_key(&key) {
+                legacy.push(String::from_utf8_lossy(&key).into_owned());
+            }
+        }
+
+        for key in &legacy {
+            self.manager.delete(key)?;
+        }
+        Ok(legacy.len())
+    }
+
     /// 获取缓存的搜索结果
     ///
     /// # 参数
@@ -81,8 +333,12 @@ impl ResultCache {
     /// 返回缓存的搜索结果，如果不存在或已过期则返回 None
     pub fn get(&self, query: &SearchQuery, engine_name: &str) -> Result<Option<SearchResult>> {
         let key = Self::generate_key(query, engine_name);
-        
-        match self.manager.get(&key)? {
+        let data = match self.manager.get(&key)? {
+            Some(data) => Some(data),
+            None => self.migrate_legacy_entry(query, engine_name, &key)?,
+        };
+
+        match data {
             Some(data) => {
                 // 反序列化搜索结果
                 let result: SearchResult = bincode::serde::decode_from_slice(&data, bincode::config::standard())
@@ -111,9 +367,16 @@ impl ResultCache {
     /// 如果缓存不存在，返回 None
     pub fn is_stale(&self, query: &SearchQuery, engine_name: &str, timeline: u64) -> Result<Option<bool>> {
         let key = Self::generate_key(query, engine_name);
-        
-        // 获取缓存元数据
-        if let Some(metadata) = self.manager.get_metadata(&key)? {
+
+        // 获取缓存元数据（尚未迁移的旧条目按旧键的元数据判断）
+        let mut metadata = self.manager.get_metadata(&key)?;
+        for legacy in Self::previous_keys(query, engine_name) {
+            if metadata.is_some() {
+                break;
+            }
+            metadata = self.manager.get_metadata(&legacy)?;
+        }
+        if let Some(metadata) = metadata {
             use std::time::{SystemTime, UNIX_EPOCH};
             let now = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
@@ -152,6 +415,32 @@ impl ResultCache {
         self.manager.set(key, data, ttl)
     }
 
+    /// 原子地缓存一次聚合搜索的各引擎结果
+    ///
+    /// 每个引擎的结果按 `result.engine_name` 写入各自的键，所有条目要么全部写入，要么全部不写入
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 搜索查询
+    /// * `results` - 各引擎的搜索结果
+    /// * `ttl` - 生存时间，None 表示使用默认值
+    pub fn set_batch(
+        &self,
+        query: &SearchQuery,
+        results: &[SearchResult],
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        let mut batch = CacheBatch::new();
+        for result in results {
+            let data = bincode::serde::encode_to_vec(result, bincode::config::standard()).map_err(|e| {
+                CacheError::SerializationError(format!("序列化搜索结果失败: {}", e))
+            })?;
+            batch.set(Self::generate_key(query, &result.engine_name), data, ttl);
+        }
+
+        self.manager.apply_batch(batch).map(|_| ())
+    }
+
     /// 删除缓存的搜索结果
     ///
     /// # 参数
@@ -160,7 +449,166 @@ impl ResultCache {
     /// * `engine_name` - 引擎名称
     pub fn delete(&self, query: &SearchQuery, engine_name: &str) -> Result<bool> {
         let key = Self::generate_key(query, engine_name);
-        self.manager.delete(&key)
+        let mut deleted = self.manager.delete(&key)?;
+        for legacy in Self::previous_keys(query, engine_name) {
+            deleted |= self.manager.delete(&legacy)?;
+        }
+        Ok(deleted)
+    }
+
+    /// 生成负面结果缓存键
+    fn negative_key(query: &SearchQuery, engine_name: &str) -> String {
+        format!("{}{}", NEGATIVE_KEY_PREFIX, &Self::generate_key(query, engine_name)[RESULT_KEY_PREFIX.len()..])
+    }
+
+    /// 读取缓存的负面结果
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 搜索查询
+    /// * `engine_name` - 引擎名称
+    ///
+    /// # 返回值
+    ///
+    /// 返回冷却期内的负面结果，不存在或已过期时返回 None
+    pub fn get_negative(&self, query: &SearchQuery, engine_name: &str) -> Result<Option<NegativeOutcome>> {
+        let Some(data) = self.manager.get(&Self::negative_key(query, engine_name))? else {
+            return Ok(None);
+        };
+        serde_json::from_slice(&data)
+            .map(Some)
+            .map_err(|e| CacheError::SerializationError(format!("反序列化负面结果失败: {}", e)))
+    }
+
+    /// 缓存负面结果
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 搜索查询
+    /// * `engine_name` - 引擎名称
+    /// * `outcome` - 负面结果
+    /// * `ttl` - 冷却时长
+    pub fn set_negative(
+        &self,
+        query: &SearchQuery,
+        engine_name: &str,
+        outcome: NegativeOutcome,
+        ttl: Duration,
+    ) -> Result<()> {
+        let data = serde_json::to_vec(&outcome)
+            .map_err(|e| CacheError::SerializationError(format!("序列化负面结果失败: {}", e)))?;
+        self.manager.set(Self::negative_key(query, engine_name), data, Some(ttl))
+    }
+
+    /// 负面结果在引擎命名空间中的键前缀
+    fn negative_engine_prefix(engine_name: &str) -> String {
+        format!("{}{}", NEGATIVE_KEY_PREFIX, &Self::engine_prefix(engine_name)[RESULT_KEY_PREFIX.len()..])
+    }
+
+    /// 收集引擎命名空间中的键
+    ///
+    /// 按前缀扫描，开销只与该引擎的条目数有关；
+    /// 前缀相同的其他引擎（如 `a` 与 `a:b`）的键会被排除
+    fn engine_keys(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        for item in self.manager.scan_prefix(prefix) {
+            let (key, _) = item.map_err(|e| {
+                CacheError::DatabaseError(format!("遍历缓存失败: {}", e))
+            })?;
+            let key = String::from_utf8_lossy(&key).into_owned();
+            if key[prefix.len()..].len() == 32 {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
+
+    /// 使某个引擎的全部缓存结果失效
+    ///
+    /// 同时删除该引擎的负面结果；只扫描该引擎的命名空间。
+    /// 尚未迁移的旧版本条目无法归属到引擎，需要通过 [`purge_legacy_keys`](Self::purge_legacy_keys) 删除
+    ///
+    /// # 参数
+    ///
+    /// * `engine_name` - 引擎名称
+    ///
+    /// # 返回值
+    ///
+    /// 返回删除的条目数
+    pub fn invalidate_engine(&self, engine_name: &str) -> Result<usize> {
+        let mut batch = CacheBatch::new();
+        for prefix in [Self::engine_prefix(engine_name), Self::negative_engine_prefix(engine_name)] {
+            for key in self.engine_keys(&prefix)? {
+                batch.delete(key);
+            }
+        }
+        self.manager.apply_batch(batch)
+    }
+
+    /// 获取单个引擎的缓存统计
+    ///
+    /// # 参数
+    ///
+    /// * `engine_name` - 引擎名称
+    ///
+    /// # 返回值
+    ///
+    /// 返回该引擎命名空间中的条目统计
+    pub fn engine_stats(&self, engine_name: &str) -> Result<EngineCacheStats> {
+        let mut stats = EngineCacheStats::default();
+        for key in self.engine_keys(&Self::engine_prefix(engine_name))? {
+            self.count_entry(&key, &mut stats)?;
+        }
+        stats.negative_entries = self
+            .engine_keys(&Self::negative_engine_prefix(engine_name))?
+            .len();
+        Ok(stats)
+    }
+
+    /// 获取所有引擎的缓存统计
+    ///
+    /// 只扫描当前版本的搜索结果键与负面结果键
+    ///
+    /// # 返回值
+    ///
+    /// 返回按引擎名排序的统计
+    pub fn all_engine_stats(&self) -> Result<BTreeMap<String, EngineCacheStats>> {
+        let mut all: BTreeMap<String, EngineCacheStats> = BTreeMap::new();
+        let current = format!("{}v{}:", RESULT_KEY_PREFIX, RESULT_KEY_VERSION);
+        for item in self.manager.scan_prefix(&current) {
+            let (key, _) = item.map_err(|e| {
+                CacheError::DatabaseError(format!("遍历缓存失败: {}", e))
+            })?;
+            let key = String::from_utf8_lossy(&key);
+            if let Some(engine) = Self::engine_of_key(&key) {
+                self.count_entry(&key, all.entry(engine.to_string()).or_default())?;
+            }
+        }
+
+        let negative = format!("{}v{}:", NEGATIVE_KEY_PREFIX, RESULT_KEY_VERSION);
+        for item in self.manager.scan_prefix(&negative) {
+            let (key, _) = item.map_err(|e| {
+                CacheError::DatabaseError(format!("遍历缓存失败: {}", e))
+            })?;
+            let key = String::from_utf8_lossy(&key);
+            let key = format!("{}{}", RESULT_KEY_PREFIX, &key[NEGATIVE_KEY_PREFIX.len()..]);
+            if let Some(engine) = Self::engine_of_key(&key) {
+                all.entry(engine.to_string()).or_default().negative_entries += 1;
+            }
+        }
+        Ok(all)
+    }
+
+    /// 把一个搜索结果条目计入统计
+    fn count_entry(&self, key: &str, stats: &mut EngineCacheStats) -> Result<()> {
+        stats.entries += 1;
+        if let Some(metadata) = self.manager.get_metadata(key)? {
+            stats.size_bytes += metadata.size_bytes as u64;
+            if metadata.is_expired() {
+                stats.expired += 1;
+            }
+        }
+        Ok(())
     }
 
     /// 清空所有搜索结果缓存
@@ -173,6 +621,105 @@ impl ResultCache {
         &self.manager
     }
 
+    /// 异步获取缓存的搜索结果（在阻塞线程池中执行）
+    pub async fn get_async(&self, query: &SearchQuery, engine_name: &str) -> Result<Option<SearchResult>> {
+        let cache = self.clone();
+        let query = query.clone();
+        let engine_name = engine_name.to_string();
+        run_blocking(move || cache.get(&query, &engine_name)).await
+    }
+
+    /// 异步检查缓存是否过期（在阻塞线程池中执行）
+    pub async fn is_stale_async(&self, query: &SearchQuery, engine_name: &str, timeline: u64) -> Result<Option<bool>> {
+        let cache = self.clone();
+        let query = query.clone();
+        let engine_name = engine_name.to_string();
+        run_blocking(move || cache.is_stale(&query, &engine_name, timeline)).await
+    }
+
+    /// 异步缓存搜索结果（在阻塞线程池中执行）
+    pub async fn set_async(
+        &self,
+        query: &SearchQuery,
+        engine_name: &str,
+        result: &SearchResult,
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        let cache = self.clone();
+        let query = query.clone();
+        let engine_name = engine_name.to_string();
+        let result = result.clone();
+        run_blocking(move || cache.set(&query, &engine_name, &result, ttl)).await
+    }
+
+    /// 异步批量缓存搜索结果（在阻塞线程池中执行）
+    pub async fn set_batch_async(
+        &self,
+        query: &SearchQuery,
+        results: &[SearchResult],
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        let cache = self.clone();
+        let query = query.clone();
+        let results = results.to_vec();
+        run_blocking(move || cache.set_batch(&query, &results, ttl)).await
+    }
+
+    /// 异步读取缓存的负面结果（在阻塞线程池中执行）
+    pub async fn get_negative_async(&self, query: &SearchQuery, engine_name: &str) -> Result<Option<NegativeOutcome>> {
+        let cache = self.clone();
+        let query = query.clone();
+        let engine_name = engine_name.to_string();
+        run_blocking(move || cache.get_negative(&query, &engine_name)).await
+    }
+
+    /// 异步缓存负面结果（在阻塞线程池中执行）
+    pub async fn set_negative_async(
+        &self,
+        query: &SearchQuery,
+        engine_name: &str,
+        outcome: NegativeOutcome,
+        ttl: Duration,
+    ) -> Result<()> {
+        let cache = self.clone();
+        let query = query.clone();
+        let engine_name = engine_name.to_string();
+        run_blocking(move || cache.set_negative(&query, &engine_name, outcome, ttl)).await
+    }
+
+    /// 异步删除缓存的搜索结果（在阻塞线程池中执行）
+    pub async fn delete_async(&self, query: &SearchQuery, engine_name: &str) -> Result<bool> {
+        let cache = self.clone();
+        let query = query.clone();
+        let engine_name = engine_name.to_string();
+        run_blocking(move || cache.delete(&query, &engine_name)).await
+    }
+
+    /// 异步使某个引擎的全部缓存结果失效（在阻塞线程池中执行）
+    pub async fn invalidate_engine_async(&self, engine_name: &str) -> Result<usize> {
+        let cache = self.clone();
+        let engine_name = engine_name.to_string();
+        run_blocking(move || cache.invalidate_engine(&engine_name)).await
+    }
+
+    /// 异步获取所有引擎的缓存统计（在阻塞线程池中执行）
+    pub async fn all_engine_stats_async(&self) -> Result<BTreeMap<String, EngineCacheStats>> {
+        let cache = self.clone();
+        run_blocking(move || cache.all_engine_stats()).await
+    }
+
+    /// 异步全文搜索（在阻塞线程池中执行，参数同 [`search_fulltext_with`](Self::search_fulltext_with)）
+    pub async fn search_fulltext_async(
+        &self,
+        matcher: &KeywordMatcher,
+        include_stale: bool,
+        max_results: Option<usize>,
+    ) -> Result<Vec<crate::derive::types::SearchResultItem>> {
+        let cache = self.clone();
+        let matcher = matcher.clone();
+        run_blocking(move || cache.search_fulltext_with(&matcher, include_stale, max_results)).await
+    }
+
     /// 全文搜索 - 在所有缓存的搜索结果中查找包含关键词的项目
     ///
     /// # 参数
@@ -196,6 +743,27 @@ impl ResultCache {
         keywords: &[String],
         include_stale: bool,
         max_results: Option<usize>,
+    ) -> Result<Vec<crate::derive::types::SearchResultItem>> {
+        let matcher = KeywordMatcher::new(keywords, ChineseMatching::default());
+        self.search_fulltext_with(&matcher, include_stale, max_results)
+    }
+
+    /// 使用关键词匹配器进行全文搜索
+    ///
+    /// # 参数
+    ///
+    /// * `matcher` - 关键词匹配器（决定繁简统一与拼音匹配）
+    /// * `include_stale` - 是否包含过期的缓存结果
+    /// * `max_results` - 最大返回结果数（可选）
+    ///
+    /// # 返回值
+    ///
+    /// 返回标题、内容或 URL 匹配任意关键词的搜索结果项
+    pub fn search_fulltext_with(
+        &self,
+        matcher: &KeywordMatcher,
+        include_stale: bool,
+        max_results: Option<usize>,
     ) -> Result<Vec<crate::derive::types::SearchResultItem>> {
         let mut matched_items = Vec::new();
         let max = max_results.unwrap_or(usize::MAX);
@@ -239,12 +807,9 @@ impl ResultCache {
                 }
 
                 // 检查标题、内容和 URL 是否包含任何关键词
-                let matches = keywords.iter().any(|keyword| {
-                    let keyword_lower = keyword.to_lowercase();
-                    item.title.to_lowercase().contains(&keyword_lower)
-                        || item.content.to_lowercase().contains(&keyword_lower)
-                        || item.url.to_lowercase().contains(&keyword_lower)
-                });
+                let matches = matcher.matches(&item.title)
+                    || matcher.matches(&item.content)
+                    || matcher.matches(&item.url);
 
                 if matches {
                     matched_items.push(item);
@@ -255,6 +820,142 @@ impl ResultCache {
         Ok(matched_items)
     }
 
+    /// 收集缓存结果中的标题（用于构建拼写纠错词典）
+    ///
+    /// # 参数
+    ///
+    /// * `max_titles` - 最多返回的标题数（可选）
+    ///
+    /// # 返回值
+    ///
+    /// 返回缓存中的结果标题，包含过期条目
+    pub fn cached_titles(&self, max_titles: Option<usize>) -> Result<Vec<String>> {
+        let mut titles = Vec::new();
+        let max = max_titles.unwrap_or(usize::MAX);
+
+        for item in self.manager.iter() {
+            if titles.len() >= max {
+                break;
+            }
+
+            let (key, value) = item.map_err(|e| {
+                CacheError::DatabaseError(format!("遍历缓存失败: {}", e))
+            })?;
+            if !key.starts_with(RESULT_KEY_PREFIX.as_bytes()) {
+                continue;
+            }
+
+            let result: SearchResult = match bincode::serde::decode_from_slice(&value, bincode::config::standard()) {
+                Ok((res, _)) => res,
+                Err(_) => continue,
+            };
+            let remaining = max - titles.len();
+            titles.extend(result.items.into_iter().take(remaining).map(|item| item.title));
+        }
+
+        Ok(titles)
+    }
+
+    /// 将缓存的搜索结果导出为 JSONL
+    ///
+    /// # 参数
+    ///
+    /// * `writer` - 输出目标
+    /// * `include_stale` - 是否包含过期的缓存结果
+    ///
+    /// # 返回值
+    ///
+    /// 返回导出的条目数
+    pub fn export_jsonl<W: Write>(&self, writer: &mut W, include_stale: bool) -> Result<usize> {
+        let mut count = 0;
+
+        for item in self.manager.iter() {
+            let (key, value) = item.map_err(|e| {
+                CacheError::DatabaseError(format!("遍历缓存失败: {}", e))
+            })?;
+            if !key.starts_with(RESULT_KEY_PREFIX.as_bytes()) {
+                continue;
+            }
+
+            let key = String::from_utf8_lossy(&key).into_owned();
+            let metadata = self.manager.get_metadata(&key)?;
+            if !include_stale && metadata.as_ref().is_none_or(|meta| meta.is_expired()) {
+                continue;
+            }
+            let result: SearchResult = match bincode::serde::decode_from_slice(&value, bincode::config::standard()) {
+                Ok((res, _)) => res,
+                Err(_) => continue,
+            };
+
+            let entry = ExportedResult {
+                key,
+                expires_at: metadata.and_then(|meta| meta.expires_at),
+                result,
+            };
+            let line = serde_json::to_string(&entry).map_err(|e| {
+                CacheError::SerializationError(format!("序列化导出条目失败: {}", e))
+            })?;
+            writeln!(writer, "{}", line).map_err(|e| {
+                CacheError::DatabaseError(format!("写入导出文件失败: {}", e))
+            })?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// 从 JSONL 导入搜索结果缓存
+    ///
+    /// 已过期的条目会被跳过，其余条目按剩余有效期写回
+    ///
+    /// # 参数
+    ///
+    /// * `reader` - 由 [`export_jsonl`](Self::export_jsonl) 生成的内容
+    ///
+    /// # 返回值
+    ///
+    /// 返回导入的条目数
+    pub fn import_jsonl<R: BufRead>(&self, reader: R) -> Result<usize> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut count = 0;
+
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| {
+                CacheError::DatabaseError(format!("读取导入文件失败: {}", e))
+            })?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry: ExportedResult = serde_json::from_str(&line).map_err(|e| {
+                CacheError::SerializationError(format!("第 {} 行解析失败: {}", line_number + 1, e))
+            })?;
+            if !entry.key.starts_with(RESULT_KEY_PREFIX) {
+                return Err(CacheError::SerializationError(format!(
+                    "第 {} 行不是搜索结果缓存键: {}",
+                    line_number + 1,
+                    entry.key
+                )));
+            }
+
+            let ttl = match entry.expires_at {
+                Some(expires_at) if expires_at <= now => continue,
+                Some(expires_at) => Some(Duration::from_secs(expires_at - now)),
+                None => None,
+            };
+            let data = bincode::serde::encode_to_vec(&entry.result, bincode::config::standard()).map_err(|e| {
+                CacheError::SerializationError(format!("序列化搜索结果失败: {}", e))
+            })?;
+            self.manager.set(entry.key, data, ttl)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
     /// 按查询字符串搜索缓存的结果
     ///
     /// # 参数
@@ -369,6 +1070,9 @@ mod tests {
             safe_search: SafeSearchLevel::Moderate,
             time_range: None,
             params: HashMap::new(),
+            lat: None,
+            lon: None,
+            bbox: None,
         }
     }
 
@@ -420,6 +1124,37 @@ mod tests {
         assert_eq!(cached_result.items.len(), result.items.len());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_result_cache_async_roundtrip() {
+        let cache = temp_result_cache();
+        let mut query = sample_query();
+        query.query = "async roundtrip".to_string();
+        let result = sample_result();
+
+        cache.set_async(&query, "TestEngine", &result, None).await.unwrap();
+        let cached = cache.get_async(&query, "TestEngine").await.unwrap().unwrap();
+        assert_eq!(cached.items.len(), result.items.len());
+        assert_eq!(cache.is_stale_async(&query, "TestEngine", 3600).await.unwrap(), Some(false));
+
+        assert!(cache.delete_async(&query, "TestEngine").await.unwrap());
+        assert!(cache.get_async(&query, "TestEngine").await.unwrap().is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn test_result_cache_set_batch() {
+        let cache = temp_result_cache();
+        let mut query = sample_query();
+        query.query = "aggregated batch".to_string();
+        let mut other = sample_result();
+        other.engine_name = "OtherEngine".to_string();
+
+        cache.set_batch(&query, &[sample_result(), other], None).unwrap();
+        assert!(cache.get(&query, "TestEngine").unwrap().is_some());
+        assert!(cache.get(&query, "OtherEngine").unwrap().is_some());
+    }
+
     #[test]
     #[serial]
     fn test_result_cache_miss() {
@@ -432,6 +1167,34 @@ mod tests {
         assert!(cached.is_none());
     }
 
+    #[test]
+    #[serial]
+    fn test_result_cache_export_import() {
+        let cache = temp_result_cache();
+        let mut query = sample_query();
+        query.query = "export import roundtrip".to_string();
+        let result = sample_result();
+        let engine_name = "TestEngine";
+
+        cache.set(&query, engine_name, &result, None).expect("缓存搜索结果失败");
+        let mut exported = Vec::new();
+        let count = cache.export_jsonl(&mut exported, false).expect("导出失败");
+        assert!(count >= 1);
+
+        let key = ResultCache::generate_key(&query, engine_name);
+        assert!(String::from_utf8_lossy(&exported).contains(&key));
+
+        cache.delete(&query, engine_name).expect("删除失败");
+        assert!(cache.get(&query, engine_name).unwrap_or(None).is_none());
+
+        let imported = cache.import_jsonl(exported.as_slice()).expect("导入失败");
+        assert_eq!(imported, count);
+        let restored = cache.get(&query, engine_name).unwrap_or(None).expect("导入后应命中缓存");
+        assert_eq!(restored.items[0].title, "Test Result");
+
+        assert!(cache.import_jsonl("not json\n".as_bytes()).is_err());
+    }
+
     #[test]
     #[serial]
     fn test_result_cache_delete() {
@@ -452,6 +1215,21 @@ mod tests {
         assert!(cache.get(&query, engine_name).unwrap_or(None).is_none());
     }
 
+    #[test]
+    #[serial]
+    fn test_result_cache_negative_outcome() {
+        let cache = temp_result_cache();
+        let query = sample_query();
+
+        assert_eq!(cache.get_negative(&query, "NegativeEngine").unwrap(), None);
+        cache
+            .set_negative(&query, "NegativeEngine", NegativeOutcome::Blocked, Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(cache.get_negative(&query, "NegativeEngine").unwrap(), Some(NegativeOutcome::Blocked));
+        // 负面结果不影响正常结果的读取
+        assert!(cache.get(&query, "NegativeEngine").unwrap().is_none());
+    }
+
     #[test]
     #[serial]
     fn test_result_cache_key_generation() {
@@ -469,6 +1247,89 @@ mod tests {
         query3.page = 2;
         let key3 = ResultCache::generate_key(&query3, engine_name);
         assert_ne!(key1, key3);
+
+        // 位置参数不同应该生成不同的键
+        let mut query4 = sample_query();
+        query4.lat = Some(48.85);
+        query4.lon = Some(2.35);
+        let key4 = ResultCache::generate_key(&query4, engine_name);
+        assert_ne!(key1, key4);
+
+        // 键带版本标记且与 Rust 版本无关
+        assert!(key1.starts_with(&format!("result:v{}:", RESULT_KEY_VERSION)));
+        assert_eq!(key1, "result:v3:TestEngine:ad1ee3fbb6b0afb4d92b60d4d4b35bfd");
+        assert_eq!(ResultCache::engine_of_key(&key1), Some(engine_name));
+        // 版本 2 的键与当前版本共用哈希，只是不含引擎命名空间
+        assert_eq!(
+            ResultCache::unscoped_key(&query1, engine_name),
+            "result:v2:ad1ee3fbb6b0afb4d92b60d4d4b35bfd"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_result_cache_invalidate_engine() {
+        let cache = temp_result_cache();
+        let query = sample_query();
+        let result = sample_result();
+
+        cache.set(&query, "alpha", &result, None).unwrap();
+        cache.set(&query, "alpha:beta", &result, None).unwrap();
+        cache.set(&query, "gamma", &result, None).unwrap();
+        cache
+            .set_negative(&query, "alpha", NegativeOutcome::ZeroResults, Duration::from_secs(60))
+            .unwrap();
+
+        let stats = cache.engine_stats("alpha").unwrap();
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.negative_entries, 1);
+        assert!(stats.size_bytes > 0);
+        let all = cache.all_engine_stats().unwrap();
+        assert_eq!(all.keys().collect::<Vec<_>>(), vec!["alpha", "alpha:beta", "gamma"]);
+        assert_eq!(all["alpha"], stats);
+
+        // 只删除该引擎的命名空间，前缀相同的其他引擎不受影响
+        assert_eq!(cache.invalidate_engine("alpha").unwrap(), 2);
+        assert!(cache.get(&query, "alpha").unwrap().is_none());
+        assert!(cache.get_negative(&query, "alpha").unwrap().is_none());
+        assert!(cache.get(&query, "alpha:beta").unwrap().is_some());
+        assert!(cache.get(&query, "gamma").unwrap().is_some());
+        assert_eq!(cache.engine_stats("alpha").unwrap(), EngineCacheStats::default());
+    }
+
+    #[test]
+    #[serial]
+    fn test_result_cache_migrates_legacy_keys() {
+        let cache = temp_result_cache();
+        let mut query = sample_query();
+        query.query = "legacy migration".to_string();
+        let result = sample_result();
+        let data = bincode::serde::encode_to_vec(&result, bincode::config::standard()).unwrap();
+
+        // 版本 2 的条目在读取时迁移到引擎命名空间
+        let unscoped = ResultCache::unscoped_key(&query, "TestEngine");
+        cache.manager.set(unscoped.clone(), data.clone(), Some(Duration::from_secs(60))).unwrap();
+        assert!(cache.get(&query, "TestEngine").unwrap().is_some());
+        assert!(cache.manager.get(&unscoped).unwrap().is_none());
+        assert!(cache.delete(&query, "TestEngine").unwrap());
+
+        // 版本 1 的条目在读取时迁移到新键
+        let legacy = ResultCache::legacy_key(&query, "TestEngine");
+        cache.manager.set(legacy.clone(), data.clone(), Some(Duration::from_secs(60))).unwrap();
+        let migrated = cache.get(&query, "TestEngine").unwrap().unwrap();
+        assert_eq!(migrated.items.len(), result.items.len());
+        assert!(cache.manager.get(&legacy).unwrap().is_none());
+        let key = ResultCache::generate_key(&query, "TestEngine");
+        assert!(cache.manager.get(&key).unwrap().is_some());
+
+        // 无法迁移的旧条目可以批量删除
+        let mut other = sample_query();
+        other.query = "orphan".to_string();
+        cache.manager.set(ResultCache::legacy_key(&other, "TestEngine"), data, None).unwrap();
+        assert_eq!(cache.purge_legacy_keys().unwrap(), 1);
+        assert!(cache.manager.get(&key).unwrap().is_some());
+
+        assert!(cache.delete(&query, "TestEngine").unwrap());
     }
 
     #[test]
@@ -492,4 +1353,18 @@ mod tests {
         // 获取应该返回 None
         assert!(cache.get(&query, engine_name).unwrap_or(None).is_none());
     }
+
+    #[test]
+    #[serial]
+    fn test_result_cache_cached_titles() {
+        let cache = temp_result_cache();
+        let query = sample_query();
+        let result = sample_result();
+
+        cache.set(&query, "TestEngine", &result, None).expect("缓存搜索结果失败");
+
+        let titles = cache.cached_titles(None).expect("读取缓存标题失败");
+        assert!(titles.contains(&"Test Result".to_string()));
+        assert!(cache.cached_titles(Some(0)).unwrap().is_empty());
+    }
 }
@@ -16,8 +16,10 @@
 //!
 //! 提供 RSS feed 结果的专门缓存功能，支持持久化和自动更新
 
-use crate::cache::manager::{CacheManager, CacheError};
-use crate::derive::rss::RssFeed;
+use crate::cache::manager::{run_blocking, CacheManager, CacheError};
+use crate::derive::rss::{RssFeed, RssFeedItem};
+use crate::search::chinese::{ChineseMatching, KeywordMatcher};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
@@ -27,6 +29,21 @@ type Result<T> = std::result::Result<T, CacheError>;
 /// RSS 缓存键前缀
 const RSS_KEY_PREFIX: &str = "rss:";
 const RSS_META_PREFIX: &str = "rss_meta:";
+/// 已见条目记录键前缀
+const RSS_SEEN_PREFIX: &str = "rss_seen:";
+/// 已读状态键前缀（`rss_read:<客户端令牌>:<feed URL>`）
+const RSS_READ_PREFIX: &str = "rss_read:";
+
+/// 每个 feed 最多记录的已见条目数（超出时丢弃最早见到的条目）
+pub const MAX_SEEN_ITEMS_PER_FEED: usize = 5000;
+
+/// 客户端令牌的最大长度
+const MAX_READER_TOKEN_LEN: usize = 128;
+
+/// 条目标识（优先使用 GUID，没有 GUID 时使用链接）
+pub fn rss_item_key(item: &RssFeedItem) -> &str {
+    item.guid.as_deref().unwrap_or(&item.link)
+}
 
 /// RSS Feed 缓存元数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +65,7 @@ pub struct RssFeedCacheMeta {
 /// RSS 结果缓存
 ///
 /// 封装 CacheManager，提供 RSS feed 专用的缓存接口
+#[derive(Clone)]
 pub struct RssCache {
     manager: Arc<CacheManager>,
 }
@@ -68,6 +86,16 @@ impl RssCache {
         format!("{}{}", RSS_META_PREFIX, url)
     }
 
+    /// 生成已见条目记录键
+    pub fn generate_seen_key(url: &str) -> String {
+        format!("{}{}", RSS_SEEN_PREFIX, url)
+    }
+
+    /// 生成已读状态键
+    pub fn generate_read_key(token: &str, url: &str) -> String {
+        format!("{}{}:{}", RSS_READ_PREFIX, token, url)
+    }
+
     /// 获取当前时间戳
     fn current_timestamp() -> u64 {
         SystemTime::now()
@@ -97,31 +125,17 @@ impl RssCache {
 
         // 去重：基于 link 和 guid
         let mut deduped_items = Vec::new();
-        let mut seen_keys = std::collections::HashSet::new();
+        let mut seen_keys = HashSet::new();
 
         for item in &feed.items {
-            let key = if let Some(ref guid) = item.guid {
-                guid.clone()
-            } else {
-                item.link.clone()
-            };
-
-            if !seen_keys.contains(&key) {
-                seen_keys.insert(key);
+            if seen_keys.insert(rss_item_key(item).to_string()) {
                 deduped_items.push(item.clone());
             }
         }
 
         // 合并新旧项目（保留旧的，添加新的）
         for item in existing_items {
-            let key = if let Some(ref guid) = item.guid {
-                guid.clone()
-            } else {
-                item.link.clone()
-            };
-
-            if !seen_keys.contains(&key) {
-                seen_keys.insert(key);
+            if seen_keys.insert(rss_item_key(&item).to_string()) {
                 deduped_items.push(item);
             }
         }
@@ -191,11 +205,72 @@ impl RssCache {
         }
     }
 
+    /// 异步存储 RSS feed（在阻塞线程池中执行）
+    pub async fn set_async(
+        &self,
+        url: &str,
+        feed: &RssFeed,
+        persistent: bool,
+        update_interval: Option<u64>,
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        let cache = self.clone();
+        let url = url.to_string();
+        let feed = feed.clone();
+        run_blocking(move || cache.set(&url, &feed, persistent, update_interval, ttl)).await
+    }
+
+    /// 异步获取 RSS feed（在阻塞线程池中执行）
+    pub async fn get_async(&self, url: &str) -> Result<Option<RssFeed>> {
+        let cache = self.clone();
+        let url = url.to_string();
+        run_blocking(move || cache.get(&url)).await
+    }
+
+    /// 异步检查是否需要更新（在阻塞线程池中执行）
+    pub async fn needs_update_async(&self, url: &str) -> Result<bool> {
+        let cache = self.clone();
+        let url = url.to_string();
+        run_blocking(move || cache.needs_update(&url)).await
+    }
+
+    /// 异步全文搜索（在阻塞线程池中执行，参数同 [`search_fulltext_with`](Self::search_fulltext_with)）
+    pub async fn search_fulltext_async(
+        &self,
+        matcher: &KeywordMatcher,
+        include_stale: bool,
+        max_results: Option<usize>,
+    ) -> Result<Vec<(String, crate::derive::rss::RssFeedItem)>> {
+        let cache = self.clone();
+        let matcher = matcher.clone();
+        run_blocking(move || cache.search_fulltext_with(&matcher, include_stale, max_results)).await
+    }
+
     /// 列出所有持久化的 RSS feeds
+    ///
+    /// 持久化 feed 即用户的订阅，按 URL 排序返回
     pub fn list_persistent_feeds(&self) -> Result<Vec<RssFeedCacheMeta>> {
-        // Note: This is a simplified implementation
-        // In production, you might want to maintain a separate index
-        Ok(vec![])
+        let mut feeds = Vec::new();
+
+        for item in self.manager.iter() {
+            let (key, value) = item.map_err(|e| {
+                CacheError::DatabaseError(format!("遍历缓存失败: {}", e))
+            })?;
+            if !key.starts_with(RSS_META_PREFIX.as_bytes()) {
+                continue;
+            }
+
+            let meta: RssFeedCacheMeta = match bincode::serde::decode_from_slice(&value, bincode::config::standard()) {
+                Ok((meta, _)) => meta,
+                Err(_) => continue, // 跳过损坏的数据
+            };
+            if meta.persistent {
+                feeds.push(meta);
+            }
+        }
+
+        feeds.sort_by(|a, b| a.url.cmp(&b.url));
+        Ok(feeds)
     }
 
     /// 删除 RSS feed 缓存
@@ -205,7 +280,23 @@ impl RssCache {
         
         self.manager.delete(&key)?;
         self.manager.delete(&meta_key)?;
-        
+        self.manager.delete(&Self::generate_seen_key(url))?;
+
+        // 删除所有客户端在该 feed 上的已读状态
+        let mut read_keys = Vec::new();
+        for item in self.manager.iter() {
+            let (key, _) = item.map_err(|e| CacheError::DatabaseError(format!("遍历缓存失败: {}", e)))?;
+            let key = String::from_utf8_lossy(&key);
+            if let Some(rest) = key.strip_prefix(RSS_READ_PREFIX)
+                && rest.split_once(':').is_some_and(|(_, feed_url)| feed_url == url)
+            {
+                read_keys.push(key.into_owned());
+            }
+        }
+        for key in read_keys {
+            self.manager.delete(&key)?;
+        }
+
         Ok(())
     }
 
@@ -232,6 +323,27 @@ impl RssCache {
         keywords: &[String],
         include_stale: bool,
         max_results: Option<usize>,
+    ) -> Result<Vec<(String, crate::derive::rss::RssFeedItem)>> {
+        let matcher = KeywordMatcher::new(keywords, ChineseMatching::default());
+        self.search_fulltext_with(&matcher, include_stale, max_results)
+    }
+
+    /// 使用关键词匹配器进行 RSS 全文搜索
+    ///
+    /// # 参数
+    ///
+    /// * `matcher` - 关键词匹配器（决定繁简统一与拼音匹配）
+    /// * `include_stale` - 是否包含过期的缓存结果
+    /// * `max_results` - 最大返回结果数（可选）
+    ///
+    /// # 返回值
+    ///
+    /// 返回标题、描述、内容或链接匹配任意关键词的 RSS 项，每项包含 (feed_url, item)
+    pub fn search_fulltext_with(
+        &self,
+        matcher: &KeywordMatcher,
+        include_stale: bool,
+        max_results: Option<usize>,
     ) -> Result<Vec<(String, crate::derive::rss::RssFeedItem)>> {
         use crate::derive::rss::RssFeed;
 
@@ -280,17 +392,10 @@ impl RssCache {
                 }
 
                 // 检查标题、描述和内容是否包含任何关键词
-                let matches = keywords.iter().any(|keyword| {
-                    let keyword_lower = keyword.to_lowercase();
-                    rss_item.title.to_lowercase().contains(&keyword_lower)
-                        || rss_item.description.as_ref()
-                            .map(|d| d.to_lowercase().contains(&keyword_lower))
-                            .unwrap_or(false)
-                        || rss_item.content.as_ref()
-                            .map(|c| c.to_lowercase().contains(&keyword_lower))
-                            .unwrap_or(false)
-                        || rss_item.link.to_lowercase().contains(&keyword_lower)
-                });
+                let matches = matcher.matches(&rss_item.title)
+                    || rss_item.description.as_deref().is_some_and(|d| matcher.matches(d))
+                    || rss_item.content.as_deref().is_some_and(|c| matcher.matches(c))
+                    || matcher.matches(&rss_item.link);
 
                 if matches {
                     matched_items.push((feed_url.clone(), rss_item));
@@ -301,6 +406,201 @@ impl RssCache {
         Ok(matched_items)
     }
 
+    /// 读取 bincode 编码的记录（不存在时返回默认值）
+    fn load_record<T: serde::de::DeserializeOwned + Default>(&self, key: &str) -> Result<T> {
+        match self.manager.get(key)? {
+            Some(bytes) => bincode::serde::decode_from_slice(&bytes, bincode::config::standard())
+                .map(|(record, _)| record)
+                .map_err(|e| CacheError::SerializationError(format!("Failed to deserialize {}: {}", key, e))),
+            None => Ok(T::default()),
+        }
+    }
+
+    /// 写入 bincode 编码的记录（不过期）
+    fn store_record<T: Serialize>(&self, key: &str, record: &T) -> Result<()> {
+        let bytes = bincode::serde::encode_to_vec(record, bincode::config::standard())
+            .map_err(|e| CacheError::SerializationError(format!("Failed to serialize {}: {}", key, e)))?;
+        self.manager.set(key.to_string(), bytes, None)
+    }
+
+    /// 记录一次获取到的条目（跨多次获取按 GUID 去重）
+    ///
+    /// 已见条目与首次见到的时间单独保存且不过期，feed 缓存过期或条目从 feed 中移出后
+    /// 再次出现的条目不会被当作新条目
+    ///
+    /// # 参数
+    ///
+    /// * `url` - Feed URL
+    /// * `items` - 本次获取到的条目
+    ///
+    /// # 返回值
+    ///
+    /// 返回之前没有见过的条目标识；首次记录该 feed 时返回 None
+    pub fn record_seen(&self, url: &str, items: &[RssFeedItem]) -> Result<Option<Vec<String>>> {
+        let key = Self::generate_seen_key(url);
+        let mut seen: HashMap<String, u64> = self.load_record(&key)?;
+        let first_poll = seen.is_empty();
+
+        let now = Self::current_timestamp();
+        let mut new_keys = Vec::new();
+        for item in items {
+            let item_key = rss_item_key(item);
+            if !seen.contains_key(item_key) {
+                seen.insert(item_key.to_string(), now);
+                new_keys.push(item_key.to_string());
+            }
+        }
+
+        if seen.len() > MAX_SEEN_ITEMS_PER_FEED {
+            let mut by_age: Vec<(String, u64)> = seen.into_iter().collect();
+            by_age.sort_by_key(|(_, first_seen)| std::cmp::Reverse(*first_seen));
+            by_age.truncate(MAX_SEEN_ITEMS_PER_FEED);
+            seen = by_age.into_iter().collect();
+        }
+        if !new_keys.is_empty() || first_poll {
+            self.store_record(&key, &seen)?;
+        }
+
+        Ok((!first_poll).then_some(new_keys))
+    }
+
+    /// 检查客户端令牌
+    fn check_token(token: &str) -> Result<()> {
+        if token.is_empty() || token.len() > MAX_READER_TOKEN_LEN || token.contains(':') {
+            return Err(CacheError::InvalidKey(format!(
+                "客户端令牌不能为空、不能包含 ':' 且不超过 {} 个字符",
+                MAX_READER_TOKEN_LEN
+            )));
+        }
+        Ok(())
+    }
+
+    /// 获取客户端在某个 feed 上已读的条目标识
+    pub fn read_keys(&self, token: &str, url: &str) -> Result<HashSet<String>> {
+        Self::check_token(token)?;
+        self.load_record(&Self::generate_read_key(token, url))
+    }
+
+    /// 将条目标记为已读或未读
+    ///
+    /// 已读状态按客户端令牌分别保存，写入时清理已不在已见记录中的条目
+    ///
+    /// # 参数
+    ///
+    /// * `token` - 客户端令牌
+    /// * `url` - Feed URL
+    /// * `item_keys` - 条目标识（GUID，没有 GUID 时为链接）
+    /// * `read` - true 标记为已读，false 标记为未读
+    ///
+    /// # 返回值
+    ///
+    /// 返回状态发生变化的条目数
+    pub fn mark_read(&self, token: &str, url: &str, item_keys: &[String], read: bool) -> Result<usize> {
+        let mut read_keys = self.read_keys(token, url)?;
+        let changed = item_keys
+            .iter()
+            .filter(|key| {
+                if read {
+                    read_keys.insert((*key).clone())
+                } else {
+                    read_keys.remove(*key)
+                }
+            })
+            .count();
+        if changed == 0 {
+            return Ok(0);
+        }
+
+        let seen: HashMap<String, u64> = self.load_record(&Self::generate_seen_key(url))?;
+        if !seen.is_empty() {
+            read_keys.retain(|key| seen.contains_key(key));
+        }
+        let key = Self::generate_read_key(token, url);
+        if read_keys.is_empty() {
+            self.manager.delete(&key)?;
+        } else {
+            self.store_record(&key, &read_keys)?;
+        }
+        Ok(changed)
+    }
+
+    /// 将 feed 中缓存的全部条目标记为已读
+    ///
+    /// # 返回值
+    ///
+    /// 返回新标记为已读的条目数
+    pub fn mark_feed_read(&self, token: &str, url: &str) -> Result<usize> {
+        let Some(feed) = self.get(url)? else {
+            return Ok(0);
+        };
+        let keys: Vec<String> = feed.items.iter().map(|item| rss_item_key(item).to_string()).collect();
+        self.mark_read(token, url, &keys, true)
+    }
+
+    /// 统计客户端在每个订阅 feed 中的未读条目数
+    ///
+    /// # 返回值
+    ///
+    /// 返回 {feed URL: 未读条目数}，按 URL 排序
+    pub fn unread_counts(&self, token: &str) -> Result<BTreeMap<String, usize>> {
+        Self::check_token(token)?;
+        let mut counts = BTreeMap::new();
+        for meta in self.list_persistent_feeds()? {
+            let read_keys = self.read_keys(token, &meta.url)?;
+            let unread = self
+                .get(&meta.url)?
+                .map(|feed| {
+                    feed.items
+                        .iter()
+                        .filter(|item| !read_keys.contains(rss_item_key(item)))
+                        .count()
+                })
+                .unwrap_or(0);
+            counts.insert(meta.url, unread);
+        }
+        Ok(counts)
+    }
+
+    /// 异步记录获取到的条目（在阻塞线程池中执行，参数同 [`record_seen`](Self::record_seen)）
+    pub async fn record_seen_async(&self, url: &str, items: &[RssFeedItem]) -> Result<Option<Vec<String>>> {
+        let cache = self.clone();
+        let url = url.to_string();
+        let items = items.to_vec();
+        run_blocking(move || cache.record_seen(&url, &items)).await
+    }
+
+    /// 异步获取已读条目标识（在阻塞线程池中执行）
+    pub async fn read_keys_async(&self, token: &str, url: &str) -> Result<HashSet<String>> {
+        let cache = self.clone();
+        let token = token.to_string();
+        let url = url.to_string();
+        run_blocking(move || cache.read_keys(&token, &url)).await
+    }
+
+    /// 异步标记已读或未读（在阻塞线程池中执行，参数同 [`mark_read`](Self::mark_read)）
+    pub async fn mark_read_async(&self, token: &str, url: &str, item_keys: &[String], read: bool) -> Result<usize> {
+        let cache = self.clone();
+        let token = token.to_string();
+        let url = url.to_string();
+        let item_keys = item_keys.to_vec();
+        run_blocking(move || cache.mark_read(&token, &url, &item_keys, read)).await
+    }
+
+    /// 异步将 feed 全部标记为已读（在阻塞线程池中执行）
+    pub async fn mark_feed_read_async(&self, token: &str, url: &str) -> Result<usize> {
+        let cache = self.clone();
+        let token = token.to_string();
+        let url = url.to_string();
+        run_blocking(move || cache.mark_feed_read(&token, &url)).await
+    }
+
+    /// 异步统计未读条目数（在阻塞线程池中执行）
+    pub async fn unread_counts_async(&self, token: &str) -> Result<BTreeMap<String, usize>> {
+        let cache = self.clone();
+        let token = token.to_string();
+        run_blocking(move || cache.unread_counts(&token)).await
+    }
+
     /// 列出所有缓存的 RSS feeds（包括过期的）
     ///
     /// # 返回值
@@ -338,6 +638,7 @@ impl RssCache {
 mod tests {
     use super::*;
     use crate::cache::types::CacheImplConfig;
+    use crate::derive::rss::RssFeedMeta;
 
     #[test]
     fn test_rss_cache_keys() {
@@ -355,4 +656,95 @@ mod tests {
         let manager = CacheManager::instance(config).unwrap();
         let _cache = RssCache::new(manager);
     }
+
+    #[test]
+    fn test_list_persistent_feeds() {
+        let manager = CacheManager::instance(CacheImplConfig::default()).unwrap();
+        let cache = RssCache::new(manager);
+        let feed = RssFeed {
+            meta: RssFeedMeta {
+                title: "Persistent Feed".to_string(),
+                link: "https://example.com".to_string(),
+                description: None,
+                language: None,
+                copyright: None,
+                last_build_date: None,
+                pub_date: None,
+                image: None,
+            },
+            items: Vec::new(),
+        };
+        let persistent_url = "https://example.com/list-persistent.xml";
+        let temporary_url = "https://example.com/list-temporary.xml";
+        cache.set(persistent_url, &feed, true, Some(600), None).unwrap();
+        cache.set(temporary_url, &feed, false, None, None).unwrap();
+
+        let urls: Vec<String> = cache.list_persistent_feeds().unwrap().into_iter().map(|m| m.url).collect();
+        assert!(urls.contains(&persistent_url.to_string()));
+        assert!(!urls.contains(&temporary_url.to_string()));
+
+        cache.delete(persistent_url).unwrap();
+        cache.delete(temporary_url).unwrap();
+    }
+
+    fn item(guid: Option<&str>, link: &str) -> RssFeedItem {
+        RssFeedItem {
+            title: link.to_string(),
+            link: link.to_string(),
+            description: None,
+            author: None,
+            pub_date: None,
+            content: None,
+            categories: Vec::new(),
+            guid: guid.map(str::to_string),
+            enclosures: Vec::new(),
+            custom_fields: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_seen_and_read_state() {
+        let manager = CacheManager::instance(CacheImplConfig::default()).unwrap();
+        let cache = RssCache::new(manager);
+        let url = "https://example.com/read-state.xml";
+        let first = vec![item(Some("a"), "https://example.com/a"), item(None, "https://example.com/b")];
+        let feed = RssFeed {
+            meta: RssFeedMeta {
+                title: "Read State".to_string(),
+                link: "https://example.com".to_string(),
+                description: None,
+                language: None,
+                copyright: None,
+                last_build_date: None,
+                pub_date: None,
+                image: None,
+            },
+            items: first.clone(),
+        };
+        cache.set(url, &feed, true, Some(600), None).unwrap();
+
+        // 首次记录不产生新条目，之后只报告没见过的 GUID
+        assert_eq!(cache.record_seen(url, &first).unwrap(), None);
+        let second = vec![item(Some("a"), "https://example.com/a-moved"), item(Some("c"), "https://example.com/c")];
+        assert_eq!(cache.record_seen(url, &second).unwrap(), Some(vec!["c".to_string()]));
+        assert_eq!(cache.record_seen(url, &second).unwrap(), Some(Vec::new()));
+
+        assert!(cache.mark_read("", url, &["a".to_string()], true).is_err());
+        assert!(cache.mark_read("bad:token", url, &["a".to_string()], true).is_err());
+
+        assert_eq!(cache.mark_read("alice", url, &["a".to_string()], true).unwrap(), 1);
+        assert_eq!(cache.mark_read("alice", url, &["a".to_string()], true).unwrap(), 0);
+        assert_eq!(cache.unread_counts("alice").unwrap().get(url), Some(&1));
+        assert_eq!(cache.unread_counts("bob").unwrap().get(url), Some(&2));
+
+        assert_eq!(cache.mark_feed_read("alice", url).unwrap(), 1);
+        assert_eq!(cache.unread_counts("alice").unwrap().get(url), Some(&0));
+        assert_eq!(cache.mark_read("alice", url, &["a".to_string()], false).unwrap(), 1);
+        assert!(cache.read_keys("alice", url).unwrap().contains("https://example.com/b"));
+
+        cache.delete(url).unwrap();
+        assert!(cache.read_keys("alice", url).unwrap().is_empty());
+        assert_eq!(cache.record_seen(url, &first).unwrap(), None);
+        cache.delete(url).unwrap();
+    }
 }
@@ -0,0 +1,241 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 搜索会话存储
+//!
+//! 携带相同会话令牌的查询属于同一会话，会话记录最近的查询，用于后续查询的结果重排与相关查询建议。
+//! 会话保存在元数据缓存中，键为令牌的 SHA-256 哈希，有效期在每次查询时重新计算
+
+use crate::cache::alerts::now_secs;
+use crate::cache::manager::{run_blocking, CacheError};
+use crate::cache::metadata::MetadataCache;
+use crate::cache::preferences::new_token;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+type Result<T> = std::result::Result<T, CacheError>;
+
+/// 搜索会话键前缀（位于元数据缓存中）
+const SEARCH_SESSION_KEY_PREFIX: &str = "search_session:";
+
+/// 会话令牌的最大长度
+pub const MAX_SESSION_TOKEN_LEN: usize = 128;
+
+/// 会话中的一次查询
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionQuery {
+    /// 查询文本
+    pub query: String,
+    /// 查询时间（Unix 时间戳）
+    pub timestamp: u64,
+}
+
+/// 搜索会话
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchSession {
+    /// 会话中的查询（按时间先后，重复的查询只保留最近一次）
+    pub queries: Vec<SessionQuery>,
+    /// 创建时间（Unix 时间戳）
+    pub created_at: u64,
+    /// 最后查询时间（Unix 时间戳）
+    pub updated_at: u64,
+}
+
+impl SearchSession {
+    /// 除 `current` 以外的查询，最近的在前（不区分大小写比较）
+    pub fn previous_queries<'a>(&'a self, current: &str) -> impl Iterator<Item = &'a str> {
+        let current = current.trim().to_lowercase();
+        self.queries
+            .iter()
+            .rev()
+            .map(|q| q.query.as_str())
+            .filter(move |query| query.to_lowercase() != current)
+    }
+
+    /// 记录一次查询，超过 `max_queries` 时移除最早的查询
+    fn push(&mut self, query: &str, max_queries: usize) {
+        let query = query.trim();
+        let now = now_secs();
+        self.queries.retain(|q| !q.query.eq_ignore_ascii_case(query));
+        self.queries.push(SessionQuery {
+            query: query.to_string(),
+            timestamp: now,
+        });
+        let excess = self.queries.len().saturating_sub(max_queries);
+        self.queries.drain(..excess);
+        if self.created_at == 0 {
+            self.created_at = now;
+        }
+        self.updated_at = now;
+    }
+}
+
+/// 校验会话令牌（非空、不超过 [`MAX_SESSION_TOKEN_LEN`]，只含 ASCII 字母数字、`-` 与 `_`）
+pub fn validate_session_token(token: &str) -> std::result::Result<(), String> {
+    if token.is_empty() || token.len() > MAX_SESSION_TOKEN_LEN {
+        return Err(format!("会话令牌长度需在 1 到 {} 之间", MAX_SESSION_TOKEN_LEN));
+    }
+    if !token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("会话令牌只能包含字母、数字、'-' 与 '_'".to_string());
+    }
+    Ok(())
+}
+
+/// 搜索会话存储
+#[derive(Clone)]
+pub struct SearchSessionStore {
+    metadata: MetadataCache,
+    /// 会话有效期（自最后一次查询起）
+    ttl: Duration,
+    /// 每个会话保留的查询数
+    max_queries: usize,
+}
+
+impl SearchSessionStore {
+    /// 创建搜索会话存储
+    ///
+    /// # 参数
+    ///
+    /// * `metadata` - 元数据缓存
+    /// * `ttl` - 会话有效期（自最后一次查询起）
+    /// * `max_queries` - 每个会话保留的查询数
+    pub fn new(metadata: MetadataCache, ttl: Duration, max_queries: usize) -> Self {
+        Self {
+            metadata,
+            ttl,
+            max_queries: max_queries.max(1),
+        }
+    }
+
+    /// 会话有效期
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// 生成会话键（只保存令牌的哈希）
+    fn generate_key(token: &str) -> String {
+        let digest = Sha256::digest(token.as_bytes());
+        let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+        format!("{}{}", SEARCH_SESSION_KEY_PREFIX, hex)
+    }
+
+    /// 创建一个空会话
+    ///
+    /// # 返回值
+    ///
+    /// 新生成的会话令牌
+    pub fn create(&self) -> Result<String> {
+        let token = new_token();
+        let now = now_secs();
+        self.save(&token, &SearchSession { queries: Vec::new(), created_at: now, updated_at: now })?;
+        Ok(token)
+    }
+
+    /// 读取会话
+    ///
+    /// # 返回值
+    ///
+    /// 返回会话，不存在或已过期时返回 None
+    pub fn get(&self, token: &str) -> Result<Option<SearchSession>> {
+        let Some(bytes) = self.metadata.get_metadata(&Self::generate_key(token))? else {
+            return Ok(None);
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| CacheError::SerializationError(format!("Failed to deserialize search session: {}", e)))
+    }
+
+    /// 在会话中记录一次查询并刷新有效期（会话不存在时以该令牌新建）
+    ///
+    /// # 返回值
+    ///
+    /// 记录后的会话
+    pub fn record(&self, token: &str, query: &str) -> Result<SearchSession> {
+        let mut session = self.get(token)?.unwrap_or_default();
+        session.push(query, self.max_queries);
+        self.save(token, &session)?;
+        Ok(session)
+    }
+
+    /// 删除会话
+    ///
+    /// # 返回值
+    ///
+    /// 会话存在时返回 true
+    pub fn delete(&self, token: &str) -> Result<bool> {
+        self.metadata.delete_metadata(&Self::generate_key(token))
+    }
+
+    /// 保存会话
+    fn save(&self, token: &str, session: &SearchSession) -> Result<()> {
+        let bytes = serde_json::to_vec(session)
+            .map_err(|e| CacheError::SerializationError(format!("Failed to serialize search session: {}", e)))?;
+        self.metadata.set_metadata(&Self::generate_key(token), bytes, Some(self.ttl))
+    }
+
+    /// 异步创建会话（在阻塞线程池中执行）
+    pub async fn create_async(&self) -> Result<String> {
+        let store = self.clone();
+        run_blocking(move || store.create()).await
+    }
+
+    /// 异步读取会话（在阻塞线程池中执行）
+    pub async fn get_async(&self, token: &str) -> Result<Option<SearchSession>> {
+        let (store, token) = (self.clone(), token.to_string());
+        run_blocking(move || store.get(&token)).await
+    }
+
+    /// 异步记录查询（在阻塞线程池中执行）
+    pub async fn record_async(&self, token: &str, query: &str) -> Result<SearchSession> {
+        let (store, token, query) = (self.clone(), token.to_string(), query.to_string());
+        run_blocking(move || store.record(&token, &query)).await
+    }
+
+    /// 异步删除会话（在阻塞线程池中执行）
+    pub async fn delete_async(&self, token: &str) -> Result<bool> {
+        let (store, token) = (self.clone(), token.to_string());
+        run_blocking(move || store.delete(&token)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheManager;
+    use crate::cache::types::CacheImplConfig;
+
+    #[test]
+    fn test_search_session_records_queries() {
+        let manager = CacheManager::instance(CacheImplConfig::default()).unwrap();
+        let store = SearchSessionStore::new(MetadataCache::new(manager), Duration::from_secs(60), 3);
+
+        let token = store.create().unwrap();
+        assert!(validate_session_token(&token).is_ok());
+        assert!(store.get(&token).unwrap().unwrap().queries.is_empty());
+
+        for query in ["rust async", "tokio runtime", "Rust Async", "axum", "tower"] {
+            store.record(&token, query).unwrap();
+        }
+        let session = store.get(&token).unwrap().unwrap();
+        let queries: Vec<_> = session.queries.iter().map(|q| q.query.as_str()).collect();
+        assert_eq!(queries, ["Rust Async", "axum", "tower"]);
+        assert_eq!(session.previous_queries("tower").collect::<Vec<_>>(), ["axum", "Rust Async"]);
+
+        assert!(store.delete(&token).unwrap());
+        assert!(store.get(&token).unwrap().is_none());
+        assert!(validate_session_token("bad token").is_err());
+        assert!(validate_session_token("").is_err());
+    }
+}
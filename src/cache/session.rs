@@ -0,0 +1,163 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 引擎会话存储
+//!
+//! 部分引擎需要预热过的会话（同意 Cookie、偏好 Cookie）才能返回正常结果。
+//! 这里把各引擎的 Cookie 与粘性请求头保存在元数据缓存中，进程重启后恢复
+
+use crate::cache::manager::{run_blocking, CacheError};
+use crate::cache::metadata::MetadataCache;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+type Result<T> = std::result::Result<T, CacheError>;
+
+/// 引擎会话键前缀（位于元数据缓存中）
+const SESSION_KEY_PREFIX: &str = "engine_session:";
+
+/// 会话中的 Cookie
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionCookie {
+    /// 值
+    pub value: String,
+    /// 过期时间（Unix 时间戳，None 表示会话 Cookie）
+    pub expires_at: Option<i64>,
+}
+
+impl SessionCookie {
+    /// 是否已过期
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// 单个引擎的会话数据
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineSessionData {
+    /// Cookie（域名 -> Cookie 名 -> Cookie）
+    pub cookies: BTreeMap<String, BTreeMap<String, SessionCookie>>,
+    /// 粘性请求头（小写名称 -> 值）
+    pub headers: BTreeMap<String, String>,
+}
+
+impl EngineSessionData {
+    /// 删除已过期的 Cookie
+    pub fn remove_expired(&mut self, now: i64) {
+        for cookies in self.cookies.values_mut() {
+            cookies.retain(|_, cookie| !cookie.is_expired(now));
+        }
+        self.cookies.retain(|_, cookies| !cookies.is_empty());
+    }
+
+    /// 是否没有任何 Cookie 与请求头
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty() && self.headers.is_empty()
+    }
+}
+
+/// 引擎会话存储
+#[derive(Clone)]
+pub struct EngineSessionStore {
+    metadata: MetadataCache,
+}
+
+impl EngineSessionStore {
+    /// 创建引擎会话存储
+    ///
+    /// # 参数
+    ///
+    /// * `metadata` - 元数据缓存
+    pub fn new(metadata: MetadataCache) -> Self {
+        Self { metadata }
+    }
+
+    /// 生成引擎会话键
+    fn generate_key(engine_name: &str) -> String {
+        format!("{}{}", SESSION_KEY_PREFIX, engine_name)
+    }
+
+    /// 读取引擎会话
+    ///
+    /// # 参数
+    ///
+    /// * `engine_name` - 引擎名称
+    ///
+    /// # 返回值
+    ///
+    /// 返回去掉过期 Cookie 后的会话，不存在或已过期时返回 None
+    pub fn load(&self, engine_name: &str) -> Result<Option<EngineSessionData>> {
+        let Some(bytes) = self.metadata.get_metadata(&Self::generate_key(engine_name))? else {
+            return Ok(None);
+        };
+        let mut session: EngineSessionData = serde_json::from_slice(&bytes)
+            .map_err(|e| CacheError::SerializationError(format!("Failed to deserialize engine session: {}", e)))?;
+        session.remove_expired(chrono::Utc::now().timestamp());
+        Ok(Some(session))
+    }
+
+    /// 保存引擎会话
+    ///
+    /// # 参数
+    ///
+    /// * `engine_name` - 引擎名称
+    /// * `session` - 会话数据
+    /// * `ttl` - 会话有效期（每次保存时重新计算）
+    pub fn save(&self, engine_name: &str, session: &EngineSessionData, ttl: Duration) -> Result<()> {
+        let bytes = serde_json::to_vec(session)
+            .map_err(|e| CacheError::SerializationError(format!("Failed to serialize engine session: {}", e)))?;
+        self.metadata.set_metadata(&Self::generate_key(engine_name), bytes, Some(ttl))
+    }
+
+    /// 删除引擎会话
+    pub fn delete(&self, engine_name: &str) -> Result<bool> {
+        self.metadata.delete_metadata(&Self::generate_key(engine_name))
+    }
+
+    /// 异步保存引擎会话（在阻塞线程池中执行）
+    pub async fn save_async(&self, engine_name: &str, session: EngineSessionData, ttl: Duration) -> Result<()> {
+        let store = self.clone();
+        let engine_name = engine_name.to_string();
+        run_blocking(move || store.save(&engine_name, &session, ttl)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheManager;
+    use crate::cache::types::CacheImplConfig;
+
+    #[test]
+    fn test_session_roundtrip_drops_expired_cookies() {
+        let manager = CacheManager::instance(CacheImplConfig::default()).unwrap();
+        let store = EngineSessionStore::new(MetadataCache::new(manager));
+        let now = chrono::Utc::now().timestamp();
+
+        let mut session = EngineSessionData::default();
+        let cookies = session.cookies.entry("www.bing.com".to_string()).or_default();
+        cookies.insert("SRCHHPGUSR".to_string(), SessionCookie { value: "ADLT=OFF".to_string(), expires_at: Some(now + 3600) });
+        cookies.insert("old".to_string(), SessionCookie { value: "1".to_string(), expires_at: Some(now - 1) });
+        session.headers.insert("x-session".to_string(), "abc".to_string());
+
+        store.save("test_session_engine", &session, Duration::from_secs(60)).unwrap();
+        let loaded = store.load("test_session_engine").unwrap().unwrap();
+        assert_eq!(loaded.cookies["www.bing.com"].len(), 1);
+        assert_eq!(loaded.headers["x-session"], "abc");
+
+        assert!(store.delete("test_session_engine").unwrap());
+        assert!(store.load("test_session_engine").unwrap().is_none());
+    }
+}
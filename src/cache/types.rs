@@ -17,7 +17,8 @@
 //! 定义缓存模块的核心类型和数据结构
 
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
 /// 缓存键类型
 ///
@@ -111,6 +112,15 @@ pub struct CacheStats {
     pub estimated_size_bytes: u64,
     /// 过期清理次数
     pub evictions: u64,
+    /// 写入值大小分布（字节）
+    #[serde(default)]
+    pub value_size_bytes: Histogram,
+    /// 读取延迟分布（秒）
+    #[serde(default)]
+    pub get_latency_seconds: Histogram,
+    /// 写入延迟分布（秒）
+    #[serde(default)]
+    pub set_latency_seconds: Histogram,
 }
 
 impl CacheStats {
@@ -129,6 +139,105 @@ impl CacheStats {
     }
 }
 
+/// 值大小直方图的桶上界（字节）
+pub const VALUE_SIZE_BUCKETS: &[f64] = &[256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0];
+
+/// 延迟直方图的桶上界（秒）
+pub const LATENCY_BUCKETS: &[f64] = &[0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5];
+
+/// 直方图快照
+///
+/// 桶计数为累计值（小于等于上界的观测数），与 Prometheus 直方图的 `le` 语义一致
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    /// 各桶上界（升序）
+    pub bounds: Vec<f64>,
+    /// 各桶累计计数，与 `bounds` 一一对应
+    pub buckets: Vec<u64>,
+    /// 观测总数（即 `+Inf` 桶）
+    pub count: u64,
+    /// 观测值之和
+    pub sum: f64,
+}
+
+impl Histogram {
+    /// 平均值，没有观测时返回 0.0
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum / self.count as f64
+        }
+    }
+}
+
+/// 无锁直方图记录器
+#[derive(Debug)]
+pub(crate) struct HistogramRecorder {
+    /// 各桶上界（升序）
+    bounds: &'static [f64],
+    /// 各桶计数（非累计，最后一个为超出所有上界的观测）
+    counts: Vec<AtomicU64>,
+    /// 观测值之和（f64 的位表示）
+    sum_bits: AtomicU64,
+}
+
+impl HistogramRecorder {
+    /// 按给定桶上界创建记录器
+    pub(crate) fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_bits: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+
+    /// 记录一次观测
+    pub(crate) fn observe(&self, value: f64) {
+        let index = self.bounds.iter().position(|bound| value <= *bound).unwrap_or(self.bounds.len());
+        self.counts[index].fetch_add(1, Ordering::Relaxed);
+        let _ = self.sum_bits.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+            Some((f64::from_bits(bits) + value).to_bits())
+        });
+    }
+
+    /// 开始计时，返回的计时器释放时把经过的秒数记入直方图
+    pub(crate) fn start_timer(&self) -> HistogramTimer<'_> {
+        HistogramTimer {
+            recorder: self,
+            started: Instant::now(),
+        }
+    }
+
+    /// 生成累计计数的快照
+    pub(crate) fn snapshot(&self) -> Histogram {
+        let mut cumulative = 0;
+        let mut buckets = Vec::with_capacity(self.bounds.len());
+        for count in &self.counts[..self.bounds.len()] {
+            cumulative += count.load(Ordering::Relaxed);
+            buckets.push(cumulative);
+        }
+        Histogram {
+            bounds: self.bounds.to_vec(),
+            buckets,
+            count: cumulative + self.counts[self.bounds.len()].load(Ordering::Relaxed),
+            sum: f64::from_bits(self.sum_bits.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// 直方图计时器（释放时记录耗时）
+pub(crate) struct HistogramTimer<'a> {
+    recorder: &'a HistogramRecorder,
+    started: Instant,
+}
+
+impl Drop for HistogramTimer<'_> {
+    fn drop(&mut self) {
+        self.recorder.observe(self.started.elapsed().as_secs_f64());
+    }
+}
+
 /// 缓存条目元数据
 ///
 /// 存储每个缓存条目的附加信息
@@ -180,6 +289,63 @@ impl CacheEntryMetadata {
     }
 }
 
+/// 批量写入中的单个操作
+#[derive(Debug, Clone)]
+pub(crate) enum BatchOp {
+    /// 写入条目
+    Set {
+        key: CacheKey,
+        value: CacheValue,
+        ttl: Option<Duration>,
+    },
+    /// 删除条目
+    Delete { key: CacheKey },
+}
+
+/// 缓存批量写入
+///
+/// 由 [`CacheManager::apply_batch`](crate::cache::CacheManager::apply_batch) 原子地应用：
+/// 所有操作连同对应的元数据要么全部生效，要么全部不生效
+#[derive(Debug, Clone, Default)]
+pub struct CacheBatch {
+    pub(crate) ops: Vec<BatchOp>,
+}
+
+impl CacheBatch {
+    /// 创建空的批量写入
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加写入操作
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 缓存键
+    /// * `value` - 缓存值
+    /// * `ttl` - 生存时间，None 表示使用默认值
+    pub fn set(&mut self, key: impl Into<CacheKey>, value: CacheValue, ttl: Option<Duration>) -> &mut Self {
+        self.ops.push(BatchOp::Set { key: key.into(), value, ttl });
+        self
+    }
+
+    /// 添加删除操作
+    pub fn delete(&mut self, key: impl Into<CacheKey>) -> &mut Self {
+        self.ops.push(BatchOp::Delete { key: key.into() });
+        self
+    }
+
+    /// 操作数量
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// 是否没有任何操作
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+}
+
 /// 获取当前 Unix 时间戳（秒）
 #[inline]
 fn current_timestamp() -> u64 {
@@ -217,6 +383,22 @@ mod tests {
         assert_eq!(stats.hit_rate(), 1.0);
     }
 
+    #[test]
+    fn test_histogram_recorder_cumulative_buckets() {
+        let recorder = HistogramRecorder::new(&[1.0, 10.0]);
+        for value in [0.5, 1.0, 5.0, 50.0] {
+            recorder.observe(value);
+        }
+
+        let histogram = recorder.snapshot();
+        assert_eq!(histogram.bounds, vec![1.0, 10.0]);
+        assert_eq!(histogram.buckets, vec![2, 3]);
+        assert_eq!(histogram.count, 4);
+        assert_eq!(histogram.sum, 56.5);
+        assert_eq!(histogram.mean(), 14.125);
+        assert_eq!(Histogram::default().mean(), 0.0);
+    }
+
     #[test]
     fn test_cache_entry_metadata_expiration() {
         let metadata = CacheEntryMetadata::new(Some(Duration::from_secs(1)), 100);
@@ -14,8 +14,9 @@
 
 //! API 配置类型定义
 
-use crate::config::common::{AuthType, ConfigValidationResult};
+use crate::config::common::{AuthType, ConfigValidationResult, SafeSearchLevel};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// API 配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +46,15 @@ pub struct ApiConfig {
     pub documentation: DocumentationConfig,
     /// 指标配置
     pub metrics: MetricsConfig,
+    /// 租户配置档（名称 → 配置），通过 API 密钥的 `profile` 字段绑定
+    #[serde(default)]
+    pub profiles: HashMap<String, ProfileConfig>,
+    /// 未携带 API 密钥的请求使用的配置档（未设置时不做限制）
+    #[serde(default)]
+    pub default_profile: Option<String>,
+    /// 受信任的反向代理地址，来自这些地址的请求按 `X-Forwarded-For` 识别客户端地址
+    #[serde(default)]
+    pub trusted_proxies: Vec<std::net::IpAddr>,
 }
 
 fn default_true() -> bool {
@@ -57,7 +67,7 @@ pub struct MetricsConfig {
     /// 是否启用指标
     #[serde(default)]
     pub enabled: bool,
-    /// 指标端口
+    /// 指标端口（保留；指标端点目前挂在 API 服务器上，与 API 共用监听地址）
     #[serde(default = "default_metrics_port")]
     pub port: u16,
     /// 指标路径
@@ -165,6 +175,25 @@ pub struct EndpointBasedLimits {
     pub metrics_endpoint: Option<EndpointLimit>,
 }
 
+/// 租户配置档
+///
+/// 同一部署可按 API 密钥提供不同的行为，例如面向儿童的 "kids" 档强制严格安全搜索，
+/// 面向研究的 "research" 档只开放学术引擎
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileConfig {
+    /// 允许使用的引擎（为空表示不限制）；请求未指定引擎时使用该列表
+    pub engines: Vec<String>,
+    /// 强制的安全搜索级别（未设置时沿用请求）
+    pub safe_search: Option<SafeSearchLevel>,
+    /// 是否改写结果链接（未设置时沿用 `privacy.url_rewrite`）
+    pub url_rewrite: Option<bool>,
+    /// 是否代理缩略图（未设置时沿用 `privacy.image_proxy`）
+    pub image_proxy: Option<bool>,
+    /// 每个 API 密钥每分钟请求数上限（未设置时不限制）
+    pub requests_per_minute: Option<u32>,
+}
+
 /// 端点限制配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EndpointLimit {
@@ -215,7 +244,7 @@ pub struct ApiKeyInfo {
     pub name: String,
     /// 密钥值（哈希存储）
     pub key_hash: String,
-    /// 权限列表
+    /// 权限列表（包含 `admin` 时可查询审计日志 `/api/audit`）
     pub permissions: Vec<String>,
     /// 是否启用
     pub enabled: bool,
@@ -225,6 +254,9 @@ pub struct ApiKeyInfo {
     pub expires_at: Option<String>,
     /// 使用限制
     pub usage_limits: Option<ApiKeyUsageLimits>,
+    /// 绑定的租户配置档名称（未设置时不做限制）
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 /// API 密钥使用限制
@@ -726,6 +758,9 @@ impl Default for ApiConfig {
             security: SecurityConfig::default(),
             documentation: DocumentationConfig::default(),
             metrics: MetricsConfig::default(),
+            profiles: HashMap::new(),
+            default_profile: None,
+            trusted_proxies: Vec::new(),
         }
     }
 }
@@ -781,6 +816,25 @@ impl ApiConfig {
             }
         }
 
+        // 验证租户配置档
+        for key in &self.auth.api_key.api_keys {
+            if let Some(profile) = &key.profile
+                && !self.profiles.contains_key(profile)
+            {
+                result.add_error(format!("API 密钥 '{}' 绑定的配置档 '{}' 不存在", key.name, profile));
+            }
+        }
+        if let Some(profile) = &self.default_profile
+            && !self.profiles.contains_key(profile)
+        {
+            result.add_error(format!("默认配置档 '{}' 不存在", profile));
+        }
+        for (name, profile) in &self.profiles {
+            if profile.requests_per_minute == Some(0) {
+                result.add_error(format!("配置档 '{}' 的每分钟请求数必须大于 0", name));
+            }
+        }
+
         result
     }
 
@@ -0,0 +1,59 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 搜索归档配置类型定义
+
+use serde::{Deserialize, Serialize};
+
+/// 搜索归档配置
+///
+/// 启用后每次搜索的响应（含时间戳与结果来源引擎）追加写入归档，供研究与离线分析使用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveConfig {
+    /// 是否启用归档
+    pub enabled: bool,
+    /// 归档格式
+    pub format: ArchiveFormat,
+    /// 归档文件路径
+    pub path: String,
+    /// 是否归档来自结果缓存的响应
+    pub include_cached: bool,
+    /// 是否归档没有结果的响应
+    pub include_empty: bool,
+    /// 每个响应最多归档的结果数（None 表示全部）
+    pub max_items_per_response: Option<usize>,
+}
+
+/// 归档格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    /// JSON Lines，每个响应一行
+    Jsonl,
+    /// SQLite 数据库（需要 `archive-sqlite` 特性）
+    Sqlite,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: ArchiveFormat::Jsonl,
+            path: "./data/archive/searches.jsonl".to_string(),
+            include_cached: false,
+            include_empty: true,
+            max_items_per_response: None,
+        }
+    }
+}
@@ -41,6 +41,21 @@ pub struct SeeSeaConfig {
     pub logging: crate::config::logging::LoggingConfig,
     /// 搜索引擎配置
     pub engines: crate::config::engines::EnginesConfig,
+    /// 搜索归档配置
+    #[serde(default)]
+    pub archive: crate::config::archive::ArchiveConfig,
+    /// 礼貌访问配置
+    #[serde(default)]
+    pub politeness: crate::config::politeness::PolitenessConfig,
+    /// 外部集成配置
+    #[serde(default)]
+    pub integrations: crate::config::integrations::IntegrationsConfig,
+    /// 内置网页界面配置
+    #[serde(default)]
+    pub web: crate::config::web::WebConfig,
+    /// 热榜配置
+    #[serde(default)]
+    pub hot: crate::config::hot::HotConfig,
 }
 
 impl Default for SeeSeaConfig {
@@ -55,6 +70,11 @@ impl Default for SeeSeaConfig {
             api: crate::config::api::ApiConfig::default(),
             logging: crate::config::logging::LoggingConfig::default(),
             engines: crate::config::engines::EnginesConfig::default(),
+            archive: crate::config::archive::ArchiveConfig::default(),
+            politeness: crate::config::politeness::PolitenessConfig::default(),
+            integrations: crate::config::integrations::IntegrationsConfig::default(),
+            web: crate::config::web::WebConfig::default(),
+            hot: crate::config::hot::HotConfig::default(),
         }
     }
 }
@@ -87,12 +107,82 @@ impl SeeSeaConfig {
         config
     }
     
+    /// 创建指定环境的配置
+    ///
+    /// 同时设置顶层与 `general` 中的环境标识
+    pub fn for_environment(environment: Environment) -> Self {
+        let mut config = match environment {
+            Environment::Development => Self::development(),
+            Environment::Testing => Self::testing(),
+            Environment::Staging | Environment::Production => Self::production(),
+        };
+        config.environment = environment;
+        config.general.environment = environment;
+        config
+    }
+
+    /// 序列化为带注释的 TOML
+    ///
+    /// 在文件头与每个顶层配置段前加入说明注释，生成的内容可直接被
+    /// [`ConfigLoader`](crate::config::ConfigLoader) 加载
+    pub fn to_commented_toml(&self) -> Result<String, ConfigError> {
+        let body = toml::to_string_pretty(self)
+            .map_err(|e| ConfigError::Parse(format!("TOML 序列化错误: {}", e)))?;
+
+        let separator = format!("# {}", "=".repeat(77));
+        let mut output = format!(
+            "# SeeSea 配置文件（{} 环境）\n\
+             # 由 `seesea config init` 生成，所有字段均为默认值\n\
+             # 运行环境: \"development\", \"testing\", \"staging\", \"production\"\n\n",
+            self.environment
+        );
+        for line in body.lines() {
+            let section = line
+                .strip_prefix('[')
+                .and_then(|l| l.strip_suffix(']'))
+                .and_then(Self::section_description);
+            if let Some(description) = section {
+                if !output.ends_with("\n\n") {
+                    output.push('\n');
+                }
+                output.push_str(&format!("{}\n# {}\n{}\n", separator, description, separator));
+            }
+            output.push_str(line);
+            output.push('\n');
+        }
+
+        if self.server.secret_key.contains("change") {
+            output.push_str("\n# 注意：部署前请将 server.secret_key 替换为强随机密钥\n");
+        }
+        Ok(output)
+    }
+
+    /// 顶层配置段的说明
+    fn section_description(section: &str) -> Option<&'static str> {
+        match section {
+            "general" => Some("通用配置"),
+            "server" => Some("服务器配置"),
+            "search" => Some("搜索配置"),
+            "privacy" => Some("隐私保护配置"),
+            "cache" => Some("缓存配置"),
+            "api" => Some("API 配置"),
+            "logging" => Some("日志配置"),
+            "engines" => Some("搜索引擎配置"),
+            "archive" => Some("搜索归档配置"),
+            "integrations" => Some("外部集成配置"),
+            "hot" => Some("热榜配置"),
+            _ => None,
+        }
+    }
+
     /// 验证配置
     pub fn validate(&self) -> ConfigValidationResult {
         crate::config::validator::validate_config(self)
     }
     
     /// 获取配置摘要
+    ///
+    /// 校验信息中出现的密钥值会被替换（见 [`redact_text`](crate::config::redact_text)）
     pub fn get_summary(&self) -> ConfigSummary {
         ConfigSummary {
             config_path: String::new(),
@@ -101,7 +191,7 @@ impl SeeSeaConfig {
             total_engines: 0,
             enabled_proxies: 0,
             cache_enabled: true, // TODO: Get from cache config
-            validation: self.validate(),
+            validation: crate::config::redact_validation(self.validate(), self),
         }
     }
     
@@ -350,7 +440,8 @@ impl ConfigLoader {
 
     fn load_from_file(&self, path: &str) -> Result<SeeSeaConfig, ConfigError> {
         let content = std::fs::read_to_string(path)?;
-        let config: SeeSeaConfig = toml::from_str(&content)?;
+        let mut config: SeeSeaConfig = toml::from_str(&content)?;
+        crate::config::resolve_secrets(&mut config)?;
         Ok(config)
     }
 
@@ -372,7 +463,7 @@ impl ConfigLoader {
             total_engines: 0,
             enabled_proxies: 0,
             cache_enabled: true, // TODO: Get from cache config
-            validation,
+            validation: crate::config::redact_validation(validation, config),
         }
     }
 }
@@ -31,6 +31,18 @@ pub struct EnginesConfig {
     pub discovery: EngineDiscoveryConfig,
     /// 引擎健康检查配置
     pub health_check: HealthCheckConfig,
+    /// 是否开放文件/种子分类引擎（默认关闭，由运营者决定）
+    #[serde(default)]
+    pub enable_files_category: bool,
+    /// 全局结果过滤（域名黑白名单、远程域名列表与按分类覆盖，默认关闭）
+    #[serde(default)]
+    pub result_filtering: ResultFilteringConfig,
+    /// 垃圾/内容农场结果过滤（启发式评分，默认关闭）
+    #[serde(default)]
+    pub spam_filter: SpamFilterConfig,
+    /// 按请求语言提升或移除结果（默认关闭）
+    #[serde(default)]
+    pub language_filter: LanguageFilterConfig,
 }
 
 /// 引擎配置
@@ -61,6 +73,112 @@ pub struct EngineNetworkConfig {
     pub retry: RetryConfig,
     /// 超时配置
     pub timeout: TimeoutConfig,
+    /// 礼貌访问覆盖（未设置的字段使用全局 `politeness` 配置）
+    #[serde(default)]
+    pub politeness: EnginePolitenessConfig,
+    /// 地域配置（服务地区与地区镜像）
+    #[serde(default)]
+    pub geo: EngineGeoConfig,
+    /// 会话保持（Cookie 与粘性请求头）
+    #[serde(default)]
+    pub session: EngineSessionConfig,
+}
+
+/// 引擎会话配置
+///
+/// 启用后记录响应设置的 Cookie 与指定的响应头，之后的请求自动带上；
+/// 启用缓存时会话保存在元数据缓存中，进程重启后恢复
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineSessionConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 会话有效期（秒，自最后一次更新起计算）
+    pub ttl_secs: u64,
+    /// 需要粘性保持的响应头名称（响应中出现后在之后的请求中原样发送）
+    pub sticky_headers: Vec<String>,
+}
+
+impl Default for EngineSessionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: 7 * 24 * 3600,
+            sticky_headers: Vec::new(),
+        }
+    }
+}
+
+/// 引擎地域配置
+///
+/// 查询带有地区（`SearchQuery.region` 或由语言推断）时，优先使用该地区的镜像；
+/// 镜像被封锁（HTTP 403/429）时依次改用主服务源与全球镜像
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineGeoConfig {
+    /// 引擎擅长的地区代码（如 `cn`、`us`，为空表示不偏向任何地区），
+    /// 查询属于这些地区时该引擎排在前面
+    pub regions: Vec<String>,
+    /// 引擎的主服务源（`scheme://host[:port]`，如 `https://www.bing.com`），镜像替换发往此源的请求
+    pub origin: Option<String>,
+    /// 镜像列表（按优先顺序）
+    pub mirrors: Vec<EngineMirrorConfig>,
+    /// 镜像被封锁后暂停使用的时间（秒）
+    pub block_cooldown_secs: u64,
+}
+
+/// 引擎镜像
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineMirrorConfig {
+    /// 镜像源（`scheme://host[:port]`）
+    pub origin: String,
+    /// 镜像服务的地区代码（未设置时为全球镜像）
+    #[serde(default)]
+    pub region: Option<String>,
+}
+
+impl Default for EngineGeoConfig {
+    fn default() -> Self {
+        Self {
+            regions: Vec::new(),
+            origin: None,
+            mirrors: Vec::new(),
+            block_cooldown_secs: 600,
+        }
+    }
+}
+
+impl EngineGeoConfig {
+    /// 是否配置了地区或镜像
+    pub fn is_configured(&self) -> bool {
+        !self.regions.is_empty() || !self.mirrors.is_empty()
+    }
+
+    /// 验证地域配置
+    pub fn validate(&self) -> ConfigValidationResult {
+        let mut result = ConfigValidationResult::success();
+        if !self.mirrors.is_empty() && self.origin.is_none() {
+            result.add_error("配置镜像时必须设置主服务源 origin".to_string());
+        }
+        let origins = self.origin.iter().chain(self.mirrors.iter().map(|m| &m.origin));
+        for origin in origins {
+            let valid = url::Url::parse(origin)
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+            if !valid {
+                result.add_error(format!("服务源 {} 无效，应为 http(s)://host[:port]", origin));
+            }
+        }
+        result
+    }
+}
+
+/// 引擎级礼貌访问配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnginePolitenessConfig {
+    /// 同一域名两次请求之间的最小间隔（毫秒）
+    pub min_delay_ms: Option<u64>,
+    /// 是否遵守 robots.txt 中的 Crawl-delay（未设置时仅非 API 引擎遵守）
+    pub respect_crawl_delay: Option<bool>,
 }
 
 /// 请求配置
@@ -97,6 +215,36 @@ pub struct ResponseConfig {
     pub use_proxy: bool,
     /// 代理配置覆盖
     pub proxy_override: Option<String>,
+    /// 响应体大小上限（字节），超过时中止读取；未设置时使用客户端默认上限
+    #[serde(default)]
+    pub max_response_bytes: Option<usize>,
+    /// 允许的 Content-Type（支持 `text/*`、`application/*+json` 通配），为空时不限制
+    #[serde(default = "default_allowed_content_types")]
+    pub allowed_content_types: Vec<String>,
+    /// 解压后与压缩前大小的最大比例，用于拒绝解压炸弹；为 None 时不检查
+    #[serde(default = "default_max_decompression_ratio")]
+    pub max_decompression_ratio: Option<u32>,
+}
+
+/// 默认允许的 Content-Type：文本、JSON、XML 与脚本
+fn default_allowed_content_types() -> Vec<String> {
+    [
+        "text/*",
+        "application/json",
+        "application/*+json",
+        "application/xml",
+        "application/*+xml",
+        "application/javascript",
+        "application/x-javascript",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+/// 默认解压比例上限
+fn default_max_decompression_ratio() -> Option<u32> {
+    Some(100)
 }
 
 /// 重试配置
@@ -160,6 +308,9 @@ pub struct EnginePerformanceConfig {
     pub rate_limiting: EngineRateLimitConfig,
     /// 负载均衡配置
     pub load_balancing: LoadBalancingConfig,
+    /// 每日下载流量配额（字节），用尽后当日暂停使用该引擎；未设置时使用全局配额
+    #[serde(default)]
+    pub daily_bandwidth_quota_bytes: Option<u64>,
 }
 
 /// 并发配置
@@ -330,7 +481,13 @@ pub enum ParserType {
 }
 
 /// 结果过滤配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// 域名规则支持三种写法：`example.com` 匹配该域名及其子域名，
+/// 含 `*`/`?` 的规则按通配符匹配整个主机名（如 `ads.*`），
+/// `/.../` 包裹的规则按正则表达式匹配主机名。
+/// 白名单优先于黑名单与远程列表；URL、标题与内容过滤规则为正则表达式，匹配的结果被移除
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ResultFilteringConfig {
     /// 是否启用过滤
     pub enabled: bool,
@@ -344,6 +501,192 @@ pub struct ResultFilteringConfig {
     pub domain_whitelist: Vec<String>,
     /// 域名黑名单
     pub domain_blacklist: Vec<String>,
+    /// 定期获取的域名列表（广告、恶意域名等）
+    pub remote_lists: Vec<RemoteDomainListConfig>,
+    /// 按搜索分类（如 `general`、`image`、`news`）覆盖过滤设置
+    pub category_overrides: HashMap<String, CategoryFilterOverride>,
+}
+
+/// 远程域名列表配置
+///
+/// 支持 hosts 文件（`0.0.0.0 example.com`）、每行一个域名与 Adblock（`||example.com^`）格式，
+/// `#` 与 `!` 开头的行视为注释
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteDomainListConfig {
+    /// 列表地址（http/https URL 或本地文件路径，本地文件修改后在下次刷新时生效）
+    pub url: String,
+    /// 列表类型
+    #[serde(default)]
+    pub kind: DomainListKind,
+    /// 刷新间隔（秒）
+    #[serde(default = "default_list_refresh_interval")]
+    pub refresh_interval_secs: u64,
+}
+
+/// 默认的远程列表刷新间隔（1 天）
+fn default_list_refresh_interval() -> u64 {
+    86400
+}
+
+/// 域名列表类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DomainListKind {
+    /// 屏蔽列表中的域名
+    #[default]
+    Block,
+    /// 放行列表中的域名（优先于屏蔽规则）
+    Allow,
+}
+
+/// 按分类的过滤覆盖
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CategoryFilterOverride {
+    /// 是否对该分类启用过滤（未设置时沿用全局设置）
+    pub enabled: Option<bool>,
+    /// 追加的域名白名单
+    pub domain_whitelist: Vec<String>,
+    /// 追加的域名黑名单
+    pub domain_blacklist: Vec<String>,
+    /// 是否应用远程域名列表
+    pub use_remote_lists: bool,
+}
+
+impl Default for CategoryFilterOverride {
+    fn default() -> Self {
+        Self {
+            enabled: None,
+            domain_whitelist: Vec::new(),
+            domain_blacklist: Vec::new(),
+            use_remote_lists: true,
+        }
+    }
+}
+
+impl ResultFilteringConfig {
+    /// 验证结果过滤配置
+    pub fn validate(&self) -> ConfigValidationResult {
+        let mut result = ConfigValidationResult::success();
+
+        let patterns = self
+            .url_filters
+            .iter()
+            .chain(&self.title_filters)
+            .chain(&self.content_filters);
+        for pattern in patterns {
+            if let Err(e) = regex::Regex::new(pattern) {
+                result.add_error(format!("结果过滤规则 {} 无效: {}", pattern, e));
+            }
+        }
+
+        let overrides = self.category_overrides.values();
+        let domain_rules = self
+            .domain_whitelist
+            .iter()
+            .chain(&self.domain_blacklist)
+            .chain(overrides.flat_map(|o| o.domain_whitelist.iter().chain(&o.domain_blacklist)));
+        for rule in domain_rules {
+            if let Some(pattern) = rule.strip_prefix('/').and_then(|r| r.strip_suffix('/'))
+                && let Err(e) = regex::Regex::new(pattern)
+            {
+                result.add_error(format!("域名规则 {} 无效: {}", rule, e));
+            }
+        }
+
+        for list in &self.remote_lists {
+            if list.url.trim().is_empty() {
+                result.add_error("远程域名列表地址不能为空".to_string());
+            }
+            if list.refresh_interval_secs == 0 {
+                result.add_error(format!("远程域名列表 {} 的刷新间隔必须大于 0", list.url));
+            }
+        }
+
+        result
+    }
+}
+
+/// 垃圾/内容农场结果过滤配置
+///
+/// 按启发式信号为聚合后的每条结果打分（0.0 - 1.0），达到 `threshold` 的结果被移除：
+/// 低信誉域名、标题与摘要中的关键词堆砌、不同站点之间套用相同摘要模板
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpamFilterConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 移除结果的垃圾评分阈值（0.0 - 1.0）
+    pub threshold: f64,
+    /// 低信誉域名（同时匹配其子域名），如已知的采集站与内容农场
+    pub low_reputation_domains: Vec<String>,
+}
+
+impl Default for SpamFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.6,
+            low_reputation_domains: Vec::new(),
+        }
+    }
+}
+
+impl SpamFilterConfig {
+    /// 验证垃圾结果过滤配置
+    pub fn validate(&self) -> ConfigValidationResult {
+        let mut result = ConfigValidationResult::success();
+        if !(0.0..=1.0).contains(&self.threshold) {
+            result.add_error("垃圾结果评分阈值必须在 0.0-1.0 之间".to_string());
+        }
+        result
+    }
+}
+
+/// 结果语言处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LanguageFilterMode {
+    /// 提升请求语言的结果，其余结果保留
+    Boost,
+    /// 移除检测语言与请求语言不同的结果（无法检测语言的结果保留）
+    Filter,
+}
+
+/// 结果语言过滤配置
+///
+/// 请求指定语言（如 `language=zh`）时，按标准化阶段检测到的结果语言提升或移除结果；
+/// 请求未指定、由查询自动检测语言时不处理
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanguageFilterConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 处理方式
+    pub mode: LanguageFilterMode,
+    /// 提升模式下语言匹配结果的评分增幅（0.2 表示评分乘以 1.2）
+    pub boost: f64,
+}
+
+impl Default for LanguageFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: LanguageFilterMode::Boost,
+            boost: 0.5,
+        }
+    }
+}
+
+impl LanguageFilterConfig {
+    /// 验证结果语言过滤配置
+    pub fn validate(&self) -> ConfigValidationResult {
+        let mut result = ConfigValidationResult::success();
+        if !self.boost.is_finite() || self.boost < 0.0 {
+            result.add_error("结果语言提升幅度必须是非负数".to_string());
+        }
+        result
+    }
 }
 
 /// 结果排序配置
@@ -381,7 +724,7 @@ pub struct ResultLimitingConfig {
 }
 
 /// 引擎特定配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EngineSpecificConfig {
     /// 引擎类型特定配置
     pub api_key: Option<String>,
@@ -446,7 +789,7 @@ pub struct TokenRefreshConfig {
 /// 引擎依赖配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EngineDependencies {
-    /// 必需的依赖
+    /// 必需的依赖（`bin:`、`env:`、`tcp:`、`feature:` 或 `api_key`，启动时检查）
     pub required: Vec<String>,
     /// 可选的依赖
     pub optional: Vec<String>,
@@ -461,7 +804,7 @@ pub struct SystemRequirements {
     pub min_memory_mb: Option<usize>,
     /// 最小 CPU 核心数
     pub min_cpu_cores: Option<usize>,
-    /// 所需的磁盘空间（MB）
+    /// 所需的磁盘空间（MB，启动时不检查）
     pub min_disk_space_mb: Option<usize>,
     /// 支持的平台
     pub supported_platforms: Vec<String>,
@@ -478,12 +821,28 @@ pub struct CategoryConfig {
     pub icon: Option<String>,
     /// 默认引擎
     pub default_engines: Vec<String>,
-    /// 分类权重
+    /// 分类权重（乘到该分类所有引擎的权重上）
     pub weight: f32,
+    /// 分类内各引擎的权重倍数（未列出的引擎为 1.0），与分类权重相乘后参与聚合评分
+    #[serde(default)]
+    pub engine_weights: HashMap<String, f32>,
     /// 是否启用
     pub enabled: bool,
 }
 
+impl CategoryConfig {
+    /// 计算分类内各默认引擎的权重倍数
+    pub fn engine_multipliers(&self) -> HashMap<String, f64> {
+        self.default_engines
+            .iter()
+            .map(|engine| {
+                let weight = self.engine_weights.get(engine).copied().unwrap_or(1.0);
+                (engine.clone(), f64::from(weight * self.weight))
+            })
+            .collect()
+    }
+}
+
 /// 全局引擎设置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalEngineSettings {
@@ -565,6 +924,10 @@ impl Default for EnginesConfig {
             global_settings: GlobalEngineSettings::default(),
             discovery: EngineDiscoveryConfig::default(),
             health_check: HealthCheckConfig::default(),
+            enable_files_category: false,
+            result_filtering: ResultFilteringConfig::default(),
+            spam_filter: SpamFilterConfig::default(),
+            language_filter: LanguageFilterConfig::default(),
         }
     }
 }
@@ -602,6 +965,15 @@ impl EnginesConfig {
                     result.add_error(format!("引擎 {} 的超时时间必须大于 0", engine_name));
                 }
             }
+
+            for error in engine_config.network.geo.validate().errors {
+                result.add_error(format!("引擎 {} 的地域配置: {}", engine_name, error));
+            }
+
+            let session = &engine_config.network.session;
+            if session.enabled && session.ttl_secs == 0 {
+                result.add_error(format!("引擎 {} 的会话有效期必须大于 0", engine_name));
+            }
         }
 
         // 验证健康检查配置
@@ -617,6 +989,17 @@ impl EnginesConfig {
             }
         }
 
+        // 验证结果过滤配置
+        for error in self.result_filtering.validate().errors {
+            result.add_error(error);
+        }
+        for error in self.spam_filter.validate().errors {
+            result.add_error(error);
+        }
+        for error in self.language_filter.validate().errors {
+            result.add_error(error);
+        }
+
         result
     }
 
@@ -700,6 +1083,9 @@ impl Default for EngineNetworkConfig {
             response: ResponseConfig::default(),
             retry: RetryConfig::default(),
             timeout: TimeoutConfig::default(),
+            politeness: EnginePolitenessConfig::default(),
+            geo: EngineGeoConfig::default(),
+            session: EngineSessionConfig::default(),
         }
     }
 }
@@ -727,6 +1113,9 @@ impl Default for ResponseConfig {
             verify_ssl: true,
             use_proxy: false,
             proxy_override: None,
+            max_response_bytes: None,
+            allowed_content_types: default_allowed_content_types(),
+            max_decompression_ratio: default_max_decompression_ratio(),
         }
     }
 }
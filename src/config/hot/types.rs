@@ -0,0 +1,141 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 热榜配置类型定义
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::common::ConfigValidationResult;
+
+/// 热榜配置
+///
+/// 内置微博、知乎、Hacker News 与 Reddit 热榜；`platforms` 中的条目按 ID 覆盖内置平台
+/// （例如设置 `enabled = false` 关闭某个平台）或添加新平台（如任意 RSS 源）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotConfig {
+    /// 是否启用热榜
+    pub enabled: bool,
+    /// 热榜缓存时间（秒，0 表示不缓存）
+    pub cache_ttl_secs: u64,
+    /// 每个平台最多返回的条目数
+    pub max_items: usize,
+    /// 请求超时（毫秒）
+    pub timeout_ms: u64,
+    /// 平台覆盖与自定义平台（平台 ID -> 平台配置）
+    pub platforms: BTreeMap<String, HotPlatformConfig>,
+}
+
+/// 热榜平台配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotPlatformConfig {
+    /// 显示名称
+    pub name: String,
+    /// 热榜数据格式
+    pub format: HotFormat,
+    /// 热榜地址
+    pub url: String,
+    /// 是否启用
+    #[serde(default = "default_platform_enabled")]
+    pub enabled: bool,
+}
+
+/// 热榜数据格式（决定使用哪个解析器）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotFormat {
+    /// 微博热搜 JSON
+    Weibo,
+    /// 知乎热榜 JSON
+    Zhihu,
+    /// Hacker News（Algolia API）JSON
+    HackerNews,
+    /// Reddit listing JSON
+    Reddit,
+    /// RSS 2.0 / Atom 源
+    Rss,
+}
+
+impl HotFormat {
+    /// 格式名称（与配置中的取值一致）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HotFormat::Weibo => "weibo",
+            HotFormat::Zhihu => "zhihu",
+            HotFormat::HackerNews => "hacker_news",
+            HotFormat::Reddit => "reddit",
+            HotFormat::Rss => "rss",
+        }
+    }
+}
+
+fn default_platform_enabled() -> bool {
+    true
+}
+
+impl Default for HotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cache_ttl_secs: 600,
+            max_items: 50,
+            timeout_ms: 10_000,
+            platforms: BTreeMap::new(),
+        }
+    }
+}
+
+impl HotConfig {
+    /// 验证热榜配置
+    pub fn validate(&self) -> ConfigValidationResult {
+        let mut result = ConfigValidationResult::success();
+        if self.max_items == 0 {
+            result.add_error("hot.max_items 必须大于 0".to_string());
+        }
+        if self.timeout_ms == 0 {
+            result.add_error("hot.timeout_ms 必须大于 0".to_string());
+        }
+        for (id, platform) in &self.platforms {
+            let valid = url::Url::parse(&platform.url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+            if !valid {
+                result.add_error(format!("热榜平台 {} 的地址无效: {}", id, platform.url));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hot_config_validation() {
+        let mut config = HotConfig::default();
+        assert!(config.validate().is_valid);
+
+        config.platforms.insert(
+            "lobsters".to_string(),
+            HotPlatformConfig {
+                name: "Lobsters".to_string(),
+                format: HotFormat::Rss,
+                url: "ftp://lobste.rs/rss".to_string(),
+                enabled: true,
+            },
+        );
+        assert!(!config.validate().is_valid);
+    }
+}
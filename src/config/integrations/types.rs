@@ -0,0 +1,103 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 外部集成配置类型定义
+
+use serde::{Deserialize, Serialize};
+
+/// 外部集成配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrationsConfig {
+    /// Webhook 配置
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
+}
+
+/// Webhook 配置
+///
+/// 发生订阅的事件时向各端点 POST JSON 负载，配置了密钥的端点附带 HMAC-SHA256 签名
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// 是否启用 Webhook
+    pub enabled: bool,
+    /// 接收端点
+    pub endpoints: Vec<WebhookEndpointConfig>,
+    /// 保存的查询（新 RSS 条目包含查询中所有关键词时触发 `rss_items_matched`）
+    pub rss_saved_queries: Vec<String>,
+    /// 投递失败后的最大重试次数
+    pub max_retries: u32,
+    /// 首次重试前的等待时间（毫秒），之后每次翻倍
+    pub initial_backoff_ms: u64,
+    /// 单次投递超时（秒）
+    pub timeout_secs: u64,
+}
+
+/// Webhook 端点配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEndpointConfig {
+    /// 端点 URL
+    pub url: String,
+    /// 签名密钥（未设置时不签名）
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// 订阅的事件（为空时订阅全部事件）
+    #[serde(default)]
+    pub events: Vec<WebhookEventKind>,
+}
+
+/// Webhook 事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    /// 订阅的 RSS feed 出现匹配保存查询的新条目
+    RssItemsMatched,
+    /// 引擎因连续零结果被临时禁用
+    EngineDisabled,
+    /// 抓取引擎疑似选择器失效被标记为降级
+    EngineDegraded,
+    /// 保存的搜索出现新结果
+    SavedSearchMatched,
+}
+
+impl WebhookEventKind {
+    /// 事件名称（与序列化名称一致）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RssItemsMatched => "rss_items_matched",
+            Self::EngineDisabled => "engine_disabled",
+            Self::EngineDegraded => "engine_degraded",
+            Self::SavedSearchMatched => "saved_search_matched",
+        }
+    }
+}
+
+impl WebhookEndpointConfig {
+    /// 端点是否订阅了指定事件
+    pub fn subscribes_to(&self, kind: WebhookEventKind) -> bool {
+        self.events.is_empty() || self.events.contains(&kind)
+    }
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoints: Vec::new(),
+            rss_saved_queries: Vec::new(),
+            max_retries: 3,
+            initial_backoff_ms: 500,
+            timeout_secs: 10,
+        }
+    }
+}
@@ -16,7 +16,7 @@
 //!
 //! 提供灵活的配置文件加载功能
 
-use crate::config::{SeeSeaConfig, ConfigError, ConfigLoadResult};
+use crate::config::{resolve_secrets, SeeSeaConfig, ConfigError, ConfigLoadResult};
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
 use tokio::fs;
@@ -58,6 +58,12 @@ impl ConfigLoader {
         self
     }
 
+    /// 只在指定路径中查找配置文件（替换默认搜索路径）
+    pub fn with_search_paths<P: AsRef<Path>>(mut self, paths: &[P]) -> Self {
+        self.search_paths = paths.iter().map(|p| p.as_ref().to_path_buf()).collect();
+        self
+    }
+
     /// 添加文件模式
     pub fn add_file_pattern<S: Into<String>>(mut self, pattern: S) -> Self {
         self.file_patterns.push(pattern.into());
@@ -112,14 +118,15 @@ impl ConfigLoader {
         // 应用后处理
         self.post_process(&mut final_config).await?;
 
-        // 验证配置
-        let validation_result = final_config.validate();
+        // 验证配置（校验信息中的密钥值替换为指纹）
+        let validation_result = crate::config::redact_validation(final_config.validate(), &final_config);
         if !validation_result.is_valid {
             return Err(ConfigError::ValidationFailed(validation_result.errors));
         }
 
         warnings.extend(validation_result.warnings);
         let summary = final_config.get_summary();
+        tracing::debug!(config = %crate::config::Redact(&final_config), "生效的配置");
 
         let load_result = ConfigLoadResult {
             config: final_config,
@@ -147,6 +154,8 @@ impl ConfigLoader {
     }
 
     /// 从指定文件加载配置
+    ///
+    /// 密钥类字段中的 `env:`、`file:`、`vault:`、`sops:` 引用在此解析（见 [`resolve_secrets`]）
     pub async fn load_from_file<P: AsRef<Path>>(
         &self,
         path: P,
@@ -155,7 +164,8 @@ impl ConfigLoader {
         let content = fs::read_to_string(path).await
             .map_err(|e| ConfigError::IoError(format!("读取配置文件失败: {}", e)))?;
 
-        let config = self.parse_config_content(&content, path)?;
+        let mut config = self.parse_config_content(&content, path)?;
+        resolve_secrets(&mut config)?;
         Ok(config)
     }
 
@@ -165,6 +175,7 @@ impl ConfigLoader {
 
         // 应用环境变量覆盖
         self.apply_env_overrides(&mut config)?;
+        resolve_secrets(&mut config)?;
 
         Ok(config)
     }
@@ -322,6 +333,9 @@ impl ConfigLoader {
         for (name, engine) in source.engines.drain() {
             target.engines.insert(name, engine);
         }
+        if source.enable_files_category {
+            target.enable_files_category = true;
+        }
         Ok(())
     }
 
@@ -536,6 +550,24 @@ port = 9999
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_commented_toml_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        let config = SeeSeaConfig::for_environment(crate::config::Environment::Production);
+        let content = config.to_commented_toml()?;
+        assert!(content.contains("# 服务器配置"));
+
+        let temp_file = tempfile::Builder::new().suffix(".toml").tempfile()?;
+        fs::write(temp_file.path(), &content).await?;
+
+        let loader = ConfigLoader::new();
+        let loaded = loader.load_from_file(temp_file.path()).await?;
+        assert_eq!(loaded.environment, crate::config::Environment::Production);
+        assert_eq!(loaded.general.environment, crate::config::Environment::Production);
+        assert_eq!(loaded.server.port, config.server.port);
+
+        Ok(())
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_environment_overrides() {
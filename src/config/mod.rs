@@ -28,6 +28,11 @@ pub mod cache;
 pub mod api;
 pub mod logging;
 pub mod engines;
+pub mod archive;
+pub mod politeness;
+pub mod integrations;
+pub mod web;
+pub mod hot;
 
 // 核心类型定义
 pub mod types;
@@ -36,9 +41,13 @@ pub mod types;
 pub mod config;
 
 // 公共接口
+#[cfg(feature = "native")]
 pub mod on;
+#[cfg(feature = "native")]
 pub mod loader;
 pub mod validator;
+pub mod secrets;
+pub mod redact;
 
 // 重新导出关键公共类型
 pub use common::{
@@ -54,8 +63,16 @@ pub use cache::CacheConfig;
 pub use api::ApiConfig;
 pub use logging::LoggingConfig;
 pub use engines::EnginesConfig;
+pub use archive::{ArchiveConfig, ArchiveFormat};
+pub use politeness::PolitenessConfig;
+pub use hot::{HotConfig, HotFormat, HotPlatformConfig};
+pub use integrations::{IntegrationsConfig, WebhookConfig, WebhookEndpointConfig, WebhookEventKind};
 pub use types::Environment;
 pub use config::{SeeSeaConfig, ConfigLoadResult, ConfigSummary, ConfigError, ConfigSource};
+#[cfg(feature = "native")]
 pub use on::{ConfigManager, get_global_config, init_config, init_config_with_env};
+#[cfg(feature = "native")]
 pub use loader::ConfigLoader;
-pub use validator::{ConfigValidator, validate_config};
\ No newline at end of file
+pub use validator::{ConfigValidator, validate_config};
+pub use secrets::{redact_config, redact_text, redact_validation, resolve_secrets};
+pub use redact::{fingerprint, Redact};
\ No newline at end of file
@@ -90,7 +90,7 @@ impl ConfigManager {
     /// 加载配置文件
     pub async fn load_config(&self) -> Result<ConfigLoadResult, ConfigError> {
         let config = Self::load_from_file(&self.config_path).await?;
-        let validation_result = config.validate();
+        let validation_result = crate::config::redact_validation(config.validate(), &config);
         let summary = config.get_summary();
 
         let load_result = ConfigLoadResult {
@@ -113,6 +113,7 @@ impl ConfigManager {
         }
 
         tracing::info!("配置加载成功: {:?}", self.config_path);
+        tracing::debug!(config = %crate::config::Redact(&load_result.config), "生效的配置");
         for warning in &load_result.warnings {
             tracing::warn!("配置警告: {}", warning);
         }
@@ -184,8 +185,9 @@ impl ConfigManager {
             .map_err(|e| ConfigError::IoError(e.to_string()))?;
 
         // 尝试解析为 TOML
-        let config: SeeSeaConfig = toml::from_str(&config_str)
+        let mut config: SeeSeaConfig = toml::from_str(&config_str)
             .map_err(|e| ConfigError::ParseError(e.to_string()))?;
+        crate::config::resolve_secrets(&mut config)?;
 
         Ok(config)
     }
@@ -0,0 +1,49 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 礼貌访问配置类型定义
+
+use serde::{Deserialize, Serialize};
+
+/// 礼貌访问配置
+///
+/// 启用后对同一域名的请求保持最小间隔，并对非 API 引擎遵守 robots.txt 中的 Crawl-delay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolitenessConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 同一域名两次请求之间的最小间隔（毫秒）
+    pub min_delay_ms: u64,
+    /// 是否遵守 robots.txt 中的 Crawl-delay（仅对非 API 引擎生效）
+    pub respect_crawl_delay: bool,
+    /// 遵守的 Crawl-delay 上限（秒），避免站点设置过大的延迟拖慢搜索
+    pub max_crawl_delay_secs: u64,
+    /// robots.txt 缓存时间（秒）
+    pub robots_cache_ttl_secs: u64,
+    /// 匹配 robots.txt 中 User-agent 分组时使用的名称
+    pub robots_user_agent: String,
+}
+
+impl Default for PolitenessConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_delay_ms: 1000,
+            respect_crawl_delay: true,
+            max_crawl_delay_secs: 10,
+            robots_cache_ttl_secs: 24 * 3600,
+            robots_user_agent: "SeeSea".to_string(),
+        }
+    }
+}
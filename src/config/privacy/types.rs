@@ -15,8 +15,12 @@
 //! 隐私保护配置类型定义
 
 use crate::config::common::{ConfigValidationResult, FingerprintLevel, ProxyType, TimingStrategy};
+use crate::derive::types::ResultType;
 use serde::{Deserialize, Serialize};
 
+/// 搜索历史加密口令的最少字符数
+pub const MIN_HISTORY_KEY_CHARS: usize = 16;
+
 /// 隐私保护配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrivacyConfig {
@@ -38,6 +42,102 @@ pub struct PrivacyConfig {
     pub headers: HeaderConfig,
     /// Cookie 处理
     pub cookie_handling: CookieConfig,
+    /// 结果链接重写（经本地跳转端点去除追踪参数）
+    #[serde(default)]
+    pub url_rewrite: UrlRewriteConfig,
+    /// 缩略图代理（由服务器代为获取第三方图片）
+    #[serde(default)]
+    pub image_proxy: ImageProxyConfig,
+    /// 搜索历史（默认关闭，启用后加密保存）
+    #[serde(default)]
+    pub history: HistoryConfig,
+    /// 结果点击统计（默认关闭，只累计按引擎与域名的点击数）
+    #[serde(default)]
+    pub click_tracking: ClickTrackingConfig,
+    /// 结果链接签名密钥（跳转、点击与缩略图代理链接），为空则每次启动随机生成
+    #[serde(default)]
+    pub link_signing_key: Option<String>,
+}
+
+/// 结果点击统计配置
+///
+/// 启用后结果链接改写为经 `/click` 跳转，服务器只累计各引擎与目标域名的点击数，
+/// 不保存查询、完整链接或用户信息；引擎的点击率参与自适应引擎权重
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClickTrackingConfig {
+    /// 是否启用
+    pub enabled: bool,
+}
+
+/// 搜索历史配置
+///
+/// 默认关闭。启用后查询与点击的结果以 `encryption_key` 派生的密钥加密后保存，
+/// 超过保留期的记录自动删除
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 加密口令（至少 16 个字符），更换后旧记录无法解密
+    pub encryption_key: Option<String>,
+    /// 保留天数
+    pub retention_days: u32,
+}
+
+/// 缩略图代理配置
+///
+/// 启用后结果缩略图改写为 `/proxy/image?url=<原始链接>&s=<签名>`，
+/// 浏览器不再直接访问第三方图床，避免泄露用户 IP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageProxyConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 代理端点的外部访问前缀，为空则使用相对路径
+    pub base_url: Option<String>,
+    /// 单张图片最大字节数
+    pub max_size_bytes: usize,
+    /// 图片缓存时间（秒）
+    pub cache_ttl_secs: u64,
+    /// 获取图片的超时时间（秒）
+    pub timeout_secs: u64,
+    /// 缩略图预取
+    pub prefetch: ImagePrefetchConfig,
+}
+
+/// 缩略图预取配置
+///
+/// 启用后搜索完成时在后台经缩略图代理预先下载前 N 条结果的缩略图并写入缓存，
+/// 浏览器请求代理链接时可直接命中缓存；需同时启用缩略图代理
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImagePrefetchConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 每次搜索预取的缩略图数量（按结果评分取前 N 条）
+    pub top_n: usize,
+    /// 参与预取的结果类型
+    pub result_types: Vec<ResultType>,
+    /// 同时下载的缩略图数量
+    pub concurrency: usize,
+    /// 图片缓存占用的磁盘上限（字节），超出后优先淘汰已过期、其次最久未访问的图片
+    pub disk_quota_bytes: u64,
+}
+
+/// 结果链接重写配置
+///
+/// 启用后结果链接会被改写为 `/r?u=<原始链接>&s=<签名>`，由 API 服务器校验签名、去除
+/// Referer 与追踪参数后再跳转
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrlRewriteConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 跳转端点的外部访问前缀（如 `https://search.example.com`），为空则使用相对路径
+    pub base_url: Option<String>,
+    /// 需要移除的查询参数，支持以 `*` 结尾的前缀匹配
+    pub strip_params: Vec<String>,
 }
 
 /// User-Agent 轮换配置
@@ -317,6 +417,11 @@ impl Default for PrivacyConfig {
             dns_config: DnsConfig::default(),
             headers: HeaderConfig::default(),
             cookie_handling: CookieConfig::default(),
+            url_rewrite: UrlRewriteConfig::default(),
+            image_proxy: ImageProxyConfig::default(),
+            history: HistoryConfig::default(),
+            click_tracking: ClickTrackingConfig::default(),
+            link_signing_key: None,
         }
     }
 }
@@ -373,6 +478,56 @@ impl PrivacyConfig {
             }
         }
 
+        // 验证链接重写配置
+        if self.url_rewrite.enabled
+            && let Some(ref base_url) = self.url_rewrite.base_url
+            && url::Url::parse(base_url).is_err()
+        {
+            result.add_error(format!("链接重写前缀 {} 不是有效的 URL", base_url));
+        }
+
+        // 验证缩略图代理配置
+        if self.image_proxy.enabled {
+            if self.image_proxy.max_size_bytes == 0 {
+                result.add_error("缩略图代理的最大图片大小必须大于 0".to_string());
+            }
+
+            if self.image_proxy.timeout_secs == 0 {
+                result.add_error("缩略图代理超时时间必须大于 0".to_string());
+            }
+        }
+
+        // 验证缩略图预取配置
+        let prefetch = &self.image_proxy.prefetch;
+        if prefetch.enabled {
+            if !self.image_proxy.enabled {
+                result.add_warning("缩略图预取需要启用缩略图代理，当前不会生效".to_string());
+            }
+
+            if prefetch.top_n == 0 {
+                result.add_error("缩略图预取数量必须大于 0".to_string());
+            }
+
+            if prefetch.concurrency == 0 {
+                result.add_error("缩略图预取并发数必须大于 0".to_string());
+            }
+
+            if prefetch.disk_quota_bytes < self.image_proxy.max_size_bytes as u64 {
+                result.add_error("图片缓存磁盘上限不能小于单张图片最大字节数".to_string());
+            }
+        }
+
+        // 验证搜索历史配置
+        if self.history.enabled {
+            if self.history.encryption_key.as_ref().is_none_or(|key| key.chars().count() < MIN_HISTORY_KEY_CHARS) {
+                result.add_error(format!("启用搜索历史时必须指定至少 {} 个字符的加密口令", MIN_HISTORY_KEY_CHARS));
+            }
+
+            if self.history.retention_days == 0 {
+                result.add_error("搜索历史保留天数必须大于 0".to_string());
+            }
+        }
+
         result
     }
 }
@@ -390,6 +545,55 @@ impl Default for UserAgentRotationConfig {
     }
 }
 
+impl Default for UrlRewriteConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: None,
+            strip_params: vec![
+                "utm_*".to_string(),
+                "fbclid".to_string(),
+                "gclid".to_string(),
+            ],
+        }
+    }
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            encryption_key: None,
+            retention_days: 30,
+        }
+    }
+}
+
+impl Default for ImageProxyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: None,
+            max_size_bytes: 5 * 1024 * 1024, // 5MB
+            cache_ttl_secs: 86400,           // 1 day
+            timeout_secs: 10,
+            prefetch: ImagePrefetchConfig::default(),
+        }
+    }
+}
+
+impl Default for ImagePrefetchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            top_n: 10,
+            result_types: vec![ResultType::Image, ResultType::News],
+            concurrency: 4,
+            disk_quota_bytes: 256 * 1024 * 1024, // 256MB
+        }
+    }
+}
+
 impl Default for TorConfig {
     fn default() -> Self {
         Self {
@@ -15,6 +15,7 @@
 //! 搜索配置类型定义
 
 use crate::config::common::{ConfigValidationResult, SafeSearchLevel};
+use crate::search::scoring::ScoringProfile;
 use serde::{Deserialize, Serialize};
 
 /// 搜索配置
@@ -46,6 +47,110 @@ pub struct SearchConfig {
     pub aggregation: AggregationConfig,
     /// 查询处理配置
     pub query_processing: QueryProcessingConfig,
+    /// 每个引擎的默认每日下载流量配额（字节），未设置时不限制
+    #[serde(default)]
+    pub daily_bandwidth_quota_bytes: Option<u64>,
+    /// 调试捕获文件的保存目录（请求启用 `debug_capture` 时写入），未设置时不落盘
+    #[serde(default)]
+    pub debug_capture_dir: Option<String>,
+    /// 抓取引擎连续多少个不同查询返回零结果时标记为降级并投递 `engine_degraded` 事件（0 表示不检测）
+    #[serde(default = "default_selector_rot_threshold")]
+    pub selector_rot_threshold: u32,
+    /// 负面结果（零结果、验证码、HTTP 403/429）按查询与引擎缓存的时长（秒），
+    /// 期间相同查询跳过该引擎（0 表示不缓存）
+    #[serde(default = "default_negative_cache_ttl_secs")]
+    pub negative_cache_ttl_secs: u64,
+    /// 结果页面元数据补全（OpenGraph/JSON-LD），默认关闭
+    #[serde(default)]
+    pub enrichment: EnrichmentConfig,
+    /// 结果评分参数（BM25 参数、评分权重与按搜索类型的覆盖）
+    #[serde(default)]
+    pub scoring: ScoringProfile,
+    /// 搜索会话（携带相同会话令牌的查询共享上下文）
+    #[serde(default)]
+    pub sessions: SearchSessionConfig,
+}
+
+/// 默认的选择器失效判定阈值
+fn default_selector_rot_threshold() -> u32 {
+    3
+}
+
+/// 默认的负面结果缓存时长（秒）
+fn default_negative_cache_ttl_secs() -> u64 {
+    300
+}
+
+/// 结果页面元数据补全配置
+///
+/// 启用后获取排名靠前的结果页面，提取 OpenGraph 与 JSON-LD 中的描述、图片、作者与发布时间，
+/// 用于补全过短的摘要与缺失的缩略图。页面经服务端的 HTTP 客户端获取（遵循代理与礼貌访问设置），
+/// 只访问公网 http(s) 地址，提取结果按 URL 缓存
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnrichmentConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 每次搜索最多补全的结果数（按排序靠前的优先）
+    pub top_n: usize,
+    /// 同时获取的页面数上限（所有搜索共享）
+    pub max_concurrent: usize,
+    /// 单个页面的获取超时（毫秒）
+    pub timeout_ms: u64,
+    /// 单个页面的最大下载字节数
+    pub max_page_bytes: usize,
+    /// 提取结果的缓存时间（秒，0 表示不缓存）
+    pub cache_ttl_secs: u64,
+    /// 不获取的域名（同时匹配其子域名）
+    pub skip_domains: Vec<String>,
+}
+
+impl Default for EnrichmentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            top_n: 5,
+            max_concurrent: 4,
+            timeout_ms: 3000,
+            max_page_bytes: 512 * 1024,
+            cache_ttl_secs: 86400,
+            skip_domains: Vec::new(),
+        }
+    }
+}
+
+/// 搜索会话配置
+///
+/// 请求携带会话令牌时，会话中之前查询的词用于提升相关结果的排名，之前的查询作为相关查询返回。
+/// 会话保存在缓存中（需启用缓存），超过有效期未再查询的会话自动过期
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchSessionConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 会话有效期（秒，自最后一次查询起）
+    pub ttl_secs: u64,
+    /// 每个会话保留的查询数
+    pub max_queries: usize,
+    /// 参与重排的上下文词数上限
+    pub max_context_terms: usize,
+    /// 包含全部上下文词的结果的评分提升比例（按包含的比例折算）
+    pub boost: f64,
+    /// 返回的相关查询数上限
+    pub max_related_queries: usize,
+}
+
+impl Default for SearchSessionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: 1800,
+            max_queries: 10,
+            max_context_terms: 8,
+            boost: 0.2,
+            max_related_queries: 5,
+        }
+    }
 }
 
 /// 时间范围
@@ -83,6 +188,27 @@ pub struct AggregationConfig {
     pub enable_grouping: bool,
     /// 分组策略
     pub grouping_strategy: GroupingStrategy,
+    /// 聚合策略（合并后结果的排列方式）
+    #[serde(default)]
+    pub strategy: AggregationMode,
+    /// 引擎优先级（`engine_priority` 策略按此顺序排列各引擎的结果）
+    #[serde(default)]
+    pub engine_priority: Vec<String>,
+}
+
+/// 聚合策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMode {
+    /// 按相关性评分合并（综合引擎权威度）
+    #[default]
+    Merged,
+    /// 按引擎轮流交替排列
+    RoundRobin,
+    /// 严格按 `engine_priority` 中的引擎顺序排列
+    EnginePriority,
+    /// 只按结果自身的相关性评分排列，不计入引擎权威度
+    ScoreOnly,
 }
 
 /// 去重算法
@@ -152,6 +278,22 @@ pub struct QueryProcessingConfig {
     pub max_query_length: usize,
     /// 最小查询长度
     pub min_query_length: usize,
+    /// 启用敏感信息清洗（邮箱、电话号码等）
+    #[serde(default)]
+    pub enable_pii_scrubbing: bool,
+    /// 自定义敏感信息正则表达式
+    #[serde(default)]
+    pub pii_patterns: Vec<String>,
+    /// 繁简统一：查询解析与缓存全文搜索时将繁体字转换为简体后再匹配
+    #[serde(default = "default_true")]
+    pub enable_script_normalization: bool,
+    /// 拼音匹配：缓存全文搜索时允许纯字母关键词按拼音匹配中文内容
+    #[serde(default = "default_true")]
+    pub enable_pinyin_matching: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// 查询扩展方法
@@ -195,6 +337,13 @@ impl Default for SearchConfig {
             default_time_range: None,
             aggregation: AggregationConfig::default(),
             query_processing: QueryProcessingConfig::default(),
+            daily_bandwidth_quota_bytes: None,
+            debug_capture_dir: None,
+            selector_rot_threshold: default_selector_rot_threshold(),
+            negative_cache_ttl_secs: default_negative_cache_ttl_secs(),
+            enrichment: EnrichmentConfig::default(),
+            scoring: ScoringProfile::default(),
+            sessions: SearchSessionConfig::default(),
         }
     }
 }
@@ -235,6 +384,16 @@ impl SearchConfig {
             result.add_warning("并发引擎数过多可能影响性能".to_string());
         }
 
+        // 检查聚合策略
+        if self.aggregation.strategy == AggregationMode::EnginePriority && self.aggregation.engine_priority.is_empty() {
+            result.add_error("引擎优先级聚合策略需要在 engine_priority 中指定至少一个引擎".to_string());
+        }
+
+        // 检查评分参数
+        if let Err(e) = self.scoring.validate() {
+            result.add_error(format!("评分配置无效: {}", e));
+        }
+
         // 检查支持的格式
         if self.formats.is_empty() {
             result.add_error("必须指定至少一种输出格式".to_string());
@@ -253,6 +412,40 @@ impl SearchConfig {
             if processing.correction_threshold < 0.0 || processing.correction_threshold > 1.0 {
                 result.add_error("纠正阈值必须在 0.0-1.0 之间".to_string());
             }
+
+            for pattern in &processing.pii_patterns {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    result.add_error(format!("敏感信息模式 '{}' 无效: {}", pattern, e));
+                }
+            }
+        }
+
+        // 检查结果补全
+        let enrichment = &self.enrichment;
+        if enrichment.enabled {
+            if enrichment.max_concurrent == 0 {
+                result.add_error("结果补全的并发数必须大于 0".to_string());
+            }
+            if enrichment.timeout_ms == 0 {
+                result.add_error("结果补全的超时时间必须大于 0".to_string());
+            }
+            if enrichment.max_page_bytes == 0 {
+                result.add_error("结果补全的页面大小上限必须大于 0".to_string());
+            }
+        }
+
+        // 检查搜索会话
+        let sessions = &self.sessions;
+        if sessions.enabled {
+            if sessions.ttl_secs == 0 {
+                result.add_error("搜索会话的有效期必须大于 0".to_string());
+            }
+            if sessions.max_queries == 0 {
+                result.add_error("搜索会话保留的查询数必须大于 0".to_string());
+            }
+            if !(0.0..=1.0).contains(&sessions.boost) {
+                result.add_error("搜索会话的评分提升比例必须在 0.0-1.0 之间".to_string());
+            }
         }
 
         result
@@ -285,6 +478,8 @@ impl Default for AggregationConfig {
             min_engine_weight: 0.1,
             enable_grouping: true,
             grouping_strategy: GroupingStrategy::Smart,
+            strategy: AggregationMode::default(),
+            engine_priority: Vec::new(),
         }
     }
 }
@@ -303,6 +498,10 @@ impl Default for QueryProcessingConfig {
             enable_stop_words: true,
             max_query_length: 200,
             min_query_length: 1,
+            enable_pii_scrubbing: false,
+            pii_patterns: Vec::new(),
+            enable_script_normalization: true,
+            enable_pinyin_matching: true,
         }
     }
 }
\ No newline at end of file
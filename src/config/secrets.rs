@@ -0,0 +1,224 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 配置密钥解析
+//!
+//! 密钥类字段（服务器密钥、JWT 密钥、引擎 API 密钥等）可以写成外部引用，加载配置时解析：
+//! - `env:VAR` - 读取环境变量
+//! - `file:/run/secrets/seesea` - 读取文件内容（去掉首尾空白）
+//! - `vault:secret/seesea#api_key` - 通过 `vault kv get -field=api_key secret/seesea` 读取
+//! - `sops:/etc/seesea/secrets.yaml#engines.github.token` - 通过 `sops --decrypt --extract` 读取
+//!
+//! 其他值按明文使用。配置摘要与校验报告中出现的密钥值替换为指纹（见 [`fingerprint`]）
+
+use crate::config::redact::fingerprint;
+use crate::config::{ConfigError, ConfigValidationResult, SeeSeaConfig};
+use std::process::Command;
+
+/// 短于该长度的密钥值不在文本中替换（避免误替换常见片段）
+const MIN_REDACT_LEN: usize = 4;
+
+/// 收集配置中所有密钥类字段（字段路径 → 值）
+fn secret_fields(config: &mut SeeSeaConfig) -> Vec<(String, &mut String)> {
+    let mut fields: Vec<(String, &mut String)> = vec![
+        ("server.secret_key".to_string(), &mut config.server.secret_key),
+        ("api.auth.jwt.secret".to_string(), &mut config.api.auth.jwt.secret),
+    ];
+    if let Some(oauth) = config.api.auth.oauth.as_mut() {
+        fields.push(("api.auth.oauth.client_secret".to_string(), &mut oauth.client_secret));
+    }
+    if let Some(key) = config.privacy.history.encryption_key.as_mut() {
+        fields.push(("privacy.history.encryption_key".to_string(), key));
+    }
+    if let Some(password) = config.privacy.tor_config.control_password.as_mut() {
+        fields.push(("privacy.tor_config.control_password".to_string(), password));
+    }
+    for (i, proxy) in config.privacy.proxy_chain.iter_mut().enumerate() {
+        if let Some(password) = proxy.password.as_mut() {
+            fields.push((format!("privacy.proxy_chain[{}].password", i), password));
+        }
+    }
+    for (i, endpoint) in config.integrations.webhooks.endpoints.iter_mut().enumerate() {
+        if let Some(secret) = endpoint.secret.as_mut() {
+            fields.push((format!("integrations.webhooks.endpoints[{}].secret", i), secret));
+        }
+    }
+    for (name, engine) in config.engines.engines.iter_mut() {
+        let specific = &mut engine.specific;
+        if let Some(key) = specific.api_key.as_mut() {
+            fields.push((format!("engines.engines.{}.specific.api_key", name), key));
+        }
+        if let Some(auth) = specific.authentication.as_mut() {
+            for (credential, value) in auth.credentials.iter_mut() {
+                fields.push((
+                    format!("engines.engines.{}.specific.authentication.credentials.{}", name, credential),
+                    value,
+                ));
+            }
+            if let Some(refresh) = auth.token_refresh.as_mut() {
+                let prefix = format!("engines.engines.{}.specific.authentication.token_refresh", name);
+                fields.push((format!("{}.client_secret", prefix), &mut refresh.client_secret));
+                fields.push((format!("{}.refresh_token", prefix), &mut refresh.refresh_token));
+            }
+        }
+    }
+    fields
+}
+
+/// 解析配置中的密钥引用
+///
+/// # Arguments
+///
+/// * `config` - 刚加载的配置，引用被替换为解析出的值
+///
+/// # Returns
+///
+/// 返回解析的引用数；任一引用无法解析时返回错误（错误信息不含密钥值）
+pub fn resolve_secrets(config: &mut SeeSeaConfig) -> Result<usize, ConfigError> {
+    let mut resolved = 0;
+    for (path, value) in secret_fields(config) {
+        if let Some(secret) = resolve_reference(value).map_err(|e| match e {
+            ConfigError::Environment(msg) => ConfigError::Environment(format!("{}: {}", path, msg)),
+            ConfigError::Io(msg) => ConfigError::Io(format!("{}: {}", path, msg)),
+            other => other,
+        })? {
+            *value = secret;
+            resolved += 1;
+        }
+    }
+    Ok(resolved)
+}
+
+/// 解析单个值，不是外部引用时返回 None
+fn resolve_reference(value: &str) -> Result<Option<String>, ConfigError> {
+    let Some((scheme, reference)) = value.split_once(':') else {
+        return Ok(None);
+    };
+    let secret = match scheme {
+        "env" => std::env::var(reference)
+            .map_err(|_| ConfigError::Environment(format!("环境变量 {} 未设置", reference)))?,
+        "file" => std::fs::read_to_string(reference)
+            .map_err(|e| ConfigError::Io(format!("读取密钥文件 {} 失败: {}", reference, e)))?
+            .trim()
+            .to_string(),
+        "vault" => {
+            let (path, field) = split_field(reference, "vault")?;
+            run_provider(Command::new("vault").args(["kv", "get", &format!("-field={}", field), path]), "vault")?
+        }
+        "sops" => {
+            let (file, key) = split_field(reference, "sops")?;
+            let extract: String = key.split('.').map(|part| format!("[\"{}\"]", part)).collect();
+            run_provider(Command::new("sops").args(["--decrypt", "--extract", &extract, file]), "sops")?
+        }
+        _ => return Ok(None),
+    };
+    if secret.is_empty() {
+        return Err(ConfigError::Environment(format!("{} 引用解析为空值", scheme)));
+    }
+    Ok(Some(secret))
+}
+
+/// 拆分 `路径#字段` 形式的引用
+fn split_field<'a>(reference: &'a str, scheme: &str) -> Result<(&'a str, &'a str), ConfigError> {
+    reference
+        .split_once('#')
+        .filter(|(path, field)| !path.is_empty() && !field.is_empty())
+        .ok_or_else(|| ConfigError::Parse(format!("{} 引用需要 `路径#字段` 格式", scheme)))
+}
+
+/// 执行外部密钥工具并读取标准输出
+fn run_provider(command: &mut Command, name: &str) -> Result<String, ConfigError> {
+    let output = command
+        .output()
+        .map_err(|e| ConfigError::Io(format!("无法执行 {}: {}", name, e)))?;
+    if !output.status.success() {
+        return Err(ConfigError::Io(format!(
+            "{} 读取密钥失败: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 返回密钥类字段替换为指纹的配置副本（空值保持为空）
+pub fn redact_config(config: &SeeSeaConfig) -> SeeSeaConfig {
+    let mut redacted = config.clone();
+    for (_, value) in secret_fields(&mut redacted) {
+        if !value.is_empty() {
+            *value = fingerprint(value);
+        }
+    }
+    redacted
+}
+
+/// 将文本中出现的密钥值替换为指纹
+pub fn redact_text(text: &str, config: &SeeSeaConfig) -> String {
+    let mut config = config.clone();
+    let mut secrets: Vec<String> = secret_fields(&mut config)
+        .into_iter()
+        .map(|(_, value)| value.clone())
+        .filter(|value| value.len() >= MIN_REDACT_LEN)
+        .collect();
+    // 先替换较长的值，避免其中包含的较短密钥先被替换
+    secrets.sort_by_key(|value| std::cmp::Reverse(value.len()));
+    secrets
+        .iter()
+        .fold(text.to_string(), |text, secret| text.replace(secret.as_str(), &fingerprint(secret)))
+}
+
+/// 替换校验结果中出现的密钥值
+pub fn redact_validation(mut result: ConfigValidationResult, config: &SeeSeaConfig) -> ConfigValidationResult {
+    for message in result.errors.iter_mut().chain(result.warnings.iter_mut()) {
+        *message = redact_text(message, config);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_and_redact_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_file = dir.path().join("jwt");
+        std::fs::write(&key_file, "jwt-secret-from-file\n").unwrap();
+        // SAFETY: 测试专用的环境变量名，不与其他测试共享
+        unsafe { std::env::set_var("SEESEA_TEST_SECRET_KEY", "server-secret-from-env") };
+
+        let mut config = SeeSeaConfig::default();
+        config.server.secret_key = "env:SEESEA_TEST_SECRET_KEY".to_string();
+        config.api.auth.jwt.secret = format!("file:{}", key_file.display());
+        config.privacy.history.encryption_key = Some("plain-passphrase".to_string());
+        assert_eq!(resolve_secrets(&mut config).unwrap(), 2);
+        assert_eq!(config.server.secret_key, "server-secret-from-env");
+        assert_eq!(config.api.auth.jwt.secret, "jwt-secret-from-file");
+        assert_eq!(config.privacy.history.encryption_key.as_deref(), Some("plain-passphrase"));
+
+        let redacted = redact_config(&config);
+        assert_eq!(redacted.server.secret_key, fingerprint("server-secret-from-env"));
+        assert_eq!(redacted.privacy.history.encryption_key, Some(fingerprint("plain-passphrase")));
+        assert_eq!(
+            redact_text("key is server-secret-from-env", &config),
+            format!("key is {}", fingerprint("server-secret-from-env"))
+        );
+
+        config.server.secret_key = "env:SEESEA_TEST_MISSING_SECRET".to_string();
+        let error = resolve_secrets(&mut config).unwrap_err();
+        assert!(matches!(error, ConfigError::Environment(ref msg) if msg.starts_with("server.secret_key")));
+        config.server.secret_key = "vault:secret/seesea".to_string();
+        assert!(matches!(resolve_secrets(&mut config), Err(ConfigError::Parse(_))));
+    }
+}
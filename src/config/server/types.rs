@@ -45,6 +45,15 @@ pub struct ServerConfig {
     pub max_request_size: usize,
     /// 是否启用压缩
     pub enable_compression: bool,
+    /// Unix 域套接字路径（设置后在该路径监听，替代 `bind_address`/`port`，仅 Unix）
+    #[serde(default)]
+    pub unix_socket: Option<PathBuf>,
+    /// Unix 域套接字文件权限（如 `0o660`，未设置时沿用 umask）
+    #[serde(default)]
+    pub unix_socket_mode: Option<u32>,
+    /// 使用 systemd 套接字激活传入的已绑定监听（`LISTEN_FDS` 中的第一个描述符，仅 Unix）
+    #[serde(default)]
+    pub socket_activation: bool,
 }
 
 /// TLS 配置
@@ -77,6 +86,9 @@ impl Default for ServerConfig {
             request_timeout: 30,
             max_request_size: 10 * 1024 * 1024, // 10MB
             enable_compression: true,
+            unix_socket: None,
+            unix_socket_mode: None,
+            socket_activation: false,
         }
     }
 }
@@ -112,6 +124,17 @@ impl ServerConfig {
             }
         }
 
+        // 检查监听方式
+        if self.unix_socket.is_some() && self.socket_activation {
+            result.add_error("unix_socket 与 socket_activation 不能同时启用".to_string());
+        }
+        if !cfg!(unix) && (self.unix_socket.is_some() || self.socket_activation) {
+            result.add_error("Unix 域套接字与 systemd 套接字激活仅在 Unix 平台可用".to_string());
+        }
+        if self.unix_socket_mode.is_some_and(|mode| mode > 0o777) {
+            result.add_error("unix_socket_mode 必须是有效的文件权限（0o000-0o777）".to_string());
+        }
+
         // 检查请求超时
         if self.request_timeout == 0 {
             result.add_error("请求超时时间必须大于 0".to_string());
@@ -47,6 +47,20 @@ impl std::fmt::Display for Environment {
     }
 }
 
+impl std::str::FromStr for Environment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "development" | "dev" => Ok(Environment::Development),
+            "testing" | "test" => Ok(Environment::Testing),
+            "staging" => Ok(Environment::Staging),
+            "production" | "prod" => Ok(Environment::Production),
+            other => Err(format!("未知的运行环境: {}", other)),
+        }
+    }
+}
+
 /// 应用元数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApplicationMeta {
@@ -314,6 +314,42 @@ impl ConfigValidator {
         if !config.api.version.starts_with("v") {
             result.add_warning("API 版本建议以 'v' 开头，如 'v1'".to_string());
         }
+
+        // 搜索归档
+        if config.archive.enabled {
+            if config.archive.path.trim().is_empty() {
+                result.add_error("启用搜索归档时必须指定 archive.path".to_string());
+            }
+            if config.archive.format == crate::config::ArchiveFormat::Sqlite && !cfg!(feature = "archive-sqlite") {
+                result.add_error("SQLite 归档需要启用 archive-sqlite 特性编译".to_string());
+            }
+        }
+
+        // 礼貌访问
+        let politeness = &config.politeness;
+        if politeness.enabled && politeness.respect_crawl_delay && politeness.robots_user_agent.trim().is_empty() {
+            result.add_error("遵守 Crawl-delay 时必须指定 politeness.robots_user_agent".to_string());
+        }
+
+        // 热榜
+        for error in config.hot.validate().errors {
+            result.add_error(error);
+        }
+
+        // Webhook
+        let webhooks = &config.integrations.webhooks;
+        if webhooks.enabled {
+            if webhooks.endpoints.is_empty() {
+                result.add_warning("已启用 Webhook 但未配置任何端点".to_string());
+            }
+            for endpoint in &webhooks.endpoints {
+                if !endpoint.url.starts_with("http://") && !endpoint.url.starts_with("https://") {
+                    result.add_error(format!("Webhook 端点 URL 无效: {}", endpoint.url));
+                } else if endpoint.secret.is_none() {
+                    result.add_warning(format!("Webhook 端点 {} 未配置签名密钥", endpoint.url));
+                }
+            }
+        }
     }
 
     /// 验证自定义规则
@@ -393,17 +429,26 @@ impl ConfigValidator {
     }
 
     /// 生成配置报告
+    ///
+    /// 报告中出现的密钥值会被替换（见 [`redact_text`](crate::config::redact_text)）
     pub fn generate_report(&self, config: &SeeSeaConfig) -> ConfigReport {
         let validation_result = self.validate(config);
+        let redact = |messages: Vec<String>| -> Vec<String> {
+            messages
+                .iter()
+                .map(|message| crate::config::redact_text(message, config))
+                .collect()
+        };
 
         ConfigReport {
             timestamp: chrono::Utc::now(),
             environment: format!("{:?}", config.general.environment),
             is_valid: validation_result.is_valid,
-            errors: validation_result.errors,
-            warnings: validation_result.warnings,
-            recommendations: self.generate_recommendations(config),
+            errors: redact(validation_result.errors),
+            warnings: redact(validation_result.warnings),
+            recommendations: redact(self.generate_recommendations(config)),
             summary: self.generate_summary(config),
+            config: crate::config::Redact(config).to_value(),
         }
     }
 
@@ -577,6 +622,9 @@ pub struct ConfigReport {
     pub recommendations: Vec<String>,
     /// 摘要
     pub summary: ConfigSummary,
+    /// 生效的配置（密钥、密码与代理凭据已替换为指纹）
+    #[serde(default)]
+    pub config: serde_json::Value,
 }
 
 /// 配置摘要
@@ -0,0 +1,38 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 内置网页界面配置类型定义
+
+use serde::{Deserialize, Serialize};
+
+/// 内置网页界面配置
+///
+/// 启用后 API 服务器在 `/` 提供内置的搜索页面，单个二进制即可作为私有搜索站点使用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 站点名称（显示在页面标题与页首）
+    pub title: String,
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            title: "SeeSea".to_string(),
+        }
+    }
+}
@@ -20,67 +20,134 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, ItemFn, Attribute};
+use syn::{parse_macro_input, Attribute, Ident, ItemFn, ReturnType};
 
 /// transactional 装饰器宏
-/// 
+///
 /// 用于标记事务函数，自动处理事务的开始、提交和回滚
-/// 
+///
+/// 函数必须返回 `Result<T, E>`，且 `E: From<TransactionError>`。函数体返回
+/// `Ok` 时提交事务并原样返回值，返回 `Err` 时回滚事务。支持 `async fn`，
+/// 函数体在事务开始后被 await，完成后再提交或回滚；异步函数在
+/// `TransactionManager::scope` 中运行，并发任务的事务互不干扰
+///
+/// # 参数
+///
+/// * `nested` - 以嵌套事务运行：已有活跃事务时开始一层嵌套事务，
+///   失败时只回滚该层添加的调用，不影响父事务
+///
 /// # 示例
-/// 
+///
 /// ```rust,ignore
-/// use transaction::transactional;
-/// 
+/// use transaction::{transactional, TransactionError};
+///
 /// #[transactional]
-/// fn my_transaction() {
+/// fn my_transaction() -> Result<u32, TransactionError> {
 ///     // 函数体会被自动包装在事务中
+///     Ok(42)
+/// }
+///
+/// #[transactional(nested)]
+/// async fn my_nested_transaction() -> Result<(), TransactionError> {
+///     // 在调用方的事务中作为嵌套事务运行
+///     Ok(())
 /// }
 /// ```
 #[proc_macro_attribute]
 pub fn transactional(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as ItemFn);
-    
-    // 检查是否有参数（暂时不使用）
-    let _args = attr.to_string();
-    
-    let _fn_name = &input.sig.ident;
+
+    // 解析参数：空或 `nested`
+    let nested = if attr.is_empty() {
+        false
+    } else {
+        let arg = parse_macro_input!(attr as Ident);
+        if arg != "nested" {
+            return syn::Error::new(arg.span(), "expected `nested`")
+                .to_compile_error()
+                .into();
+        }
+        true
+    };
+
     let fn_vis = &input.vis;
     let fn_sig = &input.sig;
     let fn_block = &input.block;
     let fn_attrs: Vec<&Attribute> = input.attrs.iter().collect();
-    
+
+    // 保留原返回类型，事务错误通过 `?` 转换
+    let ret_ty = match &fn_sig.output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => {
+            return syn::Error::new_spanned(
+                &fn_sig.ident,
+                "#[transactional] functions must return a Result",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let (begin, commit, rollback) = if nested {
+        (
+            quote! { begin_nested_transaction },
+            quote! { commit_nested_transaction },
+            quote! { rollback_nested_transaction },
+        )
+    } else {
+        (
+            quote! { begin_transaction },
+            quote! { commit_transaction },
+            quote! { rollback_transaction },
+        )
+    };
+
+    // 执行原函数体，函数体中的 return 只退出函数体本身
+    let is_async = fn_sig.asyncness.is_some();
+    let body = if is_async {
+        quote! { async move #fn_block.await }
+    } else {
+        quote! { (move || -> #ret_ty #fn_block)() }
+    };
+
+    let transaction = quote! {
+        // 获取事务管理器
+        let __tm = ::transaction::TransactionManager::new();
+
+        // 开始事务
+        __tm.#begin()?;
+
+        let __result: #ret_ty = #body;
+
+        // 根据结果提交或回滚
+        match __result {
+            Ok(__value) => {
+                __tm.#commit()?;
+                Ok(__value)
+            }
+            Err(e) => {
+                let _ = __tm.#rollback();
+                Err(e)
+            }
+        }
+    };
+
+    // 异步函数的事务保存在任务本地的上下文中，避免与其他任务的事务交错
+    let wrapped = if is_async {
+        quote! {
+            ::transaction::TransactionManager::scope(async move { #transaction }).await
+        }
+    } else {
+        transaction
+    };
+
     // 生成包装后的函数
     let expanded = quote! {
         #(#fn_attrs)*
         #fn_vis #fn_sig {
-            use transaction::{TransactionManager, TransactionError};
-            
-            // 获取事务管理器
-            let __tm = TransactionManager::new();
-            
-            // 开始事务
-            __tm.begin_transaction()?;
-            
-            // 执行原函数体
-            let __result: Result<_, TransactionError> = (|| {
-                #fn_block
-                Ok(())
-            })();
-            
-            // 根据结果提交或回滚
-            match __result {
-                Ok(_) => {
-                    __tm.commit_transaction()?;
-                    Ok(())
-                }
-                Err(e) => {
-                    let _ = __tm.rollback_transaction();
-                    Err(e)
-                }
-            }
+            #wrapped
         }
     };
-    
+
     TokenStream::from(expanded)
 }
-
@@ -75,6 +75,10 @@ pub enum TransactionError {
     /// 其他错误
     #[error("其他错误: {0}")]
     Other(String),
+
+    /// 保存点未找到
+    #[error("保存点未找到: {0}")]
+    SavepointNotFound(String),
 }
 
 #[cfg(test)]
@@ -14,19 +14,30 @@
 
 //! # 事务管理器模块
 //!
-//! 实现事务管理器单例，管理事务的生命周期
+//! 实现事务管理器单例，管理事务的生命周期。
+//! 在 [`TransactionManager::scope`] 中运行的异步任务各自持有活跃事务，
+//! 其余调用共享全局的活跃事务
 
 use crate::error::TransactionError;
 use crate::transaction::{Callable, Transaction};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex, LazyLock};
+use std::future::Future;
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// 活跃事务槽位
+type TransactionSlot = Arc<Mutex<Option<Transaction>>>;
+
+tokio::task_local! {
+    /// 当前任务的活跃事务槽位，由 [`TransactionManager::scope`] 设置
+    static TASK_TRANSACTION: TransactionSlot;
+}
 
 /// 事务管理器
 ///
 /// 单例模式，管理所有事务的生命周期
 pub struct TransactionManager {
-    /// 当前活跃事务
-    current_transaction: Arc<Mutex<Option<Transaction>>>,
+    /// 当前活跃事务（任务本地事务之外的调用共享）
+    current_transaction: TransactionSlot,
     /// 事务历史记录
     transaction_history: Arc<Mutex<Vec<Transaction>>>,
     /// 事务ID映射
@@ -56,13 +67,57 @@ impl TransactionManager {
         INSTANCE.clone()
     }
 
+    /// 在独立的事务上下文中运行 future
+    ///
+    /// 事务状态保存在 tokio 任务本地存储中，并发运行的 future 各自的事务与保存点互不干扰；
+    /// 已处于事务上下文中时沿用外层上下文，嵌套调用的 `#[transactional]` 函数看到外层的活跃事务：
+    /// 标记为 `#[transactional(nested)]` 的函数作为嵌套事务运行，未标记 `nested` 的函数开始事务时
+    /// 返回 [`TransactionError::AlreadyActive`]。异步的 `#[transactional]` 函数自动在该上下文中运行
+    ///
+    /// # 参数
+    ///
+    /// * `future` - 要运行的 future
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use transaction::TransactionManager;
+    ///
+    /// # tokio_test::block_on(async {
+    /// TransactionManager::scope(async {
+    ///     let tm = TransactionManager::new();
+    ///     tm.begin_transaction().unwrap();
+    ///     tm.commit_transaction().unwrap();
+    /// })
+    /// .await;
+    /// # });
+    /// ```
+    pub async fn scope<F: Future>(future: F) -> F::Output {
+        if TASK_TRANSACTION.try_with(|_| ()).is_ok() {
+            future.await
+        } else {
+            TASK_TRANSACTION
+                .scope(Arc::new(Mutex::new(None)), future)
+                .await
+        }
+    }
+
+    /// 当前上下文的活跃事务槽位
+    ///
+    /// 在 [`scope`](Self::scope) 中运行时为任务本地槽位，否则为全局槽位
+    fn current_slot(&self) -> TransactionSlot {
+        TASK_TRANSACTION
+            .try_with(Arc::clone)
+            .unwrap_or_else(|_| Arc::clone(&self.current_transaction))
+    }
+
     /// 检查是否有活跃的事务
     ///
     /// # 返回值
     ///
     /// 如果当前有活跃事务返回 true，否则返回 false
     pub fn is_transaction_active(&self) -> bool {
-        self.current_transaction.lock().unwrap().is_some()
+        self.current_slot().lock().unwrap().is_some()
     }
 
     /// 添加调用到当前事务
@@ -75,7 +130,8 @@ impl TransactionManager {
     ///
     /// 如果没有活跃的事务，返回 NoActiveTransaction 错误
     pub fn add_call(&self, callable: Arc<dyn Callable>) -> Result<(), TransactionError> {
-        let mut current = self.current_transaction.lock().unwrap();
+        let slot = self.current_slot();
+        let mut current = slot.lock().unwrap();
         match current.as_mut() {
             Some(tx) => {
                 tx.add_call(callable);
@@ -100,7 +156,8 @@ impl TransactionManager {
     /// tm.begin_transaction().unwrap();
     /// ```
     pub fn begin_transaction(&self) -> Result<(), TransactionError> {
-        let mut current = self.current_transaction.lock().unwrap();
+        let slot = self.current_slot();
+        let mut current = slot.lock().unwrap();
         if current.is_some() {
             return Err(TransactionError::AlreadyActive);
         }
@@ -127,8 +184,11 @@ impl TransactionManager {
     /// tm.commit_transaction().unwrap();
     /// ```
     pub fn commit_transaction(&self) -> Result<(), TransactionError> {
-        let mut current = self.current_transaction.lock().unwrap();
-        let tx = current.take().ok_or(TransactionError::NoActiveTransaction)?;
+        let slot = self.current_slot();
+        let mut current = slot.lock().unwrap();
+        let tx = current
+            .take()
+            .ok_or(TransactionError::NoActiveTransaction)?;
 
         // 执行事务
         tx.execute()
@@ -137,10 +197,7 @@ impl TransactionManager {
         // 添加到历史记录
         let tx_id = tx.transaction_id().to_string();
         self.transaction_history.lock().unwrap().push(tx.clone());
-        self.transaction_id_map
-            .lock()
-            .unwrap()
-            .insert(tx_id, tx);
+        self.transaction_id_map.lock().unwrap().insert(tx_id, tx);
 
         Ok(())
     }
@@ -164,8 +221,11 @@ impl TransactionManager {
     /// tm.rollback_transaction().unwrap();
     /// ```
     pub fn rollback_transaction(&self) -> Result<(), TransactionError> {
-        let mut current = self.current_transaction.lock().unwrap();
-        let _tx = current.take().ok_or(TransactionError::NoActiveTransaction)?;
+        let slot = self.current_slot();
+        let mut current = slot.lock().unwrap();
+        let _tx = current
+            .take()
+            .ok_or(TransactionError::NoActiveTransaction)?;
 
         // 回滚只是丢弃事务，不执行任何操作
         // 因为操作从未执行过，所以不需要撤销
@@ -173,6 +233,151 @@ impl TransactionManager {
         Ok(())
     }
 
+    /// 开始嵌套事务
+    ///
+    /// 没有活跃事务时开始最外层事务，否则在当前事务中开始一层嵌套事务。
+    /// 嵌套事务回滚只丢弃其中添加的调用，不影响父事务
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use transaction::TransactionManager;
+    ///
+    /// let tm = TransactionManager::new();
+    /// tm.begin_nested_transaction().unwrap();
+    /// tm.begin_nested_transaction().unwrap();
+    /// // ... 添加调用 ...
+    /// tm.rollback_nested_transaction().unwrap();
+    /// tm.commit_nested_transaction().unwrap();
+    /// ```
+    pub fn begin_nested_transaction(&self) -> Result<(), TransactionError> {
+        let slot = self.current_slot();
+        let mut current = slot.lock().unwrap();
+        match current.as_mut() {
+            Some(tx) => tx.begin_nested(),
+            None => *current = Some(Transaction::new()),
+        }
+        Ok(())
+    }
+
+    /// 提交最内层嵌套事务
+    ///
+    /// 嵌套事务的调用并入父事务；没有嵌套事务时提交最外层事务
+    ///
+    /// # 错误
+    ///
+    /// 如果没有活跃的事务或执行失败，返回相应错误
+    pub fn commit_nested_transaction(&self) -> Result<(), TransactionError> {
+        {
+            let slot = self.current_slot();
+            let mut current = slot.lock().unwrap();
+            let tx = current
+                .as_mut()
+                .ok_or(TransactionError::NoActiveTransaction)?;
+            if tx.commit_nested() {
+                return Ok(());
+            }
+        }
+        self.commit_transaction()
+    }
+
+    /// 回滚最内层嵌套事务
+    ///
+    /// 只丢弃嵌套事务中添加的调用；没有嵌套事务时回滚最外层事务
+    ///
+    /// # 错误
+    ///
+    /// 如果没有活跃的事务，返回相应错误
+    pub fn rollback_nested_transaction(&self) -> Result<(), TransactionError> {
+        let slot = self.current_slot();
+        let mut current = slot.lock().unwrap();
+        let tx = current
+            .as_mut()
+            .ok_or(TransactionError::NoActiveTransaction)?;
+        if !tx.rollback_nested() {
+            *current = None;
+        }
+        Ok(())
+    }
+
+    /// 获取当前事务的嵌套深度
+    ///
+    /// # 返回值
+    ///
+    /// 没有活跃事务时返回 0，最外层事务为 1
+    pub fn transaction_depth(&self) -> usize {
+        self.current_slot()
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(0, Transaction::depth)
+    }
+
+    /// 在当前事务中创建保存点
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 保存点名称
+    ///
+    /// # 错误
+    ///
+    /// 如果没有活跃的事务，返回 NoActiveTransaction 错误
+    pub fn savepoint(&self, name: &str) -> Result<(), TransactionError> {
+        let slot = self.current_slot();
+        let mut current = slot.lock().unwrap();
+        let tx = current
+            .as_mut()
+            .ok_or(TransactionError::NoActiveTransaction)?;
+        tx.savepoint(name);
+        Ok(())
+    }
+
+    /// 回滚到保存点
+    ///
+    /// 丢弃保存点之后添加的调用，保存点本身保留
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 保存点名称
+    ///
+    /// # 错误
+    ///
+    /// 如果没有活跃的事务或保存点不存在，返回相应错误
+    pub fn rollback_to_savepoint(&self, name: &str) -> Result<(), TransactionError> {
+        let slot = self.current_slot();
+        let mut current = slot.lock().unwrap();
+        let tx = current
+            .as_mut()
+            .ok_or(TransactionError::NoActiveTransaction)?;
+        if !tx.rollback_to_savepoint(name) {
+            return Err(TransactionError::SavepointNotFound(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// 释放保存点
+    ///
+    /// 保留保存点之后添加的调用
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 保存点名称
+    ///
+    /// # 错误
+    ///
+    /// 如果没有活跃的事务或保存点不存在，返回相应错误
+    pub fn release_savepoint(&self, name: &str) -> Result<(), TransactionError> {
+        let slot = self.current_slot();
+        let mut current = slot.lock().unwrap();
+        let tx = current
+            .as_mut()
+            .ok_or(TransactionError::NoActiveTransaction)?;
+        if !tx.release_savepoint(name) {
+            return Err(TransactionError::SavepointNotFound(name.to_string()));
+        }
+        Ok(())
+    }
+
     /// 撤销指定的历史事务
     ///
     /// # 参数
@@ -209,7 +414,7 @@ impl TransactionManager {
     ///
     /// 如果有活跃事务返回其克隆，否则返回 None
     pub fn current_transaction(&self) -> Option<Transaction> {
-        self.current_transaction.lock().unwrap().clone()
+        self.current_slot().lock().unwrap().clone()
     }
 
     /// 根据ID获取事务
@@ -421,7 +626,11 @@ mod tests {
         }
 
         tm.begin_transaction().unwrap();
-        let tx_id = tm.current_transaction().unwrap().transaction_id().to_string();
+        let tx_id = tm
+            .current_transaction()
+            .unwrap()
+            .transaction_id()
+            .to_string();
 
         let callable = Arc::new(TestCallable::new("test"));
         tm.add_call(callable).unwrap();
@@ -431,4 +640,60 @@ mod tests {
         assert!(tx.is_some());
         assert_eq!(tx.unwrap().transaction_id(), tx_id);
     }
+
+    #[test]
+    fn test_nested_rollback_keeps_parent() {
+        let _lock = TEST_LOCK.lock().unwrap();
+        let tm = TransactionManager::new();
+
+        // 如果有活跃事务，先回滚
+        if tm.is_transaction_active() {
+            let _ = tm.rollback_transaction();
+        }
+
+        let parent = Arc::new(TestCallable::new("parent"));
+        let child = Arc::new(TestCallable::new("child"));
+
+        tm.begin_nested_transaction().unwrap();
+        tm.add_call(parent.clone()).unwrap();
+        tm.begin_nested_transaction().unwrap();
+        assert_eq!(tm.transaction_depth(), 2);
+        tm.add_call(child.clone()).unwrap();
+        tm.rollback_nested_transaction().unwrap();
+
+        assert!(tm.is_transaction_active());
+        assert_eq!(tm.transaction_depth(), 1);
+        tm.commit_nested_transaction().unwrap();
+
+        assert!(parent.is_executed());
+        assert!(!child.is_executed());
+        assert_eq!(tm.transaction_depth(), 0);
+    }
+
+    #[test]
+    fn test_savepoint_not_found() {
+        let _lock = TEST_LOCK.lock().unwrap();
+        let tm = TransactionManager::new();
+
+        // 如果有活跃事务，先回滚
+        if tm.is_transaction_active() {
+            let _ = tm.rollback_transaction();
+        }
+
+        assert!(matches!(
+            tm.savepoint("sp"),
+            Err(TransactionError::NoActiveTransaction)
+        ));
+
+        tm.begin_transaction().unwrap();
+        tm.savepoint("sp").unwrap();
+        tm.release_savepoint("sp").unwrap();
+        assert!(matches!(
+            tm.rollback_to_savepoint("sp"),
+            Err(TransactionError::SavepointNotFound(_))
+        ));
+
+        // 清理
+        let _ = tm.rollback_transaction();
+    }
 }
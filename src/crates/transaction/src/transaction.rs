@@ -69,6 +69,17 @@ impl Call {
     }
 }
 
+/// 保存点
+///
+/// 记录创建时事务中的调用数量，回滚到保存点时丢弃之后添加的调用
+#[derive(Clone, Debug)]
+struct Savepoint {
+    /// 保存点名称（嵌套事务为 None）
+    name: Option<String>,
+    /// 创建时的调用数量
+    call_count: usize,
+}
+
 /// 事务结构
 ///
 /// 表示一个完整的事务，包含事务ID、线程ID和所有调用记录
@@ -80,6 +91,8 @@ pub struct Transaction {
     thread_id: u64,
     /// 函数调用列表
     calls: Vec<Call>,
+    /// 保存点栈（包括嵌套事务）
+    savepoints: Vec<Savepoint>,
 }
 
 impl Transaction {
@@ -98,6 +111,7 @@ impl Transaction {
             transaction_id: Self::generate_transaction_id(),
             thread_id: Self::get_thread_id(),
             calls: Vec::new(),
+            savepoints: Vec::new(),
         }
     }
 
@@ -189,6 +203,121 @@ impl Transaction {
     pub fn call_names(&self) -> Vec<&str> {
         self.calls.iter().map(|call| call.name()).collect()
     }
+
+    /// 创建命名保存点
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 保存点名称，重名时回滚和释放作用于最近创建的一个
+    pub fn savepoint(&mut self, name: &str) {
+        self.savepoints.push(Savepoint {
+            name: Some(name.to_string()),
+            call_count: self.calls.len(),
+        });
+    }
+
+    /// 回滚到命名保存点
+    ///
+    /// 丢弃保存点之后添加的调用和创建的保存点，保存点本身保留，可以再次回滚
+    ///
+    /// # 返回值
+    ///
+    /// 保存点不存在时返回 false
+    pub fn rollback_to_savepoint(&mut self, name: &str) -> bool {
+        let Some(index) = self.find_savepoint(name) else {
+            return false;
+        };
+        self.calls.truncate(self.savepoints[index].call_count);
+        self.savepoints.truncate(index + 1);
+        true
+    }
+
+    /// 释放命名保存点
+    ///
+    /// 保留保存点之后的调用，同时释放之后创建的保存点
+    ///
+    /// # 返回值
+    ///
+    /// 保存点不存在时返回 false
+    pub fn release_savepoint(&mut self, name: &str) -> bool {
+        let Some(index) = self.find_savepoint(name) else {
+            return false;
+        };
+        self.savepoints.truncate(index);
+        true
+    }
+
+    /// 开始嵌套事务
+    pub fn begin_nested(&mut self) {
+        self.savepoints.push(Savepoint {
+            name: None,
+            call_count: self.calls.len(),
+        });
+    }
+
+    /// 提交最内层嵌套事务
+    ///
+    /// 其中的调用并入父事务，随最外层事务一起执行
+    ///
+    /// # 返回值
+    ///
+    /// 没有嵌套事务时返回 false
+    pub fn commit_nested(&mut self) -> bool {
+        let Some(index) = self.innermost_nested() else {
+            return false;
+        };
+        self.savepoints.truncate(index);
+        true
+    }
+
+    /// 回滚最内层嵌套事务
+    ///
+    /// 只丢弃嵌套事务中添加的调用，父事务不受影响
+    ///
+    /// # 返回值
+    ///
+    /// 没有嵌套事务时返回 false
+    pub fn rollback_nested(&mut self) -> bool {
+        let Some(index) = self.innermost_nested() else {
+            return false;
+        };
+        self.calls.truncate(self.savepoints[index].call_count);
+        self.savepoints.truncate(index);
+        true
+    }
+
+    /// 获取嵌套深度
+    ///
+    /// # 返回值
+    ///
+    /// 最外层事务为 1，每开始一层嵌套事务加 1
+    pub fn depth(&self) -> usize {
+        1 + self.savepoints.iter().filter(|sp| sp.name.is_none()).count()
+    }
+
+    /// 获取所有命名保存点的名称
+    pub fn savepoint_names(&self) -> Vec<&str> {
+        self.savepoints
+            .iter()
+            .filter_map(|sp| sp.name.as_deref())
+            .collect()
+    }
+
+    /// 查找最近创建的同名保存点
+    ///
+    /// 查找范围限定在最内层嵌套事务内，避免回滚越过嵌套边界
+    fn find_savepoint(&self, name: &str) -> Option<usize> {
+        let start = self.innermost_nested().map_or(0, |index| index + 1);
+        self.savepoints[start..]
+            .iter()
+            .rposition(|sp| sp.name.as_deref() == Some(name))
+            .map(|index| start + index)
+    }
+
+    /// 查找最内层嵌套事务
+    fn innermost_nested(&self) -> Option<usize> {
+        self.savepoints.iter().rposition(|sp| sp.name.is_none())
+    }
 }
 
 impl Default for Transaction {
@@ -337,6 +466,60 @@ mod tests {
         assert!(display.contains("test_func"));
     }
 
+    #[test]
+    fn test_savepoint_rollback_and_release() {
+        let mut tx = Transaction::new();
+        tx.add_call(Arc::new(TestCallable::new("func1")));
+        tx.savepoint("sp1");
+        tx.add_call(Arc::new(TestCallable::new("func2")));
+        tx.savepoint("sp2");
+        tx.add_call(Arc::new(TestCallable::new("func3")));
+
+        assert!(tx.rollback_to_savepoint("sp1"));
+        assert_eq!(tx.call_names(), vec!["func1"]);
+        assert_eq!(tx.savepoint_names(), vec!["sp1"]);
+
+        // 保存点回滚后仍然可用
+        tx.add_call(Arc::new(TestCallable::new("func4")));
+        assert!(tx.rollback_to_savepoint("sp1"));
+        assert_eq!(tx.call_count(), 1);
+
+        assert!(tx.release_savepoint("sp1"));
+        assert!(!tx.rollback_to_savepoint("sp1"));
+        assert!(!tx.release_savepoint("missing"));
+    }
+
+    #[test]
+    fn test_nested_rollback_keeps_parent() {
+        let mut tx = Transaction::new();
+        tx.add_call(Arc::new(TestCallable::new("parent")));
+
+        tx.begin_nested();
+        assert_eq!(tx.depth(), 2);
+        tx.add_call(Arc::new(TestCallable::new("child")));
+        tx.savepoint("inner");
+        assert!(tx.rollback_nested());
+        assert_eq!(tx.call_names(), vec!["parent"]);
+        assert!(tx.savepoint_names().is_empty());
+
+        tx.begin_nested();
+        tx.add_call(Arc::new(TestCallable::new("child")));
+        assert!(tx.commit_nested());
+        assert_eq!(tx.call_names(), vec!["parent", "child"]);
+        assert_eq!(tx.depth(), 1);
+        assert!(!tx.commit_nested());
+    }
+
+    #[test]
+    fn test_savepoint_scoped_to_nested_transaction() {
+        let mut tx = Transaction::new();
+        tx.savepoint("outer");
+        tx.begin_nested();
+        assert!(!tx.rollback_to_savepoint("outer"));
+        assert!(tx.commit_nested());
+        assert!(tx.rollback_to_savepoint("outer"));
+    }
+
     #[test]
     fn test_call_names() {
         let mut tx = Transaction::new();
@@ -16,8 +16,8 @@
 //!
 //! 测试事务管理系统的完整功能
 
-use std::sync::{Arc, Mutex, LazyLock};
-use transaction::{Callable, Proxy, TransactionManager};
+use std::sync::{Arc, LazyLock, Mutex};
+use transaction::{transactional, Callable, Proxy, TransactionError, TransactionManager};
 
 // 测试同步锁，确保事务管理器单例的测试按顺序执行
 static TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
@@ -223,7 +223,11 @@ fn test_transaction_undo() {
 
     // 第一个事务
     tm.begin_transaction().unwrap();
-    let tx_id = tm.current_transaction().unwrap().transaction_id().to_string();
+    let tx_id = tm
+        .current_transaction()
+        .unwrap()
+        .transaction_id()
+        .to_string();
 
     tm.add_call(Arc::new(DepositCall::new(account.clone(), 500)))
         .unwrap();
@@ -323,3 +327,296 @@ fn test_transaction_isolation() {
     assert_eq!(account1.balance(), 1500);
     assert_eq!(account2.balance(), 1700);
 }
+
+/// 以嵌套事务存款，`fail` 为 true 时失败
+#[transactional(nested)]
+fn nested_deposit(account: Account, amount: i32, fail: bool) -> Result<(), TransactionError> {
+    TransactionManager::new().add_call(Arc::new(DepositCall::new(account, amount)))?;
+    if fail {
+        return Err(TransactionError::Other("存款被拒绝".to_string()));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_nested_transactional_rollback_keeps_parent() {
+    let _lock = TEST_LOCK.lock().unwrap();
+    let tm = TransactionManager::new();
+    tm.clear_history();
+
+    // 清理状态
+    if tm.is_transaction_active() {
+        let _ = tm.rollback_transaction();
+    }
+
+    let account = Account::new(1000);
+
+    tm.begin_transaction().unwrap();
+    tm.add_call(Arc::new(WithdrawCall::new(account.clone(), 100)))
+        .unwrap();
+
+    // 失败的嵌套事务只回滚自身
+    assert!(nested_deposit(account.clone(), 500, true).is_err());
+    nested_deposit(account.clone(), 50, false).unwrap();
+    assert!(tm.is_transaction_active());
+
+    tm.commit_transaction().unwrap();
+
+    // 验证结果 - 1000 - 100 + 50
+    assert_eq!(account.balance(), 950);
+}
+
+#[test]
+fn test_savepoint_rollback() {
+    let _lock = TEST_LOCK.lock().unwrap();
+    let tm = TransactionManager::new();
+    tm.clear_history();
+
+    // 清理状态
+    if tm.is_transaction_active() {
+        let _ = tm.rollback_transaction();
+    }
+
+    let account = Account::new(1000);
+
+    tm.begin_transaction().unwrap();
+    tm.add_call(Arc::new(DepositCall::new(account.clone(), 500)))
+        .unwrap();
+    tm.savepoint("before_withdraw").unwrap();
+    tm.add_call(Arc::new(WithdrawCall::new(account.clone(), 2000)))
+        .unwrap();
+
+    // 回滚到保存点，撤掉透支的取款
+    tm.rollback_to_savepoint("before_withdraw").unwrap();
+    tm.commit_transaction().unwrap();
+
+    assert_eq!(account.balance(), 1500);
+}
+
+/// 存款并返回入账后的余额
+#[transactional]
+fn deposit_and_report(account: Account, amount: i32) -> Result<i32, TransactionError> {
+    TransactionManager::new().add_call(Arc::new(DepositCall::new(account.clone(), amount)))?;
+    Ok(account.balance() + amount)
+}
+
+/// 异步转账
+#[transactional]
+async fn async_transfer(from: Account, to: Account, amount: i32) -> Result<(), TransactionError> {
+    let tm = TransactionManager::new();
+    tm.add_call(Arc::new(WithdrawCall::new(from.clone(), amount)))?;
+    tokio::task::yield_now().await;
+    if from.balance() < amount {
+        return Err(TransactionError::Other("余额不足".to_string()));
+    }
+    tm.add_call(Arc::new(DepositCall::new(to, amount)))?;
+    Ok(())
+}
+
+/// 异步存款，两个任务都进入事务后才决定提交或失败
+#[transactional]
+async fn async_deposit(
+    account: Account,
+    amount: i32,
+    fail: bool,
+    barrier: Arc<tokio::sync::Barrier>,
+) -> Result<(), TransactionError> {
+    TransactionManager::new().add_call(Arc::new(DepositCall::new(account, amount)))?;
+    barrier.wait().await;
+    if fail {
+        return Err(TransactionError::Other("存款被拒绝".to_string()));
+    }
+    Ok(())
+}
+
+/// 异步嵌套存款，两个任务都建立保存点后才决定提交或失败
+#[transactional(nested)]
+async fn async_nested_bonus(
+    account: Account,
+    amount: i32,
+    fail: bool,
+    barrier: Arc<tokio::sync::Barrier>,
+) -> Result<(), TransactionError> {
+    TransactionManager::new().add_call(Arc::new(DepositCall::new(account, amount)))?;
+    barrier.wait().await;
+    if fail {
+        return Err(TransactionError::Other("奖励被拒绝".to_string()));
+    }
+    Ok(())
+}
+
+/// 异步存款并尝试发放奖励，奖励失败只回滚到保存点
+#[transactional]
+async fn async_deposit_with_bonus(
+    account: Account,
+    fail_bonus: bool,
+    barrier: Arc<tokio::sync::Barrier>,
+) -> Result<(), TransactionError> {
+    TransactionManager::new().add_call(Arc::new(DepositCall::new(account.clone(), 100)))?;
+    let _ = async_nested_bonus(account, 10, fail_bonus, barrier).await;
+    Ok(())
+}
+
+/// 在已有事务中调用未标记 `nested` 的异步事务函数
+#[transactional]
+async fn async_deposit_with_flat_bonus(
+    account: Account,
+) -> Result<TransactionError, TransactionError> {
+    TransactionManager::new().add_call(Arc::new(DepositCall::new(account.clone(), 100)))?;
+    let bonus = async_deposit(account, 10, false, Arc::new(tokio::sync::Barrier::new(1))).await;
+    Ok(bonus.unwrap_err())
+}
+
+#[test]
+fn test_transactional_returns_value() {
+    let _lock = TEST_LOCK.lock().unwrap();
+    let tm = TransactionManager::new();
+
+    // 清理状态
+    if tm.is_transaction_active() {
+        let _ = tm.rollback_transaction();
+    }
+
+    let account = Account::new(1000);
+    assert_eq!(deposit_and_report(account.clone(), 200).unwrap(), 1200);
+    assert_eq!(account.balance(), 1200);
+    assert!(!tm.is_transaction_active());
+}
+
+#[test]
+fn test_async_transactional() {
+    let _lock = TEST_LOCK.lock().unwrap();
+    let tm = TransactionManager::new();
+
+    // 清理状态
+    if tm.is_transaction_active() {
+        let _ = tm.rollback_transaction();
+    }
+
+    let from = Account::new(1000);
+    let to = Account::new(0);
+
+    tokio_test::block_on(async_transfer(from.clone(), to.clone(), 300)).unwrap();
+    assert_eq!(from.balance(), 700);
+    assert_eq!(to.balance(), 300);
+
+    // 失败时回滚，两个账户都不变
+    assert!(tokio_test::block_on(async_transfer(from.clone(), to.clone(), 5000)).is_err());
+    assert_eq!(from.balance(), 700);
+    assert_eq!(to.balance(), 300);
+    assert!(!tm.is_transaction_active());
+}
+
+#[test]
+fn test_concurrent_async_transactions_are_isolated() {
+    let _lock = TEST_LOCK.lock().unwrap();
+    let tm = TransactionManager::new();
+
+    // 清理状态
+    if tm.is_transaction_active() {
+        let _ = tm.rollback_transaction();
+    }
+
+    let committed = Account::new(0);
+    let rolled_back = Account::new(0);
+    let barrier = Arc::new(tokio::sync::Barrier::new(2));
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let ok = tokio::spawn(async_deposit(
+            committed.clone(),
+            100,
+            false,
+            barrier.clone(),
+        ));
+        let failed = tokio::spawn(async_deposit(
+            rolled_back.clone(),
+            50,
+            true,
+            barrier.clone(),
+        ));
+        assert!(ok.await.unwrap().is_ok());
+        assert!(failed.await.unwrap().is_err());
+    });
+
+    // 一个任务回滚不影响另一个任务提交
+    assert_eq!(committed.balance(), 100);
+    assert_eq!(rolled_back.balance(), 0);
+    assert!(!tm.is_transaction_active());
+
+    // 同一任务内交错执行的两个事务同样互不干扰
+    let barrier = Arc::new(tokio::sync::Barrier::new(2));
+    let (ok, failed) = tokio_test::block_on(async {
+        tokio::join!(
+            async_deposit(committed.clone(), 10, false, barrier.clone()),
+            async_deposit(rolled_back.clone(), 5, true, barrier.clone()),
+        )
+    });
+    assert!(ok.is_ok());
+    assert!(failed.is_err());
+    assert_eq!(committed.balance(), 110);
+    assert_eq!(rolled_back.balance(), 0);
+}
+
+#[test]
+fn test_concurrent_async_savepoints_are_isolated() {
+    let _lock = TEST_LOCK.lock().unwrap();
+    let tm = TransactionManager::new();
+
+    // 清理状态
+    if tm.is_transaction_active() {
+        let _ = tm.rollback_transaction();
+    }
+
+    let rewarded = Account::new(0);
+    let refused = Account::new(0);
+    let barrier = Arc::new(tokio::sync::Barrier::new(2));
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let ok = tokio::spawn(async_deposit_with_bonus(
+            rewarded.clone(),
+            false,
+            barrier.clone(),
+        ));
+        let failed = tokio::spawn(async_deposit_with_bonus(
+            refused.clone(),
+            true,
+            barrier.clone(),
+        ));
+        assert!(ok.await.unwrap().is_ok());
+        assert!(failed.await.unwrap().is_ok());
+    });
+
+    // 每个任务只回滚自己的保存点
+    assert_eq!(rewarded.balance(), 110);
+    assert_eq!(refused.balance(), 100);
+    assert!(!tm.is_transaction_active());
+}
+
+#[test]
+fn test_async_inner_transactional_without_nested_is_rejected() {
+    let _lock = TEST_LOCK.lock().unwrap();
+    let tm = TransactionManager::new();
+
+    // 清理状态
+    if tm.is_transaction_active() {
+        let _ = tm.rollback_transaction();
+    }
+
+    let account = Account::new(0);
+    let inner_error = tokio_test::block_on(async_deposit_with_flat_bonus(account.clone())).unwrap();
+
+    // 内层函数不会加入外层事务，开始事务时返回 AlreadyActive；外层事务不受影响
+    assert!(matches!(inner_error, TransactionError::AlreadyActive));
+    assert_eq!(account.balance(), 100);
+    assert!(!tm.is_transaction_active());
+}
@@ -0,0 +1,74 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 进程管理器集成（`daemon` 特性）
+//!
+//! 让 systemd、Windows 服务控制管理器（SCM）等进程管理器可靠地监督 SeeSea：
+//! - Linux：通过 sd_notify 协议报告就绪（`READY=1`）、停机（`STOPPING=1`）并发送看门狗心跳（`WATCHDOG=1`）
+//! - Windows：以服务方式运行，响应停止/关机控制并向 SCM 报告运行状态
+//!
+//! 未在对应的进程管理器下运行时，所有通知都是空操作
+
+pub mod systemd;
+#[cfg(windows)]
+pub mod windows;
+
+pub use systemd::watchdog_interval;
+
+use std::time::Duration;
+
+use crate::lifecycle::TaskManager;
+
+/// 报告服务已就绪（监听地址已绑定、可以接受请求）
+pub fn notify_ready() {
+    send(systemd::READY);
+    #[cfg(windows)]
+    windows::report_running();
+}
+
+/// 报告服务正在停机
+pub fn notify_stopping() {
+    send(systemd::STOPPING);
+    #[cfg(windows)]
+    windows::report_stop_pending();
+}
+
+/// 发送看门狗心跳
+pub fn notify_watchdog() {
+    send(systemd::WATCHDOG);
+}
+
+/// 更新进程管理器中显示的状态文本
+pub fn notify_status(status: &str) {
+    send(&format!("STATUS={}", status));
+}
+
+/// 启动看门狗心跳任务
+///
+/// 仅在 systemd 为本进程启用看门狗（`WatchdogSec=`）时启动，心跳间隔为超时时间的一半；
+/// 返回心跳间隔，未启用时返回 `None`
+pub fn spawn_watchdog(lifecycle: &TaskManager) -> Option<Duration> {
+    let interval = watchdog_interval()?;
+    lifecycle.spawn_interval("systemd-watchdog", interval, || async {
+        notify_watchdog();
+    });
+    Some(interval)
+}
+
+/// 发送 sd_notify 状态，失败只记录日志
+fn send(state: &str) {
+    if let Err(e) = systemd::notify(state) {
+        tracing::warn!("发送 systemd 通知失败: {}", e);
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! systemd 通知协议（sd_notify）
+//!
+//! 以 `Type=notify` 运行时 systemd 通过 `NOTIFY_SOCKET` 传入一个 Unix 数据报套接字，
+//! 进程向其发送 `KEY=VALUE` 形式的状态行；`@` 开头的地址表示 Linux 抽象命名空间套接字。
+//! 环境变量不存在时（未在 systemd 下运行、或非 Unix 平台）所有通知都是空操作
+
+use std::ffi::OsStr;
+use std::io;
+use std::time::Duration;
+
+/// 通知套接字环境变量
+pub const NOTIFY_SOCKET_ENV: &str = "NOTIFY_SOCKET";
+
+/// 看门狗超时（微秒）环境变量
+pub const WATCHDOG_USEC_ENV: &str = "WATCHDOG_USEC";
+
+/// 看门狗目标进程环境变量
+pub const WATCHDOG_PID_ENV: &str = "WATCHDOG_PID";
+
+/// 服务已就绪
+pub const READY: &str = "READY=1";
+
+/// 服务正在停机
+pub const STOPPING: &str = "STOPPING=1";
+
+/// 看门狗心跳
+pub const WATCHDOG: &str = "WATCHDOG=1";
+
+/// 向 systemd 发送状态
+///
+/// # Arguments
+///
+/// * `state` - 状态行，多个状态以换行分隔
+///
+/// # Returns
+///
+/// 已发送返回 `true`，未设置 `NOTIFY_SOCKET` 时返回 `false`
+pub fn notify(state: &str) -> io::Result<bool> {
+    let Some(socket) = std::env::var_os(NOTIFY_SOCKET_ENV) else {
+        return Ok(false);
+    };
+    if socket.is_empty() {
+        return Ok(false);
+    }
+    send(&socket, state)?;
+    Ok(true)
+}
+
+/// systemd 要求的看门狗心跳间隔
+///
+/// 取 `WATCHDOG_USEC` 的一半；未设置、无法解析，或 `WATCHDOG_PID` 指向其他进程时返回 `None`
+pub fn watchdog_interval() -> Option<Duration> {
+    if let Some(pid) = std::env::var_os(WATCHDOG_PID_ENV)
+        && pid.to_str().and_then(|pid| pid.parse::<u32>().ok()) != Some(std::process::id())
+    {
+        return None;
+    }
+    let usec = std::env::var(WATCHDOG_USEC_ENV).ok()?.parse::<u64>().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec / 2))
+}
+
+#[cfg(unix)]
+fn send(socket: &OsStr, state: &str) -> io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::net::UnixDatagram;
+
+    let datagram = UnixDatagram::unbound()?;
+    if let Some(name) = socket.as_bytes().strip_prefix(b"@") {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::linux::net::SocketAddrExt;
+            let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+            datagram.send_to_addr(state.as_bytes(), &addr)?;
+            return Ok(());
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = name;
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "抽象命名空间套接字仅在 Linux 上可用"));
+        }
+    }
+    datagram.send_to(state.as_bytes(), socket)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send(_socket: &OsStr, _state: &str) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "systemd 通知仅在 Unix 平台上可用"))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixDatagram;
+
+    #[test]
+    fn test_notify_sends_to_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notify.sock");
+        let receiver = UnixDatagram::bind(&path).unwrap();
+
+        temp_env::with_var(NOTIFY_SOCKET_ENV, Some(&path), || {
+            assert!(notify(READY).unwrap());
+        });
+        let mut buf = [0u8; 64];
+        let len = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], READY.as_bytes());
+
+        temp_env::with_var_unset(NOTIFY_SOCKET_ENV, || {
+            assert!(!notify(READY).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_watchdog_interval() {
+        let pid = std::process::id().to_string();
+        temp_env::with_vars([(WATCHDOG_USEC_ENV, Some("30000000")), (WATCHDOG_PID_ENV, Some(pid.as_str()))], || {
+            assert_eq!(watchdog_interval(), Some(Duration::from_secs(15)));
+        });
+        temp_env::with_vars([(WATCHDOG_USEC_ENV, Some("30000000")), (WATCHDOG_PID_ENV, Some("0"))], || {
+            assert_eq!(watchdog_interval(), None);
+        });
+        temp_env::with_vars([(WATCHDOG_USEC_ENV, None::<&str>), (WATCHDOG_PID_ENV, None)], || {
+            assert_eq!(watchdog_interval(), None);
+        });
+    }
+}
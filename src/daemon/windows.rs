@@ -0,0 +1,133 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Windows 服务集成
+//!
+//! [`run_service`] 把当前进程交给服务控制管理器（SCM）调度：SCM 在后台线程调用服务入口，
+//! 停止/关机控制取消传给入口的令牌，入口返回后报告服务已停止。
+//! 服务需事先注册，例如 `sc.exe create SeeSea binPath= "C:\seesea\SeeSea.exe --service --config C:\seesea\config"`
+
+use std::ffi::OsString;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+use windows_service::service::{
+    ServiceControl, ServiceControlAccept, ServiceExitCode, ServiceState, ServiceStatus, ServiceType,
+};
+use windows_service::service_control_handler::{self, ServiceControlHandlerResult, ServiceStatusHandle};
+use windows_service::{define_windows_service, service_dispatcher};
+
+/// 服务名称
+pub const SERVICE_NAME: &str = "SeeSea";
+
+/// 启动与停机期间提示 SCM 的等待时间
+const PENDING_WAIT_HINT: Duration = Duration::from_secs(30);
+
+/// 服务入口：收到停止控制时令牌被取消，返回错误时以服务自定义退出码 1 停止
+type ServiceMain = Box<dyn Fn(CancellationToken) -> Result<(), String> + Send + Sync>;
+
+/// 由 [`run_service`] 设置的服务入口
+static SERVICE_MAIN: OnceLock<ServiceMain> = OnceLock::new();
+
+/// 已注册的服务状态句柄
+static STATUS_HANDLE: OnceLock<ServiceStatusHandle> = OnceLock::new();
+
+define_windows_service!(ffi_service_main, service_main);
+
+/// 以 Windows 服务方式运行，阻塞直到服务停止
+///
+/// # Arguments
+///
+/// * `main` - 服务入口，应在令牌取消后完成停机并返回
+///
+/// # Returns
+///
+/// 进程不是由 SCM 启动时返回错误
+pub fn run_service<F>(main: F) -> windows_service::Result<()>
+where
+    F: Fn(CancellationToken) -> Result<(), String> + Send + Sync + 'static,
+{
+    let _ = SERVICE_MAIN.set(Box::new(main));
+    service_dispatcher::start(SERVICE_NAME, ffi_service_main)
+}
+
+/// 报告服务已进入运行状态（未以服务方式运行时为空操作）
+pub fn report_running() {
+    report(ServiceState::Running, ServiceExitCode::NO_ERROR);
+}
+
+/// 报告服务正在停止（未以服务方式运行时为空操作）
+pub fn report_stop_pending() {
+    report(ServiceState::StopPending, ServiceExitCode::NO_ERROR);
+}
+
+fn service_main(_arguments: Vec<OsString>) {
+    let Some(main) = SERVICE_MAIN.get() else {
+        return;
+    };
+
+    let token = CancellationToken::new();
+    let stop = token.clone();
+    let handler = move |control| match control {
+        ServiceControl::Stop | ServiceControl::Shutdown => {
+            stop.cancel();
+            ServiceControlHandlerResult::NoError
+        }
+        ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+        _ => ServiceControlHandlerResult::NotImplemented,
+    };
+    let handle = match service_control_handler::register(SERVICE_NAME, handler) {
+        Ok(handle) => handle,
+        Err(e) => {
+            tracing::error!("注册 Windows 服务控制处理器失败: {}", e);
+            return;
+        }
+    };
+    let _ = STATUS_HANDLE.set(handle);
+    report(ServiceState::StartPending, ServiceExitCode::NO_ERROR);
+
+    let exit_code = match main(token) {
+        Ok(()) => ServiceExitCode::NO_ERROR,
+        Err(e) => {
+            tracing::error!("服务异常退出: {}", e);
+            ServiceExitCode::ServiceSpecific(1)
+        }
+    };
+    report(ServiceState::Stopped, exit_code);
+}
+
+/// 向 SCM 报告服务状态
+fn report(state: ServiceState, exit_code: ServiceExitCode) {
+    let Some(handle) = STATUS_HANDLE.get() else {
+        return;
+    };
+    let (controls_accepted, wait_hint) = match state {
+        ServiceState::Running => (ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN, Duration::ZERO),
+        ServiceState::StartPending | ServiceState::StopPending => (ServiceControlAccept::empty(), PENDING_WAIT_HINT),
+        _ => (ServiceControlAccept::empty(), Duration::ZERO),
+    };
+    let status = ServiceStatus {
+        service_type: ServiceType::OWN_PROCESS,
+        current_state: state,
+        controls_accepted,
+        exit_code,
+        checkpoint: 0,
+        wait_hint,
+        process_id: None,
+    };
+    if let Err(e) = handle.set_service_status(status) {
+        tracing::warn!("报告 Windows 服务状态失败: {}", e);
+    }
+}
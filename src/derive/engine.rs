@@ -196,6 +196,14 @@ pub trait RequestResponseEngine: SearchEngine {
     /// 接收响应对象，返回搜索结果项列表
     fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>>;
 
+    /// 从响应中提取下一页的续页令牌
+    ///
+    /// 使用游标或偏移分页的引擎实现此方法；返回的令牌在请求下一页时
+    /// 通过 `RequestParams.cursor` 传回。默认不提供续页令牌
+    fn next_cursor(&self, _resp: &Self::Response, _params: &RequestParams) -> Option<String> {
+        None
+    }
+
     /// 默认搜索实现（使用 request/response 模式）
     async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
         let start_time = std::time::Instant::now();
@@ -208,9 +216,14 @@ pub trait RequestResponseEngine: SearchEngine {
         let resp = self.fetch(&params).await?;
 
         // 3. 解析响应
+        let next_cursor = self.next_cursor(&resp, &params);
         let items = self.response(resp)?;
 
         // 4. 构建搜索结果
+        let mut metadata = HashMap::new();
+        if let Some(cursor) = next_cursor {
+            metadata.insert(NEXT_CURSOR_METADATA_KEY.to_string(), cursor);
+        }
         Ok(SearchResult {
             engine_name: self.info().name.clone(),
             total_results: None,
@@ -218,7 +231,7 @@ pub trait RequestResponseEngine: SearchEngine {
             items,
             pagination: None,
             suggestions: Vec::new(),
-            metadata: HashMap::new(),
+            metadata,
         })
     }
 }
@@ -31,6 +31,9 @@ pub trait QueryBuilder {
             safe_search: crate::config::common::SafeSearchLevel::Moderate,
             time_range: None,
             params: std::collections::HashMap::new(),
+            lat: None,
+            lon: None,
+            bbox: None,
         }
     }
 
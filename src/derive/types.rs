@@ -37,6 +37,10 @@ pub enum EngineType {
     Shopping,
     /// 音乐搜索引擎
     Music,
+    /// 地图搜索引擎
+    Map,
+    /// 文件/种子搜索引擎（需在配置中显式启用）
+    Files,
     /// 自定义搜索引擎
     Custom,
 }
@@ -68,6 +72,186 @@ pub struct SearchQuery {
     pub time_range: Option<TimeRange>,
     /// 自定义参数
     pub params: HashMap<String, String>,
+    /// 纬度（地图搜索，可选）
+    #[serde(default)]
+    pub lat: Option<f64>,
+    /// 经度（地图搜索，可选）
+    #[serde(default)]
+    pub lon: Option<f64>,
+    /// 限定范围（地图搜索，可选）
+    #[serde(default)]
+    pub bbox: Option<BoundingBox>,
+}
+
+/// 结果元数据中的纬度键
+pub const LATITUDE_METADATA_KEY: &str = "latitude";
+
+/// 结果元数据中的经度键
+pub const LONGITUDE_METADATA_KEY: &str = "longitude";
+
+/// 经纬度范围
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    /// 最小经度（西）
+    pub min_lon: f64,
+    /// 最小纬度（南）
+    pub min_lat: f64,
+    /// 最大经度（东）
+    pub max_lon: f64,
+    /// 最大纬度（北）
+    pub max_lat: f64,
+}
+
+impl BoundingBox {
+    /// 从 `min_lon,min_lat,max_lon,max_lat` 格式解析（与 GeoJSON bbox 顺序一致）
+    ///
+    /// # Returns
+    ///
+    /// 格式错误或坐标越界时返回错误信息
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let parts: Vec<f64> = value
+            .split(',')
+            .map(|p| p.trim().parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("无效的 bbox: {}", value))?;
+        let [min_lon, min_lat, max_lon, max_lat] = parts[..] else {
+            return Err(format!("bbox 需要 4 个数值: {}", value));
+        };
+
+        let bbox = Self { min_lon, min_lat, max_lon, max_lat };
+        if !(-180.0..=180.0).contains(&min_lon)
+            || !(-180.0..=180.0).contains(&max_lon)
+            || !(-90.0..=90.0).contains(&min_lat)
+            || !(-90.0..=90.0).contains(&max_lat)
+            || min_lon >= max_lon
+            || min_lat >= max_lat
+        {
+            return Err(format!("bbox 坐标越界或顺序错误: {}", value));
+        }
+        Ok(bbox)
+    }
+
+    /// 以某点为中心、`delta` 度为半径构造范围
+    pub fn around(lat: f64, lon: f64, delta: f64) -> Self {
+        Self {
+            min_lon: (lon - delta).max(-180.0),
+            min_lat: (lat - delta).max(-90.0),
+            max_lon: (lon + delta).min(180.0),
+            max_lat: (lat + delta).min(90.0),
+        }
+    }
+
+    /// 判断点是否在范围内
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        (self.min_lat..=self.max_lat).contains(&lat) && (self.min_lon..=self.max_lon).contains(&lon)
+    }
+}
+
+/// 结果元数据中的 BitTorrent infohash 键（40 位小写十六进制）
+pub const INFOHASH_METADATA_KEY: &str = "infohash";
+
+/// 结果元数据中的磁力链接键
+pub const MAGNET_METADATA_KEY: &str = "magnet";
+
+/// 结果元数据中的做种数键
+pub const SEEDERS_METADATA_KEY: &str = "seeders";
+
+/// 结果元数据中的下载数键
+pub const LEECHERS_METADATA_KEY: &str = "leechers";
+
+/// 磁力链接
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MagnetLink {
+    /// infohash（40 位小写十六进制）
+    pub infohash: String,
+    /// 显示名称（dn）
+    pub name: Option<String>,
+    /// Tracker 列表（tr）
+    pub trackers: Vec<String>,
+}
+
+impl MagnetLink {
+    /// 创建磁力链接
+    pub fn new(infohash: &str, name: Option<&str>, trackers: &[&str]) -> Option<Self> {
+        Some(Self {
+            infohash: Self::normalize_infohash(infohash)?,
+            name: name.map(|n| n.to_string()),
+            trackers: trackers.iter().map(|t| t.to_string()).collect(),
+        })
+    }
+
+    /// 解析 `magnet:?xt=urn:btih:...` 链接
+    ///
+    /// # Returns
+    ///
+    /// 不是 BitTorrent 磁力链接或 infohash 无效时返回 None
+    pub fn parse(uri: &str) -> Option<Self> {
+        let url = url::Url::parse(uri.trim()).ok()?;
+        if url.scheme() != "magnet" {
+            return None;
+        }
+
+        let mut infohash = None;
+        let mut name = None;
+        let mut trackers = Vec::new();
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "xt" => {
+                    if let Some(hash) = value.strip_prefix("urn:btih:") {
+                        infohash = Self::normalize_infohash(hash);
+                    }
+                }
+                "dn" => name = Some(value.into_owned()),
+                "tr" => trackers.push(value.into_owned()),
+                _ => {}
+            }
+        }
+
+        Some(Self { infohash: infohash?, name, trackers })
+    }
+
+    /// 生成磁力链接 URI
+    pub fn to_uri(&self) -> String {
+        let mut uri = format!("magnet:?xt=urn:btih:{}", self.infohash);
+        if let Some(ref name) = self.name {
+            uri.push_str("&dn=");
+            uri.push_str(&urlencoding::encode(name));
+        }
+        for tracker in &self.trackers {
+            uri.push_str("&tr=");
+            uri.push_str(&urlencoding::encode(tracker));
+        }
+        uri
+    }
+
+    /// 将十六进制或 Base32 infohash 统一为小写十六进制
+    pub fn normalize_infohash(hash: &str) -> Option<String> {
+        let hash = hash.trim();
+        match hash.len() {
+            40 if hash.chars().all(|c| c.is_ascii_hexdigit()) => Some(hash.to_ascii_lowercase()),
+            32 => {
+                // RFC 4648 Base32：每字符 5 位，共 160 位
+                let mut bits: u64 = 0;
+                let mut bit_count = 0;
+                let mut hex = String::with_capacity(40);
+                for c in hash.chars() {
+                    let value = match c.to_ascii_uppercase() {
+                        c @ 'A'..='Z' => c as u64 - 'A' as u64,
+                        c @ '2'..='7' => c as u64 - '2' as u64 + 26,
+                        _ => return None,
+                    };
+                    bits = (bits << 5) | value;
+                    bit_count += 5;
+                    while bit_count >= 4 {
+                        bit_count -= 4;
+                        hex.push_str(&format!("{:x}", (bits >> bit_count) & 0xf));
+                    }
+                }
+                Some(hex)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Default for SearchQuery {
@@ -82,10 +266,19 @@ impl Default for SearchQuery {
             safe_search: crate::config::common::SafeSearchLevel::Moderate,
             time_range: None,
             params: HashMap::new(),
+            lat: None,
+            lon: None,
+            bbox: None,
         }
     }
 }
 
+/// 查询参数中携带引擎续页令牌的键（`SearchQuery.params`）
+pub const CURSOR_PARAM_KEY: &str = "cursor";
+
+/// 引擎返回的下一页续页令牌在结果元数据中的键（`SearchResult.metadata`）
+pub const NEXT_CURSOR_METADATA_KEY: &str = "next_cursor";
+
 /// 请求参数（类似 searxng 的 params）
 ///
 /// 用于构建和传递 HTTP 请求的参数
@@ -111,6 +304,15 @@ pub struct RequestParams {
     pub safesearch: i32,
     /// 自定义参数
     pub custom: HashMap<String, String>,
+    /// 纬度
+    pub lat: Option<f64>,
+    /// 经度
+    pub lon: Option<f64>,
+    /// 限定范围
+    pub bbox: Option<BoundingBox>,
+    /// 上一页返回的续页令牌（使用游标/偏移分页的引擎优先于页码使用）
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 impl Default for RequestParams {
@@ -126,6 +328,10 @@ impl Default for RequestParams {
             time_range: None,
             safesearch: 0,
             custom: HashMap::new(),
+            lat: None,
+            lon: None,
+            bbox: None,
+            cursor: None,
         }
     }
 }
@@ -146,6 +352,10 @@ impl RequestParams {
         };
         
         params.custom = query.params.clone();
+        params.lat = query.lat;
+        params.lon = query.lon;
+        params.bbox = query.bbox;
+        params.cursor = query.params.get(CURSOR_PARAM_KEY).cloned();
         params
     }
 }
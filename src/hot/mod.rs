@@ -0,0 +1,26 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 热榜模块
+//!
+//! 获取各平台的热门话题榜单（微博、知乎、Hacker News、Reddit 以及任意 RSS 源），
+//! 解析为统一的条目格式并缓存
+
+pub mod types;
+pub mod parser;
+pub mod on;
+
+pub use types::*;
+pub use parser::*;
+pub use on::*;
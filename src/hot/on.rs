@@ -0,0 +1,272 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 热榜外部接口
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::RwLock;
+
+use crate::cache::MetadataCache;
+use crate::config::hot::{HotConfig, HotPlatformConfig};
+use crate::net::client::HttpClient;
+use crate::net::types::RequestOptions;
+
+use super::parser::parse_hot_list;
+use super::types::{builtin_platforms, HotList, HotPlatform};
+
+/// 热榜缓存键前缀（元数据缓存）
+const HOT_KEY_PREFIX: &str = "hot:";
+
+/// 热榜客户端
+///
+/// 按平台获取并解析热榜；提供元数据缓存时缓存到元数据缓存，否则缓存在进程内
+pub struct HotClient {
+    /// 热榜配置
+    config: HotConfig,
+    /// 内置平台与配置覆盖合并后的平台表
+    platforms: BTreeMap<String, HotPlatformConfig>,
+    /// HTTP 客户端
+    client: Arc<HttpClient>,
+    /// 元数据缓存（可选）
+    cache: Option<MetadataCache>,
+    /// 进程内缓存（未提供元数据缓存时使用）
+    memory: RwLock<HashMap<String, HotList>>,
+}
+
+impl HotClient {
+    /// 创建热榜客户端
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - 热榜配置
+    /// * `client` - HTTP 客户端（请求遵循其隐私与代理设置）
+    /// * `cache` - 元数据缓存（可选）
+    pub fn new(config: HotConfig, client: Arc<HttpClient>, cache: Option<MetadataCache>) -> Self {
+        let mut platforms = builtin_platforms();
+        platforms.extend(config.platforms.clone());
+        platforms.retain(|_, platform| platform.enabled);
+        Self {
+            config,
+            platforms,
+            client,
+            cache,
+            memory: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 列出启用的平台（按 ID 排序）
+    pub fn list_platforms(&self) -> Vec<HotPlatform> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+        self.platforms
+            .iter()
+            .map(|(id, platform)| HotPlatform {
+                id: id.clone(),
+                name: platform.name.clone(),
+                format: platform.format,
+                url: platform.url.clone(),
+            })
+            .collect()
+    }
+
+    /// 获取一个平台的热榜
+    ///
+    /// # Arguments
+    ///
+    /// * `platform` - 平台 ID
+    /// * `force` - 为 true 时忽略缓存
+    ///
+    /// # Returns
+    ///
+    /// 返回热榜，平台未知、已禁用或获取失败时返回错误
+    pub async fn fetch_platform(&self, platform: &str, force: bool) -> Result<HotList, Box<dyn Error + Send + Sync>> {
+        if !self.config.enabled {
+            return Err("Hot lists are disabled".into());
+        }
+        let config = self
+            .platforms
+            .get(platform)
+            .ok_or_else(|| format!("Unknown hot list platform: {}", platform))?;
+
+        if !force && let Some(mut list) = self.cached(platform).await {
+            list.cached = true;
+            return Ok(list);
+        }
+
+        let options = RequestOptions {
+            timeout: Duration::from_millis(self.config.timeout_ms),
+            ..Default::default()
+        };
+        let response = self
+            .client
+            .get(&config.url, Some(options))
+            .await
+            .map_err(|e| format!("Failed to fetch {} hot list: {}", platform, e))?;
+        if !response.status().is_success() {
+            return Err(format!("{} hot list returned HTTP {}", platform, response.status()).into());
+        }
+        let body = self
+            .client
+            .read_text(response)
+            .await
+            .map_err(|e| format!("Failed to read {} hot list: {}", platform, e))?;
+
+        let mut items = parse_hot_list(config.format, &body)?;
+        items.truncate(self.config.max_items);
+        let list = HotList {
+            platform: platform.to_string(),
+            name: config.name.clone(),
+            items,
+            fetched_at: chrono::Utc::now().timestamp(),
+            cached: false,
+        };
+        self.store(&list).await;
+        Ok(list)
+    }
+
+    /// 并发获取所有启用平台的热榜
+    ///
+    /// 获取失败的平台记录警告并跳过
+    pub async fn fetch_all(&self, force: bool) -> Vec<HotList> {
+        let ids: Vec<&String> = self.platforms.keys().collect();
+        let results = futures::future::join_all(ids.iter().map(|id| self.fetch_platform(id, force))).await;
+        ids.into_iter()
+            .zip(results)
+            .filter_map(|(id, result)| match result {
+                Ok(list) => Some(list),
+                Err(e) => {
+                    tracing::warn!("Failed to fetch hot list {}: {}", id, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// 读取未过期的缓存
+    async fn cached(&self, platform: &str) -> Option<HotList> {
+        if self.config.cache_ttl_secs == 0 {
+            return None;
+        }
+        match self.cache {
+            Some(ref cache) => {
+                let bytes = cache
+                    .get_metadata_async(&format!("{}{}", HOT_KEY_PREFIX, platform))
+                    .await
+                    .ok()??;
+                serde_json::from_slice(&bytes).ok()
+            }
+            None => {
+                let memory = self.memory.read().await;
+                let list = memory.get(platform)?;
+                let age = chrono::Utc::now().timestamp() - list.fetched_at;
+                (age < self.config.cache_ttl_secs as i64).then(|| list.clone())
+            }
+        }
+    }
+
+    /// 写入缓存
+    async fn store(&self, list: &HotList) {
+        if self.config.cache_ttl_secs == 0 {
+            return;
+        }
+        match self.cache {
+            Some(ref cache) => {
+                let Ok(bytes) = serde_json::to_vec(list) else {
+                    return;
+                };
+                let key = format!("{}{}", HOT_KEY_PREFIX, list.platform);
+                let ttl = Some(Duration::from_secs(self.config.cache_ttl_secs));
+                if let Err(e) = cache.set_metadata_async(&key, bytes, ttl).await {
+                    tracing::warn!("Failed to cache hot list {}: {}", list.platform, e);
+                }
+            }
+            None => {
+                self.memory.write().await.insert(list.platform.clone(), list.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::hot::HotFormat;
+    use crate::hot::HotItem;
+
+    fn client(config: HotConfig) -> HotClient {
+        let http = HttpClient::new(crate::net::types::NetworkConfig::default()).unwrap();
+        HotClient::new(config, Arc::new(http), None)
+    }
+
+    #[test]
+    fn test_platform_overrides() {
+        let mut config = HotConfig::default();
+        config.platforms.insert(
+            "reddit".to_string(),
+            HotPlatformConfig {
+                name: "Reddit".to_string(),
+                format: HotFormat::Reddit,
+                url: "https://www.reddit.com/r/popular/hot.json".to_string(),
+                enabled: false,
+            },
+        );
+        config.platforms.insert(
+            "lobsters".to_string(),
+            HotPlatformConfig {
+                name: "Lobsters".to_string(),
+                format: HotFormat::Rss,
+                url: "https://lobste.rs/rss".to_string(),
+                enabled: true,
+            },
+        );
+        let ids: Vec<String> = client(config).list_platforms().into_iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec!["hackernews", "lobsters", "weibo", "zhihu"]);
+
+        let disabled = HotConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(client(disabled).list_platforms().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache() {
+        let hot = client(HotConfig::default());
+        assert!(hot.fetch_platform("unknown", false).await.is_err());
+
+        let list = HotList {
+            platform: "weibo".to_string(),
+            name: "微博热搜".to_string(),
+            items: vec![HotItem {
+                rank: 1,
+                title: "话题".to_string(),
+                url: "https://s.weibo.com/weibo?q=%23话题%23".to_string(),
+                description: None,
+                heat: Some(1),
+                label: None,
+            }],
+            fetched_at: chrono::Utc::now().timestamp(),
+            cached: false,
+        };
+        hot.store(&list).await;
+        let cached = hot.fetch_platform("weibo", false).await.unwrap();
+        assert!(cached.cached);
+        assert_eq!(cached.items, list.items);
+    }
+}
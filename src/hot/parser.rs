@@ -0,0 +1,249 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 热榜解析
+//!
+//! 将各平台的响应解析为统一的 [`HotItem`] 列表
+
+use std::error::Error;
+
+use serde_json::Value;
+
+use crate::config::hot::HotFormat;
+use crate::rss::RssParser;
+
+use super::types::HotItem;
+
+/// 解析热榜响应
+///
+/// # Arguments
+///
+/// * `format` - 平台数据格式
+/// * `body` - 响应内容
+///
+/// # Returns
+///
+/// 返回按平台顺序排列、已编号的条目
+pub fn parse_hot_list(format: HotFormat, body: &str) -> Result<Vec<HotItem>, Box<dyn Error + Send + Sync>> {
+    let mut items = match format {
+        HotFormat::Weibo => parse_weibo(&serde_json::from_str(body)?),
+        HotFormat::Zhihu => parse_zhihu(&serde_json::from_str(body)?),
+        HotFormat::HackerNews => parse_hacker_news(&serde_json::from_str(body)?),
+        HotFormat::Reddit => parse_reddit(&serde_json::from_str(body)?),
+        HotFormat::Rss => parse_rss(body)?,
+    };
+    items.retain(|item| !item.title.is_empty() && !item.url.is_empty());
+    for (index, item) in items.iter_mut().enumerate() {
+        item.rank = index + 1;
+    }
+    Ok(items)
+}
+
+/// 构造未编号的条目
+fn item(title: &str, url: String) -> HotItem {
+    HotItem {
+        rank: 0,
+        title: title.trim().to_string(),
+        url,
+        description: None,
+        heat: None,
+        label: None,
+    }
+}
+
+/// 读取字符串字段（空字符串视为缺失）
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str).filter(|s| !s.trim().is_empty())
+}
+
+/// 微博热搜：`data.realtime[]`，跳过推广条目
+fn parse_weibo(json: &Value) -> Vec<HotItem> {
+    let Some(entries) = json.pointer("/data/realtime").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter(|entry| entry.get("is_ad").and_then(Value::as_u64).unwrap_or(0) == 0)
+        .filter_map(|entry| {
+            let word = str_field(entry, "word")?;
+            let url = format!("https://s.weibo.com/weibo?q={}", urlencoding::encode(&format!("#{}#", word)));
+            let mut hot = item(word, url);
+            hot.description = str_field(entry, "note").filter(|note| *note != word).map(str::to_string);
+            hot.heat = entry.get("num").and_then(Value::as_u64);
+            hot.label = str_field(entry, "label_name").map(str::to_string);
+            Some(hot)
+        })
+        .collect()
+}
+
+/// 知乎热榜：`data[].target`，API 问题链接转换为网页链接
+fn parse_zhihu(json: &Value) -> Vec<HotItem> {
+    let Some(entries) = json.get("data").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let target = entry.get("target")?;
+            let title = str_field(target, "title")?;
+            let url = str_field(target, "url")?.replace("://api.zhihu.com/questions/", "://www.zhihu.com/question/");
+            let mut hot = item(title, url);
+            hot.description = str_field(target, "excerpt").map(str::to_string);
+            hot.heat = str_field(entry, "detail_text").and_then(parse_heat_text);
+            Some(hot)
+        })
+        .collect()
+}
+
+/// Hacker News（Algolia）：`hits[]`，没有外链的帖子使用讨论页
+fn parse_hacker_news(json: &Value) -> Vec<HotItem> {
+    let Some(entries) = json.get("hits").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let title = str_field(entry, "title")?;
+            let url = match str_field(entry, "url") {
+                Some(url) => url.to_string(),
+                None => format!("https://news.ycombinator.com/item?id={}", str_field(entry, "objectID")?),
+            };
+            let mut hot = item(title, url);
+            hot.heat = entry.get("points").and_then(Value::as_u64);
+            Some(hot)
+        })
+        .collect()
+}
+
+/// Reddit listing：`data.children[].data`，跳过置顶与 NSFW 帖子，链接指向讨论页
+fn parse_reddit(json: &Value) -> Vec<HotItem> {
+    let Some(entries) = json.pointer("/data/children").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| entry.get("data"))
+        .filter(|post| {
+            !post.get("stickied").and_then(Value::as_bool).unwrap_or(false)
+                && !post.get("over_18").and_then(Value::as_bool).unwrap_or(false)
+        })
+        .filter_map(|post| {
+            let title = str_field(post, "title")?;
+            let url = format!("https://www.reddit.com{}", str_field(post, "permalink")?);
+            let mut hot = item(title, url);
+            hot.heat = post.get("score").and_then(Value::as_u64);
+            hot.label = str_field(post, "subreddit_name_prefixed").map(str::to_string);
+            Some(hot)
+        })
+        .collect()
+}
+
+/// RSS / Atom 源：按源中的顺序作为排名
+fn parse_rss(body: &str) -> Result<Vec<HotItem>, Box<dyn Error + Send + Sync>> {
+    let feed = RssParser::new().parse(body)?;
+    Ok(feed
+        .items
+        .into_iter()
+        .map(|entry| {
+            let mut hot = item(&entry.title, entry.link);
+            hot.description = entry.description.filter(|d| !d.trim().is_empty());
+            hot.label = entry.categories.into_iter().next();
+            hot
+        })
+        .collect())
+}
+
+/// 解析热度文本，如 `1234 万热度`、`3.5亿`
+fn parse_heat_text(text: &str) -> Option<u64> {
+    let number: String = text
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let value: f64 = number.parse().ok()?;
+    let rest = text.trim()[number.len()..].trim_start();
+    let multiplier = if rest.starts_with('亿') {
+        100_000_000.0
+    } else if rest.starts_with('万') {
+        10_000.0
+    } else {
+        1.0
+    };
+    Some((value * multiplier).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_weibo() {
+        let body = r#"{"ok":1,"data":{"realtime":[
+            {"word":"春节档票房","num":2345678,"label_name":"热","note":"春节档票房"},
+            {"word":"推广","num":1,"is_ad":1},
+            {"word":"新品发布","num":99,"label_name":"新"}
+        ]}}"#;
+        let items = parse_hot_list(HotFormat::Weibo, body).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].rank, 1);
+        assert_eq!(items[0].title, "春节档票房");
+        assert_eq!(items[0].heat, Some(2345678));
+        assert_eq!(items[0].label.as_deref(), Some("热"));
+        assert!(items[0].description.is_none());
+        assert!(items[0].url.starts_with("https://s.weibo.com/weibo?q=%23"));
+        assert_eq!(items[1].rank, 2);
+    }
+
+    #[test]
+    fn test_parse_zhihu() {
+        let body = r#"{"data":[{"detail_text":"1234 万热度","target":{
+            "title":"如何评价某事？","url":"https://api.zhihu.com/questions/123","excerpt":"摘要"}}]}"#;
+        let items = parse_hot_list(HotFormat::Zhihu, body).unwrap();
+        assert_eq!(items[0].url, "https://www.zhihu.com/question/123");
+        assert_eq!(items[0].heat, Some(12_340_000));
+        assert_eq!(items[0].description.as_deref(), Some("摘要"));
+    }
+
+    #[test]
+    fn test_parse_hacker_news_and_reddit() {
+        let body = r#"{"hits":[
+            {"title":"Show HN: Thing","url":null,"points":120,"objectID":"42"},
+            {"title":"Article","url":"https://example.com/a","points":80,"objectID":"43"}
+        ]}"#;
+        let items = parse_hot_list(HotFormat::HackerNews, body).unwrap();
+        assert_eq!(items[0].url, "https://news.ycombinator.com/item?id=42");
+        assert_eq!(items[1].url, "https://example.com/a");
+        assert_eq!(items[1].heat, Some(80));
+
+        let body = r#"{"data":{"children":[
+            {"data":{"title":"Pinned","permalink":"/r/x/1","stickied":true}},
+            {"data":{"title":"Post","permalink":"/r/rust/comments/2/post/","score":512,
+                     "subreddit_name_prefixed":"r/rust"}}
+        ]}}"#;
+        let items = parse_hot_list(HotFormat::Reddit, body).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].rank, 1);
+        assert_eq!(items[0].url, "https://www.reddit.com/r/rust/comments/2/post/");
+        assert_eq!(items[0].label.as_deref(), Some("r/rust"));
+    }
+
+    #[test]
+    fn test_parse_heat_text() {
+        assert_eq!(parse_heat_text("1234 万热度"), Some(12_340_000));
+        assert_eq!(parse_heat_text("3.5亿"), Some(350_000_000));
+        assert_eq!(parse_heat_text("987"), Some(987));
+        assert_eq!(parse_heat_text("热度"), None);
+        assert!(parse_hot_list(HotFormat::Weibo, "not json").is_err());
+    }
+}
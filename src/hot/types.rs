@@ -0,0 +1,103 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 热榜类型定义
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::hot::{HotFormat, HotPlatformConfig};
+
+/// 热榜条目
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotItem {
+    /// 排名（从 1 开始）
+    pub rank: usize,
+    /// 标题
+    pub title: String,
+    /// 链接
+    pub url: String,
+    /// 摘要
+    pub description: Option<String>,
+    /// 热度（平台原始数值，如搜索量、点赞数）
+    pub heat: Option<u64>,
+    /// 标签（如「热」「新」或所属版块）
+    pub label: Option<String>,
+}
+
+/// 一个平台的热榜
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotList {
+    /// 平台 ID
+    pub platform: String,
+    /// 平台显示名称
+    pub name: String,
+    /// 条目（按排名排列）
+    pub items: Vec<HotItem>,
+    /// 获取时间（Unix 时间戳）
+    pub fetched_at: i64,
+    /// 是否来自缓存
+    #[serde(default)]
+    pub cached: bool,
+}
+
+/// 可用的热榜平台
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotPlatform {
+    /// 平台 ID
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 数据格式
+    pub format: HotFormat,
+    /// 热榜地址
+    pub url: String,
+}
+
+/// 内置热榜平台
+pub fn builtin_platforms() -> BTreeMap<String, HotPlatformConfig> {
+    let platform = |name: &str, format, url: &str| HotPlatformConfig {
+        name: name.to_string(),
+        format,
+        url: url.to_string(),
+        enabled: true,
+    };
+    BTreeMap::from([
+        (
+            "weibo".to_string(),
+            platform("微博热搜", HotFormat::Weibo, "https://weibo.com/ajax/side/hotSearch"),
+        ),
+        (
+            "zhihu".to_string(),
+            platform(
+                "知乎热榜",
+                HotFormat::Zhihu,
+                "https://www.zhihu.com/api/v3/feed/topstory/hot-lists/total?limit=50",
+            ),
+        ),
+        (
+            "hackernews".to_string(),
+            platform(
+                "Hacker News",
+                HotFormat::HackerNews,
+                "https://hn.algolia.com/api/v1/search?tags=front_page&hitsPerPage=50",
+            ),
+        ),
+        (
+            "reddit".to_string(),
+            platform("Reddit", HotFormat::Reddit, "https://www.reddit.com/r/popular/hot.json?limit=50"),
+        ),
+    ])
+}
@@ -24,26 +24,54 @@ pub use error as error_crate;
 
 pub mod error;
 pub mod config;
-pub mod cache;
 pub mod derive;
+pub mod search;
+pub mod text;
+
+// 运行时、网络、缓存与服务端模块（wasm32 目标下不编译）
+#[cfg(feature = "native")]
+pub mod cache;
+#[cfg(feature = "native")]
 pub mod net;
+#[cfg(feature = "native")]
+pub mod lifecycle;
+#[cfg(feature = "native")]
+pub mod webhook;
+#[cfg(feature = "native")]
+pub mod api;
+#[cfg(feature = "native")]
+pub mod rss;
+#[cfg(feature = "native")]
+pub mod hot;
+#[cfg(feature = "native")]
+pub mod web;
+#[cfg(feature = "daemon")]
+pub mod daemon;
+
+#[cfg(feature = "wasm32")]
+pub mod wasm;
 
 // 创建便利的 Error 和 Result 类型别名
 pub type Error = error_crate::ErrorInfo;
 pub type Result<T> = error_crate::Result<T>;
 
 // 重新导出主要类型
-pub use config::{SeeSeaConfig, ConfigManager, ConfigError};
-pub use cache::{CacheInterface, CacheImplConfig, CacheMode};
+pub use config::{SeeSeaConfig, ConfigError};
+#[cfg(feature = "native")]
+pub use config::ConfigManager;
 pub use derive::{
     SearchEngine, SearchQuery, SearchResult, EngineInfo,
     QueryBuilder, ResultParser,
     RssFeed, RssFeedItem, RssFeedQuery, RssFeedSource,
 };
+#[cfg(feature = "native")]
+pub use cache::{CacheInterface, CacheImplConfig, CacheMode};
+#[cfg(feature = "native")]
 pub use net::{NetworkInterface, NetworkConfig, HttpClient};
-pub mod search;
-pub mod api;
-pub mod rss;
+#[cfg(feature = "native")]
+pub use lifecycle::{TaskManager, ShutdownReport};
+#[cfg(feature = "native")]
+pub use webhook::{WebhookDispatcher, WebhookEvent};
 
 #[cfg(feature = "python")]
 pub mod python_bindings;
@@ -55,14 +83,17 @@ use pyo3::prelude::*;
 #[cfg(feature = "python")]
 #[pymodule]
 fn seesea_core(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
-    use python_bindings::{py_search, py_api, py_config, py_cache, py_rss, py_browser, py_engine_registry};
+    use python_bindings::{py_search, py_async_search, py_api, py_config, py_cache, py_rss, py_hot, py_browser, py_engine_registry, py_result_processor};
 
     m.add_class::<py_search::PySearchClient>()?;
+    m.add_class::<py_async_search::PyAsyncSearchClient>()?;
     m.add_class::<py_api::PyApiServer>()?;
     m.add_class::<py_config::PyConfig>()?;
     m.add_class::<py_cache::PyCacheStats>()?;
     m.add_class::<py_cache::PyCacheInterface>()?;
     m.add_class::<py_rss::PyRssClient>()?;
+    m.add_class::<py_rss::PyRssItemIterator>()?;
+    m.add_class::<py_hot::PyHotClient>()?;
     m.add_class::<py_browser::PyBrowserConfig>()?;
     m.add_class::<py_browser::PyBrowserEngineClient>()?;
     
@@ -72,6 +103,11 @@ fn seesea_core(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(py_engine_registry::list_engines, m)?)?;
     m.add_function(wrap_pyfunction!(py_engine_registry::has_engine, m)?)?;
 
+    // 结果处理器注册函数
+    m.add_function(wrap_pyfunction!(py_result_processor::register_result_processor, m)?)?;
+    m.add_function(wrap_pyfunction!(py_result_processor::unregister_result_processor, m)?)?;
+    m.add_function(wrap_pyfunction!(py_result_processor::list_result_processors, m)?)?;
+
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
     m.add("__doc__", "SeeSea - Privacy-focused metasearch engine with RSS and browser engine support")?;
 
@@ -0,0 +1,298 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 后台任务生命周期管理
+//!
+//! 跟踪后台任务（健康检查、缓存清理、RSS 轮询等）与进行中的搜索，
+//! 停机时统一取消、等待其结束，并执行缓存刷新等收尾操作
+
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tokio_util::task::task_tracker::TrackedFuture;
+
+/// 停机收尾回调
+type ShutdownHook = Box<dyn FnOnce() + Send>;
+
+/// 任务管理器
+///
+/// 所有通过管理器启动的任务共享同一个取消令牌；调用 [`shutdown`](Self::shutdown)
+/// 后令牌被取消，管理器等待任务结束，再按注册顺序执行收尾回调
+pub struct TaskManager {
+    /// 取消令牌
+    token: CancellationToken,
+    /// 任务跟踪器
+    tracker: TaskTracker,
+    /// 停机收尾回调
+    hooks: Mutex<Vec<(String, ShutdownHook)>>,
+}
+
+/// 停机结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// 所有任务是否在超时前结束
+    pub completed: bool,
+    /// 超时后仍在运行的任务数
+    pub remaining_tasks: usize,
+    /// 已执行的收尾回调数
+    pub hooks_run: usize,
+}
+
+impl TaskManager {
+    /// 创建任务管理器
+    pub fn new() -> Self {
+        Self {
+            token: CancellationToken::new(),
+            tracker: TaskTracker::new(),
+            hooks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 获取取消令牌
+    ///
+    /// 返回子令牌，取消它不会影响管理器本身
+    pub fn token(&self) -> CancellationToken {
+        self.token.child_token()
+    }
+
+    /// 是否已开始停机
+    pub fn is_shutting_down(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// 等待停机信号
+    pub async fn cancelled(&self) {
+        self.token.cancelled().await
+    }
+
+    /// 当前仍在运行的任务数
+    pub fn active_tasks(&self) -> usize {
+        self.tracker.len()
+    }
+
+    /// 启动后台任务
+    ///
+    /// 任务收到取消信号时立即中止
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 任务名称（用于日志）
+    /// * `task` - 任务
+    pub fn spawn<F>(&self, name: &str, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let token = self.token.child_token();
+        let name = name.to_string();
+        self.tracker.spawn(async move {
+            tokio::select! {
+                _ = token.cancelled() => {
+                    tracing::debug!("后台任务 {} 已取消", name);
+                }
+                _ = task => {
+                    tracing::debug!("后台任务 {} 已结束", name);
+                }
+            }
+        });
+    }
+
+    /// 启动可自行处理取消的后台任务
+    ///
+    /// 任务获得取消令牌，可在收到信号后完成收尾再退出
+    pub fn spawn_with_token<F, Fut>(&self, task: F)
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.tracker.spawn(task(self.token.child_token()));
+    }
+
+    /// 启动周期任务
+    ///
+    /// 每隔 `period` 执行一次 `tick`，停机时在两次执行之间退出
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 任务名称（用于日志）
+    /// * `period` - 执行间隔
+    /// * `tick` - 每次执行的操作
+    pub fn spawn_interval<F, Fut>(&self, name: &str, period: Duration, mut tick: F)
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let token = self.token.child_token();
+        let name = name.to_string();
+        self.tracker.spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // 第一次 tick 立即返回，跳过它以避免启动时立即执行
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = interval.tick() => tick().await,
+                }
+            }
+            tracing::debug!("周期任务 {} 已停止", name);
+        });
+    }
+
+    /// 跟踪进行中的操作（如搜索请求）
+    ///
+    /// 与 [`spawn`](Self::spawn) 不同，被跟踪的操作不会被取消，停机时等待其完成
+    pub fn track<F>(&self, future: F) -> TrackedFuture<F>
+    where
+        F: Future,
+    {
+        self.tracker.track_future(future)
+    }
+
+    /// 注册停机收尾回调（如刷新缓存）
+    ///
+    /// 回调在所有任务结束（或超时）后按注册顺序执行
+    pub fn on_shutdown<F>(&self, name: &str, hook: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if let Ok(mut hooks) = self.hooks.lock() {
+            hooks.push((name.to_string(), Box::new(hook)));
+        }
+    }
+
+    /// 停机
+    ///
+    /// 取消所有任务，最多等待 `timeout`，然后执行收尾回调
+    ///
+    /// # 参数
+    ///
+    /// * `timeout` - 等待任务结束的最长时间
+    ///
+    /// # 返回值
+    ///
+    /// 返回停机结果
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        self.token.cancel();
+        self.tracker.close();
+
+        let completed = tokio::time::timeout(timeout, self.tracker.wait()).await.is_ok();
+        let remaining_tasks = self.tracker.len();
+        if !completed {
+            tracing::warn!("停机超时，仍有 {} 个任务未结束", remaining_tasks);
+        }
+
+        let hooks = self
+            .hooks
+            .lock()
+            .map(|mut hooks| std::mem::take(&mut *hooks))
+            .unwrap_or_default();
+        let hooks_run = hooks.len();
+        for (name, hook) in hooks {
+            tracing::debug!("执行停机回调 {}", name);
+            hook();
+        }
+
+        ShutdownReport {
+            completed,
+            remaining_tasks,
+            hooks_run,
+        }
+    }
+}
+
+impl Default for TaskManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_background_tasks() {
+        let manager = TaskManager::new();
+        manager.spawn("forever", std::future::pending());
+        assert_eq!(manager.active_tasks(), 1);
+
+        let report = manager.shutdown(Duration::from_secs(1)).await;
+        assert!(report.completed);
+        assert_eq!(report.remaining_tasks, 0);
+        assert!(manager.is_shutting_down());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_tracked_work_and_runs_hooks() {
+        let manager = TaskManager::new();
+        let finished = Arc::new(AtomicBool::new(false));
+        let flushed = Arc::new(AtomicBool::new(false));
+
+        let work = {
+            let finished = Arc::clone(&finished);
+            manager.track(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                finished.store(true, Ordering::SeqCst);
+            })
+        };
+        let handle = tokio::spawn(work);
+
+        let hook_flag = Arc::clone(&flushed);
+        manager.on_shutdown("flush", move || hook_flag.store(true, Ordering::SeqCst));
+
+        let report = manager.shutdown(Duration::from_secs(1)).await;
+        handle.await.unwrap();
+        assert!(report.completed);
+        assert_eq!(report.hooks_run, 1);
+        assert!(finished.load(Ordering::SeqCst));
+        assert!(flushed.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_interval_task_stops_on_shutdown() {
+        let manager = TaskManager::new();
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&ticks);
+        manager.spawn_interval("tick", Duration::from_millis(10), move || {
+            let counter = Arc::clone(&counter);
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(55)).await;
+        let report = manager.shutdown(Duration::from_secs(1)).await;
+        assert!(report.completed);
+        let seen = ticks.load(Ordering::SeqCst);
+        assert!(seen >= 1);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(ticks.load(Ordering::SeqCst), seen);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_times_out_on_stuck_work() {
+        let manager = TaskManager::new();
+        manager.spawn_with_token(|_token| std::future::pending::<()>());
+
+        let report = manager.shutdown(Duration::from_millis(20)).await;
+        assert!(!report.completed);
+        assert_eq!(report.remaining_tasks, 1);
+    }
+}
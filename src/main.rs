@@ -13,118 +13,509 @@
 // limitations under the License.
 
 //! SeeSea 主程序入口
+//!
+//! 以守护进程方式运行：加载配置，按 `[logging]` 段初始化日志，启动 API 服务器（含指标端点）
+//! 与后台定时任务，收到 SIGINT/SIGTERM 后优雅停机。`--check-config` 只检查配置并输出结果。
+//! 以 `daemon` 特性编译时向 systemd 报告就绪与看门狗心跳，Windows 下可用 `--service` 作为服务运行
 
-use seesea_core::config::{ConfigManager, ConfigLoader, ConfigValidator, SeeSeaConfig};
+use clap::Parser;
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing_subscriber::fmt::writer::{BoxMakeWriter, MakeWriterExt};
+use tracing_subscriber::EnvFilter;
+
+use seesea_core::api::{ApiInterface, ApiListener, ServerConfig as ApiServerConfig};
+use seesea_core::cache::{CacheImplConfig, CacheInterface};
+use seesea_core::config::loader::ConfigSource;
+use seesea_core::config::{
+    redact_validation, ConfigLoadResult, ConfigLoader, ConfigValidator, Environment, LogFormat,
+    LogLevel, LogOutput, LoggingConfig,
+};
+use seesea_core::lifecycle::TaskManager;
+use seesea_core::search::SavedSearchRunner;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// 清理过期缓存的间隔
+const CACHE_CLEANUP_INTERVAL: Duration = Duration::from_secs(600);
+
+/// 停机时等待后台任务结束的最长时间
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 删除超过保留期的搜索历史的间隔
+const HISTORY_EXPIRY_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// 重载结果过滤规则并检查到期过滤列表的间隔
+const FILTER_LIST_TICK: Duration = Duration::from_secs(300);
+
+/// 检查到期的保存的搜索的间隔
+const SAVED_SEARCH_TICK: Duration = Duration::from_secs(60);
+
+/// SeeSea 服务进程
+#[derive(Parser, Debug, Clone)]
+#[command(name = "SeeSea", version, about = "🌊 SeeSea - 隐私保护型元搜索引擎服务")]
+struct Args {
+    /// 配置文件路径或配置目录
+    #[arg(short, long, default_value = "./config")]
+    config: PathBuf,
+
+    /// 运行环境（development、testing、staging、production）；
+    /// 配置路径为目录且其中存在 `<环境>.toml` 时优先加载该文件
+    #[arg(short, long)]
+    env: Option<Environment>,
+
+    /// 日志级别（error、warn、info、debug、trace），覆盖配置中的 `logging.level`
+    #[arg(short, long, value_parser = parse_log_level)]
+    log_level: Option<LogLevel>,
+
+    /// 只加载并检查配置，输出检查结果后退出
+    #[arg(long)]
+    check_config: bool,
+
+    /// 以 Windows 服务方式运行（由服务控制管理器启动）
+    #[cfg(all(windows, feature = "daemon"))]
+    #[arg(long)]
+    service: bool,
+}
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // 初始化日志
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .init();
-
-    println!("🌊 SeeSea - 看海看得远，看得广");
-    println!("🦀 隐私保护型元搜索引擎");
-    println!();
+async fn main() -> ExitCode {
+    let args = Args::parse();
 
-    // 测试配置加载器
-    println!("📁 测试配置加载器...");
-    let loader = ConfigLoader::new()
-        .add_search_path("./config");
-
-    println!("  🔍 自动发现配置文件...");
-    match loader.auto_load().await {
-        Ok(load_result) => {
-            println!("  ✅ 配置加载成功");
-            println!("  📄 文件路径: {:?}", load_result.file_path);
-            println!("  ⚠️ 警告数量: {}", load_result.warnings.len());
-
-            if !load_result.warnings.is_empty() {
-                println!("  警告列表:");
-                for warning in &load_result.warnings {
-                    println!("    - {}", warning);
-                }
-            }
+    if args.check_config {
+        return check_config(&args).await;
+    }
+
+    #[cfg(all(windows, feature = "daemon"))]
+    if args.service {
+        return run_service(args);
+    }
+
+    match run(args, shutdown_signal()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            tracing::error!("SeeSea 异常退出: {}", e);
+            eprintln!("❌ {}", e);
+            ExitCode::FAILURE
         }
+    }
+}
+
+/// 交给 Windows 服务控制管理器调度，服务停止后返回
+#[cfg(all(windows, feature = "daemon"))]
+fn run_service(args: Args) -> ExitCode {
+    let runtime = tokio::runtime::Handle::current();
+    let result = tokio::task::block_in_place(|| {
+        seesea_core::daemon::windows::run_service(move |token| {
+            runtime
+                .block_on(run(args.clone(), token.cancelled_owned()))
+                .map_err(|e| e.to_string())
+        })
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
-            println!("  ❌ 配置加载失败: {}", e);
-            // 使用默认配置继续
-            println!("  🔄 使用默认配置继续");
+            eprintln!("❌ 无法以 Windows 服务方式运行（需由服务控制管理器启动）: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 解析日志级别参数
+fn parse_log_level(value: &str) -> Result<LogLevel, String> {
+    match value.to_lowercase().as_str() {
+        "error" => Ok(LogLevel::Error),
+        "warn" | "warning" => Ok(LogLevel::Warn),
+        "info" => Ok(LogLevel::Info),
+        "debug" => Ok(LogLevel::Debug),
+        "trace" => Ok(LogLevel::Trace),
+        other => Err(format!("未知的日志级别: {}", other)),
+    }
+}
+
+/// 日志级别对应的过滤指令
+fn level_directive(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Error => "error",
+        LogLevel::Warn => "warn",
+        LogLevel::Info => "info",
+        LogLevel::Debug => "debug",
+        LogLevel::Trace => "trace",
+    }
+}
+
+/// 确定要加载的配置文件
+///
+/// `path` 为目录时优先使用 `<环境>.toml`，否则在其中自动发现配置文件（返回的路径可能不存在）
+async fn resolve_config_file(path: &Path, environment: Option<Environment>) -> Result<PathBuf, BoxError> {
+    if !path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    let env_file = environment
+        .map(|env| path.join(format!("{}.toml", env)))
+        .filter(|file| file.exists());
+    match env_file {
+        Some(file) => Ok(file),
+        None => Ok(ConfigLoader::new().with_search_paths(&[path]).find_config_file().await?),
+    }
+}
+
+/// 加载配置
+///
+/// 找不到配置文件时使用默认配置，两种情况都会叠加环境变量覆盖
+async fn load_config(path: &Path, environment: Option<Environment>) -> Result<ConfigLoadResult, BoxError> {
+    let config_file = resolve_config_file(path, environment).await?;
+    let loader = ConfigLoader::new();
+    let mut sources = vec![ConfigSource::Defaults];
+    if config_file.exists() {
+        sources.push(ConfigSource::File(config_file));
+    }
+    sources.push(ConfigSource::Environment);
+
+    let mut load_result = loader.load_from_sources(&sources).await?;
+    if let Some(environment) = environment {
+        load_result.config.environment = environment;
+        load_result.config.general.environment = environment;
+    }
+    Ok(load_result)
+}
+
+/// 按日志配置初始化全局 tracing 订阅者
+///
+/// 全局级别之外叠加 `module_levels` 中的模块级别，`ignore_modules` 中的模块不输出；
+/// 仅在输出到终端时启用彩色
+fn init_logging(config: &LoggingConfig) -> Result<(), BoxError> {
+    let mut directives = vec![level_directive(config.level).to_string()];
+    if config.module_levels.enabled {
+        for (module, level) in &config.module_levels.levels {
+            directives.push(format!("{}={}", module, level_directive(*level)));
+        }
+    }
+    for module in &config.module_levels.ignore_modules {
+        directives.push(format!("{}=off", module));
+    }
+    let filter = EnvFilter::try_new(directives.join(","))?;
+
+    let writer = match config.output {
+        LogOutput::Stdout => BoxMakeWriter::new(std::io::stdout),
+        LogOutput::Stderr => BoxMakeWriter::new(std::io::stderr),
+        LogOutput::File => BoxMakeWriter::new(open_log_file(config)?),
+        LogOutput::Both => BoxMakeWriter::new(std::io::stdout.and(open_log_file(config)?)),
+    };
+    let ansi = config.colored && matches!(config.output, LogOutput::Stdout | LogOutput::Stderr);
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(ansi);
+    match config.format {
+        LogFormat::Simple => builder.compact().with_target(false).without_time().try_init()?,
+        LogFormat::Compact => builder.compact().try_init()?,
+        LogFormat::Full | LogFormat::Json => builder.try_init()?,
+    }
+    if matches!(config.format, LogFormat::Json) {
+        tracing::warn!("当前构建不支持 JSON 日志格式，使用完整格式");
+    }
+    Ok(())
+}
+
+/// 以追加方式打开日志文件，必要时创建父目录
+fn open_log_file(config: &LoggingConfig) -> Result<Arc<std::fs::File>, BoxError> {
+    let path = config
+        .file_path
+        .as_ref()
+        .ok_or("logging.output 为 file 或 both 时必须设置 logging.file_path")?;
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("无法打开日志文件 {}: {}", path.display(), e))?;
+    Ok(Arc::new(file))
+}
+
+/// 加载并检查配置，输出检查结果
+async fn check_config(args: &Args) -> ExitCode {
+    println!("🌊 SeeSea 配置检查");
+    println!();
+
+    let load_result = match load_config(&args.config, args.env).await {
+        Ok(load_result) => load_result,
+        Err(e) => {
+            println!("❌ 配置加载失败: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut config = load_result.config;
+    if let Some(level) = args.log_level {
+        config.logging.level = level;
+    }
+
+    if load_result.used_defaults {
+        println!("📄 配置文件: 未找到，使用默认配置");
+    } else {
+        println!("📄 配置文件: {}", load_result.file_path);
+    }
+    println!("🏗️ 环境: {}", config.general.environment);
+    println!("🔌 监听地址: {}:{}", config.server.bind_address, config.server.port);
+    println!("📝 日志级别: {}", level_directive(config.logging.level));
+    println!(
+        "📊 指标端点: {}",
+        if config.api.metrics.enabled || config.general.enable_metrics {
+            config.api.metrics.path.as_str()
+        } else {
+            "禁用"
+        }
+    );
+
+    let validation = redact_validation(ConfigValidator::new().validate(&config), &config);
+    let mut warnings = load_result.warnings;
+    for warning in validation.warnings {
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
         }
     }
 
-    // 测试配置验证器
-    println!("\n🔍 测试配置验证器...");
-    let validator = ConfigValidator::new();
-    let config = SeeSeaConfig::development();
-    let validation_result = validator.validate(&config);
+    if !warnings.is_empty() {
+        println!("\n⚠️ 警告 ({}):", warnings.len());
+        for warning in &warnings {
+            println!("  - {}", warning);
+        }
+    }
 
-    if validation_result.is_valid {
-        println!("  ✅ 配置验证通过");
+    if validation.is_valid {
+        println!("\n✅ 配置检查通过");
+        ExitCode::SUCCESS
     } else {
-        println!("  ❌ 配置验证失败");
-        println!("  错误数量: {}", validation_result.errors.len());
-        for error in &validation_result.errors {
-            println!("    - {}", error);
+        println!("\n❌ 配置检查失败 ({}):", validation.errors.len());
+        for error in &validation.errors {
+            println!("  - {}", error);
         }
+        ExitCode::FAILURE
+    }
+}
+
+/// 启动 API 服务器、指标端点与后台任务，`shutdown` 完成后优雅停机
+async fn run<F>(args: Args, shutdown: F) -> Result<(), BoxError>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let load_result = load_config(&args.config, args.env).await?;
+    let config_file = (!load_result.file_path.is_empty()).then(|| PathBuf::from(&load_result.file_path));
+    let mut app_config = load_result.config;
+    if let Some(level) = args.log_level {
+        app_config.logging.level = level;
+    }
+    if app_config.general.enable_metrics {
+        app_config.api.metrics.enabled = true;
+    }
+
+    init_logging(&app_config.logging)?;
+    tracing::info!(
+        "🌊 SeeSea {} 启动（环境: {}）",
+        env!("CARGO_PKG_VERSION"),
+        app_config.general.environment
+    );
+    match &config_file {
+        Some(path) => tracing::info!("配置文件: {}", path.display()),
+        None => tracing::warn!("未找到配置文件: {}，使用默认配置", args.config.display()),
     }
+    for warning in &load_result.warnings {
+        tracing::warn!("{}", warning);
+    }
+
+    let cache = Arc::new(
+        CacheInterface::new(CacheImplConfig::default())
+            .map_err(|e| format!("Failed to open cache: {}", e))?
+    );
+    let alerts = cache.alerts().map_err(|e| format!("Failed to open alert store: {}", e))?;
+    let preferences = cache.preferences().map_err(|e| format!("Failed to open preference store: {}", e))?;
+    let audit = cache.audit().map_err(|e| format!("Failed to open audit log: {}", e))?;
+    let mut api = ApiInterface::from_app_config(&app_config, Some(&cache))?
+        .with_alerts(alerts.clone())
+        .with_preferences(preferences)
+        .with_audit(audit);
+    let history = if app_config.privacy.history.enabled {
+        let history = cache.history(&app_config.privacy.history)
+            .map_err(|e| format!("Failed to open history store: {}", e))?;
+        api = api.with_history(history.clone());
+        Some(history)
+    } else {
+        None
+    };
 
-    if !validation_result.warnings.is_empty() {
-        println!("  ⚠️ 警告数量: {}", validation_result.warnings.len());
-        for warning in &validation_result.warnings {
-            println!("    - {}", warning);
+    // 后台任务：定期清理过期缓存与历史、刷新过滤规则、执行保存的搜索，停机时刷新缓存到磁盘
+    let lifecycle = TaskManager::new();
+    let janitor_cache = Arc::clone(&cache);
+    lifecycle.spawn_interval("cache-janitor", CACHE_CLEANUP_INTERVAL, move || {
+        let cache = Arc::clone(&janitor_cache);
+        async move {
+            let result = tokio::task::spawn_blocking(move || cache.cleanup()).await;
+            if let Ok(Err(e)) = result {
+                tracing::warn!("清理过期缓存失败: {}", e);
+            }
         }
+    });
+    if let Some(history) = history {
+        lifecycle.spawn_interval("history-retention", HISTORY_EXPIRY_INTERVAL, move || {
+            let history = history.clone();
+            async move {
+                if let Err(e) = history.expire_async().await {
+                    tracing::warn!("删除过期搜索历史失败: {}", e);
+                }
+            }
+        });
+    }
+    if !app_config.engines.result_filtering.remote_lists.is_empty() {
+        let search = Arc::clone(api.search());
+        lifecycle.spawn("filter-lists-initial", async move {
+            search.refresh_filter_lists().await;
+        });
     }
+    if config_file.is_some() || !app_config.engines.result_filtering.remote_lists.is_empty() {
+        let search = Arc::clone(api.search());
+        let environment = app_config.general.environment;
+        lifecycle.spawn_interval("result-filter", FILTER_LIST_TICK, move || {
+            let search = Arc::clone(&search);
+            let config_file = config_file.clone();
+            async move {
+                if let Some(path) = config_file {
+                    match load_config(&path, Some(environment)).await {
+                        Ok(loaded) => {
+                            if let Err(e) = search.reload_result_filter(&loaded.config.engines.result_filtering) {
+                                tracing::warn!("重载结果过滤规则失败: {}", e);
+                            }
+                            if let Err(e) = search.set_scoring_profile(loaded.config.search.scoring.clone()) {
+                                tracing::warn!("重载评分参数失败: {}", e);
+                            }
+                        }
+                        Err(e) => tracing::warn!("重新读取配置文件失败: {}", e),
+                    }
+                }
+                search.refresh_filter_lists().await;
+            }
+        });
+    }
+    let runner = Arc::new(SavedSearchRunner::new(Arc::clone(api.search()), alerts));
+    lifecycle.spawn_interval("saved-searches", SAVED_SEARCH_TICK, move || {
+        let runner = Arc::clone(&runner);
+        async move {
+            runner.run_due().await;
+        }
+    });
+    let flush_cache = Arc::clone(&cache);
+    lifecycle.on_shutdown("cache-flush", move || {
+        if let Err(e) = flush_cache.flush() {
+            tracing::error!("刷新缓存失败: {}", e);
+        }
+    });
 
-    // 测试环境特定配置
-    println!("\n🏗️ 测试环境特定配置...");
-
-    let environments = vec!["development", "testing", "staging", "production"];
-    for env in environments {
-        let env_config = match env {
-            "development" => SeeSeaConfig::development(),
-            "testing" => SeeSeaConfig::testing(),
-            "staging" => SeeSeaConfig::default(),
-            "production" => SeeSeaConfig::production(),
-            _ => SeeSeaConfig::default(),
-        };
-
-        let env_validation = validator.validate(&env_config);
-
-        println!("  {} 环境:", env);
-        println!("    验证结果: {}", if env_validation.is_valid { "✅ 通过" } else { "❌ 失败" });
-        println!("    调试模式: {}", if env_config.general.debug { "启用" } else { "禁用" });
-        println!("    HTTPS: {}", if env_config.server.is_https() { "启用" } else { "禁用" });
-        println!("    缓存: {}", if env_config.cache.enable_result_cache { "启用" } else { "禁用" });
-    }
-
-    // 测试配置管理器
-    println!("\n🛠️ 测试配置管理器...");
-    let manager = ConfigManager::with_environment(None, "development").await?;
-
-    let current_config = manager.get_config().await;
-    println!("  当前配置实例: {}", current_config.general.instance_name);
-    println!("  当前环境: {:?}", current_config.general.environment);
-    println!("  服务器端口: {}", current_config.server.port);
-    println!("  调试模式: {}", current_config.general.debug);
-    println!("  生产就绪: {}", manager.is_production_ready().await);
-
-    // 测试配置摘要
-    let summary = current_config.get_summary();
-    println!("\n📊 配置摘要:");
-    println!("  配置文件路径: {}", summary.config_path);
-    println!("  环境: {}", summary.environment);
-    println!("  启用引擎: {}/{}", summary.enabled_engines, summary.total_engines);
-    println!("  启用代理: {}", summary.enabled_proxies);
-    println!("  缓存: {}", summary.cache_enabled);
-
-    // 测试配置建议
-    println!("\n💡 配置建议:");
-    let recommendations = current_config.get_config_recommendations();
-    for recommendation in recommendations {
-        println!("  • {}", recommendation);
-    }
-
-    println!("\n🚀 SeeSea 配置系统测试完成！");
+    let server_config = ApiServerConfig::from_app_config(&app_config);
+    let listener = ApiListener::bind(&server_config)
+        .await
+        .map_err(|e| format!("Failed to bind API listener: {}", e))?;
+    tracing::info!("监听地址: {}", listener.describe());
+    if app_config.api.metrics.enabled {
+        tracing::info!("指标端点: {}", app_config.api.metrics.path);
+    }
+
+
+    // 进程管理器集成：绑定监听地址后报告就绪，按 systemd 要求发送看门狗心跳，收到停止信号后报告停机
+    #[cfg(feature = "daemon")]
+    let shutdown = {
+        if let Some(interval) = seesea_core::daemon::spawn_watchdog(&lifecycle) {
+            tracing::info!("systemd 看门狗心跳间隔: {:?}", interval);
+        }
+        seesea_core::daemon::notify_ready();
+        async move {
+            shutdown.await;
+            seesea_core::daemon::notify_stopping();
+        }
+    };
+
+    api.serve_listener(listener, shutdown).await?;
+
+    let report = lifecycle.shutdown(SHUTDOWN_TIMEOUT).await;
+    if !report.completed {
+        tracing::warn!("{} 个后台任务未能按时结束", report.remaining_tasks);
+    }
+    tracing::info!("SeeSea 已停止");
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 等待 SIGINT（Ctrl+C）或 SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("无法监听 Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("无法监听 SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("收到停止信号，等待进行中的请求完成...");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_args_parse() {
+        let args = Args::try_parse_from([
+            "SeeSea", "--config", "/etc/seesea", "--env", "prod", "--log-level", "debug", "--check-config",
+        ]).unwrap();
+        assert_eq!(args.config, PathBuf::from("/etc/seesea"));
+        assert_eq!(args.env, Some(Environment::Production));
+        assert_eq!(args.log_level, Some(LogLevel::Debug));
+        assert!(args.check_config);
+
+        assert!(Args::try_parse_from(["SeeSea", "--log-level", "loud"]).is_err());
+        assert!(Args::try_parse_from(["SeeSea", "--env", "moon"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_config_file_prefers_environment_file() {
+        let dir = std::env::temp_dir().join(format!("seesea-main-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("default.toml"), "").unwrap();
+        std::fs::write(dir.join("testing.toml"), "").unwrap();
+
+        let file = resolve_config_file(&dir, Some(Environment::Testing)).await.unwrap();
+        assert_eq!(file, dir.join("testing.toml"));
+        let file = resolve_config_file(&dir, Some(Environment::Staging)).await.unwrap();
+        assert_eq!(file, dir.join("default.toml"));
+        let file = resolve_config_file(&dir, None).await.unwrap();
+        assert_eq!(file, dir.join("default.toml"));
+
+        let explicit = dir.join("testing.toml");
+        assert_eq!(resolve_config_file(&explicit, Some(Environment::Production)).await.unwrap(), explicit);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
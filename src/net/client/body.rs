@@ -0,0 +1,602 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 响应体读取
+//!
+//! 按块读取响应体并限制总大小，超出上限时立即中止而不是先缓冲整个响应；
+//! 读取前按白名单检查 Content-Type，压缩响应边读取边解压并限制解压比例，
+//! 读取完成后按 Content-Type 中的字符集解码，UTF-8 响应直接复用缓冲区
+
+use crate::config::engines::ResponseConfig;
+use crate::error::{ErrorCategory, ErrorInfo, Result};
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::Response;
+use reqwest::header::{CONTENT_ENCODING, CONTENT_TYPE, HeaderMap};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// 引擎响应体的默认大小上限（10 MiB）
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
+
+/// 响应限制错误的错误码
+pub const RESPONSE_LIMIT_ERROR_CODE: u32 = 1001;
+
+/// 解压输出超过该大小后才检查解压比例，避免小响应因压缩率高被误判
+const RATIO_CHECK_THRESHOLD: usize = 1024 * 1024;
+
+/// 缺少 Content-Type 时用于判断二进制内容的前缀长度
+const SNIFF_LEN: usize = 512;
+
+/// 响应限制错误
+#[derive(Debug, Clone, PartialEq, error_derive::Error)]
+pub enum ResponseLimitError {
+    /// 响应体超过大小上限
+    #[error("Response body exceeds limit of {limit} bytes")]
+    TooLarge { limit: usize },
+
+    /// Content-Type 不在允许列表中
+    #[error("Content-Type not allowed: {content_type}")]
+    ContentTypeNotAllowed { content_type: String },
+
+    /// 缺少 Content-Type 且内容为二进制
+    #[error("Response without Content-Type looks like binary content")]
+    BinaryContent,
+
+    /// 解压比例超过上限
+    #[error("Decompressed response exceeds ratio of {ratio}:1")]
+    DecompressionBomb { ratio: u32 },
+
+    /// 解压失败或不支持的编码
+    #[error("Failed to decode response body: {0}")]
+    Decode(String),
+}
+
+impl From<ResponseLimitError> for ErrorInfo {
+    fn from(error: ResponseLimitError) -> Self {
+        ErrorInfo::with_source(RESPONSE_LIMIT_ERROR_CODE, error.to_string(), error)
+            .with_category(ErrorCategory::Network)
+    }
+}
+
+/// 单个引擎的响应限制
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseLimits {
+    /// 响应体（解压后）大小上限，None 表示不限制
+    pub max_bytes: Option<usize>,
+    /// 允许的 Content-Type，支持 `text/*`、`*/*` 与 `application/*+json` 形式，空表示不限制
+    pub allowed_content_types: Vec<String>,
+    /// 解压后与压缩前大小的最大比例，None 表示不检查（并交由 reqwest 自动解压）
+    pub max_decompression_ratio: Option<u32>,
+}
+
+impl Default for ResponseLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: Some(DEFAULT_MAX_RESPONSE_BYTES),
+            allowed_content_types: Vec::new(),
+            max_decompression_ratio: None,
+        }
+    }
+}
+
+impl From<&ResponseConfig> for ResponseLimits {
+    fn from(config: &ResponseConfig) -> Self {
+        Self {
+            max_bytes: Some(
+                config
+                    .max_response_bytes
+                    .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES),
+            ),
+            allowed_content_types: config.allowed_content_types.clone(),
+            max_decompression_ratio: config.max_decompression_ratio,
+        }
+    }
+}
+
+impl ResponseLimits {
+    /// 检查 Content-Type 是否允许
+    ///
+    /// 未配置白名单或响应没有 Content-Type 时放行（后者在读取后做二进制检查）
+    pub fn check_content_type(
+        &self,
+        content_type: Option<&str>,
+    ) -> std::result::Result<(), ResponseLimitError> {
+        let Some(content_type) = content_type else {
+            return Ok(());
+        };
+        if self.allowed_content_types.is_empty() {
+            return Ok(());
+        }
+
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if self
+            .allowed_content_types
+            .iter()
+            .any(|pattern| mime_matches(pattern, &mime))
+        {
+            Ok(())
+        } else {
+            Err(ResponseLimitError::ContentTypeNotAllowed { content_type: mime })
+        }
+    }
+}
+
+/// 判断 MIME 类型是否匹配模式
+fn mime_matches(pattern: &str, mime: &str) -> bool {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    if pattern == "*/*" || pattern == mime {
+        return true;
+    }
+    let (Some((pattern_type, pattern_sub)), Some((mime_type, mime_sub))) =
+        (pattern.split_once('/'), mime.split_once('/'))
+    else {
+        return false;
+    };
+    if pattern_type != mime_type {
+        return false;
+    }
+    match pattern_sub.strip_prefix('*') {
+        // `text/*` 匹配任意子类型，`*+json` 匹配结构化语法后缀
+        Some("") => true,
+        Some(suffix) => mime_sub.ends_with(suffix),
+        None => false,
+    }
+}
+
+/// 带大小上限的响应体缓冲区
+#[derive(Debug)]
+struct BodyBuffer {
+    /// 已读取（解压后）的字节
+    bytes: Vec<u8>,
+    /// 大小上限（None 表示不限制）
+    limit: Option<usize>,
+    /// 已读取的压缩字节数
+    compressed: usize,
+    /// 解压比例上限
+    max_ratio: Option<u32>,
+    /// 作为解压输出时记录的限制错误
+    violation: Option<ResponseLimitError>,
+}
+
+impl BodyBuffer {
+    /// 创建缓冲区，按 Content-Length 预分配
+    ///
+    /// Content-Length 不可信，预分配量同时受上限与默认上限约束
+    fn new(
+        content_length: Option<u64>,
+        limit: Option<usize>,
+    ) -> std::result::Result<Self, ResponseLimitError> {
+        let expected = content_length.map(|len| len as usize);
+        if let (Some(expected), Some(limit)) = (expected, limit)
+            && expected > limit
+        {
+            return Err(ResponseLimitError::TooLarge { limit });
+        }
+
+        let capacity = expected
+            .unwrap_or(0)
+            .min(limit.unwrap_or(usize::MAX))
+            .min(DEFAULT_MAX_RESPONSE_BYTES);
+        Ok(Self {
+            bytes: Vec::with_capacity(capacity),
+            limit,
+            compressed: 0,
+            max_ratio: None,
+            violation: None,
+        })
+    }
+
+    /// 追加一个数据块，累计超过上限或解压比例过高时返回错误
+    fn push(&mut self, chunk: &[u8]) -> std::result::Result<(), ResponseLimitError> {
+        let total = self.bytes.len() + chunk.len();
+        if let Some(limit) = self.limit
+            && total > limit
+        {
+            return Err(ResponseLimitError::TooLarge { limit });
+        }
+        if let Some(ratio) = self.max_ratio
+            && total > RATIO_CHECK_THRESHOLD
+            && total > self.compressed.saturating_mul(ratio as usize)
+        {
+            return Err(ResponseLimitError::DecompressionBomb { ratio });
+        }
+        self.bytes.extend_from_slice(chunk);
+        Ok(())
+    }
+}
+
+impl Write for BodyBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.push(buf) {
+            Ok(()) => Ok(buf.len()),
+            Err(e) => {
+                self.violation = Some(e);
+                Err(std::io::Error::other("response limit exceeded"))
+            }
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 按 Content-Encoding 边读边解压的响应体
+enum BodyDecoder {
+    /// 未压缩
+    Identity(BodyBuffer),
+    /// gzip
+    Gzip(flate2::write::GzDecoder<BodyBuffer>),
+    /// deflate（zlib 封装）
+    Deflate(flate2::write::ZlibDecoder<BodyBuffer>),
+    /// brotli
+    Brotli(Box<brotli::DecompressorWriter<BodyBuffer>>),
+}
+
+impl BodyDecoder {
+    /// 根据 Content-Encoding 创建解码器
+    fn new(
+        encoding: Option<&str>,
+        mut buffer: BodyBuffer,
+        max_ratio: Option<u32>,
+    ) -> std::result::Result<Self, ResponseLimitError> {
+        let encoding = encoding.map(|value| value.trim().to_ascii_lowercase());
+        let encoding = match encoding.as_deref() {
+            None | Some("") | Some("identity") => return Ok(Self::Identity(buffer)),
+            Some(encoding) => encoding,
+        };
+
+        buffer.max_ratio = max_ratio;
+        match encoding {
+            "gzip" | "x-gzip" => Ok(Self::Gzip(flate2::write::GzDecoder::new(buffer))),
+            "deflate" => Ok(Self::Deflate(flate2::write::ZlibDecoder::new(buffer))),
+            "br" => Ok(Self::Brotli(Box::new(brotli::DecompressorWriter::new(
+                buffer, 4096,
+            )))),
+            other => Err(ResponseLimitError::Decode(format!(
+                "unsupported Content-Encoding: {}",
+                other
+            ))),
+        }
+    }
+
+    /// 输出缓冲区
+    fn buffer_mut(&mut self) -> &mut BodyBuffer {
+        match self {
+            Self::Identity(buffer) => buffer,
+            Self::Gzip(decoder) => decoder.get_mut(),
+            Self::Deflate(decoder) => decoder.get_mut(),
+            Self::Brotli(decoder) => decoder.get_mut(),
+        }
+    }
+
+    /// 写入一个原始数据块
+    fn push(&mut self, chunk: &[u8]) -> std::result::Result<(), ResponseLimitError> {
+        let result = match self {
+            Self::Identity(buffer) => return buffer.push(chunk),
+            Self::Gzip(decoder) => {
+                decoder.get_mut().compressed += chunk.len();
+                decoder.write_all(chunk)
+            }
+            Self::Deflate(decoder) => {
+                decoder.get_mut().compressed += chunk.len();
+                decoder.write_all(chunk)
+            }
+            Self::Brotli(decoder) => {
+                decoder.get_mut().compressed += chunk.len();
+                decoder.write_all(chunk)
+            }
+        };
+        result.map_err(|e| self.decode_error(e))
+    }
+
+    /// 网络传输的字节数（压缩响应按压缩后大小计）
+    fn transferred(&mut self) -> usize {
+        match self {
+            Self::Identity(buffer) => buffer.bytes.len(),
+            other => other.buffer_mut().compressed,
+        }
+    }
+
+    /// 结束解压并返回解压后的字节
+    fn finish(mut self) -> std::result::Result<Vec<u8>, ResponseLimitError> {
+        let result = match &mut self {
+            Self::Identity(_) => Ok(()),
+            Self::Gzip(decoder) => decoder.try_finish(),
+            Self::Deflate(decoder) => decoder.try_finish(),
+            Self::Brotli(decoder) => decoder.flush(),
+        };
+        if let Err(e) = result {
+            return Err(self.decode_error(e));
+        }
+        Ok(std::mem::take(&mut self.buffer_mut().bytes))
+    }
+
+    /// 解压失败时优先返回缓冲区记录的限制错误
+    fn decode_error(&mut self, error: std::io::Error) -> ResponseLimitError {
+        self.buffer_mut()
+            .violation
+            .take()
+            .unwrap_or_else(|| ResponseLimitError::Decode(error.to_string()))
+    }
+}
+
+/// 读取响应头中的字符串值
+fn header_str(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// 内容是否像二进制数据（前缀中含 NUL 字节）
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(SNIFF_LEN)].contains(&0)
+}
+
+/// 读取完成的响应体
+#[derive(Debug, Clone)]
+pub struct ResponseBody {
+    /// 响应体字节（已解压）
+    pub bytes: Vec<u8>,
+    /// 网络传输的字节数（压缩响应按压缩后大小计）
+    pub transferred: usize,
+    /// Content-Type 头
+    pub content_type: Option<String>,
+}
+
+impl ResponseBody {
+    /// 按 Content-Type 中的字符集解码为文本
+    pub fn into_text(self) -> String {
+        decode_text(self.bytes, self.content_type.as_deref())
+    }
+}
+
+/// 按块读取响应体
+///
+/// 读取前检查 Content-Type；带 Content-Encoding 的响应边读取边解压，
+/// 大小上限作用于解压后的内容
+///
+/// # 参数
+///
+/// * `response` - HTTP 响应
+/// * `limits` - 响应限制
+///
+/// # 返回
+///
+/// 成功返回响应体及传输大小，超过限制或读取失败返回错误
+pub async fn read_body(mut response: Response, limits: &ResponseLimits) -> Result<ResponseBody> {
+    let content_type = header_str(response.headers(), CONTENT_TYPE);
+    limits.check_content_type(content_type.as_deref())?;
+
+    let encoding = header_str(response.headers(), CONTENT_ENCODING);
+    let buffer = BodyBuffer::new(response.content_length(), limits.max_bytes)?;
+    let mut decoder =
+        BodyDecoder::new(encoding.as_deref(), buffer, limits.max_decompression_ratio)?;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| crate::error::network_error(format!("Failed to read response: {}", e)))?
+    {
+        decoder.push(&chunk)?;
+    }
+    let transferred = decoder.transferred();
+    let bytes = decoder.finish()?;
+
+    if content_type.is_none() && !limits.allowed_content_types.is_empty() && looks_binary(&bytes) {
+        return Err(ResponseLimitError::BinaryContent.into());
+    }
+    Ok(ResponseBody {
+        bytes,
+        transferred,
+        content_type,
+    })
+}
+
+/// 按块读取响应体
+///
+/// # 参数
+///
+/// * `response` - HTTP 响应
+/// * `limits` - 响应限制
+///
+/// # 返回
+///
+/// 成功返回响应体字节，超过限制或读取失败返回错误
+pub async fn read_bytes(response: Response, limits: &ResponseLimits) -> Result<Vec<u8>> {
+    read_body(response, limits).await.map(|body| body.bytes)
+}
+
+/// 按块读取响应体并解码为文本
+///
+/// 字符集取自 Content-Type，缺省为 UTF-8；带 BOM 的响应以 BOM 为准
+///
+/// # 参数
+///
+/// * `response` - HTTP 响应
+/// * `limits` - 响应限制
+///
+/// # 返回
+///
+/// 成功返回解码后的文本，超过限制或读取失败返回错误
+pub async fn read_text(response: Response, limits: &ResponseLimits) -> Result<String> {
+    read_body(response, limits).await.map(ResponseBody::into_text)
+}
+
+/// 按字符集解码响应体
+///
+/// # 参数
+///
+/// * `bytes` - 响应体字节
+/// * `content_type` - Content-Type 头
+pub fn decode_text(bytes: Vec<u8>, content_type: Option<&str>) -> String {
+    let encoding = content_type
+        .and_then(charset)
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+
+    // 无 BOM 的 UTF-8 直接复用缓冲区，避免再复制一次
+    if encoding == UTF_8 && Encoding::for_bom(&bytes).is_none() {
+        return match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        };
+    }
+
+    let (text, _, _) = encoding.decode(&bytes);
+    text.into_owned()
+}
+
+/// 从 Content-Type 中提取字符集
+fn charset(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_buffer_limit() {
+        assert!(BodyBuffer::new(Some(11), Some(10)).is_err());
+
+        let mut buffer = BodyBuffer::new(None, Some(10)).unwrap();
+        buffer.push(b"hello").unwrap();
+        buffer.push(b"world").unwrap();
+        assert_eq!(
+            buffer.push(b"!"),
+            Err(ResponseLimitError::TooLarge { limit: 10 })
+        );
+        assert_eq!(buffer.bytes, b"helloworld");
+
+        let mut unlimited = BodyBuffer::new(Some(u64::MAX), None).unwrap();
+        unlimited.push(&[0; 64]).unwrap();
+    }
+
+    #[test]
+    fn test_content_type_allow_list() {
+        let limits = ResponseLimits::from(&ResponseConfig::default());
+        assert!(
+            limits
+                .check_content_type(Some("text/html; charset=utf-8"))
+                .is_ok()
+        );
+        assert!(limits.check_content_type(Some("application/json")).is_ok());
+        assert!(
+            limits
+                .check_content_type(Some("application/ld+json"))
+                .is_ok()
+        );
+        assert!(limits.check_content_type(None).is_ok());
+        assert_eq!(
+            limits.check_content_type(Some("image/png")),
+            Err(ResponseLimitError::ContentTypeNotAllowed {
+                content_type: "image/png".to_string()
+            })
+        );
+
+        assert!(
+            ResponseLimits::default()
+                .check_content_type(Some("image/png"))
+                .is_ok()
+        );
+    }
+
+    /// gzip 压缩数据
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// 分块写入解码器
+    fn decode(
+        encoding: &str,
+        data: &[u8],
+        limit: Option<usize>,
+        ratio: Option<u32>,
+    ) -> std::result::Result<Vec<u8>, ResponseLimitError> {
+        let buffer = BodyBuffer::new(None, limit).unwrap();
+        let mut decoder = BodyDecoder::new(Some(encoding), buffer, ratio)?;
+        for chunk in data.chunks(1024) {
+            decoder.push(chunk)?;
+        }
+        decoder.finish()
+    }
+
+    #[test]
+    fn test_gzip_decoding() {
+        let body = "搜索结果".repeat(100);
+        let decoded = decode("gzip", &gzip(body.as_bytes()), Some(4096), Some(100)).unwrap();
+        assert_eq!(decoded, body.as_bytes());
+
+        assert_eq!(
+            decode("gzip", &gzip(body.as_bytes()), Some(100), None),
+            Err(ResponseLimitError::TooLarge { limit: 100 })
+        );
+        assert!(matches!(
+            decode("gzip", b"not gzip", None, None),
+            Err(ResponseLimitError::Decode(_))
+        ));
+        assert!(matches!(
+            decode("zstd", b"", None, None),
+            Err(ResponseLimitError::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_decompression_bomb_rejected() {
+        let bomb = gzip(&vec![0; 8 * 1024 * 1024]);
+        assert_eq!(
+            decode("gzip", &bomb, None, Some(100)),
+            Err(ResponseLimitError::DecompressionBomb { ratio: 100 })
+        );
+        assert_eq!(
+            decode("gzip", &bomb, None, None).unwrap().len(),
+            8 * 1024 * 1024
+        );
+    }
+
+    #[test]
+    fn test_looks_binary() {
+        assert!(looks_binary(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"));
+        assert!(!looks_binary("<html>搜索</html>".as_bytes()));
+    }
+
+    #[test]
+    fn test_decode_text_charset() {
+        assert_eq!(
+            decode_text("搜索".as_bytes().to_vec(), Some("text/html")),
+            "搜索"
+        );
+
+        let (gbk, _, _) = encoding_rs::GBK.encode("搜索");
+        let text = decode_text(gbk.into_owned(), Some("text/html; charset=\"GBK\""));
+        assert_eq!(text, "搜索");
+
+        let mut bom = vec![0xEF, 0xBB, 0xBF];
+        bom.extend_from_slice(b"ok");
+        assert_eq!(decode_text(bom, None), "ok");
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! 响应捕获
+//!
+//! 在一个异步任务范围内记录经由 [`HttpClient`](super::HttpClient) 读取的原始响应与解析警告，
+//! 用于调试引擎选择器失效等问题。未处于捕获范围时记录操作为空操作
+
+use std::cell::RefCell;
+use std::future::Future;
+
+/// 每个响应最多保留的原始字节数
+pub const MAX_CAPTURED_BYTES: usize = 256 * 1024;
+
+tokio::task_local! {
+    static CAPTURE: RefCell<ResponseCapture>;
+}
+
+/// 捕获的单个响应
+#[derive(Debug, Clone)]
+pub struct CapturedResponse {
+    /// 最终请求 URL
+    pub url: String,
+    /// HTTP 状态码
+    pub status: u16,
+    /// Content-Type
+    pub content_type: Option<String>,
+    /// 原始响应体（解压后，最多 [`MAX_CAPTURED_BYTES`] 字节）
+    pub body: Vec<u8>,
+    /// 解压后的完整响应体大小（字节）
+    pub total_bytes: usize,
+}
+
+impl CapturedResponse {
+    /// 响应体是否被截断
+    pub fn is_truncated(&self) -> bool {
+        self.total_bytes > self.body.len()
+    }
+}
+
+/// 一个捕获范围内记录的全部内容
+#[derive(Debug, Clone, Default)]
+pub struct ResponseCapture {
+    /// 按读取顺序记录的响应
+    pub responses: Vec<CapturedResponse>,
+    /// 解析警告
+    pub warnings: Vec<String>,
+}
+
+/// 在捕获范围内执行 future
+///
+/// # 参数
+///
+/// * `future` - 要执行的 future（通常是一次引擎搜索）
+///
+/// # 返回
+///
+/// 返回 future 的输出与捕获到的响应、警告
+pub async fn capture_responses<F: Future>(future: F) -> (F::Output, ResponseCapture) {
+    CAPTURE
+        .scope(RefCell::new(ResponseCapture::default()), async {
+            let output = future.await;
+            (output, CAPTURE.with(RefCell::take))
+        })
+        .await
+}
+
+/// 当前任务是否处于捕获范围内
+pub fn is_capturing() -> bool {
+    CAPTURE.try_with(|_| ()).is_ok()
+}
+
+/// 记录解析警告（引擎解析器发现结构异常时调用）
+///
+/// # 参数
+///
+/// * `message` - 警告内容
+pub fn warn(message: impl Into<String>) {
+    let _ = CAPTURE.try_with(|capture| capture.borrow_mut().warnings.push(message.into()));
+}
+
+/// 记录一个响应
+pub(crate) fn record(url: String, status: u16, content_type: Option<String>, body: &[u8]) {
+    let _ = CAPTURE.try_with(|capture| {
+        capture.borrow_mut().responses.push(CapturedResponse {
+            url,
+            status,
+            content_type,
+            body: body[..body.len().min(MAX_CAPTURED_BYTES)].to_vec(),
+            total_bytes: body.len(),
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_capture_scope() {
+        assert!(!is_capturing());
+        record("https://example.com/".to_string(), 200, None, b"ignored");
+
+        let (value, capture) = capture_responses(async {
+            assert!(is_capturing());
+            record("https://example.com/".to_string(), 200, Some("text/html".to_string()), &[b'a'; MAX_CAPTURED_BYTES + 1]);
+            warn("missing title");
+            42
+        })
+        .await;
+
+        assert_eq!(value, 42);
+        assert_eq!(capture.responses.len(), 1);
+        assert!(capture.responses[0].is_truncated());
+        assert_eq!(capture.warnings, vec!["missing title".to_string()]);
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! HTTP/3 传输降级
+//!
+//! 启用 `http3` 特性与 `pool.http3` 后 HTTPS 请求先经 QUIC 发送；QUIC 连接或握手失败
+//! （如网络屏蔽 UDP 或源站不支持 HTTP/3）时请求尚未发出，改用 HTTP/2 / HTTP/1.1 重发，
+//! 并在一段时间内记住该源站，期间的请求直接经 TCP 发送。
+//! 连接建立后的失败不降级，避免重复发送非幂等请求
+
+use reqwest::{Request, Version};
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// HTTP/3 降级状态
+#[derive(Debug)]
+pub struct Http3Fallback {
+    /// QUIC 不可用的源站（`主机:端口`）及其恢复尝试 HTTP/3 的时间
+    unavailable: Mutex<HashMap<String, Instant>>,
+    /// 源站被记为不可用的时长
+    retry_after: Duration,
+}
+
+impl Http3Fallback {
+    /// 创建降级状态
+    ///
+    /// # 参数
+    ///
+    /// * `retry_after` - QUIC 握手失败的源站在该时长内直接经 TCP 请求
+    pub fn new(retry_after: Duration) -> Self {
+        Self {
+            unavailable: Mutex::new(HashMap::new()),
+            retry_after,
+        }
+    }
+
+    /// 将请求改为经 HTTP/3 发送
+    ///
+    /// # 参数
+    ///
+    /// * `request` - 待发送的请求
+    ///
+    /// # 返回
+    ///
+    /// HTTP/3 失败时用于重发的请求副本；不是 HTTPS、源站已知不支持 QUIC 或请求体无法复制时
+    /// 返回 None，请求保持原样
+    pub fn prepare(&self, request: &mut Request) -> Option<Request> {
+        if request.url().scheme() != "https" || self.is_unavailable(request.url()) {
+            return None;
+        }
+        let fallback = request.try_clone()?;
+        *request.version_mut() = Version::HTTP_3;
+        Some(fallback)
+    }
+
+    /// 记录源站暂时不支持 QUIC
+    pub fn mark_unavailable(&self, url: &Url) {
+        let mut unavailable = self.unavailable.lock().unwrap();
+        let now = Instant::now();
+        unavailable.retain(|_, until| *until > now);
+        unavailable.insert(origin(url), now + self.retry_after);
+    }
+
+    /// 源站是否已知暂时不支持 QUIC（记录过期后重新尝试 HTTP/3）
+    pub fn is_unavailable(&self, url: &Url) -> bool {
+        self.unavailable
+            .lock()
+            .unwrap()
+            .get(&origin(url))
+            .is_some_and(|until| *until > Instant::now())
+    }
+}
+
+/// 错误是否发生在 QUIC 连接建立阶段（此时请求尚未发出，可以安全地经 TCP 重发）
+///
+/// reqwest 把建立 QUIC 连接的错误直接作为（内层）请求错误的来源，而请求发出后的错误来源是 h3 的流错误，
+/// 因此只检查紧跟在 reqwest 错误之后的来源
+pub fn is_connect_error(error: &(dyn Error + 'static)) -> bool {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        let source = error.source();
+        if error.is::<reqwest::Error>() && source.is_some_and(is_quic_connect_failure) {
+            return true;
+        }
+        current = source;
+    }
+    false
+}
+
+#[cfg(feature = "http3")]
+fn is_quic_connect_failure(source: &(dyn Error + 'static)) -> bool {
+    source.is::<quinn::ConnectError>()
+        || source.is::<quinn::ConnectionError>()
+        // 同一源站的并发连接失败或没有可用地址时 reqwest 只给出该消息
+        || source.to_string() == "failed to establish connection for HTTP/3 request"
+}
+
+#[cfg(not(feature = "http3"))]
+fn is_quic_connect_failure(_source: &(dyn Error + 'static)) -> bool {
+    false
+}
+
+/// 源站标识
+fn origin(url: &Url) -> String {
+    format!(
+        "{}:{}",
+        url.host_str().unwrap_or_default(),
+        url.port_or_known_default().unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Method;
+
+    fn request(url: &str) -> Request {
+        Request::new(Method::GET, Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_prepare_https_request() {
+        let fallback = Http3Fallback::new(Duration::from_secs(60));
+        let mut req = request("https://example.com/a");
+        let retry = fallback.prepare(&mut req).unwrap();
+        assert_eq!(req.version(), Version::HTTP_3);
+        assert_ne!(retry.version(), Version::HTTP_3);
+        assert_eq!(retry.url(), req.url());
+    }
+
+    #[test]
+    fn test_prepare_skips_plain_http_and_unavailable_origins() {
+        let fallback = Http3Fallback::new(Duration::from_secs(60));
+        let mut req = request("http://example.com/a");
+        assert!(fallback.prepare(&mut req).is_none());
+        assert_ne!(req.version(), Version::HTTP_3);
+
+        fallback.mark_unavailable(&Url::parse("https://example.com/other").unwrap());
+        let mut req = request("https://example.com/a");
+        assert!(fallback.prepare(&mut req).is_none());
+        assert_ne!(req.version(), Version::HTTP_3);
+
+        // 端口不同视为不同源站
+        assert!(fallback.prepare(&mut request("https://example.com:8443/a")).is_some());
+    }
+
+    #[test]
+    fn test_unavailable_origins_expire() {
+        let fallback = Http3Fallback::new(Duration::ZERO);
+        let url = Url::parse("https://example.com/a").unwrap();
+        fallback.mark_unavailable(&url);
+        assert!(!fallback.is_unavailable(&url));
+        assert!(fallback.prepare(&mut request("https://example.com/a")).is_some());
+    }
+
+    #[test]
+    fn test_is_connect_error_ignores_other_errors() {
+        let error = std::io::Error::other("connection reset");
+        assert!(!is_connect_error(&error));
+    }
+}
@@ -16,26 +16,117 @@
 //!
 //! 提供基于 reqwest 的强大 HTTP 客户端封装
 
+pub mod body;
+pub mod capture;
+pub mod http3;
 pub mod pool;
 pub mod proxy;
+pub mod public;
 pub mod tls;
+pub mod trace;
 
+use crate::cache::page::{CachedPage, PageCache, DEFAULT_PAGE_TTL};
 use crate::error::Result;
 use crate::net::types::{NetworkConfig, RequestOptions};
+use crate::net::auth::EngineAuth;
+use crate::net::session::EngineSession;
+use crate::net::politeness::Politeness;
 use crate::net::privacy::PrivacyManager;
-use reqwest::{Client, ClientBuilder, Response};
+use crate::net::resolver::family;
+use reqwest::{Client, ClientBuilder, Request, RequestBuilder, Response};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+/// 下载流量记录器
+///
+/// 每成功读取一个响应体调用一次，`bytes` 为网络传输的字节数（压缩响应按压缩后大小计）
+pub trait BandwidthRecorder: Send + Sync {
+    /// 记录引擎下载的字节数
+    fn record(&self, engine_name: &str, bytes: u64);
+}
+
 /// HTTP 客户端封装
 #[derive(Clone)]
 pub struct HttpClient {
     /// 底层 reqwest 客户端
     client: Arc<Client>,
+    /// 不自动解压的 reqwest 客户端（启用解压比例检查时使用）
+    raw_client: Arc<Client>,
     /// 网络配置
     config: Arc<NetworkConfig>,
     /// 隐私管理器
     privacy_manager: Option<Arc<PrivacyManager>>,
+    /// 响应限制
+    limits: body::ResponseLimits,
+    /// 礼貌访问管理器（未启用时为 None）
+    politeness: Option<Arc<Politeness>>,
+    /// 页面缓存（未设置时不缓存）
+    page_cache: Option<PageCache>,
+    /// 下载流量记录器（未设置时不记录）
+    bandwidth: Option<Arc<dyn BandwidthRecorder>>,
+    /// 发起请求的引擎名称（用于查找礼貌访问策略与记录流量）
+    engine_name: Option<Arc<str>>,
+    /// 源地址重定向（源 -> 替代地址，未设置时按原地址请求）
+    origin_overrides: Option<Arc<HashMap<String, String>>>,
+    /// 引擎认证层（未设置时不附加凭据）
+    auth: Option<Arc<EngineAuth>>,
+    /// 引擎会话（未设置时不保持 Cookie 与粘性请求头）
+    session: Option<Arc<EngineSession>>,
+    /// HTTP/3 降级状态（未启用 HTTP/3 时为 None）
+    http3: Option<Arc<http3::Http3Fallback>>,
+}
+
+/// 按网络配置构建 reqwest 客户端
+///
+/// # 参数
+///
+/// * `config` - 网络配置
+/// * `decompress` - 是否由 reqwest 自动解压响应
+fn build_client(config: &NetworkConfig, decompress: bool) -> Result<Client> {
+    let mut builder = ClientBuilder::new();
+
+    // 配置连接池
+    builder = builder
+        .pool_max_idle_per_host(config.pool.max_idle_connections)
+        .pool_idle_timeout(Some(Duration::from_secs(config.pool.idle_timeout_secs)));
+
+    // 配置 HTTP/2
+    if config.pool.http2_only {
+        builder = builder.http2_prior_knowledge();
+    }
+
+    // 配置 HTTP/3：空闲超时同时限制 QUIC 握手时间，握手失败后尽快降级
+    #[cfg(feature = "http3")]
+    if config.pool.http3 {
+        builder = builder.http3_max_idle_timeout(Duration::from_secs(config.pool.http3_idle_timeout_secs));
+    }
+
+    // 配置连接地址族策略（Happy Eyeballs 由连接器内置实现）
+    if let Some(resolver) = family::FamilyResolver::for_strategy(config.connect_strategy) {
+        builder = builder.dns_resolver(Arc::new(resolver));
+    }
+
+    // 配置 TLS
+    builder = tls::configure_tls(builder, &config.tls)?;
+
+    // 配置代理
+    if config.proxy.enabled {
+        builder = proxy::configure_proxy(builder, &config.proxy)?;
+    }
+
+    // 配置隐私保护
+    builder = crate::net::privacy::headers::configure_privacy(builder, &config.privacy);
+
+    // 关闭自动解压，由 body 模块边读边解压
+    if !decompress {
+        builder = builder.no_gzip().no_brotli().no_deflate();
+    }
+
+    builder
+        .build()
+        .map_err(|e| crate::error::network_error(format!("Failed to build HTTP client: {}", e)))
 }
 
 impl HttpClient {
@@ -49,28 +140,17 @@ impl HttpClient {
     ///
     /// 成功返回配置好的 HttpClient，失败返回错误
     pub fn new(config: NetworkConfig) -> Result<Self> {
-        let mut builder = ClientBuilder::new();
-
-        // 配置连接池
-        builder = builder
-            .pool_max_idle_per_host(config.pool.max_idle_connections)
-            .pool_idle_timeout(Some(Duration::from_secs(config.pool.idle_timeout_secs)));
-
-        // 配置 HTTP/2
-        if config.pool.http2_only {
-            builder = builder.http2_prior_knowledge();
-        }
-
-        // 配置 TLS
-        builder = tls::configure_tls(builder, &config.tls)?;
-
-        // 配置代理
-        if config.proxy.enabled {
-            builder = proxy::configure_proxy(builder, &config.proxy)?;
-        }
-
-        // 配置隐私保护
-        builder = crate::net::privacy::headers::configure_privacy(builder, &config.privacy);
+        let http3 = if !config.pool.http3 {
+            None
+        } else if cfg!(feature = "http3") {
+            let retry_after = Duration::from_secs(config.pool.http3_retry_after_secs);
+            Some(Arc::new(http3::Http3Fallback::new(retry_after)))
+        } else {
+            tracing::warn!("HTTP/3 requested but this build lacks the `http3` feature, using HTTP/2 / HTTP/1.1");
+            None
+        };
+        let client = build_client(&config, true)?;
+        let raw_client = build_client(&config, false)?;
 
         // 创建隐私管理器
         let privacy_manager = Arc::new(PrivacyManager::new(
@@ -79,18 +159,302 @@ impl HttpClient {
             config.doh.clone(),
         ));
 
-        // 构建客户端
-        let client = builder
-            .build()
-            .map_err(|e| crate::error::network_error(format!("Failed to build HTTP client: {}", e)))?;
-
         Ok(Self {
             client: Arc::new(client),
+            raw_client: Arc::new(raw_client),
             config: Arc::new(config),
             privacy_manager: Some(privacy_manager),
+            limits: body::ResponseLimits::default(),
+            politeness: None,
+            page_cache: None,
+            bandwidth: None,
+            engine_name: None,
+            origin_overrides: None,
+            auth: None,
+            session: None,
+            http3,
+        })
+    }
+
+    /// 返回使用指定响应体上限的客户端
+    ///
+    /// 与原客户端共享连接池与隐私管理器
+    ///
+    /// # 参数
+    ///
+    /// * `limit` - 响应体大小上限（None 表示不限制）
+    pub fn with_max_response_bytes(&self, limit: Option<usize>) -> Self {
+        self.with_response_limits(body::ResponseLimits {
+            max_bytes: limit,
+            ..self.limits.clone()
         })
     }
 
+    /// 返回使用指定响应限制的客户端
+    ///
+    /// 与原客户端共享连接池与隐私管理器
+    ///
+    /// # 参数
+    ///
+    /// * `limits` - 响应限制
+    pub fn with_response_limits(&self, limits: body::ResponseLimits) -> Self {
+        Self {
+            limits,
+            ..self.clone()
+        }
+    }
+
+    /// 返回供指定引擎使用的客户端
+    ///
+    /// 引擎名称用于查找礼貌访问策略与记录下载流量
+    ///
+    /// # 参数
+    ///
+    /// * `engine_name` - 引擎名称
+    pub fn for_engine(&self, engine_name: &str) -> Self {
+        Self {
+            engine_name: Some(Arc::from(engine_name)),
+            ..self.clone()
+        }
+    }
+
+    /// 返回按礼貌访问策略限速的客户端
+    ///
+    /// 与原客户端共享连接池与隐私管理器
+    ///
+    /// # 参数
+    ///
+    /// * `politeness` - 礼貌访问管理器
+    pub fn with_politeness(&self, politeness: Arc<Politeness>) -> Self {
+        Self {
+            politeness: Some(politeness),
+            ..self.clone()
+        }
+    }
+
+    /// 返回使用页面缓存的客户端
+    ///
+    /// 只影响 [`fetch_page`](Self::fetch_page)，其余请求不经过缓存
+    ///
+    /// # 参数
+    ///
+    /// * `cache` - 页面缓存
+    pub fn with_page_cache(&self, cache: PageCache) -> Self {
+        Self {
+            page_cache: Some(cache),
+            ..self.clone()
+        }
+    }
+
+    /// 返回记录下载流量的客户端
+    ///
+    /// # 参数
+    ///
+    /// * `recorder` - 下载流量记录器
+    pub fn with_bandwidth_recorder(&self, recorder: Arc<dyn BandwidthRecorder>) -> Self {
+        Self {
+            bandwidth: Some(recorder),
+            ..self.clone()
+        }
+    }
+
+    /// 返回为请求附加引擎凭据的客户端
+    ///
+    /// # 参数
+    ///
+    /// * `auth` - 引擎认证层
+    pub fn with_auth(&self, auth: Arc<EngineAuth>) -> Self {
+        Self {
+            auth: Some(auth),
+            ..self.clone()
+        }
+    }
+
+    /// 返回保持引擎会话（Cookie 与粘性请求头）的客户端
+    ///
+    /// # 参数
+    ///
+    /// * `session` - 引擎会话
+    pub fn with_session(&self, session: Arc<EngineSession>) -> Self {
+        Self {
+            session: Some(session),
+            ..self.clone()
+        }
+    }
+
+    /// 返回将指定源的请求改发到另一地址的客户端
+    ///
+    /// 路径与查询参数保持不变，只替换 `scheme://host[:port]`；
+    /// 用于在测试中把引擎固定的服务地址指向本地模拟服务器
+    ///
+    /// # 参数
+    ///
+    /// * `origin` - 被替换的源，如 `https://www.bing.com`
+    /// * `target` - 替代地址，如 `http://127.0.0.1:8080`
+    pub fn with_origin_override(&self, origin: &str, target: &str) -> Self {
+        let mut overrides = self.origin_overrides.as_deref().cloned().unwrap_or_default();
+        overrides.insert(
+            origin.trim_end_matches('/').to_string(),
+            target.trim_end_matches('/').to_string(),
+        );
+        Self {
+            origin_overrides: Some(Arc::new(overrides)),
+            ..self.clone()
+        }
+    }
+
+    /// 按源地址重定向解析实际请求的 URL
+    fn resolve_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        let Some(overrides) = self.origin_overrides.as_deref() else {
+            return Cow::Borrowed(url);
+        };
+        let Ok(parsed) = url::Url::parse(url) else {
+            return Cow::Borrowed(url);
+        };
+        match overrides.get(&parsed.origin().ascii_serialization()) {
+            Some(target) => Cow::Owned(format!("{}{}", target, &parsed[url::Position::BeforePath..])),
+            None => Cow::Borrowed(url),
+        }
+    }
+
+    /// 响应体大小上限
+    pub fn max_response_bytes(&self) -> Option<usize> {
+        self.limits.max_bytes
+    }
+
+    /// 响应限制
+    pub fn response_limits(&self) -> &body::ResponseLimits {
+        &self.limits
+    }
+
+    /// 按块读取响应体并解码为文本，超过限制时中止
+    ///
+    /// # 参数
+    ///
+    /// * `response` - HTTP 响应
+    ///
+    /// # 返回
+    ///
+    /// 成功返回响应文本，超过限制或读取失败返回错误
+    pub async fn read_text(&self, response: Response) -> Result<String> {
+        let origin = capture::is_capturing()
+            .then(|| (response.url().to_string(), response.status().as_u16()));
+        let body = body::read_body(response, &self.limits).await?;
+        self.record_bandwidth(body.transferred);
+        if let Some((url, status)) = origin {
+            capture::record(url, status, body.content_type.clone(), &body.bytes);
+        }
+        Ok(body.into_text())
+    }
+
+    /// 获取页面，启用页面缓存时优先返回未过期的缓存
+    ///
+    /// 只缓存 2xx 响应；缓存读写失败不影响请求
+    ///
+    /// # 参数
+    ///
+    /// * `url` - 页面 URL
+    /// * `ttl` - 缓存时间（None 时使用默认缓存时间）
+    ///
+    /// # 返回
+    ///
+    /// 成功返回页面（可能来自缓存），请求或读取失败返回错误
+    pub async fn fetch_page(&self, url: &str, ttl: Option<Duration>) -> Result<CachedPage> {
+        if let Some(ref pages) = self.page_cache {
+            match pages.get_async(url).await {
+                Ok(Some(page)) => {
+                    trace::record_cache_hit();
+                    return Ok(page);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to read page cache for {}: {}", url, e),
+            }
+        }
+
+        let response = self.get(url, None).await?;
+        let status = response.status();
+        let origin = capture::is_capturing().then(|| response.url().to_string());
+        let body = body::read_body(response, &self.limits).await?;
+        self.record_bandwidth(body.transferred);
+        if let Some(origin) = origin {
+            capture::record(origin, status.as_u16(), body.content_type.clone(), &body.bytes);
+        }
+
+        let page = CachedPage::new(url, status.as_u16(), body.content_type, body.bytes, ttl.unwrap_or(DEFAULT_PAGE_TTL));
+        if let Some(ref pages) = self.page_cache
+            && status.is_success()
+            && let Err(e) = pages.put_async(page.clone()).await
+        {
+            tracing::warn!("Failed to cache page {}: {}", url, e);
+        }
+        Ok(page)
+    }
+
+    /// 记录本客户端所属引擎的下载流量
+    fn record_bandwidth(&self, bytes: usize) {
+        if let (Some(recorder), Some(engine_name)) = (&self.bandwidth, &self.engine_name) {
+            recorder.record(engine_name, bytes as u64);
+        }
+    }
+
+    /// 发送请求使用的 reqwest 客户端
+    ///
+    /// 启用解压比例检查时使用不自动解压的客户端，压缩响应交由 body 模块解压
+    fn request_client(&self) -> &Client {
+        if self.limits.max_decompression_ratio.is_some() {
+            &self.raw_client
+        } else {
+            &self.client
+        }
+    }
+
+    /// 启用礼貌访问时等待到允许请求 URL 所在域名
+    async fn wait_politely(&self, url: &str) {
+        if let Some(ref politeness) = self.politeness {
+            politeness
+                .wait(&self.client, url, self.engine_name.as_deref())
+                .await;
+        }
+    }
+
+    /// 发送请求，设置了引擎会话时附加并记录会话，设置了认证层时附加凭据
+    async fn send(&self, request: RequestBuilder) -> std::result::Result<Response, Box<dyn std::error::Error + Send + Sync>> {
+        let (client, request) = request.build_split();
+        let mut request = request?;
+        if let Some(ref session) = self.session {
+            session.apply(&mut request);
+        }
+        let attempt = self
+            .http3
+            .as_ref()
+            .and_then(|http3| http3.prepare(&mut request).map(|fallback| (http3, fallback)));
+        let response = match attempt {
+            Some((http3, fallback)) => match self.execute(&client, request).await {
+                Ok(response) => response,
+                // 只在 QUIC 连接失败（请求尚未发出）时降级，避免重复发送非幂等请求
+                Err(e) if http3::is_connect_error(&*e) => {
+                    tracing::debug!("QUIC connection to {} failed, retrying over HTTP/2 / HTTP/1.1: {}", fallback.url(), e);
+                    http3.mark_unavailable(fallback.url());
+                    self.execute(&client, fallback).await?
+                }
+                Err(e) => return Err(e),
+            },
+            None => self.execute(&client, request).await?,
+        };
+        if let Some(ref session) = self.session {
+            session.capture(response.url(), response.headers()).await;
+        }
+        Ok(response)
+    }
+
+    /// 经认证层（如有）发送单个请求
+    async fn execute(&self, client: &Client, request: Request) -> std::result::Result<Response, Box<dyn std::error::Error + Send + Sync>> {
+        match self.auth {
+            Some(ref auth) => auth.send(client, request).await,
+            None => Ok(client.execute(request).await?),
+        }
+    }
+
     /// 获取隐私管理器
     pub fn privacy_manager(&self) -> Option<&Arc<PrivacyManager>> {
         self.privacy_manager.as_ref()
@@ -108,8 +472,11 @@ impl HttpClient {
     /// 成功返回 HTTP 响应，失败返回错误
     pub async fn get(&self, url: &str, options: Option<RequestOptions>) -> Result<Response> {
         let opts = options.unwrap_or_default();
+        let url = self.resolve_url(url);
+        let url = url.as_ref();
+        trace::record_request(|| trace::request_key("GET", url, &[]));
         
-        let mut request = self.client
+        let mut request = self.request_client()
             .get(url)
             .timeout(opts.timeout);
 
@@ -126,10 +493,12 @@ impl HttpClient {
             request = request.header(&key, &value);
         }
 
+        self.wait_politely(url).await;
+
         // 发送请求
-        request
-            .send()
+        self.send(request)
             .await
+            .inspect(|response| trace::record_status(response.status().as_u16()))
             .map_err(|e| {
                 crate::error::network_error(format!("GET request failed: {}", e))
             })
@@ -148,8 +517,11 @@ impl HttpClient {
     /// 成功返回 HTTP 响应，失败返回错误
     pub async fn post(&self, url: &str, body: Vec<u8>, options: Option<RequestOptions>) -> Result<Response> {
         let opts = options.unwrap_or_default();
+        let url = self.resolve_url(url);
+        let url = url.as_ref();
+        trace::record_request(|| trace::request_key("POST", url, &body));
         
-        let mut request = self.client
+        let mut request = self.request_client()
             .post(url)
             .timeout(opts.timeout)
             .body(body);
@@ -167,10 +539,12 @@ impl HttpClient {
             request = request.header(&key, &value);
         }
 
+        self.wait_politely(url).await;
+
         // 发送请求
-        request
-            .send()
+        self.send(request)
             .await
+            .inspect(|response| trace::record_status(response.status().as_u16()))
             .map_err(|e| crate::error::network_error(format!("POST request failed: {}", e)))
     }
 
@@ -187,8 +561,11 @@ impl HttpClient {
     /// 成功返回 HTTP 响应，失败返回错误
     pub async fn post_json<T: serde::Serialize>(&self, url: &str, json: &T, options: Option<RequestOptions>) -> Result<Response> {
         let opts = options.unwrap_or_default();
+        let url = self.resolve_url(url);
+        let url = url.as_ref();
+        trace::record_request(|| trace::request_key("POST", url, &serde_json::to_vec(json).unwrap_or_default()));
         
-        let mut request = self.client
+        let mut request = self.request_client()
             .post(url)
             .timeout(opts.timeout)
             .json(json);
@@ -198,10 +575,12 @@ impl HttpClient {
             request = request.header(&key, &value);
         }
 
+        self.wait_politely(url).await;
+
         // 发送请求
-        request
-            .send()
+        self.send(request)
             .await
+            .inspect(|response| trace::record_status(response.status().as_u16()))
             .map_err(|e| crate::error::network_error(format!("POST JSON request failed: {}", e)))
     }
 
@@ -233,4 +612,116 @@ mod tests {
         let client = HttpClient::new(config.clone()).unwrap();
         assert_eq!(client.config().pool.max_idle_connections, config.pool.max_idle_connections);
     }
+
+    #[cfg(not(feature = "http3"))]
+    #[test]
+    fn test_http3_requires_feature() {
+        let mut config = NetworkConfig::default();
+        config.pool.http3 = true;
+        let client = HttpClient::new(config).unwrap();
+        assert!(client.config().pool.http3);
+        assert!(client.http3.is_none());
+    }
+
+    /// 只在 TCP 上提供 HTTPS 的本地服务器（没有 QUIC 监听），返回监听地址
+    #[cfg(feature = "http3")]
+    async fn spawn_tls_server() -> std::net::SocketAddr {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio_rustls::rustls::{self, pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer}};
+
+        const CERT: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls/localhost.crt"));
+        const KEY: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls/localhost.key"));
+        let config = rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from_pem_slice(CERT.as_bytes()).unwrap()],
+                PrivateKeyDer::from_pem_slice(KEY.as_bytes()).unwrap(),
+            )
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(mut stream) = acceptor.accept(stream).await else {
+                        return;
+                    };
+                    let mut buf = [0u8; 4096];
+                    let _ = stream.read(&mut buf).await;
+                    let _ = stream
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+                        .await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+        addr
+    }
+
+    #[cfg(feature = "http3")]
+    #[tokio::test]
+    async fn test_http3_falls_back_to_tcp() {
+        let addr = spawn_tls_server().await;
+        let mut config = NetworkConfig::default();
+        config.pool.http3 = true;
+        config.pool.http3_idle_timeout_secs = 1;
+        config.tls.verify_certificates = false;
+        let client = HttpClient::new(config).unwrap();
+        let url = format!("https://127.0.0.1:{}/", addr.port());
+
+        // QUIC 握手失败后经 TCP 重发
+        let response = client.get(&url, None).await.unwrap();
+        assert_ne!(response.version(), reqwest::Version::HTTP_3);
+        assert_eq!(response.text().await.unwrap(), "ok");
+        assert!(client.http3.as_ref().unwrap().is_unavailable(&url::Url::parse(&url).unwrap()));
+
+        // 已知不支持 QUIC 的源站直接经 TCP 请求，不再等待握手超时
+        let started = std::time::Instant::now();
+        let response = client.get(&url, None).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_with_max_response_bytes() {
+        let client = HttpClient::new(NetworkConfig::default()).unwrap();
+        assert_eq!(client.max_response_bytes(), Some(body::DEFAULT_MAX_RESPONSE_BYTES));
+
+        let limited = client.with_max_response_bytes(Some(1024));
+        assert_eq!(limited.max_response_bytes(), Some(1024));
+        assert!(Arc::ptr_eq(&client.client, &limited.client));
+    }
+
+    #[test]
+    fn test_with_response_limits() {
+        let client = HttpClient::new(NetworkConfig::default()).unwrap();
+        assert!(std::ptr::eq(client.request_client(), client.client.as_ref()));
+
+        let limits = body::ResponseLimits {
+            max_decompression_ratio: Some(100),
+            ..Default::default()
+        };
+        let limited = client.with_response_limits(limits.clone());
+        assert_eq!(limited.response_limits(), &limits);
+        assert!(std::ptr::eq(limited.request_client(), limited.raw_client.as_ref()));
+    }
+
+    #[test]
+    fn test_origin_override() {
+        let client = HttpClient::new(NetworkConfig::default())
+            .unwrap()
+            .with_origin_override("https://www.bing.com/", "http://127.0.0.1:8080");
+
+        assert_eq!(
+            client.resolve_url("https://www.bing.com/search?q=rust&first=11"),
+            "http://127.0.0.1:8080/search?q=rust&first=11"
+        );
+        assert_eq!(client.resolve_url("https://cn.bing.com/search"), "https://cn.bing.com/search");
+        assert!(matches!(client.resolve_url("not a url"), Cow::Borrowed(_)));
+    }
 }
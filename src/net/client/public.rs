@@ -0,0 +1,203 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 公网请求
+//!
+//! 代为访问搜索结果或用户提供的链接（缩略图代理、结果补全）时使用：
+//! 连接时由 [`PublicResolver`] 过滤解析结果，重定向逐跳手动跟随并检查目标，
+//! 公网主机解析到内网地址或重定向到内网地址都会被拒绝
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use reqwest::header::LOCATION;
+use url::Url;
+
+use crate::net::privacy::PrivacyManager;
+use crate::net::resolver::public::{is_public_ip, BlockedAddress, PublicResolver};
+
+/// 最多跟随的重定向次数
+pub const MAX_REDIRECTS: usize = 5;
+
+/// 只连接公网地址、不自动跟随重定向的 HTTP 客户端
+static PUBLIC_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+/// 公网请求错误
+#[derive(Debug, Clone, PartialEq, Eq, error_derive::Error)]
+pub enum PublicFetchError {
+    /// 目标（或某一跳重定向的目标）不是公网地址
+    #[error("{0} is not a public address")]
+    Blocked(String),
+    /// 请求失败或重定向无效
+    #[error("{0}")]
+    Request(String),
+}
+
+/// 是否允许连接该地址
+///
+/// 只接受 http/https；以 IP 表示的主机必须是公网地址，域名在建立连接时由 [`PublicResolver`] 过滤解析结果
+pub fn is_allowed_url(url: &Url) -> bool {
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return false;
+    }
+    match url.host() {
+        Some(url::Host::Domain(_)) => true,
+        Some(url::Host::Ipv4(ip)) => is_public_ip(ip.into()),
+        Some(url::Host::Ipv6(ip)) => is_public_ip(ip.into()),
+        None => false,
+    }
+}
+
+/// 获取公网请求专用的 HTTP 客户端
+fn public_client() -> reqwest::Result<&'static reqwest::Client> {
+    if let Some(client) = PUBLIC_CLIENT.get() {
+        return Ok(client);
+    }
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()?;
+    Ok(PUBLIC_CLIENT.get_or_init(|| client))
+}
+
+/// 从请求错误中找出被拒绝的非公网主机
+fn blocked_address(error: &reqwest::Error) -> Option<&BlockedAddress> {
+    let mut source = std::error::Error::source(error);
+    while let Some(error) = source {
+        if let Some(blocked) = error.downcast_ref::<BlockedAddress>() {
+            return Some(blocked);
+        }
+        source = error.source();
+    }
+    None
+}
+
+/// 发送只连接公网地址的 GET 请求并逐跳跟随重定向
+///
+/// 每一跳都检查目标地址，重定向到内网地址与直接请求内网地址一样被拒绝
+///
+/// # 参数
+///
+/// * `target` - 请求地址
+/// * `timeout` - 单跳请求超时
+/// * `privacy` - 隐私管理器（设置时每一跳都附加伪装请求头）
+///
+/// # 返回
+///
+/// 返回第一个非重定向响应；目标不是公网地址、请求失败或重定向过多时返回错误
+pub async fn get_public(
+    target: &str,
+    timeout: Duration,
+    privacy: Option<&PrivacyManager>,
+) -> Result<reqwest::Response, PublicFetchError> {
+    let client = public_client().map_err(|e| PublicFetchError::Request(e.to_string()))?;
+    get_checked(client, target, timeout, privacy, is_allowed_url).await
+}
+
+/// 逐跳发送 GET 请求，每一跳先用 `allowed` 检查目标
+async fn get_checked(
+    client: &reqwest::Client,
+    target: &str,
+    timeout: Duration,
+    privacy: Option<&PrivacyManager>,
+    allowed: impl Fn(&Url) -> bool,
+) -> Result<reqwest::Response, PublicFetchError> {
+    let request_error = |e: &dyn std::fmt::Display| PublicFetchError::Request(e.to_string());
+    let mut url = Url::parse(target).map_err(|e| request_error(&e))?;
+
+    for _ in 0..=MAX_REDIRECTS {
+        if !allowed(&url) {
+            return Err(PublicFetchError::Blocked(url.host_str().unwrap_or_default().to_string()));
+        }
+
+        let mut request = client.get(url.clone()).timeout(timeout);
+        if let Some(privacy) = privacy {
+            for (key, value) in privacy.get_privacy_headers(url.as_str()).await {
+                request = request.header(&key, &value);
+            }
+        }
+
+        let response = request.send().await.map_err(|e| match blocked_address(&e) {
+            Some(blocked) => PublicFetchError::Blocked(blocked.host.clone()),
+            None => request_error(&e),
+        })?;
+        if !matches!(response.status().as_u16(), 301 | 302 | 303 | 307 | 308) {
+            return Ok(response);
+        }
+
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| PublicFetchError::Request("Redirect response without Location".to_string()))?;
+        url = url.join(location).map_err(|e| request_error(&e))?;
+    }
+
+    Err(PublicFetchError::Request(format!("More than {} redirects", MAX_REDIRECTS)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_is_allowed_url() {
+        let allowed = |target: &str| is_allowed_url(&Url::parse(target).unwrap());
+        assert!(allowed("https://i.example.com/a.jpg"));
+        assert!(allowed("http://93.184.216.34/a.jpg"));
+        assert!(!allowed("http://127.0.0.1:8080/admin"));
+        assert!(!allowed("http://169.254.169.254/latest/meta-data/"));
+        assert!(!allowed("http://100.64.0.1/"));
+        assert!(!allowed("http://[::1]/a.jpg"));
+        assert!(!allowed("http://[::ffff:10.0.0.1]/a.jpg"));
+        assert!(!allowed("http://0x7f000001/a.jpg"));
+        assert!(!allowed("file:///etc/passwd"));
+    }
+
+    #[tokio::test]
+    async fn test_get_public_rejects_private_targets() {
+        let error = get_public("http://127.0.0.1:9/", Duration::from_secs(1), None).await.unwrap_err();
+        assert_eq!(error, PublicFetchError::Blocked("127.0.0.1".to_string()));
+
+        // 域名只解析到回环地址时在连接前被拒绝
+        let error = get_public("http://localhost:9/", Duration::from_secs(1), None).await.unwrap_err();
+        assert_eq!(error, PublicFetchError::Blocked("localhost".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_public_checks_redirect_targets() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket
+                .write_all(b"HTTP/1.1 302 Found\r\nLocation: http://169.254.169.254/\r\nContent-Length: 0\r\n\r\n")
+                .await;
+        });
+
+        // 首跳放行本地测试服务器，其余目标按公网规则检查
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        let allowed = |url: &Url| url.port() == Some(addr.port()) || is_allowed_url(url);
+        let error = get_checked(&client, &format!("http://{}/", addr), Duration::from_secs(1), None, allowed)
+            .await
+            .unwrap_err();
+        assert_eq!(error, PublicFetchError::Blocked("169.254.169.254".to_string()));
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! 请求追踪
+//!
+//! 在一个异步任务范围内统计经由 [`HttpClient`](super::HttpClient) 发出的请求与页面缓存命中，
+//! 用于在搜索响应中给出每个引擎的请求明细。未处于追踪范围时记录操作为空操作
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::future::Future;
+
+tokio::task_local! {
+    static TRACE: RefCell<RequestTrace>;
+}
+
+/// 一个追踪范围内记录的请求
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestTrace {
+    /// 按发送顺序记录的请求标识（方法、URL 与请求体摘要）
+    pub requests: Vec<String>,
+    /// 页面缓存命中次数
+    pub cache_hits: u32,
+    /// 被目标站点拒绝（HTTP 403/429）的响应数
+    pub blocked_responses: u32,
+}
+
+impl RequestTrace {
+    /// 重试次数，即相同请求（方法、URL 与请求体均相同）的重复发送次数
+    pub fn retries(&self) -> u32 {
+        let unique: HashSet<&String> = self.requests.iter().collect();
+        (self.requests.len() - unique.len()) as u32
+    }
+
+    /// 是否完全由缓存提供（命中缓存且没有发出网络请求）
+    pub fn served_from_cache(&self) -> bool {
+        self.cache_hits > 0 && self.requests.is_empty()
+    }
+
+    /// 是否有请求被目标站点拒绝（通常意味着触发了反爬或地区封锁）
+    pub fn blocked(&self) -> bool {
+        self.blocked_responses > 0
+    }
+}
+
+/// 在追踪范围内执行 future
+///
+/// # 参数
+///
+/// * `future` - 要执行的 future（通常是一次引擎搜索）
+///
+/// # 返回
+///
+/// 返回 future 的输出与追踪到的请求
+pub async fn trace_requests<F: Future>(future: F) -> (F::Output, RequestTrace) {
+    TRACE
+        .scope(RefCell::new(RequestTrace::default()), async {
+            let output = future.await;
+            (output, TRACE.with(RefCell::take))
+        })
+        .await
+}
+
+/// 记录一次请求，`key` 仅在追踪范围内求值
+pub(crate) fn record_request(key: impl FnOnce() -> String) {
+    let _ = TRACE.try_with(|trace| trace.borrow_mut().requests.push(key()));
+}
+
+/// 记录一次页面缓存命中
+pub(crate) fn record_cache_hit() {
+    let _ = TRACE.try_with(|trace| trace.borrow_mut().cache_hits += 1);
+}
+
+/// 记录响应状态码，403 与 429 计为被拒绝
+pub(crate) fn record_status(status: u16) {
+    if matches!(status, 403 | 429) {
+        let _ = TRACE.try_with(|trace| trace.borrow_mut().blocked_responses += 1);
+    }
+}
+
+/// 请求标识：方法、URL 与请求体摘要
+pub(crate) fn request_key(method: &str, url: &str, body: &[u8]) -> String {
+    format!("{} {} {:016x}", method, url, xxhash_rust::xxh3::xxh3_64(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_trace_scope() {
+        record_request(|| unreachable!("not tracing"));
+
+        let (value, trace) = trace_requests(async {
+            record_request(|| request_key("GET", "https://example.com/a", b""));
+            record_request(|| request_key("GET", "https://example.com/a", b""));
+            record_request(|| request_key("POST", "https://example.com/a", b"x"));
+            record_request(|| request_key("POST", "https://example.com/a", b"y"));
+            42
+        })
+        .await;
+        assert_eq!(value, 42);
+        assert_eq!(trace.requests.len(), 4);
+        assert_eq!(trace.retries(), 1);
+        assert!(!trace.served_from_cache());
+
+        let ((), trace) = trace_requests(async { record_cache_hit() }).await;
+        assert!(trace.served_from_cache());
+        assert_eq!(trace.retries(), 0);
+        assert!(!trace.blocked());
+
+        let ((), trace) = trace_requests(async {
+            record_status(200);
+            record_status(429);
+        })
+        .await;
+        assert!(trace.blocked());
+    }
+}
@@ -21,18 +21,27 @@
 //! - DNS over HTTPS (DoH)
 //! - 隐私保护特性（User-Agent 轮换、请求头伪造等）
 //! - 连接池管理
+//! - 礼貌访问（robots.txt Crawl-delay 与按域名的请求间隔）
+//! - 引擎认证（Bearer、Basic、Digest、API 密钥与 OAuth 2.0 令牌刷新）
+//! - 引擎会话保持（Cookie 与粘性请求头，可持久化到元数据缓存）
 
 pub mod types;
 pub mod client;
 pub mod privacy;
 pub mod resolver;
+pub mod politeness;
+pub mod auth;
+pub mod session;
 pub mod on;
 
 // 导出核心类型
 pub use types::{
     NetworkConfig, ProxyConfig, ProxyType, TlsConfig, TlsFingerprintLevel,
-    DohConfig, PrivacyConfig, UserAgentStrategy, PoolConfig, RequestOptions,
+    DohConfig, PrivacyConfig, UserAgentStrategy, PoolConfig, RequestOptions, ConnectStrategy,
 };
 
 pub use on::NetworkInterface;
 pub use client::HttpClient;
+pub use politeness::{Politeness, PolitenessPolicy};
+pub use auth::EngineAuth;
+pub use session::EngineSession;
@@ -0,0 +1,317 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 礼貌访问
+//!
+//! 按域名缓存 robots.txt 中的 Crawl-delay，并保证对同一域名的两次请求之间保持最小间隔。
+//! 只读取 Crawl-delay，不按 Disallow 拒绝请求：搜索结果页通常被 robots.txt 禁止抓取，
+//! 遵守 Disallow 会让抓取类引擎完全不可用
+
+use crate::config::PolitenessConfig;
+use crate::net::client::body::{self, ResponseLimits};
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// robots.txt 的大小上限
+const MAX_ROBOTS_BYTES: usize = 512 * 1024;
+
+/// 获取 robots.txt 的超时时间
+const ROBOTS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 单个引擎的礼貌访问策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolitenessPolicy {
+    /// 同一域名两次请求之间的最小间隔
+    pub min_delay: Duration,
+    /// 是否遵守 robots.txt 中的 Crawl-delay
+    pub respect_crawl_delay: bool,
+}
+
+/// 缓存的 robots.txt 信息
+#[derive(Debug, Clone, Copy)]
+struct RobotsEntry {
+    /// Crawl-delay（未声明或获取失败时为 None）
+    crawl_delay: Option<Duration>,
+    /// 获取时间
+    fetched_at: Instant,
+}
+
+/// 礼貌访问管理器
+///
+/// 在同一个搜索接口的所有引擎客户端之间共享，按域名（含端口）记录下一次允许请求的时间
+#[derive(Debug)]
+pub struct Politeness {
+    /// 全局配置
+    config: PolitenessConfig,
+    /// 各引擎的策略（未登记的引擎使用默认策略）
+    policies: RwLock<HashMap<String, PolitenessPolicy>>,
+    /// 按域名缓存的 robots.txt
+    robots: RwLock<HashMap<String, RobotsEntry>>,
+    /// 各域名下一次允许请求的时间
+    next_slots: Mutex<HashMap<String, Instant>>,
+}
+
+impl Politeness {
+    /// 创建礼貌访问管理器
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 全局礼貌访问配置
+    pub fn new(config: PolitenessConfig) -> Self {
+        Self {
+            config,
+            policies: RwLock::new(HashMap::new()),
+            robots: RwLock::new(HashMap::new()),
+            next_slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 全局配置
+    pub fn config(&self) -> &PolitenessConfig {
+        &self.config
+    }
+
+    /// 按全局配置得到的默认策略
+    pub fn default_policy(&self) -> PolitenessPolicy {
+        PolitenessPolicy {
+            min_delay: Duration::from_millis(self.config.min_delay_ms),
+            respect_crawl_delay: self.config.respect_crawl_delay,
+        }
+    }
+
+    /// 登记引擎的策略
+    ///
+    /// # 参数
+    ///
+    /// * `engine_name` - 引擎名称
+    /// * `policy` - 礼貌访问策略
+    pub fn set_policy(&self, engine_name: &str, policy: PolitenessPolicy) {
+        self.policies
+            .write()
+            .unwrap()
+            .insert(engine_name.to_string(), policy);
+    }
+
+    /// 获取引擎的策略（未登记时返回默认策略）
+    pub fn policy(&self, engine_name: Option<&str>) -> PolitenessPolicy {
+        engine_name
+            .and_then(|name| self.policies.read().unwrap().get(name).copied())
+            .unwrap_or_else(|| self.default_policy())
+    }
+
+    /// 等待到允许向 URL 所在域名发起请求
+    ///
+    /// # 参数
+    ///
+    /// * `client` - 获取 robots.txt 使用的客户端（需自动解压）
+    /// * `url` - 即将请求的 URL
+    /// * `engine_name` - 发起请求的引擎（None 时使用默认策略）
+    ///
+    /// # 返回
+    ///
+    /// 实际等待的时间，URL 无法解析时不等待
+    pub async fn wait(&self, client: &Client, url: &str, engine_name: Option<&str>) -> Duration {
+        let Some(origin) = origin(url) else {
+            return Duration::ZERO;
+        };
+        let policy = self.policy(engine_name);
+
+        let mut delay = policy.min_delay;
+        if policy.respect_crawl_delay
+            && let Some(crawl_delay) = self.crawl_delay(client, &origin).await
+        {
+            delay = delay.max(crawl_delay.min(Duration::from_secs(self.config.max_crawl_delay_secs)));
+        }
+
+        let slot = self.reserve(&origin, delay);
+        let waited = slot.saturating_duration_since(Instant::now());
+        if !waited.is_zero() {
+            tokio::time::sleep_until(slot.into()).await;
+        }
+        waited
+    }
+
+    /// 预约域名的下一个请求时间
+    ///
+    /// 返回本次请求可以发出的时间，并把之后的请求顺延 `delay`
+    fn reserve(&self, origin: &str, delay: Duration) -> Instant {
+        let now = Instant::now();
+        let mut slots = self.next_slots.lock().unwrap();
+        let slot = slots.get(origin).map_or(now, |next| (*next).max(now));
+        slots.insert(origin.to_string(), slot + delay);
+        slot
+    }
+
+    /// 获取域名的 Crawl-delay（带缓存）
+    async fn crawl_delay(&self, client: &Client, origin: &str) -> Option<Duration> {
+        let ttl = Duration::from_secs(self.config.robots_cache_ttl_secs);
+        if let Some(entry) = self.robots.read().unwrap().get(origin)
+            && entry.fetched_at.elapsed() < ttl
+        {
+            return entry.crawl_delay;
+        }
+
+        let crawl_delay = self
+            .fetch_robots(client, origin)
+            .await
+            .and_then(|robots| parse_crawl_delay(&robots, &self.config.robots_user_agent));
+        self.robots.write().unwrap().insert(
+            origin.to_string(),
+            RobotsEntry {
+                crawl_delay,
+                fetched_at: Instant::now(),
+            },
+        );
+        crawl_delay
+    }
+
+    /// 获取 robots.txt，失败或非 2xx 响应返回 None
+    async fn fetch_robots(&self, client: &Client, origin: &str) -> Option<String> {
+        let response = client
+            .get(format!("{}/robots.txt", origin))
+            .timeout(ROBOTS_TIMEOUT)
+            .send()
+            .await
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let limits = ResponseLimits {
+            max_bytes: Some(MAX_ROBOTS_BYTES),
+            ..Default::default()
+        };
+        body::read_text(response, &limits).await.ok()
+    }
+}
+
+/// 提取 URL 的源（协议、主机与端口）
+fn origin(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    Some(url.origin().ascii_serialization())
+}
+
+/// 解析 robots.txt 中适用于指定 User-agent 的 Crawl-delay
+///
+/// 优先使用名称匹配（不区分大小写的子串匹配）的分组，没有时使用 `*` 分组
+///
+/// # 参数
+///
+/// * `robots` - robots.txt 内容
+/// * `user_agent` - 匹配 User-agent 分组时使用的名称
+pub fn parse_crawl_delay(robots: &str, user_agent: &str) -> Option<Duration> {
+    let user_agent = user_agent.to_ascii_lowercase();
+    let mut agents: Vec<String> = Vec::new();
+    let mut in_rules = false;
+    let mut specific = None;
+    let mut wildcard = None;
+
+    for line in robots.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim().to_ascii_lowercase(), value.trim());
+
+        if key == "user-agent" {
+            // 规则之后的 User-agent 开始新的分组
+            if in_rules {
+                agents.clear();
+                in_rules = false;
+            }
+            agents.push(value.to_ascii_lowercase());
+            continue;
+        }
+
+        in_rules = true;
+        if key != "crawl-delay" {
+            continue;
+        }
+        let Some(delay) = value
+            .parse::<f64>()
+            .ok()
+            .and_then(|delay| Duration::try_from_secs_f64(delay).ok())
+        else {
+            continue;
+        };
+        for agent in &agents {
+            if agent == "*" {
+                wildcard.get_or_insert(delay);
+            } else if !user_agent.is_empty() && user_agent.contains(agent.as_str()) {
+                specific.get_or_insert(delay);
+            }
+        }
+    }
+
+    specific.or(wildcard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_crawl_delay() {
+        let robots = "\
+User-agent: *
+Disallow: /search
+Crawl-delay: 2
+
+User-agent: Googlebot
+User-agent: SeeSea
+Crawl-delay: 0.5 # 半秒
+";
+        assert_eq!(parse_crawl_delay(robots, "SeeSea"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_crawl_delay(robots, "OtherBot"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_crawl_delay("User-agent: *\nDisallow: /", "SeeSea"), None);
+        assert_eq!(parse_crawl_delay("User-agent: *\nCrawl-delay: soon", "SeeSea"), None);
+    }
+
+    #[test]
+    fn test_reserve_spaces_requests_per_origin() {
+        let politeness = Politeness::new(PolitenessConfig::default());
+        let delay = Duration::from_secs(1);
+
+        let first = politeness.reserve("https://www.bing.com", delay);
+        let second = politeness.reserve("https://www.bing.com", delay);
+        let other = politeness.reserve("https://yandex.com", delay);
+        assert_eq!(second - first, delay);
+        assert!(other < second);
+    }
+
+    #[test]
+    fn test_policy_lookup() {
+        let politeness = Politeness::new(PolitenessConfig::default());
+        let custom = PolitenessPolicy {
+            min_delay: Duration::from_millis(200),
+            respect_crawl_delay: false,
+        };
+        politeness.set_policy("github", custom);
+
+        assert_eq!(politeness.policy(Some("github")), custom);
+        assert_eq!(politeness.policy(Some("bing")), politeness.default_policy());
+        assert_eq!(politeness.policy(None), politeness.default_policy());
+    }
+
+    #[test]
+    fn test_origin() {
+        assert_eq!(origin("https://www.bing.com/search?q=rust").as_deref(), Some("https://www.bing.com"));
+        assert_eq!(origin("http://localhost:8080/a").as_deref(), Some("http://localhost:8080"));
+        assert_eq!(origin("data:text/plain,hi"), None);
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 地址族偏好解析
+//!
+//! 按 `ConnectStrategy` 重排解析出的地址，供 HTTP 客户端的连接器按顺序尝试
+
+use crate::net::types::ConnectStrategy;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::SocketAddr;
+
+/// 按地址族偏好排序的 DNS 解析器（使用系统 DNS）
+#[derive(Debug, Clone, Copy)]
+pub struct FamilyResolver {
+    /// 是否优先 IPv6
+    prefer_ipv6: bool,
+}
+
+impl FamilyResolver {
+    /// 按连接策略创建解析器
+    ///
+    /// # 参数
+    ///
+    /// * `strategy` - 连接地址族策略
+    ///
+    /// # 返回
+    ///
+    /// Happy Eyeballs 由连接器内置实现，返回 None；其余策略返回对应的解析器
+    pub fn for_strategy(strategy: ConnectStrategy) -> Option<Self> {
+        match strategy {
+            ConnectStrategy::PreferIpv4 => Some(Self { prefer_ipv6: false }),
+            ConnectStrategy::PreferIpv6 => Some(Self { prefer_ipv6: true }),
+            ConnectStrategy::HappyEyeballs => None,
+        }
+    }
+}
+
+impl Resolve for FamilyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let prefer_ipv6 = self.prefer_ipv6;
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            let addrs: Addrs = Box::new(order_addresses(addrs, prefer_ipv6).into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// 将首选地址族的地址排在前面，同族地址保持解析顺序
+///
+/// 连接器先依次尝试首选地址族，迟迟未建立连接时再并行尝试另一地址族
+///
+/// # 参数
+///
+/// * `addrs` - 解析出的地址
+/// * `prefer_ipv6` - 是否优先 IPv6
+pub fn order_addresses(addrs: Vec<SocketAddr>, prefer_ipv6: bool) -> Vec<SocketAddr> {
+    let (mut preferred, fallback): (Vec<_>, Vec<_>) =
+        addrs.into_iter().partition(|addr| addr.is_ipv6() == prefer_ipv6);
+    preferred.extend(fallback);
+    preferred
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addrs() -> Vec<SocketAddr> {
+        vec![
+            "[2001:db8::1]:0".parse().unwrap(),
+            "192.0.2.1:0".parse().unwrap(),
+            "[2001:db8::2]:0".parse().unwrap(),
+            "192.0.2.2:0".parse().unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_order_addresses_prefers_family() {
+        let ordered = order_addresses(addrs(), false);
+        let ips: Vec<String> = ordered.iter().map(|addr| addr.ip().to_string()).collect();
+        assert_eq!(ips, vec!["192.0.2.1", "192.0.2.2", "2001:db8::1", "2001:db8::2"]);
+
+        let ordered = order_addresses(addrs(), true);
+        assert!(ordered[..2].iter().all(SocketAddr::is_ipv6));
+        assert!(ordered[2..].iter().all(SocketAddr::is_ipv4));
+    }
+
+    #[test]
+    fn test_for_strategy() {
+        assert!(FamilyResolver::for_strategy(ConnectStrategy::HappyEyeballs).is_none());
+        assert!(FamilyResolver::for_strategy(ConnectStrategy::PreferIpv4).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_localhost() {
+        let resolver = FamilyResolver::for_strategy(ConnectStrategy::PreferIpv4).unwrap();
+        let addrs: Vec<SocketAddr> = resolver.resolve("localhost".parse().unwrap()).await.unwrap().collect();
+        assert!(!addrs.is_empty());
+        if addrs.iter().any(SocketAddr::is_ipv4) {
+            assert!(addrs[0].is_ipv4());
+        }
+    }
+}
@@ -17,7 +17,9 @@
 //! 提供 DNS 解析、DNS over HTTPS (DoH) 支持
 
 pub mod doh;
+pub mod family;
 pub mod pool;
+pub mod public;
 
 use crate::error::Result;
 use crate::net::types::DohConfig;
@@ -0,0 +1,152 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 公网地址解析
+//!
+//! 代为访问用户提供的链接（如缩略图代理）时只允许连接公网地址，
+//! 拒绝回环、私有、链路本地与保留地址，防止服务器被用来探测内网（SSRF）
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// 目标主机只解析出非公网地址
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedAddress {
+    /// 被拒绝的主机名
+    pub host: String,
+}
+
+impl std::fmt::Display for BlockedAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} 只解析到非公网地址", self.host)
+    }
+}
+
+impl std::error::Error for BlockedAddress {}
+
+/// 只返回公网地址的 DNS 解析器（使用系统 DNS）
+///
+/// 在建立连接时过滤地址，对重定向后的主机同样生效，也不受 DNS 重绑定影响；
+/// 直接以 IP 表示的主机不经过解析器，需由调用方用 [`is_public_ip`] 检查
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(Box::new(BlockedAddress { host: name.as_str().to_string() }) as _);
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// 判断地址是否为可公开访问的单播地址
+///
+/// # 参数
+///
+/// * `ip` - 待检查的地址
+///
+/// # 返回
+///
+/// 回环、私有、运营商级 NAT、链路本地、组播、文档示例与其他保留地址返回 false；
+/// IPv4 映射与 NAT64 形式的 IPv6 地址按内嵌的 IPv4 地址判断
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        // 100.64.0.0/10 运营商级 NAT
+        || (a == 100 && (b & 0xc0) == 64)
+        // 192.0.0.0/24 IETF 协议分配
+        || (a == 192 && b == 0 && c == 0)
+        // 198.18.0.0/15 基准测试
+        || (a == 198 && (b & 0xfe) == 18)
+        // 240.0.0.0/4 保留
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_public_ipv4(v4);
+    }
+    let segments = ip.segments();
+    // 64:ff9b::/96 NAT64
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        let [_, _, _, _, _, _, high, low] = segments;
+        return is_public_ipv4(Ipv4Addr::from(((high as u32) << 16) | low as u32));
+    }
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local()
+        // fec0::/10 已废弃的站点本地地址
+        || (segments[0] & 0xffc0) == 0xfec0
+        // 2001:db8::/32 文档示例
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+        // ::/96 已废弃的 IPv4 兼容地址
+        || segments[..6] == [0; 6])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public(ip: &str) -> bool {
+        is_public_ip(ip.parse().unwrap())
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["93.184.216.34", "1.1.1.1", "2606:4700::1111", "::ffff:1.1.1.1", "64:ff9b::101:101"] {
+            assert!(public(ip), "{} should be public", ip);
+        }
+        for ip in [
+            "127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1",
+            "0.0.0.0", "255.255.255.255", "224.0.0.1", "198.18.0.1", "240.0.0.1", "192.0.0.8",
+            "::1", "::", "fe80::1", "fd00::1", "fec0::1", "ff02::1", "2001:db8::1",
+            "::ffff:127.0.0.1", "::ffff:169.254.169.254", "64:ff9b::a00:1", "::7f00:1",
+        ] {
+            assert!(!public(ip), "{} should be blocked", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_rejects_loopback() {
+        let error = PublicResolver.resolve("localhost".parse().unwrap()).await.err().unwrap();
+        assert_eq!(
+            error.downcast_ref::<BlockedAddress>(),
+            Some(&BlockedAddress { host: "localhost".to_string() })
+        );
+    }
+}
@@ -0,0 +1,289 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 引擎会话保持
+//!
+//! 记录响应中设置的 Cookie 与配置的粘性响应头，并在之后发往同一引擎的请求中带上。
+//! 设置了会话存储时，每次会话变化后写入元数据缓存，创建时从缓存恢复，
+//! 使同意 Cookie、偏好 Cookie 等预热状态在进程重启后依然有效。
+//! Cookie 只按域名匹配，不区分 Path 与 Secure 属性
+
+use crate::cache::session::{EngineSessionData, EngineSessionStore, SessionCookie};
+use crate::config::engines::EngineSessionConfig;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, COOKIE, SET_COOKIE};
+use reqwest::Request;
+use std::sync::Mutex;
+use std::time::Duration;
+use url::Url;
+
+/// 单个引擎的会话
+pub struct EngineSession {
+    /// 引擎名称（会话存储的键）
+    engine_name: String,
+    /// 会话有效期
+    ttl: Duration,
+    /// 需要粘性保持的响应头
+    sticky_headers: Vec<HeaderName>,
+    /// 当前会话数据
+    data: Mutex<EngineSessionData>,
+    /// 会话存储（未设置时只保存在内存中）
+    store: Option<EngineSessionStore>,
+}
+
+impl EngineSession {
+    /// 创建引擎会话，设置了存储时从存储恢复
+    ///
+    /// # 参数
+    ///
+    /// * `engine_name` - 引擎名称
+    /// * `config` - 会话配置
+    /// * `store` - 会话存储（None 表示不持久化）
+    pub fn new(engine_name: &str, config: &EngineSessionConfig, store: Option<EngineSessionStore>) -> Self {
+        let data = store
+            .as_ref()
+            .and_then(|store| match store.load(engine_name) {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::warn!("恢复引擎 {} 的会话失败: {}", engine_name, e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        let sticky_headers = config
+            .sticky_headers
+            .iter()
+            .filter_map(|name| HeaderName::from_bytes(name.trim().as_bytes()).ok())
+            .collect();
+
+        Self {
+            engine_name: engine_name.to_string(),
+            ttl: Duration::from_secs(config.ttl_secs),
+            sticky_headers,
+            data: Mutex::new(data),
+            store,
+        }
+    }
+
+    /// 当前会话数据的快照
+    pub fn snapshot(&self) -> EngineSessionData {
+        self.data.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 为请求附加会话中的 Cookie 与粘性请求头
+    ///
+    /// 请求中已有的同名 Cookie 与请求头保持不变
+    pub fn apply(&self, request: &mut Request) {
+        let Some(host) = request.url().host_str().map(str::to_ascii_lowercase) else {
+            return;
+        };
+        let now = chrono::Utc::now().timestamp();
+        let data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+
+        let headers = request.headers_mut();
+        for (name, value) in &data.headers {
+            if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value))
+                && !headers.contains_key(&name)
+            {
+                headers.insert(name, value);
+            }
+        }
+
+        let existing = headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| pair.split_once('=').map(|(name, _)| name.trim().to_string()))
+            .collect::<Vec<_>>();
+        let mut pairs = headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        for (domain, cookies) in &data.cookies {
+            if !domain_matches(&host, domain) {
+                continue;
+            }
+            for (name, cookie) in cookies {
+                if !cookie.is_expired(now) && !existing.contains(name) {
+                    pairs.push(format!("{}={}", name, cookie.value));
+                }
+            }
+        }
+        if let Ok(value) = HeaderValue::from_str(&pairs.join("; "))
+            && !pairs.is_empty()
+        {
+            headers.insert(COOKIE, value);
+        }
+    }
+
+    /// 从响应中记录 Cookie 与粘性响应头，会话变化时写入存储
+    ///
+    /// # 参数
+    ///
+    /// * `url` - 响应的最终地址（用于确定 Cookie 所属域名）
+    /// * `headers` - 响应头
+    pub async fn capture(&self, url: &Url, headers: &HeaderMap) {
+        let Some(host) = url.host_str().map(str::to_ascii_lowercase) else {
+            return;
+        };
+        let now = chrono::Utc::now().timestamp();
+
+        let snapshot = {
+            let mut data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+            let before = data.clone();
+
+            for value in headers.get_all(SET_COOKIE).iter().filter_map(|value| value.to_str().ok()) {
+                let Some((domain, name, cookie)) = parse_set_cookie(value, &host, now) else {
+                    continue;
+                };
+                if cookie.is_expired(now) {
+                    if let Some(cookies) = data.cookies.get_mut(&domain) {
+                        cookies.remove(&name);
+                    }
+                } else {
+                    data.cookies.entry(domain).or_default().insert(name, cookie);
+                }
+            }
+            for name in &self.sticky_headers {
+                if let Some(value) = headers.get(name).and_then(|value| value.to_str().ok()) {
+                    data.headers.insert(name.as_str().to_string(), value.to_string());
+                }
+            }
+            data.remove_expired(now);
+
+            if *data == before {
+                return;
+            }
+            data.clone()
+        };
+
+        if let Some(ref store) = self.store
+            && let Err(e) = store.save_async(&self.engine_name, snapshot, self.ttl).await
+        {
+            tracing::warn!("保存引擎 {} 的会话失败: {}", self.engine_name, e);
+        }
+    }
+}
+
+/// 主机名是否属于 Cookie 域名（相同或为其子域名）
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// 解析 Set-Cookie 响应头
+///
+/// # 返回值
+///
+/// 返回（所属域名, Cookie 名, Cookie）；Domain 与请求主机不匹配或格式错误时返回 None。
+/// Max-Age 优先于 Expires，过期时间不晚于当前时间表示删除该 Cookie
+fn parse_set_cookie(header: &str, host: &str, now: i64) -> Option<(String, String, SessionCookie)> {
+    let mut parts = header.split(';');
+    let (name, value) = parts.next()?.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut domain = host.to_string();
+    let mut max_age = None;
+    let mut expires = None;
+    for attribute in parts {
+        let (key, attr_value) = attribute.split_once('=').unwrap_or((attribute, ""));
+        let attr_value = attr_value.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "domain" if !attr_value.is_empty() => {
+                domain = attr_value.trim_start_matches('.').to_ascii_lowercase();
+            }
+            "max-age" => max_age = attr_value.parse::<i64>().ok(),
+            "expires" => {
+                expires = chrono::DateTime::parse_from_rfc2822(attr_value)
+                    .ok()
+                    .map(|time| time.timestamp());
+            }
+            _ => {}
+        }
+    }
+    if !domain_matches(host, &domain) {
+        return None;
+    }
+
+    let expires_at = match max_age {
+        Some(max_age) => Some(now.saturating_add(max_age)),
+        None => expires,
+    };
+    let cookie = SessionCookie {
+        value: value.trim().trim_matches('"').to_string(),
+        expires_at,
+    };
+    Some((domain, name.to_string(), cookie))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(sticky_headers: &[&str]) -> EngineSession {
+        let config = EngineSessionConfig {
+            enabled: true,
+            sticky_headers: sticky_headers.iter().map(|name| name.to_string()).collect(),
+            ..Default::default()
+        };
+        EngineSession::new("test", &config, None)
+    }
+
+    #[tokio::test]
+    async fn test_capture_and_apply() {
+        let session = session(&["X-Session-Id"]);
+        let url = Url::parse("https://www.example.com/search").unwrap();
+        let mut headers = HeaderMap::new();
+        headers.append(SET_COOKIE, HeaderValue::from_static("CONSENT=YES+1; Domain=.example.com; Path=/; Max-Age=3600"));
+        headers.append(SET_COOKIE, HeaderValue::from_static("pref=dark; Path=/"));
+        headers.append(SET_COOKIE, HeaderValue::from_static("tracker=1; Domain=other.com"));
+        headers.insert("x-session-id", HeaderValue::from_static("abc"));
+        session.capture(&url, &headers).await;
+
+        let client = reqwest::Client::new();
+        let mut request = client
+            .get("https://api.example.com/")
+            .header(COOKIE, "pref=light")
+            .build()
+            .unwrap();
+        session.apply(&mut request);
+        assert_eq!(request.headers()[COOKIE], "pref=light; CONSENT=YES+1");
+        assert_eq!(request.headers()["x-session-id"], "abc");
+
+        let mut request = client.get("https://www.example.com/").build().unwrap();
+        session.apply(&mut request);
+        assert_eq!(request.headers()[COOKIE], "CONSENT=YES+1; pref=dark");
+
+        let mut headers = HeaderMap::new();
+        headers.append(SET_COOKIE, HeaderValue::from_static("CONSENT=; Domain=example.com; Max-Age=0"));
+        session.capture(&url, &headers).await;
+        assert!(!session.snapshot().cookies.contains_key("example.com"));
+    }
+
+    #[test]
+    fn test_parse_set_cookie_expires() {
+        let (domain, name, cookie) =
+            parse_set_cookie("id=1; Expires=Thu, 01 Jan 1970 00:00:10 GMT", "example.com", 100).unwrap();
+        assert_eq!((domain.as_str(), name.as_str()), ("example.com", "id"));
+        assert!(cookie.is_expired(100));
+        assert!(parse_set_cookie("id=1; Domain=evil.com", "example.com", 0).is_none());
+    }
+}
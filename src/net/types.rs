@@ -179,6 +179,27 @@ pub struct PoolConfig {
     pub idle_timeout_secs: u64,
     /// 是否启用 HTTP/2
     pub http2_only: bool,
+    /// 是否优先使用 HTTP/3（QUIC）
+    ///
+    /// 需要启用 `http3` 特性；QUIC 连接或握手失败、构建不含该特性时降级到 HTTP/2 / HTTP/1.1
+    #[serde(default)]
+    pub http3: bool,
+    /// HTTP/3 连接的空闲超时（秒），同时限制 QUIC 握手的等待时间
+    #[serde(default = "default_http3_idle_timeout_secs")]
+    pub http3_idle_timeout_secs: u64,
+    /// QUIC 握手失败的源站在多少秒内直接经 TCP 请求，之后重新尝试 HTTP/3
+    #[serde(default = "default_http3_retry_after_secs")]
+    pub http3_retry_after_secs: u64,
+}
+
+/// 默认 HTTP/3 空闲超时：QUIC 被屏蔽时约 5 秒后降级
+fn default_http3_idle_timeout_secs() -> u64 {
+    5
+}
+
+/// 默认 HTTP/3 重新尝试间隔：10 分钟
+fn default_http3_retry_after_secs() -> u64 {
+    600
 }
 
 impl Default for PoolConfig {
@@ -188,6 +209,9 @@ impl Default for PoolConfig {
             max_connections_per_host: 50,     // 增加到50
             idle_timeout_secs: 300,           // 增加到5分钟
             http2_only: false,
+            http3: false,
+            http3_idle_timeout_secs: default_http3_idle_timeout_secs(),
+            http3_retry_after_secs: default_http3_retry_after_secs(),
         }
     }
 }
@@ -222,6 +246,21 @@ impl Default for RequestOptions {
     }
 }
 
+/// 连接地址族策略
+///
+/// 部分网络的 IPv6 不可用，优先尝试 IPv6 会使请求一直等到超时
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectStrategy {
+    /// 优先连接 IPv4 地址，IPv6 作为后备
+    PreferIpv4,
+    /// 优先连接 IPv6 地址，IPv4 作为后备
+    PreferIpv6,
+    /// Happy Eyeballs（RFC 8305）：按系统解析顺序连接，首选地址族未及时建立连接时并行尝试另一地址族
+    #[default]
+    HappyEyeballs,
+}
+
 /// 网络层配置（总配置）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConfig {
@@ -235,6 +274,9 @@ pub struct NetworkConfig {
     pub privacy: PrivacyConfig,
     /// 连接池配置
     pub pool: PoolConfig,
+    /// 连接地址族策略
+    #[serde(default)]
+    pub connect_strategy: ConnectStrategy,
 }
 
 impl Default for NetworkConfig {
@@ -245,6 +287,7 @@ impl Default for NetworkConfig {
             doh: DohConfig::default(),
             privacy: PrivacyConfig::default(),
             pool: PoolConfig::default(),
+            connect_strategy: ConnectStrategy::default(),
         }
     }
 }
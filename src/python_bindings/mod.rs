@@ -19,12 +19,18 @@ pub mod py_api;
 #[cfg(feature = "python")]
 pub mod py_search;
 #[cfg(feature = "python")]
+pub mod py_async_search;
+#[cfg(feature = "python")]
 pub mod py_config;
 #[cfg(feature = "python")]
 pub mod py_cache;
 #[cfg(feature = "python")]
 pub mod py_rss;
 #[cfg(feature = "python")]
+pub mod py_hot;
+#[cfg(feature = "python")]
 pub mod py_browser;
 #[cfg(feature = "python")]
 pub mod py_engine_registry;
+#[cfg(feature = "python")]
+pub mod py_result_processor;
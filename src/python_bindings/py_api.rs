@@ -70,7 +70,7 @@ impl PyApiServer {
         self.runtime.block_on(async {
             let listener = tokio::net::TcpListener::bind(&addr).await
                 .map_err(|e| format!("Failed to bind: {}", e))?;
-            axum::serve(listener, app).await
+            axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>()).await
                 .map_err(|e| format!("Server error: {}", e))
         }).map_err(|e: String| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e))
     }
@@ -0,0 +1,193 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Async Python bindings for search functionality
+//!
+//! 方法返回 asyncio 协程，搜索在 pyo3-async-runtimes 管理的 Tokio 运行时中执行，
+//! 不阻塞调用线程
+
+use pyo3::prelude::*;
+use std::sync::Arc;
+use tracing::Instrument;
+
+use crate::derive::SearchQuery;
+use crate::search::engine_config::EngineMode;
+use crate::search::{generate_request_id, request_span, SearchConfig, SearchInterface, SearchRequest};
+
+use super::py_search::{engine_result_to_py, response_to_py, validated_session};
+
+/// 异步搜索客户端
+#[pyclass]
+pub struct PyAsyncSearchClient {
+    interface: Arc<SearchInterface>,
+}
+
+/// 构建搜索请求（指定引擎时使用自定义模式，否则使用全局模式）
+fn build_request(
+    query: String,
+    page: Option<usize>,
+    page_size: Option<usize>,
+    language: Option<String>,
+    region: Option<String>,
+    engines: Option<Vec<String>>,
+    force: Option<bool>,
+) -> (SearchRequest, EngineMode) {
+    let search_query = SearchQuery {
+        query,
+        page: page.unwrap_or(1),
+        page_size: page_size.unwrap_or(10),
+        language,
+        region,
+        ..Default::default()
+    };
+
+    let (engines_to_use, mode) = match engines {
+        Some(engines) => (engines.clone(), EngineMode::Custom(engines)),
+        None => (vec![], EngineMode::Global),
+    };
+
+    let request = SearchRequest {
+        query: search_query,
+        engines: engines_to_use,
+        timeout: None,
+        max_results: None,
+        force: force.unwrap_or(false),
+        cache_timeline: None,
+        translate_to: None,
+        highlight: false,
+        debug_capture: false,
+        max_latency_ms: None,
+        explain: false,
+        session: None,
+    };
+    (request, mode)
+}
+
+/// 搜索错误转换为 Python 异常
+fn search_error(e: Box<dyn std::error::Error + Send + Sync>) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Search failed: {}", e))
+}
+
+#[pymethods]
+impl PyAsyncSearchClient {
+    /// 创建异步搜索客户端
+    #[new]
+    pub fn new() -> PyResult<Self> {
+        let interface = pyo3_async_runtimes::tokio::get_runtime()
+            .block_on(async { SearchInterface::new(SearchConfig::default()) })
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                format!("Failed to create search interface: {}", e)
+            ))?;
+
+        Ok(Self {
+            interface: Arc::new(interface),
+        })
+    }
+
+    /// 执行搜索，返回协程
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 搜索查询
+    /// * `page` - 页码（可选）
+    /// * `page_size` - 每页大小（可选）
+    /// * `language` - 语言（可选）
+    /// * `region` - 地区（可选）
+    /// * `engines` - 指定引擎列表（可选）
+    /// * `force` - 绕过缓存（可选）
+    /// * `session` - 搜索会话令牌（可选，相同令牌的查询共享上下文）
+    pub fn search<'py>(
+        &self,
+        py: Python<'py>,
+        query: String,
+        page: Option<usize>,
+        page_size: Option<usize>,
+        language: Option<String>,
+        region: Option<String>,
+        engines: Option<Vec<String>>,
+        force: Option<bool>,
+        session: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let (mut request, mode) = build_request(query, page, page_size, language, region, engines, force);
+        request.session = session.map(validated_session).transpose()?;
+        let interface = Arc::clone(&self.interface);
+
+        let request_id = generate_request_id();
+        let span = request_span(&request_id);
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut response = match mode {
+                EngineMode::Custom(_) => interface.search(&request).await,
+                mode => interface.search_with_mode(&request, mode).await,
+            }
+            .map_err(search_error)?;
+            response.request_id = Some(request_id);
+            Python::attach(|py| response_to_py(py, &response))
+        }.instrument(span))
+    }
+
+    /// 流式搜索，返回协程
+    ///
+    /// 每个引擎完成时在运行时线程上调用 `callback(result)`（回调应尽快返回），
+    /// 协程完成时返回最终聚合的搜索结果
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 搜索查询
+    /// * `callback` - 每个引擎完成时调用的函数
+    /// * `page` - 页码（可选）
+    /// * `page_size` - 每页大小（可选）
+    /// * `engines` - 指定引擎列表（可选）
+    pub fn search_streaming<'py>(
+        &self,
+        py: Python<'py>,
+        query: String,
+        callback: Py<PyAny>,
+        page: Option<usize>,
+        page_size: Option<usize>,
+        engines: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let (request, _) = build_request(query, page, page_size, None, None, engines, None);
+        let interface = Arc::clone(&self.interface);
+
+        let request_id = generate_request_id();
+        let span = request_span(&request_id);
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut response = interface
+                .search_streaming(&request, move |result, engine_name| {
+                    Python::attach(|py| {
+                        if let Ok(result_dict) = engine_result_to_py(py, &engine_name, &result) {
+                            let _ = callback.call1(py, (result_dict,));
+                        }
+                    });
+                })
+                .await
+                .map_err(search_error)?;
+            response.request_id = Some(request_id);
+            Python::attach(|py| response_to_py(py, &response))
+        }.instrument(span))
+    }
+
+    /// 查询建议（拼写纠正），返回协程
+    pub fn suggest<'py>(&self, py: Python<'py>, query: String) -> PyResult<Bound<'py, PyAny>> {
+        let interface = Arc::clone(&self.interface);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move { Ok(interface.suggest(&query)) })
+    }
+
+    /// 列出所有可用引擎
+    pub fn list_engines(&self) -> Vec<String> {
+        self.interface.list_engines()
+    }
+}
@@ -15,11 +15,12 @@
 //! Python bindings for cache
 
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyBytes, PyDict};
 use pyo3::IntoPyObjectExt;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::cache::{CacheInterface, CacheImplConfig, CacheMode};
+use crate::cache::{CacheError, CacheInterface, CacheImplConfig, CacheMode, CachedPage, DEFAULT_PAGE_TTL};
 
 #[pyclass]
 #[derive(Clone)]
@@ -127,4 +128,73 @@ impl PyCacheInterface {
                 format!("Failed to cleanup cache: {}", e)
             ))
     }
+
+    /// 获取缓存的页面
+    ///
+    /// 浏览器引擎与 Rust 端搜索引擎共享同一页面缓存
+    ///
+    /// # 参数
+    ///
+    /// * `url` - 页面 URL
+    ///
+    /// # 返回
+    ///
+    /// 未过期时返回包含 url、status、content_type、body、text、fetched_at、expires_at 的字典，否则返回 None
+    pub fn get_page(&self, py: Python<'_>, url: &str) -> PyResult<Option<Py<PyAny>>> {
+        let page = self.cache.pages().and_then(|pages| pages.get(url)).map_err(page_error)?;
+        let Some(page) = page else {
+            return Ok(None);
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("url", &page.url)?;
+        dict.set_item("status", page.status)?;
+        dict.set_item("content_type", &page.content_type)?;
+        dict.set_item("text", page.text())?;
+        dict.set_item("body", PyBytes::new(py, &page.body))?;
+        dict.set_item("fetched_at", page.fetched_at)?;
+        dict.set_item("expires_at", page.expires_at)?;
+        Ok(Some(dict.into_py_any(py)?))
+    }
+
+    /// 缓存页面
+    ///
+    /// # 参数
+    ///
+    /// * `url` - 页面 URL
+    /// * `body` - 页面内容（bytes 或 str）
+    /// * `content_type` - Content-Type
+    /// * `status` - HTTP 状态码
+    /// * `ttl_secs` - 缓存时间（秒，默认 1 小时）
+    #[pyo3(signature = (url, body, content_type=None, status=200, ttl_secs=None))]
+    pub fn put_page(
+        &self,
+        url: &str,
+        body: &Bound<'_, PyAny>,
+        content_type: Option<String>,
+        status: u16,
+        ttl_secs: Option<u64>,
+    ) -> PyResult<()> {
+        let body = match body.cast::<PyBytes>() {
+            Ok(bytes) => bytes.as_bytes().to_vec(),
+            Err(_) => body.extract::<String>()?.into_bytes(),
+        };
+        let ttl = ttl_secs.map_or(DEFAULT_PAGE_TTL, Duration::from_secs);
+        let page = CachedPage::new(url, status, content_type, body, ttl);
+        self.cache.pages().and_then(|pages| pages.put(&page)).map_err(page_error)
+    }
+
+    /// 删除缓存的页面
+    ///
+    /// # 返回
+    ///
+    /// 页面存在时返回 True
+    pub fn delete_page(&self, url: &str) -> PyResult<bool> {
+        self.cache.pages().and_then(|pages| pages.delete(url)).map_err(page_error)
+    }
+}
+
+/// 页面缓存错误转换为 Python 异常
+fn page_error(e: CacheError) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Page cache error: {}", e))
 }
@@ -17,10 +17,14 @@
 //! 提供从Python端动态注册搜索引擎的功能
 
 use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
 use pyo3::types::PyDict;
-use std::sync::Arc;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use async_trait::async_trait;
 
 use crate::derive::{SearchEngine, SearchQuery, SearchResult, EngineInfo, EngineType, EngineStatus, EngineCapabilities, ResultType};
@@ -49,12 +53,42 @@ where
     }
 }
 
+/// 请求频率限制的统计窗口
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// 回调协程使用的专用事件循环（首次需要时在后台线程中启动）
+static ENGINE_EVENT_LOOP: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+/// 获取专用事件循环
+///
+/// 事件循环运行在 Python 守护线程中，不阻塞解释器退出
+pub(crate) fn dedicated_event_loop(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+    let event_loop = ENGINE_EVENT_LOOP.get_or_try_init(py, || -> PyResult<Py<PyAny>> {
+        let event_loop = py.import("asyncio")?.call_method0("new_event_loop")?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("target", event_loop.getattr("run_forever")?)?;
+        kwargs.set_item("name", "seesea-python-engines")?;
+        kwargs.set_item("daemon", true)?;
+        py.import("threading")?
+            .call_method("Thread", (), Some(&kwargs))?
+            .call_method0("start")?;
+        Ok(event_loop.unbind())
+    })?;
+    Ok(event_loop.bind(py).clone())
+}
+
 /// Python引擎包装器
 ///
 /// 实现SearchEngine trait，通过Python回调执行实际搜索
 pub struct PythonEngineWrapper {
     info: EngineInfo,
     callback: Arc<RwLock<Option<Py<PyAny>>>>,
+    /// 支持的语言（空表示不限制）
+    languages: Vec<String>,
+    /// 运行协程回调的事件循环（None 时使用专用事件循环）
+    event_loop: Option<Py<PyAny>>,
+    /// 最近一个统计窗口内的请求时间
+    recent_requests: Mutex<VecDeque<Instant>>,
 }
 
 impl PythonEngineWrapper {
@@ -103,7 +137,62 @@ impl PythonEngineWrapper {
                 tokens: vec![],
             },
             callback: Arc::new(RwLock::new(None)),
+            languages: vec![],
+            event_loop: None,
+            recent_requests: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// 应用Python端声明的能力
+    ///
+    /// # 参数
+    ///
+    /// * `capabilities` - 能力字典，支持的键：
+    ///   `pagination`、`max_page_size`、`max_page`、`languages`、`rate_limit`（每分钟请求数，0 表示不限制）、
+    ///   `time_range`、`region`、`safe_search`、`timeout`（秒）
+    ///
+    /// # 返回
+    ///
+    /// 包含未知键或值类型错误时返回 ValueError / TypeError
+    pub fn with_capabilities(mut self, capabilities: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let caps = &mut self.info.capabilities;
+        for (key, value) in capabilities.iter() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "pagination" => caps.supports_pagination = value.extract()?,
+                "max_page_size" => caps.max_page_size = value.extract()?,
+                "max_page" => self.info.max_page = value.extract()?,
+                "languages" => self.languages = value.extract()?,
+                "rate_limit" => {
+                    let limit: Option<usize> = value.extract()?;
+                    caps.rate_limit = limit.filter(|limit| *limit > 0);
+                }
+                "time_range" => caps.supports_time_range = value.extract()?,
+                "region" => caps.supports_region_filter = value.extract()?,
+                "safe_search" => caps.supports_safe_search = value.extract()?,
+                "timeout" => self.info.timeout = value.extract()?,
+                _ => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        format!("Unknown engine capability: {}", key)
+                    ));
+                }
+            }
         }
+        caps.supports_language_filter = !self.languages.is_empty();
+        Ok(self)
+    }
+
+    /// 设置运行协程回调的事件循环
+    ///
+    /// 事件循环必须在回调执行期间保持运行（例如调用方自己的 asyncio 循环）
+    pub fn with_event_loop(mut self, event_loop: Py<PyAny>) -> Self {
+        self.event_loop = Some(event_loop);
+        self
+    }
+
+    /// 支持的语言
+    pub fn languages(&self) -> &[String] {
+        &self.languages
     }
 
     /// 设置Python回调函数
@@ -111,6 +200,89 @@ impl PythonEngineWrapper {
         let mut cb = self.callback.write().await;
         *cb = Some(callback);
     }
+
+    /// 记录一次请求，超出频率限制时返回 false
+    fn try_acquire_rate_limit(&self) -> bool {
+        let Some(limit) = self.info.capabilities.rate_limit else {
+            return true;
+        };
+        let Ok(mut recent) = self.recent_requests.lock() else {
+            return true;
+        };
+
+        let now = Instant::now();
+        while recent.front().is_some_and(|sent| now.duration_since(*sent) >= RATE_LIMIT_WINDOW) {
+            recent.pop_front();
+        }
+        if recent.len() >= limit {
+            return false;
+        }
+        recent.push_back(now);
+        true
+    }
+
+    /// 构建传给回调的查询参数
+    fn query_dict<'py>(&self, py: Python<'py>, query: &SearchQuery) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("query", &query.query)?;
+        dict.set_item("page", query.page)?;
+        dict.set_item("page_size", query.page_size.min(self.info.capabilities.max_page_size))?;
+
+        if let Some(ref lang) = query.language {
+            dict.set_item("language", lang)?;
+        }
+        if let Some(ref region) = query.region {
+            dict.set_item("region", region)?;
+        }
+        Ok(dict)
+    }
+
+    /// 解析回调返回的结果字典
+    fn parse_result(&self, result: &Bound<'_, PyAny>) -> PyResult<SearchResult> {
+        let result_dict = result.cast::<PyDict>()?;
+
+        // 提取结果列表
+        let items = if let Ok(Some(results_list)) = result_dict.get_item("results") {
+            results_list.extract::<Vec<HashMap<String, String>>>()?
+                .into_iter()
+                .map(|item| {
+                    crate::derive::SearchResultItem {
+                        title: item.get("title").cloned().unwrap_or_default(),
+                        url: item.get("url").cloned().unwrap_or_default(),
+                        content: item.get("snippet").or(item.get("content")).cloned().unwrap_or_default(),
+                        display_url: item.get("display_url").cloned(),
+                        site_name: item.get("site_name").cloned(),
+                        result_type: ResultType::Web,
+                        thumbnail: item.get("thumbnail").cloned(),
+                        metadata: HashMap::new(),
+                        published_date: None,
+                        score: 1.0,
+                        template: None,
+                    }
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Ok(SearchResult {
+            engine_name: self.info.name.clone(),
+            total_results: Some(items.len()),
+            elapsed_ms: 0,
+            pagination: None,
+            suggestions: vec![],
+            metadata: HashMap::new(),
+            items,
+        })
+    }
+}
+
+/// 回调的一次调用结果
+pub(crate) enum CallbackOutcome {
+    /// 同步回调的返回值
+    Ready(Py<PyAny>),
+    /// 协程回调，在事件循环中等待完成
+    Pending(Pin<Box<dyn Future<Output = PyResult<Py<PyAny>>> + Send>>),
 }
 
 #[async_trait]
@@ -120,86 +292,49 @@ impl SearchEngine for PythonEngineWrapper {
     }
 
     async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn std::error::Error + Send + Sync>> {
-        let callback_guard = self.callback.read().await;
-        
-        if let Some(ref callback) = *callback_guard {
-            // 调用Python回调函数
-            let result = Python::attach(|py| -> PyResult<SearchResult> {
-                let dict = PyDict::new(py);
-                dict.set_item("query", &query.query)?;
-                dict.set_item("page", query.page)?;
-                dict.set_item("page_size", query.page_size)?;
-                
-                if let Some(ref lang) = query.language {
-                    dict.set_item("language", lang)?;
-                }
-                if let Some(ref region) = query.region {
-                    dict.set_item("region", region)?;
-                }
-                
-                // 调用Python函数
-                let py_result = callback.call1(py, (dict,))?;
-
-                // 检查结果是否是协程，如果是则运行它
-                let final_result = if py_result.getattr(py, "__await__").is_ok() {
-                    // 这是一个协程，需要运行它
-                    Python::attach(|py_inner| -> PyResult<Py<PyAny>> {
-                        // 运行协程到完成
-                        let coro = py_result.clone_ref(py_inner);
-
-                        // 使用 asyncio.run() 来运行协程
-                        let asyncio = py_inner.import("asyncio")?;
-                        let run_result = asyncio.call_method1("run", (coro,))?;
-                        Ok(run_result.into())
-                    }).map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                        format!("Failed to run async callback: {}", e)
-                    ))?
-                } else {
-                    py_result.into()
-                };
-                
-                // 解析结果
-                let result_dict = final_result.bind(py).cast::<PyDict>()?;
-                
-                // 提取结果列表
-                let items = if let Ok(Some(results_list)) = result_dict.get_item("results") {
-                    results_list.extract::<Vec<HashMap<String, String>>>()?
-                        .into_iter()
-                        .map(|item| {
-                            crate::derive::SearchResultItem {
-                                title: item.get("title").cloned().unwrap_or_default(),
-                                url: item.get("url").cloned().unwrap_or_default(),
-                                content: item.get("snippet").or(item.get("content")).cloned().unwrap_or_default(),
-                                display_url: item.get("display_url").cloned(),
-                                site_name: item.get("site_name").cloned(),
-                                result_type: ResultType::Web,
-                                thumbnail: item.get("thumbnail").cloned(),
-                                metadata: HashMap::new(),
-                                published_date: None,
-                                score: 1.0,
-                                template: None,
-                            }
-                        })
-                        .collect()
-                } else {
-                    vec![]
-                };
-                
-                Ok(SearchResult {
-                    engine_name: self.info.name.clone(),
-                    total_results: Some(items.len()),
-                    elapsed_ms: 0,
-                    pagination: None,
-                    suggestions: vec![],
-                    metadata: HashMap::new(),
-                    items,
-                })
-            });
-            
-            result.map_err(|e| format!("Python callback error: {}", e).into())
-        } else {
-            Err("No callback registered for this Python engine".into())
+        self.validate_query(query)?;
+        if !self.try_acquire_rate_limit() {
+            return Err(format!(
+                "Python engine '{}' rate limit exceeded ({} requests per minute)",
+                self.info.name,
+                self.info.capabilities.rate_limit.unwrap_or_default()
+            ).into());
         }
+
+        let callback = {
+            let callback_guard = self.callback.read().await;
+            match *callback_guard {
+                Some(ref callback) => Python::attach(|py| callback.clone_ref(py)),
+                None => return Err("No callback registered for this Python engine".into()),
+            }
+        };
+
+        // 调用Python回调；返回协程时交给事件循环执行，等待期间不持有 GIL
+        let outcome = Python::attach(|py| -> PyResult<CallbackOutcome> {
+            let py_result = callback.call1(py, (self.query_dict(py, query)?,))?;
+            let py_result = py_result.into_bound(py);
+            if !py_result.hasattr("__await__")? {
+                return Ok(CallbackOutcome::Ready(py_result.unbind()));
+            }
+
+            let event_loop = match self.event_loop {
+                Some(ref event_loop) => event_loop.bind(py).clone(),
+                None => dedicated_event_loop(py)?,
+            };
+            let locals = pyo3_async_runtimes::TaskLocals::new(event_loop);
+            let future = pyo3_async_runtimes::into_future_with_locals(&locals, py_result)?;
+            Ok(CallbackOutcome::Pending(Box::pin(future)))
+        }).map_err(|e| format!("Python callback error: {}", e))?;
+
+        let py_result = match outcome {
+            CallbackOutcome::Ready(result) => result,
+            CallbackOutcome::Pending(future) => future
+                .await
+                .map_err(|e| format!("Python async callback error: {}", e))?,
+        };
+
+        Python::attach(|py| self.parse_result(py_result.bind(py)))
+            .map_err(|e| format!("Python callback error: {}", e).into())
     }
 
     async fn is_available(&self) -> bool {
@@ -220,13 +355,34 @@ impl SearchEngine for PythonEngineWrapper {
     }
 
     fn validate_query(&self, query: &SearchQuery) -> Result<(), crate::derive::types::ValidationError> {
+        use crate::derive::types::ValidationError;
+
         if query.query.is_empty() {
-            return Err(crate::derive::types::ValidationError::EmptyQuery);
+            return Err(ValidationError::EmptyQuery);
+        }
+        if query.page > 1 && !self.info.capabilities.supports_pagination {
+            return Err(ValidationError::UnsupportedParameter("page".to_string()));
+        }
+        if query.page > self.info.max_page {
+            return Err(ValidationError::InvalidParameter(format!("page > {}", self.info.max_page)));
+        }
+        if let Some(ref language) = query.language
+            && !self.languages.is_empty()
+            && !self.languages.iter().any(|supported| language_matches(supported, language))
+        {
+            return Err(ValidationError::UnsupportedParameter(format!("language={}", language)));
         }
         Ok(())
     }
 }
 
+/// 语言是否匹配（忽略大小写，"zh" 匹配 "zh-CN"）
+fn language_matches(supported: &str, language: &str) -> bool {
+    let primary = |tag: &str| tag.split(['-', '_']).next().unwrap_or(tag).to_ascii_lowercase();
+    supported.eq_ignore_ascii_case(language)
+        || (!supported.contains(['-', '_']) && primary(supported) == primary(language))
+}
+
 /// Python引擎注册器 (内部使用，不暴露给Python)
 ///
 /// 全局注册表只在Rust侧创建，Python侧只能通过函数访问
@@ -245,18 +401,11 @@ impl PyEngineRegistry {
     /// 注册一个新的Python引擎（内部使用）
     pub async fn register_engine_internal(
         &self,
-        name: String,
-        engine_type: EngineType,
-        description: String,
-        categories: Vec<String>,
+        wrapper: PythonEngineWrapper,
         callback: Py<PyAny>,
     ) -> Result<(), String> {
-        let wrapper = Arc::new(PythonEngineWrapper::new(
-            name.clone(),
-            engine_type,
-            description,
-            categories,
-        ));
+        let name = wrapper.info().name.clone();
+        let wrapper = Arc::new(wrapper);
 
         wrapper.set_callback(callback).await;
 
@@ -321,18 +470,25 @@ pub fn get_global_registry() -> Arc<PyEngineRegistry> {
 /// * `engine_type` - 引擎类型 ("web", "news", "images", "videos" 等)
 /// * `description` - 引擎描述
 /// * `categories` - 分类列表
-/// * `callback` - Python搜索函数，接受查询参数dict，返回结果dict
+/// * `callback` - Python搜索函数，接受查询参数dict，返回结果dict（可以是 async 函数）
+/// * `capabilities` - 能力声明（pagination、max_page_size、max_page、languages、rate_limit、
+///   time_range、region、safe_search、timeout）
+/// * `event_loop` - 运行 async 回调的事件循环（默认使用后台线程中的专用事件循环）；
+///   传入调用方的循环时，该循环在搜索期间不能被阻塞
 ///
 /// # 返回
 ///
 /// 成功返回 True，失败抛出异常
 #[pyfunction]
+#[pyo3(signature = (name, engine_type, description, categories, callback, capabilities=None, event_loop=None))]
 pub fn register_engine(
     name: String,
     engine_type: String,
     description: String,
     categories: Vec<String>,
     callback: Py<PyAny>,
+    capabilities: Option<Bound<'_, PyDict>>,
+    event_loop: Option<Py<PyAny>>,
 ) -> PyResult<bool> {
     let registry = get_global_registry();
     
@@ -345,18 +501,23 @@ pub fn register_engine(
         "code" => EngineType::Code,
         "shopping" => EngineType::Shopping,
         "music" => EngineType::Music,
+        "map" | "maps" => EngineType::Map,
+        "files" | "torrent" => EngineType::Files,
         "custom" => EngineType::Custom,
         _ => EngineType::General,
     };
     
+    let mut wrapper = PythonEngineWrapper::new(name, engine_type_enum, description, categories);
+    if let Some(capabilities) = capabilities {
+        wrapper = wrapper.with_capabilities(&capabilities)?;
+    }
+    if let Some(event_loop) = event_loop {
+        wrapper = wrapper.with_event_loop(event_loop);
+    }
+
     execute_with_runtime(async move {
-        registry.register_engine_internal(
-            name,
-            engine_type_enum,
-            description,
-            categories,
-            callback,
-        ).await.map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e))?;
+        registry.register_engine_internal(wrapper, callback)
+            .await.map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e))?;
         Ok(true)
     })?
 }
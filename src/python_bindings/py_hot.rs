@@ -0,0 +1,124 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Python bindings for hot list functionality
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::IntoPyObjectExt;
+use std::sync::Arc;
+
+use crate::config::hot::HotConfig;
+use crate::hot::{HotClient, HotList};
+use crate::net::{client::HttpClient, types::NetworkConfig};
+
+#[pyclass]
+pub struct PyHotClient {
+    runtime: tokio::runtime::Runtime,
+    client: Arc<HotClient>,
+}
+
+/// 转换热榜为 Python 字典
+fn hot_list_to_py(py: Python<'_>, list: &HotList) -> PyResult<Py<PyAny>> {
+    let dict = PyDict::new(py);
+    dict.set_item("platform", &list.platform)?;
+    dict.set_item("name", &list.name)?;
+    dict.set_item("fetched_at", list.fetched_at)?;
+    dict.set_item("cached", list.cached)?;
+    let items = list
+        .items
+        .iter()
+        .map(|item| {
+            let item_dict = PyDict::new(py);
+            item_dict.set_item("rank", item.rank)?;
+            item_dict.set_item("title", &item.title)?;
+            item_dict.set_item("url", &item.url)?;
+            item_dict.set_item("description", &item.description)?;
+            item_dict.set_item("heat", item.heat)?;
+            item_dict.set_item("label", &item.label)?;
+            item_dict.into_py_any(py)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    dict.set_item("items", items)?;
+    dict.into_py_any(py)
+}
+
+/// 热榜操作错误转换为 Python 异常
+fn hot_error(e: Box<dyn std::error::Error + Send + Sync>) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to fetch hot list: {}", e))
+}
+
+#[pymethods]
+impl PyHotClient {
+    /// 创建热榜客户端（使用默认热榜配置，热榜缓存在进程内）
+    #[new]
+    pub fn new() -> PyResult<Self> {
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                format!("Failed to create runtime: {}", e)
+            ))?;
+        let http_client = HttpClient::new(NetworkConfig::default())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                format!("Failed to create HTTP client: {}", e)
+            ))?;
+
+        Ok(Self {
+            runtime,
+            client: Arc::new(HotClient::new(HotConfig::default(), Arc::new(http_client), None)),
+        })
+    }
+
+    /// 列出可用的热榜平台
+    pub fn list_platforms(&self) -> PyResult<Py<PyAny>> {
+        Python::attach(|py| {
+            let platforms = self
+                .client
+                .list_platforms()
+                .iter()
+                .map(|platform| {
+                    let dict = PyDict::new(py);
+                    dict.set_item("id", &platform.id)?;
+                    dict.set_item("name", &platform.name)?;
+                    dict.set_item("format", platform.format.as_str())?;
+                    dict.set_item("url", &platform.url)?;
+                    dict.into_py_any(py)
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            platforms.into_py_any(py)
+        })
+    }
+
+    /// 获取一个平台的热榜
+    #[pyo3(signature = (platform, force=false))]
+    pub fn fetch_platform(&self, platform: String, force: bool) -> PyResult<Py<PyAny>> {
+        let list = self
+            .runtime
+            .block_on(self.client.fetch_platform(&platform, force))
+            .map_err(hot_error)?;
+        Python::attach(|py| hot_list_to_py(py, &list))
+    }
+
+    /// 获取所有平台的热榜（获取失败的平台被跳过）
+    #[pyo3(signature = (force=false))]
+    pub fn fetch_all(&self, force: bool) -> PyResult<Py<PyAny>> {
+        let lists = self.runtime.block_on(self.client.fetch_all(force));
+        Python::attach(|py| {
+            lists
+                .iter()
+                .map(|list| hot_list_to_py(py, list))
+                .collect::<PyResult<Vec<_>>>()?
+                .into_py_any(py)
+        })
+    }
+}
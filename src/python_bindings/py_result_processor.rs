@@ -0,0 +1,237 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Python结果处理器注册模块
+//!
+//! 提供从Python端动态注册结果处理器的功能，Python 包无需重新编译即可提供
+//! 重排、标注或过滤逻辑。处理器回调接受查询参数 dict 与结果 dict 列表，
+//! 返回处理后的结果列表（返回 None 表示不修改，可以是 async 函数）
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::IntoPyObjectExt;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use async_trait::async_trait;
+
+use crate::derive::{ResultType, SearchQuery, SearchResultItem};
+use crate::search::processor::{ProcessorError, ResultProcessor};
+
+use super::py_engine_registry::{dedicated_event_loop, CallbackOutcome};
+
+/// Python结果处理器包装器
+///
+/// 实现ResultProcessor trait，通过Python回调处理结果
+pub struct PythonResultProcessor {
+    name: String,
+    callback: Py<PyAny>,
+    /// 运行协程回调的事件循环（None 时使用专用事件循环）
+    event_loop: Option<Py<PyAny>>,
+}
+
+impl PythonResultProcessor {
+    /// 创建新的Python结果处理器
+    pub fn new(name: String, callback: Py<PyAny>, event_loop: Option<Py<PyAny>>) -> Self {
+        Self { name, callback, event_loop }
+    }
+
+    /// 结果转换为传给回调的字典
+    fn item_dict<'py>(py: Python<'py>, item: &SearchResultItem) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("title", &item.title)?;
+        dict.set_item("url", &item.url)?;
+        dict.set_item("content", &item.content)?;
+        dict.set_item("display_url", &item.display_url)?;
+        dict.set_item("site_name", &item.site_name)?;
+        dict.set_item("thumbnail", &item.thumbnail)?;
+        dict.set_item("score", item.score)?;
+        dict.set_item("published_date", item.published_date.map(|date| date.to_rfc3339()))?;
+        dict.set_item("metadata", &item.metadata)?;
+        Ok(dict)
+    }
+
+    /// 解析回调返回的结果字典
+    ///
+    /// URL 与原结果相同时在原结果基础上更新字典中出现的字段，否则作为新结果
+    fn parse_item(dict: &Bound<'_, PyDict>, originals: &HashMap<String, SearchResultItem>) -> PyResult<SearchResultItem> {
+        let url: String = dict
+            .get_item("url")?
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("Result is missing 'url'"))?
+            .extract()?;
+        let mut item = originals.get(&url).cloned().unwrap_or_else(|| SearchResultItem {
+            title: String::new(),
+            url: url.clone(),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            result_type: ResultType::Web,
+            thumbnail: None,
+            metadata: HashMap::new(),
+            published_date: None,
+            score: 0.0,
+            template: None,
+        });
+
+        if let Some(title) = dict.get_item("title")? {
+            item.title = title.extract()?;
+        }
+        if let Some(content) = dict.get_item("content")?.or(dict.get_item("snippet")?) {
+            item.content = content.extract()?;
+        }
+        if let Some(display_url) = dict.get_item("display_url")? {
+            item.display_url = display_url.extract()?;
+        }
+        if let Some(site_name) = dict.get_item("site_name")? {
+            item.site_name = site_name.extract()?;
+        }
+        if let Some(thumbnail) = dict.get_item("thumbnail")? {
+            item.thumbnail = thumbnail.extract()?;
+        }
+        if let Some(score) = dict.get_item("score")? {
+            item.score = score.extract()?;
+        }
+        if let Some(metadata) = dict.get_item("metadata")? {
+            item.metadata = metadata.extract()?;
+        }
+        Ok(item)
+    }
+
+    /// 调用回调并等待结果（协程回调交给事件循环执行，等待期间不持有 GIL）
+    async fn call(&self, query: &SearchQuery, items: &[SearchResultItem]) -> PyResult<Py<PyAny>> {
+        let outcome = Python::attach(|py| -> PyResult<CallbackOutcome> {
+            let query_dict = PyDict::new(py);
+            query_dict.set_item("query", &query.query)?;
+            query_dict.set_item("page", query.page)?;
+            query_dict.set_item("page_size", query.page_size)?;
+            query_dict.set_item("language", &query.language)?;
+            query_dict.set_item("region", &query.region)?;
+            let results = items
+                .iter()
+                .map(|item| Self::item_dict(py, item)?.into_py_any(py))
+                .collect::<PyResult<Vec<_>>>()?;
+
+            let py_result = self.callback.call1(py, (query_dict, results))?.into_bound(py);
+            if !py_result.hasattr("__await__")? {
+                return Ok(CallbackOutcome::Ready(py_result.unbind()));
+            }
+            let event_loop = match self.event_loop {
+                Some(ref event_loop) => event_loop.bind(py).clone(),
+                None => dedicated_event_loop(py)?,
+            };
+            let locals = pyo3_async_runtimes::TaskLocals::new(event_loop);
+            let future = pyo3_async_runtimes::into_future_with_locals(&locals, py_result)?;
+            Ok(CallbackOutcome::Pending(Box::pin(future)))
+        })?;
+
+        match outcome {
+            CallbackOutcome::Ready(result) => Ok(result),
+            CallbackOutcome::Pending(future) => future.await,
+        }
+    }
+}
+
+#[async_trait]
+impl ResultProcessor for PythonResultProcessor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn process(&self, query: &SearchQuery, items: &mut Vec<SearchResultItem>) -> Result<(), ProcessorError> {
+        let py_result = self
+            .call(query, items)
+            .await
+            .map_err(|e| format!("Python processor error: {}", e))?;
+
+        let processed = Python::attach(|py| -> PyResult<Option<Vec<SearchResultItem>>> {
+            let py_result = py_result.bind(py);
+            if py_result.is_none() {
+                return Ok(None);
+            }
+            let originals: HashMap<String, SearchResultItem> =
+                items.iter().map(|item| (item.url.clone(), item.clone())).collect();
+            py_result
+                .extract::<Vec<Bound<'_, PyDict>>>()?
+                .iter()
+                .map(|dict| Self::parse_item(dict, &originals))
+                .collect::<PyResult<Vec<_>>>()
+                .map(Some)
+        })
+        .map_err(|e| format!("Python processor returned invalid results: {}", e))?;
+
+        if let Some(processed) = processed {
+            *items = processed;
+        }
+        Ok(())
+    }
+}
+
+/// 全局结果处理器注册表（按注册顺序执行）
+static GLOBAL_PROCESSORS: once_cell::sync::Lazy<RwLock<Vec<Arc<PythonResultProcessor>>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(Vec::new()));
+
+/// 已注册的Python结果处理器（SearchInterface 在聚合之后执行）
+pub fn registered_processors() -> Vec<Arc<dyn ResultProcessor>> {
+    match GLOBAL_PROCESSORS.read() {
+        Ok(processors) => processors
+            .iter()
+            .map(|processor| Arc::clone(processor) as Arc<dyn ResultProcessor>)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// 注册表锁错误转换为 Python 异常
+fn lock_error<T>(_: T) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Result processor registry is poisoned")
+}
+
+/// Python函数：注册一个结果处理器
+///
+/// # 参数
+///
+/// * `name` - 处理器名称（同名处理器被替换，保留原来的执行位置）
+/// * `callback` - 处理函数，接受查询参数dict与结果dict列表，返回处理后的结果列表或 None
+///   （可以是 async 函数）；结果按 `url` 与原结果对应，未返回的结果被移除
+/// * `event_loop` - 运行 async 回调的事件循环（默认使用后台线程中的专用事件循环）
+///
+/// # 返回
+///
+/// 成功返回 True，失败抛出异常
+#[pyfunction]
+#[pyo3(signature = (name, callback, event_loop=None))]
+pub fn register_result_processor(name: String, callback: Py<PyAny>, event_loop: Option<Py<PyAny>>) -> PyResult<bool> {
+    let processor = Arc::new(PythonResultProcessor::new(name.clone(), callback, event_loop));
+    let mut processors = GLOBAL_PROCESSORS.write().map_err(lock_error)?;
+    match processors.iter_mut().find(|p| p.name == name) {
+        Some(existing) => *existing = processor,
+        None => processors.push(processor),
+    }
+    Ok(true)
+}
+
+/// Python函数：注销一个结果处理器
+#[pyfunction]
+pub fn unregister_result_processor(name: String) -> PyResult<bool> {
+    let mut processors = GLOBAL_PROCESSORS.write().map_err(lock_error)?;
+    let before = processors.len();
+    processors.retain(|p| p.name != name);
+    Ok(processors.len() != before)
+}
+
+/// Python函数：获取已注册的结果处理器名称（按执行顺序）
+#[pyfunction]
+pub fn list_result_processors() -> PyResult<Vec<String>> {
+    let processors = GLOBAL_PROCESSORS.read().map_err(lock_error)?;
+    Ok(processors.iter().map(|p| p.name.clone()).collect())
+}
@@ -20,7 +20,9 @@ use pyo3::IntoPyObjectExt;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use crate::rss::{RssInterface, RssFeedQuery};
+use crate::cache::rss::RssFeedCacheMeta;
+use crate::derive::rss::RssFeedItem;
+use crate::rss::{CachedRssItem, RssInterface, RssFeedQuery, RssItemFilter};
 use crate::net::{client::HttpClient, types::NetworkConfig};
 use crate::cache::{CacheInterface, types::CacheImplConfig};
 
@@ -30,6 +32,64 @@ pub struct PyRssClient {
     interface: Arc<RwLock<RssInterface>>,
 }
 
+/// 缓存条目迭代器
+#[pyclass]
+pub struct PyRssItemIterator {
+    items: std::vec::IntoIter<CachedRssItem>,
+}
+
+#[pymethods]
+impl PyRssItemIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<Py<PyAny>>> {
+        let Some(cached) = slf.items.next() else {
+            return Ok(None);
+        };
+        let py = slf.py();
+        let dict = feed_item_to_py(py, &cached.item)?;
+        dict.set_item("feed_url", &cached.feed_url)?;
+        dict.set_item("feed_title", &cached.feed_title)?;
+        Ok(Some(dict.into_py_any(py)?))
+    }
+
+    fn __len__(&self) -> usize {
+        self.items.len()
+    }
+}
+
+/// 转换 feed 条目为 Python 字典
+fn feed_item_to_py<'py>(py: Python<'py>, item: &RssFeedItem) -> PyResult<Bound<'py, PyDict>> {
+    let item_dict = PyDict::new(py);
+    item_dict.set_item("title", &item.title)?;
+    item_dict.set_item("link", &item.link)?;
+    item_dict.set_item("guid", &item.guid)?;
+    item_dict.set_item("description", &item.description)?;
+    item_dict.set_item("author", &item.author)?;
+    item_dict.set_item("pub_date", &item.pub_date)?;
+    item_dict.set_item("content", &item.content)?;
+    item_dict.set_item("categories", &item.categories)?;
+    Ok(item_dict)
+}
+
+/// 转换订阅元数据为 Python 字典
+fn subscription_to_py<'py>(py: Python<'py>, meta: &RssFeedCacheMeta) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("url", &meta.url)?;
+    dict.set_item("name", &meta.name)?;
+    dict.set_item("last_updated", meta.last_updated)?;
+    dict.set_item("update_interval", meta.update_interval)?;
+    dict.set_item("item_count", meta.item_count)?;
+    Ok(dict)
+}
+
+/// RSS 操作错误转换为 Python 异常
+fn rss_error(action: &str, e: Box<dyn std::error::Error + Send + Sync>) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to {}: {}", action, e))
+}
+
 #[pymethods]
 impl PyRssClient {
     #[new]
@@ -101,15 +161,9 @@ impl PyRssClient {
 
             // 添加项目
             let items: Vec<Py<PyAny>> = feed.items.iter().map(|item| {
-                let item_dict = PyDict::new(py);
-                let _ = item_dict.set_item("title", &item.title);
-                let _ = item_dict.set_item("link", &item.link);
-                let _ = item_dict.set_item("description", &item.description);
-                let _ = item_dict.set_item("author", &item.author);
-                let _ = item_dict.set_item("pub_date", &item.pub_date);
-                let _ = item_dict.set_item("content", &item.content);
-                let _ = item_dict.set_item("categories", &item.categories);
-                item_dict.into_py_any(py).unwrap_or_else(|_| py.None())
+                feed_item_to_py(py, item)
+                    .and_then(|item_dict| item_dict.into_py_any(py))
+                    .unwrap_or_else(|_| py.None())
             }).collect();
 
             dict.set_item("items", items)?;
@@ -178,6 +232,165 @@ impl PyRssClient {
         Ok(count)
     }
 
+    /// 订阅 RSS feed（立即获取并持久化）
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Feed URL
+    /// * `update_interval` - 更新间隔（秒，默认 3600）
+    ///
+    /// # Returns
+    ///
+    /// 返回订阅信息
+    pub fn subscribe(&self, url: String, update_interval: Option<u64>) -> PyResult<Py<PyAny>> {
+        let update_interval = update_interval.unwrap_or(crate::rss::DEFAULT_UPDATE_INTERVAL);
+        let meta = self.runtime.block_on(async {
+            let interface = self.interface.read().await;
+            interface.fetch_persistent(&url, update_interval).await?;
+            let subscriptions = interface.subscriptions().await?;
+            Ok(subscriptions.into_iter().find(|meta| meta.url == url))
+        }).map_err(|e| rss_error("subscribe", e))?;
+
+        Python::attach(|py| match meta {
+            Some(meta) => subscription_to_py(py, &meta)?.into_py_any(py),
+            None => Ok(py.None()),
+        })
+    }
+
+    /// 取消订阅，返回订阅是否存在
+    pub fn unsubscribe(&self, url: String) -> PyResult<bool> {
+        self.runtime.block_on(async {
+            let interface = self.interface.read().await;
+            interface.unsubscribe(&url).await
+        }).map_err(|e| rss_error("unsubscribe", e))
+    }
+
+    /// 列出订阅
+    pub fn list_subscriptions(&self) -> PyResult<Vec<Py<PyAny>>> {
+        let subscriptions = self.runtime.block_on(async {
+            let interface = self.interface.read().await;
+            interface.subscriptions().await
+        }).map_err(|e| rss_error("list subscriptions", e))?;
+
+        Python::attach(|py| {
+            subscriptions
+                .iter()
+                .map(|meta| subscription_to_py(py, meta)?.into_py_any(py))
+                .collect()
+        })
+    }
+
+    /// 立即刷新订阅，忽略更新间隔
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - 只刷新该 feed（未指定时刷新所有订阅）
+    ///
+    /// # Returns
+    ///
+    /// 返回 {url: 条目数}；刷新全部时失败的 feed 不包含在结果中
+    pub fn fetch_now(&self, url: Option<String>) -> PyResult<std::collections::HashMap<String, usize>> {
+        self.runtime.block_on(async {
+            let interface = self.interface.read().await;
+            let mut refreshed = std::collections::HashMap::new();
+
+            if let Some(url) = url {
+                let feed = interface.refresh(&url).await?;
+                refreshed.insert(url, feed.items.len());
+                return Ok(refreshed);
+            }
+
+            for meta in interface.subscriptions().await? {
+                match interface.refresh(&meta.url).await {
+                    Ok(feed) => {
+                        refreshed.insert(meta.url, feed.items.len());
+                    }
+                    Err(e) => tracing::warn!("Failed to refresh RSS feed {}: {}", meta.url, e),
+                }
+            }
+            Ok(refreshed)
+        }).map_err(|e| rss_error("refresh RSS feed", e))
+    }
+
+    /// 遍历订阅中缓存的条目（按发布时间从新到旧，不发起网络请求）
+    ///
+    /// # Arguments
+    ///
+    /// * `categories` - 只包含带有其中任一分类的条目
+    /// * `since` - 发布时间下限（Unix 时间戳）
+    /// * `until` - 发布时间上限（Unix 时间戳）
+    /// * `feed_url` - 只包含该 feed 的条目
+    /// * `unread_for` - 只包含该客户端令牌未读的条目
+    ///
+    /// # Returns
+    ///
+    /// 返回条目迭代器，每个条目额外包含 feed_url 和 feed_title
+    #[pyo3(signature = (categories=None, since=None, until=None, feed_url=None, unread_for=None))]
+    pub fn iter_items(
+        &self,
+        categories: Option<Vec<String>>,
+        since: Option<i64>,
+        until: Option<i64>,
+        feed_url: Option<String>,
+        unread_for: Option<String>,
+    ) -> PyResult<PyRssItemIterator> {
+        let filter = RssItemFilter {
+            feed_url,
+            categories: categories.unwrap_or_default(),
+            since,
+            until,
+            unread_for,
+        };
+        let items = self.runtime.block_on(async {
+            let interface = self.interface.read().await;
+            interface.cached_items(&filter).await
+        }).map_err(|e| rss_error("read cached items", e))?;
+
+        Ok(PyRssItemIterator {
+            items: items.into_iter(),
+        })
+    }
+
+    /// 标记条目为已读或未读
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - 客户端令牌（不能为空或包含 ':'）
+    /// * `url` - Feed URL
+    /// * `item_keys` - 条目 GUID（没有 GUID 时为链接）
+    /// * `read` - False 时重新标记为未读
+    ///
+    /// # Returns
+    ///
+    /// 返回状态发生变化的条目数
+    #[pyo3(signature = (token, url, item_keys, read=true))]
+    pub fn mark_read(&self, token: String, url: String, item_keys: Vec<String>, read: bool) -> PyResult<usize> {
+        self.runtime.block_on(async {
+            let interface = self.interface.read().await;
+            if read {
+                interface.mark_read(&token, &url, &item_keys).await
+            } else {
+                interface.mark_unread(&token, &url, &item_keys).await
+            }
+        }).map_err(|e| rss_error("mark items", e))
+    }
+
+    /// 将 feed 中缓存的全部条目标记为已读，返回新标记为已读的条目数
+    pub fn mark_feed_read(&self, token: String, url: String) -> PyResult<usize> {
+        self.runtime.block_on(async {
+            let interface = self.interface.read().await;
+            interface.mark_feed_read(&token, &url).await
+        }).map_err(|e| rss_error("mark feed read", e))
+    }
+
+    /// 统计客户端在每个订阅 feed 中的未读条目数，返回 {url: 未读数}
+    pub fn unread_counts(&self, token: String) -> PyResult<std::collections::BTreeMap<String, usize>> {
+        self.runtime.block_on(async {
+            let interface = self.interface.read().await;
+            interface.unread_counts(&token).await
+        }).map_err(|e| rss_error("count unread items", e))
+    }
+
     /// 创建RSS榜单 - 基于关键词对RSS项目进行评分和排名
     ///
     /// # Arguments
@@ -19,9 +19,12 @@ use pyo3::types::PyDict;
 use pyo3::IntoPyObjectExt;
 use std::sync::Arc;
 
-use crate::search::{SearchInterface, SearchConfig, SearchRequest};
+use crate::search::{generate_request_id, request_span, SearchInterface, SearchConfig, SearchRequest};
+use tracing::Instrument;
 use crate::search::engine_config::EngineMode;
 use crate::derive::SearchQuery;
+use crate::cache::{validate_session_token, Alert, AlertDelivery, AlertStore, CacheImplConfig, CacheInterface, SavedSearch, SearchSessionStore};
+use crate::search::SavedSearchRunner;
 
 #[pyclass]
 pub struct PySearchClient {
@@ -60,7 +63,9 @@ impl PySearchClient {
         engines: Option<Vec<String>>,
         force: Option<bool>,
         cache_timeline: Option<u64>,
+        session: Option<String>,
     ) -> PyResult<Py<PyAny>> {
+        let session = session.map(validated_session).transpose()?;
         let search_query = SearchQuery {
             query,
             page: page.unwrap_or(1),
@@ -86,44 +91,33 @@ impl PySearchClient {
             max_results: None,
             force: force.unwrap_or(false),
             cache_timeline,
+            translate_to: None,
+            highlight: false,
+            debug_capture: false,
+            max_latency_ms: None,
+            explain: false,
+            session,
         };
 
-        let response = if let EngineMode::Custom(_) = mode {
-            // 自定义引擎模式，使用常规搜索
-            self.runtime.block_on(async {
-                self.interface.search(&request).await
-            })
-        } else {
-            // 全局模式，使用模式搜索
-            self.runtime.block_on(async {
-                self.interface.search_with_mode(&request, mode).await
-            })
-        }.map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+        // 搜索在携带请求 ID 的 span 中执行
+        let request_id = generate_request_id();
+        let mut response = self.runtime.block_on(
+            async {
+                if let EngineMode::Custom(_) = mode {
+                    // 自定义引擎模式，使用常规搜索
+                    self.interface.search(&request).await
+                } else {
+                    // 全局模式，使用模式搜索
+                    self.interface.search_with_mode(&request, mode).await
+                }
+            }
+            .instrument(request_span(&request_id)),
+        ).map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
             format!("Search failed: {}", e)
         ))?;
+        response.request_id = Some(request_id);
         
-        Python::attach(|py| {
-            let dict = PyDict::new(py);
-            dict.set_item("query", response.query.query)?;
-            dict.set_item("total_count", response.total_count)?;
-            dict.set_item("cached", response.cached)?;
-            dict.set_item("query_time_ms", response.query_time_ms)?;
-            dict.set_item("engines_used", response.engines_used)?;
-            
-            let results: Vec<Py<PyAny>> = response.results.iter().flat_map(|r| {
-                r.items.iter().map(|item| {
-                    let item_dict = PyDict::new(py);
-                    let _ = item_dict.set_item("title", &item.title);
-                    let _ = item_dict.set_item("url", &item.url);
-                    let _ = item_dict.set_item("content", &item.content);
-                    let _ = item_dict.set_item("score", item.score);
-                    item_dict.into_py_any(py).unwrap_or_else(|_| py.None())
-                }).collect::<Vec<_>>()
-            }).collect();
-            
-            dict.set_item("results", results)?;
-            Ok(dict.into_py_any(py)?)
-        })
+        Python::attach(|py| response_to_py(py, &response))
     }
     
     pub fn get_stats(&self) -> PyResult<Py<PyAny>> {
@@ -138,6 +132,7 @@ impl PySearchClient {
             dict.set_item("cache_misses", stats.cache_misses)?;
             dict.set_item("engine_failures", stats.engine_failures)?;
             dict.set_item("timeouts", stats.timeouts)?;
+            dict.set_item("coalesced_requests", stats.coalesced_requests)?;
             dict.into_py_any(py)
         })
     }
@@ -161,6 +156,14 @@ impl PySearchClient {
         Ok(self.interface.list_global_engines())
     }
 
+    /// 引擎能力目录（JSON 数组，包括 Python 端注册的引擎）
+    pub fn engine_catalog(&self) -> PyResult<String> {
+        let catalog = self.runtime.block_on(self.interface.engine_catalog());
+        serde_json::to_string(&catalog).map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+            format!("Failed to serialize engine catalog: {}", e)
+        ))
+    }
+
     /// 健康检查所有引擎
     pub fn health_check(&self) -> PyResult<Py<PyAny>> {
         let results = self.runtime.block_on(async {
@@ -220,60 +223,35 @@ impl PySearchClient {
             max_results: None,
             force: false,
             cache_timeline: None,
+            translate_to: None,
+            highlight: false,
+            debug_capture: false,
+            max_latency_ms: None,
+            explain: false,
+            session: None,
         };
 
         // 创建回调包装器
         let py_callback = callback.clone_ref(py);
         
-        let response = self.runtime.block_on(async move {
+        let request_id = generate_request_id();
+        let span = request_span(&request_id);
+        let mut response = self.runtime.block_on(async move {
             self.interface.search_streaming(&request, move |result, engine_name| {
                 // 在回调中调用Python函数
-                Python::with_gil(|py| {
-                    let result_dict = PyDict::new(py);
-                    let _ = result_dict.set_item("engine", engine_name);
-                    let _ = result_dict.set_item("total_results", result.total_results);
-                    
-                    let items: Vec<Py<PyAny>> = result.items.iter().map(|item| {
-                        let item_dict = PyDict::new(py);
-                        let _ = item_dict.set_item("title", &item.title);
-                        let _ = item_dict.set_item("url", &item.url);
-                        let _ = item_dict.set_item("content", &item.content);
-                        let _ = item_dict.set_item("score", item.score);
-                        item_dict.into_py_any(py).unwrap_or_else(|_| py.None())
-                    }).collect();
-                    
-                    let _ = result_dict.set_item("items", items);
-                    
+                Python::attach(|py| {
                     // 调用Python回调
-                    let _ = py_callback.call1(py, (result_dict,));
+                    if let Ok(result_dict) = engine_result_to_py(py, &engine_name, &result) {
+                        let _ = py_callback.call1(py, (result_dict,));
+                    }
                 });
             }).await
-        }).map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+        }.instrument(span)).map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
             format!("Streaming search failed: {}", e)
         ))?;
+        response.request_id = Some(request_id);
         
-        Python::attach(|py| {
-            let dict = PyDict::new(py);
-            dict.set_item("query", response.query.query)?;
-            dict.set_item("total_count", response.total_count)?;
-            dict.set_item("cached", response.cached)?;
-            dict.set_item("query_time_ms", response.query_time_ms)?;
-            dict.set_item("engines_used", response.engines_used)?;
-            
-            let results: Vec<Py<PyAny>> = response.results.iter().flat_map(|r| {
-                r.items.iter().map(|item| {
-                    let item_dict = PyDict::new(py);
-                    let _ = item_dict.set_item("title", &item.title);
-                    let _ = item_dict.set_item("url", &item.url);
-                    let _ = item_dict.set_item("content", &item.content);
-                    let _ = item_dict.set_item("score", item.score);
-                    item_dict.into_py_any(py).unwrap_or_else(|_| py.None())
-                }).collect::<Vec<_>>()
-            }).collect();
-            
-            dict.set_item("results", results)?;
-            Ok(dict.into_py_any(py)?)
-        })
+        Python::attach(|py| response_to_py(py, &response))
     }
 
     /// 获取引擎状态信息
@@ -357,6 +335,12 @@ impl PySearchClient {
             max_results: None,
             force: false,
             cache_timeline: None,
+            translate_to: None,
+            highlight: false,
+            debug_capture: false,
+            max_latency_ms: None,
+            explain: false,
+            session: None,
         };
 
         let response = self.runtime.block_on(async {
@@ -365,28 +349,7 @@ impl PySearchClient {
             format!("Fulltext search failed: {}", e)
         ))?;
         
-        Python::attach(|py| {
-            let dict = PyDict::new(py);
-            dict.set_item("query", response.query.query)?;
-            dict.set_item("total_count", response.total_count)?;
-            dict.set_item("cached", response.cached)?;
-            dict.set_item("query_time_ms", response.query_time_ms)?;
-            dict.set_item("engines_used", response.engines_used)?;
-            
-            let results: Vec<Py<PyAny>> = response.results.iter().flat_map(|r| {
-                r.items.iter().map(|item| {
-                    let item_dict = PyDict::new(py);
-                    let _ = item_dict.set_item("title", &item.title);
-                    let _ = item_dict.set_item("url", &item.url);
-                    let _ = item_dict.set_item("content", &item.content);
-                    let _ = item_dict.set_item("score", item.score);
-                    item_dict.into_py_any(py).unwrap_or_else(|_| py.None())
-                }).collect::<Vec<_>>()
-            }).collect();
-            
-            dict.set_item("results", results)?;
-            Ok(dict.into_py_any(py)?)
-        })
+        Python::attach(|py| response_to_py(py, &response))
     }
 
     /// 获取隐私保护统计信息
@@ -409,4 +372,217 @@ impl PySearchClient {
             }
         })
     }
+
+    /// 保存搜索（按间隔重新执行，新结果产生告警）
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 搜索查询
+    /// * `interval_secs` - 执行间隔（秒，默认 3600）
+    /// * `engines` - 指定引擎列表（可选）
+    /// * `delivery` - 新结果的投递方式（store/webhook/both，默认 store）
+    ///
+    /// # Returns
+    ///
+    /// 返回保存的搜索
+    pub fn save_search(
+        &self,
+        query: String,
+        interval_secs: Option<u64>,
+        engines: Option<Vec<String>>,
+        delivery: Option<String>,
+    ) -> PyResult<Py<PyAny>> {
+        let delivery = match delivery {
+            Some(delivery) => delivery
+                .parse::<AlertDelivery>()
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?,
+            None => AlertDelivery::default(),
+        };
+        let search = SavedSearch::new(&query, engines.unwrap_or_default(), interval_secs.unwrap_or(3600), delivery);
+        self.alert_store()?.save_search(&search).map_err(alert_error)?;
+        Python::attach(|py| saved_search_to_py(py, &search))
+    }
+
+    /// 列出保存的搜索
+    pub fn list_saved_searches(&self) -> PyResult<Vec<Py<PyAny>>> {
+        let searches = self.alert_store()?.list_searches().map_err(alert_error)?;
+        Python::attach(|py| searches.iter().map(|search| saved_search_to_py(py, search)).collect())
+    }
+
+    /// 删除保存的搜索及其告警，返回是否存在
+    pub fn delete_saved_search(&self, id: String) -> PyResult<bool> {
+        self.alert_store()?.delete_search(&id).map_err(alert_error)
+    }
+
+    /// 列出告警（最新的在前）
+    ///
+    /// # Arguments
+    ///
+    /// * `saved_search_id` - 只列出该保存的搜索的告警（可选）
+    pub fn list_alerts(&self, saved_search_id: Option<String>) -> PyResult<Vec<Py<PyAny>>> {
+        let alerts = self.alert_store()?.list_alerts(saved_search_id.as_deref()).map_err(alert_error)?;
+        Python::attach(|py| alerts.iter().map(|alert| alert_to_py(py, alert)).collect())
+    }
+
+    /// 删除告警，返回是否存在
+    pub fn delete_alert(&self, id: String) -> PyResult<bool> {
+        self.alert_store()?.delete_alert(&id).map_err(alert_error)
+    }
+
+    /// 创建搜索会话，返回会话令牌（搜索时通过 `session` 参数传入）
+    pub fn create_session(&self) -> PyResult<String> {
+        self.session_store()?.create().map_err(session_error)
+    }
+
+    /// 读取搜索会话中的查询（最早的在前），会话不存在或已过期时返回 None
+    pub fn get_session(&self, token: String) -> PyResult<Option<Vec<String>>> {
+        let session = self.session_store()?.get(&validated_session(token)?).map_err(session_error)?;
+        Ok(session.map(|session| session.queries.into_iter().map(|q| q.query).collect()))
+    }
+
+    /// 结束搜索会话，返回是否存在
+    pub fn end_session(&self, token: String) -> PyResult<bool> {
+        self.session_store()?.delete(&validated_session(token)?).map_err(session_error)
+    }
+
+    /// 立即执行所有到期的保存的搜索，返回产生的告警数
+    pub fn run_saved_searches(&self) -> PyResult<usize> {
+        let runner = SavedSearchRunner::new(Arc::clone(&self.interface), self.alert_store()?);
+        Ok(self.runtime.block_on(runner.run_due()))
+    }
+}
+
+impl PySearchClient {
+    /// 获取搜索会话存储（未启用缓存时报错）
+    fn session_store(&self) -> PyResult<&SearchSessionStore> {
+        self.interface.sessions().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Search sessions are disabled (cache required)")
+        })
+    }
+
+    /// 打开保存的搜索与告警存储
+    fn alert_store(&self) -> PyResult<AlertStore> {
+        CacheInterface::new(CacheImplConfig::default())
+            .and_then(|cache| cache.alerts())
+            .map_err(alert_error)
+    }
+}
+
+/// 结果条目转换为 Python 字典
+fn item_to_py(py: Python<'_>, item: &crate::derive::SearchResultItem) -> PyResult<Py<PyAny>> {
+    let item_dict = PyDict::new(py);
+    item_dict.set_item("title", &item.title)?;
+    item_dict.set_item("url", &item.url)?;
+    item_dict.set_item("content", &item.content)?;
+    item_dict.set_item("score", item.score)?;
+    item_dict.into_py_any(py)
+}
+
+/// 单个引擎的结果转换为 Python 字典（流式回调的参数）
+pub(crate) fn engine_result_to_py(
+    py: Python<'_>,
+    engine_name: &str,
+    result: &crate::derive::SearchResult,
+) -> PyResult<Py<PyAny>> {
+    let result_dict = PyDict::new(py);
+    result_dict.set_item("engine", engine_name)?;
+    result_dict.set_item("total_results", result.total_results)?;
+    let items = result
+        .items
+        .iter()
+        .map(|item| item_to_py(py, item))
+        .collect::<PyResult<Vec<_>>>()?;
+    result_dict.set_item("items", items)?;
+    result_dict.into_py_any(py)
+}
+
+/// 搜索响应转换为 Python 字典
+pub(crate) fn response_to_py(py: Python<'_>, response: &crate::search::SearchResponse) -> PyResult<Py<PyAny>> {
+    let dict = PyDict::new(py);
+    dict.set_item("query", &response.query.query)?;
+    dict.set_item("total_count", response.total_count)?;
+    dict.set_item("cached", response.cached)?;
+    dict.set_item("query_time_ms", response.query_time_ms)?;
+    dict.set_item("engines_used", &response.engines_used)?;
+    dict.set_item("answer", response.answer.as_ref().map(|a| a.answer.clone()))?;
+    dict.set_item("suggestions", &response.suggestions)?;
+    dict.set_item("related_queries", &response.related_queries)?;
+    dict.set_item("partial", response.partial)?;
+    dict.set_item("request_id", &response.request_id)?;
+
+    let per_engine = response
+        .per_engine
+        .iter()
+        .map(|timing| {
+            let entry = PyDict::new(py);
+            entry.set_item("engine", &timing.engine)?;
+            entry.set_item("elapsed_ms", timing.elapsed_ms)?;
+            entry.set_item("result_count", timing.result_count)?;
+            entry.set_item("cache_hit", timing.cache_hit)?;
+            entry.set_item("error", &timing.error)?;
+            entry.set_item("retries", timing.retries)?;
+            Ok(entry)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    dict.set_item("per_engine", per_engine)?;
+
+    let results = response
+        .results
+        .iter()
+        .flat_map(|r| r.items.iter())
+        .map(|item| item_to_py(py, item))
+        .collect::<PyResult<Vec<_>>>()?;
+    dict.set_item("results", results)?;
+    dict.into_py_any(py)
+}
+
+/// 告警存储错误转换为 Python 异常
+/// 校验搜索会话令牌
+pub(crate) fn validated_session(token: String) -> PyResult<String> {
+    validate_session_token(&token).map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+    Ok(token)
+}
+
+/// 搜索会话存储错误转换为 Python 异常
+fn session_error(e: crate::cache::CacheError) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Search session storage failed: {}", e))
+}
+
+fn alert_error(e: crate::cache::CacheError) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Alert store error: {}", e))
+}
+
+/// 保存的搜索转换为 Python 字典
+fn saved_search_to_py(py: Python<'_>, search: &SavedSearch) -> PyResult<Py<PyAny>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", &search.id)?;
+    dict.set_item("query", &search.query)?;
+    dict.set_item("engines", &search.engines)?;
+    dict.set_item("interval_secs", search.interval_secs)?;
+    dict.set_item("delivery", format!("{:?}", search.delivery).to_lowercase())?;
+    dict.set_item("created_at", search.created_at)?;
+    dict.set_item("last_run", search.last_run)?;
+    dict.into_py_any(py)
+}
+
+/// 告警转换为 Python 字典
+fn alert_to_py(py: Python<'_>, alert: &Alert) -> PyResult<Py<PyAny>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", &alert.id)?;
+    dict.set_item("saved_search_id", &alert.saved_search_id)?;
+    dict.set_item("query", &alert.query)?;
+    dict.set_item("created_at", alert.created_at)?;
+    let results = alert
+        .results
+        .iter()
+        .map(|result| {
+            let item = PyDict::new(py);
+            item.set_item("title", &result.title)?;
+            item.set_item("url", &result.url)?;
+            item.set_item("content", &result.content)?;
+            item.into_py_any(py)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    dict.set_item("results", results)?;
+    dict.into_py_any(py)
 }
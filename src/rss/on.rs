@@ -16,14 +16,69 @@
 //!
 //! 提供统一的 RSS feed 外部接口
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use crate::derive::rss::*;
 use crate::net::client::HttpClient;
-use crate::cache::rss::RssCache;
+use crate::cache::rss::{rss_item_key, RssCache, RssFeedCacheMeta};
 use super::fetcher::RssFetcher;
 use super::parser::RssParser;
 use super::template::RssTemplateManager;
+use crate::webhook::{MatchedRssItem, WebhookDispatcher, WebhookEvent};
+
+/// 订阅默认更新间隔（秒）
+pub const DEFAULT_UPDATE_INTERVAL: u64 = 3600;
+
+/// 缓存条目过滤条件
+#[derive(Debug, Clone, Default)]
+pub struct RssItemFilter {
+    /// 只包含该 feed 的条目
+    pub feed_url: Option<String>,
+    /// 条目需包含其中任一分类（忽略大小写，空表示不限）
+    pub categories: Vec<String>,
+    /// 发布时间下限（Unix 时间戳，含）
+    pub since: Option<i64>,
+    /// 发布时间上限（Unix 时间戳，含）
+    pub until: Option<i64>,
+    /// 只包含该客户端令牌未读的条目
+    pub unread_for: Option<String>,
+}
+
+impl RssItemFilter {
+    /// 条目是否满足过滤条件
+    ///
+    /// 设置了时间范围时，缺少或无法解析发布时间的条目不匹配
+    pub fn matches(&self, item: &RssFeedItem) -> bool {
+        if !self.categories.is_empty()
+            && !item.categories.iter().any(|category| {
+                self.categories.iter().any(|wanted| wanted.eq_ignore_ascii_case(category))
+            })
+        {
+            return false;
+        }
+
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let Some(published) = item.pub_date.as_deref().and_then(parse_pub_date) else {
+            return false;
+        };
+        self.since.is_none_or(|since| published >= since)
+            && self.until.is_none_or(|until| published <= until)
+    }
+}
+
+/// 缓存中的订阅条目
+#[derive(Debug, Clone)]
+pub struct CachedRssItem {
+    /// 所属 feed URL
+    pub feed_url: String,
+    /// 所属 feed 标题
+    pub feed_title: String,
+    /// 条目
+    pub item: RssFeedItem,
+}
 
 /// RSS Feed 接口
 ///
@@ -37,6 +92,8 @@ pub struct RssInterface {
     cache: Option<Arc<RwLock<RssCache>>>,
     /// 模板管理器
     template_manager: Option<RssTemplateManager>,
+    /// 事件 Webhook 分发器（新条目匹配保存查询时投递）
+    webhooks: Option<Arc<WebhookDispatcher>>,
 }
 
 impl RssInterface {
@@ -47,6 +104,7 @@ impl RssInterface {
             parser: RssParser::new(),
             cache: None,
             template_manager: None,
+            webhooks: None,
         }
     }
 
@@ -57,9 +115,18 @@ impl RssInterface {
             parser: RssParser::new(),
             cache: Some(cache),
             template_manager: None,
+            webhooks: None,
         }
     }
 
+    /// 设置事件 Webhook 分发器
+    ///
+    /// 持久化 feed 更新时，匹配保存查询的新条目会以 `rss_items_matched` 事件投递
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookDispatcher>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
     /// 设置模板目录
     pub fn set_template_dir(&mut self, template_dir: &str) {
         self.template_manager = Some(RssTemplateManager::new(template_dir));
@@ -69,15 +136,15 @@ impl RssInterface {
     pub async fn fetch(&self, query: &RssFeedQuery) -> Result<RssFeed, Box<dyn std::error::Error + Send + Sync>> {
         // 检查缓存
         if let Some(ref cache) = self.cache {
-            let cache_guard = cache.read().await;
-            
+            let cache = cache.read().await.clone();
+
             // 检查是否需要更新
-            let needs_update = cache_guard.needs_update(&query.url)
+            let needs_update = cache.needs_update_async(&query.url).await
                 .unwrap_or(true);
 
             if !needs_update {
                 // 从缓存获取
-                if let Ok(Some(feed)) = cache_guard.get(&query.url) {
+                if let Ok(Some(feed)) = cache.get_async(&query.url).await {
                     return Ok(feed);
                 }
             }
@@ -88,14 +155,14 @@ impl RssInterface {
 
         // 存入缓存（临时 RSS，默认TTL）
         if let Some(ref cache) = self.cache {
-            let cache_guard = cache.write().await;
-            let _ = cache_guard.set(
+            let cache = cache.read().await.clone();
+            let _ = cache.set_async(
                 &query.url,
                 &feed,
                 false, // 临时
                 None,  // 无自动更新间隔
                 Some(std::time::Duration::from_secs(3600)), // 1小时TTL
-            );
+            ).await;
         }
 
         Ok(feed)
@@ -106,18 +173,41 @@ impl RssInterface {
         &self,
         url: &str,
         update_interval: u64,
+    ) -> Result<RssFeed, Box<dyn std::error::Error + Send + Sync>> {
+        self.fetch_persistent_inner(url, update_interval, false).await
+    }
+
+    /// 立即刷新订阅，忽略更新间隔
+    ///
+    /// 未订阅的 URL 会以默认间隔订阅
+    pub async fn refresh(&self, url: &str) -> Result<RssFeed, Box<dyn std::error::Error + Send + Sync>> {
+        let mut update_interval = DEFAULT_UPDATE_INTERVAL;
+        if let Some(ref cache) = self.cache {
+            let meta = cache.read().await.get_meta(url).map_err(|e| e.to_string())?;
+            if let Some(interval) = meta.and_then(|meta| meta.update_interval) {
+                update_interval = interval;
+            }
+        }
+        self.fetch_persistent_inner(url, update_interval, true).await
+    }
+
+    /// 获取持久化 RSS feed，`force` 为 true 时忽略更新间隔
+    async fn fetch_persistent_inner(
+        &self,
+        url: &str,
+        update_interval: u64,
+        force: bool,
     ) -> Result<RssFeed, Box<dyn std::error::Error + Send + Sync>> {
         // 检查缓存
         if let Some(ref cache) = self.cache {
-            let cache_guard = cache.read().await;
-            
+            let cache = cache.read().await.clone();
+
             // 检查是否需要更新
-            let needs_update = cache_guard.needs_update(url)
+            let needs_update = force || cache.needs_update_async(url).await
                 .unwrap_or(true);
-
             if !needs_update {
                 // 从缓存获取
-                if let Ok(Some(feed)) = cache_guard.get(url) {
+                if let Ok(Some(feed)) = cache.get_async(url).await {
                     return Ok(feed);
                 }
             }
@@ -132,21 +222,56 @@ impl RssInterface {
         };
         let feed = self.fetcher.fetch_and_parse(&query).await?;
 
-        // 存入缓存（持久化 RSS）
+        // 存入缓存（持久化 RSS），并按 GUID 记录已见条目
         if let Some(ref cache) = self.cache {
-            let cache_guard = cache.write().await;
-            let _ = cache_guard.set(
+            let cache = cache.read().await.clone();
+            let _ = cache.set_async(
                 url,
                 &feed,
                 true, // 持久化
                 Some(update_interval),
                 None, // 不设置TTL
-            );
+            ).await;
+
+            // 首次获取的条目不视为新条目
+            if let Ok(Some(new_keys)) = cache.record_seen_async(url, &feed.items).await {
+                self.notify_new_items(url, &new_items(&new_keys, &feed));
+            }
         }
 
         Ok(feed)
     }
 
+    /// 新条目匹配保存查询时投递 Webhook 事件
+    fn notify_new_items(&self, url: &str, new_items: &[&RssFeedItem]) {
+        let Some(webhooks) = &self.webhooks else {
+            return;
+        };
+
+        if new_items.is_empty() {
+            return;
+        }
+
+        for query in &webhooks.config().rss_saved_queries {
+            let items: Vec<_> = new_items
+                .iter()
+                .filter(|item| matches_saved_query(query, item))
+                .map(|item| MatchedRssItem {
+                    title: item.title.clone(),
+                    link: item.link.clone(),
+                    pub_date: item.pub_date.clone(),
+                })
+                .collect();
+            if !items.is_empty() {
+                webhooks.dispatch(WebhookEvent::RssItemsMatched {
+                    feed_url: url.to_string(),
+                    query: query.clone(),
+                    items,
+                });
+            }
+        }
+    }
+
     /// 解析 RSS feed 内容
     pub fn parse(&self, content: &str) -> Result<RssFeed, Box<dyn std::error::Error + Send + Sync>> {
         self.parser.parse(content)
@@ -197,6 +322,168 @@ impl RssInterface {
 
         Ok(added_count)
     }
+
+    /// 列出订阅（持久化的 RSS feeds）
+    pub async fn subscriptions(&self) -> Result<Vec<RssFeedCacheMeta>, Box<dyn std::error::Error + Send + Sync>> {
+        let cache = self.cache.as_ref().ok_or("RSS cache not configured")?;
+        let feeds = cache.read().await.list_persistent_feeds().map_err(|e| e.to_string())?;
+        Ok(feeds)
+    }
+
+    /// 取消订阅并删除缓存的 feed
+    ///
+    /// # Returns
+    ///
+    /// 订阅不存在时返回 false
+    pub async fn unsubscribe(&self, url: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let cache = self.cache.as_ref().ok_or("RSS cache not configured")?;
+        let cache_guard = cache.write().await;
+        let subscribed = cache_guard
+            .get_meta(url)
+            .map_err(|e| e.to_string())?
+            .is_some_and(|meta| meta.persistent);
+        if subscribed {
+            cache_guard.delete(url).map_err(|e| e.to_string())?;
+        }
+        Ok(subscribed)
+    }
+
+    /// 列出订阅 feed 中缓存的条目（按发布时间从新到旧，无发布时间的排在最后）
+    ///
+    /// 只读取缓存，不发起网络请求
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - 过滤条件
+    pub async fn cached_items(
+        &self,
+        filter: &RssItemFilter,
+    ) -> Result<Vec<CachedRssItem>, Box<dyn std::error::Error + Send + Sync>> {
+        let cache = self.cache.as_ref().ok_or("RSS cache not configured")?;
+        let cache = cache.read().await.clone();
+
+        let mut items = Vec::new();
+        for meta in cache.list_persistent_feeds().map_err(|e| e.to_string())? {
+            if filter.feed_url.as_ref().is_some_and(|url| *url != meta.url) {
+                continue;
+            }
+            let Some(feed) = cache.get_async(&meta.url).await.map_err(|e| e.to_string())? else {
+                continue;
+            };
+            let read_keys = match filter.unread_for {
+                Some(ref token) => cache.read_keys_async(token, &meta.url).await.map_err(|e| e.to_string())?,
+                None => HashSet::new(),
+            };
+            items.extend(
+                feed.items
+                    .into_iter()
+                    .filter(|item| filter.matches(item) && !read_keys.contains(rss_item_key(item)))
+                    .map(|item| CachedRssItem {
+                        feed_url: meta.url.clone(),
+                        feed_title: feed.meta.title.clone(),
+                        item,
+                    }),
+            );
+        }
+
+        items.sort_by_cached_key(|cached| {
+            std::cmp::Reverse(cached.item.pub_date.as_deref().and_then(parse_pub_date))
+        });
+        Ok(items)
+    }
+
+    /// 将条目标记为已读
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - 客户端令牌（不能为空或包含 ':'）
+    /// * `url` - Feed URL
+    /// * `item_keys` - 条目 GUID（没有 GUID 时为链接）
+    ///
+    /// # Returns
+    ///
+    /// 新标记为已读的条目数
+    pub async fn mark_read(
+        &self,
+        token: &str,
+        url: &str,
+        item_keys: &[String],
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let cache = self.cache.as_ref().ok_or("RSS cache not configured")?;
+        let cache = cache.read().await.clone();
+        Ok(cache.mark_read_async(token, url, item_keys, true).await.map_err(|e| e.to_string())?)
+    }
+
+    /// 将条目重新标记为未读，返回状态发生变化的条目数
+    pub async fn mark_unread(
+        &self,
+        token: &str,
+        url: &str,
+        item_keys: &[String],
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let cache = self.cache.as_ref().ok_or("RSS cache not configured")?;
+        let cache = cache.read().await.clone();
+        Ok(cache.mark_read_async(token, url, item_keys, false).await.map_err(|e| e.to_string())?)
+    }
+
+    /// 将 feed 中缓存的全部条目标记为已读，返回新标记为已读的条目数
+    pub async fn mark_feed_read(
+        &self,
+        token: &str,
+        url: &str,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let cache = self.cache.as_ref().ok_or("RSS cache not configured")?;
+        let cache = cache.read().await.clone();
+        Ok(cache.mark_feed_read_async(token, url).await.map_err(|e| e.to_string())?)
+    }
+
+    /// 统计客户端在每个订阅 feed 中的未读条目数
+    ///
+    /// # Returns
+    ///
+    /// {feed URL: 未读条目数}
+    pub async fn unread_counts(
+        &self,
+        token: &str,
+    ) -> Result<std::collections::BTreeMap<String, usize>, Box<dyn std::error::Error + Send + Sync>> {
+        let cache = self.cache.as_ref().ok_or("RSS cache not configured")?;
+        let cache = cache.read().await.clone();
+        Ok(cache.unread_counts_async(token).await.map_err(|e| e.to_string())?)
+    }
+}
+
+/// 解析条目发布时间（RSS 2.0 使用 RFC 2822，Atom 使用 RFC 3339）
+///
+/// # Returns
+///
+/// 返回 Unix 时间戳，无法解析时返回 None
+pub fn parse_pub_date(date: &str) -> Option<i64> {
+    let date = date.trim();
+    chrono::DateTime::parse_from_rfc2822(date)
+        .or_else(|_| chrono::DateTime::parse_from_rfc3339(date))
+        .ok()
+        .map(|time| time.timestamp())
+}
+
+/// 取出 feed 中标识属于 `new_keys` 的条目
+fn new_items<'a>(new_keys: &[String], feed: &'a RssFeed) -> Vec<&'a RssFeedItem> {
+    let new_keys: HashSet<&str> = new_keys.iter().map(String::as_str).collect();
+    feed.items
+        .iter()
+        .filter(|item| new_keys.contains(rss_item_key(item)))
+        .collect()
+}
+
+/// 条目的标题或描述是否包含保存查询的所有关键词（忽略大小写）
+fn matches_saved_query(query: &str, item: &RssFeedItem) -> bool {
+    let text = format!(
+        "{} {}",
+        item.title,
+        item.description.as_deref().unwrap_or("")
+    )
+    .to_lowercase();
+    let mut keywords = query.split_whitespace().peekable();
+    keywords.peek().is_some() && keywords.all(|keyword| text.contains(&keyword.to_lowercase()))
 }
 
 #[cfg(test)]
@@ -210,4 +497,64 @@ mod tests {
         let interface = RssInterface::new(client);
         assert!(true);
     }
+
+    /// 构造只包含指定条目（标题, 链接）的 feed
+    fn feed(items: &[(&str, &str)]) -> RssFeed {
+        let items: String = items
+            .iter()
+            .map(|(title, link)| format!("<item><title>{}</title><link>{}</link></item>", title, link))
+            .collect();
+        let xml = format!(
+            "<?xml version=\"1.0\"?><rss version=\"2.0\"><channel><title>t</title><link>https://example.com</link><description>d</description>{}</channel></rss>",
+            items
+        );
+        RssParser::new().parse(&xml).unwrap()
+    }
+
+    #[test]
+    fn test_new_items_matching_saved_query() {
+        let current = feed(&[
+            ("Rust 1.81 released", "https://example.com/2"),
+            ("Go 1.23 released", "https://example.com/3"),
+            ("Rust 1.80 released", "https://example.com/1"),
+        ]);
+        let new_keys = vec!["https://example.com/2".to_string(), "https://example.com/3".to_string()];
+
+        let new = new_items(&new_keys, &current);
+        assert_eq!(new.len(), 2);
+
+        let matched: Vec<_> = new
+            .iter()
+            .filter(|item| matches_saved_query("rust RELEASED", item))
+            .map(|item| item.link.as_str())
+            .collect();
+        assert_eq!(matched, vec!["https://example.com/2"]);
+        assert!(!matches_saved_query("   ", new[0]));
+    }
+
+    #[test]
+    fn test_item_filter_category_and_date() {
+        let mut item = feed(&[("Rust", "https://example.com/1")]).items.remove(0);
+        item.categories = vec!["Tech".to_string()];
+        item.pub_date = Some("Tue, 10 Jun 2025 08:00:00 GMT".to_string());
+        let published = parse_pub_date("2025-06-10T08:00:00Z").unwrap();
+        assert_eq!(parse_pub_date(item.pub_date.as_deref().unwrap()), Some(published));
+
+        let mut filter = RssItemFilter {
+            categories: vec!["tech".to_string()],
+            since: Some(published),
+            ..Default::default()
+        };
+        assert!(filter.matches(&item));
+
+        filter.until = Some(published - 1);
+        assert!(!filter.matches(&item));
+
+        filter.until = None;
+        filter.categories = vec!["sports".to_string()];
+        assert!(!filter.matches(&item));
+
+        item.pub_date = None;
+        assert!(!RssItemFilter { since: Some(0), ..Default::default() }.matches(&item));
+    }
 }
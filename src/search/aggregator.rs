@@ -16,22 +16,80 @@
 //!
 //! 负责合并、去重、排序多个搜索引擎的结果
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::config::search::{AggregationConfig, AggregationMode};
 use crate::derive::{SearchResult, SearchResultItem, SearchQuery};
-use super::scoring::{score_and_sort_results, ScoringWeights};
-use super::standardization::{standardize_results, deduplicate_by_url};
+use super::session::{apply_session_context, requested_context};
+use super::explain::{attach_explanations, duplicate_sources, explain_requested};
+use super::scoring::{get_engine_authority, score_and_explain_results, score_and_sort_results_with_params, BM25Params, ScoringWeights, SharedScoringProfile};
+use super::standardization::{standardize_results, deduplicate_by_url, item_price, normalize_price, CurrencyConverter};
+use super::filtering::ResultFilter;
+use super::spam::SpamFilter;
+use super::language::LanguageFilter;
+
+/// 结果元数据中记录来源引擎的键
+pub const ENGINE_METADATA_KEY: &str = "engine";
+
+/// 查询参数中指定排序方式的键（值见 [`SortBy::from_param`]）
+pub const SORT_PARAM_KEY: &str = "sort";
 
 /// 聚合策略
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AggregationStrategy {
     /// 按相关性合并
     Merged,
-    /// 轮询各引擎
+    /// 轮询各引擎（引擎按其最高分结果的先后排列，每轮各取一条）
     RoundRobin,
+    /// 严格按引擎优先级排列，同一引擎内保持评分顺序，未列出的引擎排在最后
+    EnginePriority {
+        /// 从高到低的引擎优先级
+        engines: Vec<String>,
+    },
+    /// 只按结果自身的相关性评分排列，不计入引擎权威度与自适应引擎权重
+    ScoreOnly,
     /// 加权排序
     Ranked,
     /// 自定义
     Custom,
+    /// 抢先返回：指定数量的引擎返回结果后立即按相关性合并，
+    /// 其余引擎在后台继续执行以预热页面缓存
+    Race {
+        /// 需要等待的引擎数（至少为 1）
+        engines: usize,
+    },
+}
+
+impl AggregationStrategy {
+    /// 从配置文件的 `search.aggregation` 段构建聚合策略
+    pub fn from_config(config: &AggregationConfig) -> Self {
+        match config.strategy {
+            AggregationMode::Merged => Self::Merged,
+            AggregationMode::RoundRobin => Self::RoundRobin,
+            AggregationMode::EnginePriority => Self::EnginePriority {
+                engines: config.engine_priority.clone(),
+            },
+            AggregationMode::ScoreOnly => Self::ScoreOnly,
+        }
+    }
+
+    /// 抢先返回需要等待的引擎数（非抢先策略返回 None）
+    pub fn race_quorum(&self) -> Option<usize> {
+        match *self {
+            Self::Race { engines } => Some(engines.max(1)),
+            _ => None,
+        }
+    }
+
+    /// 引擎在优先级中的位置（未列出的引擎排在所有已列出的引擎之后）
+    fn engine_rank(engines: &[String], engine: &str) -> usize {
+        engines
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(engine))
+            .unwrap_or(engines.len())
+    }
 }
 
 /// 排序方式
@@ -43,6 +101,21 @@ pub enum SortBy {
     Time,
     /// 来源
     Source,
+    /// 价格（从低到高，无价格的结果排在最后）
+    Price,
+}
+
+impl SortBy {
+    /// 从请求参数解析排序方式
+    pub fn from_param(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "relevance" => Some(Self::Relevance),
+            "time" | "date" => Some(Self::Time),
+            "source" => Some(Self::Source),
+            "price" => Some(Self::Price),
+            _ => None,
+        }
+    }
 }
 
 /// 搜索聚合器
@@ -53,6 +126,18 @@ pub struct SearchAggregator {
     sort_by: SortBy,
     /// 评分权重（可选）
     scoring_weights: Option<ScoringWeights>,
+    /// 评分配置档（可选，设置后替代 `scoring_weights` 并按搜索类型选择评分参数）
+    scoring_profile: Option<Arc<SharedScoringProfile>>,
+    /// 价格换算（可选）
+    currency_converter: Option<CurrencyConverter>,
+    /// 结果过滤器（可选）
+    result_filter: Option<Arc<ResultFilter>>,
+    /// 垃圾结果过滤器（可选）
+    spam_filter: Option<SpamFilter>,
+    /// 结果语言过滤器（可选）
+    language_filter: Option<LanguageFilter>,
+    /// 会话上下文的评分提升比例（0 表示不提升）
+    session_boost: f64,
 }
 
 impl SearchAggregator {
@@ -62,6 +147,12 @@ impl SearchAggregator {
             strategy, 
             sort_by,
             scoring_weights: None,
+            scoring_profile: None,
+            currency_converter: None,
+            result_filter: None,
+            spam_filter: None,
+            language_filter: None,
+            session_boost: 0.0,
         }
     }
 
@@ -71,14 +162,102 @@ impl SearchAggregator {
         self
     }
 
+    /// 设置评分配置档，按查询的搜索类型选择 BM25 参数与评分权重
+    pub fn with_scoring_profile(mut self, profile: Arc<SharedScoringProfile>) -> Self {
+        self.scoring_profile = Some(profile);
+        self
+    }
+
+    /// 设置价格换算，价格统一换算为目标货币后再排序
+    pub fn with_currency_conversion(mut self, converter: CurrencyConverter) -> Self {
+        self.currency_converter = Some(converter);
+        self
+    }
+
+    /// 设置结果过滤器，标准化后按域名与正则规则移除结果
+    pub fn with_result_filter(mut self, filter: Arc<ResultFilter>) -> Self {
+        self.result_filter = Some(filter);
+        self
+    }
+
+    /// 设置垃圾结果过滤器，去重后移除垃圾评分达到阈值的结果
+    pub fn with_spam_filter(mut self, filter: SpamFilter) -> Self {
+        self.spam_filter = Some(filter);
+        self
+    }
+
+    /// 设置结果语言过滤器，评分后按请求语言提升或移除结果
+    pub fn with_language_filter(mut self, filter: LanguageFilter) -> Self {
+        self.language_filter = Some(filter);
+        self
+    }
+
+    /// 设置会话上下文的评分提升比例，评分后提升包含查询携带的会话上下文词的结果
+    pub fn with_session_boost(mut self, boost: f64) -> Self {
+        self.session_boost = boost;
+        self
+    }
+
     /// 聚合多个搜索结果（使用智能评分）
     pub fn aggregate_with_scoring(
         &self, 
+        results: Vec<SearchResult>,
+        query: &SearchQuery,
+    ) -> SearchResult {
+        self.aggregate_scored(results, query, None, None)
+    }
+
+    /// 聚合多个搜索结果（自适应引擎权重）
+    ///
+    /// 使用引擎的历史质量评分替代静态权威度表：
+    /// 质量高的引擎的结果被提升，缓慢或不稳定引擎的结果被降低。
+    /// `engine_weights` 中没有的引擎回退到 [`get_engine_authority`]
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - 各引擎的搜索结果
+    /// * `query` - 搜索查询
+    /// * `engine_weights` - 引擎名到质量评分（0.0 - 1.0）的映射
+    pub fn aggregate_with_engine_weights(
+        &self,
+        results: Vec<SearchResult>,
+        query: &SearchQuery,
+        engine_weights: &HashMap<String, f64>,
+    ) -> SearchResult {
+        self.aggregate_scored(results, query, Some(engine_weights), None)
+    }
+
+    /// 按指定的聚合策略与排序方式聚合（替代聚合器的默认策略与排序方式）
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - 各引擎的搜索结果
+    /// * `query` - 搜索查询
+    /// * `engine_weights` - 引擎名到质量评分的映射（None 时使用静态权威度）
+    /// * `strategy` - 聚合策略
+    /// * `sort_by` - 排序方式（优先于查询参数中的排序方式）
+    pub fn aggregate_with_options(
+        &self,
+        results: Vec<SearchResult>,
+        query: &SearchQuery,
+        engine_weights: Option<&HashMap<String, f64>>,
+        strategy: &AggregationStrategy,
+        sort_by: SortBy,
+    ) -> SearchResult {
+        self.aggregate_scored(results, query, engine_weights, Some((strategy, sort_by)))
+    }
+
+    /// 标准化、合并、去重并评分
+    ///
+    /// `options` 为本次聚合使用的策略与排序方式，None 时使用聚合器的默认设置
+    fn aggregate_scored(
+        &self,
         mut results: Vec<SearchResult>,
         query: &SearchQuery,
+        engine_weights: Option<&HashMap<String, f64>>,
+        options: Option<(&AggregationStrategy, SortBy)>,
     ) -> SearchResult {
-        use std::collections::HashMap;
-        
+        let strategy = options.map_or(&self.strategy, |(strategy, _)| strategy);
         if results.is_empty() {
             return SearchResult {
                 engine_name: "aggregated".to_string(),
@@ -91,9 +270,20 @@ impl SearchAggregator {
             };
         }
 
-        // 1. 标准化每个引擎的结果
+        // 1. 标准化并过滤每个引擎的结果，并记录结果来源引擎
         for result in &mut results {
             standardize_results(result);
+            if let Some(filter) = &self.result_filter {
+                filter.apply(result, query.engine_type);
+            }
+            for item in &mut result.items {
+                item.metadata
+                    .entry(ENGINE_METADATA_KEY.to_string())
+                    .or_insert_with(|| result.engine_name.clone());
+                if self.currency_converter.is_some() {
+                    normalize_price(item, self.currency_converter.as_ref());
+                }
+            }
         }
 
         // 2. 合并所有结果
@@ -102,11 +292,76 @@ impl SearchAggregator {
             .flat_map(|r| r.items.into_iter())
             .collect();
 
-        // 3. 去重
+        // 3. 去重，并移除垃圾结果（模板检测需要比对不同站点的结果）
+        //    请求评分解释时先记录将被合并的重复结果
+        let explain = explain_requested(query);
+        let duplicates = if explain { duplicate_sources(&all_items) } else { HashMap::new() };
         deduplicate_by_url(&mut all_items);
+        if let Some(filter) = &self.spam_filter {
+            filter.apply(&mut all_items);
+        }
 
-        // 4. 重新评分（基于查询）
-        score_and_sort_results(&mut all_items, query, "aggregated", self.scoring_weights.clone());
+        // 4. 重新评分（基于查询，评分参数按搜索类型选择）
+        let profile = self.scoring_profile.as_ref().map(|profile| profile.get());
+        let (bm25_params, scoring_weights) = match &profile {
+            Some(profile) => {
+                let (bm25, weights) = profile.for_category(query.engine_type);
+                (bm25.clone(), weights.clone())
+            }
+            None => (BM25Params::default(), self.scoring_weights.clone().unwrap_or_default()),
+        };
+        let aggregated_authority = get_engine_authority("aggregated");
+        let authority: Box<dyn Fn(&SearchResultItem) -> f64 + '_> = match engine_weights {
+            _ if *strategy == AggregationStrategy::ScoreOnly => Box::new(|_| 1.0),
+            Some(weights) => Box::new(move |item: &SearchResultItem| {
+                let engine = item.metadata.get(ENGINE_METADATA_KEY).map(String::as_str).unwrap_or("");
+                weights
+                    .get(engine)
+                    .copied()
+                    .unwrap_or_else(|| get_engine_authority(engine))
+            }),
+            None => Box::new(move |_| aggregated_authority),
+        };
+        let breakdowns = if explain {
+            score_and_explain_results(&mut all_items, query, &*authority, &scoring_weights, &bm25_params)
+        } else {
+            score_and_sort_results_with_params(&mut all_items, query, &*authority, &scoring_weights, &bm25_params);
+            HashMap::new()
+        };
+
+        // 5. 按请求指定的语言提升或移除结果
+        if let Some(filter) = &self.language_filter
+            && let Some(language) = LanguageFilter::requested_language(query)
+        {
+            filter.apply(&mut all_items, &language);
+        }
+
+        // 5.1 按会话上下文提升与之前查询相关的结果
+        let context = requested_context(query);
+        if !context.is_empty() {
+            apply_session_context(&mut all_items, &context, self.session_boost);
+        }
+
+        // 6. 按聚合策略排列，再按请求或默认方式排序（相关性顺序已由评分确定）
+        let mut all_items = Self::arrange_by_engine(strategy, all_items);
+        let sort_by = match options {
+            Some((_, sort_by)) => sort_by,
+            None => query
+                .params
+                .get(SORT_PARAM_KEY)
+                .and_then(|v| SortBy::from_param(v))
+                .unwrap_or(self.sort_by),
+        };
+        Self::sort_items(sort_by, &mut all_items);
+        if explain {
+            attach_explanations(
+                &mut all_items,
+                breakdowns,
+                duplicates,
+                query.engine_type,
+                (&bm25_params, &scoring_weights),
+            );
+        }
 
         let total_results = all_items.len();
 
@@ -123,8 +378,6 @@ impl SearchAggregator {
 
     /// 聚合多个搜索结果
     pub fn aggregate(&self, results: Vec<SearchResult>) -> SearchResult {
-        use std::collections::HashMap;
-        
         if results.is_empty() {
             return SearchResult {
                 engine_name: "aggregated".to_string(),
@@ -157,7 +410,7 @@ impl SearchAggregator {
         let mut merged_items = Vec::new();
 
         match self.strategy {
-            AggregationStrategy::Merged => {
+            AggregationStrategy::Merged | AggregationStrategy::Race { .. } => {
                 for result in results {
                     for item in result.items {
                         if seen_urls.insert(item.url.clone()) {
@@ -165,7 +418,7 @@ impl SearchAggregator {
                         }
                     }
                 }
-                self.sort_items(&mut merged_items);
+                Self::sort_items(self.sort_by, &mut merged_items);
             }
             AggregationStrategy::RoundRobin => {
                 let max_len = results.iter().map(|r| r.items.len()).max().unwrap_or(0);
@@ -179,6 +432,27 @@ impl SearchAggregator {
                     }
                 }
             }
+            AggregationStrategy::EnginePriority { ref engines } => {
+                let mut results = results;
+                results.sort_by_key(|r| AggregationStrategy::engine_rank(engines, &r.engine_name));
+                for result in results {
+                    for item in result.items {
+                        if seen_urls.insert(item.url.clone()) {
+                            merged_items.push(item);
+                        }
+                    }
+                }
+            }
+            AggregationStrategy::ScoreOnly => {
+                for result in results {
+                    for item in result.items {
+                        if seen_urls.insert(item.url.clone()) {
+                            merged_items.push(item);
+                        }
+                    }
+                }
+                merged_items.sort_by(|a, b| b.score.total_cmp(&a.score));
+            }
             AggregationStrategy::Ranked => {
                 for result in results {
                     for item in result.items {
@@ -187,7 +461,7 @@ impl SearchAggregator {
                         }
                     }
                 }
-                self.sort_items(&mut merged_items);
+                Self::sort_items(self.sort_by, &mut merged_items);
             }
             AggregationStrategy::Custom => {
                 for result in results {
@@ -203,9 +477,45 @@ impl SearchAggregator {
         merged_items
     }
 
+    /// 按轮询或引擎优先级策略重新排列已评分的结果（其他策略保持评分顺序）
+    fn arrange_by_engine(strategy: &AggregationStrategy, items: Vec<SearchResultItem>) -> Vec<SearchResultItem> {
+        let engine_of = |item: &SearchResultItem| {
+            item.metadata.get(ENGINE_METADATA_KEY).cloned().unwrap_or_default()
+        };
+
+        match strategy {
+            AggregationStrategy::RoundRobin => {
+                let total = items.len();
+                let mut queues: Vec<(String, VecDeque<SearchResultItem>)> = Vec::new();
+                for item in items {
+                    let engine = engine_of(&item);
+                    match queues.iter_mut().find(|(name, _)| *name == engine) {
+                        Some((_, queue)) => queue.push_back(item),
+                        None => queues.push((engine, VecDeque::from([item]))),
+                    }
+                }
+
+                let mut arranged = Vec::with_capacity(total);
+                while arranged.len() < total {
+                    for (_, queue) in &mut queues {
+                        arranged.extend(queue.pop_front());
+                    }
+                }
+                arranged
+            }
+            AggregationStrategy::EnginePriority { engines } => {
+                let mut items = items;
+                // 稳定排序：同一引擎的结果保持评分顺序
+                items.sort_by_key(|item| AggregationStrategy::engine_rank(engines, &engine_of(item)));
+                items
+            }
+            _ => items,
+        }
+    }
+
     /// 排序结果项
-    fn sort_items(&self, items: &mut [SearchResultItem]) {
-        match self.sort_by {
+    fn sort_items(sort_by: SortBy, items: &mut [SearchResultItem]) {
+        match sort_by {
             SortBy::Relevance => {
                 // 默认顺序即为相关性顺序
             }
@@ -215,6 +525,15 @@ impl SearchAggregator {
             SortBy::Source => {
                 items.sort_by(|a, b| a.url.cmp(&b.url));
             }
+            SortBy::Price => {
+                // 稳定排序：同价结果保持相关性顺序
+                items.sort_by(|a, b| match (item_price(a), item_price(b)) {
+                    (Some(pa), Some(pb)) => pa.total_cmp(&pb),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                });
+            }
         }
     }
 }
@@ -296,6 +615,13 @@ mod tests {
         assert_eq!(aggregated.items.len(), 3); // 去重后只有3个
     }
 
+    #[test]
+    fn test_race_quorum() {
+        assert_eq!(AggregationStrategy::Race { engines: 2 }.race_quorum(), Some(2));
+        assert_eq!(AggregationStrategy::Race { engines: 0 }.race_quorum(), Some(1));
+        assert_eq!(AggregationStrategy::Merged.race_quorum(), None);
+    }
+
     #[test]
     fn test_round_robin_strategy() {
         use std::collections::HashMap;
@@ -334,4 +660,390 @@ mod tests {
         assert_eq!(aggregated.items[0].title, "A1");
         assert_eq!(aggregated.items[1].title, "B1");
     }
+
+    /// 聚合策略测试用的固定结果：engine_a 三条、engine_b 两条，标题与查询的匹配程度依次递减
+    fn strategy_fixture() -> (Vec<SearchResult>, SearchQuery) {
+        let make = |engine: &str, items: Vec<SearchResultItem>| SearchResult {
+            engine_name: engine.to_string(),
+            total_results: Some(items.len()),
+            elapsed_ms: 10,
+            items,
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        let results = vec![
+            make(
+                "engine_a",
+                vec![
+                    create_test_item("https://a.example.com/1", "rust async runtime"),
+                    create_test_item("https://a.example.com/2", "rust"),
+                    create_test_item("https://a.example.com/3", "cooking"),
+                ],
+            ),
+            make(
+                "engine_b",
+                vec![
+                    create_test_item("https://b.example.com/1", "rust async runtime"),
+                    create_test_item("https://b.example.com/2", "gardening"),
+                ],
+            ),
+        ];
+        let query = SearchQuery {
+            query: "rust async runtime".to_string(),
+            ..Default::default()
+        };
+        (results, query)
+    }
+
+    fn engines_of(result: &SearchResult) -> Vec<&str> {
+        result
+            .items
+            .iter()
+            .map(|item| item.metadata[ENGINE_METADATA_KEY].as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_round_robin_strategy_with_scoring() {
+        let (results, query) = strategy_fixture();
+        let agg = SearchAggregator::new(AggregationStrategy::RoundRobin, SortBy::Relevance);
+
+        let aggregated = agg.aggregate_with_scoring(results, &query);
+        assert_eq!(
+            engines_of(&aggregated),
+            vec!["engine_a", "engine_b", "engine_a", "engine_b", "engine_a"]
+        );
+        // 同一引擎内保持评分顺序
+        assert_eq!(aggregated.items[4].url, "https://a.example.com/3");
+    }
+
+    #[test]
+    fn test_engine_priority_strategy() {
+        let (results, query) = strategy_fixture();
+        let agg = SearchAggregator::new(
+            AggregationStrategy::EnginePriority { engines: vec!["engine_b".to_string()] },
+            SortBy::Relevance,
+        );
+
+        let aggregated = agg.aggregate_with_scoring(results.clone(), &query);
+        assert_eq!(
+            engines_of(&aggregated),
+            vec!["engine_b", "engine_b", "engine_a", "engine_a", "engine_a"]
+        );
+        assert_eq!(aggregated.items[0].url, "https://b.example.com/1");
+        assert_eq!(aggregated.items[2].url, "https://a.example.com/1");
+
+        // 不评分的聚合同样按引擎优先级排列
+        let urls: Vec<String> = agg.aggregate(results).items.into_iter().map(|i| i.url).collect();
+        assert_eq!(urls[0], "https://b.example.com/1");
+        assert_eq!(urls[2], "https://a.example.com/1");
+    }
+
+    #[test]
+    fn test_score_only_strategy_ignores_engine_weights() {
+        let (results, query) = strategy_fixture();
+        let mut weights = HashMap::new();
+        weights.insert("engine_a".to_string(), 0.1);
+        weights.insert("engine_b".to_string(), 1.0);
+
+        // 默认策略下同样匹配的结果按引擎权重区分
+        let merged = SearchAggregator::default().aggregate_with_engine_weights(results.clone(), &query, &weights);
+        assert!(merged.items[0].score > merged.items[1].score);
+
+        // 只按评分排列时，交换引擎权重不影响结果顺序与评分
+        let agg = SearchAggregator::new(AggregationStrategy::ScoreOnly, SortBy::Relevance);
+        let aggregated = agg.aggregate_with_engine_weights(results.clone(), &query, &weights);
+        let swapped = HashMap::from([("engine_a".to_string(), 1.0), ("engine_b".to_string(), 0.1)]);
+        let reversed = agg.aggregate_with_engine_weights(results, &query, &swapped);
+        assert_eq!(aggregated.items.len(), 5);
+        assert!(aggregated.items.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        for (a, b) in aggregated.items.iter().zip(&reversed.items) {
+            assert_eq!(a.url, b.url);
+            assert_eq!(a.score, b.score);
+        }
+    }
+
+    #[test]
+    fn test_strategy_override_per_aggregation() {
+        let (results, query) = strategy_fixture();
+        let agg = SearchAggregator::default();
+        let urls = |result: SearchResult| result.items.into_iter().map(|i| i.url).collect::<Vec<_>>();
+
+        let round_robin = agg.aggregate_with_options(
+            results.clone(),
+            &query,
+            None,
+            &AggregationStrategy::RoundRobin,
+            SortBy::Relevance,
+        );
+        assert_eq!(
+            engines_of(&round_robin),
+            vec!["engine_a", "engine_b", "engine_a", "engine_b", "engine_a"]
+        );
+
+        let priority = agg.aggregate_with_options(
+            results.clone(),
+            &query,
+            None,
+            &AggregationStrategy::EnginePriority { engines: vec!["engine_b".to_string()] },
+            SortBy::Relevance,
+        );
+        assert_eq!(
+            urls(priority),
+            vec![
+                "https://b.example.com/1",
+                "https://b.example.com/2",
+                "https://a.example.com/1",
+                "https://a.example.com/2",
+                "https://a.example.com/3",
+            ]
+        );
+
+        // 只按评分排列时引擎权重不参与排序
+        let weights = HashMap::from([("engine_a".to_string(), 0.1), ("engine_b".to_string(), 1.0)]);
+        let score_only = agg.aggregate_with_options(
+            results.clone(),
+            &query,
+            Some(&weights),
+            &AggregationStrategy::ScoreOnly,
+            SortBy::Relevance,
+        );
+        assert!(score_only.items.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        let swapped = HashMap::from([("engine_a".to_string(), 1.0), ("engine_b".to_string(), 0.1)]);
+        let reversed = agg.aggregate_with_options(
+            results.clone(),
+            &query,
+            Some(&swapped),
+            &AggregationStrategy::ScoreOnly,
+            SortBy::Relevance,
+        );
+        assert_eq!(urls(score_only), urls(reversed));
+
+        // 显式排序方式优先于查询参数
+        let mut sorted_query = query.clone();
+        sorted_query.params.insert(SORT_PARAM_KEY.to_string(), "relevance".to_string());
+        let by_source = agg.aggregate_with_options(
+            results,
+            &sorted_query,
+            None,
+            &AggregationStrategy::Merged,
+            SortBy::Source,
+        );
+        let by_source = urls(by_source);
+        let mut expected = by_source.clone();
+        expected.sort();
+        assert_eq!(by_source, expected);
+
+        // 聚合器的默认策略不受影响
+        assert_eq!(agg.strategy, AggregationStrategy::Merged);
+    }
+
+    #[test]
+    fn test_strategy_from_config() {
+        let config = AggregationConfig {
+            strategy: AggregationMode::EnginePriority,
+            engine_priority: vec!["bing".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            AggregationStrategy::from_config(&config),
+            AggregationStrategy::EnginePriority { engines: vec!["bing".to_string()] }
+        );
+        assert_eq!(AggregationStrategy::from_config(&AggregationConfig::default()), AggregationStrategy::Merged);
+    }
+
+    #[test]
+    fn test_adaptive_engine_weights() {
+        let agg = SearchAggregator::default();
+        let query = SearchQuery {
+            query: "rust".to_string(),
+            ..Default::default()
+        };
+        let make = |engine: &str, url: &str| SearchResult {
+            engine_name: engine.to_string(),
+            total_results: Some(1),
+            elapsed_ms: 10,
+            items: vec![create_test_item(url, "rust")],
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        };
+
+        let mut weights = HashMap::new();
+        weights.insert("flaky".to_string(), 0.1);
+        weights.insert("solid".to_string(), 1.0);
+
+        let result = agg.aggregate_with_engine_weights(
+            vec![make("flaky", "https://a.com/"), make("solid", "https://b.com/")],
+            &query,
+            &weights,
+        );
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.items[0].metadata.get(ENGINE_METADATA_KEY).unwrap(), "solid");
+        assert!(result.items[0].score > result.items[1].score);
+    }
+
+    #[test]
+    fn test_spam_filter_removes_low_reputation_results() {
+        let config = crate::config::engines::SpamFilterConfig {
+            enabled: true,
+            low_reputation_domains: vec!["scraper.com".to_string()],
+            ..Default::default()
+        };
+        let agg = SearchAggregator::default().with_spam_filter(SpamFilter::new(&config));
+        let query = SearchQuery {
+            query: "rust".to_string(),
+            ..Default::default()
+        };
+        let result = SearchResult {
+            engine_name: "engine1".to_string(),
+            total_results: Some(2),
+            elapsed_ms: 10,
+            items: vec![
+                create_test_item("https://m.scraper.com/rust", "rust"),
+                create_test_item("https://rust-lang.org/", "rust"),
+            ],
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        };
+
+        let aggregated = agg.aggregate_with_scoring(vec![result], &query);
+        assert_eq!(aggregated.items.len(), 1);
+        assert_eq!(aggregated.items[0].url, "https://rust-lang.org/");
+    }
+
+    #[test]
+    fn test_language_filter_uses_requested_language() {
+        let config = crate::config::engines::LanguageFilterConfig {
+            enabled: true,
+            mode: crate::config::engines::LanguageFilterMode::Filter,
+            ..Default::default()
+        };
+        let agg = SearchAggregator::default().with_language_filter(LanguageFilter::new(&config));
+        let mut english = create_test_item("https://rust-lang.org/", "The Rust Programming Language");
+        english.content = "A language empowering everyone to build reliable and efficient software".to_string();
+        let mut chinese = create_test_item("https://rustcc.cn/", "Rust 中文社区");
+        chinese.content = "Rust 编程语言中文社区与资料".to_string();
+        let result = SearchResult {
+            engine_name: "engine1".to_string(),
+            total_results: Some(2),
+            elapsed_ms: 10,
+            items: vec![english, chinese],
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        };
+
+        let mut query = SearchQuery {
+            query: "rust".to_string(),
+            language: Some("zh".to_string()),
+            ..Default::default()
+        };
+        let aggregated = agg.aggregate_with_scoring(vec![result.clone()], &query);
+        assert_eq!(aggregated.items.len(), 1);
+        assert_eq!(aggregated.items[0].url, "https://rustcc.cn/");
+        assert_eq!(
+            aggregated.items[0].metadata.get(crate::search::standardization::LANGUAGE_METADATA_KEY).map(String::as_str),
+            Some("zh")
+        );
+
+        // 自动检测的查询语言不用于过滤
+        query.params.insert(crate::search::language::LANGUAGE_DETECTED_PARAM_KEY.to_string(), "true".to_string());
+        assert_eq!(agg.aggregate_with_scoring(vec![result], &query).items.len(), 2);
+    }
+
+    #[test]
+    fn test_session_context_boosts_related_results() {
+        let (results, mut query) = strategy_fixture();
+        query.query = "runtime".to_string();
+        let agg = SearchAggregator::default().with_session_boost(0.5);
+        let plain = agg.aggregate_with_scoring(results.clone(), &query);
+        assert_eq!(plain.items[0].url, "https://a.example.com/1");
+
+        // 上下文词只出现在 engine_b 的结果摘要中
+        let mut results = results;
+        results[1].items[0].content = "tokio tutorial".to_string();
+        query.params.insert(crate::search::SESSION_CONTEXT_PARAM_KEY.to_string(), "tokio".to_string());
+        let boosted = agg.aggregate_with_scoring(results, &query);
+        assert_eq!(boosted.items[0].url, "https://b.example.com/1");
+    }
+
+    #[test]
+    fn test_explain_attaches_breakdowns() {
+        let (mut results, mut query) = strategy_fixture();
+        results[1]
+            .items
+            .push(create_test_item("https://A.example.com/1", "rust async runtime"));
+        let agg = SearchAggregator::default();
+
+        let plain = agg.aggregate_with_scoring(results.clone(), &query);
+        assert!(plain.items.iter().all(|item| crate::search::explanation(item).is_none()));
+
+        query.params.insert(crate::search::EXPLAIN_PARAM_KEY.to_string(), "true".to_string());
+        let explained = agg.aggregate_with_scoring(results, &query);
+        assert_eq!(explained.items.len(), plain.items.len());
+        for (index, item) in explained.items.iter().enumerate() {
+            let explanation = crate::search::explanation(item).unwrap();
+            assert_eq!(explanation.rank, index + 1);
+            assert!((explanation.final_score - item.score).abs() < 1e-9);
+            assert!(explanation.breakdown.title_bm25 >= 0.0);
+        }
+        let merged = explained
+            .items
+            .iter()
+            .find(|item| item.url == "https://a.example.com/1")
+            .and_then(crate::search::explanation)
+            .unwrap();
+        assert_eq!(merged.merged_duplicates, vec!["engine_b"]);
+        assert!(merged.breakdown.title_exact > 0.0);
+    }
+
+    #[test]
+    fn test_sort_by_price_with_conversion() {
+        use std::collections::HashMap;
+
+        let converter = CurrencyConverter::new("USD", HashMap::from([("EUR".to_string(), 1.1)]));
+        let agg = SearchAggregator::default().with_currency_conversion(converter);
+        let mut query = SearchQuery {
+            query: "keyboard".to_string(),
+            ..Default::default()
+        };
+        query.params.insert(SORT_PARAM_KEY.to_string(), "price".to_string());
+
+        let priced = |url: &str, price: Option<&str>| {
+            let mut item = create_test_item(url, "keyboard");
+            item.result_type = ResultType::Shopping;
+            if let Some(price) = price {
+                item.metadata.insert("price".to_string(), price.to_string());
+            }
+            item
+        };
+        let result = SearchResult {
+            engine_name: "shop".to_string(),
+            total_results: Some(3),
+            elapsed_ms: 10,
+            items: vec![
+                priced("https://shop.example.com/a", Some("$30.00")),
+                priced("https://shop.example.com/b", None),
+                priced("https://shop.example.com/c", Some("€20,00")),
+            ],
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        };
+
+        let aggregated = agg.aggregate_with_scoring(vec![result], &query);
+        let urls: Vec<&str> = aggregated.items.iter().map(|i| i.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://shop.example.com/c", "https://shop.example.com/a", "https://shop.example.com/b"]);
+        assert_eq!(aggregated.items[0].metadata["price"], "22.00");
+        assert_eq!(aggregated.items[0].metadata["currency"], "USD");
+    }
+
+    #[test]
+    fn test_sort_by_from_param() {
+        assert_eq!(SortBy::from_param("PRICE"), Some(SortBy::Price));
+        assert_eq!(SortBy::from_param("unknown"), None);
+    }
 }
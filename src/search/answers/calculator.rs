@@ -0,0 +1,165 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 算术表达式回答器
+//!
+//! 支持 `+ - * / % ^`、括号与一元负号，如 `2+2*3`、`(1.5 + 2) ^ 2`
+
+use async_trait::async_trait;
+
+use super::{format_number, Answerer, InstantAnswer};
+
+/// 计算器
+pub struct CalculatorAnswerer;
+
+#[async_trait]
+impl Answerer for CalculatorAnswerer {
+    fn name(&self) -> &str {
+        "calculator"
+    }
+
+    async fn answer(&self, query: &str) -> Option<InstantAnswer> {
+        let expression = query.trim().trim_end_matches('=').trim();
+        let value = evaluate(expression)?;
+        Some(
+            InstantAnswer::new(self.name(), format_number(value))
+                .with_detail("expression", expression),
+        )
+    }
+}
+
+/// 计算表达式
+///
+/// 不是算术表达式（不含运算符、含其他字符）或结果非有限值时返回 None
+pub fn evaluate(expression: &str) -> Option<f64> {
+    if !expression
+        .chars()
+        .all(|c| c.is_ascii_digit() || c.is_whitespace() || "+-*/%^().".contains(c))
+    {
+        return None;
+    }
+    // 纯数字不算表达式
+    if !expression.chars().any(|c| "+-*/%^".contains(c)) {
+        return None;
+    }
+
+    let tokens: Vec<char> = expression.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut parser = Parser { tokens: &tokens, pos: 0 };
+    let value = parser.expr()?;
+    (parser.pos == tokens.len() && value.is_finite()).then_some(value)
+}
+
+/// 递归下降解析器
+struct Parser<'a> {
+    tokens: &'a [char],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    /// expr = term (('+' | '-') term)*
+    fn expr(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Some(value)
+    }
+
+    /// term = unary (('*' | '/' | '%') unary)*
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.unary()?;
+        while let Some(op @ ('*' | '/' | '%')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.unary()?;
+            value = match op {
+                '*' => value * rhs,
+                '/' => value / rhs,
+                _ => value % rhs,
+            };
+        }
+        Some(value)
+    }
+
+    /// unary = '-' unary | power
+    fn unary(&mut self) -> Option<f64> {
+        if self.peek() == Some('-') {
+            self.pos += 1;
+            return self.unary().map(|v| -v);
+        }
+        self.power()
+    }
+
+    /// power = primary ('^' unary)?，右结合
+    fn power(&mut self) -> Option<f64> {
+        let base = self.primary()?;
+        if self.peek() == Some('^') {
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return Some(base.powf(exponent));
+        }
+        Some(base)
+    }
+
+    /// primary = number | '(' expr ')'
+    fn primary(&mut self) -> Option<f64> {
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            let value = self.expr()?;
+            if self.peek() != Some(')') {
+                return None;
+            }
+            self.pos += 1;
+            return Some(value);
+        }
+
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() || c == '.' {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        self.tokens[start..self.pos].iter().collect::<String>().parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(evaluate("2+2*3"), Some(8.0));
+        assert_eq!(evaluate("(2 + 2) * 3"), Some(12.0));
+        assert_eq!(evaluate("2^3^2"), Some(512.0));
+        assert_eq!(evaluate("-2^2"), Some(-4.0));
+        assert_eq!(evaluate("10 % 4 - 0.5"), Some(1.5));
+    }
+
+    #[test]
+    fn test_rejects_non_expressions() {
+        assert_eq!(evaluate("42"), None);
+        assert_eq!(evaluate("1/0"), None);
+        assert_eq!(evaluate("2 +"), None);
+        assert_eq!(evaluate("(1 + 2"), None);
+        assert_eq!(evaluate("rust 2024"), None);
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 换算回答器
+//!
+//! 处理 `<数值> <单位> to|in <单位>` 形式的查询：
+//! - [`CurrencyAnswerer`] 使用配置的汇率表换算货币，如 `25 usd to eur`
+//! - [`UnitAnswerer`] 换算长度、质量与温度，如 `10 km in mi`
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{format_number, Answerer, InstantAnswer};
+use crate::search::standardization::CurrencyConverter;
+
+/// `<数值> <单位> to|in|-> <单位>`
+static CONVERSION_QUERY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(-?\d+(?:[.,]\d+)?)\s*([a-z°]+)\s+(?:to|in|into|->)\s+([a-z°]+)$")
+        .expect("valid conversion query regex")
+});
+
+/// 解析换算查询，返回 (数值, 源单位, 目标单位)
+fn parse_conversion(query: &str) -> Option<(f64, String, String)> {
+    let caps = CONVERSION_QUERY.captures(query.trim())?;
+    let value = caps[1].replace(',', ".").parse().ok()?;
+    Some((value, caps[2].to_lowercase(), caps[3].to_lowercase()))
+}
+
+/// 货币换算
+pub struct CurrencyAnswerer {
+    converter: CurrencyConverter,
+}
+
+impl CurrencyAnswerer {
+    /// 使用汇率表创建
+    pub fn new(converter: CurrencyConverter) -> Self {
+        Self { converter }
+    }
+}
+
+#[async_trait]
+impl Answerer for CurrencyAnswerer {
+    fn name(&self) -> &str {
+        "currency"
+    }
+
+    async fn answer(&self, query: &str) -> Option<InstantAnswer> {
+        let (amount, from, to) = parse_conversion(query)?;
+        if from.len() != 3 || to.len() != 3 {
+            return None;
+        }
+        let converted = self.converter.convert_between(amount, &from, &to)?;
+        let rate = self.converter.convert_between(1.0, &from, &to)?;
+        let (from, to) = (from.to_uppercase(), to.to_uppercase());
+
+        Some(
+            InstantAnswer::new(self.name(), format!("{:.2} {}", converted, to))
+                .with_detail("rate", format!("1 {} = {} {}", from, format_number(rate), to))
+                .with_detail("from", from)
+                .with_detail("to", to),
+        )
+    }
+}
+
+/// 物理量
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Length,
+    Mass,
+    Temperature,
+}
+
+/// 单位表：(别名, 物理量, 折合基本单位的系数)，基本单位为米、千克
+const UNITS: &[(&[&str], Dimension, f64)] = &[
+    (&["mm", "millimeter", "millimeters"], Dimension::Length, 0.001),
+    (&["cm", "centimeter", "centimeters"], Dimension::Length, 0.01),
+    (&["m", "meter", "meters", "metre", "metres"], Dimension::Length, 1.0),
+    (&["km", "kilometer", "kilometers", "kilometre", "kilometres"], Dimension::Length, 1000.0),
+    (&["in", "inch", "inches"], Dimension::Length, 0.0254),
+    (&["ft", "foot", "feet"], Dimension::Length, 0.3048),
+    (&["yd", "yard", "yards"], Dimension::Length, 0.9144),
+    (&["mi", "mile", "miles"], Dimension::Length, 1609.344),
+    (&["mg", "milligram", "milligrams"], Dimension::Mass, 0.000_001),
+    (&["g", "gram", "grams"], Dimension::Mass, 0.001),
+    (&["kg", "kilogram", "kilograms"], Dimension::Mass, 1.0),
+    (&["t", "tonne", "tonnes"], Dimension::Mass, 1000.0),
+    (&["oz", "ounce", "ounces"], Dimension::Mass, 0.028_349_523_125),
+    (&["lb", "lbs", "pound", "pounds"], Dimension::Mass, 0.453_592_37),
+    (&["c", "°c", "celsius"], Dimension::Temperature, 0.0),
+    (&["f", "°f", "fahrenheit"], Dimension::Temperature, 0.0),
+    (&["k", "kelvin"], Dimension::Temperature, 0.0),
+];
+
+/// 查找单位，返回 (规范名, 物理量, 系数)
+fn lookup_unit(name: &str) -> Option<(&'static str, Dimension, f64)> {
+    UNITS
+        .iter()
+        .find(|(aliases, _, _)| aliases.contains(&name))
+        .map(|(aliases, dimension, factor)| (aliases[0], *dimension, *factor))
+}
+
+/// 温度换算（经由摄氏度）
+fn convert_temperature(value: f64, from: &str, to: &str) -> f64 {
+    let celsius = match from.trim_start_matches('°') {
+        "f" => (value - 32.0) * 5.0 / 9.0,
+        "k" => value - 273.15,
+        _ => value,
+    };
+    match to.trim_start_matches('°') {
+        "f" => celsius * 9.0 / 5.0 + 32.0,
+        "k" => celsius + 273.15,
+        _ => celsius,
+    }
+}
+
+/// 换算数值，单位未知或物理量不一致时返回 None
+pub fn convert_units(value: f64, from: &str, to: &str) -> Option<f64> {
+    let (from_name, from_dimension, from_factor) = lookup_unit(&from.to_lowercase())?;
+    let (to_name, to_dimension, to_factor) = lookup_unit(&to.to_lowercase())?;
+    if from_dimension != to_dimension {
+        return None;
+    }
+    if from_dimension == Dimension::Temperature {
+        return Some(convert_temperature(value, from_name, to_name));
+    }
+    Some(value * from_factor / to_factor)
+}
+
+/// 单位换算
+pub struct UnitAnswerer;
+
+#[async_trait]
+impl Answerer for UnitAnswerer {
+    fn name(&self) -> &str {
+        "unit_conversion"
+    }
+
+    async fn answer(&self, query: &str) -> Option<InstantAnswer> {
+        let (value, from, to) = parse_conversion(query)?;
+        let converted = convert_units(value, &from, &to)?;
+        // 显示时使用规范单位名
+        let (from, _, _) = lookup_unit(&from)?;
+        let (to, _, _) = lookup_unit(&to)?;
+
+        Some(
+            InstantAnswer::new(self.name(), format!("{} {}", format_number(converted), to))
+                .with_detail("from", format!("{} {}", format_number(value), from))
+                .with_detail("to", to),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_currency_answer() {
+        let rates = HashMap::from([("USD".to_string(), 0.9), ("GBP".to_string(), 1.2)]);
+        let answerer = CurrencyAnswerer::new(CurrencyConverter::new("EUR", rates));
+
+        let answer = answerer.answer("25 usd to eur").await.unwrap();
+        assert_eq!(answer.answer, "22.50 EUR");
+        assert_eq!(answer.details["rate"], "1 USD = 0.9 EUR");
+
+        let answer = answerer.answer("12 gbp in usd").await.unwrap();
+        assert_eq!(answer.answer, "16.00 USD");
+
+        assert!(answerer.answer("25 usd to jpy").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unit_answer() {
+        let answer = UnitAnswerer.answer("5 km to mi").await.unwrap();
+        assert_eq!(answer.answer, "3.1068559612 mi");
+
+        let answer = UnitAnswerer.answer("100 f to c").await.unwrap();
+        assert_eq!(answer.answer, "37.7777777778 c");
+
+        let answer = UnitAnswerer.answer("12 in to cm").await.unwrap();
+        assert_eq!(answer.answer, "30.48 cm");
+
+        assert!(UnitAnswerer.answer("5 kg to km").await.is_none());
+        assert!(UnitAnswerer.answer("how to cook rice").await.is_none());
+    }
+}
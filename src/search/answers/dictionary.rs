@@ -0,0 +1,120 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 词典回答器
+//!
+//! 处理 `define ephemeral`、`meaning of serendipity`，释义来自 Free Dictionary API
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use std::sync::Arc;
+
+use super::{Answerer, InstantAnswer};
+use crate::net::client::HttpClient;
+
+/// Free Dictionary API 地址
+const API_URL: &str = "https://api.dictionaryapi.dev/api/v2/entries/en";
+
+/// `define <词>` / `definition of <词>` / `meaning of <词>`
+static DEFINE_QUERY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?:define|definition\s+of|meaning\s+of)\s+([a-z][a-z'-]*)$").expect("valid define query regex")
+});
+
+/// 词典查询
+pub struct DictionaryAnswerer {
+    client: Arc<HttpClient>,
+}
+
+impl DictionaryAnswerer {
+    /// 使用共享 HTTP 客户端创建
+    pub fn new(client: Arc<HttpClient>) -> Self {
+        Self { client }
+    }
+
+    /// 解析 API 响应，取第一个词条的第一条释义
+    fn parse_definition(word: &str, json_str: &str) -> Option<InstantAnswer> {
+        let entries: Value = serde_json::from_str(json_str).ok()?;
+        let entry = entries.as_array()?.first()?;
+        let meaning = entry.get("meanings")?.as_array()?.first()?;
+        let definition = meaning
+            .get("definitions")?
+            .as_array()?
+            .first()?
+            .get("definition")?
+            .as_str()?;
+
+        let mut answer = InstantAnswer::new("dictionary", definition).with_detail("word", word);
+        if let Some(part) = meaning.get("partOfSpeech").and_then(|p| p.as_str()) {
+            answer = answer.with_detail("part_of_speech", part);
+        }
+        if let Some(phonetic) = entry.get("phonetic").and_then(|p| p.as_str()) {
+            answer = answer.with_detail("phonetic", phonetic);
+        }
+        answer.url = entry
+            .get("sourceUrls")
+            .and_then(|u| u.as_array())
+            .and_then(|u| u.first())
+            .and_then(|u| u.as_str())
+            .map(|u| u.to_string());
+        Some(answer)
+    }
+}
+
+#[async_trait]
+impl Answerer for DictionaryAnswerer {
+    fn name(&self) -> &str {
+        "dictionary"
+    }
+
+    async fn answer(&self, query: &str) -> Option<InstantAnswer> {
+        let caps = DEFINE_QUERY.captures(query.trim())?;
+        let word = caps[1].to_lowercase();
+
+        let url = format!("{}/{}", API_URL, urlencoding::encode(&word));
+        let response = self.client.get(&url, None).await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let body = response.text().await.ok()?;
+        Self::parse_definition(&word, &body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_define_query() {
+        assert_eq!(&DEFINE_QUERY.captures("define ephemeral").unwrap()[1], "ephemeral");
+        assert_eq!(&DEFINE_QUERY.captures("Meaning of Serendipity").unwrap()[1], "Serendipity");
+        assert!(DEFINE_QUERY.captures("define rust traits").is_none());
+    }
+
+    #[test]
+    fn test_parse_definition() {
+        let json = r#"[{"word":"ephemeral","phonetic":"/ɪˈfɛm(ə)rəl/",
+            "meanings":[{"partOfSpeech":"adjective","definitions":[{"definition":"Lasting for a short period of time."}]}],
+            "sourceUrls":["https://en.wiktionary.org/wiki/ephemeral"]}]"#;
+        let answer = DictionaryAnswerer::parse_definition("ephemeral", json).unwrap();
+        assert_eq!(answer.answer, "Lasting for a short period of time.");
+        assert_eq!(answer.details["part_of_speech"], "adjective");
+        assert_eq!(answer.url.as_deref(), Some("https://en.wiktionary.org/wiki/ephemeral"));
+
+        let not_found = r#"{"title":"No Definitions Found"}"#;
+        assert!(DictionaryAnswerer::parse_definition("xyzzy", not_found).is_none());
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 哈希回答器
+//!
+//! 处理 `sha256 of hello`、`md5 hello` 这类查询
+
+use async_trait::async_trait;
+use md5::Md5;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+
+use super::{Answerer, InstantAnswer};
+
+/// `<算法> [of] <文本>`
+static HASH_QUERY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(md5|sha224|sha256|sha384|sha512)\s+(?:of\s+)?(.+)$").expect("valid hash query regex")
+});
+
+/// 哈希计算器
+pub struct HashAnswerer;
+
+/// 转为小写十六进制
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 计算指定算法的哈希，未知算法返回 None
+pub fn hash_hex(algorithm: &str, input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let digest = match algorithm.to_ascii_lowercase().as_str() {
+        "md5" => to_hex(&Md5::digest(bytes)),
+        "sha224" => to_hex(&Sha224::digest(bytes)),
+        "sha256" => to_hex(&Sha256::digest(bytes)),
+        "sha384" => to_hex(&Sha384::digest(bytes)),
+        "sha512" => to_hex(&Sha512::digest(bytes)),
+        _ => return None,
+    };
+    Some(digest)
+}
+
+#[async_trait]
+impl Answerer for HashAnswerer {
+    fn name(&self) -> &str {
+        "hash"
+    }
+
+    async fn answer(&self, query: &str) -> Option<InstantAnswer> {
+        let caps = HASH_QUERY.captures(query.trim())?;
+        let algorithm = caps[1].to_ascii_lowercase();
+        let input = &caps[2];
+        let digest = hash_hex(&algorithm, input)?;
+        Some(
+            InstantAnswer::new(self.name(), digest)
+                .with_detail("algorithm", algorithm)
+                .with_detail("input", input),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hash_answers() {
+        let answer = HashAnswerer.answer("sha256 of hello").await.unwrap();
+        assert_eq!(answer.answer, "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+        assert_eq!(answer.details["algorithm"], "sha256");
+
+        let answer = HashAnswerer.answer("MD5 hello").await.unwrap();
+        assert_eq!(answer.answer, "5d41402abc4b2a76b9719d911017c592");
+
+        assert!(HashAnswerer.answer("sha256").await.is_none());
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 即时答案模块
+//!
+//! 拦截计算、单位/货币换算、哈希、词典释义等查询，在网页结果之前返回即时答案。
+//! 新的回答器只需实现 [`Answerer`] 并注册到 [`AnswerRegistry`]
+
+pub mod calculator;
+pub mod convert;
+pub mod dictionary;
+pub mod hash;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::net::client::HttpClient;
+use crate::search::standardization::CurrencyConverter;
+
+pub use calculator::CalculatorAnswerer;
+pub use convert::{CurrencyAnswerer, UnitAnswerer};
+pub use dictionary::DictionaryAnswerer;
+pub use hash::HashAnswerer;
+
+/// 即时答案
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstantAnswer {
+    /// 产生答案的回答器名称
+    pub answerer: String,
+    /// 答案正文
+    pub answer: String,
+    /// 附加信息（如表达式、汇率、词性）
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub details: HashMap<String, String>,
+    /// 来源链接
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl InstantAnswer {
+    /// 创建即时答案
+    pub fn new(answerer: &str, answer: impl Into<String>) -> Self {
+        Self {
+            answerer: answerer.to_string(),
+            answer: answer.into(),
+            details: HashMap::new(),
+            url: None,
+        }
+    }
+
+    /// 添加附加信息
+    pub fn with_detail(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.details.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+/// 回答器 trait
+///
+/// 不能回答的查询返回 None，由注册表继续尝试下一个回答器
+#[async_trait]
+pub trait Answerer: Send + Sync {
+    /// 回答器名称
+    fn name(&self) -> &str;
+
+    /// 尝试回答查询
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 用户查询
+    ///
+    /// # Returns
+    ///
+    /// 可以回答时返回即时答案
+    async fn answer(&self, query: &str) -> Option<InstantAnswer>;
+}
+
+/// 回答器注册表
+///
+/// 按注册顺序依次尝试，返回第一个答案
+#[derive(Default, Clone)]
+pub struct AnswerRegistry {
+    answerers: Vec<Arc<dyn Answerer>>,
+}
+
+impl AnswerRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建包含内置回答器的注册表
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - 词典查询使用的 HTTP 客户端
+    /// * `converter` - 汇率表，提供时启用货币换算
+    pub fn with_defaults(client: Arc<HttpClient>, converter: Option<&CurrencyConverter>) -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(CalculatorAnswerer));
+        registry.register(Arc::new(HashAnswerer));
+        if let Some(converter) = converter {
+            registry.register(Arc::new(CurrencyAnswerer::new(converter.clone())));
+        }
+        registry.register(Arc::new(UnitAnswerer));
+        registry.register(Arc::new(DictionaryAnswerer::new(client)));
+        registry
+    }
+
+    /// 注册回答器
+    pub fn register(&mut self, answerer: Arc<dyn Answerer>) {
+        self.answerers.push(answerer);
+    }
+
+    /// 已注册的回答器名称
+    pub fn names(&self) -> Vec<&str> {
+        self.answerers.iter().map(|a| a.name()).collect()
+    }
+
+    /// 是否没有任何回答器
+    pub fn is_empty(&self) -> bool {
+        self.answerers.is_empty()
+    }
+
+    /// 获取即时答案
+    pub async fn answer(&self, query: &str) -> Option<InstantAnswer> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        for answerer in &self.answerers {
+            if let Some(answer) = answerer.answer(query).await {
+                return Some(answer);
+            }
+        }
+        None
+    }
+}
+
+impl std::fmt::Debug for AnswerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnswerRegistry").field("answerers", &self.names()).finish()
+    }
+}
+
+/// 格式化数值答案：整数不带小数点，其余最多保留 10 位小数
+pub(crate) fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        return format!("{}", value as i64);
+    }
+    let formatted = format!("{:.10}", value);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoAnswerer;
+
+    #[async_trait]
+    impl Answerer for EchoAnswerer {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        async fn answer(&self, query: &str) -> Option<InstantAnswer> {
+            query.strip_prefix("echo ").map(|text| InstantAnswer::new("echo", text))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registry_order() {
+        let mut registry = AnswerRegistry::new();
+        registry.register(Arc::new(CalculatorAnswerer));
+        registry.register(Arc::new(EchoAnswerer));
+
+        assert_eq!(registry.names(), vec!["calculator", "echo"]);
+        assert_eq!(registry.answer("2+2*3").await.unwrap().answer, "8");
+        assert_eq!(registry.answer("echo hi").await.unwrap().answerer, "echo");
+        assert!(registry.answer("rust programming").await.is_none());
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(8.0), "8");
+        assert_eq!(format_number(-2.5), "-2.5");
+        assert_eq!(format_number(1.0 / 3.0), "0.3333333333");
+    }
+}
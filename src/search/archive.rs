@@ -0,0 +1,473 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 搜索结果归档
+//!
+//! 把搜索响应连同时间戳与结果来源引擎追加写入 JSONL 文件或 SQLite 数据库；
+//! 写入在独立线程中进行，搜索流程只负责投递记录
+
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::aggregator::ENGINE_METADATA_KEY;
+use super::types::SearchResponse;
+use crate::config::{ArchiveConfig, ArchiveFormat};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// 一次搜索的归档记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    /// 归档时间
+    pub timestamp: DateTime<Utc>,
+    /// 查询文本
+    pub query: String,
+    /// 页码
+    pub page: usize,
+    /// 查询语言
+    pub language: Option<String>,
+    /// 使用的引擎
+    pub engines_used: Vec<String>,
+    /// 是否来自缓存
+    pub cached: bool,
+    /// 查询耗时（毫秒）
+    pub query_time_ms: u64,
+    /// 总结果数
+    pub total_count: usize,
+    /// 结果列表（按排序先后）
+    pub items: Vec<ArchivedItem>,
+}
+
+/// 归档的单条结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedItem {
+    /// 排名（从 1 开始）
+    pub rank: usize,
+    /// 来源引擎
+    pub engine: String,
+    /// 标题
+    pub title: String,
+    /// URL
+    pub url: String,
+    /// 摘要
+    pub content: String,
+    /// 评分
+    pub score: f64,
+    /// 发布时间
+    pub published_date: Option<DateTime<Utc>>,
+}
+
+impl ArchiveRecord {
+    /// 从搜索响应构建归档记录
+    ///
+    /// 结果的来源引擎取自聚合时写入的元数据，缺失时使用所属结果集的引擎名
+    ///
+    /// # 参数
+    ///
+    /// * `response` - 搜索响应
+    /// * `max_items` - 最多归档的结果数（None 表示全部）
+    pub fn from_response(response: &SearchResponse, max_items: Option<usize>) -> Self {
+        let items = response
+            .results
+            .iter()
+            .flat_map(|result| {
+                result.items.iter().map(move |item| {
+                    let engine = item
+                        .metadata
+                        .get(ENGINE_METADATA_KEY)
+                        .cloned()
+                        .unwrap_or_else(|| result.engine_name.clone());
+                    (engine, item)
+                })
+            })
+            .take(max_items.unwrap_or(usize::MAX))
+            .enumerate()
+            .map(|(index, (engine, item))| ArchivedItem {
+                rank: index + 1,
+                engine,
+                title: item.title.clone(),
+                url: item.url.clone(),
+                content: item.content.clone(),
+                score: item.score,
+                published_date: item.published_date,
+            })
+            .collect();
+
+        Self {
+            timestamp: Utc::now(),
+            query: response.query.query.clone(),
+            page: response.query.page,
+            language: response.query.language.clone(),
+            engines_used: response.engines_used.clone(),
+            cached: response.cached,
+            query_time_ms: response.query_time_ms,
+            total_count: response.total_count,
+            items,
+        }
+    }
+}
+
+/// 归档写入端
+trait ArchiveWriter: Send {
+    /// 写入一条记录
+    fn write(&mut self, record: &ArchiveRecord) -> Result<()>;
+
+    /// 把缓冲的数据写入磁盘
+    fn flush(&mut self) -> Result<()>;
+}
+
+/// JSONL 归档，每个响应一行
+struct JsonlWriter {
+    writer: BufWriter<std::fs::File>,
+}
+
+impl JsonlWriter {
+    fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+impl ArchiveWriter for JsonlWriter {
+    fn write(&mut self, record: &ArchiveRecord) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// SQLite 归档
+///
+/// `searches` 表每次搜索一行，`results` 表保存各条结果及其来源引擎
+#[cfg(feature = "archive-sqlite")]
+struct SqliteWriter {
+    connection: rusqlite::Connection,
+}
+
+#[cfg(feature = "archive-sqlite")]
+impl SqliteWriter {
+    fn open(path: &Path) -> Result<Self> {
+        let connection = rusqlite::Connection::open(path)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS searches (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                query TEXT NOT NULL,
+                page INTEGER NOT NULL,
+                language TEXT,
+                engines TEXT NOT NULL,
+                cached INTEGER NOT NULL,
+                query_time_ms INTEGER NOT NULL,
+                total_count INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS results (
+                search_id INTEGER NOT NULL REFERENCES searches(id),
+                rank INTEGER NOT NULL,
+                engine TEXT NOT NULL,
+                title TEXT NOT NULL,
+                url TEXT NOT NULL,
+                content TEXT NOT NULL,
+                score REAL NOT NULL,
+                published_date TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_searches_query ON searches(query);
+            CREATE INDEX IF NOT EXISTS idx_results_search ON results(search_id);",
+        )?;
+        Ok(Self { connection })
+    }
+}
+
+#[cfg(feature = "archive-sqlite")]
+impl ArchiveWriter for SqliteWriter {
+    fn write(&mut self, record: &ArchiveRecord) -> Result<()> {
+        let tx = self.connection.transaction()?;
+        tx.execute(
+            "INSERT INTO searches (timestamp, query, page, language, engines, cached, query_time_ms, total_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                record.timestamp.to_rfc3339(),
+                record.query,
+                record.page as i64,
+                record.language,
+                record.engines_used.join(","),
+                record.cached,
+                record.query_time_ms as i64,
+                record.total_count as i64,
+            ],
+        )?;
+        let search_id = tx.last_insert_rowid();
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO results (search_id, rank, engine, title, url, content, score, published_date)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for item in &record.items {
+                insert.execute(rusqlite::params![
+                    search_id,
+                    item.rank as i64,
+                    item.engine,
+                    item.title,
+                    item.url,
+                    item.content,
+                    item.score,
+                    item.published_date.map(|date| date.to_rfc3339()),
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 搜索归档
+///
+/// 记录通过通道交给后台线程写入；关闭（或释放）时等待已投递的记录写完
+pub struct SearchArchive {
+    /// 归档配置
+    config: ArchiveConfig,
+    /// 记录发送端（关闭后为 None）
+    sender: Mutex<Option<mpsc::Sender<ArchiveRecord>>>,
+    /// 写入线程
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl SearchArchive {
+    /// 打开归档
+    ///
+    /// 创建归档文件所在目录并启动写入线程
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 归档配置
+    ///
+    /// # 返回值
+    ///
+    /// 返回归档实例，文件无法打开或格式未编译支持时返回错误
+    pub fn open(config: &ArchiveConfig) -> Result<Self> {
+        let path = Path::new(&config.path);
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut writer: Box<dyn ArchiveWriter> = match config.format {
+            ArchiveFormat::Jsonl => Box::new(JsonlWriter::open(path)?),
+            #[cfg(feature = "archive-sqlite")]
+            ArchiveFormat::Sqlite => Box::new(SqliteWriter::open(path)?),
+            #[cfg(not(feature = "archive-sqlite"))]
+            ArchiveFormat::Sqlite => {
+                return Err("SQLite archive requires the `archive-sqlite` feature".into());
+            }
+        };
+
+        let (sender, receiver) = mpsc::channel::<ArchiveRecord>();
+        let worker = std::thread::Builder::new()
+            .name("seesea-archive".to_string())
+            .spawn(move || {
+                while let Ok(record) = receiver.recv() {
+                    // 一次写入所有已到达的记录后再刷新
+                    for record in std::iter::once(record).chain(receiver.try_iter()) {
+                        if let Err(e) = writer.write(&record) {
+                            tracing::warn!("写入搜索归档失败: {}", e);
+                        }
+                    }
+                    if let Err(e) = writer.flush() {
+                        tracing::warn!("刷新搜索归档失败: {}", e);
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            config: config.clone(),
+            sender: Mutex::new(Some(sender)),
+            worker: Mutex::new(Some(worker)),
+        })
+    }
+
+    /// 归档一次搜索响应
+    ///
+    /// 按配置跳过缓存响应或空响应；归档关闭后调用无效果
+    ///
+    /// # 参数
+    ///
+    /// * `response` - 搜索响应
+    pub fn record(&self, response: &SearchResponse) {
+        if response.cached && !self.config.include_cached {
+            return;
+        }
+        if response.total_count == 0 && !self.config.include_empty {
+            return;
+        }
+
+        let record = ArchiveRecord::from_response(response, self.config.max_items_per_response);
+        if let Ok(sender) = self.sender.lock()
+            && let Some(sender) = sender.as_ref()
+        {
+            let _ = sender.send(record);
+        }
+    }
+
+    /// 关闭归档，等待已投递的记录写完
+    pub fn close(&self) {
+        if let Ok(mut sender) = self.sender.lock() {
+            sender.take();
+        }
+        let worker = self.worker.lock().ok().and_then(|mut worker| worker.take());
+        if let Some(worker) = worker {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for SearchArchive {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derive::types::{SearchQuery, SearchResult, SearchResultItem};
+    use std::collections::HashMap;
+
+    fn sample_response(items: usize) -> SearchResponse {
+        let items = (0..items)
+            .map(|i| {
+                let mut metadata = HashMap::new();
+                metadata.insert(ENGINE_METADATA_KEY.to_string(), format!("engine{}", i % 2));
+                SearchResultItem {
+                    title: format!("title {}", i),
+                    url: format!("https://example.com/{}", i),
+                    content: "content".to_string(),
+                    display_url: None,
+                    site_name: None,
+                    score: 1.0,
+                    result_type: Default::default(),
+                    thumbnail: None,
+                    published_date: None,
+                    template: None,
+                    metadata,
+                }
+            })
+            .collect::<Vec<_>>();
+        let query = SearchQuery {
+            query: "rust".to_string(),
+            ..Default::default()
+        };
+
+        SearchResponse {
+            results: vec![SearchResult {
+                engine_name: "aggregated".to_string(),
+                total_results: None,
+                elapsed_ms: 0,
+                items,
+                pagination: None,
+                suggestions: Vec::new(),
+                metadata: HashMap::new(),
+            }],
+            engines_used: vec!["engine0".to_string(), "engine1".to_string()],
+            total_count: 3,
+            query_time_ms: 12,
+            query,
+            cached: false,
+            answer: None,
+            suggestions: Vec::new(),
+            per_engine: Vec::new(),
+            partial: false,
+            related_queries: Vec::new(),
+            request_id: None,
+        }
+    }
+
+    #[test]
+    fn test_record_from_response_keeps_provenance() {
+        let record = ArchiveRecord::from_response(&sample_response(3), Some(2));
+        assert_eq!(record.query, "rust");
+        assert_eq!(record.items.len(), 2);
+        assert_eq!(record.items[0].rank, 1);
+        assert_eq!(record.items[0].engine, "engine0");
+        assert_eq!(record.items[1].engine, "engine1");
+    }
+
+    #[test]
+    fn test_jsonl_archive_appends_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/searches.jsonl");
+        let config = ArchiveConfig {
+            enabled: true,
+            path: path.to_string_lossy().to_string(),
+            ..ArchiveConfig::default()
+        };
+
+        let archive = SearchArchive::open(&config).unwrap();
+        archive.record(&sample_response(3));
+        let mut cached = sample_response(1);
+        cached.cached = true;
+        archive.record(&cached);
+        archive.record(&sample_response(2));
+        archive.close();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<ArchiveRecord> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].items.len(), 3);
+        assert_eq!(records[1].items.len(), 2);
+    }
+
+    #[cfg(feature = "archive-sqlite")]
+    #[test]
+    fn test_sqlite_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("searches.db");
+        let config = ArchiveConfig {
+            enabled: true,
+            format: ArchiveFormat::Sqlite,
+            path: path.to_string_lossy().to_string(),
+            ..ArchiveConfig::default()
+        };
+
+        let archive = SearchArchive::open(&config).unwrap();
+        archive.record(&sample_response(3));
+        archive.close();
+
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        let searches: i64 = connection.query_row("SELECT COUNT(*) FROM searches", [], |row| row.get(0)).unwrap();
+        let engine: String = connection
+            .query_row("SELECT engine FROM results WHERE rank = 2", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(searches, 1);
+        assert_eq!(engine, "engine1");
+    }
+}
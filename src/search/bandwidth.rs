@@ -0,0 +1,286 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 引擎下载流量统计
+//!
+//! 按引擎和自然日（UTC）累计下载的字节数并持久化到元数据缓存；
+//! 配置了每日配额的引擎在当日用量达到配额后暂停使用，次日自动恢复
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::MetadataCache;
+use crate::net::client::BandwidthRecorder;
+
+/// 流量统计在元数据缓存中的键前缀
+const BANDWIDTH_KEY_PREFIX: &str = "bandwidth:";
+
+/// 每日流量记录在缓存中的保留时间
+const HISTORY_TTL: Duration = Duration::from_secs(31 * 24 * 3600);
+
+/// 当前日期（UTC，`YYYY-MM-DD`）
+fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+/// 单个引擎的流量报告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineBandwidth {
+    /// 统计日期（UTC，`YYYY-MM-DD`）
+    pub day: String,
+    /// 当日下载的字节数
+    pub bytes_today: u64,
+    /// 每日配额（字节，None 表示不限制）
+    pub daily_quota_bytes: Option<u64>,
+    /// 当日配额是否已用尽
+    pub quota_exhausted: bool,
+}
+
+/// 引擎流量账本
+///
+/// 内存中保存各引擎当日用量，每次记录后写入元数据缓存（键为 `bandwidth:<引擎>:<日期>`）；
+/// 未配置缓存时只保存在内存中
+pub struct BandwidthLedger {
+    /// 各引擎的当日用量：(日期, 字节数)
+    usage: Mutex<HashMap<String, (String, u64)>>,
+    /// 持久化用的元数据缓存
+    cache: Option<MetadataCache>,
+    /// 默认每日配额
+    default_quota: Option<u64>,
+    /// 各引擎的每日配额
+    quotas: HashMap<String, u64>,
+}
+
+impl BandwidthLedger {
+    /// 创建流量账本
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - 持久化用的元数据缓存（None 时只保存在内存中）
+    /// * `default_quota` - 未单独配置的引擎的每日配额
+    /// * `quotas` - 各引擎的每日配额
+    pub fn new(cache: Option<MetadataCache>, default_quota: Option<u64>, quotas: HashMap<String, u64>) -> Self {
+        Self {
+            usage: Mutex::new(HashMap::new()),
+            cache,
+            default_quota,
+            quotas,
+        }
+    }
+
+    fn cache_key(engine: &str, day: &str) -> String {
+        format!("{}{}:{}", BANDWIDTH_KEY_PREFIX, engine, day)
+    }
+
+    /// 从缓存加载引擎某日的用量
+    fn load(&self, engine: &str, day: &str) -> u64 {
+        let Some(cache) = self.cache.as_ref() else {
+            return 0;
+        };
+        match cache.get_metadata(&Self::cache_key(engine, day)) {
+            Ok(Some(bytes)) => bytes
+                .try_into()
+                .map(u64::from_le_bytes)
+                .unwrap_or_else(|_| {
+                    tracing::warn!("Invalid bandwidth record for engine {} on {}", engine, day);
+                    0
+                }),
+            Ok(None) => 0,
+            Err(e) => {
+                tracing::warn!("Failed to load bandwidth for engine {}: {}", engine, e);
+                0
+            }
+        }
+    }
+
+    /// 将引擎某日的用量写入缓存
+    fn persist(&self, engine: &str, day: &str, bytes: u64) {
+        let Some(cache) = self.cache.as_ref() else {
+            return;
+        };
+        let key = Self::cache_key(engine, day);
+        if let Err(e) = cache.set_metadata(&key, bytes.to_le_bytes().to_vec(), Some(HISTORY_TTL)) {
+            tracing::warn!("Failed to persist bandwidth for engine {}: {}", engine, e);
+        }
+    }
+
+    /// 累加引擎某日的用量，返回累加后的用量
+    ///
+    /// 持锁写入缓存，保证并发记录按累加顺序持久化，较小的总量不会覆盖较大的总量
+    fn add(&self, engine: &str, day: &str, bytes: u64) -> u64 {
+        let mut usage = self.usage.lock().unwrap();
+        let entry = usage
+            .entry(engine.to_string())
+            .or_insert_with(|| (day.to_string(), self.load(engine, day)));
+        if entry.0 != day {
+            *entry = (day.to_string(), self.load(engine, day));
+        }
+        entry.1 += bytes;
+        let total = entry.1;
+        self.persist(engine, day, total);
+        total
+    }
+
+    /// 引擎某日的用量（字节）
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - 引擎名称
+    /// * `day` - 日期（UTC，`YYYY-MM-DD`），缓存保留最近 31 天
+    pub fn usage(&self, engine: &str, day: &str) -> u64 {
+        if let Some((cached_day, bytes)) = self.usage.lock().unwrap().get(engine)
+            && cached_day == day
+        {
+            return *bytes;
+        }
+        self.load(engine, day)
+    }
+
+    /// 引擎当日的用量（字节）
+    pub fn bytes_today(&self, engine: &str) -> u64 {
+        self.usage(engine, &today())
+    }
+
+    /// 引擎的每日配额
+    pub fn quota(&self, engine: &str) -> Option<u64> {
+        self.quotas.get(engine).copied().or(self.default_quota)
+    }
+
+    /// 引擎当日配额是否已用尽
+    pub fn is_exhausted(&self, engine: &str) -> bool {
+        self.quota(engine)
+            .is_some_and(|quota| self.bytes_today(engine) >= quota)
+    }
+
+    /// 生成引擎的流量报告
+    pub fn report(&self, engine: &str) -> EngineBandwidth {
+        let day = today();
+        let bytes_today = self.usage(engine, &day);
+        let daily_quota_bytes = self.quota(engine);
+        EngineBandwidth {
+            day,
+            bytes_today,
+            daily_quota_bytes,
+            quota_exhausted: daily_quota_bytes.is_some_and(|quota| bytes_today >= quota),
+        }
+    }
+}
+
+impl BandwidthRecorder for BandwidthLedger {
+    fn record(&self, engine_name: &str, bytes: u64) {
+        let total = self.add(engine_name, &today(), bytes);
+        if let Some(quota) = self.quota(engine_name)
+            && total >= quota
+            && total - bytes < quota
+        {
+            tracing::warn!(
+                "Engine {} exhausted its daily bandwidth quota ({} bytes), pausing until tomorrow",
+                engine_name,
+                quota
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::{CacheImplConfig, CacheMode};
+    use crate::cache::CacheManager;
+    use serial_test::serial;
+
+    #[test]
+    fn test_record_and_quota() {
+        let quotas = HashMap::from([("bing".to_string(), 1000)]);
+        let ledger = BandwidthLedger::new(None, Some(5000), quotas);
+
+        ledger.record("bing", 600);
+        assert_eq!(ledger.bytes_today("bing"), 600);
+        assert!(!ledger.is_exhausted("bing"));
+
+        ledger.record("bing", 400);
+        assert!(ledger.is_exhausted("bing"));
+        assert!(ledger.report("bing").quota_exhausted);
+
+        // 未单独配置的引擎使用默认配额
+        ledger.record("yandex", 1000);
+        assert_eq!(ledger.quota("yandex"), Some(5000));
+        assert!(!ledger.is_exhausted("yandex"));
+    }
+
+    #[test]
+    fn test_new_day_resets_usage() {
+        let ledger = BandwidthLedger::new(None, None, HashMap::new());
+        ledger.add("bing", "2025-01-01", 100);
+        ledger.add("bing", "2025-01-02", 30);
+        assert_eq!(ledger.usage("bing", "2025-01-02"), 30);
+        assert!(!ledger.is_exhausted("bing"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_persisted_usage_survives_new_ledger() {
+        let db_path = std::env::temp_dir().join(format!("test_bandwidth_{}", std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            default_ttl_secs: 3600,
+            max_size_bytes: 1024 * 1024,
+            enabled: true,
+            compression: false,
+            mode: CacheMode::HighThroughput,
+        };
+        let manager = CacheManager::instance(config).expect("Failed to create cache manager");
+        let engine = format!("bandwidth_test_{}", std::process::id());
+
+        let ledger = BandwidthLedger::new(Some(MetadataCache::new(manager.clone())), None, HashMap::new());
+        ledger.record(&engine, 2048);
+
+        let reopened = BandwidthLedger::new(Some(MetadataCache::new(manager)), Some(2048), HashMap::new());
+        assert_eq!(reopened.bytes_today(&engine), 2048);
+        assert!(reopened.is_exhausted(&engine));
+    }
+
+    #[test]
+    #[serial]
+    fn test_concurrent_records_persist_latest_total() {
+        let db_path = std::env::temp_dir().join(format!("test_bandwidth_concurrent_{}", std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            default_ttl_secs: 3600,
+            max_size_bytes: 1024 * 1024,
+            enabled: true,
+            compression: false,
+            mode: CacheMode::HighThroughput,
+        };
+        let manager = CacheManager::instance(config).expect("Failed to create cache manager");
+        let engine = format!("bandwidth_concurrent_{}", std::process::id());
+
+        let ledger = BandwidthLedger::new(Some(MetadataCache::new(manager.clone())), None, HashMap::new());
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..50 {
+                        ledger.record(&engine, 10);
+                    }
+                });
+            }
+        });
+
+        let reopened = BandwidthLedger::new(Some(MetadataCache::new(manager)), None, HashMap::new());
+        assert_eq!(reopened.bytes_today(&engine), 8 * 50 * 10);
+    }
+}
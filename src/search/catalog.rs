@@ -0,0 +1,53 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 引擎能力目录
+//!
+//! 以机器可读的形式描述每个可用引擎的信息与能力（包括 Python 端注册的引擎），
+//! 供前端动态构建引擎选择器
+
+use serde::{Deserialize, Serialize};
+
+use crate::derive::EngineInfo;
+
+/// 引擎来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EngineSource {
+    /// 内置的 Rust 引擎
+    Builtin,
+    /// Python 端注册的引擎
+    Python,
+    /// 从发现路径加载的 WASM 插件引擎
+    Wasm,
+}
+
+/// 引擎目录中的一项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineCatalogEntry {
+    /// 引擎标识（搜索请求的 `engines` 参数中使用的名称，`name` 为显示名称）
+    pub id: String,
+    /// 引擎信息与能力
+    #[serde(flatten)]
+    pub info: EngineInfo,
+    /// 引擎来源
+    pub source: EngineSource,
+    /// 是否属于全局模式的默认引擎
+    pub global: bool,
+    /// 是否可用（依赖满足）
+    pub available: bool,
+    /// 不可用的原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inactive_reason: Option<String>,
+}
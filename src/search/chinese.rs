@@ -0,0 +1,788 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! 中文查询规范化
+//!
+//! 提供繁简转换与拼音匹配，用于查询解析和缓存全文搜索：
+//! 繁体字统一转换为简体后再比较，纯字母关键词可按拼音匹配中文文本。
+//! 转换表内置常用字，未收录的字保持原样
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 拼音匹配时关键词至少需要覆盖的汉字数（避免 "de"、"an" 等短词匹配到单个常用字）
+pub const MIN_PINYIN_SYLLABLES: usize = 2;
+
+/// 中文匹配选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChineseMatching {
+    /// 繁简统一：比较前将繁体字转换为简体
+    pub normalize_script: bool,
+    /// 拼音回退：纯字母关键词按拼音匹配中文文本
+    pub pinyin: bool,
+}
+
+impl Default for ChineseMatching {
+    fn default() -> Self {
+        Self {
+            normalize_script: true,
+            pinyin: true,
+        }
+    }
+}
+
+/// 繁体字到简体字的映射
+static TRADITIONAL_TO_SIMPLIFIED: Lazy<HashMap<char, char>> =
+    Lazy::new(|| TRADITIONAL_SIMPLIFIED_PAIRS.iter().copied().collect());
+
+/// 简体字到拼音（不带声调，ü 写作 v）的映射
+static PINYIN: Lazy<HashMap<char, &'static str>> = Lazy::new(|| {
+    PINYIN_TABLE
+        .iter()
+        .flat_map(|(syllable, chars)| chars.chars().map(move |c| (c, *syllable)))
+        .collect()
+});
+
+/// 将文本中的繁体字转换为简体，其余字符保持不变
+pub fn to_simplified(text: &str) -> String {
+    text.chars()
+        .map(|c| TRADITIONAL_TO_SIMPLIFIED.get(&c).copied().unwrap_or(c))
+        .collect()
+}
+
+/// 查询单个汉字的拼音（繁体字按对应简体字查询，多音字取常用读音）
+pub fn pinyin_of(c: char) -> Option<&'static str> {
+    let c = TRADITIONAL_TO_SIMPLIFIED.get(&c).copied().unwrap_or(c);
+    PINYIN.get(&c).copied()
+}
+
+/// 将文本中的汉字转换为拼音
+///
+/// 音节之间以空格分隔，未收录的汉字和其他字符保持原样（转为小写），
+/// 如 `"北京 2024"` 转换为 `"bei jing 2024"`
+pub fn to_pinyin(text: &str) -> String {
+    let mut output = String::with_capacity(text.len() * 3);
+    let mut after_syllable = false;
+    for c in text.chars() {
+        if let Some(syllable) = pinyin_of(c) {
+            if !output.is_empty() && !output.ends_with(' ') {
+                output.push(' ');
+            }
+            output.push_str(syllable);
+            after_syllable = true;
+        } else {
+            if after_syllable && !c.is_whitespace() {
+                output.push(' ');
+            }
+            output.extend(c.to_lowercase());
+            after_syllable = false;
+        }
+    }
+    output
+}
+
+/// 文本中是否包含有拼音的汉字
+pub fn contains_pinyin_chars(text: &str) -> bool {
+    text.chars().any(|c| pinyin_of(c).is_some())
+}
+
+/// 将关键词规范化为拼音形式（去掉空格、撇号和连字符后只含 ASCII 字母）
+fn pinyin_keyword(keyword: &str) -> Option<String> {
+    let letters: String = keyword
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '\'' | '-'))
+        .collect();
+    (letters.len() >= MIN_PINYIN_SYLLABLES && letters.chars().all(|c| c.is_ascii_alphabetic()))
+        .then(|| letters.to_ascii_lowercase())
+}
+
+/// 将文本拆分为连续汉字的拼音音节序列（遇到无拼音的字符时断开）
+fn pinyin_runs(text: &str) -> Vec<Vec<&'static str>> {
+    let mut runs = Vec::new();
+    let mut current = Vec::new();
+    for c in text.chars() {
+        match pinyin_of(c) {
+            Some(syllable) => current.push(syllable),
+            None if !current.is_empty() => runs.push(std::mem::take(&mut current)),
+            None => {}
+        }
+    }
+    if !current.is_empty() {
+        runs.push(current);
+    }
+    runs
+}
+
+/// 拼音关键词是否与某段连续汉字的拼音完全对应（按音节边界对齐）
+fn matches_pinyin(runs: &[Vec<&'static str>], keyword: &str) -> bool {
+    runs.iter().any(|run| {
+        (0..run.len()).any(|start| {
+            let mut rest = keyword;
+            for (count, syllable) in run[start..].iter().enumerate() {
+                match rest.strip_prefix(syllable) {
+                    Some("") => return count + 1 >= MIN_PINYIN_SYLLABLES,
+                    Some(remaining) => rest = remaining,
+                    None => return false,
+                }
+            }
+            false
+        })
+    })
+}
+
+/// 规范化后的关键词
+#[derive(Debug, Clone)]
+struct Keyword {
+    /// 小写（并按需转为简体）的关键词
+    folded: String,
+    /// 可作为拼音匹配的形式
+    pinyin: Option<String>,
+}
+
+/// 关键词匹配器
+///
+/// 不区分大小写地判断文本是否包含关键词；按选项统一繁简字形，
+/// 并允许纯字母关键词（如 `beijing`、`bei jing`）按拼音匹配中文文本（如“北京”）
+#[derive(Debug, Clone)]
+pub struct KeywordMatcher {
+    keywords: Vec<Keyword>,
+    options: ChineseMatching,
+}
+
+impl KeywordMatcher {
+    /// 创建关键词匹配器
+    ///
+    /// # Arguments
+    ///
+    /// * `keywords` - 关键词列表（空白关键词被忽略）
+    /// * `options` - 中文匹配选项
+    pub fn new<I, S>(keywords: I, options: ChineseMatching) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut matcher = Self {
+            keywords: Vec::new(),
+            options,
+        };
+        for keyword in keywords {
+            let keyword = keyword.as_ref().trim();
+            if keyword.is_empty() {
+                continue;
+            }
+            let folded = matcher.fold(keyword);
+            let pinyin = if options.pinyin { pinyin_keyword(keyword) } else { None };
+            matcher.keywords.push(Keyword { folded, pinyin });
+        }
+        matcher
+    }
+
+    /// 是否没有任何关键词
+    pub fn is_empty(&self) -> bool {
+        self.keywords.is_empty()
+    }
+
+    /// 文本是否包含任意一个关键词
+    pub fn matches(&self, text: &str) -> bool {
+        self.count_matches(text) > 0
+    }
+
+    /// 统计文本包含的关键词个数
+    pub fn count_matches(&self, text: &str) -> usize {
+        if self.keywords.is_empty() || text.is_empty() {
+            return 0;
+        }
+        let folded = self.fold(text);
+        let mut runs = None;
+        self.keywords
+            .iter()
+            .filter(|keyword| {
+                if folded.contains(&keyword.folded) {
+                    return true;
+                }
+                let Some(ref pinyin) = keyword.pinyin else {
+                    return false;
+                };
+                let runs = runs.get_or_insert_with(|| pinyin_runs(&folded));
+                matches_pinyin(runs, pinyin)
+            })
+            .count()
+    }
+
+    /// 转为小写，并按选项将繁体转换为简体
+    fn fold(&self, text: &str) -> String {
+        let lower = text.to_lowercase();
+        if self.options.normalize_script {
+            to_simplified(&lower)
+        } else {
+            lower
+        }
+    }
+}
+
+/// 常用繁体字与简体字对照
+const TRADITIONAL_SIMPLIFIED_PAIRS: &[(char, char)] = &[
+    ('丟', '丢'), ('並', '并'), ('亂', '乱'), ('亞', '亚'), ('佈', '布'), ('佔', '占'), ('來', '来'), ('侖', '仑'), ('侶', '侣'), ('係', '系'),
+    ('俠', '侠'), ('倆', '俩'), ('倉', '仓'), ('個', '个'), ('們', '们'), ('倖', '幸'), ('倫', '伦'), ('偉', '伟'), ('側', '侧'), ('偵', '侦'),
+    ('偽', '伪'), ('傑', '杰'), ('傘', '伞'), ('備', '备'), ('傢', '家'), ('傭', '佣'), ('傳', '传'), ('債', '债'), ('傷', '伤'), ('傾', '倾'),
+    ('僂', '偻'), ('僅', '仅'), ('僑', '侨'), ('僕', '仆'), ('僥', '侥'), ('價', '价'), ('儀', '仪'), ('儂', '侬'), ('億', '亿'), ('儈', '侩'),
+    ('儉', '俭'), ('儘', '尽'), ('償', '偿'), ('優', '优'), ('儲', '储'), ('兇', '凶'), ('兒', '儿'), ('內', '内'), ('兩', '两'), ('兪', '俞'),
+    ('冊', '册'), ('凈', '净'), ('凍', '冻'), ('凜', '凛'), ('凱', '凯'), ('別', '别'), ('刪', '删'), ('則', '则'), ('剋', '克'), ('剎', '刹'),
+    ('剛', '刚'), ('剝', '剥'), ('創', '创'), ('剷', '铲'), ('劃', '划'), ('劇', '剧'), ('劉', '刘'), ('劍', '剑'), ('劑', '剂'), ('勁', '劲'),
+    ('動', '动'), ('務', '务'), ('勝', '胜'), ('勞', '劳'), ('勢', '势'), ('勳', '勋'), ('勵', '励'), ('勸', '劝'), ('勻', '匀'), ('匯', '汇'),
+    ('匱', '匮'), ('區', '区'), ('協', '协'), ('卻', '却'), ('厭', '厌'), ('厲', '厉'), ('參', '参'), ('叢', '丛'), ('吳', '吴'), ('員', '员'),
+    ('問', '问'), ('啓', '启'), ('啞', '哑'), ('啟', '启'), ('喚', '唤'), ('喪', '丧'), ('喫', '吃'), ('喬', '乔'), ('單', '单'), ('喲', '哟'),
+    ('嗎', '吗'), ('嗚', '呜'), ('嘆', '叹'), ('嘗', '尝'), ('嘩', '哗'), ('嘯', '啸'), ('嘰', '叽'), ('噴', '喷'), ('噸', '吨'), ('嚇', '吓'),
+    ('嚐', '尝'), ('嚨', '咙'), ('嚮', '向'), ('嚴', '严'), ('囪', '囱'), ('國', '国'), ('圍', '围'), ('園', '园'), ('圓', '圆'), ('圖', '图'),
+    ('團', '团'), ('執', '执'), ('堅', '坚'), ('堯', '尧'), ('報', '报'), ('場', '场'), ('塊', '块'), ('塗', '涂'), ('塵', '尘'), ('墊', '垫'),
+    ('墜', '坠'), ('墳', '坟'), ('墾', '垦'), ('壇', '坛'), ('壓', '压'), ('壘', '垒'), ('壞', '坏'), ('壟', '垄'), ('壩', '坝'), ('壯', '壮'),
+    ('壺', '壶'), ('壽', '寿'), ('夠', '够'), ('夢', '梦'), ('夥', '伙'), ('夾', '夹'), ('奪', '夺'), ('奮', '奋'), ('妝', '妆'), ('姍', '姗'),
+    ('姦', '奸'), ('娛', '娱'), ('婁', '娄'), ('婦', '妇'), ('婭', '娅'), ('媽', '妈'), ('嫵', '妩'), ('嬌', '娇'), ('嬰', '婴'), ('嬸', '婶'),
+    ('孫', '孙'), ('學', '学'), ('孿', '孪'), ('宮', '宫'), ('寢', '寝'), ('實', '实'), ('寧', '宁'), ('審', '审'), ('寫', '写'), ('寬', '宽'),
+    ('寵', '宠'), ('寶', '宝'), ('將', '将'), ('專', '专'), ('尋', '寻'), ('對', '对'), ('導', '导'), ('尷', '尴'), ('屆', '届'), ('屍', '尸'),
+    ('層', '层'), ('屬', '属'), ('岡', '冈'), ('峯', '峰'), ('島', '岛'), ('峽', '峡'), ('崗', '岗'), ('嶺', '岭'), ('嶽', '岳'), ('巒', '峦'),
+    ('巖', '岩'), ('帥', '帅'), ('師', '师'), ('帳', '帐'), ('帶', '带'), ('幟', '帜'), ('幣', '币'), ('幫', '帮'), ('幹', '干'), ('幾', '几'),
+    ('庫', '库'), ('廁', '厕'), ('廂', '厢'), ('廈', '厦'), ('廚', '厨'), ('廟', '庙'), ('廠', '厂'), ('廢', '废'), ('廣', '广'), ('廬', '庐'),
+    ('廳', '厅'), ('弔', '吊'), ('張', '张'), ('強', '强'), ('彈', '弹'), ('彌', '弥'), ('彎', '弯'), ('彙', '汇'), ('彥', '彦'), ('彫', '雕'),
+    ('後', '后'), ('徑', '径'), ('從', '从'), ('復', '复'), ('徵', '征'), ('徹', '彻'), ('恆', '恒'), ('恥', '耻'), ('悅', '悦'), ('悵', '怅'),
+    ('惡', '恶'), ('惱', '恼'), ('惻', '恻'), ('愛', '爱'), ('態', '态'), ('慘', '惨'), ('慚', '惭'), ('慟', '恸'), ('慣', '惯'), ('慮', '虑'),
+    ('慶', '庆'), ('憂', '忧'), ('憐', '怜'), ('憑', '凭'), ('憤', '愤'), ('憫', '悯'), ('憲', '宪'), ('憶', '忆'), ('懇', '恳'), ('應', '应'),
+    ('懣', '懑'), ('懲', '惩'), ('懶', '懒'), ('懷', '怀'), ('懸', '悬'), ('懼', '惧'), ('戀', '恋'), ('戰', '战'), ('戲', '戏'), ('戶', '户'),
+    ('拋', '抛'), ('挾', '挟'), ('捨', '舍'), ('捲', '卷'), ('掃', '扫'), ('掄', '抡'), ('掙', '挣'), ('掛', '挂'), ('揀', '拣'), ('揚', '扬'),
+    ('換', '换'), ('揮', '挥'), ('損', '损'), ('搖', '摇'), ('搗', '捣'), ('搶', '抢'), ('摑', '掴'), ('摟', '搂'), ('摯', '挚'), ('撈', '捞'),
+    ('撐', '撑'), ('撥', '拨'), ('撫', '抚'), ('撲', '扑'), ('撿', '捡'), ('擁', '拥'), ('擄', '掳'), ('擇', '择'), ('擊', '击'), ('擋', '挡'),
+    ('擔', '担'), ('據', '据'), ('擠', '挤'), ('擧', '举'), ('擬', '拟'), ('擰', '拧'), ('擱', '搁'), ('擲', '掷'), ('擴', '扩'), ('擺', '摆'),
+    ('擾', '扰'), ('攏', '拢'), ('攔', '拦'), ('攜', '携'), ('攝', '摄'), ('攤', '摊'), ('攪', '搅'), ('敗', '败'), ('敘', '叙'), ('敵', '敌'),
+    ('數', '数'), ('斂', '敛'), ('斃', '毙'), ('斬', '斩'), ('斷', '断'), ('於', '于'), ('時', '时'), ('晉', '晋'), ('晝', '昼'), ('暈', '晕'),
+    ('暉', '晖'), ('暢', '畅'), ('暫', '暂'), ('曆', '历'), ('曉', '晓'), ('曖', '暧'), ('曠', '旷'), ('曬', '晒'), ('書', '书'), ('會', '会'),
+    ('朧', '胧'), ('東', '东'), ('條', '条'), ('棄', '弃'), ('棟', '栋'), ('棧', '栈'), ('棲', '栖'), ('楊', '杨'), ('楓', '枫'), ('業', '业'),
+    ('極', '极'), ('榮', '荣'), ('構', '构'), ('槍', '枪'), ('槓', '杠'), ('樁', '桩'), ('樂', '乐'), ('樓', '楼'), ('標', '标'), ('樞', '枢'),
+    ('樣', '样'), ('樹', '树'), ('橋', '桥'), ('機', '机'), ('橫', '横'), ('檔', '档'), ('檢', '检'), ('檯', '台'), ('檸', '柠'), ('櫃', '柜'),
+    ('櫥', '橱'), ('櫻', '樱'), ('欄', '栏'), ('權', '权'), ('欽', '钦'), ('歎', '叹'), ('歐', '欧'), ('歡', '欢'), ('歲', '岁'), ('歷', '历'),
+    ('歸', '归'), ('殘', '残'), ('殯', '殡'), ('殲', '歼'), ('殺', '杀'), ('殼', '壳'), ('毀', '毁'), ('氈', '毡'), ('氣', '气'), ('氫', '氢'),
+    ('汙', '污'), ('決', '决'), ('沒', '没'), ('沖', '冲'), ('況', '况'), ('涼', '凉'), ('淒', '凄'), ('淚', '泪'), ('淨', '净'), ('淵', '渊'),
+    ('減', '减'), ('渦', '涡'), ('測', '测'), ('渾', '浑'), ('湊', '凑'), ('湧', '涌'), ('湯', '汤'), ('準', '准'), ('溝', '沟'), ('溫', '温'),
+    ('溼', '湿'), ('滄', '沧'), ('滅', '灭'), ('滬', '沪'), ('滷', '卤'), ('滿', '满'), ('漁', '渔'), ('漢', '汉'), ('漲', '涨'), ('漸', '渐'),
+    ('漿', '浆'), ('潑', '泼'), ('潔', '洁'), ('潛', '潜'), ('潤', '润'), ('潰', '溃'), ('澀', '涩'), ('澗', '涧'), ('澤', '泽'), ('濁', '浊'),
+    ('濃', '浓'), ('濕', '湿'), ('濟', '济'), ('濤', '涛'), ('濫', '滥'), ('濺', '溅'), ('濾', '滤'), ('瀉', '泻'), ('瀋', '沈'), ('瀏', '浏'),
+    ('瀕', '濒'), ('瀟', '潇'), ('灑', '洒'), ('灘', '滩'), ('灣', '湾'), ('災', '灾'), ('為', '为'), ('烏', '乌'), ('無', '无'), ('煉', '炼'),
+    ('煙', '烟'), ('煩', '烦'), ('熱', '热'), ('燈', '灯'), ('燉', '炖'), ('燒', '烧'), ('燙', '烫'), ('營', '营'), ('燦', '灿'), ('燭', '烛'),
+    ('燼', '烬'), ('爍', '烁'), ('爐', '炉'), ('爛', '烂'), ('爭', '争'), ('爺', '爷'), ('爾', '尔'), ('牀', '床'), ('牆', '墙'), ('牘', '牍'),
+    ('牠', '它'), ('牽', '牵'), ('犢', '犊'), ('犧', '牺'), ('狀', '状'), ('狹', '狭'), ('狽', '狈'), ('猙', '狰'), ('猶', '犹'), ('獄', '狱'),
+    ('獅', '狮'), ('獎', '奖'), ('獨', '独'), ('獰', '狞'), ('獲', '获'), ('獵', '猎'), ('獸', '兽'), ('現', '现'), ('瑋', '玮'), ('瑣', '琐'),
+    ('瑤', '瑶'), ('瑪', '玛'), ('璉', '琏'), ('環', '环'), ('璽', '玺'), ('瓊', '琼'), ('甕', '瓮'), ('產', '产'), ('畝', '亩'), ('畢', '毕'),
+    ('畫', '画'), ('異', '异'), ('當', '当'), ('疇', '畴'), ('疊', '叠'), ('瘋', '疯'), ('瘡', '疮'), ('療', '疗'), ('癒', '愈'), ('癡', '痴'),
+    ('癢', '痒'), ('發', '发'), ('皚', '皑'), ('皺', '皱'), ('盃', '杯'), ('盜', '盗'), ('盞', '盏'), ('盡', '尽'), ('監', '监'), ('盤', '盘'),
+    ('盧', '卢'), ('眾', '众'), ('睏', '困'), ('睜', '睁'), ('睞', '睐'), ('瞞', '瞒'), ('矚', '瞩'), ('矯', '矫'), ('碩', '硕'), ('確', '确'),
+    ('碼', '码'), ('磚', '砖'), ('礎', '础'), ('礙', '碍'), ('礦', '矿'), ('礪', '砺'), ('祕', '秘'), ('祿', '禄'), ('禍', '祸'), ('禪', '禅'),
+    ('禮', '礼'), ('禿', '秃'), ('稅', '税'), ('稈', '秆'), ('稜', '棱'), ('種', '种'), ('稱', '称'), ('穀', '谷'), ('積', '积'), ('穎', '颖'),
+    ('穩', '稳'), ('窩', '窝'), ('窪', '洼'), ('窮', '穷'), ('窯', '窑'), ('竄', '窜'), ('竅', '窍'), ('竊', '窃'), ('競', '竞'), ('筆', '笔'),
+    ('筍', '笋'), ('節', '节'), ('範', '范'), ('築', '筑'), ('簡', '简'), ('簽', '签'), ('籃', '篮'), ('籌', '筹'), ('籤', '签'), ('粵', '粤'),
+    ('糞', '粪'), ('糧', '粮'), ('糰', '团'), ('糾', '纠'), ('紀', '纪'), ('約', '约'), ('紅', '红'), ('紋', '纹'), ('納', '纳'), ('紐', '纽'),
+    ('純', '纯'), ('紗', '纱'), ('紙', '纸'), ('級', '级'), ('紛', '纷'), ('紡', '纺'), ('紥', '扎'), ('紮', '扎'), ('細', '细'), ('紳', '绅'),
+    ('紹', '绍'), ('終', '终'), ('組', '组'), ('結', '结'), ('絕', '绝'), ('絞', '绞'), ('絡', '络'), ('給', '给'), ('絨', '绒'), ('統', '统'),
+    ('絲', '丝'), ('綁', '绑'), ('經', '经'), ('綜', '综'), ('綠', '绿'), ('綢', '绸'), ('綫', '线'), ('維', '维'), ('綱', '纲'), ('網', '网'),
+    ('綴', '缀'), ('綿', '绵'), ('緊', '紧'), ('緒', '绪'), ('線', '线'), ('緝', '缉'), ('緞', '缎'), ('緣', '缘'), ('編', '编'), ('緩', '缓'),
+    ('緯', '纬'), ('練', '练'), ('緻', '致'), ('縛', '缚'), ('縣', '县'), ('縫', '缝'), ('縮', '缩'), ('縱', '纵'), ('縷', '缕'), ('總', '总'),
+    ('績', '绩'), ('織', '织'), ('繞', '绕'), ('繡', '绣'), ('繩', '绳'), ('繪', '绘'), ('繫', '系'), ('繳', '缴'), ('繼', '继'), ('續', '续'),
+    ('纏', '缠'), ('纖', '纤'), ('罈', '坛'), ('罰', '罚'), ('罵', '骂'), ('罷', '罢'), ('羅', '罗'), ('羣', '群'), ('羨', '羡'), ('義', '义'),
+    ('習', '习'), ('翹', '翘'), ('聖', '圣'), ('聞', '闻'), ('聯', '联'), ('聰', '聪'), ('聲', '声'), ('聳', '耸'), ('聶', '聂'), ('職', '职'),
+    ('聽', '听'), ('聾', '聋'), ('肅', '肃'), ('脅', '胁'), ('脈', '脉'), ('脛', '胫'), ('脫', '脱'), ('脹', '胀'), ('腎', '肾'), ('腦', '脑'),
+    ('腫', '肿'), ('腳', '脚'), ('腸', '肠'), ('膚', '肤'), ('膠', '胶'), ('膩', '腻'), ('膽', '胆'), ('臉', '脸'), ('臍', '脐'), ('臟', '脏'),
+    ('臥', '卧'), ('臨', '临'), ('臺', '台'), ('與', '与'), ('興', '兴'), ('舉', '举'), ('舊', '旧'), ('艙', '舱'), ('艦', '舰'), ('艱', '艰'),
+    ('艷', '艳'), ('莊', '庄'), ('莖', '茎'), ('華', '华'), ('菸', '烟'), ('萬', '万'), ('葉', '叶'), ('蒼', '苍'), ('蓆', '席'), ('蓋', '盖'),
+    ('蓮', '莲'), ('蔣', '蒋'), ('蕭', '萧'), ('薑', '姜'), ('薦', '荐'), ('薩', '萨'), ('藍', '蓝'), ('藝', '艺'), ('藥', '药'), ('蘆', '芦'),
+    ('蘇', '苏'), ('蘊', '蕴'), ('蘋', '苹'), ('蘭', '兰'), ('蘿', '萝'), ('處', '处'), ('虛', '虚'), ('虜', '虏'), ('號', '号'), ('虧', '亏'),
+    ('蛻', '蜕'), ('蝕', '蚀'), ('蝦', '虾'), ('蝸', '蜗'), ('螞', '蚂'), ('螢', '萤'), ('蟲', '虫'), ('蠅', '蝇'), ('蠟', '蜡'), ('蠶', '蚕'),
+    ('蠻', '蛮'), ('衆', '众'), ('衊', '蔑'), ('術', '术'), ('衛', '卫'), ('衝', '冲'), ('袞', '衮'), ('裏', '里'), ('補', '补'), ('裝', '装'),
+    ('裡', '里'), ('製', '制'), ('複', '复'), ('褲', '裤'), ('襖', '袄'), ('襪', '袜'), ('襯', '衬'), ('襲', '袭'), ('見', '见'), ('規', '规'),
+    ('覓', '觅'), ('視', '视'), ('親', '亲'), ('覺', '觉'), ('覽', '览'), ('觀', '观'), ('觸', '触'), ('訂', '订'), ('計', '计'), ('訊', '讯'),
+    ('討', '讨'), ('訓', '训'), ('託', '托'), ('記', '记'), ('訛', '讹'), ('訝', '讶'), ('訪', '访'), ('設', '设'), ('許', '许'), ('訴', '诉'),
+    ('詐', '诈'), ('評', '评'), ('詛', '诅'), ('詞', '词'), ('詠', '咏'), ('詢', '询'), ('試', '试'), ('詩', '诗'), ('話', '话'), ('該', '该'),
+    ('詳', '详'), ('誇', '夸'), ('誌', '志'), ('認', '认'), ('誕', '诞'), ('誘', '诱'), ('語', '语'), ('誠', '诚'), ('誤', '误'), ('誦', '诵'),
+    ('說', '说'), ('誰', '谁'), ('課', '课'), ('誼', '谊'), ('調', '调'), ('談', '谈'), ('請', '请'), ('諒', '谅'), ('論', '论'), ('諧', '谐'),
+    ('諮', '咨'), ('諷', '讽'), ('諸', '诸'), ('諾', '诺'), ('謀', '谋'), ('謁', '谒'), ('謂', '谓'), ('謊', '谎'), ('謎', '谜'), ('謙', '谦'),
+    ('講', '讲'), ('謝', '谢'), ('謠', '谣'), ('謬', '谬'), ('謹', '谨'), ('證', '证'), ('譏', '讥'), ('識', '识'), ('譚', '谭'), ('譜', '谱'),
+    ('譯', '译'), ('議', '议'), ('譴', '谴'), ('護', '护'), ('讀', '读'), ('變', '变'), ('讌', '宴'), ('讓', '让'), ('讚', '赞'), ('豈', '岂'),
+    ('豎', '竖'), ('豐', '丰'), ('豔', '艳'), ('豬', '猪'), ('貓', '猫'), ('貝', '贝'), ('貞', '贞'), ('負', '负'), ('財', '财'), ('貢', '贡'),
+    ('貧', '贫'), ('貨', '货'), ('販', '贩'), ('貪', '贪'), ('貫', '贯'), ('責', '责'), ('貴', '贵'), ('貶', '贬'), ('買', '买'), ('貸', '贷'),
+    ('費', '费'), ('貼', '贴'), ('貿', '贸'), ('賀', '贺'), ('賃', '赁'), ('賄', '贿'), ('資', '资'), ('賈', '贾'), ('賊', '贼'), ('賑', '赈'),
+    ('賓', '宾'), ('賜', '赐'), ('賞', '赏'), ('賠', '赔'), ('賢', '贤'), ('賣', '卖'), ('賤', '贱'), ('賦', '赋'), ('質', '质'), ('賬', '账'),
+    ('賭', '赌'), ('賴', '赖'), ('賺', '赚'), ('購', '购'), ('賽', '赛'), ('贈', '赠'), ('贊', '赞'), ('贍', '赡'), ('贏', '赢'), ('贓', '赃'),
+    ('贖', '赎'), ('趕', '赶'), ('趙', '赵'), ('趨', '趋'), ('跡', '迹'), ('踐', '践'), ('蹟', '迹'), ('蹤', '踪'), ('蹧', '糟'), ('躊', '踌'),
+    ('躍', '跃'), ('軀', '躯'), ('車', '车'), ('軋', '轧'), ('軌', '轨'), ('軍', '军'), ('軒', '轩'), ('軟', '软'), ('軸', '轴'), ('較', '较'),
+    ('載', '载'), ('輔', '辅'), ('輕', '轻'), ('輛', '辆'), ('輝', '辉'), ('輩', '辈'), ('輪', '轮'), ('輯', '辑'), ('輸', '输'), ('輿', '舆'),
+    ('轄', '辖'), ('轅', '辕'), ('轉', '转'), ('轍', '辙'), ('轎', '轿'), ('轟', '轰'), ('辦', '办'), ('辭', '辞'), ('辮', '辫'), ('辯', '辩'),
+    ('農', '农'), ('迴', '回'), ('逕', '径'), ('這', '这'), ('連', '连'), ('週', '周'), ('進', '进'), ('遊', '游'), ('運', '运'), ('過', '过'),
+    ('達', '达'), ('違', '违'), ('遙', '遥'), ('遜', '逊'), ('遞', '递'), ('遠', '远'), ('適', '适'), ('遯', '遁'), ('遲', '迟'), ('遷', '迁'),
+    ('選', '选'), ('遺', '遗'), ('遼', '辽'), ('邁', '迈'), ('還', '还'), ('邇', '迩'), ('邊', '边'), ('邏', '逻'), ('郵', '邮'), ('鄉', '乡'),
+    ('鄧', '邓'), ('鄭', '郑'), ('鄰', '邻'), ('醃', '腌'), ('醜', '丑'), ('醞', '酝'), ('醫', '医'), ('醬', '酱'), ('釀', '酿'), ('釁', '衅'),
+    ('釋', '释'), ('釘', '钉'), ('針', '针'), ('釣', '钓'), ('鈍', '钝'), ('鈔', '钞'), ('鈴', '铃'), ('鉅', '巨'), ('鉗', '钳'), ('鉛', '铅'),
+    ('鉤', '钩'), ('銀', '银'), ('銅', '铜'), ('銘', '铭'), ('銜', '衔'), ('銷', '销'), ('鋁', '铝'), ('鋒', '锋'), ('鋤', '锄'), ('鋪', '铺'),
+    ('鋸', '锯'), ('鋼', '钢'), ('錄', '录'), ('錐', '锥'), ('錘', '锤'), ('錢', '钱'), ('錦', '锦'), ('錯', '错'), ('錶', '表'), ('鍋', '锅'),
+    ('鍍', '镀'), ('鍛', '锻'), ('鍬', '锹'), ('鍵', '键'), ('鍾', '钟'), ('鎊', '镑'), ('鎔', '熔'), ('鎖', '锁'), ('鎮', '镇'), ('鏈', '链'),
+    ('鏟', '铲'), ('鏡', '镜'), ('鐘', '钟'), ('鐮', '镰'), ('鐲', '镯'), ('鐵', '铁'), ('鑄', '铸'), ('鑑', '鉴'), ('鑒', '鉴'), ('鑛', '矿'),
+    ('鑰', '钥'), ('鑽', '钻'), ('鑿', '凿'), ('長', '长'), ('門', '门'), ('閃', '闪'), ('閉', '闭'), ('開', '开'), ('閒', '闲'), ('間', '间'),
+    ('閘', '闸'), ('閣', '阁'), ('閨', '闺'), ('閩', '闽'), ('閱', '阅'), ('閻', '阎'), ('闆', '板'), ('闊', '阔'), ('闌', '阑'), ('闕', '阙'),
+    ('闖', '闯'), ('關', '关'), ('闡', '阐'), ('陝', '陕'), ('陣', '阵'), ('陰', '阴'), ('陳', '陈'), ('陸', '陆'), ('陽', '阳'), ('隄', '堤'),
+    ('隊', '队'), ('階', '阶'), ('隕', '陨'), ('際', '际'), ('隣', '邻'), ('隨', '随'), ('險', '险'), ('隱', '隐'), ('隴', '陇'), ('隸', '隶'),
+    ('隻', '只'), ('雖', '虽'), ('雙', '双'), ('雛', '雏'), ('雜', '杂'), ('雞', '鸡'), ('離', '离'), ('難', '难'), ('雲', '云'), ('電', '电'),
+    ('霧', '雾'), ('霽', '霁'), ('靂', '雳'), ('靈', '灵'), ('靚', '靓'), ('靜', '静'), ('鞏', '巩'), ('鞦', '秋'), ('韁', '缰'), ('韆', '千'),
+    ('韌', '韧'), ('韓', '韩'), ('韻', '韵'), ('響', '响'), ('頁', '页'), ('頂', '顶'), ('項', '项'), ('順', '顺'), ('須', '须'), ('頌', '颂'),
+    ('預', '预'), ('頑', '顽'), ('頒', '颁'), ('領', '领'), ('頭', '头'), ('頰', '颊'), ('頸', '颈'), ('頹', '颓'), ('頻', '频'), ('顆', '颗'),
+    ('題', '题'), ('額', '额'), ('顏', '颜'), ('願', '愿'), ('顛', '颠'), ('類', '类'), ('顧', '顾'), ('顫', '颤'), ('顯', '显'), ('顱', '颅'),
+    ('風', '风'), ('颱', '台'), ('颳', '刮'), ('颶', '飓'), ('飄', '飘'), ('飛', '飞'), ('飢', '饥'), ('飯', '饭'), ('飲', '饮'), ('飼', '饲'),
+    ('餃', '饺'), ('餅', '饼'), ('養', '养'), ('餓', '饿'), ('餘', '余'), ('館', '馆'), ('餵', '喂'), ('饅', '馒'), ('饑', '饥'), ('饒', '饶'),
+    ('饞', '馋'), ('馬', '马'), ('馭', '驭'), ('馮', '冯'), ('馳', '驰'), ('駁', '驳'), ('駐', '驻'), ('駕', '驾'), ('駛', '驶'), ('駭', '骇'),
+    ('駱', '骆'), ('騎', '骑'), ('騙', '骗'), ('騰', '腾'), ('騷', '骚'), ('驅', '驱'), ('驍', '骁'), ('驕', '骄'), ('驗', '验'), ('驚', '惊'),
+    ('驛', '驿'), ('驟', '骤'), ('驢', '驴'), ('骯', '肮'), ('體', '体'), ('髮', '发'), ('鬆', '松'), ('鬍', '胡'), ('鬚', '须'), ('鬢', '鬓'),
+    ('鬥', '斗'), ('鬧', '闹'), ('鬨', '哄'), ('鬱', '郁'), ('魘', '魇'), ('魚', '鱼'), ('魯', '鲁'), ('鮮', '鲜'), ('鯊', '鲨'), ('鯨', '鲸'),
+    ('鰻', '鳗'), ('鱗', '鳞'), ('鳥', '鸟'), ('鳩', '鸠'), ('鳳', '凤'), ('鳴', '鸣'), ('鴉', '鸦'), ('鴨', '鸭'), ('鴻', '鸿'), ('鴿', '鸽'),
+    ('鵑', '鹃'), ('鵝', '鹅'), ('鵬', '鹏'), ('鵲', '鹊'), ('鶯', '莺'), ('鶴', '鹤'), ('鷗', '鸥'), ('鷹', '鹰'), ('鸚', '鹦'), ('鹹', '咸'),
+    ('鹼', '碱'), ('鹽', '盐'), ('麗', '丽'), ('麥', '麦'), ('麪', '面'), ('麵', '面'), ('麼', '么'), ('麽', '么'), ('黃', '黄'), ('點', '点'),
+    ('黨', '党'), ('黴', '霉'), ('黷', '黩'), ('齊', '齐'), ('齋', '斋'), ('齒', '齿'), ('齡', '龄'), ('齣', '出'), ('龍', '龙'), ('龐', '庞'),
+    ('龕', '龛'), ('龜', '龟'),
+];
+
+/// 常用汉字拼音表（音节, 汉字）
+const PINYIN_TABLE: &[(&str, &str)] = &[
+    ("a", "阿呵啊"),
+    ("ai", "哀埃艾爱哎唉矮碍"),
+    ("an", "安岸按案暗俺"),
+    ("ang", "昂"),
+    ("ao", "傲奥澳熬"),
+    ("ba", "八拔把爸巴吧罢坝"),
+    ("bai", "白百摆败拜柏"),
+    ("ban", "班般搬板版办半伴扮"),
+    ("bang", "帮榜棒绑傍"),
+    ("bao", "包宝保报抱暴爆饱胞"),
+    ("bei", "杯悲北贝备背倍被辈碑"),
+    ("ben", "奔本笨"),
+    ("beng", "崩"),
+    ("bi", "逼鼻比彼笔必毕闭壁避臂币"),
+    ("bian", "边编变便遍辩鞭扁"),
+    ("biao", "标表"),
+    ("bie", "别"),
+    ("bin", "宾"),
+    ("bing", "冰兵并病饼丙"),
+    ("bo", "拨波玻播伯勃脖博薄泊膊"),
+    ("bu", "补捕不布步怖部"),
+    ("ca", "擦"),
+    ("cai", "猜才材财裁采彩菜"),
+    ("can", "参餐残惨灿"),
+    ("cang", "苍藏仓舱"),
+    ("cao", "操草槽"),
+    ("ce", "侧测策册厕"),
+    ("ceng", "层曾"),
+    ("cha", "插查茶察差叉"),
+    ("chai", "拆柴"),
+    ("chan", "缠产颤馋"),
+    ("chang", "昌尝常厂场唱长肠畅倡"),
+    ("chao", "超朝潮吵抄"),
+    ("che", "车扯彻撤"),
+    ("chen", "尘臣沉陈晨衬趁"),
+    ("cheng", "称成承诚城乘程撑惩"),
+    ("chi", "吃池迟持尺赤齿翅"),
+    ("chong", "充冲虫宠崇"),
+    ("chou", "抽仇丑臭愁"),
+    ("chu", "出初除础处触楚厨储"),
+    ("chuan", "川穿传船串"),
+    ("chuang", "窗床创闯"),
+    ("chui", "吹垂"),
+    ("chun", "春纯唇"),
+    ("ci", "词辞磁此次刺瓷"),
+    ("cong", "匆聪从丛"),
+    ("cu", "粗促醋"),
+    ("cui", "脆催"),
+    ("cun", "村存寸"),
+    ("cuo", "措错"),
+    ("da", "搭达答打大"),
+    ("dai", "呆代带待袋戴贷"),
+    ("dan", "丹单担胆旦但弹淡蛋"),
+    ("dang", "当党荡档挡"),
+    ("dao", "刀导岛到倒道盗"),
+    ("de", "德的得"),
+    ("deng", "灯登等邓"),
+    ("di", "地低滴迪敌底抵弟帝递第蒂"),
+    ("dian", "典点电店殿"),
+    ("diao", "调掉雕"),
+    ("die", "爹跌"),
+    ("ding", "丁盯顶定订"),
+    ("diu", "丢"),
+    ("dong", "东冬懂动洞"),
+    ("dou", "都抖斗豆"),
+    ("du", "督毒独读杜肚度渡堵"),
+    ("duan", "端短段断"),
+    ("dui", "堆队对"),
+    ("dun", "盾顿吨蹲"),
+    ("duo", "多夺躲朵"),
+    ("e", "俄额恶饿鹅"),
+    ("en", "恩"),
+    ("er", "儿而尔耳二"),
+    ("fa", "发乏法罚"),
+    ("fan", "番翻凡烦繁反返犯泛饭范"),
+    ("fang", "方芳防房仿访放"),
+    ("fei", "飞非啡菲废费肥"),
+    ("fen", "分纷粉份奋愤坟"),
+    ("feng", "丰风封疯峰凤奉缝"),
+    ("fo", "佛"),
+    ("fou", "否"),
+    ("fu", "伏扶服浮符幅福抚府腐父付妇负附复副富夫傅肤"),
+    ("gai", "该改盖概"),
+    ("gan", "甘赶敢感干杆肝"),
+    ("gang", "刚钢港岗"),
+    ("gao", "高搞稿告"),
+    ("ge", "戈哥歌革格隔个各割"),
+    ("gei", "给"),
+    ("gen", "根跟"),
+    ("geng", "更耕"),
+    ("gong", "工公功攻供宫共"),
+    ("gou", "狗构够沟"),
+    ("gu", "估姑孤古谷股骨鼓固故顾"),
+    ("gua", "瓜挂刮"),
+    ("guai", "怪乖"),
+    ("guan", "关观官馆管惯"),
+    ("guang", "光广"),
+    ("gui", "归规鬼贵跪柜"),
+    ("gun", "滚"),
+    ("guo", "国果过锅"),
+    ("ha", "哈"),
+    ("hai", "还孩海害"),
+    ("han", "含函寒韩喊汉汗"),
+    ("hang", "航"),
+    ("hao", "毫豪好号"),
+    ("he", "喝合何和河核荷赫"),
+    ("hei", "黑"),
+    ("hen", "痕很狠恨"),
+    ("heng", "恒横衡"),
+    ("hong", "轰红洪"),
+    ("hou", "后厚候猴"),
+    ("hu", "呼忽胡湖虎互户护乎糊"),
+    ("hua", "花华滑化划画话"),
+    ("huai", "怀坏"),
+    ("huan", "环缓幻唤换欢"),
+    ("huang", "荒慌皇黄"),
+    ("hui", "灰恢挥辉回悔毁汇会慧"),
+    ("hun", "昏婚浑魂混"),
+    ("huo", "活火伙或货获惑"),
+    ("ji", "击机鸡迹积基激及吉级即极急集几己挤计记纪技际季既济继寂寄辑"),
+    ("jia", "加夹家甲假价驾架嫁"),
+    ("jian", "尖坚间肩监减检简见件建剑健舰渐键"),
+    ("jiang", "江将讲奖蒋降"),
+    ("jiao", "交焦角脚叫较教"),
+    ("jie", "阶皆接揭街节杰洁结截解介戒届界借姐"),
+    ("jin", "今金津仅尽紧劲近进禁"),
+    ("jing", "京经惊精井景警净径竞竟敬境静镜睛"),
+    ("jiu", "究九久酒旧救就舅"),
+    ("ju", "居局举巨句拒具剧惧据距聚"),
+    ("juan", "卷"),
+    ("jue", "决绝觉"),
+    ("jun", "军君均俊"),
+    ("ka", "咖卡"),
+    ("kai", "开"),
+    ("kan", "刊看"),
+    ("kang", "康抗"),
+    ("kao", "考靠"),
+    ("ke", "科颗可渴克刻客课"),
+    ("ken", "肯"),
+    ("kong", "空孔恐控"),
+    ("kou", "口"),
+    ("ku", "哭苦库裤"),
+    ("kuai", "块快"),
+    ("kuan", "宽款"),
+    ("kuang", "狂况"),
+    ("kun", "困"),
+    ("kuo", "扩括"),
+    ("la", "拉啦"),
+    ("lai", "来莱赖"),
+    ("lan", "兰蓝懒烂"),
+    ("lang", "郎狼朗浪"),
+    ("lao", "劳牢老"),
+    ("le", "乐了勒"),
+    ("lei", "雷泪类累"),
+    ("leng", "冷"),
+    ("li", "离璃黎礼里理力历厉立丽利例莉粒李"),
+    ("lia", "俩"),
+    ("lian", "连怜莲联脸练恋"),
+    ("liang", "良凉梁粮两亮辆量"),
+    ("liao", "聊料"),
+    ("lie", "列烈裂"),
+    ("lin", "邻林临"),
+    ("ling", "灵凌零龄领令另"),
+    ("liu", "溜刘流留柳六"),
+    ("long", "龙隆"),
+    ("lou", "楼"),
+    ("lu", "卢鲁陆录路露"),
+    ("lv", "旅律虑率绿"),
+    ("lve", "略"),
+    ("luan", "乱"),
+    ("lun", "伦轮论"),
+    ("luo", "罗洛络落"),
+    ("ma", "妈麻马玛码骂吗嘛"),
+    ("mai", "埋买麦卖"),
+    ("man", "满曼慢漫"),
+    ("mang", "忙茫"),
+    ("mao", "猫毛矛冒贸帽貌"),
+    ("me", "么"),
+    ("mei", "没眉梅媒每美妹"),
+    ("men", "门闷们"),
+    ("meng", "盟蒙猛梦"),
+    ("mi", "迷米秘密"),
+    ("mian", "眠免面"),
+    ("miao", "描妙"),
+    ("mie", "灭"),
+    ("min", "民敏"),
+    ("ming", "名明鸣命"),
+    ("mo", "摸模摩魔末陌莫漠墨默"),
+    ("mou", "谋某"),
+    ("mu", "母姆木目幕慕"),
+    ("na", "拿哪那纳娜"),
+    ("nai", "乃奶奈耐"),
+    ("nan", "男南难"),
+    ("nao", "恼脑闹"),
+    ("ne", "呢"),
+    ("nei", "内"),
+    ("neng", "能"),
+    ("ni", "尼泥你拟"),
+    ("nian", "年念"),
+    ("niang", "娘"),
+    ("niao", "鸟"),
+    ("nin", "您"),
+    ("ning", "宁凝"),
+    ("niu", "牛扭"),
+    ("nong", "农浓弄"),
+    ("nu", "奴努怒"),
+    ("nv", "女"),
+    ("nuan", "暖"),
+    ("nuo", "诺"),
+    ("ou", "欧偶"),
+    ("pa", "爬怕"),
+    ("pai", "拍排牌派"),
+    ("pan", "盘判"),
+    ("pang", "旁胖"),
+    ("pao", "跑泡炮"),
+    ("pei", "陪培佩配"),
+    ("peng", "朋碰"),
+    ("pi", "批皮疲屁"),
+    ("pian", "偏篇片骗"),
+    ("piao", "飘票漂"),
+    ("pin", "拼品"),
+    ("ping", "平评凭瓶"),
+    ("po", "颇婆迫破"),
+    ("pu", "扑普铺"),
+    ("qi", "七妻期欺齐其奇骑旗企启起气弃汽器"),
+    ("qia", "恰"),
+    ("qian", "千牵签前钱潜浅"),
+    ("qiang", "枪强墙抢"),
+    ("qiao", "悄敲乔桥瞧巧"),
+    ("qie", "且切"),
+    ("qin", "亲侵秦琴"),
+    ("qing", "青轻倾清情请庆"),
+    ("qiong", "穷"),
+    ("qiu", "秋求球"),
+    ("qu", "区曲驱屈取去趣"),
+    ("quan", "圈全权泉拳劝"),
+    ("que", "缺却确"),
+    ("qun", "裙群"),
+    ("ran", "然燃染"),
+    ("rang", "让"),
+    ("rao", "扰绕"),
+    ("re", "热"),
+    ("ren", "人仁忍认任"),
+    ("reng", "扔仍"),
+    ("ri", "日"),
+    ("rong", "荣容融"),
+    ("rou", "柔肉"),
+    ("ru", "如入"),
+    ("ruan", "软"),
+    ("rui", "瑞"),
+    ("run", "润"),
+    ("ruo", "若弱"),
+    ("sa", "萨"),
+    ("sai", "塞赛"),
+    ("san", "三散"),
+    ("sao", "扫"),
+    ("se", "色"),
+    ("sen", "森"),
+    ("sha", "杀沙傻"),
+    ("shan", "山闪善"),
+    ("shang", "伤商赏上尚"),
+    ("shao", "烧稍少绍"),
+    ("she", "舌舍设社射涉"),
+    ("shen", "申伸身深什甚神审"),
+    ("sheng", "升生声省圣胜盛剩"),
+    ("shi", "尸失师诗施湿十石时实食史使始士氏世市示式事侍势视试饰室是适释识拾"),
+    ("shou", "收手守首受授瘦"),
+    ("shu", "书叔殊舒输熟属署鼠术束述树数"),
+    ("shuang", "双"),
+    ("shui", "谁水睡"),
+    ("shun", "顺瞬"),
+    ("shuo", "说"),
+    ("si", "丝司私思斯死四似"),
+    ("song", "松宋送"),
+    ("sou", "搜"),
+    ("su", "苏俗肃素速宿诉"),
+    ("suan", "酸算"),
+    ("sui", "虽随岁碎"),
+    ("sun", "孙损"),
+    ("suo", "缩所索锁"),
+    ("ta", "他它她塔踏"),
+    ("tai", "台抬太态泰"),
+    ("tan", "坛谈坦叹探"),
+    ("tang", "汤唐堂躺"),
+    ("tao", "涛逃桃讨套"),
+    ("te", "特"),
+    ("teng", "疼腾"),
+    ("ti", "梯提题体替"),
+    ("tian", "天田甜"),
+    ("tiao", "挑条跳"),
+    ("tie", "贴铁"),
+    ("ting", "厅听廷亭庭停挺"),
+    ("tong", "通同童统痛"),
+    ("tou", "偷头投透"),
+    ("tu", "突图徒途土涂"),
+    ("tuan", "团"),
+    ("tui", "推腿退"),
+    ("tuo", "托拖脱"),
+    ("wa", "瓦"),
+    ("wai", "外"),
+    ("wan", "弯湾完玩晚碗万"),
+    ("wang", "亡王网往忘望"),
+    ("wei", "危威微薇围违唯维伟伪尾委卫为未位味谓喂慰"),
+    ("wen", "温文闻吻稳问"),
+    ("wo", "窝我握"),
+    ("wu", "乌屋无吴五午武舞物误悟雾务伍"),
+    ("xi", "吸希析悉惜稀熙嘻习席袭洗喜戏系细西息"),
+    ("xia", "下吓夏"),
+    ("xian", "先鲜闲显险县现线限陷献仙"),
+    ("xiang", "乡相香箱详享响想向项象像"),
+    ("xiao", "消销小晓效校笑"),
+    ("xie", "些协斜鞋写谢"),
+    ("xin", "心辛欣新信"),
+    ("xing", "星刑行形型醒兴姓幸性"),
+    ("xiong", "凶兄胸雄熊"),
+    ("xiu", "休修羞秀袖"),
+    ("xu", "须虚需徐许序绪续"),
+    ("xuan", "宣旋选"),
+    ("xue", "学雪血"),
+    ("xun", "寻讯迅训"),
+    ("ya", "丫压牙雅亚呀"),
+    ("yan", "烟延严言沿研颜掩眼演厌验燕"),
+    ("yang", "央扬羊阳杨洋仰养样"),
+    ("yao", "腰摇遥咬药要耀"),
+    ("ye", "耶也野业叶页夜爷"),
+    ("yi", "一伊衣医依仪移遗疑已以椅义亿忆艺议亦异役译易益意翼宜"),
+    ("yin", "因阴音银尹引饮隐印"),
+    ("ying", "应英迎营影映硬"),
+    ("yong", "拥永勇涌用"),
+    ("you", "优忧幽悠尤由犹邮油游有又右友"),
+    ("yu", "于余鱼与予宇雨语玉育郁狱预域欲御遇愈"),
+    ("yuan", "元员园原圆援缘源远怨院愿"),
+    ("yue", "约月阅跃越"),
+    ("yun", "云允运"),
+    ("za", "杂"),
+    ("zai", "灾再在载"),
+    ("zan", "咱暂赞"),
+    ("zang", "脏"),
+    ("zao", "遭糟早造"),
+    ("ze", "则择泽责"),
+    ("zen", "怎"),
+    ("zeng", "增"),
+    ("zha", "扎炸"),
+    ("zhan", "展占战站"),
+    ("zhang", "张章掌丈帐"),
+    ("zhao", "招找召赵照"),
+    ("zhe", "折哲者这着"),
+    ("zhen", "针珍真阵振朕镇震"),
+    ("zheng", "争征睁整正证政"),
+    ("zhi", "之支枝知织执直值职止只址纸指至志制治质致智置"),
+    ("zhong", "中忠终钟种众重"),
+    ("zhou", "州周洲宙"),
+    ("zhu", "朱珠诸猪竹逐主住助注驻祝筑"),
+    ("zhua", "抓"),
+    ("zhuan", "专转"),
+    ("zhuang", "庄装壮状撞"),
+    ("zhui", "追坠"),
+    ("zhun", "准"),
+    ("zhuo", "捉桌"),
+    ("zi", "姿资紫字自子"),
+    ("zong", "宗踪总纵"),
+    ("zou", "走奏"),
+    ("zu", "足族阻组祖"),
+    ("zui", "嘴最罪醉"),
+    ("zun", "尊"),
+    ("zuo", "昨左作坐座做"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_simplified() {
+        assert_eq!(to_simplified("臺灣網絡搜尋引擎"), "台湾网络搜寻引擎");
+        assert_eq!(to_simplified("简体 Rust 123"), "简体 Rust 123");
+    }
+
+    #[test]
+    fn test_to_pinyin() {
+        assert_eq!(to_pinyin("北京"), "bei jing");
+        assert_eq!(to_pinyin("北京 2024"), "bei jing 2024");
+        assert_eq!(to_pinyin("學習Rust"), "xue xi rust");
+        assert_eq!(pinyin_of('綠'), Some("lv"));
+    }
+
+    #[test]
+    fn test_matcher_script_normalization() {
+        let matcher = KeywordMatcher::new(["機器學習"], ChineseMatching::default());
+        assert!(matcher.matches("机器学习入门教程"));
+
+        let matcher = KeywordMatcher::new(["机器学习"], ChineseMatching::default());
+        assert!(matcher.matches("機器學習入門"));
+
+        let strict = ChineseMatching {
+            normalize_script: false,
+            pinyin: false,
+        };
+        assert!(!KeywordMatcher::new(["機器學習"], strict).matches("机器学习入门教程"));
+    }
+
+    #[test]
+    fn test_matcher_pinyin_fallback() {
+        let matcher = KeywordMatcher::new(["beijing"], ChineseMatching::default());
+        assert!(matcher.matches("北京天气预报"));
+        assert!(matcher.matches("北京天氣"));
+        assert!(KeywordMatcher::new(["bei jing"], ChineseMatching::default()).matches("北京"));
+
+        // 必须按音节边界对齐
+        assert!(!KeywordMatcher::new(["eijin"], ChineseMatching::default()).matches("北京"));
+        // 单个音节不做拼音匹配
+        assert!(!KeywordMatcher::new(["de"], ChineseMatching::default()).matches("我的"));
+
+        let no_pinyin = ChineseMatching {
+            pinyin: false,
+            ..ChineseMatching::default()
+        };
+        assert!(!KeywordMatcher::new(["beijing"], no_pinyin).matches("北京"));
+    }
+
+    #[test]
+    fn test_count_matches() {
+        let matcher = KeywordMatcher::new(["rust", "xuexi", "", "python"], ChineseMatching::default());
+        assert_eq!(matcher.count_matches("Rust 学习笔记"), 2);
+        assert_eq!(matcher.count_matches(""), 0);
+        assert!(KeywordMatcher::new(["  "], ChineseMatching::default()).is_empty());
+    }
+}
@@ -0,0 +1,347 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! 结果点击统计模块
+//!
+//! 只按引擎和目标域名累计点击次数，不保存查询、完整链接或任何用户信息；
+//! 引擎的点击率（点击数 / 返回非空结果的请求数）用于调整自适应引擎权重
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use url::Url;
+
+use crate::cache::MetadataCache;
+
+/// 点击统计在元数据缓存中的键
+const CLICK_STATS_KEY: &str = "click_stats";
+
+/// 计算点击率所需的最少非空结果请求数
+pub const MIN_RESPONSES_FOR_CTR: u64 = 20;
+
+/// 点击率在引擎权重中的占比
+pub const CLICK_WEIGHT: f64 = 0.2;
+
+/// 最多单独计数的域名数（超出后新域名的点击只计入总数）
+pub const MAX_TRACKED_DOMAINS: usize = 10_000;
+
+/// 获取当前时间戳（秒）
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::from_secs(0))
+        .as_secs()
+}
+
+/// 提取用于统计的域名（小写，去掉 `www.` 前缀）
+///
+/// 仅接受 http/https 链接，其他协议或无效 URL 返回 None
+pub fn click_domain(target: &str) -> Option<String> {
+    let parsed = Url::parse(target).ok()?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return None;
+    }
+    let host = parsed.host_str()?.to_ascii_lowercase();
+    Some(host.strip_prefix("www.").map(str::to_string).unwrap_or(host))
+}
+
+/// 累计点击计数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClickCounters {
+    /// 总点击数
+    pub total_clicks: u64,
+    /// 各引擎的点击数
+    pub engines: BTreeMap<String, u64>,
+    /// 各域名的点击数
+    pub domains: BTreeMap<String, u64>,
+    /// 超出域名上限未单独计数的点击数
+    pub untracked_domain_clicks: u64,
+    /// 开始统计的时间（Unix 时间戳）
+    pub since: Option<u64>,
+}
+
+impl ClickCounters {
+    /// 记录一次点击
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - 结果来源引擎（未知时为 None）
+    /// * `domain` - 目标域名
+    /// * `max_domains` - 最多单独计数的域名数
+    pub fn record(&mut self, engine: Option<&str>, domain: &str, max_domains: usize) {
+        self.total_clicks += 1;
+        self.since.get_or_insert_with(current_timestamp);
+        if let Some(engine) = engine {
+            *self.engines.entry(engine.to_string()).or_insert(0) += 1;
+        }
+        if let Some(count) = self.domains.get_mut(domain) {
+            *count += 1;
+        } else if self.domains.len() < max_domains {
+            self.domains.insert(domain.to_string(), 1);
+        } else {
+            self.untracked_domain_clicks += 1;
+        }
+    }
+
+    /// 引擎的点击数
+    pub fn engine_clicks(&self, engine: &str) -> u64 {
+        self.engines.get(engine).copied().unwrap_or(0)
+    }
+
+    /// 点击最多的域名（按点击数降序，相同时按域名排序）
+    pub fn top_domains(&self, limit: usize) -> Vec<DomainClicks> {
+        let mut domains: Vec<DomainClicks> = self
+            .domains
+            .iter()
+            .map(|(domain, clicks)| DomainClicks {
+                domain: domain.clone(),
+                clicks: *clicks,
+            })
+            .collect();
+        domains.sort_by(|a, b| b.clicks.cmp(&a.clicks).then_with(|| a.domain.cmp(&b.domain)));
+        domains.truncate(limit);
+        domains
+    }
+}
+
+/// 单个域名的点击数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomainClicks {
+    /// 域名
+    pub domain: String,
+    /// 点击数
+    pub clicks: u64,
+}
+
+/// 点击统计报告
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClickReport {
+    /// 总点击数
+    pub total_clicks: u64,
+    /// 开始统计的时间（Unix 时间戳）
+    pub since: Option<u64>,
+    /// 各引擎的点击数
+    pub engines: BTreeMap<String, u64>,
+    /// 点击最多的域名
+    pub top_domains: Vec<DomainClicks>,
+    /// 已统计的域名数
+    pub tracked_domains: usize,
+}
+
+/// 计算点击率
+///
+/// 非空结果请求数不足 [`MIN_RESPONSES_FOR_CTR`] 时返回 None
+pub fn click_through_rate(clicks: u64, responses: u64) -> Option<f64> {
+    (responses >= MIN_RESPONSES_FOR_CTR).then(|| clicks as f64 / responses as f64)
+}
+
+/// 按点击率调整引擎权重
+///
+/// 点击率先除以本次参与引擎中的最高点击率归一化，再按 [`CLICK_WEIGHT`] 与原权重混合；
+/// 没有点击率的引擎保持原权重
+///
+/// # Arguments
+///
+/// * `weights` - 引擎权重（历史质量评分）
+/// * `rates` - 各引擎的点击率
+pub fn blend_click_rates(weights: &mut HashMap<String, f64>, rates: &HashMap<String, f64>) {
+    let max_rate = rates.values().copied().fold(0.0_f64, f64::max);
+    if max_rate <= 0.0 {
+        return;
+    }
+    for (engine, weight) in weights.iter_mut() {
+        if let Some(rate) = rates.get(engine) {
+            *weight = *weight * (1.0 - CLICK_WEIGHT) + (rate / max_rate) * CLICK_WEIGHT;
+        }
+    }
+}
+
+/// 点击统计存储
+///
+/// 内存中维护计数，每次点击后写入元数据缓存；未配置缓存时只保存在内存中
+pub struct ClickStatsStore {
+    /// 内存中的计数
+    counters: RwLock<ClickCounters>,
+    /// 持久化用的元数据缓存
+    cache: Option<MetadataCache>,
+}
+
+impl ClickStatsStore {
+    /// 创建仅保存在内存中的点击统计
+    pub fn in_memory() -> Self {
+        Self {
+            counters: RwLock::new(ClickCounters::default()),
+            cache: None,
+        }
+    }
+
+    /// 创建持久化到元数据缓存的点击统计（加载已有计数）
+    pub fn with_cache(cache: MetadataCache) -> Self {
+        let counters = Self::load(&cache).unwrap_or_default();
+        Self {
+            counters: RwLock::new(counters),
+            cache: Some(cache),
+        }
+    }
+
+    /// 从缓存加载计数
+    fn load(cache: &MetadataCache) -> Option<ClickCounters> {
+        match cache.get_metadata(CLICK_STATS_KEY) {
+            Ok(Some(bytes)) => bincode::serde::decode_from_slice(&bytes, bincode::config::standard())
+                .map(|(counters, _)| counters)
+                .map_err(|e| tracing::warn!("Failed to decode click stats: {}", e))
+                .ok(),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Failed to load click stats: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 将计数写入缓存
+    fn persist(&self, counters: &ClickCounters) {
+        let Some(cache) = self.cache.as_ref() else {
+            return;
+        };
+        let bytes = match bincode::serde::encode_to_vec(counters, bincode::config::standard()) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!("Failed to encode click stats: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = cache.set_metadata(CLICK_STATS_KEY, bytes, None) {
+            tracing::warn!("Failed to persist click stats: {}", e);
+        }
+    }
+
+    /// 记录一次点击
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - 结果来源引擎（未知时为 None）
+    /// * `domain` - 目标域名
+    pub async fn record(&self, engine: Option<&str>, domain: &str) {
+        let mut counters = self.counters.write().await;
+        counters.record(engine, domain, MAX_TRACKED_DOMAINS);
+        self.persist(&counters);
+    }
+
+    /// 引擎的点击数
+    pub async fn engine_clicks(&self, engine: &str) -> u64 {
+        self.counters.read().await.engine_clicks(engine)
+    }
+
+    /// 获取当前计数的快照
+    pub async fn snapshot(&self) -> ClickCounters {
+        self.counters.read().await.clone()
+    }
+
+    /// 生成点击统计报告
+    ///
+    /// # Arguments
+    ///
+    /// * `top_domains` - 报告中包含的域名数
+    pub async fn report(&self, top_domains: usize) -> ClickReport {
+        let counters = self.counters.read().await;
+        ClickReport {
+            total_clicks: counters.total_clicks,
+            since: counters.since,
+            engines: counters.engines.clone(),
+            top_domains: counters.top_domains(top_domains),
+            tracked_domains: counters.domains.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::{CacheImplConfig, CacheMode};
+    use crate::cache::CacheManager;
+    use serial_test::serial;
+
+    #[test]
+    fn test_click_domain() {
+        assert_eq!(click_domain("https://WWW.Rust-Lang.org/learn?x=1").as_deref(), Some("rust-lang.org"));
+        assert_eq!(click_domain("http://docs.rs/tokio").as_deref(), Some("docs.rs"));
+        assert!(click_domain("javascript:alert(1)").is_none());
+        assert!(click_domain("not a url").is_none());
+    }
+
+    #[test]
+    fn test_counters_cap_domains() {
+        let mut counters = ClickCounters::default();
+        counters.record(Some("bing"), "a.com", 2);
+        counters.record(Some("bing"), "b.com", 2);
+        counters.record(None, "c.com", 2);
+        counters.record(Some("sogou"), "a.com", 2);
+
+        assert_eq!(counters.total_clicks, 4);
+        assert_eq!(counters.engine_clicks("bing"), 2);
+        assert_eq!(counters.engine_clicks("sogou"), 1);
+        assert_eq!(counters.domains.len(), 2);
+        assert_eq!(counters.untracked_domain_clicks, 1);
+        assert_eq!(counters.top_domains(1)[0], DomainClicks { domain: "a.com".to_string(), clicks: 2 });
+        assert!(counters.since.is_some());
+    }
+
+    #[test]
+    fn test_click_through_rate_requires_samples() {
+        assert_eq!(click_through_rate(5, MIN_RESPONSES_FOR_CTR - 1), None);
+        assert_eq!(click_through_rate(10, 40), Some(0.25));
+    }
+
+    #[test]
+    fn test_blend_click_rates() {
+        let mut weights = HashMap::from([
+            ("bing".to_string(), 0.8),
+            ("sogou".to_string(), 0.8),
+            ("baidu".to_string(), 0.5),
+        ]);
+        let rates = HashMap::from([("bing".to_string(), 0.4), ("sogou".to_string(), 0.1)]);
+        blend_click_rates(&mut weights, &rates);
+
+        assert!((weights["bing"] - (0.8 * 0.8 + 0.2)).abs() < 1e-9);
+        assert!((weights["sogou"] - (0.8 * 0.8 + 0.25 * 0.2)).abs() < 1e-9);
+        assert_eq!(weights["baidu"], 0.5);
+        assert!(weights["bing"] > weights["sogou"]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_persisted_clicks_survive_new_store() {
+        let db_path = std::env::temp_dir().join(format!("test_click_stats_{}", std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            default_ttl_secs: 3600,
+            max_size_bytes: 1024 * 1024,
+            enabled: true,
+            compression: false,
+            mode: CacheMode::HighThroughput,
+        };
+        let manager = CacheManager::instance(config).expect("Failed to create cache manager");
+
+        let store = ClickStatsStore::with_cache(MetadataCache::new(manager.clone()));
+        let before = store.snapshot().await.total_clicks;
+        store.record(Some("bing"), "example.com").await;
+
+        let reopened = ClickStatsStore::with_cache(MetadataCache::new(manager));
+        let report = reopened.report(10).await;
+        assert_eq!(report.total_clicks, before + 1);
+        assert!(report.top_domains.iter().any(|d| d.domain == "example.com"));
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 相同并发查询的请求合并
+//!
+//! 同一时刻的相同查询只向引擎发起一次请求，其余调用等待该请求的结果
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::Mutex;
+
+use tokio::sync::broadcast;
+
+use super::types::SearchRequest;
+
+/// 合并请求共享的结果（错误以字符串形式共享）
+type SharedResult<T> = Result<T, String>;
+
+/// 单飞请求合并器
+///
+/// 第一个调用者执行请求，相同键的后续调用者订阅其结果；
+/// 执行者被取消时等待者各自重新执行
+pub struct SingleFlight<T: Clone> {
+    /// 进行中的请求
+    inflight: Mutex<HashMap<String, broadcast::Sender<SharedResult<T>>>>,
+}
+
+impl<T: Clone> SingleFlight<T> {
+    /// 创建合并器
+    pub fn new() -> Self {
+        Self {
+            inflight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 当前进行中的请求数
+    pub fn inflight(&self) -> usize {
+        self.inflight.lock().map(|map| map.len()).unwrap_or(0)
+    }
+
+    /// 执行请求，相同键的并发调用共享同一次执行
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 请求键
+    /// * `work` - 实际执行请求的操作
+    ///
+    /// # 返回值
+    ///
+    /// 返回请求结果，以及该结果是否来自其他调用者的执行
+    pub async fn run<F, Fut>(
+        &self,
+        key: String,
+        work: F,
+    ) -> (Result<T, Box<dyn std::error::Error + Send + Sync>>, bool)
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, Box<dyn std::error::Error + Send + Sync>>>,
+    {
+        // 锁失效时不合并，直接执行
+        let role = match self.inflight.lock() {
+            Ok(mut inflight) => match inflight.get(&key) {
+                Some(sender) => Role::Follower(sender.subscribe()),
+                None => {
+                    let (sender, _) = broadcast::channel(1);
+                    inflight.insert(key.clone(), sender);
+                    Role::Leader
+                }
+            },
+            Err(_) => Role::Bypass,
+        };
+
+        match role {
+            Role::Bypass => return (work().await, false),
+            Role::Follower(mut receiver) => {
+                return match receiver.recv().await {
+                    Ok(result) => (result.map_err(Into::into), true),
+                    // 执行者被取消，自行执行
+                    Err(_) => (work().await, false),
+                };
+            }
+            Role::Leader => {}
+        }
+
+        let guard = InflightGuard { flight: self, key };
+        let result = work().await;
+        if let Some(sender) = guard.finish() {
+            let shared = match result {
+                Ok(ref value) => Ok(value.clone()),
+                Err(ref e) => Err(e.to_string()),
+            };
+            let _ = sender.send(shared);
+        }
+        (result, false)
+    }
+}
+
+impl<T: Clone> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 调用者在一次合并中的角色
+enum Role<T> {
+    /// 执行请求并广播结果
+    Leader,
+    /// 等待执行者的结果
+    Follower(broadcast::Receiver<SharedResult<T>>),
+    /// 不参与合并
+    Bypass,
+}
+
+/// 执行者退出（完成或被取消）时移除进行中的请求
+struct InflightGuard<'a, T: Clone> {
+    /// 所属合并器
+    flight: &'a SingleFlight<T>,
+    /// 请求键
+    key: String,
+}
+
+impl<T: Clone> InflightGuard<'_, T> {
+    /// 移除请求并取出发送端，用于广播结果
+    fn finish(self) -> Option<broadcast::Sender<SharedResult<T>>> {
+        let sender = self.remove();
+        std::mem::forget(self);
+        sender
+    }
+
+    fn remove(&self) -> Option<broadcast::Sender<SharedResult<T>>> {
+        self.flight.inflight.lock().ok().and_then(|mut map| map.remove(&self.key))
+    }
+}
+
+impl<T: Clone> Drop for InflightGuard<'_, T> {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+/// 生成请求合并键
+///
+/// 查询文本忽略大小写与多余空白，引擎列表与附加参数忽略顺序；
+/// 影响引擎请求的其余字段（页码、语言、地区、是否绕过缓存等）原样参与
+pub fn coalesce_key(request: &SearchRequest, engines: &[String]) -> String {
+    let query = &request.query;
+    let text = query
+        .query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut engines = engines.to_vec();
+    engines.sort();
+    engines.dedup();
+    let params: BTreeMap<_, _> = query.params.iter().collect();
+
+    format!(
+        "{}|{:?}|{:?}|{:?}|{}|{}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{}|{:?}|{}",
+        text,
+        query.engine_type,
+        query.language,
+        query.region,
+        query.page,
+        query.page_size,
+        query.safe_search,
+        query.time_range,
+        query.lat,
+        query.lon,
+        query.bbox,
+        params,
+        engines.join(","),
+        request.max_latency_ms,
+        request.force,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_duplicates_share_one_execution() {
+        let flight = Arc::new(SingleFlight::<usize>::new());
+        let executions = Arc::new(AtomicUsize::new(0));
+
+        let mut handles = Vec::new();
+        for _ in 0..5 {
+            let flight = Arc::clone(&flight);
+            let executions = Arc::clone(&executions);
+            handles.push(tokio::spawn(async move {
+                flight
+                    .run("rust".to_string(), || async {
+                        executions.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok(42)
+                    })
+                    .await
+            }));
+        }
+
+        let mut coalesced = 0;
+        for handle in handles {
+            let (result, shared) = handle.await.unwrap();
+            assert_eq!(result.unwrap(), 42);
+            coalesced += shared as usize;
+        }
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+        assert_eq!(coalesced, 4);
+        assert_eq!(flight.inflight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_shared_and_key_is_released() {
+        let flight = Arc::new(SingleFlight::<usize>::new());
+
+        let leader = {
+            let flight = Arc::clone(&flight);
+            tokio::spawn(async move {
+                flight
+                    .run("q".to_string(), || async {
+                        tokio::time::sleep(Duration::from_millis(30)).await;
+                        Err("engine down".into())
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let (follower, shared) = flight.run("q".to_string(), || async { Ok(1) }).await;
+        assert!(shared);
+        assert_eq!(follower.unwrap_err().to_string(), "engine down");
+        assert!(leader.await.unwrap().0.is_err());
+
+        // 请求结束后相同键重新执行
+        let (result, shared) = flight.run("q".to_string(), || async { Ok(7) }).await;
+        assert!(!shared);
+        assert_eq!(result.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_leader_lets_followers_retry() {
+        let flight = Arc::new(SingleFlight::<usize>::new());
+
+        let leader = {
+            let flight = Arc::clone(&flight);
+            tokio::spawn(async move {
+                flight
+                    .run("q".to_string(), std::future::pending)
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let follower = {
+            let flight = Arc::clone(&flight);
+            tokio::spawn(async move { flight.run("q".to_string(), || async { Ok(3) }).await })
+        };
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        leader.abort();
+
+        let (result, shared) = follower.await.unwrap();
+        assert!(!shared);
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_coalesce_key_normalization() {
+        let mut a = SearchRequest::default();
+        a.query.query = "Rust  Async".to_string();
+        let mut b = SearchRequest::default();
+        b.query.query = " rust async ".to_string();
+
+        let engines_a = vec!["bing".to_string(), "brave".to_string()];
+        let engines_b = vec!["brave".to_string(), "bing".to_string()];
+        assert_eq!(coalesce_key(&a, &engines_a), coalesce_key(&b, &engines_b));
+
+        b.query.page = 2;
+        assert_ne!(coalesce_key(&a, &engines_a), coalesce_key(&b, &engines_b));
+    }
+
+    #[test]
+    fn test_forced_request_not_coalesced_with_normal_request() {
+        let engines = vec!["bing".to_string()];
+        let mut normal = SearchRequest::default();
+        normal.query.query = "rust".to_string();
+        let mut forced = normal.clone();
+        forced.force = true;
+
+        // 绕过缓存的请求不能拿到普通请求（可能来自缓存）的结果
+        assert_ne!(coalesce_key(&normal, &engines), coalesce_key(&forced, &engines));
+    }
+}
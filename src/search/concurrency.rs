@@ -0,0 +1,159 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 引擎请求并发控制
+//!
+//! 用信号量限制全局进行中的引擎请求数与单个引擎的并发数，
+//! 超出上限的请求排队等待而不是立即发出
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// 引擎请求并发限制器
+///
+/// 在同一个搜索接口的所有请求之间共享
+#[derive(Debug)]
+pub struct ConcurrencyLimiter {
+    /// 全局信号量
+    global: Arc<Semaphore>,
+    /// 全局并发上限
+    global_limit: usize,
+    /// 各引擎的信号量（未配置的引擎只受全局上限约束）
+    per_engine: HashMap<String, Arc<Semaphore>>,
+}
+
+/// 一次引擎请求持有的并发许可，释放时归还
+#[derive(Debug)]
+pub struct EnginePermit {
+    /// 全局许可
+    _global: OwnedSemaphorePermit,
+    /// 引擎许可
+    _engine: Option<OwnedSemaphorePermit>,
+}
+
+impl ConcurrencyLimiter {
+    /// 创建并发限制器
+    ///
+    /// # 参数
+    ///
+    /// * `global_limit` - 全局进行中的引擎请求上限（0 视为 1）
+    /// * `engine_limits` - 各引擎的并发上限（0 表示不限制该引擎）
+    pub fn new(global_limit: usize, engine_limits: &HashMap<String, usize>) -> Self {
+        let global_limit = global_limit.max(1);
+        let per_engine = engine_limits
+            .iter()
+            .filter(|(_, limit)| **limit > 0)
+            .map(|(name, limit)| (name.clone(), Arc::new(Semaphore::new(*limit))))
+            .collect();
+
+        Self {
+            global: Arc::new(Semaphore::new(global_limit)),
+            global_limit,
+            per_engine,
+        }
+    }
+
+    /// 全局并发上限
+    pub fn global_limit(&self) -> usize {
+        self.global_limit
+    }
+
+    /// 当前进行中的引擎请求数
+    pub fn in_flight(&self) -> usize {
+        self.global_limit - self.global.available_permits()
+    }
+
+    /// 等待发起引擎请求的许可
+    ///
+    /// 先获取引擎许可再获取全局许可，避免排队等待某个引擎时占用全局名额
+    ///
+    /// # 参数
+    ///
+    /// * `engine_name` - 引擎名称
+    pub async fn acquire(&self, engine_name: &str) -> EnginePermit {
+        let engine = match self.per_engine.get(engine_name) {
+            Some(semaphore) => Arc::clone(semaphore).acquire_owned().await.ok(),
+            None => None,
+        };
+        let global = Arc::clone(&self.global)
+            .acquire_owned()
+            .await
+            .expect("concurrency semaphore is never closed");
+
+        EnginePermit {
+            _global: global,
+            _engine: engine,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// 对每个引擎名发起一个请求，返回观察到的最大并发数
+    async fn max_observed(limiter: Arc<ConcurrencyLimiter>, engines: &[&str]) -> usize {
+        let current = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = engines
+            .iter()
+            .map(|engine| {
+                let limiter = Arc::clone(&limiter);
+                let current = Arc::clone(&current);
+                let peak = Arc::clone(&peak);
+                let engine = engine.to_string();
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire(&engine).await;
+                    let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    current.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        peak.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_global_limit_caps_in_flight_requests() {
+        let limiter = Arc::new(ConcurrencyLimiter::new(2, &HashMap::new()));
+        let peak = max_observed(Arc::clone(&limiter), &["a", "b", "c", "d", "e"]).await;
+        assert_eq!(peak, 2);
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_per_engine_limit() {
+        let limits = HashMap::from([("bing".to_string(), 1)]);
+        let limiter = Arc::new(ConcurrencyLimiter::new(10, &limits));
+        let peak = max_observed(limiter, &["bing", "bing", "bing"]).await;
+        assert_eq!(peak, 1);
+    }
+
+    #[test]
+    fn test_zero_limits() {
+        let limits = HashMap::from([("bing".to_string(), 0)]);
+        let limiter = ConcurrencyLimiter::new(0, &limits);
+        assert_eq!(limiter.global_limit(), 1);
+        assert!(limiter.per_engine.is_empty());
+    }
+}
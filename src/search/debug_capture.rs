@@ -0,0 +1,295 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! 引擎调试捕获
+//!
+//! 请求设置 `debug_capture` 时记录每个引擎的原始响应（截断）、解析出的结果数与解析警告，
+//! 写入聚合结果的元数据，并可选地保存到磁盘，无需重新编译即可排查选择器失效
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::derive::SearchResult;
+use crate::net::client::capture::ResponseCapture;
+
+/// 聚合结果元数据中保存调试捕获的键（值为引擎名到 [`EngineDebugCapture`] 的 JSON 对象）
+pub const DEBUG_CAPTURE_KEY: &str = "debug_capture";
+
+/// 元数据中保留的原始响应最大字符数（磁盘文件保留完整的捕获内容）
+pub const MAX_METADATA_RESPONSE_CHARS: usize = 4096;
+
+/// 单个引擎的调试捕获
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineDebugCapture {
+    /// 最后读取的响应 URL（解析器使用的响应）
+    pub url: Option<String>,
+    /// HTTP 状态码
+    pub status: Option<u16>,
+    /// Content-Type
+    pub content_type: Option<String>,
+    /// 原始响应大小（字节）
+    pub raw_bytes: usize,
+    /// 原始响应（截断到 [`MAX_METADATA_RESPONSE_CHARS`] 个字符）
+    pub raw_response: String,
+    /// 读取的响应数
+    pub response_count: usize,
+    /// 解析出的结果数
+    pub item_count: usize,
+    /// 解析警告
+    pub parse_warnings: Vec<String>,
+    /// 引擎错误（成功时为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 保存到磁盘的捕获文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_file: Option<String>,
+}
+
+impl EngineDebugCapture {
+    /// 根据捕获内容与引擎结果生成调试信息
+    ///
+    /// # 参数
+    ///
+    /// * `capture` - 引擎搜索期间捕获的响应与警告
+    /// * `outcome` - 引擎结果或错误信息
+    pub fn new(capture: &ResponseCapture, outcome: Result<&SearchResult, &str>) -> Self {
+        let last = capture.responses.last();
+        let raw_response = last
+            .map(|response| {
+                String::from_utf8_lossy(&response.body)
+                    .chars()
+                    .take(MAX_METADATA_RESPONSE_CHARS)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            url: last.map(|response| response.url.clone()),
+            status: last.map(|response| response.status),
+            content_type: last.and_then(|response| response.content_type.clone()),
+            raw_bytes: last.map_or(0, |response| response.total_bytes),
+            raw_response,
+            response_count: capture.responses.len(),
+            item_count: outcome.map_or(0, |result| result.items.len()),
+            parse_warnings: parse_warnings(capture, outcome),
+            error: outcome.err().map(str::to_string),
+            capture_file: None,
+        }
+    }
+}
+
+/// 检查常见的解析异常
+///
+/// 引擎通过 [`capture::warn`](crate::net::client::capture::warn) 报告的警告排在前面
+fn parse_warnings(capture: &ResponseCapture, outcome: Result<&SearchResult, &str>) -> Vec<String> {
+    let mut warnings = capture.warnings.clone();
+
+    let Some(last) = capture.responses.last() else {
+        warnings.push("no response body was read".to_string());
+        return warnings;
+    };
+    if !(200..300).contains(&last.status) {
+        warnings.push(format!("HTTP status {}", last.status));
+    }
+    if last.is_truncated() {
+        warnings.push(format!(
+            "captured response truncated to {} of {} bytes",
+            last.body.len(),
+            last.total_bytes
+        ));
+    }
+
+    let Ok(result) = outcome else {
+        return warnings;
+    };
+    if result.items.is_empty() && last.total_bytes > 0 {
+        warnings.push(format!(
+            "{} byte response produced no items; selectors may be outdated",
+            last.total_bytes
+        ));
+    }
+    let missing_titles = result.items.iter().filter(|item| item.title.trim().is_empty()).count();
+    if missing_titles > 0 {
+        warnings.push(format!("{} items have an empty title", missing_titles));
+    }
+    let missing_urls = result.items.iter().filter(|item| item.url.trim().is_empty()).count();
+    if missing_urls > 0 {
+        warnings.push(format!("{} items have an empty url", missing_urls));
+    }
+    warnings
+}
+
+/// 磁盘上的捕获文件内容
+#[derive(Serialize)]
+struct CaptureFile<'a> {
+    engine: &'a str,
+    captured_at: String,
+    summary: &'a EngineDebugCapture,
+    responses: Vec<CaptureFileResponse<'a>>,
+}
+
+/// 捕获文件中的单个响应
+#[derive(Serialize)]
+struct CaptureFileResponse<'a> {
+    url: &'a str,
+    status: u16,
+    content_type: Option<&'a str>,
+    total_bytes: usize,
+    body: String,
+}
+
+/// 将捕获内容保存到目录
+///
+/// 文件名为 `<时间戳毫秒>-<引擎名>.json`，包含调试摘要与全部捕获的响应
+///
+/// # 参数
+///
+/// * `dir` - 保存目录（不存在时创建）
+/// * `engine_name` - 引擎名称
+/// * `capture` - 捕获内容
+/// * `summary` - 调试摘要
+///
+/// # 返回
+///
+/// 返回写入的文件路径
+pub async fn save_capture(
+    dir: &Path,
+    engine_name: &str,
+    capture: &ResponseCapture,
+    summary: &EngineDebugCapture,
+) -> std::io::Result<PathBuf> {
+    let now = chrono::Utc::now();
+    let file = CaptureFile {
+        engine: engine_name,
+        captured_at: now.to_rfc3339(),
+        summary,
+        responses: capture
+            .responses
+            .iter()
+            .map(|response| CaptureFileResponse {
+                url: &response.url,
+                status: response.status,
+                content_type: response.content_type.as_deref(),
+                total_bytes: response.total_bytes,
+                body: String::from_utf8_lossy(&response.body).into_owned(),
+            })
+            .collect(),
+    };
+    let data = serde_json::to_vec_pretty(&file).map_err(std::io::Error::other)?;
+
+    let safe_name: String = engine_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let path = dir.join(format!("{}-{}.json", now.timestamp_millis(), safe_name));
+    tokio::fs::create_dir_all(dir).await?;
+    tokio::fs::write(&path, data).await?;
+    Ok(path)
+}
+
+/// 将各引擎的调试捕获写入聚合结果的元数据
+pub fn attach_debug_captures(result: &mut SearchResult, captures: BTreeMap<String, EngineDebugCapture>) {
+    if captures.is_empty() {
+        return;
+    }
+    match serde_json::to_string(&captures) {
+        Ok(json) => {
+            result.metadata.insert(DEBUG_CAPTURE_KEY.to_string(), json);
+        }
+        Err(e) => tracing::warn!("Failed to serialize debug capture: {}", e),
+    }
+}
+
+/// 从结果元数据中读取调试捕获
+pub fn debug_captures(result: &SearchResult) -> Option<BTreeMap<String, EngineDebugCapture>> {
+    let json = result.metadata.get(DEBUG_CAPTURE_KEY)?;
+    serde_json::from_str(json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derive::{ResultType, SearchResultItem};
+    use crate::net::client::capture::CapturedResponse;
+    use std::collections::HashMap;
+
+    fn result(items: Vec<SearchResultItem>) -> SearchResult {
+        SearchResult {
+            engine_name: "bing".to_string(),
+            total_results: None,
+            elapsed_ms: 0,
+            items,
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn capture(body: &str) -> ResponseCapture {
+        ResponseCapture {
+            responses: vec![CapturedResponse {
+                url: "https://example.com/search?q=rust".to_string(),
+                status: 200,
+                content_type: Some("text/html".to_string()),
+                body: body.as_bytes().to_vec(),
+                total_bytes: body.len(),
+            }],
+            warnings: vec!["result block without link".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_zero_items_warns_about_selectors() {
+        let result = result(Vec::new());
+        let debug = EngineDebugCapture::new(&capture("<html></html>"), Ok(&result));
+        assert_eq!(debug.item_count, 0);
+        assert_eq!(debug.raw_response, "<html></html>");
+        assert_eq!(debug.parse_warnings[0], "result block without link");
+        assert!(debug.parse_warnings.iter().any(|w| w.contains("selectors may be outdated")));
+    }
+
+    #[tokio::test]
+    async fn test_attach_and_save() {
+        let parsed = result(vec![SearchResultItem {
+            title: String::new(),
+            url: "https://example.com/".to_string(),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score: 0.0,
+            result_type: ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        }]);
+        let capture = capture(&"x".repeat(MAX_METADATA_RESPONSE_CHARS + 10));
+        let mut debug = EngineDebugCapture::new(&capture, Ok(&parsed));
+        assert_eq!(debug.raw_response.len(), MAX_METADATA_RESPONSE_CHARS);
+        assert!(debug.parse_warnings.iter().any(|w| w == "1 items have an empty title"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = save_capture(dir.path(), "bing", &capture, &debug).await.unwrap();
+        let saved: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved["responses"][0]["total_bytes"], MAX_METADATA_RESPONSE_CHARS + 10);
+        debug.capture_file = Some(path.display().to_string());
+
+        let mut aggregated = result(Vec::new());
+        attach_debug_captures(&mut aggregated, BTreeMap::from([("bing".to_string(), debug)]));
+        let captures = debug_captures(&aggregated).unwrap();
+        assert_eq!(captures["bing"].item_count, 1);
+        assert!(captures["bing"].capture_file.is_some());
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 引擎依赖检查
+//!
+//! 启动时按引擎配置的 `dependencies` 检查所需的程序、服务与凭据，
+//! 依赖不满足的引擎被标记为不可用并记录原因。
+//!
+//! `required` 中的每一项形如 `类型:值`：
+//! - `bin:chromium` - PATH 中存在该可执行文件（如无头浏览器）
+//! - `env:GITHUB_TOKEN` - 环境变量已设置且非空
+//! - `tcp:127.0.0.1:9050` - 能在超时内建立 TCP 连接（如 Tor SOCKS 端口）
+//! - `feature:python` - 编译时启用了该 Cargo 特性
+//! - `api_key` - 引擎的 `specific.api_key` 或认证凭据已配置
+//!
+//! `optional` 中的依赖不满足时只记录日志。系统要求检查平台、CPU 核心数与内存（仅 Linux），
+//! 不检查磁盘空间
+
+use crate::config::engines::{EngineDependencies, EngineSpecificConfig, SystemRequirements};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+/// TCP 依赖的连接超时
+const TCP_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 检查引擎依赖
+///
+/// # Arguments
+///
+/// * `engine_name` - 引擎名称（用于日志）
+/// * `dependencies` - 引擎依赖配置
+/// * `settings` - 引擎特定配置（检查 `api_key` 依赖）
+///
+/// # Returns
+///
+/// 所有必需依赖与系统要求都满足时返回 None，否则返回不满足的原因（以 `; ` 分隔）
+pub fn unmet_dependencies(
+    engine_name: &str,
+    dependencies: &EngineDependencies,
+    settings: Option<&EngineSpecificConfig>,
+) -> Option<String> {
+    let mut unmet: Vec<String> = dependencies
+        .required
+        .iter()
+        .filter_map(|dependency| check_dependency(dependency, settings).err())
+        .collect();
+    unmet.extend(check_system_requirements(&dependencies.system_requirements));
+
+    for dependency in &dependencies.optional {
+        if let Err(reason) = check_dependency(dependency, settings) {
+            tracing::info!("Optional dependency of engine {} unavailable: {}", engine_name, reason);
+        }
+    }
+
+    (!unmet.is_empty()).then(|| unmet.join("; "))
+}
+
+/// 检查单个依赖，不满足时返回原因
+fn check_dependency(dependency: &str, settings: Option<&EngineSpecificConfig>) -> Result<(), String> {
+    let dependency = dependency.trim();
+    let (kind, value) = dependency.split_once(':').unwrap_or((dependency, ""));
+    let value = value.trim();
+    match kind.trim().to_ascii_lowercase().as_str() {
+        "bin" => find_executable(value)
+            .then_some(())
+            .ok_or_else(|| format!("executable '{}' not found in PATH", value)),
+        "env" => std::env::var(value)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|_| ())
+            .ok_or_else(|| format!("environment variable {} is not set", value)),
+        "tcp" => tcp_reachable(value)
+            .then_some(())
+            .ok_or_else(|| format!("{} is not reachable", value)),
+        "feature" => feature_enabled(value)
+            .then_some(())
+            .ok_or_else(|| format!("feature '{}' is not enabled in this build", value)),
+        "api_key" => has_credentials(settings)
+            .then_some(())
+            .ok_or_else(|| "API key is not configured".to_string()),
+        _ => Err(format!("unknown dependency '{}'", dependency)),
+    }
+}
+
+/// PATH 中是否存在可执行文件（含路径分隔符时直接检查该路径）
+fn find_executable(name: &str) -> bool {
+    if name.is_empty() {
+        return false;
+    }
+    if name.contains(std::path::MAIN_SEPARATOR) || name.contains('/') {
+        return Path::new(name).is_file();
+    }
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&paths).any(|dir| {
+        dir.join(name).is_file()
+            || (cfg!(windows) && dir.join(format!("{}.exe", name)).is_file())
+    })
+}
+
+/// 能否在超时内连接到 `host:port`
+fn tcp_reachable(address: &str) -> bool {
+    let Ok(addresses) = address.to_socket_addrs() else {
+        return false;
+    };
+    addresses
+        .into_iter()
+        .any(|addr| TcpStream::connect_timeout(&addr, TCP_CHECK_TIMEOUT).is_ok())
+}
+
+/// 编译时是否启用了指定特性
+fn feature_enabled(feature: &str) -> bool {
+    match feature {
+        "native" => cfg!(feature = "native"),
+        "python" => cfg!(feature = "python"),
+        "grpc" => cfg!(feature = "grpc"),
+        "archive-sqlite" | "archive_sqlite" => cfg!(feature = "archive-sqlite"),
+        _ => false,
+    }
+}
+
+/// 引擎是否配置了 API 密钥或认证凭据
+fn has_credentials(settings: Option<&EngineSpecificConfig>) -> bool {
+    settings.is_some_and(|settings| {
+        settings.api_key.as_deref().is_some_and(|key| !key.trim().is_empty())
+            || settings
+                .authentication
+                .as_ref()
+                .is_some_and(|auth| !auth.credentials.is_empty() || auth.token_refresh.is_some())
+    })
+}
+
+/// 检查系统要求，返回不满足的项
+fn check_system_requirements(requirements: &SystemRequirements) -> Vec<String> {
+    let mut unmet = Vec::new();
+    let platform = std::env::consts::OS;
+    if !requirements.supported_platforms.is_empty()
+        && !requirements
+            .supported_platforms
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(platform))
+    {
+        unmet.push(format!("platform {} is not supported", platform));
+    }
+    if let Some(min_cores) = requirements.min_cpu_cores {
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        if cores < min_cores {
+            unmet.push(format!("requires {} CPU cores, found {}", min_cores, cores));
+        }
+    }
+    if let (Some(min_memory), Some(memory)) = (requirements.min_memory_mb, total_memory_mb())
+        && memory < min_memory
+    {
+        unmet.push(format!("requires {} MB memory, found {} MB", min_memory, memory));
+    }
+    unmet
+}
+
+/// 系统总内存（MB，仅 Linux，无法读取时返回 None）
+fn total_memory_mb() -> Option<usize> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kb: usize = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb / 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependencies(required: &[&str]) -> EngineDependencies {
+        EngineDependencies {
+            required: required.iter().map(|d| d.to_string()).collect(),
+            optional: Vec::new(),
+            system_requirements: SystemRequirements {
+                min_memory_mb: None,
+                min_cpu_cores: Some(1),
+                min_disk_space_mb: None,
+                supported_platforms: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_unmet_dependencies() {
+        assert_eq!(unmet_dependencies("test", &dependencies(&["feature:native"]), None), None);
+
+        let reason = unmet_dependencies(
+            "test",
+            &dependencies(&["bin:seesea-missing-binary", "env:SEESEA_MISSING_VAR", "api_key", "magic"]),
+            None,
+        )
+        .unwrap();
+        assert!(reason.contains("executable 'seesea-missing-binary'"));
+        assert!(reason.contains("SEESEA_MISSING_VAR"));
+        assert!(reason.contains("API key"));
+        assert!(reason.contains("unknown dependency 'magic'"));
+
+        let mut requirements = dependencies(&[]);
+        requirements.system_requirements.supported_platforms = vec!["plan9".to_string()];
+        assert!(unmet_dependencies("test", &requirements, None).unwrap().contains("platform"));
+    }
+}
@@ -25,6 +25,8 @@ pub enum EngineMode {
     Global,
     /// 自定义模式（用户指定引擎）
     Custom(Vec<String>),
+    /// 分类模式：使用配置中该分类的默认引擎与权重（由 `SearchInterface` 按分类配置解析）
+    Category(String),
 }
 
 impl Default for EngineMode {
@@ -58,6 +60,14 @@ impl Default for EngineListConfig {
             "sogou_images".to_string(),
             "sogou_videos".to_string(),
             "sogou_wechat".to_string(),
+            "github".to_string(),
+            "stackoverflow".to_string(),
+            "arxiv".to_string(),
+            "crossref".to_string(),
+            "semantic_scholar".to_string(),
+            "ebay".to_string(),
+            "nominatim".to_string(),
+            "piratebay".to_string(),
         ];
 
         #[cfg(feature = "python")]
@@ -74,6 +84,14 @@ impl Default for EngineListConfig {
             "sogou_images".to_string(),
             "sogou_videos".to_string(),
             "sogou_wechat".to_string(),
+            "github".to_string(),
+            "stackoverflow".to_string(),
+            "arxiv".to_string(),
+            "crossref".to_string(),
+            "semantic_scholar".to_string(),
+            "ebay".to_string(),
+            "nominatim".to_string(),
+            "piratebay".to_string(),
             "xinhua".to_string(),
         ];
 
@@ -132,6 +150,10 @@ impl EngineListConfig {
                     .cloned()
                     .collect()
             }
+            EngineMode::Category(_) => {
+                // 分类的默认引擎不在引擎列表配置中
+                Vec::new()
+            }
         }
     }
 
@@ -182,6 +204,36 @@ impl EngineListConfig {
     }
 }
 
+/// 文件/种子分类引擎（需启用 `engines.enable_files_category`）
+pub const FILES_ENGINES: &[&str] = &["piratebay"];
+
+/// 移除文件分类引擎
+pub fn exclude_files_engines(engines: Vec<String>) -> Vec<String> {
+    engines
+        .into_iter()
+        .filter(|engine| !FILES_ENGINES.contains(&engine.as_str()))
+        .collect()
+}
+
+/// 将优先引擎排到列表最前
+///
+/// `preferred` 中的引擎按给定顺序排在前面（不在 `engines` 中的也会被加入），
+/// 其余引擎保持原有顺序
+pub fn prioritize_engines(engines: Vec<String>, preferred: &[String]) -> Vec<String> {
+    let mut routed: Vec<String> = Vec::with_capacity(engines.len() + preferred.len());
+    for engine in preferred {
+        if !routed.contains(engine) {
+            routed.push(engine.clone());
+        }
+    }
+    for engine in engines {
+        if !routed.contains(&engine) {
+            routed.push(engine);
+        }
+    }
+    routed
+}
+
 // 全局引擎配置实例
 lazy_static::lazy_static! {
     pub static ref ENGINE_CONFIG: EngineListConfig = EngineListConfig::default();
@@ -191,6 +243,21 @@ lazy_static::lazy_static! {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_exclude_files_engines() {
+        let engines = vec!["bing".to_string(), "piratebay".to_string()];
+        assert_eq!(exclude_files_engines(engines), vec!["bing".to_string()]);
+        assert!(EngineListConfig::default().is_engine_available("piratebay"));
+    }
+
+    #[test]
+    fn test_prioritize_engines() {
+        let engines = vec!["bing".to_string(), "sogou".to_string(), "yandex".to_string()];
+        let preferred = vec!["baidu".to_string(), "sogou".to_string()];
+        let routed = prioritize_engines(engines, &preferred);
+        assert_eq!(routed, vec!["baidu", "sogou", "bing", "yandex"]);
+    }
+
     #[test]
     fn test_engine_list_config_default() {
         let config = EngineListConfig::default();
@@ -22,7 +22,12 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
 use crate::derive::{SearchEngine, SearchQuery, SearchResult};
+use crate::search::concurrency::ConcurrencyLimiter;
 use crate::search::engines::*;
+use crate::webhook::{WebhookDispatcher, WebhookEvent};
+
+/// 判定选择器失效所需的连续零结果不同查询数（默认值）
+pub const DEFAULT_SELECTOR_ROT_THRESHOLD: u32 = 3;
 
 /// 引擎运行模式
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,6 +59,10 @@ pub struct EngineState {
     pub failed_requests: u64,
     /// 平均响应时间（毫秒）
     pub avg_response_time_ms: u64,
+    /// 是否已降级（抓取引擎疑似页面结构变化导致选择器失效）
+    pub degraded: bool,
+    /// 当前连续返回零结果的不同查询（有结果时清空）
+    pub empty_queries: Vec<String>,
 }
 
 impl EngineState {
@@ -69,6 +78,8 @@ impl EngineState {
             successful_requests: 0,
             failed_requests: 0,
             avg_response_time_ms: 0,
+            degraded: false,
+            empty_queries: Vec::new(),
         }
     }
 
@@ -138,7 +149,11 @@ impl EngineState {
     /// - 第2次：25分钟
     /// - 第3次：125分钟
     /// - ...
-    pub fn record_zero_results(&mut self) {
+    ///
+    /// # 返回值
+    ///
+    /// 本次禁用的时长
+    pub fn record_zero_results(&mut self) -> Duration {
         self.consecutive_failures += 1;
         
         // 计算禁用时长：5 * 5^(n-1) 分钟
@@ -154,6 +169,62 @@ impl EngineState {
             "Engine '{}' returned zero results. Temporarily disabled for {} minutes (failure #{})",
             self.name, disable_minutes, self.consecutive_failures
         );
+
+        disable_duration
+    }
+
+    /// 记录一次成功响应（HTTP 200）的结果数，用于检测选择器失效
+    ///
+    /// 抓取引擎在页面结构变化后仍能正常响应，但解析不出任何结果。
+    /// 连续 `threshold` 个不同查询都返回零结果时将引擎标记为降级；
+    /// 同一查询重复返回零结果只计一次，任意查询返回结果即清除降级状态。
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 查询文本
+    /// * `item_count` - 解析出的结果数
+    /// * `threshold` - 判定降级的不同查询数（0 表示不检测）
+    ///
+    /// # 返回值
+    ///
+    /// 本次是否刚被标记为降级
+    pub fn record_selector_check(&mut self, query: &str, item_count: usize, threshold: u32) -> bool {
+        if item_count > 0 {
+            if self.degraded {
+                tracing::info!("Engine '{}' returned results again, clearing degraded state", self.name);
+            }
+            self.degraded = false;
+            self.empty_queries.clear();
+            return false;
+        }
+        if threshold == 0 || self.degraded {
+            return false;
+        }
+
+        let query = query.trim().to_lowercase();
+        if !self.empty_queries.contains(&query) {
+            self.empty_queries.push(query);
+        }
+        if self.empty_queries.len() < threshold as usize {
+            return false;
+        }
+
+        self.degraded = true;
+        tracing::warn!(
+            "Engine '{}' returned zero results for {} distinct queries with successful responses; selectors may be outdated",
+            self.name, self.empty_queries.len()
+        );
+        true
+    }
+}
+
+/// 构造引擎降级的 Webhook 事件
+pub(crate) fn degraded_event(state: &EngineState) -> WebhookEvent {
+    WebhookEvent::EngineDegraded {
+        engine: state.name.clone(),
+        zero_result_queries: state.empty_queries.len(),
+        reason: "zero results for distinct queries with successful responses; selectors may be outdated"
+            .to_string(),
     }
 }
 
@@ -173,6 +244,12 @@ pub struct EngineManager {
     failure_threshold: u32,
     /// 共享的 HTTP 客户端（用于优化性能）
     shared_client: Option<Arc<crate::net::client::HttpClient>>,
+    /// 引擎请求并发限制（未设置时不限制）
+    limiter: Option<Arc<ConcurrencyLimiter>>,
+    /// 判定选择器失效的连续零结果不同查询数（0 表示不检测）
+    selector_rot_threshold: u32,
+    /// 事件 Webhook（未设置时不投递）
+    webhooks: Option<Arc<WebhookDispatcher>>,
 }
 
 impl EngineManager {
@@ -229,12 +306,35 @@ impl EngineManager {
             temporary_disable_duration: 300,
             failure_threshold: 3,
             shared_client: Some(shared_client),
+            limiter: None,
+            selector_rot_threshold: DEFAULT_SELECTOR_ROT_THRESHOLD,
+            webhooks: None,
         };
         
         manager.initialize_engines();
         manager
     }
 
+    /// 设置引擎请求并发限制
+    ///
+    /// 可与其他管理器或搜索接口共享同一个限制器，使上限在它们之间共同生效
+    pub fn with_concurrency_limiter(mut self, limiter: Arc<ConcurrencyLimiter>) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// 设置判定选择器失效的连续零结果不同查询数（0 表示不检测）
+    pub fn with_selector_rot_threshold(mut self, threshold: u32) -> Self {
+        self.selector_rot_threshold = threshold;
+        self
+    }
+
+    /// 设置事件 Webhook 分发器（抓取引擎被标记为降级时投递 `engine_degraded` 事件）
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookDispatcher>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
     /// 初始化所有引擎
     fn initialize_engines(&mut self) {
         // 总是使用共享客户端创建引擎（性能最优）
@@ -363,9 +463,17 @@ impl EngineManager {
                 let states = Arc::clone(&self.states);
                 let temp_disable_duration = self.temporary_disable_duration;
                 let failure_threshold = self.failure_threshold;
+                let limiter = self.limiter.clone();
+                let selector_rot_threshold = self.selector_rot_threshold;
+                let webhooks = self.webhooks.clone();
                 
                 // 创建异步任务
                 let task = tokio::spawn(async move {
+                    // 超出并发上限时排队
+                    let _permit = match limiter {
+                        Some(ref limiter) => Some(limiter.acquire(&engine_name_clone).await),
+                        None => None,
+                    };
                     let start_time = Instant::now();
                     let result = engine_clone.search(&query_clone).await;
                     let response_time_ms = start_time.elapsed().as_millis() as u64;
@@ -377,8 +485,20 @@ impl EngineManager {
                         .or_insert_with(|| EngineState::new(engine_name_clone.clone()));
                     
                     match &result {
-                        Ok(_) => {
+                        Ok(search_result) => {
                             state.record_success(response_time_ms);
+                            // 只有抓取引擎会因页面结构变化而静默返回零结果
+                            let scraped = !engine_clone.info().about.use_official_api;
+                            if scraped
+                                && state.record_selector_check(
+                                    &query_clone.query,
+                                    search_result.items.len(),
+                                    selector_rot_threshold,
+                                )
+                                && let Some(webhooks) = &webhooks
+                            {
+                                webhooks.dispatch(degraded_event(state));
+                            }
                         }
                         Err(e) => {
                             let error_msg = e.to_string();
@@ -517,6 +637,31 @@ mod tests {
         assert_eq!(state.consecutive_failures, 1);
     }
 
+    #[test]
+    fn test_selector_rot_detection() {
+        let mut state = EngineState::new("test".to_string());
+
+        // 同一查询重复返回零结果只计一次
+        assert!(!state.record_selector_check("rust", 0, 3));
+        assert!(!state.record_selector_check(" Rust ", 0, 3));
+        assert!(!state.record_selector_check("tokio", 0, 3));
+        assert!(!state.degraded);
+
+        assert!(state.record_selector_check("serde", 0, 3));
+        assert!(state.degraded);
+        // 已降级时不重复触发
+        assert!(!state.record_selector_check("axum", 0, 3));
+
+        // 返回结果后清除降级状态
+        assert!(!state.record_selector_check("rust", 5, 3));
+        assert!(!state.degraded);
+        assert!(state.empty_queries.is_empty());
+
+        // 阈值为 0 时不检测
+        assert!(!state.record_selector_check("rust", 0, 0));
+        assert!(state.empty_queries.is_empty());
+    }
+
     #[tokio::test]
     async fn test_engine_manager_creation() {
         let manager = EngineManager::new(
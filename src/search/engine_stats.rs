@@ -0,0 +1,608 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 引擎统计模块
+//!
+//! 记录每个引擎的成功率、平均延迟、延迟分位数、结果数量和最近一次失败原因，
+//! 并持久化到元数据缓存中，进程重启后仍可用于报告和调度
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::cache::MetadataCache;
+
+use super::bandwidth::EngineBandwidth;
+
+/// 引擎统计在元数据缓存中的键前缀
+const ENGINE_STATS_KEY_PREFIX: &str = "engine_stats:";
+
+/// 计算质量评分所需的最少请求数
+pub const MIN_SAMPLES_FOR_QUALITY: u64 = 5;
+
+/// 延迟达到该值（毫秒）时延迟因子减半
+const LATENCY_HALF_POINT_MS: f64 = 2000.0;
+
+/// 计算延迟分位数时保留的最近样本数
+pub const LATENCY_WINDOW: usize = 100;
+
+/// 获取当前时间戳（秒）
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::from_secs(0))
+        .as_secs()
+}
+
+/// 单个引擎的累计统计
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EngineStatsRecord {
+    /// 引擎名称
+    pub engine: String,
+    /// 总请求数
+    pub total_requests: u64,
+    /// 成功请求数（含零结果）
+    pub successful_requests: u64,
+    /// 失败请求数（错误或超时）
+    pub failed_requests: u64,
+    /// 零结果请求数
+    pub zero_result_requests: u64,
+    /// 累计返回的结果数
+    pub total_results: u64,
+    /// 平均延迟（毫秒，仅统计成功请求）
+    pub avg_latency_ms: f64,
+    /// 最近一次失败原因
+    pub last_failure_reason: Option<String>,
+    /// 最近一次失败时间（Unix 时间戳）
+    pub last_failure_at: Option<u64>,
+    /// 最近一次成功时间（Unix 时间戳）
+    pub last_success_at: Option<u64>,
+    /// 最近成功请求的延迟样本（毫秒，最多 [`LATENCY_WINDOW`] 个）
+    pub recent_latencies_ms: VecDeque<u64>,
+}
+
+impl EngineStatsRecord {
+    /// 创建空的统计记录
+    pub fn new(engine: impl Into<String>) -> Self {
+        Self {
+            engine: engine.into(),
+            ..Default::default()
+        }
+    }
+
+    /// 记录一次成功请求
+    pub fn record_success(&mut self, latency_ms: u64, result_count: usize) {
+        self.total_requests += 1;
+        self.successful_requests += 1;
+        self.total_results += result_count as u64;
+        if result_count == 0 {
+            self.zero_result_requests += 1;
+        }
+
+        let n = self.successful_requests as f64;
+        self.avg_latency_ms += (latency_ms as f64 - self.avg_latency_ms) / n;
+        self.last_success_at = Some(current_timestamp());
+
+        if self.recent_latencies_ms.len() == LATENCY_WINDOW {
+            self.recent_latencies_ms.pop_front();
+        }
+        self.recent_latencies_ms.push_back(latency_ms);
+    }
+
+    /// 记录一次失败请求
+    pub fn record_failure(&mut self, reason: impl Into<String>) {
+        self.total_requests += 1;
+        self.failed_requests += 1;
+        self.last_failure_reason = Some(reason.into());
+        self.last_failure_at = Some(current_timestamp());
+    }
+
+    /// 成功率（0.0 - 1.0），没有请求时返回 None
+    pub fn success_rate(&self) -> Option<f64> {
+        if self.total_requests == 0 {
+            None
+        } else {
+            Some(self.successful_requests as f64 / self.total_requests as f64)
+        }
+    }
+
+    /// 历史质量评分（0.0 - 1.0）
+    ///
+    /// 综合成功率、返回非空结果的比例和平均延迟；
+    /// 样本不足 [`MIN_SAMPLES_FOR_QUALITY`] 时返回 None
+    pub fn quality_score(&self) -> Option<f64> {
+        if self.total_requests < MIN_SAMPLES_FOR_QUALITY {
+            return None;
+        }
+
+        let success = self.successful_requests as f64 / self.total_requests as f64;
+        let useful = if self.successful_requests == 0 {
+            0.0
+        } else {
+            (self.successful_requests - self.zero_result_requests) as f64
+                / self.successful_requests as f64
+        };
+        let latency = 1.0 / (1.0 + self.avg_latency_ms / LATENCY_HALF_POINT_MS);
+
+        Some((success * 0.5 + useful * 0.3 + latency * 0.2).clamp(0.0, 1.0))
+    }
+
+    /// 最近延迟的分位数（毫秒，最近邻秩法），没有样本时返回 None
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - 分位（0.0 - 1.0）
+    pub fn latency_percentile(&self, percentile: f64) -> Option<u64> {
+        if self.recent_latencies_ms.is_empty() {
+            return None;
+        }
+        let mut samples: Vec<u64> = self.recent_latencies_ms.iter().copied().collect();
+        samples.sort_unstable();
+        let rank = (percentile.clamp(0.0, 1.0) * samples.len() as f64).ceil() as usize;
+        Some(samples[rank.saturating_sub(1)])
+    }
+
+    /// 最近延迟的中位数（毫秒）
+    pub fn p50_latency_ms(&self) -> Option<u64> {
+        self.latency_percentile(0.5)
+    }
+
+    /// 最近延迟的 95 分位数（毫秒）
+    pub fn p95_latency_ms(&self) -> Option<u64> {
+        self.latency_percentile(0.95)
+    }
+
+    /// 每次成功请求的平均结果数
+    pub fn avg_results(&self) -> f64 {
+        if self.successful_requests == 0 {
+            0.0
+        } else {
+            self.total_results as f64 / self.successful_requests as f64
+        }
+    }
+}
+
+/// 引擎统计报告中的单项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineReportEntry {
+    /// 累计统计
+    #[serde(flatten)]
+    pub stats: EngineStatsRecord,
+    /// 成功率
+    pub success_rate: Option<f64>,
+    /// 平均结果数
+    pub avg_results: f64,
+    /// 最近延迟中位数（毫秒）
+    pub p50_latency_ms: Option<u64>,
+    /// 最近延迟 95 分位数（毫秒）
+    pub p95_latency_ms: Option<u64>,
+    /// 当日下载流量（由搜索接口填充）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bandwidth: Option<EngineBandwidth>,
+    /// 是否因疑似选择器失效被标记为降级（由搜索接口填充）
+    #[serde(default)]
+    pub degraded: bool,
+    /// 结果点击数（由搜索接口填充）
+    #[serde(default)]
+    pub clicks: u64,
+    /// 点击率：点击数 / 返回非空结果的请求数（样本不足时为 None，由搜索接口填充）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub click_through_rate: Option<f64>,
+}
+
+/// 引擎统计报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineReport {
+    /// 报告生成时间（Unix 时间戳）
+    pub generated_at: u64,
+    /// 总请求数
+    pub total_requests: u64,
+    /// 总失败数
+    pub total_failures: u64,
+    /// 各引擎统计（按引擎名排序）
+    pub engines: Vec<EngineReportEntry>,
+}
+
+impl EngineReport {
+    /// 查找指定引擎的统计
+    pub fn engine(&self, name: &str) -> Option<&EngineReportEntry> {
+        self.engines.iter().find(|e| e.stats.engine == name)
+    }
+}
+
+/// 自适应引擎超时配置
+///
+/// 引擎超时取最近延迟的 95 分位数乘以 `multiplier`，并限制在 `floor` 与 `ceiling` 之间；
+/// 样本不足时使用默认超时
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveTimeoutConfig {
+    /// 超时下限
+    pub floor: Duration,
+    /// 超时上限
+    pub ceiling: Duration,
+    /// p95 延迟的放大倍数
+    pub multiplier: f64,
+    /// 启用自适应超时所需的最少延迟样本数
+    pub min_samples: usize,
+}
+
+impl Default for AdaptiveTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            floor: Duration::from_secs(2),
+            ceiling: Duration::from_secs(30),
+            multiplier: 1.5,
+            min_samples: 10,
+        }
+    }
+}
+
+impl AdaptiveTimeoutConfig {
+    /// 计算引擎超时
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - 引擎统计（没有历史时为 None）
+    /// * `default` - 样本不足时使用的超时
+    pub fn timeout_for(&self, record: Option<&EngineStatsRecord>, default: Duration) -> Duration {
+        let p95 = record
+            .filter(|r| r.recent_latencies_ms.len() >= self.min_samples)
+            .and_then(EngineStatsRecord::p95_latency_ms);
+        match p95 {
+            Some(p95) => {
+                let ceiling = self.ceiling.max(self.floor);
+                Duration::from_millis((p95 as f64 * self.multiplier) as u64).clamp(self.floor, ceiling)
+            }
+            None => default,
+        }
+    }
+}
+
+/// 引擎统计存储
+///
+/// 内存中维护最新统计，每次更新后写入元数据缓存；
+/// 未配置缓存时只保存在内存中
+pub struct EngineStatsStore {
+    /// 内存中的统计
+    records: RwLock<HashMap<String, EngineStatsRecord>>,
+    /// 持久化用的元数据缓存
+    cache: Option<MetadataCache>,
+}
+
+impl EngineStatsStore {
+    /// 创建仅保存在内存中的统计存储
+    pub fn in_memory() -> Self {
+        Self {
+            records: RwLock::new(HashMap::new()),
+            cache: None,
+        }
+    }
+
+    /// 创建持久化到元数据缓存的统计存储
+    pub fn with_cache(cache: MetadataCache) -> Self {
+        Self {
+            records: RwLock::new(HashMap::new()),
+            cache: Some(cache),
+        }
+    }
+
+    /// 是否启用了持久化
+    pub fn is_persistent(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    fn cache_key(engine: &str) -> String {
+        format!("{}{}", ENGINE_STATS_KEY_PREFIX, engine)
+    }
+
+    /// 从缓存加载单个引擎的统计
+    fn load(&self, engine: &str) -> Option<EngineStatsRecord> {
+        let cache = self.cache.as_ref()?;
+        match cache.get_metadata(&Self::cache_key(engine)) {
+            Ok(Some(bytes)) => {
+                bincode::serde::decode_from_slice(&bytes, bincode::config::standard())
+                    .map(|(record, _)| record)
+                    .map_err(|e| tracing::warn!("Failed to decode stats for engine {}: {}", engine, e))
+                    .ok()
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Failed to load stats for engine {}: {}", engine, e);
+                None
+            }
+        }
+    }
+
+    /// 将统计写入缓存
+    fn persist(&self, record: &EngineStatsRecord) {
+        let Some(cache) = self.cache.as_ref() else {
+            return;
+        };
+        let bytes = match bincode::serde::encode_to_vec(record, bincode::config::standard()) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!("Failed to encode stats for engine {}: {}", record.engine, e);
+                return;
+            }
+        };
+        if let Err(e) = cache.set_metadata(&Self::cache_key(&record.engine), bytes, None) {
+            tracing::warn!("Failed to persist stats for engine {}: {}", record.engine, e);
+        }
+    }
+
+    /// 更新指定引擎的统计
+    async fn update<F>(&self, engine: &str, f: F)
+    where
+        F: FnOnce(&mut EngineStatsRecord),
+    {
+        let mut records = self.records.write().await;
+        let record = records
+            .entry(engine.to_string())
+            .or_insert_with(|| self.load(engine).unwrap_or_else(|| EngineStatsRecord::new(engine)));
+        f(record);
+        self.persist(record);
+    }
+
+    /// 记录成功请求
+    pub async fn record_success(&self, engine: &str, latency_ms: u64, result_count: usize) {
+        self.update(engine, |r| r.record_success(latency_ms, result_count)).await;
+    }
+
+    /// 记录失败请求
+    pub async fn record_failure(&self, engine: &str, reason: &str) {
+        self.update(engine, |r| r.record_failure(reason)).await;
+    }
+
+    /// 获取指定引擎的统计（包括仅存在于缓存中的历史数据）
+    pub async fn get(&self, engine: &str) -> Option<EngineStatsRecord> {
+        if let Some(record) = self.records.read().await.get(engine) {
+            return Some(record.clone());
+        }
+        self.load(engine)
+    }
+
+    /// 获取各引擎的历史质量评分
+    ///
+    /// 只包含样本充足的引擎，其余引擎由调用方回退到静态权威度
+    pub async fn quality_weights(&self, engines: &[String]) -> HashMap<String, f64> {
+        let mut weights = HashMap::new();
+        for engine in engines {
+            if let Some(score) = self.get(engine).await.and_then(|r| r.quality_score()) {
+                weights.insert(engine.clone(), score);
+            }
+        }
+        weights
+    }
+
+    /// 计算各引擎的自适应超时
+    ///
+    /// # Arguments
+    ///
+    /// * `engines` - 引擎列表
+    /// * `config` - 自适应超时配置
+    /// * `default` - 样本不足时使用的超时
+    pub async fn adaptive_timeouts(
+        &self,
+        engines: &[String],
+        config: &AdaptiveTimeoutConfig,
+        default: Duration,
+    ) -> HashMap<String, Duration> {
+        let mut timeouts = HashMap::new();
+        for engine in engines {
+            let record = self.get(engine).await;
+            timeouts.insert(engine.clone(), config.timeout_for(record.as_ref(), default));
+        }
+        timeouts
+    }
+
+    /// 生成统计报告
+    ///
+    /// # Arguments
+    ///
+    /// * `engines` - 需要包含在报告中的引擎（会从缓存补齐尚未加载的历史数据）
+    pub async fn report(&self, engines: &[String]) -> EngineReport {
+        let mut merged: HashMap<String, EngineStatsRecord> = self.records.read().await.clone();
+        for engine in engines {
+            if !merged.contains_key(engine)
+                && let Some(record) = self.load(engine)
+            {
+                merged.insert(engine.clone(), record);
+            }
+        }
+
+        let mut entries: Vec<EngineReportEntry> = merged
+            .into_values()
+            .map(|stats| EngineReportEntry {
+                success_rate: stats.success_rate(),
+                avg_results: stats.avg_results(),
+                p50_latency_ms: stats.p50_latency_ms(),
+                p95_latency_ms: stats.p95_latency_ms(),
+                bandwidth: None,
+                degraded: false,
+                clicks: 0,
+                click_through_rate: None,
+                stats,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.stats.engine.cmp(&b.stats.engine));
+
+        EngineReport {
+            generated_at: current_timestamp(),
+            total_requests: entries.iter().map(|e| e.stats.total_requests).sum(),
+            total_failures: entries.iter().map(|e| e.stats.failed_requests).sum(),
+            engines: entries,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::types::{CacheImplConfig, CacheMode};
+    use crate::cache::CacheManager;
+    use serial_test::serial;
+
+    #[test]
+    fn test_record_success_and_failure() {
+        let mut record = EngineStatsRecord::new("bing");
+        record.record_success(100, 10);
+        record.record_success(300, 0);
+        record.record_failure("timeout");
+
+        assert_eq!(record.total_requests, 3);
+        assert_eq!(record.successful_requests, 2);
+        assert_eq!(record.zero_result_requests, 1);
+        assert_eq!(record.total_results, 10);
+        assert!((record.avg_latency_ms - 200.0).abs() < f64::EPSILON);
+        assert_eq!(record.last_failure_reason.as_deref(), Some("timeout"));
+        assert!((record.success_rate().unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert!((record.avg_results() - 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_latency_percentiles_use_recent_window() {
+        let mut record = EngineStatsRecord::new("bing");
+        assert_eq!(record.p50_latency_ms(), None);
+        for latency in 1..=100 {
+            record.record_success(latency, 1);
+        }
+        assert_eq!(record.p50_latency_ms(), Some(50));
+        assert_eq!(record.p95_latency_ms(), Some(95));
+
+        // 超出窗口的旧样本被淘汰
+        for _ in 0..LATENCY_WINDOW {
+            record.record_success(1000, 1);
+        }
+        assert_eq!(record.recent_latencies_ms.len(), LATENCY_WINDOW);
+        assert_eq!(record.p50_latency_ms(), Some(1000));
+    }
+
+    #[test]
+    fn test_adaptive_timeout_bounds() {
+        let config = AdaptiveTimeoutConfig {
+            floor: Duration::from_secs(1),
+            ceiling: Duration::from_secs(5),
+            multiplier: 2.0,
+            min_samples: 3,
+        };
+        let default = Duration::from_secs(10);
+
+        let mut record = EngineStatsRecord::new("slow");
+        record.record_success(1500, 1);
+        record.record_success(1500, 1);
+        assert_eq!(config.timeout_for(Some(&record), default), default);
+        assert_eq!(config.timeout_for(None, default), default);
+
+        record.record_success(1500, 1);
+        assert_eq!(config.timeout_for(Some(&record), default), Duration::from_secs(3));
+
+        let mut fast = EngineStatsRecord::new("fast");
+        let mut very_slow = EngineStatsRecord::new("very_slow");
+        for _ in 0..3 {
+            fast.record_success(100, 1);
+            very_slow.record_success(9000, 1);
+        }
+        assert_eq!(config.timeout_for(Some(&fast), default), config.floor);
+        assert_eq!(config.timeout_for(Some(&very_slow), default), config.ceiling);
+    }
+
+    #[test]
+    fn test_empty_record_rates() {
+        let record = EngineStatsRecord::new("x");
+        assert_eq!(record.success_rate(), None);
+        assert_eq!(record.avg_results(), 0.0);
+    }
+
+    #[test]
+    fn test_quality_score() {
+        let mut record = EngineStatsRecord::new("fast");
+        for _ in 0..MIN_SAMPLES_FOR_QUALITY - 1 {
+            record.record_success(100, 10);
+        }
+        assert_eq!(record.quality_score(), None);
+        record.record_success(100, 10);
+        let fast = record.quality_score().unwrap();
+
+        let mut flaky = EngineStatsRecord::new("flaky");
+        for i in 0..10 {
+            if i % 2 == 0 {
+                flaky.record_failure("timeout");
+            } else {
+                flaky.record_success(4000, 0);
+            }
+        }
+        let flaky = flaky.quality_score().unwrap();
+
+        assert!(fast > 0.9);
+        assert!(flaky < 0.4);
+    }
+
+    #[tokio::test]
+    async fn test_quality_weights_skip_small_samples() {
+        let store = EngineStatsStore::in_memory();
+        for _ in 0..MIN_SAMPLES_FOR_QUALITY {
+            store.record_success("bing", 200, 10).await;
+        }
+        store.record_success("sogou", 200, 10).await;
+
+        let weights = store
+            .quality_weights(&["bing".to_string(), "sogou".to_string()])
+            .await;
+        assert!(weights.contains_key("bing"));
+        assert!(!weights.contains_key("sogou"));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_report() {
+        let store = EngineStatsStore::in_memory();
+        store.record_success("sogou", 50, 8).await;
+        store.record_failure("bing", "Engine bing timeout").await;
+
+        let report = store.report(&[]).await;
+        assert_eq!(report.engines.len(), 2);
+        assert_eq!(report.engines[0].stats.engine, "bing");
+        assert_eq!(report.total_requests, 2);
+        assert_eq!(report.total_failures, 1);
+        assert_eq!(report.engine("sogou").unwrap().success_rate, Some(1.0));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_persisted_stats_survive_new_store() {
+        let db_path = std::env::temp_dir().join(format!("test_engine_stats_{}", std::process::id()));
+        let config = CacheImplConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            default_ttl_secs: 3600,
+            max_size_bytes: 1024 * 1024,
+            enabled: true,
+            compression: false,
+            mode: CacheMode::HighThroughput,
+        };
+        let manager = CacheManager::instance(config).expect("Failed to create cache manager");
+        let engine = format!("persist_test_{}", std::process::id());
+
+        let store = EngineStatsStore::with_cache(MetadataCache::new(manager.clone()));
+        store.record_success(&engine, 120, 4).await;
+
+        let reopened = EngineStatsStore::with_cache(MetadataCache::new(manager));
+        let record = reopened.get(&engine).await.expect("stats should be persisted");
+        assert_eq!(record.total_requests, 1);
+        assert_eq!(record.total_results, 4);
+
+        let report = reopened.report(std::slice::from_ref(&engine)).await;
+        assert!(report.engine(&engine).is_some());
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! arXiv 论文搜索引擎
+//!
+//! 使用 arXiv 官方 API（Atom 格式）搜索预印本论文
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::error::Error;
+
+use crate::derive::{
+    EngineCapabilities, EngineInfo, EngineStatus, EngineType,
+    ResultType, SearchEngine, SearchQuery, SearchResult,
+    SearchResultItem, AboutInfo, RequestResponseEngine, RequestParams,
+};
+use crate::net::client::HttpClient;
+use crate::net::types::{NetworkConfig, RequestOptions};
+use super::utils::{academic_metadata, build_query_string_owned};
+
+/// arXiv API 地址
+const API_URL: &str = "https://export.arxiv.org/api/query";
+
+/// 每页结果数
+const PAGE_SIZE: usize = 10;
+
+pub struct ArxivEngine {
+    info: EngineInfo,
+    client: Arc<HttpClient>,
+}
+
+impl ArxivEngine {
+    pub fn new() -> Self {
+        let client = HttpClient::new(NetworkConfig::default())
+            .unwrap_or_else(|_| panic!("Failed to create HTTP client"));
+        Self::with_client(Arc::new(client))
+    }
+
+    pub fn with_client(client: Arc<HttpClient>) -> Self {
+        Self {
+            info: EngineInfo {
+                name: "arXiv".to_string(),
+                engine_type: EngineType::Academic,
+                description: "arXiv - Open-access preprints".to_string(),
+                status: EngineStatus::Active,
+                categories: vec!["academic".to_string(), "science".to_string()],
+                capabilities: EngineCapabilities {
+                    result_types: vec![ResultType::Academic],
+                    supported_params: vec![],
+                    max_page_size: PAGE_SIZE,
+                    supports_pagination: true,
+                    supports_time_range: false,
+                    supports_language_filter: false,
+                    supports_region_filter: false,
+                    supports_safe_search: false,
+                    rate_limit: Some(20),
+                },
+                about: AboutInfo {
+                    website: Some("https://arxiv.org".to_string()),
+                    wikidata_id: Some("Q118398".to_string()),
+                    official_api_documentation: Some("https://info.arxiv.org/help/api/index.html".to_string()),
+                    use_official_api: true,
+                    require_api_key: false,
+                    results: "XML".to_string(),
+                },
+                shortcut: Some("arx".to_string()),
+                timeout: Some(10),
+                disabled: false,
+                inactive: false,
+                version: Some("1.0.0".to_string()),
+                last_checked: None,
+                using_tor_proxy: false,
+                display_error_messages: true,
+                tokens: Vec::new(),
+                max_page: 10,
+            },
+            client,
+        }
+    }
+
+    /// 提取首个标签内容（支持跨行与带属性的开始标签）
+    fn extract_tag<'a>(block: &'a str, tag: &str) -> Option<&'a str> {
+        Self::extract_all(block, tag).into_iter().next()
+    }
+
+    /// 提取所有同名标签内容
+    fn extract_all<'a>(block: &'a str, tag: &str) -> Vec<&'a str> {
+        let open = format!("<{}", tag);
+        let close = format!("</{}>", tag);
+        let mut values = Vec::new();
+        let mut rest = block;
+
+        while let Some(start) = rest.find(&open) {
+            let after = &rest[start + open.len()..];
+            // 确认是完整标签名（如 <title> 而非 <titles>）
+            if !after.starts_with('>') && !after.starts_with(' ') {
+                rest = after;
+                continue;
+            }
+            let Some(head_end) = after.find('>') else { break };
+            let body = &after[head_end + 1..];
+            let Some(end) = body.find(&close) else { break };
+            values.push(body[..end].trim());
+            rest = &body[end + close.len()..];
+        }
+
+        values
+    }
+
+    /// 提取 PDF 链接
+    fn extract_pdf_link(block: &str) -> Option<String> {
+        block
+            .split("<link")
+            .skip(1)
+            .filter_map(|link| link.split('>').next())
+            .find(|attrs| attrs.contains("title=\"pdf\""))
+            .and_then(|attrs| attrs.split("href=\"").nth(1))
+            .and_then(|href| href.split('"').next())
+            .map(|href| href.to_string())
+    }
+
+    /// 将多行文本合并为单行
+    fn normalize_text(text: &str) -> String {
+        let decoded = html_escape::decode_html_entities(text);
+        decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn parse_atom_result(xml: &str) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        if !xml.contains("<feed") {
+            return Err("Invalid arXiv response: not an Atom feed".into());
+        }
+
+        let mut items = Vec::new();
+        for entry in Self::extract_all(xml, "entry") {
+            let Some(url) = Self::extract_tag(entry, "id") else {
+                continue;
+            };
+            let title = Self::extract_tag(entry, "title").map(Self::normalize_text).unwrap_or_default();
+            let content = Self::extract_tag(entry, "summary").map(Self::normalize_text).unwrap_or_default();
+
+            let authors: Vec<String> = Self::extract_all(entry, "author")
+                .into_iter()
+                .filter_map(|author| Self::extract_tag(author, "name"))
+                .map(Self::normalize_text)
+                .collect();
+
+            let published_date = Self::extract_tag(entry, "published")
+                .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+                .map(|d| d.with_timezone(&chrono::Utc));
+            let year = published_date.map(|d| chrono::Datelike::year(&d) as i64);
+
+            let mut metadata = academic_metadata(Self::extract_tag(entry, "arxiv:doi"), &authors, year, None);
+            if let Some(pdf) = Self::extract_pdf_link(entry) {
+                metadata.insert("pdf_url".to_string(), pdf);
+            }
+            if let Some(id) = url.rsplit("/abs/").next().filter(|id| *id != url) {
+                metadata.insert("arxiv_id".to_string(), id.to_string());
+            }
+            if let Some(journal) = Self::extract_tag(entry, "arxiv:journal_ref") {
+                metadata.insert("venue".to_string(), Self::normalize_text(journal));
+            }
+
+            items.push(SearchResultItem {
+                title,
+                url: url.to_string(),
+                content,
+                display_url: Some(url.to_string()),
+                site_name: Some("arXiv".to_string()),
+                score: 1.0,
+                result_type: ResultType::Academic,
+                thumbnail: None,
+                published_date,
+                template: Some("paper.html".to_string()),
+                metadata,
+            });
+        }
+
+        Ok(items)
+    }
+}
+
+impl Default for ArxivEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchEngine for ArxivEngine {
+    fn info(&self) -> &EngineInfo {
+        &self.info
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
+        <Self as RequestResponseEngine>::search(self, query).await
+    }
+
+    async fn is_available(&self) -> bool {
+        self.client.get("https://export.arxiv.org", None).await.is_ok()
+    }
+}
+
+#[async_trait]
+impl RequestResponseEngine for ArxivEngine {
+    type Response = String;
+
+    fn request(&self, query: &str, params: &mut RequestParams) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let start = (params.pageno.max(1) - 1) * PAGE_SIZE;
+        let query_params = vec![
+            ("search_query", format!("all:{}", query)),
+            ("start", start.to_string()),
+            ("max_results", PAGE_SIZE.to_string()),
+        ];
+
+        let query_string = build_query_string_owned(query_params);
+        params.url = Some(format!("{}?{}", API_URL, query_string));
+        params.method = "GET".to_string();
+
+        Ok(())
+    }
+
+    async fn fetch(&self, params: &RequestParams) -> Result<Self::Response, Box<dyn Error + Send + Sync>> {
+        let url = params.url.as_ref().ok_or("URL not set")?;
+
+        let mut options = RequestOptions::default();
+        for (key, value) in &params.headers {
+            options.headers.push((key.clone(), value.clone()));
+        }
+
+        let response = self.client.get(url, Some(options)).await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        self.client.read_text(response).await.map_err(|e| e.to_string().into())
+    }
+
+    fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        Self::parse_atom_result(&resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title type="html">ArXiv Query</title>
+  <entry>
+    <id>http://arxiv.org/abs/1706.03762v7</id>
+    <published>2017-06-12T17:57:34Z</published>
+    <title>Attention Is All
+      You Need</title>
+    <summary>  The dominant sequence transduction models &amp; more.
+    </summary>
+    <author><name>Ashish Vaswani</name></author>
+    <author><name>Noam Shazeer</name></author>
+    <arxiv:doi xmlns:arxiv="http://arxiv.org/schemas/atom">10.48550/arXiv.1706.03762</arxiv:doi>
+    <link title="pdf" href="http://arxiv.org/pdf/1706.03762v7" rel="related" type="application/pdf"/>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn test_engine_creation() {
+        let engine = ArxivEngine::new();
+        assert_eq!(engine.info().name, "arXiv");
+        assert_eq!(engine.info().engine_type, EngineType::Academic);
+    }
+
+    #[test]
+    fn test_request_pagination() {
+        let engine = ArxivEngine::new();
+        let mut params = RequestParams { pageno: 3, ..Default::default() };
+        engine.request("graph neural", &mut params).unwrap();
+        let url = params.url.unwrap();
+        assert!(url.contains("search_query=all%3Agraph%20neural"));
+        assert!(url.contains("start=20"));
+    }
+
+    #[test]
+    fn test_parse_atom_result() {
+        let items = ArxivEngine::parse_atom_result(SAMPLE).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let item = &items[0];
+        assert_eq!(item.title, "Attention Is All You Need");
+        assert_eq!(item.content, "The dominant sequence transduction models & more.");
+        assert_eq!(item.metadata["authors"], "Ashish Vaswani; Noam Shazeer");
+        assert_eq!(item.metadata["doi"], "10.48550/arXiv.1706.03762");
+        assert_eq!(item.metadata["year"], "2017");
+        assert_eq!(item.metadata["arxiv_id"], "1706.03762v7");
+        assert_eq!(item.metadata["pdf_url"], "http://arxiv.org/pdf/1706.03762v7");
+    }
+
+    #[test]
+    fn test_parse_rejects_non_atom() {
+        assert!(ArxivEngine::parse_atom_result("<html></html>").is_err());
+    }
+}
@@ -498,8 +498,8 @@ impl RequestResponseEngine for BingEngine {
         }
 
         // 获取响应文本
-        let text = response.text().await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
+        let text = self.client.read_text(response).await
+            .map_err(|e| e.to_string())?;
 
         Ok(text)
     }
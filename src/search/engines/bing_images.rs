@@ -265,7 +265,7 @@ impl RequestResponseEngine for BingImagesEngine {
         let response = self.client.get(url, Some(options)).await
             .map_err(|e| format!("Request failed: {}", e))?;
 
-        response.text().await.map_err(|e| format!("Failed to read response: {}", e).into())
+        self.client.read_text(response).await.map_err(|e| e.to_string().into())
     }
 
     fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
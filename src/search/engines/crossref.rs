@@ -0,0 +1,360 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Crossref 学术元数据搜索引擎
+//!
+//! 使用 Crossref REST API 按关键词检索已发表文献（含 DOI 与被引次数）
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::error::Error;
+use serde_json::Value;
+
+use crate::config::engines::EngineSpecificConfig;
+use crate::derive::{
+    EngineCapabilities, EngineInfo, EngineStatus, EngineType,
+    ResultType, SearchEngine, SearchQuery, SearchResult,
+    SearchResultItem, AboutInfo, RequestResponseEngine, RequestParams,
+};
+use crate::net::client::HttpClient;
+use crate::net::types::{NetworkConfig, RequestOptions};
+use super::utils::{academic_metadata, build_query_string_owned};
+
+/// Crossref API 地址
+const API_URL: &str = "https://api.crossref.org/works";
+
+/// 每页结果数
+const PAGE_SIZE: usize = 10;
+
+pub struct CrossrefEngine {
+    info: EngineInfo,
+    client: Arc<HttpClient>,
+    mailto: Option<String>,
+}
+
+impl CrossrefEngine {
+    pub fn new() -> Self {
+        let client = HttpClient::new(NetworkConfig::default())
+            .unwrap_or_else(|_| panic!("Failed to create HTTP client"));
+        Self::with_client(Arc::new(client))
+    }
+
+    pub fn with_client(client: Arc<HttpClient>) -> Self {
+        Self {
+            info: EngineInfo {
+                name: "Crossref".to_string(),
+                engine_type: EngineType::Academic,
+                description: "Crossref - Scholarly publication metadata".to_string(),
+                status: EngineStatus::Active,
+                categories: vec!["academic".to_string(), "science".to_string()],
+                capabilities: EngineCapabilities {
+                    result_types: vec![ResultType::Academic],
+                    supported_params: vec![],
+                    max_page_size: PAGE_SIZE,
+                    supports_pagination: true,
+                    supports_time_range: false,
+                    supports_language_filter: false,
+                    supports_region_filter: false,
+                    supports_safe_search: false,
+                    rate_limit: Some(50),
+                },
+                about: AboutInfo {
+                    website: Some("https://www.crossref.org".to_string()),
+                    wikidata_id: Some("Q5188229".to_string()),
+                    official_api_documentation: Some("https://api.crossref.org/swagger-ui/index.html".to_string()),
+                    use_official_api: true,
+                    require_api_key: false,
+                    results: "JSON".to_string(),
+                },
+                shortcut: Some("cr".to_string()),
+                timeout: Some(10),
+                disabled: false,
+                inactive: false,
+                version: Some("1.0.0".to_string()),
+                last_checked: None,
+                using_tor_proxy: false,
+                display_error_messages: true,
+                tokens: Vec::new(),
+                max_page: 10,
+            },
+            client,
+            mailto: None,
+        }
+    }
+
+    /// 应用引擎特定配置
+    ///
+    /// `custom_params.mailto` 用于进入 Crossref 的 polite pool
+    pub fn with_config(mut self, config: &EngineSpecificConfig) -> Self {
+        self.mailto = config
+            .custom_params
+            .get("mailto")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
+        self
+    }
+
+    /// 取字符串数组的首个元素
+    fn first_str(value: &Value, key: &str) -> Option<String> {
+        value
+            .get(key)
+            .and_then(|v| v.as_array())
+            .and_then(|v| v.first())
+            .and_then(|v| v.as_str())
+            .map(|v| v.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+
+    /// 去除摘要中的 JATS 标签
+    fn strip_jats(text: &str) -> String {
+        let mut plain = String::with_capacity(text.len());
+        let mut in_tag = false;
+        for c in text.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                _ if !in_tag => plain.push(c),
+                _ => {}
+            }
+        }
+        plain.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn parse_json_result(json_str: &str) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        let api_result: Value = serde_json::from_str(json_str)?;
+        let mut items = Vec::new();
+        let Some(works) = api_result
+            .get("message")
+            .and_then(|m| m.get("items"))
+            .and_then(|i| i.as_array())
+        else {
+            return Ok(items);
+        };
+
+        for work in works {
+            let doi = work.get("DOI").and_then(|d| d.as_str());
+            let url = work
+                .get("URL")
+                .and_then(|u| u.as_str())
+                .map(|u| u.to_string())
+                .or_else(|| doi.map(|d| format!("https://doi.org/{}", d)));
+            let (Some(url), Some(title)) = (url, Self::first_str(work, "title")) else {
+                continue;
+            };
+
+            let authors: Vec<String> = work
+                .get("author")
+                .and_then(|a| a.as_array())
+                .map(|authors| {
+                    authors
+                        .iter()
+                        .filter_map(|author| {
+                            let given = author.get("given").and_then(|g| g.as_str()).unwrap_or("");
+                            let family = author.get("family").and_then(|f| f.as_str())
+                                .or_else(|| author.get("name").and_then(|n| n.as_str()))?;
+                            Some(format!("{} {}", given, family).trim().to_string())
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            // issued.date-parts = [[year, month, day]]
+            let date_parts = work
+                .get("issued")
+                .and_then(|i| i.get("date-parts"))
+                .and_then(|d| d.as_array())
+                .and_then(|d| d.first())
+                .and_then(|d| d.as_array());
+            let year = date_parts.and_then(|d| d.first()).and_then(|y| y.as_i64());
+            let published_date = year.and_then(|y| {
+                let month = date_parts.and_then(|d| d.get(1)).and_then(|m| m.as_u64()).unwrap_or(1) as u32;
+                let day = date_parts.and_then(|d| d.get(2)).and_then(|d| d.as_u64()).unwrap_or(1) as u32;
+                chrono::NaiveDate::from_ymd_opt(y as i32, month, day)
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .map(|d| d.and_utc())
+            });
+
+            let citations = work.get("is-referenced-by-count").and_then(|c| c.as_u64());
+            let mut metadata = academic_metadata(doi, &authors, year, citations);
+            if let Some(venue) = Self::first_str(work, "container-title") {
+                metadata.insert("venue".to_string(), venue);
+            }
+            if let Some(publisher) = work.get("publisher").and_then(|p| p.as_str()) {
+                metadata.insert("publisher".to_string(), publisher.to_string());
+            }
+
+            let content = work
+                .get("abstract")
+                .and_then(|a| a.as_str())
+                .map(Self::strip_jats)
+                .unwrap_or_default();
+
+            items.push(SearchResultItem {
+                title,
+                url: url.clone(),
+                content,
+                display_url: Some(url),
+                site_name: Some("Crossref".to_string()),
+                score: 1.0,
+                result_type: ResultType::Academic,
+                thumbnail: None,
+                published_date,
+                template: Some("paper.html".to_string()),
+                metadata,
+            });
+        }
+
+        Ok(items)
+    }
+}
+
+impl Default for CrossrefEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchEngine for CrossrefEngine {
+    fn info(&self) -> &EngineInfo {
+        &self.info
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
+        <Self as RequestResponseEngine>::search(self, query).await
+    }
+
+    async fn is_available(&self) -> bool {
+        self.client.get("https://api.crossref.org", None).await.is_ok()
+    }
+}
+
+#[async_trait]
+impl RequestResponseEngine for CrossrefEngine {
+    type Response = String;
+
+    fn request(&self, query: &str, params: &mut RequestParams) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut query_params = vec![
+            ("query", query.to_string()),
+            ("rows", PAGE_SIZE.to_string()),
+        ];
+        // 深度分页使用游标：第一页以 `*` 开启游标，后续页传回上一页的 next-cursor；
+        // 没有游标的后续页退回偏移分页
+        match params.cursor {
+            Some(ref cursor) => query_params.push(("cursor", cursor.clone())),
+            None if params.pageno <= 1 => query_params.push(("cursor", "*".to_string())),
+            None => query_params.push(("offset", ((params.pageno - 1) * PAGE_SIZE).to_string())),
+        }
+        if let Some(ref mailto) = self.mailto {
+            query_params.push(("mailto", mailto.clone()));
+        }
+
+        let query_string = build_query_string_owned(query_params);
+        params.url = Some(format!("{}?{}", API_URL, query_string));
+        params.method = "GET".to_string();
+
+        Ok(())
+    }
+
+    async fn fetch(&self, params: &RequestParams) -> Result<Self::Response, Box<dyn Error + Send + Sync>> {
+        let url = params.url.as_ref().ok_or("URL not set")?;
+
+        let mut options = RequestOptions::default();
+        for (key, value) in &params.headers {
+            options.headers.push((key.clone(), value.clone()));
+        }
+
+        let response = self.client.get(url, Some(options)).await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        self.client.read_text(response).await.map_err(|e| e.to_string().into())
+    }
+
+    fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        Self::parse_json_result(&resp)
+    }
+
+    fn next_cursor(&self, resp: &Self::Response, _params: &RequestParams) -> Option<String> {
+        let json: Value = serde_json::from_str(resp).ok()?;
+        json.get("message")
+            .and_then(|m| m.get("next-cursor"))
+            .and_then(|c| c.as_str())
+            .map(|c| c.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_creation() {
+        let engine = CrossrefEngine::new();
+        assert_eq!(engine.info().name, "Crossref");
+        assert_eq!(engine.info().engine_type, EngineType::Academic);
+    }
+
+    #[test]
+    fn test_request_with_mailto() {
+        let mut config = EngineSpecificConfig::default();
+        config.custom_params.insert("mailto".to_string(), Value::String("me@example.com".to_string()));
+        let engine = CrossrefEngine::new().with_config(&config);
+
+        let mut params = RequestParams { pageno: 2, ..Default::default() };
+        engine.request("deep learning", &mut params).unwrap();
+        let url = params.url.unwrap();
+        assert!(url.contains("offset=10"));
+        assert!(url.contains("mailto=me%40example.com"));
+    }
+
+    #[test]
+    fn test_cursor_pagination() {
+        let engine = CrossrefEngine::new();
+        let mut params = RequestParams::default();
+        engine.request("deep learning", &mut params).unwrap();
+        assert!(params.url.unwrap().contains("cursor=%2A"));
+
+        let json = r#"{"status":"ok","message":{"next-cursor":"DnF1ZXJ5VGhlbkZldGNo","items":[]}}"#.to_string();
+        let next = engine.next_cursor(&json, &RequestParams::default());
+        assert_eq!(next.as_deref(), Some("DnF1ZXJ5VGhlbkZldGNo"));
+
+        let mut params = RequestParams { pageno: 2, cursor: next, ..Default::default() };
+        engine.request("deep learning", &mut params).unwrap();
+        let url = params.url.unwrap();
+        assert!(url.contains("cursor=DnF1ZXJ5VGhlbkZldGNo"));
+        assert!(!url.contains("offset="));
+    }
+
+    #[test]
+    fn test_parse_work() {
+        let json = r#"{"status":"ok","message":{"items":[{
+            "DOI":"10.1038/nature14539","URL":"https://doi.org/10.1038/nature14539",
+            "title":["Deep learning"],"container-title":["Nature"],"publisher":"Springer",
+            "author":[{"given":"Yann","family":"LeCun"},{"given":"Yoshua","family":"Bengio"}],
+            "issued":{"date-parts":[[2015,5,27]]},"is-referenced-by-count":50000,
+            "abstract":"<jats:p>Deep learning allows <jats:italic>models</jats:italic>.</jats:p>"
+        }]}}"#;
+        let items = CrossrefEngine::parse_json_result(json).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let item = &items[0];
+        assert_eq!(item.title, "Deep learning");
+        assert_eq!(item.content, "Deep learning allows models.");
+        assert_eq!(item.metadata["doi"], "10.1038/nature14539");
+        assert_eq!(item.metadata["authors"], "Yann LeCun; Yoshua Bengio");
+        assert_eq!(item.metadata["year"], "2015");
+        assert_eq!(item.metadata["citations"], "50000");
+        assert_eq!(item.metadata["venue"], "Nature");
+        assert!(item.published_date.is_some());
+    }
+}
@@ -0,0 +1,304 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! eBay 购物搜索引擎
+//!
+//! 解析 eBay 搜索结果页，原始价格写入 `price` 元数据，由标准化阶段解析为数值与货币
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::error::Error;
+
+use crate::config::engines::EngineSpecificConfig;
+use crate::derive::{
+    EngineCapabilities, EngineInfo, EngineStatus, EngineType,
+    ResultType, SearchEngine, SearchQuery, SearchResult,
+    SearchResultItem, AboutInfo, RequestResponseEngine, RequestParams,
+};
+use crate::net::client::HttpClient;
+use crate::net::types::{NetworkConfig, RequestOptions};
+use crate::search::standardization::PRICE_METADATA_KEY;
+use super::utils::build_query_string_owned;
+
+pub struct EbayEngine {
+    info: EngineInfo,
+    client: Arc<HttpClient>,
+    base_url: String,
+}
+
+impl EbayEngine {
+    pub fn new() -> Self {
+        let client = HttpClient::new(NetworkConfig::default())
+            .unwrap_or_else(|_| panic!("Failed to create HTTP client"));
+        Self::with_client(Arc::new(client))
+    }
+
+    pub fn with_client(client: Arc<HttpClient>) -> Self {
+        Self {
+            info: EngineInfo {
+                name: "eBay".to_string(),
+                engine_type: EngineType::Shopping,
+                description: "eBay - Online marketplace".to_string(),
+                status: EngineStatus::Active,
+                categories: vec!["shopping".to_string()],
+                capabilities: EngineCapabilities {
+                    result_types: vec![ResultType::Shopping],
+                    supported_params: vec![],
+                    max_page_size: 60,
+                    supports_pagination: true,
+                    supports_time_range: false,
+                    supports_language_filter: false,
+                    supports_region_filter: true,
+                    supports_safe_search: false,
+                    rate_limit: Some(30),
+                },
+                about: AboutInfo {
+                    website: Some("https://www.ebay.com".to_string()),
+                    wikidata_id: Some("Q58024".to_string()),
+                    official_api_documentation: Some("https://developer.ebay.com/".to_string()),
+                    use_official_api: false,
+                    require_api_key: false,
+                    results: "HTML".to_string(),
+                },
+                shortcut: Some("eb".to_string()),
+                timeout: Some(10),
+                disabled: false,
+                inactive: false,
+                version: Some("1.0.0".to_string()),
+                last_checked: None,
+                using_tor_proxy: false,
+                display_error_messages: true,
+                tokens: Vec::new(),
+                max_page: 10,
+            },
+            client,
+            base_url: "https://www.ebay.com".to_string(),
+        }
+    }
+
+    /// 应用引擎特定配置（`endpoint_url` 可切换站点，如 `https://www.ebay.de`）
+    pub fn with_config(mut self, config: &EngineSpecificConfig) -> Self {
+        if let Some(ref endpoint) = config.endpoint_url {
+            self.base_url = endpoint.trim_end_matches('/').to_string();
+        }
+        self
+    }
+
+    fn parse_html_results(html: &str) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        use scraper::{Html, Selector};
+
+        if html.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let document = Html::parse_document(html);
+        let mut items = Vec::with_capacity(60);
+
+        let result_selector = Selector::parse("li.s-item").expect("valid selector");
+        let link_selector = Selector::parse("a.s-item__link").expect("valid selector");
+        let title_selector = Selector::parse(".s-item__title").expect("valid selector");
+        let price_selector = Selector::parse(".s-item__price").expect("valid selector");
+        let shipping_selector = Selector::parse(".s-item__shipping, .s-item__logisticsCost").expect("valid selector");
+        let condition_selector = Selector::parse(".SECONDARY_INFO").expect("valid selector");
+        let location_selector = Selector::parse(".s-item__location").expect("valid selector");
+        let image_selector = Selector::parse(".s-item__image img").expect("valid selector");
+
+        let text_of = |element: scraper::ElementRef, selector: &Selector| {
+            element
+                .select(selector)
+                .next()
+                .map(|e| e.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|t| !t.is_empty())
+        };
+
+        for result in document.select(&result_selector) {
+            let Some(url) = result
+                .select(&link_selector)
+                .next()
+                .and_then(|a| a.value().attr("href"))
+                .map(|href| href.split('?').next().unwrap_or(href).to_string())
+            else {
+                continue;
+            };
+
+            let Some(title) = text_of(result, &title_selector)
+                .map(|t| t.trim_start_matches("New Listing").trim().to_string())
+            else {
+                continue;
+            };
+            // eBay 在列表开头放一个占位商品
+            if title == "Shop on eBay" {
+                continue;
+            }
+
+            let mut metadata = HashMap::new();
+            if let Some(price) = text_of(result, &price_selector) {
+                metadata.insert(PRICE_METADATA_KEY.to_string(), price);
+            }
+            if let Some(shipping) = text_of(result, &shipping_selector) {
+                metadata.insert("shipping".to_string(), shipping);
+            }
+            if let Some(condition) = text_of(result, &condition_selector) {
+                metadata.insert("condition".to_string(), condition);
+            }
+            if let Some(location) = text_of(result, &location_selector) {
+                metadata.insert("location".to_string(), location.trim_start_matches("from ").to_string());
+            }
+
+            let thumbnail = result
+                .select(&image_selector)
+                .next()
+                .and_then(|img| img.value().attr("data-src").or_else(|| img.value().attr("src")))
+                .map(|src| src.to_string());
+
+            let content = [metadata.get("condition"), metadata.get("shipping")]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" · ");
+
+            items.push(SearchResultItem {
+                title,
+                url: url.clone(),
+                content,
+                display_url: Some(url),
+                site_name: Some("eBay".to_string()),
+                score: 1.0,
+                result_type: ResultType::Shopping,
+                thumbnail,
+                published_date: None,
+                template: Some("products.html".to_string()),
+                metadata,
+            });
+        }
+
+        Ok(items)
+    }
+}
+
+impl Default for EbayEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchEngine for EbayEngine {
+    fn info(&self) -> &EngineInfo {
+        &self.info
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
+        <Self as RequestResponseEngine>::search(self, query).await
+    }
+
+    async fn is_available(&self) -> bool {
+        self.client.get(&self.base_url, None).await.is_ok()
+    }
+}
+
+#[async_trait]
+impl RequestResponseEngine for EbayEngine {
+    type Response = String;
+
+    fn request(&self, query: &str, params: &mut RequestParams) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let query_params = vec![
+            ("_nkw", query.to_string()),
+            ("_sacat", "0".to_string()),
+            ("_pgn", params.pageno.to_string()),
+        ];
+
+        let query_string = build_query_string_owned(query_params);
+        params.url = Some(format!("{}/sch/i.html?{}", self.base_url, query_string));
+        params.method = "GET".to_string();
+
+        Ok(())
+    }
+
+    async fn fetch(&self, params: &RequestParams) -> Result<Self::Response, Box<dyn Error + Send + Sync>> {
+        let url = params.url.as_ref().ok_or("URL not set")?;
+
+        let mut options = RequestOptions::default();
+        for (key, value) in &params.headers {
+            options.headers.push((key.clone(), value.clone()));
+        }
+
+        let response = self.client.get(url, Some(options)).await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        self.client.read_text(response).await.map_err(|e| e.to_string().into())
+    }
+
+    fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        Self::parse_html_results(&resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::standardization::{standardize_item, CURRENCY_METADATA_KEY};
+
+    const SAMPLE: &str = r#"<html><body><ul>
+        <li class="s-item"><a class="s-item__link" href="https://ebay.com/itm/0"><div class="s-item__title">Shop on eBay</div></a></li>
+        <li class="s-item">
+          <div class="s-item__image"><img src="https://i.ebayimg.com/1.jpg"></div>
+          <a class="s-item__link" href="https://www.ebay.com/itm/123?hash=abc">
+            <div class="s-item__title"><span>New Listing</span>Mechanical Keyboard</div>
+          </a>
+          <span class="SECONDARY_INFO">Pre-Owned</span>
+          <span class="s-item__price">$45.99</span>
+          <span class="s-item__shipping">+$5.00 shipping</span>
+          <span class="s-item__location">from United States</span>
+        </li>
+    </ul></body></html>"#;
+
+    #[test]
+    fn test_engine_creation() {
+        let engine = EbayEngine::new();
+        assert_eq!(engine.info().name, "eBay");
+        assert_eq!(engine.info().engine_type, EngineType::Shopping);
+    }
+
+    #[test]
+    fn test_request_with_endpoint() {
+        let config = EngineSpecificConfig {
+            endpoint_url: Some("https://www.ebay.de/".to_string()),
+            ..Default::default()
+        };
+        let engine = EbayEngine::new().with_config(&config);
+        let mut params = RequestParams { pageno: 2, ..Default::default() };
+        engine.request("usb hub", &mut params).unwrap();
+        assert_eq!(params.url.unwrap(), "https://www.ebay.de/sch/i.html?_nkw=usb%20hub&_sacat=0&_pgn=2");
+    }
+
+    #[test]
+    fn test_parse_html_results() {
+        let mut items = EbayEngine::parse_html_results(SAMPLE).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let item = &mut items[0];
+        assert_eq!(item.title, "Mechanical Keyboard");
+        assert_eq!(item.url, "https://www.ebay.com/itm/123");
+        assert_eq!(item.metadata["condition"], "Pre-Owned");
+        assert_eq!(item.metadata["location"], "United States");
+        assert_eq!(item.thumbnail.as_deref(), Some("https://i.ebayimg.com/1.jpg"));
+
+        standardize_item(item);
+        assert_eq!(item.metadata[PRICE_METADATA_KEY], "45.99");
+        assert_eq!(item.metadata[CURRENCY_METADATA_KEY], "USD");
+    }
+}
@@ -0,0 +1,405 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! GitHub 代码搜索引擎
+//!
+//! 使用 GitHub 官方 REST API 搜索仓库；配置令牌后可切换为代码搜索
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::error::Error;
+use serde_json::Value;
+
+use crate::config::engines::EngineSpecificConfig;
+use crate::derive::{
+    EngineCapabilities, EngineInfo, EngineStatus, EngineType,
+    ResultType, SearchEngine, SearchQuery, SearchResult,
+    SearchResultItem, AboutInfo, RequestResponseEngine, RequestParams,
+};
+use crate::net::client::HttpClient;
+use crate::net::types::{NetworkConfig, RequestOptions};
+use super::utils::{build_query_string_owned, resolve_api_token, trim_code_snippet};
+
+/// GitHub API 地址
+const API_BASE_URL: &str = "https://api.github.com";
+
+/// 每页结果数
+const PAGE_SIZE: usize = 10;
+
+/// 代码片段最大行数
+const SNIPPET_MAX_LINES: usize = 12;
+
+/// 搜索类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitHubSearchType {
+    /// 仓库搜索（无需令牌）
+    Repositories,
+    /// 代码搜索（需要令牌）
+    Code,
+}
+
+pub struct GitHubEngine {
+    info: EngineInfo,
+    client: Arc<HttpClient>,
+    token: Option<String>,
+    search_type: GitHubSearchType,
+}
+
+impl GitHubEngine {
+    pub fn new() -> Self {
+        let client = HttpClient::new(NetworkConfig::default())
+            .unwrap_or_else(|_| panic!("Failed to create HTTP client"));
+        Self::with_client(Arc::new(client))
+    }
+
+    pub fn with_client(client: Arc<HttpClient>) -> Self {
+        Self {
+            info: EngineInfo {
+                name: "GitHub".to_string(),
+                engine_type: EngineType::Code,
+                description: "GitHub - Repository and code search".to_string(),
+                status: EngineStatus::Active,
+                categories: vec!["code".to_string(), "it".to_string()],
+                capabilities: EngineCapabilities {
+                    result_types: vec![ResultType::Code],
+                    supported_params: vec![],
+                    max_page_size: PAGE_SIZE,
+                    supports_pagination: true,
+                    supports_time_range: false,
+                    supports_language_filter: false,
+                    supports_region_filter: false,
+                    supports_safe_search: false,
+                    rate_limit: Some(10),
+                },
+                about: AboutInfo {
+                    website: Some("https://github.com".to_string()),
+                    wikidata_id: Some("Q364".to_string()),
+                    official_api_documentation: Some("https://docs.github.com/en/rest/search".to_string()),
+                    use_official_api: true,
+                    require_api_key: false,
+                    results: "JSON".to_string(),
+                },
+                shortcut: Some("gh".to_string()),
+                timeout: Some(10),
+                disabled: false,
+                inactive: false,
+                version: Some("1.0.0".to_string()),
+                last_checked: None,
+                using_tor_proxy: false,
+                display_error_messages: true,
+                tokens: Vec::new(),
+                max_page: 10,
+            },
+            client,
+            token: None,
+            search_type: GitHubSearchType::Repositories,
+        }
+    }
+
+    /// 应用引擎特定配置
+    ///
+    /// 令牌取自 `api_key` 或认证凭据中的 `token`；
+    /// `custom_params.search_type = "code"` 且存在令牌时使用代码搜索
+    pub fn with_config(mut self, config: &EngineSpecificConfig) -> Self {
+        self.token = resolve_api_token(config);
+        let wants_code = config
+            .custom_params
+            .get("search_type")
+            .and_then(|v| v.as_str())
+            .is_some_and(|v| v == "code");
+        self.search_type = if wants_code && self.token.is_some() {
+            GitHubSearchType::Code
+        } else {
+            GitHubSearchType::Repositories
+        };
+        self
+    }
+
+    /// 当前搜索类型
+    pub fn search_type(&self) -> GitHubSearchType {
+        self.search_type
+    }
+
+    fn parse_repositories(api_result: &Value) -> Vec<SearchResultItem> {
+        let mut items = Vec::new();
+        let Some(results) = api_result.get("items").and_then(|i| i.as_array()) else {
+            return items;
+        };
+
+        for repo in results {
+            let Some(url) = repo.get("html_url").and_then(|u| u.as_str()) else {
+                continue;
+            };
+            let title = repo.get("full_name").and_then(|n| n.as_str()).unwrap_or("").to_string();
+            let content = repo.get("description").and_then(|d| d.as_str()).unwrap_or("").to_string();
+
+            let mut metadata = HashMap::new();
+            if let Some(stars) = repo.get("stargazers_count").and_then(|s| s.as_u64()) {
+                metadata.insert("stars".to_string(), stars.to_string());
+            }
+            if let Some(forks) = repo.get("forks_count").and_then(|f| f.as_u64()) {
+                metadata.insert("forks".to_string(), forks.to_string());
+            }
+            if let Some(language) = repo.get("language").and_then(|l| l.as_str()) {
+                metadata.insert("language".to_string(), language.to_string());
+            }
+            if let Some(license) = repo.get("license").and_then(|l| l.get("spdx_id")).and_then(|s| s.as_str()) {
+                metadata.insert("license".to_string(), license.to_string());
+            }
+            if let Some(topics) = repo.get("topics").and_then(|t| t.as_array()) {
+                let topics: Vec<&str> = topics.iter().filter_map(|t| t.as_str()).collect();
+                if !topics.is_empty() {
+                    metadata.insert("topics".to_string(), topics.join(","));
+                }
+            }
+
+            let published_date = repo
+                .get("updated_at")
+                .and_then(|d| d.as_str())
+                .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+                .map(|d| d.with_timezone(&chrono::Utc));
+
+            items.push(SearchResultItem {
+                title,
+                url: url.to_string(),
+                content,
+                display_url: Some(url.to_string()),
+                site_name: Some("GitHub".to_string()),
+                score: 1.0,
+                result_type: ResultType::Code,
+                thumbnail: repo
+                    .get("owner")
+                    .and_then(|o| o.get("avatar_url"))
+                    .and_then(|a| a.as_str())
+                    .map(|s| s.to_string()),
+                published_date,
+                template: Some("code.html".to_string()),
+                metadata,
+            });
+        }
+
+        items
+    }
+
+    fn parse_code(api_result: &Value) -> Vec<SearchResultItem> {
+        let mut items = Vec::new();
+        let Some(results) = api_result.get("items").and_then(|i| i.as_array()) else {
+            return items;
+        };
+
+        for file in results {
+            let Some(url) = file.get("html_url").and_then(|u| u.as_str()) else {
+                continue;
+            };
+            let path = file.get("path").and_then(|p| p.as_str()).unwrap_or("");
+            let repository = file
+                .get("repository")
+                .and_then(|r| r.get("full_name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or("");
+
+            // text-match 片段即匹配到的代码
+            let snippet = file
+                .get("text_matches")
+                .and_then(|m| m.as_array())
+                .and_then(|m| m.first())
+                .and_then(|m| m.get("fragment"))
+                .and_then(|f| f.as_str())
+                .map(|f| trim_code_snippet(f, SNIPPET_MAX_LINES))
+                .unwrap_or_default();
+
+            let mut metadata = HashMap::new();
+            metadata.insert("repository".to_string(), repository.to_string());
+            metadata.insert("path".to_string(), path.to_string());
+            if !snippet.is_empty() {
+                metadata.insert("code_snippet".to_string(), snippet.clone());
+            }
+            if let Some(ext) = path.rsplit_once('.').map(|(_, ext)| ext) {
+                metadata.insert("code_language".to_string(), ext.to_string());
+            }
+
+            items.push(SearchResultItem {
+                title: format!("{}/{}", repository, path),
+                url: url.to_string(),
+                content: snippet,
+                display_url: Some(url.to_string()),
+                site_name: Some("GitHub".to_string()),
+                score: 1.0,
+                result_type: ResultType::Code,
+                thumbnail: None,
+                published_date: None,
+                template: Some("code.html".to_string()),
+                metadata,
+            });
+        }
+
+        items
+    }
+
+    fn parse_json_result(&self, json_str: &str) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        let api_result: Value = serde_json::from_str(json_str)?;
+        if let Some(message) = api_result.get("message").and_then(|m| m.as_str())
+            && api_result.get("items").is_none()
+        {
+            return Err(format!("GitHub API error: {}", message).into());
+        }
+
+        Ok(match self.search_type {
+            GitHubSearchType::Repositories => Self::parse_repositories(&api_result),
+            GitHubSearchType::Code => Self::parse_code(&api_result),
+        })
+    }
+}
+
+impl Default for GitHubEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchEngine for GitHubEngine {
+    fn info(&self) -> &EngineInfo {
+        &self.info
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
+        <Self as RequestResponseEngine>::search(self, query).await
+    }
+
+    async fn is_available(&self) -> bool {
+        self.client.get(API_BASE_URL, None).await.is_ok()
+    }
+}
+
+#[async_trait]
+impl RequestResponseEngine for GitHubEngine {
+    type Response = String;
+
+    fn request(&self, query: &str, params: &mut RequestParams) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (path, accept) = match self.search_type {
+            GitHubSearchType::Repositories => ("search/repositories", "application/vnd.github+json"),
+            GitHubSearchType::Code => ("search/code", "application/vnd.github.text-match+json"),
+        };
+
+        let query_params = vec![
+            ("q", query.to_string()),
+            ("page", params.pageno.to_string()),
+            ("per_page", PAGE_SIZE.to_string()),
+        ];
+        let query_string = build_query_string_owned(query_params);
+
+        params.url = Some(format!("{}/{}?{}", API_BASE_URL, path, query_string));
+        params.method = "GET".to_string();
+        params.headers.insert("Accept".to_string(), accept.to_string());
+        params.headers.insert("X-GitHub-Api-Version".to_string(), "2022-11-28".to_string());
+        if let Some(ref token) = self.token {
+            params.headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+        }
+
+        Ok(())
+    }
+
+    async fn fetch(&self, params: &RequestParams) -> Result<Self::Response, Box<dyn Error + Send + Sync>> {
+        let url = params.url.as_ref().ok_or("URL not set")?;
+
+        let mut options = RequestOptions::default();
+        for (key, value) in &params.headers {
+            options.headers.push((key.clone(), value.clone()));
+        }
+
+        let response = self.client.get(url, Some(options)).await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        self.client.read_text(response).await.map_err(|e| e.to_string().into())
+    }
+
+    fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        self.parse_json_result(&resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code_config(token: Option<&str>) -> EngineSpecificConfig {
+        let mut config = EngineSpecificConfig {
+            api_key: token.map(|t| t.to_string()),
+            ..Default::default()
+        };
+        config.custom_params.insert("search_type".to_string(), Value::String("code".to_string()));
+        config
+    }
+
+    #[test]
+    fn test_engine_creation() {
+        let engine = GitHubEngine::new();
+        assert_eq!(engine.info().name, "GitHub");
+        assert_eq!(engine.info().engine_type, EngineType::Code);
+        assert_eq!(engine.search_type(), GitHubSearchType::Repositories);
+    }
+
+    #[test]
+    fn test_code_search_requires_token() {
+        let engine = GitHubEngine::new().with_config(&code_config(None));
+        assert_eq!(engine.search_type(), GitHubSearchType::Repositories);
+
+        let engine = GitHubEngine::new().with_config(&code_config(Some("ghp_test")));
+        assert_eq!(engine.search_type(), GitHubSearchType::Code);
+
+        let mut params = RequestParams::default();
+        engine.request("tokio spawn", &mut params).unwrap();
+        assert!(params.url.unwrap().starts_with("https://api.github.com/search/code?q=tokio%20spawn"));
+        assert_eq!(params.headers.get("Authorization").unwrap(), "Bearer ghp_test");
+    }
+
+    #[test]
+    fn test_parse_repositories() {
+        let engine = GitHubEngine::new();
+        let json = r#"{"total_count":1,"items":[{
+            "full_name":"rust-lang/rust","html_url":"https://github.com/rust-lang/rust",
+            "description":"Empowering everyone","stargazers_count":100000,"forks_count":12000,
+            "language":"Rust","license":{"spdx_id":"MIT"},"topics":["compiler","rust"],
+            "updated_at":"2025-01-01T00:00:00Z","owner":{"avatar_url":"https://avatars.example/1"}
+        }]}"#;
+        let items = engine.parse_json_result(json).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "rust-lang/rust");
+        assert_eq!(items[0].metadata["stars"], "100000");
+        assert_eq!(items[0].metadata["topics"], "compiler,rust");
+        assert!(items[0].published_date.is_some());
+    }
+
+    #[test]
+    fn test_parse_code() {
+        let engine = GitHubEngine::new().with_config(&code_config(Some("t")));
+        let json = r#"{"items":[{
+            "path":"src/main.rs","html_url":"https://github.com/a/b/blob/main/src/main.rs",
+            "repository":{"full_name":"a/b"},
+            "text_matches":[{"fragment":"    fn main() {\n        run();\n    }"}]
+        }]}"#;
+        let items = engine.parse_json_result(json).unwrap();
+        assert_eq!(items[0].title, "a/b/src/main.rs");
+        assert_eq!(items[0].metadata["code_snippet"], "fn main() {\n    run();\n}");
+        assert_eq!(items[0].metadata["code_language"], "rs");
+    }
+
+    #[test]
+    fn test_parse_api_error() {
+        let engine = GitHubEngine::new();
+        assert!(engine.parse_json_result(r#"{"message":"API rate limit exceeded"}"#).is_err());
+    }
+}
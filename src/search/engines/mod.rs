@@ -32,6 +32,14 @@ pub mod sogou_images;
 pub mod sogou_videos;
 pub mod sogou_wechat;
 pub mod bilibili;
+pub mod github;
+pub mod stackoverflow;
+pub mod arxiv;
+pub mod crossref;
+pub mod semantic_scholar;
+pub mod ebay;
+pub mod nominatim;
+pub mod piratebay;
 
 // 统一导出引擎类型
 pub use bing::BingEngine;
@@ -46,4 +54,12 @@ pub use sogou_images::SogouImagesEngine;
 pub use sogou_videos::SogouVideosEngine;
 pub use sogou_wechat::SogouWeChatEngine;
 pub use bilibili::BilibiliEngine;
+pub use github::GitHubEngine;
+pub use stackoverflow::StackOverflowEngine;
+pub use arxiv::ArxivEngine;
+pub use crossref::CrossrefEngine;
+pub use semantic_scholar::SemanticScholarEngine;
+pub use ebay::EbayEngine;
+pub use nominatim::NominatimEngine;
+pub use piratebay::PirateBayEngine;
 
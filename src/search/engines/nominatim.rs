@@ -0,0 +1,309 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OpenStreetMap Nominatim 地图搜索引擎
+//!
+//! 使用 Nominatim 搜索 API 进行地点查询，结果坐标写入元数据
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::error::Error;
+use serde_json::Value;
+
+use crate::config::engines::EngineSpecificConfig;
+use crate::derive::{
+    EngineCapabilities, EngineInfo, EngineStatus, EngineType,
+    ResultType, SearchEngine, SearchQuery, SearchResult,
+    SearchResultItem, AboutInfo, RequestResponseEngine, RequestParams,
+    BoundingBox, LATITUDE_METADATA_KEY, LONGITUDE_METADATA_KEY,
+};
+use crate::net::client::HttpClient;
+use crate::net::types::{NetworkConfig, RequestOptions};
+use super::utils::build_query_string_owned;
+
+/// 每页结果数
+const PAGE_SIZE: usize = 10;
+
+/// 只给出中心点时用于偏向附近结果的范围半径（度）
+const POINT_BIAS_DELTA: f64 = 0.5;
+
+pub struct NominatimEngine {
+    info: EngineInfo,
+    client: Arc<HttpClient>,
+    base_url: String,
+}
+
+impl NominatimEngine {
+    pub fn new() -> Self {
+        let client = HttpClient::new(NetworkConfig::default())
+            .unwrap_or_else(|_| panic!("Failed to create HTTP client"));
+        Self::with_client(Arc::new(client))
+    }
+
+    pub fn with_client(client: Arc<HttpClient>) -> Self {
+        Self {
+            info: EngineInfo {
+                name: "OpenStreetMap".to_string(),
+                engine_type: EngineType::Map,
+                description: "OpenStreetMap Nominatim - Place search".to_string(),
+                status: EngineStatus::Active,
+                categories: vec!["map".to_string()],
+                capabilities: EngineCapabilities {
+                    result_types: vec![ResultType::Map],
+                    supported_params: vec!["lat".to_string(), "lon".to_string(), "bbox".to_string()],
+                    max_page_size: PAGE_SIZE,
+                    supports_pagination: false,
+                    supports_time_range: false,
+                    supports_language_filter: true,
+                    supports_region_filter: false,
+                    supports_safe_search: false,
+                    // Nominatim 使用政策：每秒最多 1 次请求
+                    rate_limit: Some(60),
+                },
+                about: AboutInfo {
+                    website: Some("https://www.openstreetmap.org".to_string()),
+                    wikidata_id: Some("Q936".to_string()),
+                    official_api_documentation: Some("https://nominatim.org/release-docs/latest/api/Search/".to_string()),
+                    use_official_api: true,
+                    require_api_key: false,
+                    results: "JSON".to_string(),
+                },
+                shortcut: Some("osm".to_string()),
+                timeout: Some(10),
+                disabled: false,
+                inactive: false,
+                version: Some("1.0.0".to_string()),
+                last_checked: None,
+                using_tor_proxy: false,
+                display_error_messages: true,
+                tokens: Vec::new(),
+                max_page: 1,
+            },
+            client,
+            base_url: "https://nominatim.openstreetmap.org".to_string(),
+        }
+    }
+
+    /// 应用引擎特定配置（`endpoint_url` 可指向自建 Nominatim 实例）
+    pub fn with_config(mut self, config: &EngineSpecificConfig) -> Self {
+        if let Some(ref endpoint) = config.endpoint_url {
+            self.base_url = endpoint.trim_end_matches('/').to_string();
+        }
+        self
+    }
+
+    fn parse_json_result(json_str: &str) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        let places: Value = serde_json::from_str(json_str)?;
+        let Some(places) = places.as_array() else {
+            return Err("Invalid Nominatim response: expected an array".into());
+        };
+
+        let mut items = Vec::with_capacity(places.len());
+        for place in places {
+            // Nominatim 以字符串返回坐标
+            let coordinate = |key: &str| place.get(key).and_then(|v| v.as_str()).and_then(|v| v.parse::<f64>().ok());
+            let (Some(lat), Some(lon)) = (coordinate("lat"), coordinate("lon")) else {
+                continue;
+            };
+
+            let display_name = place.get("display_name").and_then(|d| d.as_str()).unwrap_or("");
+            let title = place
+                .get("name")
+                .and_then(|n| n.as_str())
+                .filter(|n| !n.is_empty())
+                .unwrap_or(display_name)
+                .to_string();
+
+            let osm_type = place.get("osm_type").and_then(|t| t.as_str());
+            let osm_id = place.get("osm_id").and_then(|i| i.as_u64());
+            let url = match (osm_type, osm_id) {
+                (Some(kind), Some(id)) => format!("https://www.openstreetmap.org/{}/{}", kind, id),
+                _ => format!("https://www.openstreetmap.org/?mlat={}&mlon={}", lat, lon),
+            };
+
+            let mut metadata = HashMap::new();
+            metadata.insert(LATITUDE_METADATA_KEY.to_string(), lat.to_string());
+            metadata.insert(LONGITUDE_METADATA_KEY.to_string(), lon.to_string());
+            if let Some(kind) = osm_type {
+                metadata.insert("osm_type".to_string(), kind.to_string());
+            }
+            if let Some(id) = osm_id {
+                metadata.insert("osm_id".to_string(), id.to_string());
+            }
+            if let Some(category) = place.get("category").or_else(|| place.get("class")).and_then(|c| c.as_str()) {
+                metadata.insert("place_category".to_string(), category.to_string());
+            }
+            if let Some(kind) = place.get("type").and_then(|t| t.as_str()) {
+                metadata.insert("place_type".to_string(), kind.to_string());
+            }
+            // boundingbox = [min_lat, max_lat, min_lon, max_lon]
+            if let Some(bbox) = place.get("boundingbox").and_then(|b| b.as_array()) {
+                let values: Vec<&str> = bbox.iter().filter_map(|v| v.as_str()).collect();
+                if let [min_lat, max_lat, min_lon, max_lon] = values[..] {
+                    metadata.insert("bbox".to_string(), format!("{},{},{},{}", min_lon, min_lat, max_lon, max_lat));
+                }
+            }
+
+            items.push(SearchResultItem {
+                title,
+                url: url.clone(),
+                content: display_name.to_string(),
+                display_url: Some(url),
+                site_name: Some("OpenStreetMap".to_string()),
+                score: 1.0,
+                result_type: ResultType::Map,
+                thumbnail: None,
+                published_date: None,
+                template: Some("map.html".to_string()),
+                metadata,
+            });
+        }
+
+        Ok(items)
+    }
+}
+
+impl Default for NominatimEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchEngine for NominatimEngine {
+    fn info(&self) -> &EngineInfo {
+        &self.info
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
+        <Self as RequestResponseEngine>::search(self, query).await
+    }
+
+    async fn is_available(&self) -> bool {
+        self.client.get(&format!("{}/status", self.base_url), None).await.is_ok()
+    }
+}
+
+#[async_trait]
+impl RequestResponseEngine for NominatimEngine {
+    type Response = String;
+
+    fn request(&self, query: &str, params: &mut RequestParams) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut query_params = vec![
+            ("q", query.to_string()),
+            ("format", "jsonv2".to_string()),
+            ("limit", PAGE_SIZE.to_string()),
+        ];
+
+        // 指定范围时限定在范围内；只给中心点时仅作为偏好
+        let (viewbox, bounded) = match (params.bbox, params.lat, params.lon) {
+            (Some(bbox), _, _) => (Some(bbox), true),
+            (None, Some(lat), Some(lon)) => (Some(BoundingBox::around(lat, lon, POINT_BIAS_DELTA)), false),
+            _ => (None, false),
+        };
+        if let Some(bbox) = viewbox {
+            // Nominatim viewbox = <x1>,<y1>,<x2>,<y2>
+            query_params.push((
+                "viewbox",
+                format!("{},{},{},{}", bbox.min_lon, bbox.max_lat, bbox.max_lon, bbox.min_lat),
+            ));
+            if bounded {
+                query_params.push(("bounded", "1".to_string()));
+            }
+        }
+        if let Some(ref language) = params.language {
+            query_params.push(("accept-language", language.clone()));
+        }
+
+        let query_string = build_query_string_owned(query_params);
+        params.url = Some(format!("{}/search?{}", self.base_url, query_string));
+        params.method = "GET".to_string();
+
+        Ok(())
+    }
+
+    async fn fetch(&self, params: &RequestParams) -> Result<Self::Response, Box<dyn Error + Send + Sync>> {
+        let url = params.url.as_ref().ok_or("URL not set")?;
+
+        let mut options = RequestOptions::default();
+        for (key, value) in &params.headers {
+            options.headers.push((key.clone(), value.clone()));
+        }
+
+        let response = self.client.get(url, Some(options)).await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        self.client.read_text(response).await.map_err(|e| e.to_string().into())
+    }
+
+    fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        Self::parse_json_result(&resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_with_bbox() {
+        let engine = NominatimEngine::new();
+        let mut params = RequestParams {
+            bbox: Some(BoundingBox::parse("2.2,48.8,2.4,48.9").unwrap()),
+            ..Default::default()
+        };
+        engine.request("cafe", &mut params).unwrap();
+
+        let url = params.url.unwrap();
+        assert!(url.starts_with("https://nominatim.openstreetmap.org/search?q=cafe"));
+        assert!(url.contains("viewbox=2.2%2C48.9%2C2.4%2C48.8"));
+        assert!(url.contains("bounded=1"));
+    }
+
+    #[test]
+    fn test_request_with_point_bias() {
+        let engine = NominatimEngine::new();
+        let mut params = RequestParams {
+            lat: Some(10.0),
+            lon: Some(20.0),
+            ..Default::default()
+        };
+        engine.request("museum", &mut params).unwrap();
+
+        let url = params.url.unwrap();
+        assert!(url.contains("viewbox=19.5%2C10.5%2C20.5%2C9.5"));
+        assert!(!url.contains("bounded"));
+    }
+
+    #[test]
+    fn test_parse_json_result() {
+        let json = r#"[{
+            "place_id":1,"osm_type":"way","osm_id":5013364,"lat":"48.8582602","lon":"2.2944990",
+            "category":"tourism","type":"attraction","name":"Tour Eiffel",
+            "display_name":"Tour Eiffel, Paris, France",
+            "boundingbox":["48.8574753","48.8590465","2.2933084","2.2956897"]
+        }]"#;
+        let items = NominatimEngine::parse_json_result(json).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let item = &items[0];
+        assert_eq!(item.title, "Tour Eiffel");
+        assert_eq!(item.url, "https://www.openstreetmap.org/way/5013364");
+        assert_eq!(item.result_type, ResultType::Map);
+        assert_eq!(item.metadata[LATITUDE_METADATA_KEY], "48.8582602");
+        assert_eq!(item.metadata[LONGITUDE_METADATA_KEY], "2.294499");
+        assert_eq!(item.metadata["bbox"], "2.2933084,48.8574753,2.2956897,48.8590465");
+    }
+}
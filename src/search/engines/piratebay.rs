@@ -0,0 +1,273 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The Pirate Bay 种子搜索引擎
+//!
+//! 使用 apibay JSON 接口，结果附带磁力链接、infohash 与做种数。
+//! 属于文件分类，仅在配置 `engines.enable_files_category` 后可用
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::error::Error;
+use serde_json::Value;
+
+use crate::config::engines::EngineSpecificConfig;
+use crate::derive::{
+    EngineCapabilities, EngineInfo, EngineStatus, EngineType,
+    ResultType, SearchEngine, SearchQuery, SearchResult,
+    SearchResultItem, AboutInfo, RequestResponseEngine, RequestParams,
+    MagnetLink, INFOHASH_METADATA_KEY, LEECHERS_METADATA_KEY, MAGNET_METADATA_KEY, SEEDERS_METADATA_KEY,
+};
+use crate::net::client::HttpClient;
+use crate::net::types::{NetworkConfig, RequestOptions};
+use super::utils::build_query_string_owned;
+
+/// 写入磁力链接的公共 Tracker
+const TRACKERS: &[&str] = &[
+    "udp://tracker.opentrackr.org:1337/announce",
+    "udp://open.stealth.si:80/announce",
+    "udp://tracker.torrent.eu.org:451/announce",
+    "udp://exodus.desync.com:6969/announce",
+];
+
+/// 无结果时 apibay 返回的占位 infohash
+const EMPTY_INFOHASH: &str = "0000000000000000000000000000000000000000";
+
+pub struct PirateBayEngine {
+    info: EngineInfo,
+    client: Arc<HttpClient>,
+    api_url: String,
+}
+
+impl PirateBayEngine {
+    pub fn new() -> Self {
+        let client = HttpClient::new(NetworkConfig::default())
+            .unwrap_or_else(|_| panic!("Failed to create HTTP client"));
+        Self::with_client(Arc::new(client))
+    }
+
+    pub fn with_client(client: Arc<HttpClient>) -> Self {
+        Self {
+            info: EngineInfo {
+                name: "The Pirate Bay".to_string(),
+                engine_type: EngineType::Files,
+                description: "The Pirate Bay - Torrent search".to_string(),
+                status: EngineStatus::Active,
+                categories: vec!["files".to_string()],
+                capabilities: EngineCapabilities {
+                    result_types: vec![ResultType::Torrent],
+                    supported_params: vec![],
+                    max_page_size: 100,
+                    supports_pagination: false,
+                    supports_time_range: false,
+                    supports_language_filter: false,
+                    supports_region_filter: false,
+                    supports_safe_search: false,
+                    rate_limit: Some(30),
+                },
+                about: AboutInfo {
+                    website: Some("https://thepiratebay.org".to_string()),
+                    wikidata_id: Some("Q22663".to_string()),
+                    official_api_documentation: None,
+                    use_official_api: false,
+                    require_api_key: false,
+                    results: "JSON".to_string(),
+                },
+                shortcut: Some("tpb".to_string()),
+                timeout: Some(10),
+                disabled: false,
+                inactive: false,
+                version: Some("1.0.0".to_string()),
+                last_checked: None,
+                using_tor_proxy: false,
+                display_error_messages: true,
+                tokens: Vec::new(),
+                max_page: 1,
+            },
+            client,
+            api_url: "https://apibay.org".to_string(),
+        }
+    }
+
+    /// 应用引擎特定配置（`endpoint_url` 可指向 apibay 镜像）
+    pub fn with_config(mut self, config: &EngineSpecificConfig) -> Self {
+        if let Some(ref endpoint) = config.endpoint_url {
+            self.api_url = endpoint.trim_end_matches('/').to_string();
+        }
+        self
+    }
+
+    /// apibay 的数值字段以字符串返回
+    fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+        value.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty())
+    }
+
+    fn parse_json_result(json_str: &str) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        let torrents: Value = serde_json::from_str(json_str)?;
+        let Some(torrents) = torrents.as_array() else {
+            return Err("Invalid apibay response: expected an array".into());
+        };
+
+        let mut items = Vec::with_capacity(torrents.len());
+        for torrent in torrents {
+            let (Some(id), Some(name), Some(info_hash)) = (
+                Self::str_field(torrent, "id"),
+                Self::str_field(torrent, "name"),
+                Self::str_field(torrent, "info_hash"),
+            ) else {
+                continue;
+            };
+            if info_hash == EMPTY_INFOHASH {
+                continue;
+            }
+            let Some(magnet) = MagnetLink::new(info_hash, Some(name), TRACKERS) else {
+                continue;
+            };
+
+            let seeders = Self::str_field(torrent, "seeders").unwrap_or("0");
+            let leechers = Self::str_field(torrent, "leechers").unwrap_or("0");
+            let mut metadata = HashMap::new();
+            metadata.insert(MAGNET_METADATA_KEY.to_string(), magnet.to_uri());
+            metadata.insert(INFOHASH_METADATA_KEY.to_string(), magnet.infohash.clone());
+            metadata.insert(SEEDERS_METADATA_KEY.to_string(), seeders.to_string());
+            metadata.insert(LEECHERS_METADATA_KEY.to_string(), leechers.to_string());
+            if let Some(size) = Self::str_field(torrent, "size") {
+                metadata.insert("size".to_string(), size.to_string());
+            }
+            if let Some(files) = Self::str_field(torrent, "num_files") {
+                metadata.insert("num_files".to_string(), files.to_string());
+            }
+
+            let published_date = Self::str_field(torrent, "added")
+                .and_then(|t| t.parse::<i64>().ok())
+                .and_then(|t| chrono::DateTime::from_timestamp(t, 0));
+
+            let url = format!("https://thepiratebay.org/description.php?id={}", id);
+            items.push(SearchResultItem {
+                title: name.to_string(),
+                url: url.clone(),
+                content: format!("Seeders: {} · Leechers: {}", seeders, leechers),
+                display_url: Some(url),
+                site_name: Some("The Pirate Bay".to_string()),
+                score: 1.0,
+                result_type: ResultType::Torrent,
+                thumbnail: None,
+                published_date,
+                template: Some("torrent.html".to_string()),
+                metadata,
+            });
+        }
+
+        Ok(items)
+    }
+}
+
+impl Default for PirateBayEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchEngine for PirateBayEngine {
+    fn info(&self) -> &EngineInfo {
+        &self.info
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
+        <Self as RequestResponseEngine>::search(self, query).await
+    }
+
+    async fn is_available(&self) -> bool {
+        self.client.get(&self.api_url, None).await.is_ok()
+    }
+}
+
+#[async_trait]
+impl RequestResponseEngine for PirateBayEngine {
+    type Response = String;
+
+    fn request(&self, query: &str, params: &mut RequestParams) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let query_params = vec![
+            ("q", query.to_string()),
+            ("cat", "0".to_string()),
+        ];
+
+        let query_string = build_query_string_owned(query_params);
+        params.url = Some(format!("{}/q.php?{}", self.api_url, query_string));
+        params.method = "GET".to_string();
+
+        Ok(())
+    }
+
+    async fn fetch(&self, params: &RequestParams) -> Result<Self::Response, Box<dyn Error + Send + Sync>> {
+        let url = params.url.as_ref().ok_or("URL not set")?;
+
+        let mut options = RequestOptions::default();
+        for (key, value) in &params.headers {
+            options.headers.push((key.clone(), value.clone()));
+        }
+
+        let response = self.client.get(url, Some(options)).await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        self.client.read_text(response).await.map_err(|e| e.to_string().into())
+    }
+
+    fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        Self::parse_json_result(&resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_creation() {
+        let engine = PirateBayEngine::new();
+        assert_eq!(engine.info().engine_type, EngineType::Files);
+
+        let mut params = RequestParams::default();
+        engine.request("ubuntu iso", &mut params).unwrap();
+        assert_eq!(params.url.unwrap(), "https://apibay.org/q.php?q=ubuntu%20iso&cat=0");
+    }
+
+    #[test]
+    fn test_parse_json_result() {
+        let json = r#"[{"id":"123","name":"ubuntu-24.04-desktop-amd64.iso",
+            "info_hash":"3B245504CF5F11BBDBE1201CEA6A6BF45AEE1BC0","leechers":"12","seeders":"345",
+            "num_files":"1","size":"6114656256","added":"1714000000","category":"303"}]"#;
+        let items = PirateBayEngine::parse_json_result(json).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let item = &items[0];
+        assert_eq!(item.result_type, ResultType::Torrent);
+        assert_eq!(item.metadata[INFOHASH_METADATA_KEY], "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0");
+        assert_eq!(item.metadata[SEEDERS_METADATA_KEY], "345");
+
+        let magnet = MagnetLink::parse(&item.metadata[MAGNET_METADATA_KEY]).unwrap();
+        assert_eq!(magnet.infohash, item.metadata[INFOHASH_METADATA_KEY]);
+        assert_eq!(magnet.name.as_deref(), Some("ubuntu-24.04-desktop-amd64.iso"));
+        assert_eq!(magnet.trackers.len(), TRACKERS.len());
+    }
+
+    #[test]
+    fn test_parse_no_results() {
+        let json = r#"[{"id":"0","name":"No results returned","info_hash":"0000000000000000000000000000000000000000",
+            "leechers":"0","seeders":"0","num_files":"0","size":"0","added":"0"}]"#;
+        assert!(PirateBayEngine::parse_json_result(json).unwrap().is_empty());
+    }
+}
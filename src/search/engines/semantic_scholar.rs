@@ -0,0 +1,304 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Semantic Scholar 论文搜索引擎
+//!
+//! 使用 Semantic Scholar Graph API 搜索论文，可选配置 API key 提高配额
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::error::Error;
+use serde_json::Value;
+
+use crate::config::engines::EngineSpecificConfig;
+use crate::derive::{
+    EngineCapabilities, EngineInfo, EngineStatus, EngineType,
+    ResultType, SearchEngine, SearchQuery, SearchResult,
+    SearchResultItem, AboutInfo, RequestResponseEngine, RequestParams,
+};
+use crate::net::client::HttpClient;
+use crate::net::types::{NetworkConfig, RequestOptions};
+use super::utils::{academic_metadata, build_query_string_owned, resolve_api_token};
+
+/// Semantic Scholar 论文搜索地址
+const API_URL: &str = "https://api.semanticscholar.org/graph/v1/paper/search";
+
+/// 请求的字段
+const FIELDS: &str = "title,url,abstract,year,authors,citationCount,externalIds,venue,publicationDate";
+
+/// 每页结果数
+const PAGE_SIZE: usize = 10;
+
+pub struct SemanticScholarEngine {
+    info: EngineInfo,
+    client: Arc<HttpClient>,
+    api_key: Option<String>,
+}
+
+impl SemanticScholarEngine {
+    pub fn new() -> Self {
+        let client = HttpClient::new(NetworkConfig::default())
+            .unwrap_or_else(|_| panic!("Failed to create HTTP client"));
+        Self::with_client(Arc::new(client))
+    }
+
+    pub fn with_client(client: Arc<HttpClient>) -> Self {
+        Self {
+            info: EngineInfo {
+                name: "Semantic Scholar".to_string(),
+                engine_type: EngineType::Academic,
+                description: "Semantic Scholar - AI-powered research paper search".to_string(),
+                status: EngineStatus::Active,
+                categories: vec!["academic".to_string(), "science".to_string()],
+                capabilities: EngineCapabilities {
+                    result_types: vec![ResultType::Academic],
+                    supported_params: vec![],
+                    max_page_size: PAGE_SIZE,
+                    supports_pagination: true,
+                    supports_time_range: false,
+                    supports_language_filter: false,
+                    supports_region_filter: false,
+                    supports_safe_search: false,
+                    rate_limit: Some(100),
+                },
+                about: AboutInfo {
+                    website: Some("https://www.semanticscholar.org".to_string()),
+                    wikidata_id: Some("Q22908627".to_string()),
+                    official_api_documentation: Some("https://api.semanticscholar.org/api-docs/graph".to_string()),
+                    use_official_api: true,
+                    require_api_key: false,
+                    results: "JSON".to_string(),
+                },
+                shortcut: Some("se".to_string()),
+                timeout: Some(10),
+                disabled: false,
+                inactive: false,
+                version: Some("1.0.0".to_string()),
+                last_checked: None,
+                using_tor_proxy: false,
+                display_error_messages: true,
+                tokens: Vec::new(),
+                max_page: 10,
+            },
+            client,
+            api_key: None,
+        }
+    }
+
+    /// 应用引擎特定配置（API key 通过 `x-api-key` 头发送）
+    pub fn with_config(mut self, config: &EngineSpecificConfig) -> Self {
+        self.api_key = resolve_api_token(config);
+        self
+    }
+
+    fn parse_json_result(json_str: &str) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        let api_result: Value = serde_json::from_str(json_str)?;
+        if let Some(message) = api_result.get("message").or_else(|| api_result.get("error")).and_then(|m| m.as_str())
+            && api_result.get("data").is_none()
+        {
+            return Err(format!("Semantic Scholar API error: {}", message).into());
+        }
+
+        let mut items = Vec::new();
+        let Some(papers) = api_result.get("data").and_then(|d| d.as_array()) else {
+            return Ok(items);
+        };
+
+        for paper in papers {
+            let (Some(url), Some(title)) = (
+                paper.get("url").and_then(|u| u.as_str()),
+                paper.get("title").and_then(|t| t.as_str()),
+            ) else {
+                continue;
+            };
+
+            let authors: Vec<String> = paper
+                .get("authors")
+                .and_then(|a| a.as_array())
+                .map(|authors| {
+                    authors
+                        .iter()
+                        .filter_map(|a| a.get("name").and_then(|n| n.as_str()))
+                        .map(|n| n.to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let external_ids = paper.get("externalIds");
+            let doi = external_ids.and_then(|ids| ids.get("DOI")).and_then(|d| d.as_str());
+            let year = paper.get("year").and_then(|y| y.as_i64());
+            let citations = paper.get("citationCount").and_then(|c| c.as_u64());
+
+            let mut metadata = academic_metadata(doi, &authors, year, citations);
+            if let Some(arxiv_id) = external_ids.and_then(|ids| ids.get("ArXiv")).and_then(|a| a.as_str()) {
+                metadata.insert("arxiv_id".to_string(), arxiv_id.to_string());
+            }
+            if let Some(venue) = paper.get("venue").and_then(|v| v.as_str()).filter(|v| !v.is_empty()) {
+                metadata.insert("venue".to_string(), venue.to_string());
+            }
+
+            let published_date = paper
+                .get("publicationDate")
+                .and_then(|d| d.as_str())
+                .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc());
+
+            items.push(SearchResultItem {
+                title: title.to_string(),
+                url: url.to_string(),
+                content: paper.get("abstract").and_then(|a| a.as_str()).unwrap_or("").to_string(),
+                display_url: Some(url.to_string()),
+                site_name: Some("Semantic Scholar".to_string()),
+                score: 1.0,
+                result_type: ResultType::Academic,
+                thumbnail: None,
+                published_date,
+                template: Some("paper.html".to_string()),
+                metadata,
+            });
+        }
+
+        Ok(items)
+    }
+}
+
+impl Default for SemanticScholarEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchEngine for SemanticScholarEngine {
+    fn info(&self) -> &EngineInfo {
+        &self.info
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
+        <Self as RequestResponseEngine>::search(self, query).await
+    }
+
+    async fn is_available(&self) -> bool {
+        self.client.get("https://api.semanticscholar.org", None).await.is_ok()
+    }
+}
+
+#[async_trait]
+impl RequestResponseEngine for SemanticScholarEngine {
+    type Response = String;
+
+    fn request(&self, query: &str, params: &mut RequestParams) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // 续页令牌为上一页响应中的 `next` 偏移
+        let offset = params
+            .cursor
+            .as_deref()
+            .and_then(|cursor| cursor.parse::<usize>().ok())
+            .unwrap_or((params.pageno.max(1) - 1) * PAGE_SIZE);
+        let query_params = vec![
+            ("query", query.to_string()),
+            ("offset", offset.to_string()),
+            ("limit", PAGE_SIZE.to_string()),
+            ("fields", FIELDS.to_string()),
+        ];
+
+        let query_string = build_query_string_owned(query_params);
+        params.url = Some(format!("{}?{}", API_URL, query_string));
+        params.method = "GET".to_string();
+        if let Some(ref key) = self.api_key {
+            params.headers.insert("x-api-key".to_string(), key.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn fetch(&self, params: &RequestParams) -> Result<Self::Response, Box<dyn Error + Send + Sync>> {
+        let url = params.url.as_ref().ok_or("URL not set")?;
+
+        let mut options = RequestOptions::default();
+        for (key, value) in &params.headers {
+            options.headers.push((key.clone(), value.clone()));
+        }
+
+        let response = self.client.get(url, Some(options)).await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        self.client.read_text(response).await.map_err(|e| e.to_string().into())
+    }
+
+    fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        Self::parse_json_result(&resp)
+    }
+
+    fn next_cursor(&self, resp: &Self::Response, _params: &RequestParams) -> Option<String> {
+        let json: Value = serde_json::from_str(resp).ok()?;
+        json.get("next").and_then(|n| n.as_u64()).map(|n| n.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_with_api_key() {
+        let config = EngineSpecificConfig {
+            api_key: Some("s2-key".to_string()),
+            ..Default::default()
+        };
+        let engine = SemanticScholarEngine::new().with_config(&config);
+        let mut params = RequestParams::default();
+        engine.request("bert", &mut params).unwrap();
+
+        assert!(params.url.unwrap().starts_with(API_URL));
+        assert_eq!(params.headers.get("x-api-key").unwrap(), "s2-key");
+    }
+
+    #[test]
+    fn test_cursor_pagination() {
+        let engine = SemanticScholarEngine::new();
+        let next = engine.next_cursor(&r#"{"total":100,"offset":0,"next":10,"data":[]}"#.to_string(), &RequestParams::default());
+        assert_eq!(next.as_deref(), Some("10"));
+
+        let mut params = RequestParams { cursor: next, ..Default::default() };
+        engine.request("bert", &mut params).unwrap();
+        assert!(params.url.unwrap().contains("offset=10"));
+    }
+
+    #[test]
+    fn test_parse_paper() {
+        let json = r#"{"total":1,"offset":0,"data":[{
+            "paperId":"df2b","url":"https://www.semanticscholar.org/paper/df2b",
+            "title":"BERT: Pre-training of Deep Bidirectional Transformers","abstract":"We introduce BERT.",
+            "year":2019,"citationCount":80000,"venue":"NAACL","publicationDate":"2019-06-01",
+            "authors":[{"authorId":"1","name":"Jacob Devlin"}],
+            "externalIds":{"DOI":"10.18653/v1/N19-1423","ArXiv":"1810.04805"}
+        }]}"#;
+        let items = SemanticScholarEngine::parse_json_result(json).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let item = &items[0];
+        assert_eq!(item.metadata["doi"], "10.18653/v1/N19-1423");
+        assert_eq!(item.metadata["citations"], "80000");
+        assert_eq!(item.metadata["arxiv_id"], "1810.04805");
+        assert_eq!(item.metadata["venue"], "NAACL");
+        assert!(item.published_date.is_some());
+    }
+
+    #[test]
+    fn test_parse_api_error() {
+        let json = r#"{"message":"Too Many Requests"}"#;
+        assert!(SemanticScholarEngine::parse_json_result(json).is_err());
+    }
+}
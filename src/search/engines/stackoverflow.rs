@@ -0,0 +1,321 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! StackOverflow 问答搜索引擎
+//!
+//! 使用 Stack Exchange 官方 API 搜索问题，提取问题正文中的首个代码块
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::error::Error;
+use serde_json::Value;
+
+use crate::config::engines::EngineSpecificConfig;
+use crate::derive::{
+    EngineCapabilities, EngineInfo, EngineStatus, EngineType,
+    ResultType, SearchEngine, SearchQuery, SearchResult,
+    SearchResultItem, AboutInfo, RequestResponseEngine, RequestParams,
+};
+use crate::net::client::HttpClient;
+use crate::net::types::{NetworkConfig, RequestOptions};
+use super::utils::{build_query_string_owned, collect_text, resolve_api_token, trim_code_snippet};
+
+/// Stack Exchange API 搜索地址
+const API_SEARCH_URL: &str = "https://api.stackexchange.com/2.3/search/advanced";
+
+/// 每页结果数
+const PAGE_SIZE: usize = 10;
+
+/// 代码片段最大行数
+const SNIPPET_MAX_LINES: usize = 12;
+
+/// 摘要最大字符数（按字素簇计，含省略号）
+const CONTENT_MAX_CHARS: usize = 300;
+
+pub struct StackOverflowEngine {
+    info: EngineInfo,
+    client: Arc<HttpClient>,
+    api_key: Option<String>,
+    site: String,
+}
+
+impl StackOverflowEngine {
+    pub fn new() -> Self {
+        let client = HttpClient::new(NetworkConfig::default())
+            .unwrap_or_else(|_| panic!("Failed to create HTTP client"));
+        Self::with_client(Arc::new(client))
+    }
+
+    pub fn with_client(client: Arc<HttpClient>) -> Self {
+        Self {
+            info: EngineInfo {
+                name: "StackOverflow".to_string(),
+                engine_type: EngineType::Code,
+                description: "StackOverflow - Programming questions and answers".to_string(),
+                status: EngineStatus::Active,
+                categories: vec!["code".to_string(), "it".to_string(), "q&a".to_string()],
+                capabilities: EngineCapabilities {
+                    result_types: vec![ResultType::Code],
+                    supported_params: vec![],
+                    max_page_size: PAGE_SIZE,
+                    supports_pagination: true,
+                    supports_time_range: false,
+                    supports_language_filter: false,
+                    supports_region_filter: false,
+                    supports_safe_search: false,
+                    rate_limit: Some(30),
+                },
+                about: AboutInfo {
+                    website: Some("https://stackoverflow.com".to_string()),
+                    wikidata_id: Some("Q549037".to_string()),
+                    official_api_documentation: Some("https://api.stackexchange.com/docs".to_string()),
+                    use_official_api: true,
+                    require_api_key: false,
+                    results: "JSON".to_string(),
+                },
+                shortcut: Some("so".to_string()),
+                timeout: Some(10),
+                disabled: false,
+                inactive: false,
+                version: Some("1.0.0".to_string()),
+                last_checked: None,
+                using_tor_proxy: false,
+                display_error_messages: true,
+                tokens: Vec::new(),
+                max_page: 10,
+            },
+            client,
+            api_key: None,
+            site: "stackoverflow".to_string(),
+        }
+    }
+
+    /// 应用引擎特定配置
+    ///
+    /// API key 可提高配额；`custom_params.site` 可切换到其他 Stack Exchange 站点
+    pub fn with_config(mut self, config: &EngineSpecificConfig) -> Self {
+        self.api_key = resolve_api_token(config);
+        if let Some(site) = config.custom_params.get("site").and_then(|v| v.as_str()) {
+            self.site = site.to_string();
+        }
+        self
+    }
+
+    /// 从问题正文 HTML 中提取纯文本摘要与首个代码块
+    fn split_body(body: &str) -> (String, Option<String>) {
+        use scraper::{Html, Selector};
+
+        let fragment = Html::parse_fragment(body);
+        let code_selector = Selector::parse("pre code, pre").expect("valid selector");
+        let text_selector = Selector::parse("p").expect("valid selector");
+
+        let snippet = fragment
+            .select(&code_selector)
+            .next()
+            .map(|code| code.text().collect::<String>())
+            .map(|code| trim_code_snippet(&code, SNIPPET_MAX_LINES))
+            .filter(|code| !code.is_empty());
+
+        let text = collect_text(fragment.select(&text_selector).flat_map(|p| p.text()));
+        let content = crate::text::truncate(&text, CONTENT_MAX_CHARS).into_owned();
+
+        (content, snippet)
+    }
+
+    fn parse_json_result(json_str: &str) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        let api_result: Value = serde_json::from_str(json_str)?;
+        if let Some(message) = api_result.get("error_message").and_then(|m| m.as_str()) {
+            return Err(format!("Stack Exchange API error: {}", message).into());
+        }
+
+        let mut items = Vec::new();
+        let Some(questions) = api_result.get("items").and_then(|i| i.as_array()) else {
+            return Ok(items);
+        };
+
+        for question in questions {
+            let Some(url) = question.get("link").and_then(|l| l.as_str()) else {
+                continue;
+            };
+            // 标题经过 HTML 转义
+            let title = question
+                .get("title")
+                .and_then(|t| t.as_str())
+                .map(|t| html_escape::decode_html_entities(t).to_string())
+                .unwrap_or_default();
+
+            let body = question.get("body").and_then(|b| b.as_str()).unwrap_or("");
+            let (content, snippet) = Self::split_body(body);
+
+            let mut metadata = HashMap::new();
+            if let Some(snippet) = snippet {
+                metadata.insert("code_snippet".to_string(), snippet);
+            }
+            if let Some(score) = question.get("score").and_then(|s| s.as_i64()) {
+                metadata.insert("votes".to_string(), score.to_string());
+            }
+            if let Some(answers) = question.get("answer_count").and_then(|a| a.as_u64()) {
+                metadata.insert("answer_count".to_string(), answers.to_string());
+            }
+            if let Some(answered) = question.get("is_answered").and_then(|a| a.as_bool()) {
+                metadata.insert("is_answered".to_string(), answered.to_string());
+            }
+            if let Some(tags) = question.get("tags").and_then(|t| t.as_array()) {
+                let tags: Vec<&str> = tags.iter().filter_map(|t| t.as_str()).collect();
+                if !tags.is_empty() {
+                    metadata.insert("tags".to_string(), tags.join(","));
+                }
+            }
+
+            let published_date = question
+                .get("creation_date")
+                .and_then(|d| d.as_i64())
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0));
+
+            items.push(SearchResultItem {
+                title,
+                url: url.to_string(),
+                content,
+                display_url: Some(url.to_string()),
+                site_name: Some("StackOverflow".to_string()),
+                score: 1.0,
+                result_type: ResultType::Code,
+                thumbnail: None,
+                published_date,
+                template: Some("code.html".to_string()),
+                metadata,
+            });
+        }
+
+        Ok(items)
+    }
+}
+
+impl Default for StackOverflowEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchEngine for StackOverflowEngine {
+    fn info(&self) -> &EngineInfo {
+        &self.info
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
+        <Self as RequestResponseEngine>::search(self, query).await
+    }
+
+    async fn is_available(&self) -> bool {
+        self.client.get("https://api.stackexchange.com", None).await.is_ok()
+    }
+}
+
+#[async_trait]
+impl RequestResponseEngine for StackOverflowEngine {
+    type Response = String;
+
+    fn request(&self, query: &str, params: &mut RequestParams) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut query_params = vec![
+            ("q", query.to_string()),
+            ("order", "desc".to_string()),
+            ("sort", "relevance".to_string()),
+            ("site", self.site.clone()),
+            ("page", params.pageno.to_string()),
+            ("pagesize", PAGE_SIZE.to_string()),
+            ("filter", "withbody".to_string()),
+        ];
+        if let Some(ref key) = self.api_key {
+            query_params.push(("key", key.clone()));
+        }
+
+        let query_string = build_query_string_owned(query_params);
+        params.url = Some(format!("{}?{}", API_SEARCH_URL, query_string));
+        params.method = "GET".to_string();
+
+        Ok(())
+    }
+
+    async fn fetch(&self, params: &RequestParams) -> Result<Self::Response, Box<dyn Error + Send + Sync>> {
+        let url = params.url.as_ref().ok_or("URL not set")?;
+
+        let mut options = RequestOptions::default();
+        for (key, value) in &params.headers {
+            options.headers.push((key.clone(), value.clone()));
+        }
+
+        let response = self.client.get(url, Some(options)).await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        self.client.read_text(response).await.map_err(|e| e.to_string().into())
+    }
+
+    fn response(&self, resp: Self::Response) -> Result<Vec<SearchResultItem>, Box<dyn Error + Send + Sync>> {
+        Self::parse_json_result(&resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_creation() {
+        let engine = StackOverflowEngine::new();
+        assert_eq!(engine.info().name, "StackOverflow");
+        assert_eq!(engine.info().engine_type, EngineType::Code);
+    }
+
+    #[test]
+    fn test_request_with_key() {
+        let config = EngineSpecificConfig {
+            api_key: Some("abc".to_string()),
+            ..Default::default()
+        };
+        let engine = StackOverflowEngine::new().with_config(&config);
+        let mut params = RequestParams::default();
+        engine.request("borrow checker", &mut params).unwrap();
+
+        let url = params.url.unwrap();
+        assert!(url.starts_with(API_SEARCH_URL));
+        assert!(url.contains("q=borrow%20checker"));
+        assert!(url.contains("site=stackoverflow"));
+        assert!(url.contains("key=abc"));
+    }
+
+    #[test]
+    fn test_parse_question() {
+        let json = r#"{"items":[{
+            "title":"Why can&#39;t I borrow?","link":"https://stackoverflow.com/q/1",
+            "body":"<p>My code fails:</p><pre><code>let a = &amp;mut x;\nlet b = &amp;x;\n</code></pre>",
+            "score":42,"answer_count":3,"is_answered":true,"tags":["rust","borrow-checker"],
+            "creation_date":1700000000
+        }]}"#;
+        let items = StackOverflowEngine::parse_json_result(json).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Why can't I borrow?");
+        assert_eq!(items[0].content, "My code fails:");
+        assert_eq!(items[0].metadata["code_snippet"], "let a = &mut x;\nlet b = &x;");
+        assert_eq!(items[0].metadata["tags"], "rust,borrow-checker");
+        assert!(items[0].published_date.is_some());
+    }
+
+    #[test]
+    fn test_parse_api_error() {
+        let json = r#"{"error_id":502,"error_message":"too many requests"}"#;
+        assert!(StackOverflowEngine::parse_json_result(json).is_err());
+    }
+}
@@ -122,6 +122,105 @@ where
     result
 }
 
+/// Trim a code snippet for display in search results
+///
+/// Removes surrounding blank lines and common indentation, then keeps at most
+/// `max_lines` whole lines. When the snippet must be cut, it prefers to stop
+/// right after a line where bracket nesting returns to its starting depth, so
+/// blocks are not left half-open, and marks the cut with a trailing `...`.
+///
+/// # Arguments
+///
+/// * `snippet` - Raw code snippet
+/// * `max_lines` - Maximum number of lines to keep
+///
+/// # Returns
+///
+/// The trimmed snippet
+pub fn trim_code_snippet(snippet: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = snippet.lines().map(|l| l.trim_end()).collect();
+    let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !l.is_empty()).map_or(start, |i| i + 1);
+    let lines = &lines[start..end];
+
+    let indent = lines
+        .iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let dedented: Vec<&str> = lines
+        .iter()
+        .map(|l| if l.len() >= indent && l.is_char_boundary(indent) { &l[indent..] } else { l.trim_start() })
+        .collect();
+
+    if max_lines == 0 || dedented.len() <= max_lines {
+        return dedented.join("\n");
+    }
+
+    // Find the last balanced line within the limit, but keep at least half of it
+    let mut depth: i32 = 0;
+    let mut cut = max_lines;
+    for (i, line) in dedented.iter().take(max_lines).enumerate() {
+        for c in line.chars() {
+            match c {
+                '{' | '(' | '[' => depth += 1,
+                '}' | ')' | ']' => depth -= 1,
+                _ => {}
+            }
+        }
+        if depth <= 0 && i + 1 >= max_lines.div_ceil(2) {
+            cut = i + 1;
+        }
+    }
+
+    let mut trimmed = dedented[..cut].join("\n");
+    trimmed.push_str("\n...");
+    trimmed
+}
+
+/// Resolve an API token from engine specific configuration
+///
+/// Uses `api_key` first, then the `token` credential of the authentication config.
+pub fn resolve_api_token(config: &crate::config::engines::EngineSpecificConfig) -> Option<String> {
+    config
+        .api_key
+        .clone()
+        .or_else(|| {
+            config
+                .authentication
+                .as_ref()
+                .and_then(|auth| auth.credentials.get("token").cloned())
+        })
+        .filter(|token| !token.is_empty())
+}
+
+/// Build the shared metadata fields of an academic result
+///
+/// Keys: `doi`, `authors` (joined with `"; "`), `year` and `citations`.
+/// Missing values are omitted.
+pub fn academic_metadata(
+    doi: Option<&str>,
+    authors: &[String],
+    year: Option<i64>,
+    citations: Option<u64>,
+) -> std::collections::HashMap<String, String> {
+    let mut metadata = std::collections::HashMap::new();
+    if let Some(doi) = doi.filter(|d| !d.is_empty()) {
+        metadata.insert("doi".to_string(), doi.to_string());
+    }
+    if !authors.is_empty() {
+        metadata.insert("authors".to_string(), authors.join("; "));
+    }
+    if let Some(year) = year {
+        metadata.insert("year".to_string(), year.to_string());
+    }
+    if let Some(citations) = citations {
+        metadata.insert("citations".to_string(), citations.to_string());
+    }
+    metadata
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +265,27 @@ mod tests {
         let result = collect_text(fragments.iter().copied());
         assert_eq!(result, "");
     }
+
+    #[test]
+    fn test_trim_code_snippet_dedent() {
+        let snippet = "\n    fn main() {\n        println!(\"hi\");\n    }\n\n";
+        assert_eq!(trim_code_snippet(snippet, 10), "fn main() {\n    println!(\"hi\");\n}");
+    }
+
+    #[test]
+    fn test_trim_code_snippet_balanced_cut() {
+        let snippet = "fn a() {\n    1\n}\nfn b() {\n    2\n}";
+        // The 4-line limit falls inside `fn b`, so the cut happens after `fn a`
+        assert_eq!(trim_code_snippet(snippet, 4), "fn a() {\n    1\n}\n...");
+    }
+
+    #[test]
+    fn test_academic_metadata() {
+        let authors = vec!["Ada Lovelace".to_string(), "Alan Turing".to_string()];
+        let metadata = academic_metadata(Some("10.1000/xyz"), &authors, Some(1950), None);
+        assert_eq!(metadata["doi"], "10.1000/xyz");
+        assert_eq!(metadata["authors"], "Ada Lovelace; Alan Turing");
+        assert_eq!(metadata["year"], "1950");
+        assert!(!metadata.contains_key("citations"));
+    }
 }
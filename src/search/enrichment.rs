@@ -0,0 +1,500 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 结果元数据补全
+//!
+//! 可选的后处理阶段：获取排名靠前的结果页面，提取 OpenGraph 与 JSON-LD 中的描述、图片、
+//! 作者与发布时间，补全过短的摘要、缺失的缩略图与发布时间。
+//! 页面获取受全局并发上限与单页超时约束，提取结果按 URL 缓存在元数据缓存中。
+//! 页面经 [`get_public`] 获取：只连接公网地址，每一跳重定向都会检查，避免借补全访问内部服务
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use once_cell::sync::Lazy;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::Semaphore;
+use url::Url;
+
+use crate::cache::MetadataCache;
+use crate::config::search::EnrichmentConfig;
+use crate::derive::SearchResultItem;
+use crate::net::client::body::{read_body, ResponseLimits};
+use crate::net::client::public::{get_public, is_allowed_url};
+use crate::net::client::HttpClient;
+use crate::net::privacy::PrivacyManager;
+
+use super::filtering::{domain_suffixes, item_host};
+use super::standardization::clean_text;
+
+/// 提取结果在元数据缓存中的键前缀
+const ENRICHMENT_KEY_PREFIX: &str = "enrichment:";
+
+/// 记录作者的元数据键
+pub const AUTHOR_METADATA_KEY: &str = "author";
+
+/// 标记摘要已由页面描述补全的元数据键
+pub const ENRICHED_METADATA_KEY: &str = "enriched";
+
+/// 摘要短于该字符数时用页面描述替换
+const MIN_SNIPPET_CHARS: usize = 80;
+
+/// 页面描述的最大保留字符数
+const MAX_DESCRIPTION_CHARS: usize = 300;
+
+static META_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("meta").expect("valid selector"));
+static JSON_LD_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse(r#"script[type="application/ld+json"]"#).expect("valid selector"));
+
+/// 从页面提取的元数据
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageMetadata {
+    /// 页面描述
+    pub description: Option<String>,
+    /// 代表图片（绝对 URL）
+    pub image: Option<String>,
+    /// 作者
+    pub author: Option<String>,
+    /// 发布时间
+    pub published: Option<DateTime<Utc>>,
+}
+
+impl PageMetadata {
+    /// 是否没有提取到任何字段
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none() && self.image.is_none() && self.author.is_none() && self.published.is_none()
+    }
+}
+
+/// 从 HTML 中提取 OpenGraph、JSON-LD 与常规 meta 元数据
+///
+/// 描述与图片优先取 OpenGraph，作者与发布时间优先取 JSON-LD；相对图片地址按 `base_url` 解析
+///
+/// # 参数
+///
+/// * `html` - 页面 HTML
+/// * `base_url` - 页面 URL
+pub fn extract_metadata(html: &str, base_url: &str) -> PageMetadata {
+    let document = Html::parse_document(html);
+
+    let mut meta: HashMap<String, String> = HashMap::new();
+    for element in document.select(&META_SELECTOR) {
+        let element = element.value();
+        let key = element.attr("property").or_else(|| element.attr("name"));
+        if let (Some(key), Some(content)) = (key, element.attr("content"))
+            && !content.trim().is_empty()
+        {
+            meta.entry(key.trim().to_ascii_lowercase()).or_insert_with(|| content.trim().to_string());
+        }
+    }
+
+    let mut ld = JsonLd::default();
+    for script in document.select(&JSON_LD_SELECTOR) {
+        if let Ok(value) = serde_json::from_str::<Value>(&script.text().collect::<String>()) {
+            ld.collect(&value);
+        }
+    }
+
+    let first = |keys: &[&str]| keys.iter().find_map(|key| meta.get(*key).cloned());
+    let description = first(&["og:description"])
+        .or(ld.description)
+        .or_else(|| first(&["description", "twitter:description"]))
+        .map(|text| clean_text(&text, MAX_DESCRIPTION_CHARS))
+        .filter(|text| !text.is_empty());
+    let image = first(&["og:image", "og:image:url", "og:image:secure_url"])
+        .or(ld.image)
+        .or_else(|| first(&["twitter:image"]))
+        .and_then(|image| resolve_http_url(base_url, &image));
+    let author = ld
+        .author
+        .or_else(|| first(&["author", "article:author"]).filter(|author| !author.starts_with("http")));
+    let published = ld
+        .date_published
+        .or_else(|| first(&["article:published_time", "og:published_time", "date"]))
+        .and_then(|date| parse_date(&date));
+
+    PageMetadata {
+        description,
+        image,
+        author,
+        published,
+    }
+}
+
+/// JSON-LD 中收集的字段（各取第一个非空值）
+#[derive(Debug, Default)]
+struct JsonLd {
+    description: Option<String>,
+    image: Option<String>,
+    author: Option<String>,
+    date_published: Option<String>,
+}
+
+impl JsonLd {
+    /// 遍历 JSON-LD 节点（支持数组与 `@graph`）
+    fn collect(&mut self, value: &Value) {
+        match value {
+            Value::Array(nodes) => nodes.iter().for_each(|node| self.collect(node)),
+            Value::Object(node) => {
+                if let Some(graph) = node.get("@graph") {
+                    self.collect(graph);
+                }
+                if self.description.is_none() {
+                    self.description = node.get("description").and_then(json_text);
+                }
+                if self.image.is_none() {
+                    self.image = node.get("image").and_then(json_url);
+                }
+                if self.author.is_none() {
+                    self.author = node.get("author").and_then(json_names);
+                }
+                if self.date_published.is_none() {
+                    self.date_published = node.get("datePublished").and_then(json_text);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 非空字符串值
+fn json_text(value: &Value) -> Option<String> {
+    value.as_str().map(str::trim).filter(|text| !text.is_empty()).map(str::to_string)
+}
+
+/// 图片值：字符串、`{"url": ...}` 或数组中的第一个
+fn json_url(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(values) => values.iter().find_map(json_url),
+        Value::Object(object) => object.get("url").or_else(|| object.get("contentUrl")).and_then(json_text),
+        value => json_text(value),
+    }
+}
+
+/// 作者值：字符串、`{"name": ...}` 或其数组（多个作者以逗号连接）
+fn json_names(value: &Value) -> Option<String> {
+    let names: Vec<String> = match value {
+        Value::Array(values) => values.iter().filter_map(json_names).collect(),
+        Value::Object(object) => object.get("name").and_then(json_text).into_iter().collect(),
+        value => json_text(value).into_iter().collect(),
+    };
+    (!names.is_empty()).then(|| names.join(", "))
+}
+
+/// 解析 RFC 3339 时间或 `YYYY-MM-DD` 日期
+fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        return Some(date.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(text.get(..10)?, "%Y-%m-%d")
+        .ok()?
+        .and_hms_opt(0, 0, 0)
+        .map(|date| date.and_utc())
+}
+
+/// 按页面 URL 解析相对地址，只接受 http(s)
+fn resolve_http_url(base_url: &str, url: &str) -> Option<String> {
+    let resolved = Url::parse(base_url).ok()?.join(url.trim()).ok()?;
+    matches!(resolved.scheme(), "http" | "https").then(|| resolved.to_string())
+}
+
+/// 用页面元数据补全结果
+///
+/// 摘要过短时替换为页面描述，缺失的缩略图、发布时间与作者从元数据补全
+///
+/// # 返回值
+///
+/// 结果有任何字段被补全时返回 true
+pub fn apply_metadata(item: &mut SearchResultItem, metadata: &PageMetadata) -> bool {
+    let mut changed = false;
+    if let Some(ref description) = metadata.description
+        && item.content.chars().count() < MIN_SNIPPET_CHARS
+        && description.chars().count() > item.content.chars().count()
+    {
+        item.content = description.clone();
+        item.metadata.insert(ENRICHED_METADATA_KEY.to_string(), "true".to_string());
+        changed = true;
+    }
+    if item.thumbnail.is_none() && metadata.image.is_some() {
+        item.thumbnail = metadata.image.clone();
+        changed = true;
+    }
+    if item.published_date.is_none() && metadata.published.is_some() {
+        item.published_date = metadata.published;
+        changed = true;
+    }
+    if let Some(ref author) = metadata.author
+        && !item.metadata.contains_key(AUTHOR_METADATA_KEY)
+    {
+        item.metadata.insert(AUTHOR_METADATA_KEY.to_string(), author.clone());
+        changed = true;
+    }
+    changed
+}
+
+/// 结果元数据补全器
+pub struct Enricher {
+    /// 补全配置
+    config: EnrichmentConfig,
+    /// 隐私管理器（获取页面时附加伪装请求头）
+    privacy: Option<Arc<PrivacyManager>>,
+    /// 页面响应限制
+    limits: ResponseLimits,
+    /// 提取结果缓存（未启用缓存时为 None）
+    cache: Option<MetadataCache>,
+    /// 页面获取并发许可（所有搜索共享）
+    permits: Arc<Semaphore>,
+}
+
+impl Enricher {
+    /// 创建补全器
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 补全配置
+    /// * `client` - 引擎 HTTP 客户端（只取其隐私设置，页面由只连接公网地址的客户端获取）
+    /// * `cache` - 元数据缓存（可选）
+    pub fn new(config: EnrichmentConfig, client: Arc<HttpClient>, cache: Option<MetadataCache>) -> Self {
+        let limits = ResponseLimits {
+            max_bytes: Some(config.max_page_bytes),
+            ..Default::default()
+        };
+        let permits = Arc::new(Semaphore::new(config.max_concurrent.max(1)));
+        Self {
+            config,
+            privacy: client.privacy_manager().cloned(),
+            limits,
+            cache,
+            permits,
+        }
+    }
+
+    /// 补全排名靠前的结果
+    ///
+    /// # 返回值
+    ///
+    /// 返回被补全的结果数
+    pub async fn enrich(&self, items: &mut [SearchResultItem]) -> usize {
+        let targets: Vec<(usize, String)> = items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| self.should_fetch(&item.url))
+            .take(self.config.top_n)
+            .map(|(index, item)| (index, item.url.clone()))
+            .collect();
+
+        let metadata = futures::future::join_all(targets.iter().map(|(_, url)| self.metadata_for(url))).await;
+
+        targets
+            .iter()
+            .zip(metadata)
+            .filter(|((index, _), metadata)| metadata.as_ref().is_some_and(|m| apply_metadata(&mut items[*index], m)))
+            .count()
+    }
+
+    /// 是否允许获取该 URL
+    ///
+    /// 以 IP 表示的主机必须是公网地址；域名解析到的地址在连接时检查
+    fn should_fetch(&self, url: &str) -> bool {
+        if !Url::parse(url).is_ok_and(|parsed| is_allowed_url(&parsed)) {
+            return false;
+        }
+        let Some(host) = item_host(url) else {
+            return false;
+        };
+        !domain_suffixes(&host).any(|domain| {
+                self.config
+                    .skip_domains
+                    .iter()
+                    .any(|skip| skip.trim_start_matches('.').eq_ignore_ascii_case(domain))
+            })
+    }
+
+    /// 获取页面元数据（优先读缓存）
+    async fn metadata_for(&self, url: &str) -> Option<PageMetadata> {
+        let key = format!("{}{}", ENRICHMENT_KEY_PREFIX, url);
+        if let Some(ref cache) = self.cache
+            && let Ok(Some(bytes)) = cache.get_metadata_async(&key).await
+            && let Ok(metadata) = serde_json::from_slice::<PageMetadata>(&bytes)
+        {
+            return Some(metadata);
+        }
+
+        let metadata = {
+            let _permit = self.permits.acquire().await.ok()?;
+            let timeout = Duration::from_millis(self.config.timeout_ms);
+            let page = match tokio::time::timeout(timeout, self.fetch_page(url, timeout)).await {
+                Ok(Ok(page)) => page,
+                Ok(Err(e)) => {
+                    tracing::debug!("Failed to fetch {} for enrichment: {}", url, e);
+                    return None;
+                }
+                Err(_) => {
+                    tracing::debug!("Timed out fetching {} for enrichment", url);
+                    return None;
+                }
+            };
+            extract_metadata(&page?, url)
+        };
+
+        // 没有元数据的页面同样缓存，避免重复获取
+        if let Some(ref cache) = self.cache
+            && self.config.cache_ttl_secs > 0
+            && let Ok(bytes) = serde_json::to_vec(&metadata)
+        {
+            let ttl = Some(Duration::from_secs(self.config.cache_ttl_secs));
+            if let Err(e) = cache.set_metadata_async(&key, bytes, ttl).await {
+                tracing::warn!("Failed to cache enrichment for {}: {}", url, e);
+            }
+        }
+        Some(metadata)
+    }
+
+    /// 获取页面 HTML
+    ///
+    /// # 返回值
+    ///
+    /// 非 2xx 或非 HTML 响应返回 `Ok(None)`
+    async fn fetch_page(&self, url: &str, timeout: Duration) -> crate::error::Result<Option<String>> {
+        let response = get_public(url, timeout, self.privacy.as_deref())
+            .await
+            .map_err(|e| crate::error::network_error(e.to_string()))?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        let body = read_body(response, &self.limits).await?;
+        let is_html = body
+            .content_type
+            .as_deref()
+            .is_none_or(|content_type| content_type.contains("html"));
+        Ok(is_html.then(|| body.into_text()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(url: &str, content: &str) -> SearchResultItem {
+        SearchResultItem {
+            title: "title".to_string(),
+            url: url.to_string(),
+            content: content.to_string(),
+            display_url: None,
+            site_name: None,
+            score: 1.0,
+            result_type: crate::derive::ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_extract_opengraph_and_json_ld() {
+        let html = r#"<html><head>
+            <meta property="og:description" content="  An in-depth guide to ownership.  ">
+            <meta property="og:image" content="/img/cover.png">
+            <meta name="description" content="fallback">
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@graph": [
+                {"@type": "WebSite", "name": "Example"},
+                {"@type": "Article", "author": [{"name": "Ferris"}, {"name": "Crab"}], "datePublished": "2024-05-01T08:00:00+08:00"}
+            ]}
+            </script>
+        </head><body></body></html>"#;
+
+        let metadata = extract_metadata(html, "https://example.com/posts/1");
+        assert_eq!(metadata.description.as_deref(), Some("An in-depth guide to ownership."));
+        assert_eq!(metadata.image.as_deref(), Some("https://example.com/img/cover.png"));
+        assert_eq!(metadata.author.as_deref(), Some("Ferris, Crab"));
+        assert_eq!(metadata.published.unwrap().to_rfc3339(), "2024-05-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_extract_falls_back_to_plain_meta() {
+        let html = r#"<meta name="description" content="Plain description"><meta name="author" content="Alice">
+            <meta property="article:published_time" content="2023-01-02">
+            <meta property="og:image" content="javascript:alert(1)">"#;
+        let metadata = extract_metadata(html, "https://example.com/");
+        assert_eq!(metadata.description.as_deref(), Some("Plain description"));
+        assert_eq!(metadata.author.as_deref(), Some("Alice"));
+        assert_eq!(metadata.published.unwrap().to_rfc3339(), "2023-01-02T00:00:00+00:00");
+        assert!(metadata.image.is_none());
+        assert!(extract_metadata("<p>nothing</p>", "https://example.com/").is_empty());
+    }
+
+    #[test]
+    fn test_apply_metadata_keeps_good_snippets() {
+        let metadata = PageMetadata {
+            description: Some("A much longer and more useful description of the page".to_string()),
+            image: Some("https://example.com/a.png".to_string()),
+            author: Some("Ferris".to_string()),
+            published: None,
+        };
+
+        let mut short = item("https://example.com/", "short");
+        assert!(apply_metadata(&mut short, &metadata));
+        assert_eq!(short.content, metadata.description.clone().unwrap());
+        assert_eq!(short.thumbnail, metadata.image);
+        assert_eq!(short.metadata[AUTHOR_METADATA_KEY], "Ferris");
+        assert_eq!(short.metadata[ENRICHED_METADATA_KEY], "true");
+
+        let long = "x".repeat(MIN_SNIPPET_CHARS);
+        let mut good = item("https://example.com/", &long);
+        apply_metadata(&mut good, &metadata);
+        assert_eq!(good.content, long);
+        assert!(!good.metadata.contains_key(ENRICHED_METADATA_KEY));
+    }
+
+    #[test]
+    fn test_should_fetch_only_public_hosts() {
+        let config = EnrichmentConfig {
+            enabled: true,
+            skip_domains: vec!["skip.example.com".to_string()],
+            ..Default::default()
+        };
+        let client = Arc::new(HttpClient::new(Default::default()).unwrap());
+        let enricher = Enricher::new(config, client, None);
+
+        assert!(enricher.should_fetch("https://www.rust-lang.org/learn"));
+        assert!(!enricher.should_fetch("https://a.skip.example.com/page"));
+        assert!(!enricher.should_fetch("ftp://example.com/file"));
+        assert!(!enricher.should_fetch("http://127.0.0.1:8080/admin"));
+        assert!(!enricher.should_fetch("http://192.168.1.1/"));
+        assert!(!enricher.should_fetch("http://[::1]/"));
+        assert!(!enricher.should_fetch("http://100.64.0.1/"));
+        assert!(!enricher.should_fetch("http://[::ffff:169.254.169.254]/"));
+    }
+
+    #[tokio::test]
+    async fn test_enrich_skips_ineligible_results() {
+        let client = Arc::new(HttpClient::new(Default::default()).unwrap());
+        let enricher = Enricher::new(EnrichmentConfig::default(), client, None);
+        let mut items = vec![item("http://127.0.0.1/", "short")];
+        assert_eq!(enricher.enrich(&mut items).await, 0);
+        assert_eq!(items[0].content, "short");
+
+        // 域名在连接时检查解析结果，解析到回环地址的页面不会被获取
+        let mut items = vec![item("http://localhost:9/", "short")];
+        assert_eq!(enricher.enrich(&mut items).await, 0);
+        assert_eq!(items[0].content, "short");
+    }
+}
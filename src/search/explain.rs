@@ -0,0 +1,177 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 结果评分解释
+//!
+//! 请求设置 `explain` 时为每条聚合结果记录评分明细（BM25 分项、引擎权威度、时效性等）、
+//! 使用的评分参数与去重合并的重复结果，写入结果元数据，用于排查排序回归
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::aggregator::ENGINE_METADATA_KEY;
+use super::scoring::{BM25Params, ScoreBreakdown, ScoringWeights};
+use super::standardization::dedup_key;
+use crate::derive::{EngineType, SearchQuery, SearchResultItem};
+
+/// 查询参数中请求评分解释的键
+pub const EXPLAIN_PARAM_KEY: &str = "explain";
+
+/// 结果元数据中保存评分解释的键（值为 [`ScoreExplanation`] 的 JSON）
+pub const EXPLAIN_METADATA_KEY: &str = "score_explain";
+
+/// 单条结果的评分解释
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    /// 选择评分参数使用的搜索类型
+    pub category: EngineType,
+    /// 使用的 BM25 参数
+    pub bm25: BM25Params,
+    /// 使用的评分权重
+    pub weights: ScoringWeights,
+    /// 相关性评分明细
+    pub breakdown: ScoreBreakdown,
+    /// 最终评分（含语言提升等评分之后的调整）
+    pub final_score: f64,
+    /// 最终排名（从 1 开始）
+    pub rank: usize,
+    /// 去重时合并到该结果的重复结果的来源引擎
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_duplicates: Vec<String>,
+}
+
+/// 查询是否请求了评分解释
+pub fn explain_requested(query: &SearchQuery) -> bool {
+    query.params.get(EXPLAIN_PARAM_KEY).is_some_and(|v| v == "true")
+}
+
+/// 记录去重将要移除的重复结果
+///
+/// 需在去重前调用，保留的结果按出现顺序取第一条
+///
+/// # Returns
+///
+/// 返回去重键到被移除的重复结果来源引擎的映射
+pub fn duplicate_sources(items: &[SearchResultItem]) -> HashMap<String, Vec<String>> {
+    let mut sources: HashMap<String, Vec<String>> = HashMap::new();
+    let mut seen = std::collections::HashSet::new();
+    for item in items {
+        let key = dedup_key(item);
+        if !seen.insert(key.clone()) {
+            let engine = item.metadata.get(ENGINE_METADATA_KEY).cloned().unwrap_or_default();
+            sources.entry(key).or_default().push(engine);
+        }
+    }
+    sources
+}
+
+/// 将评分解释写入结果元数据
+///
+/// # Arguments
+///
+/// * `items` - 已按最终顺序排列的结果
+/// * `breakdowns` - 结果 URL 到评分明细的映射
+/// * `duplicates` - [`duplicate_sources`] 记录的重复结果
+/// * `category` - 搜索类型
+/// * `params` - 使用的 BM25 参数与评分权重
+pub fn attach_explanations(
+    items: &mut [SearchResultItem],
+    mut breakdowns: HashMap<String, ScoreBreakdown>,
+    mut duplicates: HashMap<String, Vec<String>>,
+    category: EngineType,
+    params: (&BM25Params, &ScoringWeights),
+) {
+    for (index, item) in items.iter_mut().enumerate() {
+        let Some(breakdown) = breakdowns.remove(&item.url) else {
+            continue;
+        };
+        let explanation = ScoreExplanation {
+            category,
+            bm25: params.0.clone(),
+            weights: params.1.clone(),
+            breakdown,
+            final_score: item.score,
+            rank: index + 1,
+            merged_duplicates: duplicates.remove(&dedup_key(item)).unwrap_or_default(),
+        };
+        match serde_json::to_string(&explanation) {
+            Ok(json) => {
+                item.metadata.insert(EXPLAIN_METADATA_KEY.to_string(), json);
+            }
+            Err(e) => tracing::warn!("序列化评分解释失败: {}", e),
+        }
+    }
+}
+
+/// 读取结果的评分解释（未请求解释时为 None）
+pub fn explanation(item: &SearchResultItem) -> Option<ScoreExplanation> {
+    item.metadata
+        .get(EXPLAIN_METADATA_KEY)
+        .and_then(|json| serde_json::from_str(json).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derive::ResultType;
+
+    fn item(url: &str, engine: &str) -> SearchResultItem {
+        SearchResultItem {
+            title: "rust".to_string(),
+            url: url.to_string(),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score: 0.5,
+            result_type: ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::from([(ENGINE_METADATA_KEY.to_string(), engine.to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_explanations_round_trip() {
+        let items = vec![
+            item("https://a.example.com/", "google"),
+            item("https://A.example.com/", "bing"),
+            item("https://b.example.com/", "bing"),
+        ];
+        let duplicates = duplicate_sources(&items);
+        assert_eq!(duplicates["https://a.example.com/"], vec!["bing"]);
+
+        let mut kept = vec![items[0].clone(), items[2].clone()];
+        let breakdowns = kept
+            .iter()
+            .map(|item| (item.url.clone(), ScoreBreakdown { score: 0.5, ..Default::default() }))
+            .collect();
+        attach_explanations(
+            &mut kept,
+            breakdowns,
+            duplicates,
+            EngineType::General,
+            (&BM25Params::default(), &ScoringWeights::default()),
+        );
+
+        let first = explanation(&kept[0]).unwrap();
+        assert_eq!(first.rank, 1);
+        assert_eq!(first.merged_duplicates, vec!["bing"]);
+        let second = explanation(&kept[1]).unwrap();
+        assert_eq!(second.rank, 2);
+        assert!(second.merged_duplicates.is_empty());
+        assert_eq!(explanation(&item("https://c.example.com/", "bing")), None);
+    }
+}
@@ -0,0 +1,649 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! 结果过滤模块
+//!
+//! 在标准化阶段按域名黑白名单、远程域名列表与 URL/标题/内容正则移除结果。
+//! 规则由 [`ResultFilteringConfig`] 编译而来，可在运行时整体替换（热重载），
+//! 远程列表按各自的刷新间隔重新获取，获取失败时保留上一次的内容
+
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::{Arc, RwLock};
+
+use regex::{Regex, RegexBuilder};
+use url::Url;
+
+use crate::config::engines::{DomainListKind, RemoteDomainListConfig, ResultFilteringConfig};
+use crate::derive::{EngineType, SearchResult, SearchResultItem};
+
+/// 提取用于匹配的主机名（小写）
+pub(super) fn item_host(url: &str) -> Option<String> {
+    Url::parse(url).ok()?.host_str().map(|host| host.to_ascii_lowercase())
+}
+
+/// 主机名及其各级父域名（`a.b.com`、`b.com`、`com`）
+pub(super) fn domain_suffixes(host: &str) -> impl Iterator<Item = &str> {
+    std::iter::once(host).chain(host.match_indices('.').map(move |(i, _)| &host[i + 1..]))
+}
+
+/// 将通配符规则转换为匹配整个主机名的正则表达式
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+/// 一组域名规则
+#[derive(Debug, Default)]
+struct DomainRules {
+    /// 精确域名（同时匹配其子域名）
+    domains: HashSet<String>,
+    /// 通配符与正则规则
+    patterns: Vec<Regex>,
+}
+
+impl DomainRules {
+    /// 编译域名规则
+    fn compile(rules: &[String]) -> Result<Self, String> {
+        let mut compiled = Self::default();
+        for rule in rules {
+            let rule = rule.trim();
+            if rule.is_empty() {
+                continue;
+            }
+            let pattern = match rule.strip_prefix('/').and_then(|r| r.strip_suffix('/')) {
+                Some(pattern) => pattern.to_string(),
+                None if rule.contains(['*', '?']) => glob_to_regex(rule),
+                None => {
+                    compiled.domains.insert(rule.trim_start_matches('.').to_ascii_lowercase());
+                    continue;
+                }
+            };
+            let regex = RegexBuilder::new(&pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| format!("Invalid domain rule {}: {}", rule, e))?;
+            compiled.patterns.push(regex);
+        }
+        Ok(compiled)
+    }
+
+    /// 主机名是否匹配任一规则
+    fn matches(&self, host: &str) -> bool {
+        domain_suffixes(host).any(|domain| self.domains.contains(domain))
+            || self.patterns.iter().any(|pattern| pattern.is_match(host))
+    }
+}
+
+/// 编译正则过滤规则
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Regex>, String> {
+    patterns
+        .iter()
+        .map(|pattern| Regex::new(pattern).map_err(|e| format!("Invalid filter pattern {}: {}", pattern, e)))
+        .collect()
+}
+
+/// 解析分类名（与 [`EngineType`] 的序列化名称一致，如 `general`、`image`）
+fn parse_category(name: &str) -> Result<EngineType, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_ascii_lowercase()))
+        .map_err(|_| format!("Unknown result filter category: {}", name))
+}
+
+/// 编译后的分类覆盖
+#[derive(Debug)]
+struct CategoryRules {
+    enabled: Option<bool>,
+    allow: DomainRules,
+    block: DomainRules,
+    use_remote_lists: bool,
+}
+
+/// 编译后的过滤规则
+#[derive(Debug, Default)]
+struct FilterRules {
+    enabled: bool,
+    allow: DomainRules,
+    block: DomainRules,
+    url_filters: Vec<Regex>,
+    title_filters: Vec<Regex>,
+    content_filters: Vec<Regex>,
+    categories: HashMap<EngineType, CategoryRules>,
+    remote_lists: Vec<RemoteDomainListConfig>,
+}
+
+impl FilterRules {
+    fn compile(config: &ResultFilteringConfig) -> Result<Self, String> {
+        let mut categories = HashMap::new();
+        for (name, category) in &config.category_overrides {
+            categories.insert(
+                parse_category(name)?,
+                CategoryRules {
+                    enabled: category.enabled,
+                    allow: DomainRules::compile(&category.domain_whitelist)?,
+                    block: DomainRules::compile(&category.domain_blacklist)?,
+                    use_remote_lists: category.use_remote_lists,
+                },
+            );
+        }
+
+        Ok(Self {
+            enabled: config.enabled,
+            allow: DomainRules::compile(&config.domain_whitelist)?,
+            block: DomainRules::compile(&config.domain_blacklist)?,
+            url_filters: compile_patterns(&config.url_filters)?,
+            title_filters: compile_patterns(&config.title_filters)?,
+            content_filters: compile_patterns(&config.content_filters)?,
+            categories,
+            remote_lists: config.remote_lists.clone(),
+        })
+    }
+}
+
+/// 已加载的域名列表
+#[derive(Debug, Clone)]
+struct LoadedList {
+    kind: DomainListKind,
+    domains: Arc<HashSet<String>>,
+    /// 获取时间（Unix 时间戳，手动加载的列表为 0）
+    #[cfg_attr(not(feature = "native"), allow(dead_code))]
+    fetched_at: u64,
+    /// 本地文件的修改时间（用于检测文件变化）
+    #[cfg_attr(not(feature = "native"), allow(dead_code))]
+    modified: Option<std::time::SystemTime>,
+}
+
+/// 合并后的远程列表域名
+#[derive(Debug, Default)]
+struct RemoteDomains {
+    blocked: Vec<Arc<HashSet<String>>>,
+    allowed: Vec<Arc<HashSet<String>>>,
+}
+
+impl RemoteDomains {
+    fn contains(sets: &[Arc<HashSet<String>>], host: &str) -> bool {
+        domain_suffixes(host).any(|domain| sets.iter().any(|set| set.contains(domain)))
+    }
+}
+
+/// 解析域名列表
+///
+/// 支持 hosts 文件、每行一个域名与 Adblock `||domain^` 格式，忽略注释、例外规则与非域名条目
+pub fn parse_domain_list(text: &str) -> HashSet<String> {
+    let mut domains = HashSet::new();
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() || line.starts_with('!') || line.starts_with("@@") || line.starts_with('[') {
+            continue;
+        }
+
+        let entry = match line.strip_prefix("||") {
+            Some(rule) => rule.split(['^', '/', '$']).next().unwrap_or_default(),
+            None => {
+                let mut tokens = line.split_whitespace();
+                let first = tokens.next().unwrap_or_default();
+                if first.parse::<IpAddr>().is_ok() {
+                    tokens.next().unwrap_or_default()
+                } else {
+                    first
+                }
+            }
+        };
+
+        let domain = entry.trim_start_matches("*.").trim_matches('.').to_ascii_lowercase();
+        let valid = domain.contains('.')
+            && domain.parse::<IpAddr>().is_err()
+            && domain.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        if valid {
+            domains.insert(domain);
+        }
+    }
+    domains
+}
+
+/// 结果过滤器
+///
+/// 白名单（配置、分类覆盖与放行列表）优先；未放行的结果命中黑名单或屏蔽列表时被移除，
+/// 随后再检查 URL、标题与内容正则
+#[derive(Debug, Default)]
+pub struct ResultFilter {
+    /// 当前规则
+    rules: RwLock<Arc<FilterRules>>,
+    /// 已加载的远程列表（列表地址 -> 内容）
+    lists: RwLock<HashMap<String, LoadedList>>,
+    /// 合并后的远程列表域名
+    remote: RwLock<Arc<RemoteDomains>>,
+}
+
+impl ResultFilter {
+    /// 从配置创建过滤器
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - 结果过滤配置
+    ///
+    /// # Returns
+    ///
+    /// 规则无效或分类名未知时返回错误
+    pub fn from_config(config: &ResultFilteringConfig) -> Result<Self, String> {
+        Ok(Self {
+            rules: RwLock::new(Arc::new(FilterRules::compile(config)?)),
+            ..Self::default()
+        })
+    }
+
+    /// 热重载过滤规则
+    ///
+    /// 新规则编译成功后整体替换；不再配置的远程列表被移除，其余列表保留已加载的内容
+    pub fn reload(&self, config: &ResultFilteringConfig) -> Result<(), String> {
+        let rules = FilterRules::compile(config)?;
+        {
+            let mut lists = self.lists.write().unwrap_or_else(|e| e.into_inner());
+            lists.retain(|url, loaded| {
+                rules
+                    .remote_lists
+                    .iter()
+                    .any(|list| &list.url == url && list.kind == loaded.kind)
+            });
+        }
+        *self.rules.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(rules);
+        self.rebuild_remote();
+        Ok(())
+    }
+
+    /// 是否对任一分类启用了过滤
+    pub fn is_enabled(&self) -> bool {
+        let rules = self.current_rules();
+        rules.enabled || rules.categories.values().any(|c| c.enabled == Some(true))
+    }
+
+    fn current_rules(&self) -> Arc<FilterRules> {
+        Arc::clone(&self.rules.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// 由已加载的列表重建合并后的远程域名
+    fn rebuild_remote(&self) {
+        let lists = self.lists.read().unwrap_or_else(|e| e.into_inner());
+        let mut remote = RemoteDomains::default();
+        for list in lists.values() {
+            match list.kind {
+                DomainListKind::Block => remote.blocked.push(Arc::clone(&list.domains)),
+                DomainListKind::Allow => remote.allowed.push(Arc::clone(&list.domains)),
+            }
+        }
+        *self.remote.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(remote);
+    }
+
+    /// 加载域名列表内容
+    ///
+    /// 手动加载的列表不记录获取时间，下次刷新时仍会重新获取
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - 列表地址（需与配置中的地址一致）
+    /// * `text` - 列表内容
+    ///
+    /// # Returns
+    ///
+    /// 返回解析出的域名数；地址未在配置中时返回 None
+    pub fn load_list(&self, url: &str, text: &str) -> Option<usize> {
+        self.store_list(url, text, 0, None)
+    }
+
+    fn store_list(
+        &self,
+        url: &str,
+        text: &str,
+        fetched_at: u64,
+        modified: Option<std::time::SystemTime>,
+    ) -> Option<usize> {
+        let rules = self.current_rules();
+        let config = rules.remote_lists.iter().find(|list| list.url == url)?;
+        let domains = parse_domain_list(text);
+        let count = domains.len();
+        self.lists.write().unwrap_or_else(|e| e.into_inner()).insert(
+            url.to_string(),
+            LoadedList {
+                kind: config.kind,
+                domains: Arc::new(domains),
+                fetched_at,
+                modified,
+            },
+        );
+        self.rebuild_remote();
+        Some(count)
+    }
+
+    /// 判断结果是否保留
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - 搜索结果项
+    /// * `category` - 本次搜索的分类
+    pub fn allows(&self, item: &SearchResultItem, category: EngineType) -> bool {
+        let rules = self.current_rules();
+        let category_rules = rules.categories.get(&category);
+        if !category_rules.and_then(|c| c.enabled).unwrap_or(rules.enabled) {
+            return true;
+        }
+
+        if let Some(host) = item_host(&item.url) {
+            let use_remote = category_rules.is_none_or(|c| c.use_remote_lists);
+            let remote = Arc::clone(&self.remote.read().unwrap_or_else(|e| e.into_inner()));
+            let allowed = rules.allow.matches(&host)
+                || category_rules.is_some_and(|c| c.allow.matches(&host))
+                || (use_remote && RemoteDomains::contains(&remote.allowed, &host));
+            let blocked = !allowed
+                && (rules.block.matches(&host)
+                    || category_rules.is_some_and(|c| c.block.matches(&host))
+                    || (use_remote && RemoteDomains::contains(&remote.blocked, &host)));
+            if blocked {
+                return false;
+            }
+        }
+
+        !(rules.url_filters.iter().any(|p| p.is_match(&item.url))
+            || rules.title_filters.iter().any(|p| p.is_match(&item.title))
+            || rules.content_filters.iter().any(|p| p.is_match(&item.content)))
+    }
+
+    /// 移除搜索结果中被过滤的条目
+    ///
+    /// # Returns
+    ///
+    /// 返回移除的条目数
+    pub fn apply(&self, result: &mut SearchResult, category: EngineType) -> usize {
+        let before = result.items.len();
+        result.items.retain(|item| self.allows(item, category));
+        before - result.items.len()
+    }
+
+    /// 已加载的远程列表（列表地址 -> 域名数）
+    pub fn loaded_lists(&self) -> HashMap<String, usize> {
+        self.lists
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(url, list)| (url.clone(), list.domains.len()))
+            .collect()
+    }
+}
+
+#[cfg(feature = "native")]
+impl ResultFilter {
+    /// 获取到期的远程列表
+    ///
+    /// http/https 列表按刷新间隔重新获取；本地文件在修改时间变化时重新读取。
+    /// 获取失败的列表保留上一次的内容，并在下次调用时重试
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - HTTP 客户端
+    ///
+    /// # Returns
+    ///
+    /// 返回本次更新的列表数
+    pub async fn refresh_lists(&self, client: &crate::net::HttpClient) -> usize {
+        let rules = self.current_rules();
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        let mut updated = 0;
+
+        for list in &rules.remote_lists {
+            let loaded = self.lists.read().unwrap_or_else(|e| e.into_inner()).get(&list.url).cloned();
+            let is_remote = list.url.starts_with("http://") || list.url.starts_with("https://");
+
+            let result = if is_remote {
+                if loaded.is_some_and(|l| now.saturating_sub(l.fetched_at) < list.refresh_interval_secs) {
+                    continue;
+                }
+                Self::fetch_remote(client, &list.url).await.map(|text| (text, None))
+            } else {
+                let path = list.url.clone();
+                let previous = loaded.and_then(|l| l.modified);
+                match tokio::task::spawn_blocking(move || Self::read_if_modified(&path, previous)).await {
+                    Ok(Ok(Some((text, modified)))) => Ok((text, Some(modified))),
+                    Ok(Ok(None)) => continue,
+                    Ok(Err(e)) => Err(e),
+                    Err(e) => Err(e.to_string()),
+                }
+            };
+
+            match result {
+                Ok((text, modified)) => {
+                    if let Some(count) = self.store_list(&list.url, &text, now, modified) {
+                        tracing::info!("Loaded {} domains from filter list {}", count, list.url);
+                        updated += 1;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to fetch filter list {}: {}", list.url, e),
+            }
+        }
+        updated
+    }
+
+    async fn fetch_remote(client: &crate::net::HttpClient, url: &str) -> Result<String, String> {
+        let response = client.get(url, None).await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        response.text().await.map_err(|e| e.to_string())
+    }
+
+    /// 读取本地列表文件（修改时间未变化时返回 None）
+    fn read_if_modified(
+        path: &str,
+        previous: Option<std::time::SystemTime>,
+    ) -> Result<Option<(String, std::time::SystemTime)>, String> {
+        let modified = std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .map_err(|e| e.to_string())?;
+        if previous == Some(modified) {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Ok(Some((text, modified)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::engines::CategoryFilterOverride;
+
+    fn item(url: &str, title: &str) -> SearchResultItem {
+        SearchResultItem {
+            title: title.to_string(),
+            url: url.to_string(),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score: 1.0,
+            result_type: crate::derive::ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn config() -> ResultFilteringConfig {
+        ResultFilteringConfig {
+            enabled: true,
+            domain_blacklist: vec![
+                "spam.com".to_string(),
+                "ads.*".to_string(),
+                "/^track[0-9]+\\./".to_string(),
+            ],
+            domain_whitelist: vec!["good.spam.com".to_string()],
+            title_filters: vec!["(?i)casino".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_domain_rules() {
+        let filter = ResultFilter::from_config(&config()).unwrap();
+        let general = EngineType::General;
+        assert!(!filter.allows(&item("https://spam.com/a", "x"), general));
+        assert!(!filter.allows(&item("https://www.spam.com/a", "x"), general));
+        assert!(filter.allows(&item("https://good.spam.com/a", "x"), general));
+        assert!(!filter.allows(&item("https://ads.example.org/", "x"), general));
+        assert!(!filter.allows(&item("https://TRACK42.example.org/", "x"), general));
+        assert!(filter.allows(&item("https://notspam.com/", "x"), general));
+        assert!(!filter.allows(&item("https://rust-lang.org/", "Online Casino"), general));
+    }
+
+    #[test]
+    fn test_disabled_filter_keeps_everything() {
+        let mut config = config();
+        config.enabled = false;
+        let filter = ResultFilter::from_config(&config).unwrap();
+        assert!(!filter.is_enabled());
+        assert!(filter.allows(&item("https://spam.com/", "x"), EngineType::General));
+    }
+
+    #[test]
+    fn test_category_overrides() {
+        let mut config = config();
+        config.category_overrides.insert(
+            "image".to_string(),
+            CategoryFilterOverride {
+                enabled: Some(false),
+                ..Default::default()
+            },
+        );
+        config.category_overrides.insert(
+            "news".to_string(),
+            CategoryFilterOverride {
+                domain_blacklist: vec!["tabloid.com".to_string()],
+                use_remote_lists: false,
+                ..Default::default()
+            },
+        );
+        config.remote_lists.push(RemoteDomainListConfig {
+            url: "https://lists.example.com/ads.txt".to_string(),
+            kind: DomainListKind::Block,
+            refresh_interval_secs: 3600,
+        });
+        let filter = ResultFilter::from_config(&config).unwrap();
+        filter.load_list("https://lists.example.com/ads.txt", "0.0.0.0 remote-ads.net\n");
+
+        assert!(filter.allows(&item("https://spam.com/", "x"), EngineType::Image));
+        assert!(!filter.allows(&item("https://tabloid.com/", "x"), EngineType::News));
+        assert!(filter.allows(&item("https://tabloid.com/", "x"), EngineType::General));
+        assert!(filter.allows(&item("https://remote-ads.net/", "x"), EngineType::News));
+        assert!(!filter.allows(&item("https://remote-ads.net/", "x"), EngineType::General));
+    }
+
+    #[test]
+    fn test_unknown_category_is_rejected() {
+        let mut config = config();
+        config.category_overrides.insert("podcasts".to_string(), CategoryFilterOverride::default());
+        assert!(ResultFilter::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_parse_domain_list_formats() {
+        let text = "\
+# hosts file
+127.0.0.1 localhost
+0.0.0.0 ads.example.com   # inline comment
+tracker.example.net
+||adblock.example.org^$third-party
+@@||allowed.example.org^
+! adblock comment
+192.168.1.1
+";
+        let domains = parse_domain_list(text);
+        assert_eq!(domains.len(), 3);
+        assert!(domains.contains("ads.example.com"));
+        assert!(domains.contains("tracker.example.net"));
+        assert!(domains.contains("adblock.example.org"));
+    }
+
+    #[test]
+    fn test_allow_list_overrides_block_list() {
+        let mut config = config();
+        config.remote_lists = vec![
+            RemoteDomainListConfig {
+                url: "block.txt".to_string(),
+                kind: DomainListKind::Block,
+                refresh_interval_secs: 60,
+            },
+            RemoteDomainListConfig {
+                url: "allow.txt".to_string(),
+                kind: DomainListKind::Allow,
+                refresh_interval_secs: 60,
+            },
+        ];
+        let filter = ResultFilter::from_config(&config).unwrap();
+        filter.load_list("block.txt", "cdn.example.com\nexample.org\n");
+        filter.load_list("allow.txt", "cdn.example.com\n");
+        assert!(filter.load_list("unknown.txt", "x.com").is_none());
+
+        assert!(filter.allows(&item("https://cdn.example.com/", "x"), EngineType::General));
+        assert!(!filter.allows(&item("https://img.example.org/", "x"), EngineType::General));
+    }
+
+    #[test]
+    fn test_reload_replaces_rules_and_drops_lists() {
+        let mut config = config();
+        config.remote_lists.push(RemoteDomainListConfig {
+            url: "block.txt".to_string(),
+            kind: DomainListKind::Block,
+            refresh_interval_secs: 60,
+        });
+        let filter = ResultFilter::from_config(&config).unwrap();
+        filter.load_list("block.txt", "remote.example\n");
+        assert!(!filter.allows(&item("https://remote.example/", "x"), EngineType::General));
+
+        let reloaded = ResultFilteringConfig {
+            enabled: true,
+            domain_blacklist: vec!["other.com".to_string()],
+            ..Default::default()
+        };
+        filter.reload(&reloaded).unwrap();
+        assert!(filter.loaded_lists().is_empty());
+        assert!(filter.allows(&item("https://remote.example/", "x"), EngineType::General));
+        assert!(filter.allows(&item("https://spam.com/", "x"), EngineType::General));
+        assert!(!filter.allows(&item("https://other.com/", "x"), EngineType::General));
+
+        let mut invalid = reloaded.clone();
+        invalid.url_filters.push("(".to_string());
+        assert!(filter.reload(&invalid).is_err());
+        assert!(!filter.allows(&item("https://other.com/", "x"), EngineType::General));
+    }
+
+    #[test]
+    fn test_apply_counts_removed() {
+        let filter = ResultFilter::from_config(&config()).unwrap();
+        let mut result = SearchResult {
+            engine_name: "test".to_string(),
+            total_results: None,
+            elapsed_ms: 0,
+            items: vec![item("https://spam.com/", "a"), item("https://rust-lang.org/", "b")],
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        assert_eq!(filter.apply(&mut result, EngineType::General), 1);
+        assert_eq!(result.items.len(), 1);
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 引擎地域路由
+//!
+//! 根据查询地区为引擎选择服务源：优先使用该地区的镜像，
+//! 镜像被封锁（HTTP 403/429）后在冷却期内跳过，依次改用主服务源与全球镜像
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::engines::EngineGeoConfig;
+use crate::derive::SearchQuery;
+
+/// 未指定地区时按查询语言推断的默认地区
+///
+/// 英语等跨地区通用的语言不推断地区
+const LANGUAGE_REGIONS: &[(&str, &str)] = &[
+    ("zh", "cn"),
+    ("ja", "jp"),
+    ("ko", "kr"),
+    ("ru", "ru"),
+    ("uk", "ua"),
+    ("de", "de"),
+    ("fr", "fr"),
+    ("it", "it"),
+    ("nl", "nl"),
+    ("pl", "pl"),
+    ("tr", "tr"),
+    ("vi", "vn"),
+    ("th", "th"),
+    ("id", "id"),
+    ("sv", "se"),
+];
+
+/// 规范化地区代码
+///
+/// 转为小写；带语言前缀的代码（如 `zh-CN`、`en_US`）取两字母的地区部分
+pub fn normalize_region(region: &str) -> String {
+    let region = region.trim().to_ascii_lowercase();
+    match region.rsplit(['-', '_']).next() {
+        Some(subtag) if subtag.len() == 2 && subtag != region => subtag.to_string(),
+        _ => region,
+    }
+}
+
+/// 确定查询用于路由的地区
+///
+/// 优先使用 `query.region`；未指定时从语言代码中的地区部分或按语言推断
+pub fn routing_region(query: &SearchQuery) -> Option<String> {
+    if let Some(region) = query.region.as_deref().filter(|r| !r.trim().is_empty()) {
+        return Some(normalize_region(region));
+    }
+    let language = query.language.as_deref()?.trim().to_ascii_lowercase();
+    if let Some((_, subtag)) = language.split_once(['-', '_'])
+        && subtag.len() == 2
+    {
+        return Some(subtag.to_string());
+    }
+    LANGUAGE_REGIONS
+        .iter()
+        .find(|(lang, _)| *lang == language)
+        .map(|(_, region)| region.to_string())
+}
+
+/// 引擎地域路由器
+///
+/// 在同一个搜索接口的所有请求之间共享，记录被封锁的服务源
+#[derive(Debug, Default)]
+pub struct GeoRouter {
+    /// 各引擎的地域配置（只含配置了地区或镜像的引擎）
+    engines: HashMap<String, EngineGeoConfig>,
+    /// 被封锁的服务源（(引擎, 服务源) -> 解除时间，主服务源记为空字符串）
+    blocked: Mutex<HashMap<(String, String), Instant>>,
+}
+
+impl GeoRouter {
+    /// 创建地域路由器
+    ///
+    /// # 参数
+    ///
+    /// * `engines` - 各引擎的地域配置
+    pub fn new(engines: HashMap<String, EngineGeoConfig>) -> Self {
+        let engines = engines
+            .into_iter()
+            .map(|(name, mut config)| {
+                config.regions = config.regions.iter().map(|r| normalize_region(r)).collect();
+                for mirror in &mut config.mirrors {
+                    mirror.region = mirror.region.as_deref().map(normalize_region);
+                    mirror.origin = mirror.origin.trim_end_matches('/').to_string();
+                }
+                config.origin = config.origin.map(|o| o.trim_end_matches('/').to_string());
+                (name, config)
+            })
+            .collect();
+        Self {
+            engines,
+            blocked: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 引擎是否擅长该地区
+    pub fn serves(&self, engine: &str, region: &str) -> bool {
+        self.engines
+            .get(engine)
+            .is_some_and(|config| config.regions.iter().any(|r| r == region))
+    }
+
+    /// 引擎的主服务源（镜像替换发往此源的请求）
+    pub fn origin(&self, engine: &str) -> Option<&str> {
+        self.engines.get(engine)?.origin.as_deref()
+    }
+
+    /// 按优先顺序列出引擎可用的服务源
+    ///
+    /// 顺序为：该地区的镜像、主服务源（`None`）、全球镜像；其他地区的镜像不使用。
+    /// 冷却期内被封锁的服务源被跳过，全部被封锁时仍返回完整列表
+    ///
+    /// # 参数
+    ///
+    /// * `engine` - 引擎名称
+    /// * `region` - 查询地区（已规范化）
+    ///
+    /// # 返回值
+    ///
+    /// 返回服务源列表，至少包含一项
+    pub fn endpoints(&self, engine: &str, region: Option<&str>) -> Vec<Option<String>> {
+        let Some(config) = self.engines.get(engine).filter(|c| c.origin.is_some()) else {
+            return vec![None];
+        };
+
+        let regional = config
+            .mirrors
+            .iter()
+            .filter(|m| region.is_some() && m.region.as_deref() == region)
+            .map(|m| Some(m.origin.clone()));
+        let global = config
+            .mirrors
+            .iter()
+            .filter(|m| m.region.is_none())
+            .map(|m| Some(m.origin.clone()));
+        let candidates: Vec<Option<String>> = regional.chain(std::iter::once(None)).chain(global).collect();
+
+        let now = Instant::now();
+        let Ok(mut blocked) = self.blocked.lock() else {
+            return candidates;
+        };
+        blocked.retain(|_, until| *until > now);
+        let available: Vec<Option<String>> = candidates
+            .iter()
+            .filter(|endpoint| !blocked.contains_key(&Self::block_key(engine, endpoint.as_deref())))
+            .cloned()
+            .collect();
+        if available.is_empty() { candidates } else { available }
+    }
+
+    /// 标记服务源被封锁，冷却期内不再优先使用
+    ///
+    /// # 参数
+    ///
+    /// * `engine` - 引擎名称
+    /// * `endpoint` - 被封锁的镜像源（`None` 表示主服务源）
+    pub fn mark_blocked(&self, engine: &str, endpoint: Option<&str>) {
+        let Some(config) = self.engines.get(engine) else {
+            return;
+        };
+        let until = Instant::now() + Duration::from_secs(config.block_cooldown_secs);
+        if let Ok(mut blocked) = self.blocked.lock() {
+            blocked.insert(Self::block_key(engine, endpoint), until);
+        }
+        tracing::warn!(
+            "Engine {} was blocked at {}, failing over for {}s",
+            engine,
+            endpoint.or(config.origin.as_deref()).unwrap_or("its primary origin"),
+            config.block_cooldown_secs
+        );
+    }
+
+    fn block_key(engine: &str, endpoint: Option<&str>) -> (String, String) {
+        (engine.to_string(), endpoint.unwrap_or_default().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::engines::EngineMirrorConfig;
+
+    fn router() -> GeoRouter {
+        let config = EngineGeoConfig {
+            regions: vec!["US".to_string()],
+            origin: Some("https://www.example.com/".to_string()),
+            mirrors: vec![
+                EngineMirrorConfig {
+                    origin: "https://cn.example.com".to_string(),
+                    region: Some("CN".to_string()),
+                },
+                EngineMirrorConfig {
+                    origin: "https://global.example.com".to_string(),
+                    region: None,
+                },
+            ],
+            block_cooldown_secs: 60,
+        };
+        GeoRouter::new(HashMap::from([("example".to_string(), config)]))
+    }
+
+    #[test]
+    fn test_normalize_region() {
+        assert_eq!(normalize_region("CN"), "cn");
+        assert_eq!(normalize_region("zh-CN"), "cn");
+        assert_eq!(normalize_region("en_us"), "us");
+        assert_eq!(normalize_region("wt-wt"), "wt");
+    }
+
+    #[test]
+    fn test_routing_region() {
+        let mut query = SearchQuery {
+            region: Some("zh-TW".to_string()),
+            language: Some("zh".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(routing_region(&query).as_deref(), Some("tw"));
+
+        query.region = None;
+        assert_eq!(routing_region(&query).as_deref(), Some("cn"));
+
+        query.language = Some("en-GB".to_string());
+        assert_eq!(routing_region(&query).as_deref(), Some("gb"));
+
+        query.language = Some("en".to_string());
+        assert_eq!(routing_region(&query), None);
+    }
+
+    #[test]
+    fn test_endpoints_prefer_region_then_failover() {
+        let router = router();
+        assert!(router.serves("example", "us"));
+        assert_eq!(router.origin("example"), Some("https://www.example.com"));
+
+        assert_eq!(
+            router.endpoints("example", Some("cn")),
+            vec![
+                Some("https://cn.example.com".to_string()),
+                None,
+                Some("https://global.example.com".to_string()),
+            ]
+        );
+        // 其他地区不使用 cn 镜像
+        assert_eq!(
+            router.endpoints("example", Some("de")),
+            vec![None, Some("https://global.example.com".to_string())]
+        );
+        assert_eq!(router.endpoints("unknown", Some("cn")), vec![None]);
+
+        router.mark_blocked("example", Some("https://cn.example.com"));
+        assert_eq!(router.endpoints("example", Some("cn"))[0], None);
+
+        router.mark_blocked("example", None);
+        router.mark_blocked("example", Some("https://global.example.com"));
+        // 全部被封锁时仍返回完整列表
+        assert_eq!(router.endpoints("example", Some("cn")).len(), 3);
+    }
+}
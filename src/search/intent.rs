@@ -0,0 +1,203 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 查询意图分类
+//!
+//! 根据关键词与正则规则判断查询意图，映射到引擎分类（news、code、academic、shopping 等）
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// 意图规则
+///
+/// 查询命中任一关键词或正则时归入 `category`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IntentRule {
+    /// 目标引擎分类
+    pub category: String,
+    /// 关键词（忽略大小写；ASCII 关键词按整词匹配，其余按子串匹配）
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// 正则表达式
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl IntentRule {
+    /// 创建规则
+    pub fn new(category: &str, keywords: &[&str], patterns: &[&str]) -> Self {
+        Self {
+            category: category.to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+/// 默认意图规则
+pub fn default_intent_rules() -> Vec<IntentRule> {
+    vec![
+        IntentRule::new(
+            "news",
+            &["news", "latest", "breaking", "headlines", "today", "新闻", "最新", "头条", "快讯"],
+            &[],
+        ),
+        IntentRule::new(
+            "code",
+            &[
+                "rust", "python", "javascript", "typescript", "golang", "java", "api", "sdk",
+                "error", "exception", "compile", "stacktrace", "github", "npm", "cargo", "pip",
+                "代码", "编程", "报错", "源码",
+            ],
+            &[r"\w+::\w+", r"\w+\.(rs|py|js|ts|go|java|cpp)\b", r"\w+\(\)"],
+        ),
+        IntentRule::new(
+            "academic",
+            &["paper", "papers", "arxiv", "doi", "journal", "thesis", "citation", "论文", "期刊", "文献"],
+            &[r"\b10\.\d{4,9}/\S+"],
+        ),
+        IntentRule::new(
+            "shopping",
+            &["buy", "price", "cheap", "deal", "deals", "discount", "coupon", "购买", "价格", "多少钱", "优惠"],
+            &[r"[$¥€£]\s?\d+"],
+        ),
+    ]
+}
+
+/// 编译后的规则
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    category: String,
+    keywords: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl CompiledRule {
+    fn matches(&self, query: &str, tokens: &[&str]) -> bool {
+        let keyword_hit = self.keywords.iter().any(|keyword| {
+            if keyword.is_ascii() {
+                tokens.contains(&keyword.as_str())
+            } else {
+                query.contains(keyword.as_str())
+            }
+        });
+        keyword_hit || self.patterns.iter().any(|p| p.is_match(query))
+    }
+}
+
+/// 意图分类器
+#[derive(Debug, Clone)]
+pub struct IntentClassifier {
+    rules: Vec<CompiledRule>,
+}
+
+impl Default for IntentClassifier {
+    fn default() -> Self {
+        Self::new(&default_intent_rules()).expect("default intent rules must compile")
+    }
+}
+
+impl IntentClassifier {
+    /// 根据规则创建分类器
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - 意图规则
+    ///
+    /// # Returns
+    ///
+    /// 正则无效时返回错误
+    pub fn new(rules: &[IntentRule]) -> Result<Self, regex::Error> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let patterns = rule
+                    .patterns
+                    .iter()
+                    .map(|p| Regex::new(&format!("(?i){}", p)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(CompiledRule {
+                    category: rule.category.clone(),
+                    keywords: rule.keywords.iter().map(|k| k.to_lowercase()).collect(),
+                    patterns,
+                })
+            })
+            .collect::<Result<Vec<_>, regex::Error>>()?;
+        Ok(Self { rules })
+    }
+
+    /// 分类查询
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 查询字符串
+    ///
+    /// # Returns
+    ///
+    /// 命中的分类列表（按规则顺序，已去重），未命中返回空列表
+    pub fn classify(&self, query: &str) -> Vec<String> {
+        let lowered = query.to_lowercase();
+        let tokens: Vec<&str> = lowered
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        let mut categories: Vec<String> = Vec::new();
+        for rule in &self.rules {
+            if !categories.contains(&rule.category) && rule.matches(&lowered, &tokens) {
+                categories.push(rule.category.clone());
+            }
+        }
+        categories
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_default_rules() {
+        let classifier = IntentClassifier::default();
+        assert_eq!(classifier.classify("latest election news"), vec!["news"]);
+        assert_eq!(classifier.classify("tokio::spawn panics"), vec!["code"]);
+        assert_eq!(classifier.classify("transformer paper 2017"), vec!["academic"]);
+        assert_eq!(classifier.classify("iphone 价格"), vec!["shopping"]);
+        assert!(classifier.classify("weather in paris").is_empty());
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words() {
+        let classifier = IntentClassifier::default();
+        // "newsletter" 不应命中 "news"
+        assert!(classifier.classify("newsletter template").is_empty());
+    }
+
+    #[test]
+    fn test_multiple_categories() {
+        let classifier = IntentClassifier::default();
+        assert_eq!(classifier.classify("rust news"), vec!["news", "code"]);
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let rules = vec![IntentRule::new("music", &["lyrics"], &[r"\bfeat\.\s"])];
+        let classifier = IntentClassifier::new(&rules).unwrap();
+        assert_eq!(classifier.classify("Song feat. Someone"), vec!["music"]);
+        assert!(classifier.classify("latest news").is_empty());
+
+        let invalid = vec![IntentRule::new("bad", &[], &["("])];
+        assert!(IntentClassifier::new(&invalid).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 结果语言过滤模块
+//!
+//! 引擎有时不理会请求的语言（如 `language=zh` 仍返回英文结果）。标准化阶段为每条结果检测语言
+//! （见 [`LANGUAGE_METADATA_KEY`](super::standardization::LANGUAGE_METADATA_KEY)），
+//! 聚合评分后按请求的语言提升匹配的结果，或移除语言不符的结果
+
+use crate::config::engines::{LanguageFilterConfig, LanguageFilterMode};
+use crate::derive::{SearchQuery, SearchResultItem};
+
+use super::standardization::{item_language, primary_language};
+
+/// 查询参数中标记语言由查询自动检测（而非请求指定）的键
+///
+/// 自动检测的语言只用于引擎路由，不用于过滤结果
+pub const LANGUAGE_DETECTED_PARAM_KEY: &str = "language_detected";
+
+/// 结果语言过滤器
+#[derive(Debug, Clone)]
+pub struct LanguageFilter {
+    /// 处理方式
+    mode: LanguageFilterMode,
+    /// 提升模式下的评分增幅
+    boost: f64,
+}
+
+impl LanguageFilter {
+    /// 根据配置创建过滤器
+    pub fn new(config: &LanguageFilterConfig) -> Self {
+        Self {
+            mode: config.mode,
+            boost: config.boost.max(0.0),
+        }
+    }
+
+    /// 请求指定的语言（主语言部分），未指定或为自动检测时返回 None
+    pub fn requested_language(query: &SearchQuery) -> Option<String> {
+        if query.params.contains_key(LANGUAGE_DETECTED_PARAM_KEY) {
+            return None;
+        }
+        query
+            .language
+            .as_deref()
+            .map(primary_language)
+            .filter(|language| !language.is_empty() && language != "all")
+    }
+
+    /// 按请求语言处理已评分排序的结果
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - 按评分降序排列的结果
+    /// * `language` - 请求语言的主语言部分
+    ///
+    /// # Returns
+    ///
+    /// 移除的结果数（提升模式下为 0）
+    pub fn apply(&self, items: &mut Vec<SearchResultItem>, language: &str) -> usize {
+        let mismatched = |item: &SearchResultItem| {
+            item_language(item).is_some_and(|detected| primary_language(detected) != language)
+        };
+        match self.mode {
+            LanguageFilterMode::Filter => {
+                let before = items.len();
+                items.retain(|item| !mismatched(item));
+                before - items.len()
+            }
+            LanguageFilterMode::Boost => {
+                for item in items.iter_mut() {
+                    if item_language(item).is_some_and(|detected| primary_language(detected) == language) {
+                        item.score *= 1.0 + self.boost;
+                    }
+                }
+                // 稳定排序：同分结果保持原有顺序
+                items.sort_by(|a, b| b.score.total_cmp(&a.score));
+                0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derive::ResultType;
+    use crate::search::standardization::LANGUAGE_METADATA_KEY;
+    use std::collections::HashMap;
+
+    fn item(url: &str, language: Option<&str>, score: f64) -> SearchResultItem {
+        let mut metadata = HashMap::new();
+        if let Some(language) = language {
+            metadata.insert(LANGUAGE_METADATA_KEY.to_string(), language.to_string());
+        }
+        SearchResultItem {
+            title: url.to_string(),
+            url: url.to_string(),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score,
+            result_type: ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata,
+        }
+    }
+
+    fn urls(items: &[SearchResultItem]) -> Vec<&str> {
+        items.iter().map(|item| item.url.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filter_and_boost() {
+        let items = vec![item("en", Some("en"), 1.0), item("zh", Some("zh"), 0.8), item("unknown", None, 0.5)];
+
+        let filter = LanguageFilter::new(&LanguageFilterConfig {
+            enabled: true,
+            mode: LanguageFilterMode::Filter,
+            boost: 0.5,
+        });
+        let mut filtered = items.clone();
+        assert_eq!(filter.apply(&mut filtered, "zh"), 1);
+        assert_eq!(urls(&filtered), vec!["zh", "unknown"]);
+
+        let boost = LanguageFilter::new(&LanguageFilterConfig {
+            enabled: true,
+            mode: LanguageFilterMode::Boost,
+            boost: 0.5,
+        });
+        let mut boosted = items;
+        assert_eq!(boost.apply(&mut boosted, "zh"), 0);
+        assert_eq!(urls(&boosted), vec!["zh", "en", "unknown"]);
+        assert!((boosted[0].score - 1.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_requested_language() {
+        let mut query = SearchQuery { language: Some("zh-CN".to_string()), ..Default::default() };
+        assert_eq!(LanguageFilter::requested_language(&query).as_deref(), Some("zh"));
+
+        query.params.insert(LANGUAGE_DETECTED_PARAM_KEY.to_string(), "true".to_string());
+        assert_eq!(LanguageFilter::requested_language(&query), None);
+
+        let query = SearchQuery { language: Some("all".to_string()), ..Default::default() };
+        assert_eq!(LanguageFilter::requested_language(&query), None);
+    }
+}
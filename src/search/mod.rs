@@ -22,30 +22,122 @@
 //! - 生命周期管理，避免重复创建资源
 //! - 清晰的职责划分，每个组件只负责一个功能
 
+// 查询解析、评分、标准化与聚合（不依赖运行时，wasm32 目标下可用）
 pub mod aggregator;
-pub mod engines;
 pub mod query;
-pub mod types;
 pub mod scoring;
+pub mod explain;
 pub mod standardization;
+pub mod intent;
+pub mod spelling;
+pub mod engine_config;
+pub mod filtering;
+pub mod spam;
+pub mod language;
+pub mod chinese;
+pub mod rewrite;
+pub mod session;
+pub mod processor;
+
+#[cfg(feature = "native")]
+pub mod answers;
+#[cfg(feature = "native")]
+pub mod engines;
+#[cfg(feature = "native")]
+pub mod types;
+#[cfg(feature = "native")]
 pub mod engine_manager;
+#[cfg(feature = "native")]
+pub mod engine_stats;
+#[cfg(feature = "native")]
+pub mod catalog;
+#[cfg(feature = "native")]
+pub mod clicks;
+#[cfg(feature = "native")]
+pub mod bandwidth;
+#[cfg(feature = "native")]
+pub mod translation;
+#[cfg(feature = "native")]
+pub mod enrichment;
+#[cfg(feature = "native")]
+pub mod coalesce;
+#[cfg(feature = "native")]
+pub mod concurrency;
+#[cfg(feature = "native")]
+pub mod geo;
+#[cfg(feature = "native")]
+pub mod archive;
+#[cfg(feature = "native")]
+pub mod saved;
+#[cfg(feature = "native")]
+pub mod debug_capture;
+#[cfg(feature = "native")]
+pub mod dependencies;
+#[cfg(feature = "native")]
+pub mod request_id;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm_plugin;
 
 // 核心组件
-pub mod engine_config;
+#[cfg(feature = "native")]
 pub mod on;
 
 // 统一导出 - 明确导出以避免歧义
-pub use aggregator::{SearchAggregator, AggregationStrategy, SortBy};
-pub use query::{QueryParser, ParsedQuery};
-pub use types::{SearchRequest, SearchResponse, SearchConfig};
-pub use scoring::{BM25Params, ScoringWeights, get_engine_authority, score_results, score_and_sort_results};
-pub use standardization::{clean_text, standardize_item, deduplicate_by_url, standardize_results};
+pub use aggregator::{SearchAggregator, AggregationStrategy, SortBy, ENGINE_METADATA_KEY, SORT_PARAM_KEY};
+pub use query::{QueryParser, ParsedQuery, PiiKind, ScrubResult, DictionarySource};
+pub use spelling::SpellCorrector;
+pub use scoring::{BM25Params, CategoryScoring, ScoringProfile, ScoringWeights, SharedScoringProfile, Tokenizer, get_engine_authority, score_results, score_and_sort_results, score_and_sort_results_with_authority, score_and_sort_results_with_params, score_and_explain_results, ScoreBreakdown};
+pub use explain::{ScoreExplanation, explanation, EXPLAIN_METADATA_KEY, EXPLAIN_PARAM_KEY};
+pub use standardization::{clean_text, clean_text_cow, standardize_item, detect_item_language, item_language, LANGUAGE_METADATA_KEY, deduplicate_by_url, standardize_results, parse_price, normalize_price, CurrencyConverter, HighlightMarkers, highlight_terms, highlight_text, highlight_results};
+pub use intent::{IntentClassifier, IntentRule, default_intent_rules};
+pub use filtering::{ResultFilter, parse_domain_list};
+pub use spam::SpamFilter;
+pub use language::{LanguageFilter, LANGUAGE_DETECTED_PARAM_KEY};
+pub use processor::{FnProcessor, ProcessorError, ResultProcessor, ResultProcessors};
+pub use rewrite::{FnRewriter, QueryRewriter, QueryRewriters, StopWordRewriter, SynonymRewriter};
+pub use session::{apply_session_context, context_terms, requested_context, SESSION_CONTEXT_PARAM_KEY};
+pub use chinese::{ChineseMatching, KeywordMatcher, to_pinyin, to_simplified};
 
 // 引擎配置导出
 pub use engine_config::{EngineListConfig, EngineMode};
 
+#[cfg(feature = "native")]
+pub use types::{SearchRequest, SearchResponse, SearchConfig, EngineTiming};
+
 // 引擎管理器导出（避免全局导出避免冲突）
-pub use engine_manager::{EngineManager, EngineState};
+#[cfg(feature = "native")]
+pub use engine_manager::{EngineManager, EngineState, DEFAULT_SELECTOR_ROT_THRESHOLD};
+#[cfg(feature = "native")]
+pub use catalog::{EngineCatalogEntry, EngineSource};
+#[cfg(feature = "native")]
+pub use engine_stats::{AdaptiveTimeoutConfig, EngineStatsRecord, EngineStatsStore, EngineReport, EngineReportEntry};
+#[cfg(feature = "native")]
+pub use clicks::{ClickCounters, ClickReport, ClickStatsStore, DomainClicks};
+#[cfg(feature = "native")]
+pub use bandwidth::{BandwidthLedger, EngineBandwidth};
+#[cfg(feature = "native")]
+pub use answers::{Answerer, AnswerRegistry, InstantAnswer};
+#[cfg(feature = "native")]
+pub use translation::{Translator, TranslationBackend, TranslationConfig};
+#[cfg(feature = "native")]
+pub use enrichment::{Enricher, PageMetadata};
+#[cfg(feature = "native")]
+pub use coalesce::{SingleFlight, coalesce_key};
+#[cfg(feature = "native")]
+pub use concurrency::{ConcurrencyLimiter, EnginePermit};
+#[cfg(feature = "native")]
+pub use geo::{GeoRouter, normalize_region, routing_region};
+#[cfg(feature = "native")]
+pub use archive::{ArchiveRecord, ArchivedItem, SearchArchive};
+#[cfg(feature = "native")]
+pub use saved::SavedSearchRunner;
+#[cfg(feature = "native")]
+pub use debug_capture::{EngineDebugCapture, DEBUG_CAPTURE_KEY};
+#[cfg(feature = "native")]
+pub use dependencies::unmet_dependencies;
+#[cfg(feature = "native")]
+pub use request_id::{accept_request_id, generate_request_id, request_span, REQUEST_ID_HEADER};
 
 // 主要接口导出
-pub use on::{SearchInterface, SearchStats, SearchStatsResult};
+#[cfg(feature = "native")]
+pub use on::{SearchInterface, SearchStats, SearchStatsResult, QUERY_WARNING_KEY};
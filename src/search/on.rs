@@ -16,17 +16,57 @@
 //!
 //! 提供统一的搜索接口供外部使用
 
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio::time::timeout;
 use futures::stream::{FuturesUnordered, StreamExt};
 
-use super::aggregator::{SearchAggregator, AggregationStrategy, SortBy};
-use super::query::QueryParser;
-use super::types::{SearchConfig, SearchRequest, SearchResponse};
-use super::engine_config::{EngineListConfig, EngineMode};
-use crate::derive::SearchResult;
+use super::aggregator::{SearchAggregator, AggregationStrategy, SortBy, ENGINE_METADATA_KEY};
+use super::query::{DictionarySource, QueryParser};
+use super::types::{EngineTiming, SearchConfig, SearchRequest, SearchResponse};
+use super::engine_config::{exclude_files_engines, prioritize_engines, EngineListConfig, EngineMode};
+use super::geo::{routing_region, GeoRouter};
+use super::engine_stats::{EngineReport, EngineStatsStore};
+use super::catalog::{EngineCatalogEntry, EngineSource};
+use super::clicks::{blend_click_rates, click_through_rate, ClickReport, ClickStatsStore};
+use super::filtering::ResultFilter;
+use super::spam::SpamFilter;
+use super::language::{LanguageFilter, LANGUAGE_DETECTED_PARAM_KEY};
+use super::explain::EXPLAIN_PARAM_KEY;
+use super::session::{context_terms, SESSION_CONTEXT_PARAM_KEY};
+use super::processor::{apply_processors, ResultProcessor, ResultProcessors};
+use super::rewrite::{QueryRewriter, QueryRewriters, StopWordRewriter, SynonymRewriter};
+use super::bandwidth::BandwidthLedger;
+use super::standardization::highlight_results;
+use super::scoring::{get_engine_authority, ScoringProfile, SharedScoringProfile};
+use super::answers::{Answerer, AnswerRegistry};
+use super::intent::IntentClassifier;
+use super::coalesce::{coalesce_key, SingleFlight};
+use super::concurrency::ConcurrencyLimiter;
+use super::archive::SearchArchive;
+use super::debug_capture::{attach_debug_captures, save_capture, EngineDebugCapture};
+use super::dependencies::unmet_dependencies;
+use tracing::Instrument;
+use crate::net::client::capture::capture_responses;
+use crate::net::client::trace::trace_requests;
+use crate::webhook::{WebhookDispatcher, WebhookEvent};
+use crate::net::client::BandwidthRecorder;
+use crate::net::auth::EngineAuth;
+use crate::net::session::EngineSession;
+use crate::net::politeness::{Politeness, PolitenessPolicy};
+use super::translation::{apply_translations, Translator};
+use super::enrichment::Enricher;
+use crate::cache::{CursorCache, EngineCacheStats, EngineSessionStore, NegativeOutcome, PageCache, ResultCache, SearchSessionStore};
+use crate::derive::{EngineType, SearchResult, CURSOR_PARAM_KEY, NEXT_CURSOR_METADATA_KEY};
+
+/// 聚合结果元数据中存放查询警告的键
+pub const QUERY_WARNING_KEY: &str = "query_warning";
+
+/// 构建拼写纠错词典时最多读取的缓存标题数
+const MAX_DICTIONARY_SEED_TITLES: usize = 20_000;
 
 /// 搜索接口
 ///
@@ -38,6 +78,18 @@ pub struct SearchInterface {
     aggregator: SearchAggregator,
     /// 查询解析器
     parser: QueryParser,
+    /// 查询意图分类器
+    intent_classifier: IntentClassifier,
+    /// 即时答案回答器
+    answers: AnswerRegistry,
+    /// 查询改写器（发送到引擎前依次执行）
+    rewriters: QueryRewriters,
+    /// 结果处理器（聚合之后依次执行）
+    processors: ResultProcessors,
+    /// 结果翻译后端
+    translator: Option<Arc<dyn Translator>>,
+    /// 结果元数据补全（未启用时为 None）
+    enricher: Option<Arc<Enricher>>,
     /// HTTP客户端（复用）
     http_client: Arc<crate::net::client::HttpClient>,
     /// 引擎实例缓存
@@ -46,6 +98,106 @@ pub struct SearchInterface {
     engine_states: Arc<RwLock<std::collections::HashMap<String, super::engine_manager::EngineState>>>,
     /// 统计信息
     stats: Arc<SearchStats>,
+    /// 引擎累计统计（持久化到元数据缓存）
+    engine_stats: Arc<EngineStatsStore>,
+    /// 结果点击统计（只含按引擎与域名的累计数）
+    click_stats: Arc<ClickStatsStore>,
+    /// 引擎续页令牌（未启用缓存时为 None，使用游标分页的引擎退回页码分页）
+    cursors: Option<CursorCache>,
+    /// 进行中的引擎请求（用于合并相同的并发查询）
+    inflight: Arc<SingleFlight<SearchResponse>>,
+    /// 引擎请求并发限制（所有搜索共享）
+    limiter: Arc<ConcurrencyLimiter>,
+    /// 搜索结果归档（未启用时为 None）
+    archive: Option<Arc<SearchArchive>>,
+    /// 事件 Webhook 分发器（未启用时为 None）
+    webhooks: Option<Arc<WebhookDispatcher>>,
+    /// 礼貌访问管理器（未启用时为 None）
+    politeness: Option<Arc<Politeness>>,
+    /// 各引擎的认证层（只包含配置了认证的引擎）
+    engine_auth: std::collections::HashMap<String, Arc<EngineAuth>>,
+    /// 各引擎的会话（只包含启用了会话保持的引擎）
+    engine_sessions: std::collections::HashMap<String, Arc<EngineSession>>,
+    /// 依赖不满足的引擎（引擎名 -> 原因，启动时检查）
+    inactive_engines: std::collections::HashMap<String, String>,
+    /// 引擎下载流量账本
+    bandwidth: Arc<BandwidthLedger>,
+    /// 引擎共享的页面缓存（未启用缓存时为 None）
+    pages: Option<PageCache>,
+    /// 搜索结果缓存（未启用缓存时为 None，用于按引擎失效与统计）
+    result_cache: Option<ResultCache>,
+    /// 负面结果缓存（未启用缓存或负面结果缓存时为 None）
+    negative_cache: Option<ResultCache>,
+    /// 结果过滤器（与聚合器共享，支持热重载）
+    result_filter: Arc<ResultFilter>,
+    /// 评分配置档（与聚合器共享，支持运行时替换）
+    scoring_profile: Arc<SharedScoringProfile>,
+    /// 搜索会话存储（未启用缓存或搜索会话时为 None）
+    search_sessions: Option<SearchSessionStore>,
+    /// 引擎地域路由（地域镜像与封锁故障转移）
+    geo: Arc<GeoRouter>,
+    /// 指向镜像服务源的引擎实例缓存
+    mirror_engines: Arc<RwLock<MirrorEngines>>,
+    /// WASM 插件引擎宿主（从 `plugin_paths` 加载）
+    #[cfg(feature = "wasm-plugins")]
+    plugins: Arc<super::wasm_plugin::WasmPluginHost>,
+}
+
+/// 镜像服务源引擎实例表，键为（引擎名, 镜像源）
+type MirrorEngines = std::collections::HashMap<(String, String), Arc<dyn crate::derive::SearchEngine + Send + Sync>>;
+
+/// 一个引擎本次请求可用的服务源（按地域路由的尝试顺序）
+struct EngineRoute {
+    /// （镜像源, 引擎实例），镜像源为 None 表示引擎的主服务源
+    candidates: Vec<(Option<String>, Arc<dyn crate::derive::SearchEngine + Send + Sync>)>,
+    /// 地域路由器（记录被封锁的服务源）
+    geo: Arc<GeoRouter>,
+}
+
+impl EngineRoute {
+    /// 依次尝试各服务源，服务源被封锁（403/429）导致失败时转移到下一个服务源
+    ///
+    /// 被封锁的服务源会被记录，后续请求在冷却期内跳过它
+    ///
+    /// # Returns
+    ///
+    /// 返回最后一次尝试的结果、调试捕获与执行明细（明细中记录实际使用的镜像源）
+    async fn run(
+        &self,
+        engine_name: &str,
+        query: &crate::derive::SearchQuery,
+        timeout_duration: Duration,
+        stats: &SearchStats,
+        debug_capture: bool,
+        capture_dir: Option<&Path>,
+    ) -> (Result<SearchResult, String>, Option<EngineDebugCapture>, EngineTiming) {
+        let mut remaining = self.candidates.len();
+        let mut attempt = None;
+        for (endpoint, engine) in &self.candidates {
+            remaining -= 1;
+            let (outcome, debug, mut timing) = SearchInterface::run_engine(
+                Arc::clone(engine),
+                engine_name,
+                query,
+                timeout_duration,
+                stats,
+                debug_capture,
+                capture_dir,
+            )
+            .await;
+            timing.endpoint = endpoint.clone();
+            if timing.blocked {
+                self.geo.mark_blocked(engine_name, endpoint.as_deref());
+            }
+            let failed_over = timing.blocked && outcome.is_err() && remaining > 0;
+            attempt = Some((outcome, debug, timing));
+            if !failed_over {
+                break;
+            }
+            tracing::info!("Engine {} blocked on {}, trying next endpoint", engine_name, endpoint.as_deref().unwrap_or("primary"));
+        }
+        attempt.expect("engine route has at least one endpoint")
+    }
 }
 
 impl SearchInterface {
@@ -61,8 +213,36 @@ impl SearchInterface {
     pub fn new(
         config: SearchConfig,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let aggregator = SearchAggregator::default();
-        let parser = QueryParser::default();
+        let result_filter = Arc::new(
+            ResultFilter::from_config(&config.result_filtering)
+                .map_err(|e| format!("Invalid result filter: {}", e))?,
+        );
+        let scoring_profile = Arc::new(
+            SharedScoringProfile::new(config.scoring.clone())
+                .map_err(|e| format!("Invalid scoring profile: {}", e))?,
+        );
+        let mut aggregator = SearchAggregator::new(config.aggregation.clone(), SortBy::Relevance)
+            .with_result_filter(Arc::clone(&result_filter))
+            .with_scoring_profile(Arc::clone(&scoring_profile));
+        if let Some(ref converter) = config.currency_conversion {
+            aggregator = aggregator.with_currency_conversion(converter.clone());
+        }
+        if config.spam_filter.enabled {
+            aggregator = aggregator.with_spam_filter(SpamFilter::new(&config.spam_filter));
+        }
+        if config.language_filter.enabled {
+            aggregator = aggregator.with_language_filter(LanguageFilter::new(&config.language_filter));
+        }
+        if let Some(ref sessions) = config.sessions {
+            aggregator = aggregator.with_session_boost(sessions.boost);
+        }
+        let parser = QueryParser::default()
+            .with_pii_scrubbing(config.scrub_query_pii)
+            .with_chinese_matching(config.chinese_matching)
+            .with_custom_patterns(&config.query_scrub_patterns)
+            .map_err(|e| format!("Invalid query scrub pattern: {}", e))?;
+        let intent_classifier = IntentClassifier::new(&config.intent_rules)
+            .map_err(|e| format!("Invalid intent rule pattern: {}", e))?;
 
         // 创建共享HTTP客户端以提高性能
         let network_config = crate::net::types::NetworkConfig::default();
@@ -70,18 +250,238 @@ impl SearchInterface {
             crate::net::client::HttpClient::new(network_config)
                 .map_err(|e| format!("Failed to create HTTP client: {}", e))?
         );
+        let translator = config
+            .translation
+            .as_ref()
+            .map(|translation| translation.backend.build(Arc::clone(&http_client)));
+        #[cfg(feature = "wasm-plugins")]
+        let plugins = {
+            let mut host = super::wasm_plugin::WasmPluginHost::new()
+                .map_err(|e| format!("Failed to initialize WASM plugin host: {}", e))?;
+            host.load_dirs(&config.plugin_paths);
+            Arc::new(host)
+        };
+        let answers = if config.enable_instant_answers {
+            AnswerRegistry::with_defaults(Arc::clone(&http_client), config.currency_conversion.as_ref())
+        } else {
+            AnswerRegistry::new()
+        };
+
+        // 启用缓存时将引擎统计持久化到元数据缓存，打开失败则退回内存统计
+        let cache = if config.enable_cache {
+            match crate::cache::CacheInterface::new(crate::cache::CacheImplConfig::default()) {
+                Ok(cache) => Some(cache),
+                Err(e) => {
+                    tracing::warn!("Failed to open cache for engine stats, keeping them in memory: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let engine_stats = match cache {
+            Some(ref cache) => EngineStatsStore::with_cache(cache.metadata()),
+            None => EngineStatsStore::in_memory(),
+        };
+        let click_stats = match cache {
+            Some(ref cache) => ClickStatsStore::with_cache(cache.metadata()),
+            None => ClickStatsStore::in_memory(),
+        };
+        let cursors = cache.as_ref().map(|cache| cache.cursors());
+        let pages = cache.as_ref().and_then(|cache| match cache.pages() {
+            Ok(pages) => Some(pages),
+            Err(e) => {
+                tracing::warn!("Failed to open page cache: {}", e);
+                None
+            }
+        });
+        let metadata_cache = cache.as_ref().map(|cache| cache.metadata());
+        let result_cache = cache.as_ref().map(|cache| cache.results());
+        let negative_cache = result_cache
+            .clone()
+            .filter(|_| config.negative_cache_ttl.is_some());
+        let bandwidth = Arc::new(BandwidthLedger::new(
+            cache.as_ref().map(|cache| cache.metadata()),
+            config.daily_bandwidth_quota,
+            config.engine_bandwidth_quotas.clone(),
+        ));
+
+        // 拼写纠错词典在首次需要时从缓存的结果标题构建
+        let parser = if config.enable_spell_correction {
+            let source = cache.map(|cache| {
+                let results = cache.results();
+                Arc::new(move || results.cached_titles(Some(MAX_DICTIONARY_SEED_TITLES)).unwrap_or_default())
+                    as DictionarySource
+            });
+            parser.with_spell_correction(source)
+        } else {
+            parser
+        };
+
+        let limiter = Arc::new(ConcurrencyLimiter::new(
+            config.max_concurrent_engines,
+            &config.engine_concurrency,
+        ));
+
+        // 归档打开失败不影响搜索
+        let archive = config.archive.as_ref().and_then(|archive| match SearchArchive::open(archive) {
+            Ok(archive) => Some(Arc::new(archive)),
+            Err(e) => {
+                tracing::warn!("Failed to open search archive {}: {}", archive.path, e);
+                None
+            }
+        });
+
+        let webhooks = match config.webhooks.clone() {
+            Some(webhooks) => Some(Arc::new(
+                WebhookDispatcher::new(webhooks)
+                    .map_err(|e| format!("Failed to create webhook dispatcher: {}", e))?,
+            )),
+            None => None,
+        };
+
+        let politeness = config
+            .politeness
+            .clone()
+            .map(|politeness| Arc::new(Politeness::new(politeness)));
+        let geo = Arc::new(GeoRouter::new(config.engine_geo.clone()));
+        let engine_auth = config
+            .engine_settings
+            .iter()
+            .filter_map(|(name, settings)| {
+                let auth = EngineAuth::from_config(settings.authentication.as_ref()?)?;
+                Some((name.clone(), Arc::new(auth)))
+            })
+            .collect();
+        let session_store = metadata_cache.clone().map(EngineSessionStore::new);
+        let search_sessions = config.sessions.as_ref().and_then(|sessions| {
+            let metadata = metadata_cache.clone()?;
+            Some(SearchSessionStore::new(
+                metadata,
+                Duration::from_secs(sessions.ttl_secs),
+                sessions.max_queries,
+            ))
+        });
+        let engine_sessions = config
+            .engine_sessions
+            .iter()
+            .map(|(name, session)| {
+                let session = EngineSession::new(name, session, session_store.clone());
+                (name.clone(), Arc::new(session))
+            })
+            .collect();
+        let inactive_engines = config
+            .engine_dependencies
+            .iter()
+            .filter_map(|(name, dependencies)| {
+                let reason = unmet_dependencies(name, dependencies, config.engine_settings.get(name))?;
+                tracing::warn!("Engine {} marked inactive: {}", name, reason);
+                Some((name.clone(), reason))
+            })
+            .collect();
+
+        let mut rewriters = QueryRewriters::new();
+        if config.trim_stop_words {
+            rewriters.register(Arc::new(StopWordRewriter::default()));
+        }
+        if config.expand_synonyms {
+            rewriters.register(Arc::new(SynonymRewriter::default()));
+        }
+
+        // 结果页面获取与引擎请求遵循相同的礼貌访问设置
+        let enricher = config.enrichment.clone().map(|enrichment| {
+            let client = match politeness {
+                Some(ref politeness) => Arc::new(http_client.with_politeness(Arc::clone(politeness))),
+                None => Arc::clone(&http_client),
+            };
+            Arc::new(Enricher::new(enrichment, client, metadata_cache.clone()))
+        });
 
         Ok(Self {
             config,
             aggregator,
             parser,
+            intent_classifier,
+            answers,
+            rewriters,
+            processors: ResultProcessors::new(),
+            translator,
+            enricher,
             http_client,
             engine_cache: Arc::new(RwLock::new(std::collections::HashMap::new())),
             engine_states: Arc::new(RwLock::new(std::collections::HashMap::new())),
             stats: Arc::new(SearchStats::default()),
+            engine_stats: Arc::new(engine_stats),
+            click_stats: Arc::new(click_stats),
+            cursors,
+            inflight: Arc::new(SingleFlight::new()),
+            limiter,
+            archive,
+            webhooks,
+            politeness,
+            engine_auth,
+            engine_sessions,
+            inactive_engines,
+            bandwidth,
+            pages,
+            result_cache,
+            negative_cache,
+            result_filter,
+            scoring_profile,
+            search_sessions,
+            geo,
+            mirror_engines: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            #[cfg(feature = "wasm-plugins")]
+            plugins,
         })
     }
 
+    /// 设置结果翻译后端（覆盖配置中的后端）
+    ///
+    /// # Arguments
+    ///
+    /// * `translator` - 翻译后端
+    ///
+    /// # Returns
+    ///
+    /// 返回设置后的搜索接口
+    pub fn with_translator(mut self, translator: Arc<dyn Translator>) -> Self {
+        self.translator = Some(translator);
+        self
+    }
+
+    /// 注册自定义即时答案回答器（排在内置回答器之后）
+    ///
+    /// # Arguments
+    ///
+    /// * `answerer` - 回答器
+    ///
+    /// # Returns
+    ///
+    /// 返回注册后的搜索接口
+    pub fn with_answerer(mut self, answerer: Arc<dyn Answerer>) -> Self {
+        self.answers.register(answerer);
+        self
+    }
+
+    /// 注册自定义查询改写器（排在内置改写器之后）
+    ///
+    /// # Arguments
+    ///
+    /// * `rewriter` - 改写器
+    pub fn add_rewriter(&mut self, rewriter: Arc<dyn QueryRewriter>) {
+        self.rewriters.register(rewriter);
+    }
+
+    /// 注册自定义结果处理器（按注册顺序执行）
+    ///
+    /// # Arguments
+    ///
+    /// * `processor` - 处理器
+    pub fn add_processor(&mut self, processor: Arc<dyn ResultProcessor>) {
+        self.processors.register(processor);
+    }
+
     /// 执行搜索
     ///
     /// # Arguments
@@ -95,35 +495,57 @@ impl SearchInterface {
         &self,
         request: &SearchRequest,
     ) -> Result<SearchResponse, Box<dyn std::error::Error + Send + Sync>> {
-        // 解析查询
-        let _parsed = self.parser.parse(&request.query.query);
+        self.run_search(request, None).await
+    }
+
+    /// 执行搜索
+    ///
+    /// `aggregation` 为本次搜索的聚合策略与排序方式（None 时使用聚合器的默认设置）；
+    /// 抢先返回策略等待指定数量的引擎后立即返回
+    async fn run_search(
+        &self,
+        request: &SearchRequest,
+        aggregation: Option<(&AggregationStrategy, SortBy)>,
+    ) -> Result<SearchResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let race = aggregation.and_then(|(strategy, _)| strategy.race_quorum());
+        // 清洗敏感信息并检测查询语言
+        let (prepared_request, warning, related_queries) = self.prepare_request(request).await;
+        let request = &prepared_request;
 
         // 确定要使用的引擎列表
         let engines_to_use = if request.engines.is_empty() {
-            // 如果没有指定引擎，使用默认全局引擎
-            let all_engines = EngineListConfig::get_default_engines();
-            all_engines
+            // 如果没有指定引擎，使用默认全局引擎（按查询意图与语言调整）
+            let engines = self.route_by_intent(EngineListConfig::get_default_engines(), &request.query).await;
+            let engines = self.route_by_language(engines, &request.query);
+            self.route_by_region(engines, &request.query)
         } else {
             // 使用请求中指定的引擎列表（验证可用性）
-            let config = EngineListConfig::default();
-            config.filter_available_engines(&request.engines)
+            self.filter_requested_engines(&request.engines)
         };
 
+        // 未开放文件分类时移除种子引擎
+        let engines_to_use = self.gate_files_category(engines_to_use);
+
         if engines_to_use.is_empty() {
             return Err("No available engines".into());
         }
 
-        // 执行并发搜索
-        let mut response = self.execute_concurrent_search(request, &engines_to_use).await?;
+        // 执行并发搜索，同时尝试生成即时答案
+        let (response, answer) = tokio::join!(
+            self.execute_concurrent_search(request, &engines_to_use, race),
+            self.answers.answer(&request.query.query),
+        );
+        let (mut response, captures) = response?;
+        response.answer = answer;
 
         // 对结果进行聚合、评分和排序（无论有几个结果）
-        let aggregated = self.aggregator.aggregate_with_scoring(
+        let aggregated = self.aggregate_results(
             response.results.clone(),
-            &request.query
-        );
-        response.total_count = aggregated.items.len();
-        // 用聚合后的结果替换原始结果
-        response.results = vec![aggregated];
+            &request.query,
+            None,
+            aggregation,
+        ).await;
+        self.finalize_response(&mut response, request, aggregated, warning, captures, related_queries).await;
 
         Ok(response)
     }
@@ -133,43 +555,69 @@ impl SearchInterface {
     /// # Arguments
     ///
     /// * `request` - 搜索请求
-    /// * `mode` - 引擎模式（全局/中国/自定义）
+    /// * `mode` - 引擎模式（全局/自定义/分类）；分类模式使用该分类的默认引擎，
+    ///   分类与引擎权重参与聚合评分
     ///
     /// # Returns
     ///
-    /// 返回搜索响应或错误
+    /// 返回搜索响应或错误，分类不存在或未启用时返回错误
     pub async fn search_with_mode(
         &self,
         request: &SearchRequest,
         mode: EngineMode,
     ) -> Result<SearchResponse, Box<dyn std::error::Error + Send + Sync>> {
-        // 解析查询
-        let _parsed = self.parser.parse(&request.query.query);
+        // 清洗敏感信息并检测查询语言
+        let (prepared_request, warning, related_queries) = self.prepare_request(request).await;
+        let request = &prepared_request;
 
-        // 根据模式获取引擎列表
+        // 根据模式获取引擎列表（分类模式同时确定引擎权重）
         let engine_config = EngineListConfig::default();
-        let engines_to_use = engine_config.get_engines_for_mode(&mode);
+        let (engines_to_use, weights) = match mode {
+            EngineMode::Category(ref name) => {
+                let category = self
+                    .config
+                    .categories
+                    .get(name)
+                    .ok_or_else(|| format!("Unknown or disabled category: {}", name))?;
+                let engines = self.filter_requested_engines(&category.default_engines);
+                (engines, Some(category.engine_multipliers()))
+            }
+            _ => (engine_config.get_engines_for_mode(&mode), None),
+        };
+
+        // 未开放文件分类时移除种子引擎
+        let engines_to_use = self.gate_files_category(engines_to_use);
 
         if engines_to_use.is_empty() {
             return Err("No available engines for this mode".into());
         }
 
-        // 执行并发搜索
-        let mut response = self.execute_concurrent_search(request, &engines_to_use).await?;
+        // 执行并发搜索，同时尝试生成即时答案
+        let (response, answer) = tokio::join!(
+            self.execute_concurrent_search(request, &engines_to_use, None),
+            self.answers.answer(&request.query.query),
+        );
+        let (mut response, captures) = response?;
+        response.answer = answer;
 
         // 对结果进行聚合、评分和排序（无论有几个结果）
-        let aggregated = self.aggregator.aggregate_with_scoring(
+        let aggregated = self.aggregate_results(
             response.results.clone(),
-            &request.query
-        );
-        response.total_count = aggregated.items.len();
-        response.results = vec![aggregated];
+            &request.query,
+            weights.as_ref(),
+            None,
+        ).await;
+        self.finalize_response(&mut response, request, aggregated, warning, captures, related_queries).await;
 
         Ok(response)
     }
 
     /// 带选项执行搜索
     ///
+    /// 结果按指定的聚合策略排列、按指定的排序方式排序，不影响其他搜索使用的默认设置。
+    /// 使用 [`AggregationStrategy::Race`] 时，指定数量的引擎返回结果后立即返回（响应标记 `partial`），
+    /// 其余引擎在后台继续执行以预热页面缓存
+    ///
     /// # Arguments
     ///
     /// * `request` - 搜索请求
@@ -182,10 +630,39 @@ impl SearchInterface {
     pub async fn search_with_options(
         &self,
         request: &SearchRequest,
-        _strategy: AggregationStrategy,
-        _sort_by: SortBy,
+        strategy: AggregationStrategy,
+        sort_by: SortBy,
+    ) -> Result<SearchResponse, Box<dyn std::error::Error + Send + Sync>> {
+        self.run_search(request, Some((&strategy, sort_by))).await
+    }
+
+    /// 获取聚合结果的下一页
+    ///
+    /// 只向上一页实际返回结果的引擎请求，使用游标分页的引擎沿用上一页保存的续页令牌，
+    /// 因此同一请求多次翻页得到确定的结果序列
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - 上一页的搜索请求
+    /// * `previous` - 上一页的搜索响应
+    ///
+    /// # Returns
+    ///
+    /// 返回下一页的搜索响应或错误
+    pub async fn search_next_page(
+        &self,
+        request: &SearchRequest,
+        previous: &SearchResponse,
     ) -> Result<SearchResponse, Box<dyn std::error::Error + Send + Sync>> {
-        self.search(request).await
+        if previous.engines_used.is_empty() {
+            return Err("No engines returned results for the previous page".into());
+        }
+
+        let mut next = request.clone();
+        next.query.page = request.query.page.max(1) + 1;
+        next.query.params.remove(CURSOR_PARAM_KEY);
+        next.engines = previous.engines_used.clone();
+        self.search(&next).await
     }
 
     /// 流式搜索 - 哪个搜索引擎先完成就先返回哪个的结果
@@ -213,18 +690,22 @@ impl SearchInterface {
         
         let start_time = std::time::Instant::now();
 
-        // 解析查询
-        let _parsed = self.parser.parse(&request.query.query);
+        // 清洗敏感信息并检测查询语言
+        let (prepared_request, warning, related_queries) = self.prepare_request(request).await;
+        let request = &prepared_request;
 
         // 确定要使用的引擎列表
         let engines_to_use = if request.engines.is_empty() {
-            let all_engines = EngineListConfig::get_default_engines();
-            all_engines
+            let engines = self.route_by_intent(EngineListConfig::get_default_engines(), &request.query).await;
+            let engines = self.route_by_language(engines, &request.query);
+            self.route_by_region(engines, &request.query)
         } else {
-            let config = EngineListConfig::default();
-            config.filter_available_engines(&request.engines)
+            self.filter_requested_engines(&request.engines)
         };
 
+        // 未开放文件分类时移除种子引擎
+        let engines_to_use = self.gate_files_category(engines_to_use);
+
         if engines_to_use.is_empty() {
             return Err("No available engines".into());
         }
@@ -241,6 +722,8 @@ impl SearchInterface {
         // 创建 FuturesUnordered 用于流式处理
         let mut futures_unordered = FuturesUnordered::new();
         let mut engines_to_execute = Vec::new();
+        let region = routing_region(&request.query);
+        let mut per_engine = Vec::new();
 
         // 获取所有要执行的引擎实例
         for engine_name in &engines_to_use {
@@ -249,69 +732,121 @@ impl SearchInterface {
                 let states = self.engine_states.read().await;
                 if let Some(state) = states.get(engine_name) {
                     if !state.is_available() {
+                        per_engine.push(EngineTiming::skipped(engine_name, "temporarily disabled"));
                         continue;
                     }
                 }
             }
+            // 依赖不满足的引擎
+            if let Some(reason) = self.inactive_engines.get(engine_name) {
+                per_engine.push(EngineTiming::skipped(engine_name, format!("inactive: {}", reason)));
+                continue;
+            }
+            // 当日流量配额用尽的引擎暂停到次日
+            if self.bandwidth.is_exhausted(engine_name) {
+                per_engine.push(EngineTiming::skipped(engine_name, "daily bandwidth quota exhausted"));
+                continue;
+            }
+            // 冷却期内对相同查询返回过负面结果的引擎
+            if let Some(outcome) = self.cached_negative(request, engine_name).await {
+                per_engine.push(EngineTiming::skipped(
+                    engine_name,
+                    format!("cached negative outcome: {}", outcome.as_str()),
+                ));
+                continue;
+            }
             match self.get_or_create_engine(engine_name).await {
                 Ok(engine) => {
-                    engines_to_execute.push((engine_name.clone(), engine));
+                    let route = self.engine_route(engine_name, engine, region.as_deref()).await;
+                    engines_to_execute.push((engine_name.clone(), route));
                 }
-                Err(_e) => {
+                Err(e) => {
                     self.stats.engine_failures.fetch_add(1, Ordering::Relaxed);
+                    per_engine.push(EngineTiming::skipped(engine_name, e.to_string()));
                 }
             }
         }
 
-        // 创建并发任务
-        for (engine_name, engine) in engines_to_execute {
-            let query = request.query.clone();
-            let timeout_duration = Duration::from_secs(self.config.default_timeout.as_secs());
+        // 创建并发任务（按引擎历史延迟确定超时）
+        let timeouts = self.engine_timeouts(&engines_to_execute).await;
+        let launched: Vec<String> = engines_to_execute.iter().map(|(name, _)| name.clone()).collect();
+        for (engine_name, route) in engines_to_execute {
+            let query = self.query_with_cursor(&request.query, &engine_name);
+            let timeout_duration = timeouts.get(&engine_name).copied().unwrap_or(self.config.default_timeout);
             let stats = Arc::clone(&self.stats);
+            let limiter = Arc::clone(&self.limiter);
+            let debug_capture = request.debug_capture;
+            let capture_dir = self.config.debug_capture_dir.clone();
+            let span = tracing::debug_span!("engine", engine = %engine_name);
             
             let future = async move {
-                let search_start = std::time::Instant::now();
-                match timeout(timeout_duration, engine.search(&query)).await {
-                    Ok(Ok(mut result)) => {
-                        result.elapsed_ms = search_start.elapsed().as_millis() as u64;
-                        Some((Ok(result), engine_name))
-                    }
-                    Ok(Err(e)) => {
-                        stats.engine_failures.fetch_add(1, Ordering::Relaxed);
-                        Some((Err(format!("Engine {} error: {}", engine_name, e)), engine_name))
-                    }
-                    Err(_) => {
-                        stats.timeouts.fetch_add(1, Ordering::Relaxed);
-                        Some((Err(format!("Engine {} timeout", engine_name)), engine_name))
-                    }
-                }
+                // 超出并发上限时排队，排队时间不计入引擎超时
+                let _permit = limiter.acquire(&engine_name).await;
+                let (outcome, debug, timing) = route.run(
+                    &engine_name,
+                    &query,
+                    timeout_duration,
+                    &stats,
+                    debug_capture,
+                    capture_dir.as_deref(),
+                )
+                .await;
+                Some((outcome, engine_name, debug, timing))
             };
             
-            futures_unordered.push(future);
+            futures_unordered.push(future.instrument(span));
         }
 
         // 流式处理结果
         let mut successful_results = Vec::new();
         let mut engines_used = Vec::new();
 
-        while let Some(result) = futures_unordered.next().await {
-            if let Some((search_result, engine_name)) = result {
+        let mut captures = BTreeMap::new();
+
+        // 延迟预算到期时放弃尚未完成的引擎
+        let deadline = latency_deadline(request, start_time);
+        let mut partial = false;
+        loop {
+            let result = match next_within(&mut futures_unordered, deadline).await {
+                Ok(Some(result)) => result,
+                Ok(None) => break,
+                Err(_) => {
+                    partial = true;
+                    break;
+                }
+            };
+            if let Some((search_result, engine_name, debug, timing)) = result {
+                if let Some(debug) = debug {
+                    captures.insert(engine_name.clone(), debug);
+                }
+                self.record_negative(&request.query, &engine_name, &search_result, &timing).await;
+                per_engine.push(timing);
                 match search_result {
                     Ok(result) => {
+                        self.engine_stats
+                            .record_success(&engine_name, result.elapsed_ms, result.items.len())
+                            .await;
+                        self.store_next_cursor(&request.query, &engine_name, &result);
+
                         // 检查是否为零结果
                         let is_zero_results = result.items.is_empty();
 
+                        let scraped = self.is_scraped_engine(&engine_name).await;
+
                         if is_zero_results {
                             // 零结果，更新引擎状态
                             let mut states = self.engine_states.write().await;
                             if let Some(state) = states.get_mut(&engine_name) {
-                                state.record_zero_results();
+                                let disabled_for = state.record_zero_results();
+                                self.notify_engine_disabled(state, disabled_for);
+                                self.check_selector_rot(state, &request.query.query, 0, scraped);
                             }
                         } else {
                             // 有结果，记录成功
                             let mut states = self.engine_states.write().await;
                             if let Some(state) = states.get_mut(&engine_name) {
                                 state.record_success(result.elapsed_ms);
+                                self.check_selector_rot(state, &request.query.query, result.items.len(), scraped);
                             }
                             
                             // 立即回调返回结果
@@ -321,16 +856,22 @@ impl SearchInterface {
                             engines_used.push(engine_name);
                         }
                     }
-                    Err(_e) => {
+                    Err(e) => {
                         // 错误处理
                         self.stats.engine_failures.fetch_add(1, Ordering::Relaxed);
+                        self.engine_stats.record_failure(&engine_name, &e).await;
                     }
                 }
             }
         }
 
+        drop(futures_unordered);
+
         let total_count = successful_results.iter().map(|r| r.items.len()).sum();
         let query_time_ms = start_time.elapsed().as_millis() as u64;
+        if partial {
+            mark_unfinished(&mut per_engine, &launched, query_time_ms, LATENCY_BUDGET_EXCEEDED);
+        }
 
         // 聚合最终结果
         let mut response = SearchResponse {
@@ -340,15 +881,22 @@ impl SearchInterface {
             engines_used,
             query_time_ms,
             cached: false,
+            answer: self.answers.answer(&request.query.query).await,
+            suggestions: Vec::new(),
+            per_engine,
+            partial,
+            related_queries: Vec::new(),
+            request_id: None,
         };
 
         // 对结果进行聚合、评分和排序
-        let aggregated = self.aggregator.aggregate_with_scoring(
+        let aggregated = self.aggregate_results(
             response.results.clone(),
-            &request.query
-        );
-        response.total_count = aggregated.items.len();
-        response.results = vec![aggregated];
+            &request.query,
+            None,
+            None,
+        ).await;
+        self.finalize_response(&mut response, request, aggregated, warning, captures, related_queries).await;
 
         Ok(response)
     }
@@ -381,15 +929,12 @@ impl SearchInterface {
         let cache_interface = CacheInterface::new(cache_config)
             .map_err(|e| format!("Failed to create cache interface: {}", e))?;
         
-        // 从查询中提取关键词
-        let query_keywords: Vec<String> = request.query.query
-            .split_whitespace()
-            .map(|s| s.to_string())
-            .collect();
+        // 从查询中提取关键词（按配置统一繁简字形并允许拼音匹配）
+        let matcher = self.parser.keyword_matcher(&request.query.query);
         
         // 从结果缓存搜索历史结果
         let result_cache = cache_interface.results();
-        let cached_items = match result_cache.search_fulltext(&query_keywords, true, Some(50)) {
+        let cached_items = match result_cache.search_fulltext_async(&matcher, true, Some(50)).await {
             Ok(items) => items,
             Err(e) => {
                 // 记录错误但不中断搜索流程
@@ -400,7 +945,7 @@ impl SearchInterface {
         
         // 从 RSS 缓存搜索相关内容
         let rss_cache = cache_interface.rss();
-        let rss_items = match rss_cache.search_fulltext(&query_keywords, true, Some(30)) {
+        let rss_items = match rss_cache.search_fulltext_async(&matcher, true, Some(30)).await {
             Ok(items) => items,
             Err(e) => {
                 // 记录错误但不中断搜索流程
@@ -461,20 +1006,9 @@ impl SearchInterface {
         for item in &mut deduped_items {
             let mut score = item.score;
             
-            // 根据关键词在标题和内容中的出现次数调整得分
-            for keyword in &query_keywords {
-                let keyword_lower = keyword.to_lowercase();
-                
-                // 标题匹配权重更高
-                if item.title.to_lowercase().contains(&keyword_lower) {
-                    score += 0.3;
-                }
-                
-                // 内容匹配
-                if item.content.to_lowercase().contains(&keyword_lower) {
-                    score += 0.1;
-                }
-            }
+            // 根据标题和内容匹配的关键词个数调整得分（标题匹配权重更高）
+            score += 0.3 * matcher.count_matches(&item.title) as f64;
+            score += 0.1 * matcher.count_matches(&item.content) as f64;
             
             // 限制最大得分
             item.score = score.min(1.0);
@@ -513,9 +1047,306 @@ impl SearchInterface {
             engines_used,
             query_time_ms,
             cached: false, // 混合了网络和缓存结果
+            answer: None,
+            suggestions: Vec::new(),
+            per_engine: network_response.per_engine,
+            partial: network_response.partial,
+            related_queries: Vec::new(),
+            request_id: network_response.request_id,
         })
     }
 
+    /// 预处理请求：清洗敏感信息、补全查询语言、记录搜索会话并执行查询改写
+    ///
+    /// 返回发送给引擎的请求副本、需要附加到响应中的警告以及会话中的相关查询
+    async fn prepare_request(&self, request: &SearchRequest) -> (SearchRequest, Option<String>, Vec<String>) {
+        let mut prepared = request.clone();
+        let result = self.parser.scrub(&request.query.query);
+        let warning = if result.is_modified() {
+            tracing::warn!("Removed sensitive content from query before dispatching to engines");
+            let warning = result.warning();
+            prepared.query.query = result.query;
+            warning
+        } else {
+            None
+        };
+
+        // 未指定语言时使用检测结果（标记为自动检测，结果语言过滤不据此移除结果）
+        if prepared.query.language.is_none() {
+            prepared.query.language = self.parser.parse(&prepared.query.query).language;
+            if prepared.query.language.is_some() {
+                prepared.query.params.insert(LANGUAGE_DETECTED_PARAM_KEY.to_string(), "true".to_string());
+            }
+        }
+
+        if prepared.explain {
+            prepared.query.params.insert(EXPLAIN_PARAM_KEY.to_string(), "true".to_string());
+        }
+
+        // 记录改写前的查询，避免同义词扩展等改写出现在相关查询中
+        let related_queries = self.record_session(&mut prepared).await;
+
+        let applied = self.rewriters.apply(&mut prepared.query).await;
+        if !applied.is_empty() {
+            tracing::debug!("Query rewritten by {:?}: {}", applied, prepared.query.query);
+        }
+
+        (prepared, warning, related_queries)
+    }
+
+    /// 在请求的搜索会话中记录查询，并附加会话上下文词供聚合时重排
+    ///
+    /// 返回会话中之前的查询（最近的在前）；未携带会话令牌、未启用搜索会话或读写失败时返回空列表
+    async fn record_session(&self, request: &mut SearchRequest) -> Vec<String> {
+        let (Some(store), Some(config), Some(token)) =
+            (&self.search_sessions, &self.config.sessions, request.session.as_deref())
+        else {
+            return Vec::new();
+        };
+        let session = match store.record_async(token, &request.query.query).await {
+            Ok(session) => session,
+            Err(e) => {
+                tracing::warn!("Failed to record search session: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let current = &request.query.query;
+        let terms = context_terms(session.previous_queries(current), &request.query, config.max_context_terms);
+        if !terms.is_empty() {
+            request.query.params.insert(SESSION_CONTEXT_PARAM_KEY.to_string(), terms.join(" "));
+        }
+        session
+            .previous_queries(current)
+            .take(config.max_related_queries)
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// 过滤请求或配置中指定的引擎，保留可用的内置引擎与已加载的 WASM 插件引擎
+    fn filter_requested_engines(&self, engines: &[String]) -> Vec<String> {
+        let config = EngineListConfig::default();
+        engines
+            .iter()
+            .filter(|engine| config.is_engine_available(engine) || self.is_plugin_engine(engine))
+            .cloned()
+            .collect()
+    }
+
+    /// 是否为已加载的 WASM 插件引擎（未启用 `wasm-plugins` 特性时总是 false）
+    fn is_plugin_engine(&self, engine: &str) -> bool {
+        #[cfg(feature = "wasm-plugins")]
+        {
+            self.plugins.contains(engine)
+        }
+        #[cfg(not(feature = "wasm-plugins"))]
+        {
+            let _ = engine;
+            false
+        }
+    }
+
+    /// 按查询意图选择引擎
+    ///
+    /// 查询命中意图分类时，只向注册在这些分类下的引擎以及通用（general）引擎分发；
+    /// 未启用、未命中或没有对应引擎时保持原列表
+    async fn route_by_intent(&self, engines: Vec<String>, query: &crate::derive::SearchQuery) -> Vec<String> {
+        if !self.config.enable_intent_routing {
+            return engines;
+        }
+        let categories = self.intent_classifier.classify(&query.query);
+        if categories.is_empty() {
+            return engines;
+        }
+
+        let mut matched = Vec::new();
+        let mut general = Vec::new();
+        for name in EngineListConfig::default().all_available_engines {
+            let engine = match self.get_or_create_engine(&name).await {
+                Ok(engine) => engine,
+                Err(_) => continue,
+            };
+            let engine_categories = &engine.info().categories;
+            if engine_categories.iter().any(|c| categories.contains(c)) {
+                matched.push(name);
+            } else if engines.contains(&name) && engine_categories.iter().any(|c| c == "general") {
+                general.push(name);
+            }
+        }
+
+        if matched.is_empty() {
+            return engines;
+        }
+        tracing::debug!("Query intent {:?} routed to engines {:?}", categories, matched);
+        prioritize_engines(general, &matched)
+    }
+
+    /// 按查询语言调整引擎优先级
+    ///
+    /// `SearchConfig::language_engine_priority` 中为该语言配置的引擎（若可用）
+    /// 排在最前，其余引擎保持原有顺序
+    fn route_by_language(&self, engines: Vec<String>, query: &crate::derive::SearchQuery) -> Vec<String> {
+        let Some(language) = query.language.as_deref() else {
+            return engines;
+        };
+        match self.config.language_engine_priority.get(language) {
+            Some(preferred) => {
+                let preferred = self.filter_requested_engines(preferred);
+                prioritize_engines(engines, &preferred)
+            }
+            None => engines,
+        }
+    }
+
+    /// 按查询地域调整引擎优先级
+    ///
+    /// 在引擎地域配置中声明服务该地域的引擎排在最前，其余引擎保持原有顺序
+    fn route_by_region(&self, engines: Vec<String>, query: &crate::derive::SearchQuery) -> Vec<String> {
+        let Some(region) = routing_region(query) else {
+            return engines;
+        };
+        let preferred: Vec<String> = engines
+            .iter()
+            .filter(|engine| self.geo.serves(engine, &region))
+            .cloned()
+            .collect();
+        prioritize_engines(engines, &preferred)
+    }
+
+    /// 聚合、评分并排序各引擎结果
+    ///
+    /// 启用自适应权重时使用引擎历史质量评分替代静态权威度表；
+    /// `multipliers`（分类模式的引擎权重倍数）乘到对应引擎的权重上；
+    /// `aggregation` 为本次搜索的聚合策略与排序方式（None 时使用聚合器的默认设置）
+    async fn aggregate_results(
+        &self,
+        results: Vec<SearchResult>,
+        query: &crate::derive::SearchQuery,
+        multipliers: Option<&std::collections::HashMap<String, f64>>,
+        aggregation: Option<(&AggregationStrategy, SortBy)>,
+    ) -> SearchResult {
+        if !self.config.adaptive_engine_weighting && multipliers.is_none() {
+            return match aggregation {
+                Some((strategy, sort_by)) => {
+                    self.aggregator.aggregate_with_options(results, query, None, strategy, sort_by)
+                }
+                None => self.aggregator.aggregate_with_scoring(results, query),
+            };
+        }
+
+        let mut weights = if self.config.adaptive_engine_weighting {
+            let mut engines: Vec<String> = results
+                .iter()
+                .flat_map(|r| r.items.iter())
+                .filter_map(|item| item.metadata.get(ENGINE_METADATA_KEY).cloned())
+                .collect();
+            engines.sort();
+            engines.dedup();
+            let mut weights = self.engine_stats.quality_weights(&engines).await;
+            let rates = self.click_through_rates(&engines).await;
+            blend_click_rates(&mut weights, &rates);
+            weights
+        } else {
+            std::collections::HashMap::new()
+        };
+        for (engine, multiplier) in multipliers.into_iter().flatten() {
+            let base = weights.get(engine).copied().unwrap_or_else(|| get_engine_authority(engine));
+            weights.insert(engine.clone(), base * multiplier);
+        }
+        match aggregation {
+            Some((strategy, sort_by)) => {
+                self.aggregator.aggregate_with_options(results, query, Some(&weights), strategy, sort_by)
+            }
+            None => self.aggregator.aggregate_with_engine_weights(results, query, &weights),
+        }
+    }
+
+    /// 计算各引擎的结果点击率（样本不足的引擎不包含在内）
+    async fn click_through_rates(&self, engines: &[String]) -> std::collections::HashMap<String, f64> {
+        let counters = self.click_stats.snapshot().await;
+        let mut rates = std::collections::HashMap::new();
+        for engine in engines {
+            let Some(record) = self.engine_stats.get(engine).await else {
+                continue;
+            };
+            let responses = record.successful_requests.saturating_sub(record.zero_result_requests);
+            if let Some(rate) = click_through_rate(counters.engine_clicks(engine), responses) {
+                rates.insert(engine.clone(), rate);
+            }
+        }
+        rates
+    }
+
+    /// 计算各引擎本次请求的超时
+    ///
+    /// 未启用自适应超时时返回空表，调用方使用 `default_timeout`
+    async fn engine_timeouts<E>(&self, engines: &[(String, E)]) -> std::collections::HashMap<String, Duration> {
+        let Some(ref adaptive) = self.config.adaptive_timeout else {
+            return std::collections::HashMap::new();
+        };
+        let names: Vec<String> = engines.iter().map(|(name, _)| name.clone()).collect();
+        self.engine_stats
+            .adaptive_timeouts(&names, adaptive, self.config.default_timeout)
+            .await
+    }
+
+    /// 完成搜索响应：用聚合结果替换各引擎结果，补全、处理、翻译与高亮后写入归档
+    ///
+    /// 所有搜索入口共用，保证每个响应经过相同的后处理并全部归档
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - 引擎执行后的搜索响应
+    /// * `request` - 预处理后的搜索请求
+    /// * `aggregated` - 聚合、评分并排序后的结果
+    /// * `warning` - 查询清洗警告
+    /// * `captures` - 各引擎的调试捕获
+    /// * `related_queries` - 搜索会话中之前的查询
+    async fn finalize_response(
+        &self,
+        response: &mut SearchResponse,
+        request: &SearchRequest,
+        mut aggregated: SearchResult,
+        warning: Option<String>,
+        captures: BTreeMap<String, EngineDebugCapture>,
+        related_queries: Vec<String>,
+    ) {
+        Self::attach_query_warning(&mut aggregated, warning);
+        attach_debug_captures(&mut aggregated, captures);
+        response.total_count = aggregated.items.len();
+        // 用聚合后的结果替换原始结果
+        response.results = vec![aggregated];
+        self.suggest_spelling(response, &request.query.query);
+        response.related_queries = related_queries;
+
+        // 用结果页面的元数据补全摘要与缩略图（翻译之前进行，补全的摘要一并翻译）
+        self.enrich_results(response).await;
+
+        // 执行自定义结果处理器（重排、标注或移除结果）
+        self.process_results(response, &request.query).await;
+
+        // 按请求翻译结果标题与摘要
+        self.translate_results(response, request.translate_to.as_deref()).await;
+
+        // 高亮摘要中的查询词（翻译之后进行，避免标记被送入翻译后端）
+        if request.highlight {
+            for result in &mut response.results {
+                highlight_results(result, &request.query.query, &self.config.highlight_markers);
+            }
+        }
+
+        if let Some(archive) = &self.archive {
+            archive.record(response);
+        }
+    }
+
+    /// 将查询警告写入聚合结果的元数据
+    fn attach_query_warning(result: &mut SearchResult, warning: Option<String>) {
+        if let Some(warning) = warning {
+            result.metadata.insert(QUERY_WARNING_KEY.to_string(), warning);
+        }
+    }
+
     /// 获取或创建引擎实例（带缓存）
     async fn get_or_create_engine(
         &self,
@@ -530,7 +1361,10 @@ impl SearchInterface {
         }
 
         // 缓存未命中，创建新实例
-        let engine = self.create_engine_instance(engine_name)?;
+        let engine = self.create_engine_instance(engine_name, None)?;
+        if engine.info().engine_type == EngineType::Files && !self.config.enable_files_category {
+            return Err(format!("Engine '{}' belongs to the files category, which is disabled", engine_name).into());
+        }
 
         // 添加到缓存
         {
@@ -541,35 +1375,292 @@ impl SearchInterface {
         Ok(engine)
     }
 
-    /// 创建引擎实例（Arc版本，用于缓存）
-    fn create_engine_instance(
+    /// 获取或创建指向镜像服务源的引擎实例（带缓存）
+    async fn get_or_create_mirror_engine(
         &self,
         engine_name: &str,
+        mirror: &str,
     ) -> Result<Arc<dyn crate::derive::SearchEngine + Send + Sync>, Box<dyn std::error::Error + Send + Sync>> {
-        use crate::search::engines::*;
+        let key = (engine_name.to_string(), mirror.to_string());
+        if let Some(cached_engine) = self.mirror_engines.read().await.get(&key) {
+            return Ok(Arc::clone(cached_engine));
+        }
 
-        let engine: Arc<dyn crate::derive::SearchEngine + Send + Sync> = match engine_name {
-            "bing" => Arc::new(BingEngine::with_client(Arc::clone(&self.http_client))),
-            "baidu" => Arc::new(BaiduEngine::with_client(Arc::clone(&self.http_client))),
-            "yandex" => Arc::new(YandexEngine::with_client(Arc::clone(&self.http_client))),
-            "unsplash" => Arc::new(UnsplashEngine::with_client(Arc::clone(&self.http_client))),
-            "bing_images" => Arc::new(BingImagesEngine::with_client(Arc::clone(&self.http_client))),
-            "bing_news" => Arc::new(BingNewsEngine::with_client(Arc::clone(&self.http_client))),
-            "bing_videos" => Arc::new(BingVideosEngine::with_client(Arc::clone(&self.http_client))),
-            "bilibili" => Arc::new(BilibiliEngine::with_client(Arc::clone(&self.http_client))),
-            "sogou" => Arc::new(SogouEngine::with_client(Arc::clone(&self.http_client))),
-            "sogou_images" => Arc::new(SogouImagesEngine::with_client(Arc::clone(&self.http_client))),
-            "sogou_videos" => Arc::new(SogouVideosEngine::with_client(Arc::clone(&self.http_client))),
-            "sogou_wechat" => Arc::new(SogouWeChatEngine::with_client(Arc::clone(&self.http_client))),
-            _ => {
-                // 尝试从Python注册表获取引擎
-                #[cfg(feature = "python")]
-                {
-                    use crate::python_bindings::py_engine_registry::try_get_python_engine_sync;
-                    if let Some(py_engine) = try_get_python_engine_sync(engine_name) {
-                        return Ok(py_engine as Arc<dyn crate::derive::SearchEngine + Send + Sync>);
-                    }
-                    return Err(format!("Engine '{}' not found in Rust or Python registries", engine_name).into());
+        let engine = self.create_engine_instance(engine_name, Some(mirror))?;
+        self.mirror_engines.write().await.insert(key, Arc::clone(&engine));
+        Ok(engine)
+    }
+
+    /// 按查询地域确定引擎本次请求的服务源
+    ///
+    /// 主服务源实例由调用方提供；镜像实例创建失败时跳过该镜像
+    async fn engine_route(
+        &self,
+        engine_name: &str,
+        primary: Arc<dyn crate::derive::SearchEngine + Send + Sync>,
+        region: Option<&str>,
+    ) -> EngineRoute {
+        let mut candidates = Vec::new();
+        for endpoint in self.geo.endpoints(engine_name, region) {
+            match endpoint {
+                None => candidates.push((None, Arc::clone(&primary))),
+                Some(mirror) => match self.get_or_create_mirror_engine(engine_name, &mirror).await {
+                    Ok(engine) => candidates.push((Some(mirror), engine)),
+                    Err(e) => tracing::warn!("Failed to create {} engine for mirror {}: {}", engine_name, mirror, e),
+                },
+            }
+        }
+        if candidates.is_empty() {
+            candidates.push((None, primary));
+        }
+        EngineRoute {
+            candidates,
+            geo: Arc::clone(&self.geo),
+        }
+    }
+
+    /// 未启用文件分类时移除文件/种子引擎
+    fn gate_files_category(&self, engines: Vec<String>) -> Vec<String> {
+        if self.config.enable_files_category {
+            engines
+        } else {
+            exclude_files_engines(engines)
+        }
+    }
+
+    /// 查询建议
+    ///
+    /// 返回查询的拼写纠正（未启用拼写纠正或无需纠正时为空）
+    pub fn suggest(&self, query: &str) -> Vec<String> {
+        self.parser.suggest_correction(query).into_iter().collect()
+    }
+
+    /// 从结果标题学习词汇，结果过少时给出拼写纠正建议
+    fn suggest_spelling(&self, response: &mut SearchResponse, query: &str) {
+        if !self.parser.spell_correction_enabled() {
+            return;
+        }
+        self.parser.learn(
+            response.results.iter().flat_map(|r| r.items.iter()).map(|item| item.title.as_str()),
+        );
+        if response.total_count < self.config.spell_correction_threshold
+            && let Some(corrected) = self.parser.suggest_correction(query)
+        {
+            response.suggestions.push(corrected);
+        }
+    }
+
+    /// 为后续页请求附加引擎上一页返回的续页令牌
+    ///
+    /// 请求已显式携带令牌或缓存中没有令牌时按原查询（页码分页）请求
+    fn query_with_cursor(&self, query: &crate::derive::SearchQuery, engine_name: &str) -> crate::derive::SearchQuery {
+        let mut query = query.clone();
+        if query.page <= 1 || query.params.contains_key(CURSOR_PARAM_KEY) {
+            return query;
+        }
+        let Some(ref cursors) = self.cursors else {
+            return query;
+        };
+        match cursors.get(&query, engine_name, query.page) {
+            Ok(Some(cursor)) => {
+                query.params.insert(CURSOR_PARAM_KEY.to_string(), cursor);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to load cursor for engine {}: {}", engine_name, e),
+        }
+        query
+    }
+
+    /// 保存引擎返回的下一页续页令牌
+    fn store_next_cursor(&self, query: &crate::derive::SearchQuery, engine_name: &str, result: &SearchResult) {
+        let (Some(cursors), Some(cursor)) = (self.cursors.as_ref(), result.metadata.get(NEXT_CURSOR_METADATA_KEY)) else {
+            return;
+        };
+        if let Err(e) = cursors.set(query, engine_name, query.page.max(1) + 1, cursor) {
+            tracing::warn!("Failed to store cursor for engine {}: {}", engine_name, e);
+        }
+    }
+
+    /// 用结果页面的 OpenGraph/JSON-LD 元数据补全排名靠前的结果
+    async fn enrich_results(&self, response: &mut SearchResponse) {
+        let Some(ref enricher) = self.enricher else {
+            return;
+        };
+        if let Some(result) = response.results.first_mut() {
+            enricher.enrich(&mut result.items).await;
+        }
+    }
+
+    /// 依次执行注册的结果处理器（启用 Python 绑定时包括 Python 端注册的处理器）
+    async fn process_results(&self, response: &mut SearchResponse, query: &crate::derive::SearchQuery) {
+        #[cfg(feature = "python")]
+        let python_processors = crate::python_bindings::py_result_processor::registered_processors();
+        #[cfg(not(feature = "python"))]
+        let python_processors: Vec<Arc<dyn ResultProcessor>> = Vec::new();
+
+        if self.processors.is_empty() && python_processors.is_empty() {
+            return;
+        }
+        let Some(result) = response.results.first_mut() else {
+            return;
+        };
+        self.processors.apply(query, &mut result.items).await;
+        apply_processors(&python_processors, query, &mut result.items).await;
+        response.total_count = result.items.len();
+    }
+
+    /// 将结果标题与摘要翻译为目标语言
+    ///
+    /// 已是目标语言的结果跳过，同一源语言的结果合并为一次批量请求；
+    /// 翻译失败时保留原文
+    async fn translate_results(&self, response: &mut SearchResponse, target: Option<&str>) {
+        let (Some(translator), Some(target)) = (self.translator.as_ref(), target) else {
+            return;
+        };
+        // `en-US` -> `en`
+        let target = target.split(['-', '_']).next().unwrap_or(target).to_lowercase();
+        let max_items = self.config.translation.as_ref().map(|t| t.max_items).unwrap_or(usize::MAX);
+
+        let mut groups: std::collections::HashMap<Option<String>, Vec<&mut crate::derive::SearchResultItem>> =
+            std::collections::HashMap::new();
+        for item in response.results.iter_mut().flat_map(|r| r.items.iter_mut()).take(max_items) {
+            let source = self.parser.detect_language(&format!("{} {}", item.title, item.content));
+            if source.as_deref() == Some(target.as_str()) {
+                continue;
+            }
+            groups.entry(source).or_default().push(item);
+        }
+
+        for (source, mut items) in groups {
+            let texts: Vec<String> = items
+                .iter()
+                .flat_map(|item| [item.title.clone(), item.content.clone()])
+                .collect();
+            match translator.translate(&texts, source.as_deref(), &target).await {
+                Ok(translations) => apply_translations(&mut items, translations, source.as_deref()),
+                Err(e) => tracing::warn!("Translation via {} failed: {}", translator.name(), e),
+            }
+        }
+    }
+
+    /// 获取引擎特定配置（未配置时返回默认值）
+    fn engine_settings(&self, engine_name: &str) -> crate::config::engines::EngineSpecificConfig {
+        self.config.engine_settings.get(engine_name).cloned().unwrap_or_default()
+    }
+
+    /// 获取引擎使用的 HTTP 客户端
+    ///
+    /// 按引擎的响应限制读取响应体并记录下载流量，启用缓存时共享页面缓存，启用礼貌访问时限速，
+    /// 配置了认证时附加凭据，启用会话保持时附加并记录会话；
+    /// 指定镜像源时将引擎主源的请求改写到该镜像（凭据与会话不发往镜像）
+    fn engine_client(&self, engine_name: &str, mirror: Option<&str>) -> Arc<crate::net::client::HttpClient> {
+        let limits = self
+            .config
+            .engine_response_limits
+            .get(engine_name)
+            .unwrap_or(&self.config.response_limits);
+        let client = self
+            .http_client
+            .for_engine(engine_name)
+            .with_response_limits(limits.clone())
+            .with_bandwidth_recorder(Arc::clone(&self.bandwidth) as Arc<dyn BandwidthRecorder>);
+        let client = match self.pages {
+            Some(ref pages) => client.with_page_cache(pages.clone()),
+            None => client,
+        };
+        let client = match (mirror, self.geo.origin(engine_name), self.engine_auth.get(engine_name)) {
+            (Some(mirror), Some(origin), _) => client.with_origin_override(origin, mirror),
+            (_, _, Some(auth)) => client.with_auth(Arc::clone(auth)),
+            _ => client,
+        };
+        let client = match (mirror, self.engine_sessions.get(engine_name)) {
+            (None, Some(session)) => client.with_session(Arc::clone(session)),
+            _ => client,
+        };
+        match self.politeness {
+            Some(ref politeness) => Arc::new(client.with_politeness(Arc::clone(politeness))),
+            None => Arc::new(client),
+        }
+    }
+
+    /// 计算引擎的礼貌访问策略
+    ///
+    /// 引擎配置中的覆盖优先；未覆盖时只有非 API 引擎遵守 Crawl-delay
+    fn politeness_policy(&self, politeness: &Politeness, engine_name: &str, official_api: bool) -> PolitenessPolicy {
+        let default = politeness.default_policy();
+        let overrides = self.config.engine_politeness.get(engine_name);
+        PolitenessPolicy {
+            min_delay: overrides
+                .and_then(|o| o.min_delay_ms)
+                .map_or(default.min_delay, Duration::from_millis),
+            respect_crawl_delay: overrides
+                .and_then(|o| o.respect_crawl_delay)
+                .unwrap_or(default.respect_crawl_delay && !official_api),
+        }
+    }
+
+    /// 创建引擎实例（Arc版本，用于缓存）
+    ///
+    /// `mirror` 为 None 时使用引擎的主服务源
+    fn create_engine_instance(
+        &self,
+        engine_name: &str,
+        mirror: Option<&str>,
+    ) -> Result<Arc<dyn crate::derive::SearchEngine + Send + Sync>, Box<dyn std::error::Error + Send + Sync>> {
+        use crate::search::engines::*;
+
+        let client = self.engine_client(engine_name, mirror);
+
+        let engine: Arc<dyn crate::derive::SearchEngine + Send + Sync> = match engine_name {
+            "bing" => Arc::new(BingEngine::with_client(Arc::clone(&client))),
+            "baidu" => Arc::new(BaiduEngine::with_client(Arc::clone(&client))),
+            "yandex" => Arc::new(YandexEngine::with_client(Arc::clone(&client))),
+            "unsplash" => Arc::new(UnsplashEngine::with_client(Arc::clone(&client))),
+            "bing_images" => Arc::new(BingImagesEngine::with_client(Arc::clone(&client))),
+            "bing_news" => Arc::new(BingNewsEngine::with_client(Arc::clone(&client))),
+            "bing_videos" => Arc::new(BingVideosEngine::with_client(Arc::clone(&client))),
+            "bilibili" => Arc::new(BilibiliEngine::with_client(Arc::clone(&client))),
+            "sogou" => Arc::new(SogouEngine::with_client(Arc::clone(&client))),
+            "sogou_images" => Arc::new(SogouImagesEngine::with_client(Arc::clone(&client))),
+            "sogou_videos" => Arc::new(SogouVideosEngine::with_client(Arc::clone(&client))),
+            "sogou_wechat" => Arc::new(SogouWeChatEngine::with_client(Arc::clone(&client))),
+            "github" => Arc::new(
+                GitHubEngine::with_client(Arc::clone(&client)).with_config(&self.engine_settings(engine_name)),
+            ),
+            "stackoverflow" => Arc::new(
+                StackOverflowEngine::with_client(Arc::clone(&client)).with_config(&self.engine_settings(engine_name)),
+            ),
+            "arxiv" => Arc::new(ArxivEngine::with_client(Arc::clone(&client))),
+            "crossref" => Arc::new(
+                CrossrefEngine::with_client(Arc::clone(&client)).with_config(&self.engine_settings(engine_name)),
+            ),
+            "semantic_scholar" => Arc::new(
+                SemanticScholarEngine::with_client(Arc::clone(&client)).with_config(&self.engine_settings(engine_name)),
+            ),
+            "ebay" => Arc::new(
+                EbayEngine::with_client(Arc::clone(&client)).with_config(&self.engine_settings(engine_name)),
+            ),
+            "nominatim" => Arc::new(
+                NominatimEngine::with_client(Arc::clone(&client)).with_config(&self.engine_settings(engine_name)),
+            ),
+            "piratebay" => Arc::new(
+                PirateBayEngine::with_client(Arc::clone(&client)).with_config(&self.engine_settings(engine_name)),
+            ),
+            // 从发现路径加载的 WASM 插件（内置引擎同名时内置引擎优先）
+            #[cfg(feature = "wasm-plugins")]
+            name if self.plugins.contains(name) => {
+                let plugin = self.plugins.create_engine(name, Arc::clone(&client));
+                Arc::new(plugin.ok_or_else(|| format!("WASM plugin '{}' is not loaded", name))?)
+            }
+            _ => {
+                // 尝试从Python注册表获取引擎
+                #[cfg(feature = "python")]
+                {
+                    use crate::python_bindings::py_engine_registry::try_get_python_engine_sync;
+                    if let Some(py_engine) = try_get_python_engine_sync(engine_name) {
+                        return Ok(py_engine as Arc<dyn crate::derive::SearchEngine + Send + Sync>);
+                    }
+                    return Err(format!("Engine '{}' not found in Rust or Python registries", engine_name).into());
                 }
                 #[cfg(not(feature = "python"))]
                 {
@@ -578,16 +1669,136 @@ impl SearchInterface {
             },
         };
 
+        if let Some(ref politeness) = self.politeness {
+            let policy = self.politeness_policy(politeness, engine_name, engine.info().about.use_official_api);
+            politeness.set_policy(engine_name, policy);
+        }
+
         Ok(engine)
     }
 
     
     /// 并发执行搜索引擎
+    ///
+    /// 调试捕获请求不参与合并，需要捕获本次请求实际读取的响应；抢先返回的请求同样不参与合并
     async fn execute_concurrent_search(
         &self,
         request: &SearchRequest,
         engine_names: &[String],
-    ) -> Result<SearchResponse, Box<dyn std::error::Error + Send + Sync>> {
+        race: Option<usize>,
+    ) -> Result<(SearchResponse, BTreeMap<String, EngineDebugCapture>), Box<dyn std::error::Error + Send + Sync>> {
+        use std::sync::atomic::Ordering;
+
+        if !self.config.coalesce_requests || request.debug_capture || race.is_some() {
+            return self.fan_out_search(request, engine_names, race).await;
+        }
+
+        // 相同的并发查询等待同一次引擎请求
+        let key = coalesce_key(request, engine_names);
+        let (result, coalesced) = self
+            .inflight
+            .run(key, || async {
+                self.fan_out_search(request, engine_names, None)
+                    .await
+                    .map(|(response, _)| response)
+            })
+            .await;
+        if coalesced {
+            self.stats.coalesced_requests.fetch_add(1, Ordering::Relaxed);
+        }
+        result.map(|response| (response, BTreeMap::new()))
+    }
+
+    /// 执行一次引擎搜索，请求调试捕获时同时记录读取的响应
+    ///
+    /// # Arguments
+    ///
+    /// * `debug_capture` - 是否捕获原始响应
+    /// * `capture_dir` - 捕获文件的保存目录（None 时不落盘）
+    ///
+    /// # Returns
+    ///
+    /// 返回引擎结果（错误已格式化为消息）、调试捕获（未请求时为 None）与执行明细
+    async fn run_engine(
+        engine: Arc<dyn crate::derive::SearchEngine + Send + Sync>,
+        engine_name: &str,
+        query: &crate::derive::SearchQuery,
+        timeout_duration: Duration,
+        stats: &SearchStats,
+        debug_capture: bool,
+        capture_dir: Option<&Path>,
+    ) -> (Result<SearchResult, String>, Option<EngineDebugCapture>, EngineTiming) {
+        use std::sync::atomic::Ordering;
+
+        let search_start = std::time::Instant::now();
+        let search = trace_requests(async {
+            match timeout(timeout_duration, engine.search(query)).await {
+                Ok(Ok(mut result)) => {
+                    result.elapsed_ms = search_start.elapsed().as_millis() as u64;
+                    // 以注册名标记结果来源，便于按引擎统计和加权
+                    for item in &mut result.items {
+                        item.metadata
+                            .entry(ENGINE_METADATA_KEY.to_string())
+                            .or_insert_with(|| engine_name.to_string());
+                    }
+                    Ok(result)
+                }
+                Ok(Err(e)) => {
+                    stats.engine_failures.fetch_add(1, Ordering::Relaxed);
+                    Err(format!("Engine {} error: {}", engine_name, e))
+                }
+                Err(_) => {
+                    stats.timeouts.fetch_add(1, Ordering::Relaxed);
+                    Err(format!("Engine {} timeout", engine_name))
+                }
+            }
+        });
+        let ((outcome, trace), debug) = if debug_capture {
+            let ((outcome, trace), captured) = capture_responses(search).await;
+            let mut debug = EngineDebugCapture::new(&captured, outcome.as_ref().map_err(String::as_str));
+            if let Some(dir) = capture_dir {
+                match save_capture(dir, engine_name, &captured, &debug).await {
+                    Ok(path) => debug.capture_file = Some(path.display().to_string()),
+                    Err(e) => tracing::warn!("Failed to save debug capture for {}: {}", engine_name, e),
+                }
+            }
+            ((outcome, trace), Some(debug))
+        } else {
+            (search.await, None)
+        };
+
+        let timing = EngineTiming {
+            engine: engine_name.to_string(),
+            elapsed_ms: match outcome {
+                Ok(ref result) => result.elapsed_ms,
+                Err(_) => search_start.elapsed().as_millis() as u64,
+            },
+            result_count: outcome.as_ref().map(|result| result.items.len()).unwrap_or(0),
+            cache_hit: trace.served_from_cache(),
+            error: outcome.as_ref().err().cloned(),
+            retries: trace.retries(),
+            blocked: trace.blocked(),
+            endpoint: None,
+        };
+        (outcome, debug, timing)
+    }
+
+    /// 向各引擎并发发起请求并收集结果
+    ///
+    /// # Arguments
+    ///
+    /// * `race` - 抢先返回需要等待的引擎数：达到该数量的引擎返回结果后不再等待，
+    ///   其余引擎在后台执行完毕（只预热页面缓存，不计入引擎统计）
+    ///
+    /// # Returns
+    ///
+    /// 返回各引擎的结果与调试捕获（未请求调试捕获时为空）
+    async fn fan_out_search(
+        &self,
+        request: &SearchRequest,
+        engine_names: &[String],
+        race: Option<usize>,
+    ) -> Result<(SearchResponse, BTreeMap<String, EngineDebugCapture>), Box<dyn std::error::Error + Send + Sync>> {
         use std::sync::atomic::Ordering;
         
         // 增加搜索计数
@@ -596,6 +1807,8 @@ impl SearchInterface {
         let start_time = std::time::Instant::now();
         let mut futures_list = Vec::new();
         let mut engines_to_execute = Vec::new();
+        let region = routing_region(&request.query);
+        let mut per_engine = Vec::new();
 
         // 预先确保所有引擎都有状态记录
         {
@@ -613,72 +1826,145 @@ impl SearchInterface {
                 let states = self.engine_states.read().await;
                 if let Some(state) = states.get(engine_name) {
                     if !state.is_available() {
+                        per_engine.push(EngineTiming::skipped(engine_name, "temporarily disabled"));
                         continue;
                     }
                 }
             }
+            // 依赖不满足的引擎
+            if let Some(reason) = self.inactive_engines.get(engine_name) {
+                per_engine.push(EngineTiming::skipped(engine_name, format!("inactive: {}", reason)));
+                continue;
+            }
+            // 当日流量配额用尽的引擎暂停到次日
+            if self.bandwidth.is_exhausted(engine_name) {
+                per_engine.push(EngineTiming::skipped(engine_name, "daily bandwidth quota exhausted"));
+                continue;
+            }
+            // 冷却期内对相同查询返回过负面结果的引擎
+            if let Some(outcome) = self.cached_negative(request, engine_name).await {
+                per_engine.push(EngineTiming::skipped(
+                    engine_name,
+                    format!("cached negative outcome: {}", outcome.as_str()),
+                ));
+                continue;
+            }
             match self.get_or_create_engine(engine_name).await {
                 Ok(engine) => {
-                    engines_to_execute.push((engine_name.clone(), engine));
+                    let route = self.engine_route(engine_name, engine, region.as_deref()).await;
+                    engines_to_execute.push((engine_name.clone(), route));
                 }
-                Err(_e) => {
+                Err(e) => {
                     self.stats.engine_failures.fetch_add(1, Ordering::Relaxed);
+                    per_engine.push(EngineTiming::skipped(engine_name, e.to_string()));
                 }
             }
         }
 
-        // 创建并发任务
-        for (engine_name, engine) in engines_to_execute {
-            let query = request.query.clone();
-            let timeout_duration = Duration::from_secs(self.config.default_timeout.as_secs());
+        // 创建并发任务（按引擎历史延迟确定超时）
+        let timeouts = self.engine_timeouts(&engines_to_execute).await;
+        let launched: Vec<String> = engines_to_execute.iter().map(|(name, _)| name.clone()).collect();
+        for (engine_name, route) in engines_to_execute {
+            let query = self.query_with_cursor(&request.query, &engine_name);
+            let timeout_duration = timeouts.get(&engine_name).copied().unwrap_or(self.config.default_timeout);
             let stats = Arc::clone(&self.stats);
+            let limiter = Arc::clone(&self.limiter);
+            let debug_capture = request.debug_capture;
+            let capture_dir = self.config.debug_capture_dir.clone();
+            let span = tracing::debug_span!("engine", engine = %engine_name);
             
             let future = async move {
-                let search_start = std::time::Instant::now();
-                match timeout(timeout_duration, engine.search(&query)).await {
-                    Ok(Ok(mut result)) => {
-                        result.elapsed_ms = search_start.elapsed().as_millis() as u64;
-                        Some((Ok(result), engine_name))
-                    }
-                    Ok(Err(e)) => {
-                        stats.engine_failures.fetch_add(1, Ordering::Relaxed);
-                        Some((Err(format!("Engine {} error: {}", engine_name, e)), engine_name))
-                    }
-                    Err(_) => {
-                        stats.timeouts.fetch_add(1, Ordering::Relaxed);
-                        Some((Err(format!("Engine {} timeout", engine_name)), engine_name))
-                    }
-                }
+                // 超出并发上限时排队，排队时间不计入引擎超时
+                let _permit = limiter.acquire(&engine_name).await;
+                let (outcome, debug, timing) = route.run(
+                    &engine_name,
+                    &query,
+                    timeout_duration,
+                    &stats,
+                    debug_capture,
+                    capture_dir.as_deref(),
+                )
+                .await;
+                Some((outcome, engine_name, debug, timing))
             };
             
-            futures_list.push(future);
+            futures_list.push(future.instrument(span));
         }
         
-        // 并发执行所有搜索
-        let results = futures::future::join_all(futures_list).await;
+        // 并发执行所有搜索，延迟预算到期时放弃尚未完成的引擎，抢先返回时其余引擎转入后台
+        let deadline = latency_deadline(request, start_time);
+        let mut pending: FuturesUnordered<_> = futures_list.into_iter().collect();
+        let mut results = Vec::new();
+        let mut responded = 0;
+        let mut partial = false;
+        let mut raced = false;
+        loop {
+            if race.is_some_and(|quorum| responded >= quorum) && !pending.is_empty() {
+                partial = true;
+                raced = true;
+                break;
+            }
+            match next_within(&mut pending, deadline).await {
+                Ok(Some(result)) => {
+                    if let Some((Ok(ref result), ..)) = result
+                        && !result.items.is_empty()
+                    {
+                        responded += 1;
+                    }
+                    results.push(result);
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    partial = true;
+                    break;
+                }
+            }
+        }
+        if raced {
+            tokio::spawn(async move { while pending.next().await.is_some() {} }.in_current_span());
+        } else {
+            drop(pending);
+        }
 
         // 收集成功的结果，并检测零结果情况
         let mut successful_results = Vec::new();
         let mut engines_used = Vec::new();
 
+        let mut captures = BTreeMap::new();
+
         for result in results.iter() {
-            if let Some((search_result, engine_name)) = result {
+            if let Some((search_result, engine_name, debug, timing)) = result {
+                if let Some(debug) = debug {
+                    captures.insert(engine_name.clone(), debug.clone());
+                }
+                self.record_negative(&request.query, engine_name, search_result, timing).await;
+                per_engine.push(timing.clone());
                 match search_result {
                     Ok(result) => {
+                        self.engine_stats
+                            .record_success(engine_name, result.elapsed_ms, result.items.len())
+                            .await;
+                        self.store_next_cursor(&request.query, engine_name, result);
+
                         // 检查是否为零结果
                         let is_zero_results = result.items.is_empty();
 
+                        let scraped = self.is_scraped_engine(engine_name).await;
+
                         if is_zero_results {
                             // 零结果，更新引擎状态并应用指数退避
                             let mut states = self.engine_states.write().await;
                             if let Some(state) = states.get_mut(engine_name) {
-                                state.record_zero_results();
+                                let disabled_for = state.record_zero_results();
+                                self.notify_engine_disabled(state, disabled_for);
+                                self.check_selector_rot(state, &request.query.query, 0, scraped);
                             }
                         } else {
                             // 有结果，记录成功
                             let mut states = self.engine_states.write().await;
                             if let Some(state) = states.get_mut(engine_name) {
                                 state.record_success(result.elapsed_ms);
+                                self.check_selector_rot(state, &request.query.query, result.items.len(), scraped);
                             }
                         }
 
@@ -686,8 +1972,9 @@ impl SearchInterface {
                         successful_results.push(result.clone());
                         engines_used.push(engine_name.clone());
                     }
-                    Err(_) => {
+                    Err(e) => {
                         // 失败，记录失败
+                        self.engine_stats.record_failure(engine_name, e).await;
                         let mut states = self.engine_states.write().await;
                         let state = states.entry(engine_name.clone())
                             .or_insert_with(|| super::engine_manager::EngineState::new(engine_name.clone()));
@@ -698,15 +1985,62 @@ impl SearchInterface {
         }
         
         let query_time_ms = start_time.elapsed().as_millis() as u64;
+        if partial {
+            let reason = if raced { RACE_CONTINUED_IN_BACKGROUND } else { LATENCY_BUDGET_EXCEEDED };
+            mark_unfinished(&mut per_engine, &launched, query_time_ms, reason);
+        }
         let total_count: usize = successful_results.iter().map(|r| r.items.len()).sum();
-        Ok(SearchResponse {
+        let response = SearchResponse {
             query: request.query.clone(),
             results: successful_results,
             total_count,
             engines_used,
             query_time_ms,
             cached: false,
-        })
+            answer: None,
+            suggestions: Vec::new(),
+            per_engine,
+            partial,
+            related_queries: Vec::new(),
+            request_id: None,
+        };
+        Ok((response, captures))
+    }
+
+    /// 读取冷却期内缓存的负面结果（强制搜索时忽略）
+    async fn cached_negative(&self, request: &SearchRequest, engine_name: &str) -> Option<NegativeOutcome> {
+        let cache = self.negative_cache.as_ref()?;
+        if request.force {
+            return None;
+        }
+        match cache.get_negative_async(&request.query, engine_name).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                tracing::warn!("Failed to read negative cache for {}: {}", engine_name, e);
+                None
+            }
+        }
+    }
+
+    /// 缓存引擎的负面结果（零结果、验证码、HTTP 403/429），其余结果不记录
+    async fn record_negative(
+        &self,
+        query: &crate::derive::SearchQuery,
+        engine_name: &str,
+        outcome: &Result<SearchResult, String>,
+        timing: &EngineTiming,
+    ) {
+        let (Some(cache), Some(ttl)) = (self.negative_cache.as_ref(), self.config.negative_cache_ttl) else {
+            return;
+        };
+        let negative = match outcome {
+            Ok(result) if result.items.is_empty() => NegativeOutcome::ZeroResults,
+            Err(e) if timing.blocked || e.to_lowercase().contains("captcha") => NegativeOutcome::Blocked,
+            _ => return,
+        };
+        if let Err(e) = cache.set_negative_async(query, engine_name, negative, ttl).await {
+            tracing::warn!("Failed to cache negative outcome for {}: {}", engine_name, e);
+        }
     }
 
     /// 获取统计信息
@@ -719,9 +2053,94 @@ impl SearchInterface {
             cache_misses: self.stats.cache_misses.load(Ordering::Relaxed),
             engine_failures: self.stats.engine_failures.load(Ordering::Relaxed),
             timeouts: self.stats.timeouts.load(Ordering::Relaxed),
+            coalesced_requests: self.stats.coalesced_requests.load(Ordering::Relaxed),
         }
     }
 
+    /// 生成引擎统计报告
+    ///
+    /// 包含每个引擎的成功率、平均延迟、结果数量、最近一次失败原因、当日下载流量与降级状态，
+    /// 数据在启用缓存时跨进程重启保留
+    pub async fn engine_report(&self) -> EngineReport {
+        let mut report = self.engine_stats.report(&self.list_engines()).await;
+        let states = self.engine_states.read().await;
+        let clicks = self.click_stats.snapshot().await;
+        for entry in &mut report.engines {
+            entry.bandwidth = Some(self.bandwidth.report(&entry.stats.engine));
+            entry.degraded = states.get(&entry.stats.engine).is_some_and(|state| state.degraded);
+            entry.clicks = clicks.engine_clicks(&entry.stats.engine);
+            entry.click_through_rate = click_through_rate(
+                entry.clicks,
+                entry.stats.successful_requests.saturating_sub(entry.stats.zero_result_requests),
+            );
+        }
+        report
+    }
+
+    /// 记录一次结果点击
+    ///
+    /// 只累计引擎与目标域名的点击数；不在引擎列表中的引擎名只计入域名统计
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - 结果来源引擎
+    /// * `domain` - 目标域名
+    pub async fn record_click(&self, engine: Option<&str>, domain: &str) {
+        let engine = engine.filter(|engine| self.list_engines().iter().any(|name| name == engine));
+        self.click_stats.record(engine, domain).await;
+    }
+
+    /// 获取到期的结果过滤列表（远程列表按刷新间隔获取，本地文件在修改后重新读取）
+    ///
+    /// # Returns
+    ///
+    /// 返回本次更新的列表数
+    pub async fn refresh_filter_lists(&self) -> usize {
+        self.result_filter.refresh_lists(&self.http_client).await
+    }
+
+    /// 热重载结果过滤规则
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - 新的结果过滤配置
+    ///
+    /// # Returns
+    ///
+    /// 规则无效时返回错误并保留原有规则
+    pub fn reload_result_filter(&self, config: &crate::config::engines::ResultFilteringConfig) -> Result<(), String> {
+        self.result_filter.reload(config)
+    }
+
+    /// 替换评分配置档（BM25 参数与评分权重）
+    ///
+    /// 替换后之后的搜索立即使用新参数，无需重启
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - 新的评分配置档
+    ///
+    /// # Returns
+    ///
+    /// 参数无效时返回错误并保留原有配置档
+    pub fn set_scoring_profile(&self, profile: ScoringProfile) -> Result<(), String> {
+        self.scoring_profile.set(profile)
+    }
+
+    /// 获取当前的评分配置档
+    pub fn scoring_profile(&self) -> Arc<ScoringProfile> {
+        self.scoring_profile.get()
+    }
+
+    /// 获取结果点击统计报告
+    ///
+    /// # Arguments
+    ///
+    /// * `top_domains` - 报告中包含的域名数
+    pub async fn click_report(&self, top_domains: usize) -> ClickReport {
+        self.click_stats.report(top_domains).await
+    }
+
     /// 获取引擎缓存统计
     pub async fn get_engine_cache_stats(&self) -> (usize, Vec<String>) {
         let cache = self.engine_cache.read().await;
@@ -733,6 +2152,7 @@ impl SearchInterface {
     pub async fn clear_engine_cache(&self) {
         let mut cache = self.engine_cache.write().await;
         cache.clear();
+        self.mirror_engines.write().await.clear();
     }
 
     /// 清除缓存
@@ -743,7 +2163,7 @@ impl SearchInterface {
 
     /// 列出可用引擎
     pub fn list_engines(&self) -> Vec<String> {
-        EngineListConfig::default().all_available_engines.clone()
+        self.gate_files_category(EngineListConfig::default().all_available_engines)
     }
 
     /// 列出全局模式引擎
@@ -751,12 +2171,79 @@ impl SearchInterface {
         EngineListConfig::default().global_engines.clone()
     }
 
+    /// 生成引擎能力目录
+    ///
+    /// 列出每个可用引擎的信息与能力（内置引擎在前，其后依次为 WASM 插件引擎与 Python 端注册的引擎，按名称排序），
+    /// 供前端动态构建引擎选择器；无法创建的引擎会被跳过
+    pub async fn engine_catalog(&self) -> Vec<EngineCatalogEntry> {
+        let global: std::collections::HashSet<String> = self.list_global_engines().into_iter().collect();
+        let mut catalog = Vec::new();
+        for name in self.list_engines() {
+            match self.get_or_create_engine(&name).await {
+                Ok(engine) => catalog.push(self.catalog_entry(name, engine.info().clone(), EngineSource::Builtin, &global)),
+                Err(e) => tracing::warn!("Skipping engine {} in catalog: {}", name, e),
+            }
+        }
+
+        #[cfg(feature = "wasm-plugins")]
+        for name in self.plugins.names() {
+            match self.get_or_create_engine(&name).await {
+                Ok(engine) => catalog.push(self.catalog_entry(name, engine.info().clone(), EngineSource::Wasm, &global)),
+                Err(e) => tracing::warn!("Skipping WASM plugin {} in catalog: {}", name, e),
+            }
+        }
+
+        #[cfg(feature = "python")]
+        {
+            let registry = crate::python_bindings::py_engine_registry::get_global_registry();
+            let mut names = registry.list_engines_internal().await;
+            names.sort();
+            for name in names {
+                if let Some(engine) = registry.get_engine(&name).await {
+                    let info = crate::derive::SearchEngine::info(engine.as_ref()).clone();
+                    catalog.push(self.catalog_entry(name, info, EngineSource::Python, &global));
+                }
+            }
+        }
+
+        catalog
+    }
+
+    /// 生成单个引擎的目录项
+    fn catalog_entry(
+        &self,
+        id: String,
+        info: crate::derive::EngineInfo,
+        source: EngineSource,
+        global: &std::collections::HashSet<String>,
+    ) -> EngineCatalogEntry {
+        let inactive_reason = self.inactive_reason(&id).map(str::to_string);
+        EngineCatalogEntry {
+            global: global.contains(&id),
+            id,
+            source,
+            available: inactive_reason.is_none(),
+            inactive_reason,
+            info,
+        }
+    }
+
+    /// 引擎因依赖不满足而不可用的原因（可用时返回 None）
+    pub fn inactive_reason(&self, engine: &str) -> Option<&str> {
+        self.inactive_engines.get(engine).map(String::as_str)
+    }
+
     /// 健康检查
     pub async fn health_check(&self) -> Result<Vec<(String, bool)>, Box<dyn std::error::Error + Send + Sync>> {
-        // 可以实现健康检查逻辑
-        // 暂时返回所有引擎为健康状态
+        // 依赖不满足的引擎不健康，其余引擎暂时视为健康
         let engines = self.list_engines();
-        Ok(engines.into_iter().map(|e| (e, true)).collect())
+        Ok(engines
+            .into_iter()
+            .map(|e| {
+                let healthy = !self.inactive_engines.contains_key(&e);
+                (e, healthy)
+            })
+            .collect())
     }
 
     /// 获取引擎状态
@@ -775,12 +2262,94 @@ impl SearchInterface {
     }
 
     /// 使特定引擎缓存失效
+    ///
+    /// 丢弃引擎实例，并删除该引擎命名空间中缓存的搜索结果与负面结果
     pub async fn invalidate_engine(&self, engine_name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut cache = self.engine_cache.write().await;
         cache.remove(engine_name);
+        self.mirror_engines.write().await.retain(|(name, _), _| name != engine_name);
+        if let Some(results) = &self.result_cache {
+            let removed = results
+                .invalidate_engine_async(engine_name)
+                .await
+                .map_err(|e| format!("Failed to invalidate cached results: {}", e))?;
+            tracing::debug!("Invalidated {} cached entries of engine {}", removed, engine_name);
+        }
         Ok(())
     }
 
+    /// 获取各引擎的搜索结果缓存统计（未启用缓存时为 None）
+    pub async fn engine_cache_stats(&self) -> Option<std::collections::BTreeMap<String, EngineCacheStats>> {
+        let results = self.result_cache.as_ref()?;
+        match results.all_engine_stats_async().await {
+            Ok(stats) => Some(stats),
+            Err(e) => {
+                tracing::warn!("Failed to read engine cache stats: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 获取共享的 HTTP 客户端
+    pub fn http_client(&self) -> &Arc<crate::net::client::HttpClient> {
+        &self.http_client
+    }
+
+    /// 获取搜索结果归档（未启用时为 None）
+    pub fn archive(&self) -> Option<&Arc<SearchArchive>> {
+        self.archive.as_ref()
+    }
+
+    /// 获取搜索会话存储（未启用缓存或搜索会话时为 None）
+    pub fn sessions(&self) -> Option<&SearchSessionStore> {
+        self.search_sessions.as_ref()
+    }
+
+    /// 获取事件 Webhook 分发器（未启用时为 None）
+    pub fn webhooks(&self) -> Option<&Arc<WebhookDispatcher>> {
+        self.webhooks.as_ref()
+    }
+
+    /// 引擎被临时禁用时投递 Webhook 事件
+    fn notify_engine_disabled(&self, state: &super::engine_manager::EngineState, disabled_for: std::time::Duration) {
+        if let Some(webhooks) = &self.webhooks {
+            webhooks.dispatch(WebhookEvent::EngineDisabled {
+                engine: state.name.clone(),
+                consecutive_failures: state.consecutive_failures,
+                disabled_for_secs: disabled_for.as_secs(),
+                reason: "zero results".to_string(),
+            });
+        }
+    }
+
+    /// 引擎是否通过抓取页面获取结果（而不是官方 API）
+    async fn is_scraped_engine(&self, engine_name: &str) -> bool {
+        let cache = self.engine_cache.read().await;
+        cache
+            .get(engine_name)
+            .is_some_and(|engine| !engine.info().about.use_official_api)
+    }
+
+    /// 检测抓取引擎的选择器失效，刚被标记为降级时投递 Webhook 事件
+    ///
+    /// 引擎在非 2xx 响应时返回错误，因此这里的结果都来自成功响应
+    fn check_selector_rot(
+        &self,
+        state: &mut super::engine_manager::EngineState,
+        query: &str,
+        item_count: usize,
+        scraped: bool,
+    ) {
+        if !scraped {
+            return;
+        }
+        if state.record_selector_check(query, item_count, self.config.selector_rot_threshold)
+            && let Some(webhooks) = &self.webhooks
+        {
+            webhooks.dispatch(super::engine_manager::degraded_event(state));
+        }
+    }
+
     /// 获取隐私保护统计信息
     pub async fn get_privacy_stats(&self) -> Option<crate::net::privacy::PrivacyStats> {
         // 从 HTTP 客户端获取隐私管理器
@@ -792,6 +2361,49 @@ impl SearchInterface {
     }
 }
 
+/// 延迟预算到期时未完成引擎的错误信息
+const LATENCY_BUDGET_EXCEEDED: &str = "latency budget exceeded";
+
+/// 抢先返回时转入后台的引擎的说明
+const RACE_CONTINUED_IN_BACKGROUND: &str = "continued in background after race quorum";
+
+/// 计算请求延迟预算的截止时间（未设置预算时为 None）
+fn latency_deadline(request: &SearchRequest, start: std::time::Instant) -> Option<tokio::time::Instant> {
+    request
+        .max_latency_ms
+        .map(|ms| tokio::time::Instant::from_std(start) + Duration::from_millis(ms))
+}
+
+/// 等待下一个完成的引擎任务
+///
+/// # Returns
+///
+/// 所有任务完成时返回 `Ok(None)`，超过截止时间仍无任务完成时返回错误
+async fn next_within<S>(
+    pending: &mut S,
+    deadline: Option<tokio::time::Instant>,
+) -> Result<Option<S::Item>, tokio::time::error::Elapsed>
+where
+    S: futures::Stream + Unpin,
+{
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, pending.next()).await,
+        None => Ok(pending.next().await),
+    }
+}
+
+/// 为返回时仍未完成的引擎补充执行明细
+fn mark_unfinished(per_engine: &mut Vec<EngineTiming>, launched: &[String], elapsed_ms: u64, reason: &str) {
+    for engine in launched {
+        if !per_engine.iter().any(|timing| &timing.engine == engine) {
+            per_engine.push(EngineTiming {
+                elapsed_ms,
+                ..EngineTiming::skipped(engine, reason)
+            });
+        }
+    }
+}
+
 /// 搜索统计信息
 #[derive(Debug)]
 pub struct SearchStats {
@@ -805,6 +2417,8 @@ pub struct SearchStats {
     pub engine_failures: std::sync::atomic::AtomicU64,
     /// 超时次数
     pub timeouts: std::sync::atomic::AtomicU64,
+    /// 合并到其他进行中请求的次数
+    pub coalesced_requests: std::sync::atomic::AtomicU64,
 }
 
 impl Default for SearchStats {
@@ -815,6 +2429,7 @@ impl Default for SearchStats {
             cache_misses: std::sync::atomic::AtomicU64::new(0),
             engine_failures: std::sync::atomic::AtomicU64::new(0),
             timeouts: std::sync::atomic::AtomicU64::new(0),
+            coalesced_requests: std::sync::atomic::AtomicU64::new(0),
         }
     }
 }
@@ -832,6 +2447,8 @@ pub struct SearchStatsResult {
     pub engine_failures: u64,
     /// 超时次数
     pub timeouts: u64,
+    /// 合并到其他进行中请求的次数
+    pub coalesced_requests: u64,
 }
 
 #[cfg(test)]
@@ -855,6 +2472,7 @@ mod tests {
             cache_misses: AtomicU64::new(50),
             engine_failures: AtomicU64::new(5),
             timeouts: AtomicU64::new(2),
+            coalesced_requests: AtomicU64::new(0),
         };
 
         use std::sync::atomic::Ordering;
@@ -862,6 +2480,747 @@ mod tests {
         assert_eq!(stats.cache_hits.load(Ordering::Relaxed), 50);
     }
 
+    /// 给文本加上目标语言前缀的翻译后端
+    struct PrefixTranslator;
+
+    #[async_trait::async_trait]
+    impl Translator for PrefixTranslator {
+        fn name(&self) -> &str {
+            "prefix"
+        }
+
+        async fn translate(
+            &self,
+            texts: &[String],
+            _source: Option<&str>,
+            target: &str,
+        ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(texts.iter().map(|t| format!("[{}] {}", target, t)).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_translate_results() {
+        let interface = SearchInterface::new(SearchConfig::default())
+            .unwrap()
+            .with_translator(Arc::new(PrefixTranslator));
+
+        let item = |title: &str, content: &str| crate::derive::SearchResultItem {
+            title: title.to_string(),
+            url: format!("https://example.com/{}", title.len()),
+            content: content.to_string(),
+            display_url: None,
+            site_name: None,
+            score: 1.0,
+            result_type: crate::derive::ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: std::collections::HashMap::new(),
+        };
+        let mut response = SearchResponse {
+            results: vec![SearchResult {
+                engine_name: "test".to_string(),
+                total_results: None,
+                elapsed_ms: 0,
+                items: vec![
+                    item("Die schnelle Übersetzung", "Ein Artikel über die deutsche Sprache und ihre Grammatik"),
+                    item("The quick translation", "An article about the English language and its grammar"),
+                ],
+                pagination: None,
+                suggestions: Vec::new(),
+                metadata: std::collections::HashMap::new(),
+            }],
+            engines_used: Vec::new(),
+            total_count: 2,
+            query_time_ms: 0,
+            query: crate::derive::SearchQuery::default(),
+            cached: false,
+            answer: None,
+            suggestions: Vec::new(),
+            per_engine: Vec::new(),
+            partial: false,
+            related_queries: Vec::new(),
+            request_id: None,
+        };
+
+        interface.translate_results(&mut response, Some("en-US")).await;
+        let items = &response.results[0].items;
+        assert_eq!(items[0].title, "[en] Die schnelle Übersetzung");
+        assert_eq!(items[0].metadata["original_title"], "Die schnelle Übersetzung");
+        assert_eq!(items[0].metadata["translated_from"], "de");
+        assert_eq!(items[1].title, "The quick translation");
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let interface = SearchInterface::new(SearchConfig::default()).unwrap();
+        if interface.cursors.is_none() {
+            return;
+        }
+
+        let mut query = crate::derive::SearchQuery {
+            query: "cursor roundtrip through search interface".to_string(),
+            ..Default::default()
+        };
+        let result = SearchResult {
+            engine_name: "Crossref".to_string(),
+            total_results: None,
+            elapsed_ms: 0,
+            items: Vec::new(),
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: [(NEXT_CURSOR_METADATA_KEY.to_string(), "next-token".to_string())].into(),
+        };
+        interface.store_next_cursor(&query, "crossref", &result);
+
+        // 第一页不附加令牌，第二页取回第一页保存的令牌
+        assert!(!interface.query_with_cursor(&query, "crossref").params.contains_key(CURSOR_PARAM_KEY));
+        query.page = 2;
+        let next = interface.query_with_cursor(&query, "crossref");
+        assert_eq!(next.params.get(CURSOR_PARAM_KEY).map(String::as_str), Some("next-token"));
+        assert!(!interface.query_with_cursor(&query, "arxiv").params.contains_key(CURSOR_PARAM_KEY));
+    }
+
+    #[test]
+    fn test_suggest_spelling() {
+        let config = SearchConfig {
+            enable_cache: false,
+            ..Default::default()
+        };
+        let interface = SearchInterface::new(config).unwrap();
+
+        let result = |titles: &[&str]| SearchResult {
+            engine_name: "test".to_string(),
+            total_results: None,
+            elapsed_ms: 0,
+            items: titles
+                .iter()
+                .map(|title| crate::derive::SearchResultItem {
+                    title: title.to_string(),
+                    url: format!("https://example.com/{}", title.len()),
+                    content: String::new(),
+                    display_url: None,
+                    site_name: None,
+                    score: 1.0,
+                    result_type: crate::derive::ResultType::Web,
+                    thumbnail: None,
+                    published_date: None,
+                    template: None,
+                    metadata: std::collections::HashMap::new(),
+                })
+                .collect(),
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let response = |titles: &[&str]| SearchResponse {
+            results: vec![result(titles)],
+            engines_used: Vec::new(),
+            total_count: titles.len(),
+            query_time_ms: 0,
+            query: crate::derive::SearchQuery::default(),
+            cached: false,
+            answer: None,
+            suggestions: Vec::new(),
+            per_engine: Vec::new(),
+            partial: false,
+            related_queries: Vec::new(),
+            request_id: None,
+        };
+
+        // 结果足够多时只学习词汇，不给建议
+        let titles = ["Kubernetes docs", "Kubernetes tutorial", "Kubernetes blog", "Kubernetes API", "Kubernetes FAQ"];
+        let mut plenty = response(&titles);
+        interface.suggest_spelling(&mut plenty, "kubernetes");
+        assert!(plenty.suggestions.is_empty());
+
+        let mut few = response(&[]);
+        interface.suggest_spelling(&mut few, "kubernets");
+        assert_eq!(few.suggestions, vec!["kubernetes"]);
+    }
+
+    #[tokio::test]
+    async fn test_files_category_gate() {
+        let interface = SearchInterface::new(SearchConfig::default()).unwrap();
+        assert!(!interface.list_engines().contains(&"piratebay".to_string()));
+        assert!(interface.get_or_create_engine("piratebay").await.is_err());
+
+        let config = SearchConfig {
+            enable_files_category: true,
+            ..Default::default()
+        };
+        let interface = SearchInterface::new(config).unwrap();
+        assert!(interface.list_engines().contains(&"piratebay".to_string()));
+        assert!(interface.get_or_create_engine("piratebay").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_politeness_policies() {
+        let config = SearchConfig {
+            politeness: Some(crate::config::PolitenessConfig {
+                enabled: true,
+                ..Default::default()
+            }),
+            engine_politeness: std::collections::HashMap::from([(
+                "bing".to_string(),
+                crate::config::engines::EnginePolitenessConfig {
+                    min_delay_ms: Some(3000),
+                    respect_crawl_delay: None,
+                },
+            )]),
+            ..Default::default()
+        };
+        let interface = SearchInterface::new(config).unwrap();
+        interface.get_or_create_engine("bing").await.unwrap();
+        interface.get_or_create_engine("github").await.unwrap();
+
+        let politeness = interface.politeness.as_ref().unwrap();
+        let bing = politeness.policy(Some("bing"));
+        assert_eq!(bing.min_delay, Duration::from_secs(3));
+        assert!(bing.respect_crawl_delay);
+        // API 引擎默认不遵守 Crawl-delay
+        assert!(!politeness.policy(Some("github")).respect_crawl_delay);
+    }
+
+    #[test]
+    fn test_interface_rejects_invalid_scrub_pattern() {
+        let config = SearchConfig {
+            query_scrub_patterns: vec!["(".to_string()],
+            ..Default::default()
+        };
+        assert!(SearchInterface::new(config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prepare_request() {
+        let config = SearchConfig {
+            scrub_query_pii: true,
+            ..Default::default()
+        };
+        let interface = SearchInterface::new(config).unwrap();
+        let mut request = SearchRequest::default();
+        request.query.query = "forgot password alice@example.com".to_string();
+
+        let (prepared, warning, _) = interface.prepare_request(&request).await;
+        assert_eq!(prepared.query.query, "forgot password");
+        assert_eq!(prepared.query.language, Some("en".to_string()));
+        assert!(warning.is_some());
+
+        let mut result = SearchResult {
+            engine_name: "test".to_string(),
+            total_results: None,
+            elapsed_ms: 0,
+            items: Vec::new(),
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: std::collections::HashMap::new(),
+        };
+        SearchInterface::attach_query_warning(&mut result, warning);
+        assert!(result.metadata.contains_key(QUERY_WARNING_KEY));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_request_rewriters() {
+        let config = SearchConfig {
+            expand_synonyms: true,
+            ..Default::default()
+        };
+        let mut interface = SearchInterface::new(config).unwrap();
+        interface.add_rewriter(Arc::new(crate::search::rewrite::FnRewriter::new(
+            "suffix",
+            |query: &crate::derive::SearchQuery| Some(format!("{} docs", query.query)),
+        )));
+        let mut request = SearchRequest::default();
+        request.query.query = "k8s ingress".to_string();
+
+        let (prepared, _, _) = interface.prepare_request(&request).await;
+        // 内置改写器先执行，自定义改写器看到的是扩展后的查询
+        assert_eq!(prepared.query.query, "(k8s OR kubernetes) ingress docs");
+        assert_eq!(request.query.query, "k8s ingress");
+    }
+
+    #[tokio::test]
+    async fn test_engine_catalog() {
+        let interface = SearchInterface::new(SearchConfig::default()).unwrap();
+        let catalog = interface.engine_catalog().await;
+        assert_eq!(catalog.len(), interface.list_engines().len());
+
+        let bing = catalog.iter().find(|entry| entry.id == "bing").unwrap();
+        assert_eq!(bing.source, EngineSource::Builtin);
+        assert!(bing.global);
+        assert!(bing.available);
+        assert!(bing.info.capabilities.supports_pagination);
+
+        // 引擎信息展开在目录项顶层
+        let json = serde_json::to_value(bing).unwrap();
+        assert_eq!(json["id"], "bing");
+        assert_eq!(json["name"], "Bing");
+        assert_eq!(json["source"], "builtin");
+        assert!(json["capabilities"]["result_types"].is_array());
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    #[tokio::test]
+    async fn test_wasm_plugin_engines() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("demo.wasm"),
+            r#"(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "prepare_request") (param i32 i32) (result i64) unreachable)
+  (func (export "parse_response") (param i32 i32) (result i64) unreachable))"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("demo.json"), r#"{"name": "demo", "categories": ["it"]}"#).unwrap();
+
+        let interface = SearchInterface::new(SearchConfig {
+            plugin_paths: vec![dir.path().to_path_buf()],
+            ..Default::default()
+        })
+        .unwrap();
+
+        // 插件引擎可在请求中指定，未加载的名称仍被过滤
+        let requested = interface.filter_requested_engines(&["demo".to_string(), "missing".to_string()]);
+        assert_eq!(requested, vec!["demo".to_string()]);
+        let engine = interface.get_or_create_engine("demo").await.unwrap();
+        assert_eq!(engine.info().categories, vec!["it".to_string()]);
+
+        let catalog = interface.engine_catalog().await;
+        let demo = catalog.iter().find(|entry| entry.id == "demo").unwrap();
+        assert_eq!(demo.source, EngineSource::Wasm);
+        assert_eq!(serde_json::to_value(demo).unwrap()["source"], "wasm");
+    }
+
+    #[tokio::test]
+    async fn test_prepare_request_search_session() {
+        let interface = SearchInterface::new(SearchConfig {
+            expand_synonyms: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let token = interface.sessions().unwrap().create().unwrap();
+        let request = |query: &str| {
+            let mut request = SearchRequest {
+                session: Some(token.clone()),
+                ..Default::default()
+            };
+            request.query.query = query.to_string();
+            request
+        };
+
+        let (first, _, related) = interface.prepare_request(&request("k8s ingress")).await;
+        assert!(related.is_empty());
+        assert!(!first.query.params.contains_key(SESSION_CONTEXT_PARAM_KEY));
+
+        let (second, _, related) = interface.prepare_request(&request("nginx annotations")).await;
+        // 会话中记录改写前的查询
+        assert_eq!(related, ["k8s ingress"]);
+        assert_eq!(second.query.params[SESSION_CONTEXT_PARAM_KEY], "k8s ingress");
+
+        let (_, _, related) = interface.prepare_request(&SearchRequest::default()).await;
+        assert!(related.is_empty());
+        interface.sessions().unwrap().delete(&token).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_engine_report_in_memory() {
+        let config = SearchConfig {
+            enable_cache: false,
+            daily_bandwidth_quota: Some(4096),
+            ..Default::default()
+        };
+        let interface = SearchInterface::new(config).unwrap();
+        interface.engine_stats.record_failure("bing", "Engine bing timeout").await;
+        interface.bandwidth.record("bing", 4096);
+
+        let report = interface.engine_report().await;
+        assert_eq!(report.total_failures, 1);
+        let bing = report.engine("bing").unwrap();
+        assert_eq!(bing.stats.last_failure_reason.as_deref(), Some("Engine bing timeout"));
+        let bandwidth = bing.bandwidth.as_ref().unwrap();
+        assert_eq!(bandwidth.bytes_today, 4096);
+        assert!(bandwidth.quota_exhausted);
+    }
+
+    #[tokio::test]
+    async fn test_every_search_path_is_archived() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("searches.jsonl");
+        let config = SearchConfig {
+            enable_cache: false,
+            archive: Some(crate::config::ArchiveConfig {
+                enabled: true,
+                path: path.to_string_lossy().to_string(),
+                include_empty: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let interface = SearchInterface::new(config).unwrap();
+        // 延迟预算立即到期，不等待引擎的网络请求
+        let request = |query: &str| {
+            let mut request = SearchRequest {
+                engines: vec!["bing".to_string()],
+                max_latency_ms: Some(0),
+                ..Default::default()
+            };
+            request.query.query = query.to_string();
+            request
+        };
+
+        interface.search(&request("plain")).await.unwrap();
+        interface
+            .search_with_mode(&request("mode"), EngineMode::Custom(vec!["bing".to_string()]))
+            .await
+            .unwrap();
+        interface.search_streaming(&request("streaming"), |_, _| {}).await.unwrap();
+        interface.archive().unwrap().close();
+
+        let queries: Vec<String> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<crate::search::archive::ArchiveRecord>(line).unwrap().query)
+            .collect();
+        assert_eq!(queries, vec!["plain", "mode", "streaming"]);
+    }
+
+    #[tokio::test]
+    async fn test_aggregation_strategy_per_search() {
+        let interface = SearchInterface::new(SearchConfig { enable_cache: false, ..Default::default() }).unwrap();
+        let item = |engine: &str, n: usize, title: &str| crate::derive::SearchResultItem {
+            title: title.to_string(),
+            url: format!("https://{}.example.com/{}", engine, n),
+            content: title.to_string(),
+            display_url: None,
+            site_name: None,
+            score: 1.0,
+            result_type: crate::derive::ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: std::collections::HashMap::new(),
+        };
+        let result = |engine: &str| SearchResult {
+            engine_name: engine.to_string(),
+            total_results: Some(2),
+            elapsed_ms: 0,
+            items: vec![item(engine, 1, "rust async runtime"), item(engine, 2, "rust")],
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let results = || vec![result("google"), result("bing")];
+        let query = crate::derive::SearchQuery { query: "rust async runtime".to_string(), ..Default::default() };
+        let order = |aggregated: SearchResult| aggregated.items.into_iter().map(|i| i.url).collect::<Vec<_>>();
+
+        // 默认按相关性合并：两条完全匹配的结果排在前面
+        let merged = order(interface.aggregate_results(results(), &query, None, None).await);
+        assert!(merged[..2].iter().all(|url| url.ends_with("/1")));
+
+        let round_robin = order(
+            interface
+                .aggregate_results(results(), &query, None, Some((&AggregationStrategy::RoundRobin, SortBy::Relevance)))
+                .await,
+        );
+        let engines: Vec<_> = round_robin.iter().map(|url| url.split('.').next().unwrap()).collect();
+        assert_ne!(engines[0], engines[1]);
+        assert_eq!(engines[0], engines[2]);
+        assert_eq!(engines[1], engines[3]);
+
+        let priority = AggregationStrategy::EnginePriority { engines: vec!["bing".to_string()] };
+        let by_priority = order(
+            interface
+                .aggregate_results(results(), &query, None, Some((&priority, SortBy::Relevance)))
+                .await,
+        );
+        assert_eq!(
+            by_priority,
+            vec![
+                "https://bing.example.com/1",
+                "https://bing.example.com/2",
+                "https://google.example.com/1",
+                "https://google.example.com/2",
+            ]
+        );
+
+        let score_only = interface
+            .aggregate_results(results(), &query, None, Some((&AggregationStrategy::ScoreOnly, SortBy::Relevance)))
+            .await;
+        assert!(score_only.items.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        assert!(score_only.items[..2].iter().all(|item| item.url.ends_with("/1")));
+
+        let by_source = order(
+            interface
+                .aggregate_results(results(), &query, None, Some((&AggregationStrategy::Merged, SortBy::Source)))
+                .await,
+        );
+        let mut sorted = by_source.clone();
+        sorted.sort();
+        assert_eq!(by_source, sorted);
+    }
+
+    #[tokio::test]
+    async fn test_category_weights_in_aggregation() {
+        let interface = SearchInterface::new(SearchConfig { enable_cache: false, ..Default::default() }).unwrap();
+        let result = |engine: &str| SearchResult {
+            engine_name: engine.to_string(),
+            total_results: Some(1),
+            elapsed_ms: 0,
+            items: vec![crate::derive::SearchResultItem {
+                title: "rust language".to_string(),
+                url: format!("https://{}.example.com/rust", engine),
+                content: "rust language".to_string(),
+                display_url: None,
+                site_name: None,
+                score: 1.0,
+                result_type: crate::derive::ResultType::Web,
+                thumbnail: None,
+                published_date: None,
+                template: None,
+                metadata: std::collections::HashMap::new(),
+            }],
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let query = crate::derive::SearchQuery { query: "rust language".to_string(), ..Default::default() };
+
+        let aggregated = interface.aggregate_results(vec![result("google"), result("bing")], &query, None, None).await;
+        assert!(aggregated.items[0].url.contains("google"));
+
+        let multipliers = std::collections::HashMap::from([("bing".to_string(), 2.0), ("google".to_string(), 0.5)]);
+        let aggregated = interface
+            .aggregate_results(vec![result("google"), result("bing")], &query, Some(&multipliers), None)
+            .await;
+        assert!(aggregated.items[0].url.contains("bing"));
+
+        let error = interface
+            .search_with_mode(&SearchRequest::default(), EngineMode::Category("news".to_string()))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("news"));
+    }
+
+    #[tokio::test]
+    async fn test_per_engine_records_skipped_engines() {
+        let config = SearchConfig {
+            enable_cache: false,
+            daily_bandwidth_quota: Some(4096),
+            ..Default::default()
+        };
+        let interface = SearchInterface::new(config).unwrap();
+        interface.bandwidth.record("bing", 4096);
+
+        let request = SearchRequest::default();
+        let (response, _) = interface.fan_out_search(&request, &["bing".to_string()], None).await.unwrap();
+        assert!(response.engines_used.is_empty());
+        assert_eq!(response.per_engine.len(), 1);
+        let timing = &response.per_engine[0];
+        assert_eq!(timing.engine, "bing");
+        assert_eq!(timing.result_count, 0);
+        assert_eq!(timing.error.as_deref(), Some("daily bandwidth quota exhausted"));
+    }
+
+    #[tokio::test]
+    async fn test_negative_cache_skips_engine() {
+        let interface = SearchInterface::new(SearchConfig::default()).unwrap();
+        let Some(ref cache) = interface.negative_cache else {
+            return;
+        };
+        let mut request = SearchRequest::default();
+        request.query.query = "negative cache skip test".to_string();
+        cache
+            .set_negative(&request.query, "bing", NegativeOutcome::Blocked, Duration::from_secs(60))
+            .unwrap();
+
+        let (response, _) = interface.fan_out_search(&request, &["bing".to_string()], None).await.unwrap();
+        assert!(response.engines_used.is_empty());
+        assert_eq!(response.per_engine[0].error.as_deref(), Some("cached negative outcome: blocked"));
+
+        // 使引擎失效会删除其命名空间中的负面结果
+        interface.invalidate_engine("bing").await.unwrap();
+        assert_eq!(cache.get_negative(&request.query, "bing").unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_unmet_dependencies_mark_engine_inactive() {
+        let mut config = SearchConfig::default();
+        config.engine_dependencies.insert(
+            "bing".to_string(),
+            crate::config::engines::EngineDependencies {
+                required: vec!["env:SEESEA_TEST_MISSING_KEY".to_string()],
+                optional: Vec::new(),
+                system_requirements: crate::config::engines::SystemRequirements {
+                    min_memory_mb: None,
+                    min_cpu_cores: None,
+                    min_disk_space_mb: None,
+                    supported_platforms: Vec::new(),
+                },
+            },
+        );
+        let interface = SearchInterface::new(config).unwrap();
+        assert!(interface.inactive_reason("bing").unwrap().contains("SEESEA_TEST_MISSING_KEY"));
+        assert!(interface.inactive_reason("google").is_none());
+
+        let request = SearchRequest::default();
+        let (response, _) = interface.fan_out_search(&request, &["bing".to_string()], None).await.unwrap();
+        assert!(response.engines_used.is_empty());
+        assert!(response.per_engine[0].error.as_deref().unwrap().starts_with("inactive: "));
+    }
+
+    #[tokio::test]
+    async fn test_latency_budget_stops_waiting() {
+        let start = std::time::Instant::now();
+        let request = SearchRequest {
+            max_latency_ms: Some(50),
+            ..Default::default()
+        };
+        let mut pending: FuturesUnordered<_> = [0u64, 10_000]
+            .into_iter()
+            .map(|ms| async move {
+                tokio::time::sleep(Duration::from_millis(ms)).await;
+                ms
+            })
+            .collect();
+
+        let deadline = latency_deadline(&request, start);
+        assert_eq!(next_within(&mut pending, deadline).await.unwrap(), Some(0));
+        assert!(next_within(&mut pending, deadline).await.is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        let mut per_engine = vec![EngineTiming { engine: "bing".to_string(), ..Default::default() }];
+        mark_unfinished(&mut per_engine, &["bing".to_string(), "google".to_string()], 50, LATENCY_BUDGET_EXCEEDED);
+        assert_eq!(per_engine.len(), 2);
+        assert_eq!(per_engine[1].engine, "google");
+        assert_eq!(per_engine[1].elapsed_ms, 50);
+        assert_eq!(per_engine[1].error.as_deref(), Some(LATENCY_BUDGET_EXCEEDED));
+    }
+
+    #[test]
+    fn test_route_by_language() {
+        let interface = SearchInterface::new(SearchConfig::default()).unwrap();
+        let engines = vec!["bing".to_string(), "yandex".to_string()];
+
+        let mut query = crate::derive::SearchQuery {
+            language: Some("zh".to_string()),
+            ..Default::default()
+        };
+        let routed = interface.route_by_language(engines.clone(), &query);
+        assert_eq!(routed[0], "baidu");
+        assert_eq!(routed[1], "sogou");
+        assert!(routed.contains(&"bing".to_string()));
+
+        query.language = Some("en".to_string());
+        assert_eq!(interface.route_by_language(engines.clone(), &query), engines);
+    }
+
+    fn geo_config() -> SearchConfig {
+        let mut config = SearchConfig::default();
+        config.engine_geo.insert(
+            "yandex".to_string(),
+            crate::config::engines::EngineGeoConfig {
+                regions: vec!["ru".to_string()],
+                origin: Some("https://yandex.com".to_string()),
+                mirrors: vec![
+                    crate::config::engines::EngineMirrorConfig {
+                        origin: "https://yandex.ru".to_string(),
+                        region: Some("ru".to_string()),
+                    },
+                    crate::config::engines::EngineMirrorConfig {
+                        origin: "https://yandex.eu".to_string(),
+                        region: None,
+                    },
+                ],
+                ..Default::default()
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn test_route_by_region() {
+        let interface = SearchInterface::new(geo_config()).unwrap();
+        let engines = vec!["bing".to_string(), "yandex".to_string()];
+
+        let mut query = crate::derive::SearchQuery {
+            region: Some("RU".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(interface.route_by_region(engines.clone(), &query), vec!["yandex", "bing"]);
+
+        query.region = Some("us".to_string());
+        assert_eq!(interface.route_by_region(engines.clone(), &query), engines);
+    }
+
+    #[tokio::test]
+    async fn test_engine_route_prefers_region_mirror() {
+        let interface = SearchInterface::new(geo_config()).unwrap();
+        let primary = interface.get_or_create_engine("yandex").await.unwrap();
+
+        let route = interface.engine_route("yandex", Arc::clone(&primary), Some("ru")).await;
+        let endpoints: Vec<_> = route.candidates.iter().map(|(endpoint, _)| endpoint.as_deref()).collect();
+        assert_eq!(endpoints, vec![Some("https://yandex.ru"), None, Some("https://yandex.eu")]);
+
+        // 被封锁的镜像在冷却期内跳过
+        interface.geo.mark_blocked("yandex", Some("https://yandex.ru"));
+        let route = interface.engine_route("yandex", Arc::clone(&primary), Some("ru")).await;
+        let endpoints: Vec<_> = route.candidates.iter().map(|(endpoint, _)| endpoint.as_deref()).collect();
+        assert_eq!(endpoints, vec![None, Some("https://yandex.eu")]);
+
+        // 未配置地域的引擎只使用主服务源
+        let bing = interface.get_or_create_engine("bing").await.unwrap();
+        let route = interface.engine_route("bing", bing, Some("ru")).await;
+        assert_eq!(route.candidates.len(), 1);
+        assert!(route.candidates[0].0.is_none());
+
+        interface.invalidate_engine("yandex").await.unwrap();
+        assert!(interface.mirror_engines.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_route_code_intent_to_code_engines() {
+        let config = SearchConfig {
+            enable_intent_routing: true,
+            ..Default::default()
+        };
+        let interface = SearchInterface::new(config).unwrap();
+        let query = crate::derive::SearchQuery {
+            query: "rust lifetime error".to_string(),
+            ..Default::default()
+        };
+        let routed = interface.route_by_intent(EngineListConfig::get_default_engines(), &query).await;
+        assert_eq!(&routed[..2], &["github".to_string(), "stackoverflow".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_route_by_intent() {
+        let config = SearchConfig {
+            enable_intent_routing: true,
+            ..Default::default()
+        };
+        let interface = SearchInterface::new(config).unwrap();
+        let engines = EngineListConfig::get_default_engines();
+
+        let query = crate::derive::SearchQuery {
+            query: "breaking news today".to_string(),
+            ..Default::default()
+        };
+        let routed = interface.route_by_intent(engines.clone(), &query).await;
+        assert_eq!(routed[0], "bing_news");
+        assert!(routed.contains(&"sogou_wechat".to_string()));
+        assert!(routed.contains(&"bing".to_string()));
+        assert!(!routed.contains(&"bing_images".to_string()));
+
+        // 未命中意图时保持原列表
+        let query = crate::derive::SearchQuery {
+            query: "hello world".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(interface.route_by_intent(engines.clone(), &query).await, engines);
+    }
+
     #[test]
     fn test_list_engines() {
         let config = SearchConfig::default();
@@ -0,0 +1,201 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 结果后处理模块
+//!
+//! 聚合评分之后按注册顺序依次执行结果处理器，处理器可以重新排序、补充元数据或移除结果。
+//! 下游可以实现 [`ResultProcessor`] 注入自定义逻辑，同步处理逻辑可以直接用
+//! [`FnProcessor`] 包装闭包。处理器出错时记录警告并保留处理前的结果
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::derive::{SearchQuery, SearchResultItem};
+
+/// 结果处理错误
+pub type ProcessorError = Box<dyn std::error::Error + Send + Sync>;
+
+/// 结果处理器
+///
+/// 处理器按注册顺序执行，每个处理器看到的是前一个处理器的输出
+#[async_trait]
+pub trait ResultProcessor: Send + Sync {
+    /// 处理器名称
+    fn name(&self) -> &str;
+
+    /// 处理聚合后的结果列表
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 发送到引擎的查询
+    /// * `items` - 聚合排序后的结果（可以原地重排、修改或删除）
+    ///
+    /// # Returns
+    ///
+    /// 处理失败时返回错误，此时本处理器对结果的修改被丢弃
+    async fn process(&self, query: &SearchQuery, items: &mut Vec<SearchResultItem>) -> Result<(), ProcessorError>;
+}
+
+/// 闭包处理器，用于包装同步处理逻辑
+pub struct FnProcessor<F> {
+    name: String,
+    process: F,
+}
+
+impl<F> FnProcessor<F>
+where
+    F: Fn(&SearchQuery, &mut Vec<SearchResultItem>) + Send + Sync,
+{
+    /// 创建闭包处理器
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 处理器名称
+    /// * `process` - 处理函数
+    pub fn new(name: impl Into<String>, process: F) -> Self {
+        Self { name: name.into(), process }
+    }
+}
+
+#[async_trait]
+impl<F> ResultProcessor for FnProcessor<F>
+where
+    F: Fn(&SearchQuery, &mut Vec<SearchResultItem>) + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn process(&self, query: &SearchQuery, items: &mut Vec<SearchResultItem>) -> Result<(), ProcessorError> {
+        (self.process)(query, items);
+        Ok(())
+    }
+}
+
+/// 结果处理器列表
+#[derive(Default, Clone)]
+pub struct ResultProcessors {
+    processors: Vec<Arc<dyn ResultProcessor>>,
+}
+
+impl ResultProcessors {
+    /// 创建空列表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册处理器（追加到末尾）
+    pub fn register(&mut self, processor: Arc<dyn ResultProcessor>) {
+        self.processors.push(processor);
+    }
+
+    /// 已注册的处理器名称
+    pub fn names(&self) -> Vec<&str> {
+        self.processors.iter().map(|p| p.name()).collect()
+    }
+
+    /// 是否没有注册任何处理器
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
+    /// 依次执行所有处理器
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 发送到引擎的查询
+    /// * `items` - 聚合排序后的结果
+    pub async fn apply(&self, query: &SearchQuery, items: &mut Vec<SearchResultItem>) {
+        apply_processors(&self.processors, query, items).await;
+    }
+}
+
+/// 依次执行处理器，出错的处理器不影响结果
+pub(crate) async fn apply_processors(
+    processors: &[Arc<dyn ResultProcessor>],
+    query: &SearchQuery,
+    items: &mut Vec<SearchResultItem>,
+) {
+    for processor in processors {
+        let mut processed = items.clone();
+        match processor.process(query, &mut processed).await {
+            Ok(()) => *items = processed,
+            Err(e) => tracing::warn!("Result processor {} failed: {}", processor.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FailingProcessor;
+
+    #[async_trait]
+    impl ResultProcessor for FailingProcessor {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn process(&self, _query: &SearchQuery, items: &mut Vec<SearchResultItem>) -> Result<(), ProcessorError> {
+            items.clear();
+            Err("boom".into())
+        }
+    }
+
+    fn item(url: &str) -> SearchResultItem {
+        SearchResultItem {
+            title: url.to_string(),
+            url: url.to_string(),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score: 1.0,
+            result_type: crate::derive::ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_processors_chain() {
+        let mut processors = ResultProcessors::new();
+        processors.register(Arc::new(FnProcessor::new("drop_http", |_: &SearchQuery, items: &mut Vec<SearchResultItem>| {
+            items.retain(|item| item.url.starts_with("https://"));
+        })));
+        processors.register(Arc::new(FailingProcessor));
+        processors.register(Arc::new(FnProcessor::new("rerank", |_: &SearchQuery, items: &mut Vec<SearchResultItem>| {
+            items.reverse();
+            for item in items.iter_mut() {
+                item.metadata.insert("reranked".to_string(), "true".to_string());
+            }
+        })));
+        assert_eq!(processors.names(), vec!["drop_http", "failing", "rerank"]);
+
+        let mut items = vec![
+            item("https://a.example"),
+            item("http://b.example"),
+            item("https://c.example"),
+        ];
+        processors.apply(&SearchQuery::default(), &mut items).await;
+
+        // 出错的处理器被跳过，不影响前后处理器的结果
+        let urls: Vec<&str> = items.iter().map(|item| item.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://c.example", "https://a.example"]);
+        assert!(items.iter().all(|item| item.metadata.contains_key("reranked")));
+    }
+}
@@ -15,6 +15,51 @@
 //! 查询解析器模块
 //!
 //! 负责解析和分析搜索查询，识别查询意图、语言、地区等
+//!
+//! 另外提供可选的敏感信息清洗阶段：在查询发送到各搜索引擎之前，
+//! 移除邮箱、电话号码以及用户自定义的敏感模式
+//!
+//! 启用拼写纠错后，可为结果较少的查询给出"您是不是要找"的纠正建议
+//!
+//! 中文查询在规范化时可统一为简体，并附带拼音形式用于拼音回退匹配
+
+use once_cell::sync::{Lazy, OnceCell};
+use regex::Regex;
+use std::sync::{Arc, RwLock};
+
+use super::chinese::{contains_pinyin_chars, to_pinyin, to_simplified, ChineseMatching, KeywordMatcher};
+use super::spelling::SpellCorrector;
+
+/// 邮箱地址匹配
+static EMAIL_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}")
+        .expect("invalid email pattern")
+});
+
+/// 电话号码匹配
+///
+/// 只匹配结构明确的号码（国际区号、带分隔符的北美格式、中国大陆手机号），
+/// 避免把 "rust 2015 2018" 这类年份组合误判为电话号码
+static PHONE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{3}\)\s?|\b\d{3}[.-])\d{3}[.-]\d{4}\b|\+\d{1,3}[\s.-]?\d[\d\s.-]{6,}\d|\b1[3-9]\d{9}\b",
+    )
+    .expect("invalid phone pattern")
+});
+
+/// whatlang 检测结果的最低置信度
+///
+/// 搜索查询通常很短，whatlang 很少给出 reliable 结果，这里放宽到较低的置信度
+const LANGUAGE_CONFIDENCE_THRESHOLD: f64 = 0.35;
+
+/// 限定在常见搜索语言内的语言检测器，减少短查询的误判
+static LANGUAGE_DETECTOR: Lazy<whatlang::Detector> = Lazy::new(|| {
+    use whatlang::Lang::*;
+    whatlang::Detector::with_allowlist(vec![
+        Eng, Cmn, Jpn, Kor, Rus, Ukr, Spa, Por, Fra, Deu, Ita, Nld, Pol, Tur, Ara, Hin, Vie, Tha,
+        Ind, Swe,
+    ])
+});
 
 /// 查询意图
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,12 +82,82 @@ pub enum QueryIntent {
     Code,
 }
 
+/// 敏感信息类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PiiKind {
+    /// 邮箱地址
+    Email,
+    /// 电话号码
+    Phone,
+    /// 用户自定义模式
+    Custom,
+}
+
+impl PiiKind {
+    /// 获取可读名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "邮箱地址",
+            PiiKind::Phone => "电话号码",
+            PiiKind::Custom => "自定义敏感内容",
+        }
+    }
+}
+
+/// 敏感信息清洗结果
+#[derive(Debug, Clone)]
+pub struct ScrubResult {
+    /// 清洗后的查询
+    pub query: String,
+    /// 被移除的敏感信息类型（去重，按发现顺序）
+    pub removed: Vec<PiiKind>,
+}
+
+impl ScrubResult {
+    /// 查询是否被修改
+    pub fn is_modified(&self) -> bool {
+        !self.removed.is_empty()
+    }
+
+    /// 生成返回给用户的警告信息
+    pub fn warning(&self) -> Option<String> {
+        if self.removed.is_empty() {
+            return None;
+        }
+        let labels: Vec<&str> = self.removed.iter().map(|k| k.label()).collect();
+        Some(format!(
+            "查询中包含{}，已在发送至搜索引擎前移除",
+            labels.join("、")
+        ))
+    }
+}
+
+/// 拼写纠错词典的初始词汇来源（如缓存中的结果标题）
+pub type DictionarySource = Arc<dyn Fn() -> Vec<String> + Send + Sync>;
+
+/// 拼写纠错状态
+///
+/// 词典在第一次需要纠错时才从词汇来源构建，之后随搜索结果持续学习
+struct SpellingState {
+    corrector: RwLock<SpellCorrector>,
+    source: Option<DictionarySource>,
+    seeded: OnceCell<()>,
+}
+
 /// 查询解析器
 pub struct QueryParser {
     /// 是否启用意图识别
     enable_intent_detection: bool,
     /// 是否启用语言检测
     enable_language_detection: bool,
+    /// 是否启用敏感信息清洗
+    enable_pii_scrubbing: bool,
+    /// 用户自定义的敏感模式
+    custom_patterns: Vec<Regex>,
+    /// 拼写纠错状态，None 表示未启用
+    spelling: Option<Arc<SpellingState>>,
+    /// 中文繁简统一与拼音匹配选项
+    chinese: ChineseMatching,
 }
 
 impl QueryParser {
@@ -51,12 +166,156 @@ impl QueryParser {
         Self {
             enable_intent_detection: true,
             enable_language_detection: true,
+            enable_pii_scrubbing: false,
+            custom_patterns: Vec::new(),
+            spelling: None,
+            chinese: ChineseMatching::default(),
+        }
+    }
+
+    /// 设置中文繁简统一与拼音匹配选项
+    pub fn with_chinese_matching(mut self, options: ChineseMatching) -> Self {
+        self.chinese = options;
+        self
+    }
+
+    /// 为查询创建关键词匹配器（用于缓存全文搜索）
+    ///
+    /// 关键词为规范化查询按空白拆分的各个词，匹配时使用解析器的中文匹配选项
+    pub fn keyword_matcher(&self, query: &str) -> KeywordMatcher {
+        KeywordMatcher::new(self.normalize(query).split_whitespace(), self.chinese)
+    }
+
+    /// 启用或禁用敏感信息清洗
+    pub fn with_pii_scrubbing(mut self, enabled: bool) -> Self {
+        self.enable_pii_scrubbing = enabled;
+        self
+    }
+
+    /// 添加自定义敏感模式
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - 正则表达式列表
+    ///
+    /// # Returns
+    ///
+    /// 任意一个模式无法编译时返回错误
+    pub fn with_custom_patterns<I, S>(mut self, patterns: I) -> Result<Self, regex::Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for pattern in patterns {
+            self.custom_patterns.push(Regex::new(pattern.as_ref())?);
+        }
+        Ok(self)
+    }
+
+    /// 启用拼写纠错
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - 词典初始词汇来源，首次纠错时调用一次
+    pub fn with_spell_correction(mut self, source: Option<DictionarySource>) -> Self {
+        self.spelling = Some(Arc::new(SpellingState {
+            corrector: RwLock::new(SpellCorrector::new()),
+            source,
+            seeded: OnceCell::new(),
+        }));
+        self
+    }
+
+    /// 是否启用了拼写纠错
+    pub fn spell_correction_enabled(&self) -> bool {
+        self.spelling.is_some()
+    }
+
+    /// 将文本（如结果标题）加入拼写纠错词典
+    pub fn learn<'a, I>(&self, texts: I)
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let Some(ref spelling) = self.spelling else {
+            return;
+        };
+        if let Ok(mut corrector) = spelling.corrector.write() {
+            for text in texts {
+                corrector.add_text(text);
+            }
+        }
+    }
+
+    /// 给出查询的拼写纠正建议
+    ///
+    /// # Returns
+    ///
+    /// 未启用纠错、词典为空或查询无需纠正时返回 None
+    pub fn suggest_correction(&self, query: &str) -> Option<String> {
+        let spelling = self.spelling.as_ref()?;
+        spelling.seeded.get_or_init(|| {
+            if let Some(ref source) = spelling.source {
+                self.learn(source().iter().map(String::as_str));
+            }
+        });
+
+        let corrector = spelling.corrector.read().ok()?;
+        corrector
+            .correct(query)
+            .filter(|corrected| !corrected.eq_ignore_ascii_case(query.trim()))
+    }
+
+    /// 是否启用了敏感信息清洗
+    pub fn pii_scrubbing_enabled(&self) -> bool {
+        self.enable_pii_scrubbing
+    }
+
+    /// 清洗查询中的敏感信息
+    ///
+    /// 未启用清洗时原样返回查询
+    pub fn scrub(&self, query: &str) -> ScrubResult {
+        if !self.enable_pii_scrubbing {
+            return ScrubResult {
+                query: query.to_string(),
+                removed: Vec::new(),
+            };
+        }
+
+        let mut removed = Vec::new();
+        let mut text = query.to_string();
+
+        let mut strip = |text: &mut String, re: &Regex, kind: PiiKind| {
+            if re.is_match(text) {
+                *text = re.replace_all(text, " ").into_owned();
+                if !removed.contains(&kind) {
+                    removed.push(kind);
+                }
+            }
+        };
+
+        strip(&mut text, &EMAIL_PATTERN, PiiKind::Email);
+        strip(&mut text, &PHONE_PATTERN, PiiKind::Phone);
+        for re in &self.custom_patterns {
+            strip(&mut text, re, PiiKind::Custom);
+        }
+
+        if removed.is_empty() {
+            return ScrubResult {
+                query: query.to_string(),
+                removed,
+            };
+        }
+
+        ScrubResult {
+            query: text.split_whitespace().collect::<Vec<_>>().join(" "),
+            removed,
         }
     }
 
     /// 解析查询
     pub fn parse(&self, query: &str) -> ParsedQuery {
-        let cleaned = self.normalize(query);
+        let scrubbed = self.scrub(query);
+        let cleaned = self.normalize(&scrubbed.query);
         let intent = if self.enable_intent_detection {
             self.detect_intent(&cleaned)
         } else {
@@ -68,19 +327,32 @@ impl QueryParser {
             None
         };
 
+        let pinyin = (self.chinese.pinyin && contains_pinyin_chars(&cleaned)).then(|| to_pinyin(&cleaned));
+
         ParsedQuery {
             original: query.to_string(),
+            pinyin,
             normalized: cleaned,
             intent,
             language,
             region: None,
             expanded_terms: Vec::new(),
+            warnings: scrubbed.warning().into_iter().collect(),
         }
     }
 
-    /// 规范化查询
+    /// 规范化查询（去除首尾空白、转为小写，启用繁简统一时将繁体转为简体）
+    ///
+    /// 含假名的日文查询不做繁简转换
     fn normalize(&self, query: &str) -> String {
-        query.trim().to_lowercase()
+        let normalized = query.trim().to_lowercase();
+        if self.chinese.normalize_script
+            && !normalized.chars().any(|c| ('\u{3040}'..='\u{30ff}').contains(&c))
+        {
+            to_simplified(&normalized)
+        } else {
+            normalized
+        }
     }
 
     /// 检测查询意图
@@ -109,16 +381,9 @@ impl QueryParser {
         QueryIntent::Informational
     }
 
-    /// 检测语言
-    fn detect_language(&self, query: &str) -> Option<String> {
-        // 简单的语言检测
-        if query.chars().any(|c| '\u{4e00}' <= c && c <= '\u{9fff}') {
-            return Some("zh".to_string());
-        }
-        if query.chars().all(|c| c.is_ascii()) {
-            return Some("en".to_string());
-        }
-        None
+    /// 检测语言（见 [`detect_language`]）
+    pub fn detect_language(&self, query: &str) -> Option<String> {
+        detect_language(query)
     }
 
     /// 扩展查询（添加同义词等）
@@ -128,6 +393,65 @@ impl QueryParser {
     }
 }
 
+/// 检测文本语言，返回 ISO 639-1 代码
+///
+/// 先按文字系统快速判断中日韩文本，其余交给 whatlang；
+/// 文本较短导致 whatlang 置信度不足时，纯 ASCII 文本视为英文
+pub fn detect_language(text: &str) -> Option<String> {
+    if text.chars().any(|c| ('\u{3040}'..='\u{30ff}').contains(&c)) {
+        return Some("ja".to_string());
+    }
+    if text.chars().any(|c| ('\u{ac00}'..='\u{d7af}').contains(&c)) {
+        return Some("ko".to_string());
+    }
+    if text.chars().any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c)) {
+        return Some("zh".to_string());
+    }
+
+    if let Some(info) = LANGUAGE_DETECTOR.detect(text)
+        && (info.is_reliable() || info.confidence() >= LANGUAGE_CONFIDENCE_THRESHOLD)
+    {
+        return Some(language_code(info.lang()));
+    }
+
+    if text.is_ascii() {
+        return Some("en".to_string());
+    }
+    None
+}
+
+/// 将 whatlang 的 ISO 639-3 语言转换为搜索引擎常用的 ISO 639-1 代码
+///
+/// 没有对应两字母代码的语言保留三字母代码
+fn language_code(lang: whatlang::Lang) -> String {
+    use whatlang::Lang;
+
+    let code = match lang {
+        Lang::Eng => "en",
+        Lang::Cmn => "zh",
+        Lang::Jpn => "ja",
+        Lang::Kor => "ko",
+        Lang::Rus => "ru",
+        Lang::Ukr => "uk",
+        Lang::Spa => "es",
+        Lang::Por => "pt",
+        Lang::Fra => "fr",
+        Lang::Deu => "de",
+        Lang::Ita => "it",
+        Lang::Nld => "nl",
+        Lang::Pol => "pl",
+        Lang::Tur => "tr",
+        Lang::Ara => "ar",
+        Lang::Hin => "hi",
+        Lang::Vie => "vi",
+        Lang::Tha => "th",
+        Lang::Ind => "id",
+        Lang::Swe => "sv",
+        other => other.code(),
+    };
+    code.to_string()
+}
+
 impl Default for QueryParser {
     fn default() -> Self {
         Self::new()
@@ -141,6 +465,8 @@ pub struct ParsedQuery {
     pub original: String,
     /// 规范化查询
     pub normalized: String,
+    /// 规范化查询的拼音形式（查询不含汉字或未启用拼音匹配时为 None）
+    pub pinyin: Option<String>,
     /// 查询意图
     pub intent: QueryIntent,
     /// 语言
@@ -149,6 +475,8 @@ pub struct ParsedQuery {
     pub region: Option<String>,
     /// 扩展词汇
     pub expanded_terms: Vec<String>,
+    /// 解析过程中产生的警告（如移除了敏感信息）
+    pub warnings: Vec<String>,
 }
 
 #[cfg(test)]
@@ -160,6 +488,7 @@ mod tests {
         let parser = QueryParser::new();
         assert!(parser.enable_intent_detection);
         assert!(parser.enable_language_detection);
+        assert!(!parser.enable_pii_scrubbing);
     }
 
     #[test]
@@ -204,6 +533,24 @@ mod tests {
         assert_eq!(lang, Some("en".to_string()));
     }
 
+    #[test]
+    fn test_detect_language_japanese_and_korean() {
+        let parser = QueryParser::new();
+        assert_eq!(parser.detect_language("東京の天気"), Some("ja".to_string()));
+        assert_eq!(parser.detect_language("서울 날씨"), Some("ko".to_string()));
+    }
+
+    #[test]
+    fn test_detect_language_whatlang() {
+        let parser = QueryParser::new();
+        let lang = parser.detect_language("где находится ближайшая станция метро");
+        assert_eq!(lang, Some("ru".to_string()));
+        let lang = parser.detect_language("quelle est la meilleure façon d'apprendre le français");
+        assert_eq!(lang, Some("fr".to_string()));
+        let lang = parser.detect_language("wie spät ist es");
+        assert_eq!(lang, Some("de".to_string()));
+    }
+
     #[test]
     fn test_parse_complete() {
         let parser = QueryParser::new();
@@ -221,4 +568,103 @@ mod tests {
         assert_eq!(parsed.intent, QueryIntent::Transactional);
         assert_eq!(parsed.language, Some("zh".to_string()));
     }
+
+    #[test]
+    fn test_parse_traditional_chinese() {
+        let parser = QueryParser::new();
+        let parsed = parser.parse("北京天氣");
+        assert_eq!(parsed.normalized, "北京天气");
+        assert_eq!(parsed.pinyin.as_deref(), Some("bei jing tian qi"));
+
+        // 日文查询保留原字形
+        assert_eq!(parser.parse("東京の天気").normalized, "東京の天気");
+
+        let parser = QueryParser::new().with_chinese_matching(ChineseMatching {
+            normalize_script: false,
+            pinyin: false,
+        });
+        let parsed = parser.parse("北京天氣");
+        assert_eq!(parsed.normalized, "北京天氣");
+        assert!(parsed.pinyin.is_none());
+    }
+
+    #[test]
+    fn test_keyword_matcher_from_query() {
+        let parser = QueryParser::new();
+        let matcher = parser.keyword_matcher("  Beijing 天氣 ");
+        assert!(matcher.matches("北京的新闻"));
+        assert_eq!(matcher.count_matches("北京天气"), 2);
+    }
+
+    #[test]
+    fn test_scrub_disabled_by_default() {
+        let parser = QueryParser::new();
+        let result = parser.scrub("contact john@example.com");
+        assert!(!result.is_modified());
+        assert_eq!(result.query, "contact john@example.com");
+    }
+
+    #[test]
+    fn test_scrub_email() {
+        let parser = QueryParser::new().with_pii_scrubbing(true);
+        let result = parser.scrub("why is john.doe+test@mail.example.com bouncing");
+        assert_eq!(result.query, "why is bouncing");
+        assert_eq!(result.removed, vec![PiiKind::Email]);
+        assert!(result.warning().unwrap().contains("邮箱地址"));
+    }
+
+    #[test]
+    fn test_scrub_phone() {
+        let parser = QueryParser::new().with_pii_scrubbing(true);
+        assert_eq!(parser.scrub("who called 555-123-4567").query, "who called");
+        assert_eq!(parser.scrub("号码 13812345678 归属地").query, "号码 归属地");
+        assert_eq!(parser.scrub("call +44 20 7946 0958 now").query, "call now");
+    }
+
+    #[test]
+    fn test_scrub_keeps_plain_numbers() {
+        let parser = QueryParser::new().with_pii_scrubbing(true);
+        let result = parser.scrub("rust edition 2015 2018 2021");
+        assert!(!result.is_modified());
+        assert_eq!(result.query, "rust edition 2015 2018 2021");
+    }
+
+    #[test]
+    fn test_scrub_custom_patterns() {
+        let parser = QueryParser::new()
+            .with_pii_scrubbing(true)
+            .with_custom_patterns([r"ACCT-\d+"])
+            .unwrap();
+        let result = parser.scrub("status of ACCT-99812 and a@b.io");
+        assert_eq!(result.query, "status of and");
+        assert_eq!(result.removed, vec![PiiKind::Email, PiiKind::Custom]);
+    }
+
+    #[test]
+    fn test_invalid_custom_pattern() {
+        let parser = QueryParser::new().with_custom_patterns(["("]);
+        assert!(parser.is_err());
+    }
+
+    #[test]
+    fn test_parse_records_warning() {
+        let parser = QueryParser::new().with_pii_scrubbing(true);
+        let parsed = parser.parse("reset password me@example.org");
+        assert_eq!(parsed.normalized, "reset password");
+        assert_eq!(parsed.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_spell_correction() {
+        let parser = QueryParser::new();
+        assert!(parser.suggest_correction("rsut").is_none());
+
+        let source: DictionarySource = Arc::new(|| vec!["The Rust Programming Language".to_string()]);
+        let parser = QueryParser::new().with_spell_correction(Some(source));
+        assert_eq!(parser.suggest_correction("rsut programing").as_deref(), Some("rust programming"));
+        assert!(parser.suggest_correction("rust").is_none());
+
+        parser.learn(["Tokio asynchronous runtime"]);
+        assert_eq!(parser.suggest_correction("tokoi").as_deref(), Some("tokio"));
+    }
 }
@@ -0,0 +1,72 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 请求 ID
+//!
+//! API、CLI 与 Python 入口为每次搜索生成请求 ID，并在其 tracing span 中执行搜索，
+//! 引擎请求与缓存操作的日志因此都带有 `request_id` 字段，响应中也返回同一 ID
+
+/// 请求与响应中携带请求 ID 的 HTTP 头
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 调用方提供的请求 ID 的最大长度
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+/// 生成新的请求 ID（16 位十六进制）
+pub fn generate_request_id() -> String {
+    format!("{:016x}", fastrand::u64(..))
+}
+
+/// 采用调用方提供的请求 ID
+///
+/// 只接受不超过 64 个字符、由字母数字与 `-`、`_`、`.` 组成的 ID，避免日志注入
+///
+/// # Arguments
+///
+/// * `value` - 请求头中的请求 ID
+///
+/// # Returns
+///
+/// 合法时返回该 ID，否则返回 None
+pub fn accept_request_id(value: &str) -> Option<String> {
+    let value = value.trim();
+    let valid = !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    valid.then(|| value.to_string())
+}
+
+/// 创建携带请求 ID 的 tracing span，搜索在其中执行
+pub fn request_span(request_id: &str) -> tracing::Span {
+    tracing::info_span!("request", request_id = %request_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_ids() {
+        let id = generate_request_id();
+        assert_eq!(id.len(), 16);
+        assert_eq!(accept_request_id(&id), Some(id));
+
+        assert_eq!(accept_request_id(" trace-42 ").as_deref(), Some("trace-42"));
+        assert_eq!(accept_request_id(""), None);
+        assert_eq!(accept_request_id("bad\nid"), None);
+        assert_eq!(accept_request_id(&"a".repeat(65)), None);
+    }
+}
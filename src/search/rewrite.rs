@@ -0,0 +1,337 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 查询改写模块
+//!
+//! 在查询发送到引擎之前按注册顺序依次改写查询文本（清洗敏感信息、检测语言之后）。
+//! 下游可以实现 [`QueryRewriter`] 注入领域相关的改写规则，同步改写逻辑可以直接用
+//! [`FnRewriter`] 包装闭包。内置改写器：
+//!
+//! - [`SynonymRewriter`]：把常见缩写扩展为 `(缩写 OR 全称)`
+//! - [`StopWordRewriter`]：去掉较长英文查询中的停用词
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::derive::SearchQuery;
+
+/// 内置同义词表（只收录含义明确的缩写）
+const DEFAULT_SYNONYMS: &[(&str, &str)] = &[
+    ("js", "javascript"),
+    ("ts", "typescript"),
+    ("py", "python"),
+    ("k8s", "kubernetes"),
+    ("db", "database"),
+    ("regex", "regular expression"),
+    ("ml", "machine learning"),
+    ("nlp", "natural language processing"),
+];
+
+/// 内置英文停用词
+pub(crate) const DEFAULT_STOP_WORDS: &[&str] = &[
+    "a", "an", "the", "of", "in", "on", "at", "to", "for", "with", "by", "from", "and", "is",
+    "are", "was", "were", "be", "it", "this", "that", "what", "which", "how", "do", "does", "i",
+    "my", "me", "can", "about", "into",
+];
+
+/// 停用词过滤至少要求的词数（更短的查询保持不变）
+const STOP_WORD_MIN_TERMS: usize = 4;
+
+/// 停用词过滤后至少保留的词数
+const STOP_WORD_MIN_REMAINING: usize = 2;
+
+/// 查询改写器
+///
+/// 改写器按注册顺序执行，每个改写器看到的是前一个改写器的输出
+#[async_trait]
+pub trait QueryRewriter: Send + Sync {
+    /// 改写器名称
+    fn name(&self) -> &str;
+
+    /// 改写查询
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 即将发送到引擎的查询（语言等字段已补全）
+    ///
+    /// # Returns
+    ///
+    /// 返回改写后的查询文本，不需要改写时返回 None
+    async fn rewrite(&self, query: &SearchQuery) -> Option<String>;
+}
+
+/// 闭包改写器，用于包装同步改写逻辑
+pub struct FnRewriter<F> {
+    name: String,
+    rewrite: F,
+}
+
+impl<F> FnRewriter<F>
+where
+    F: Fn(&SearchQuery) -> Option<String> + Send + Sync,
+{
+    /// 创建闭包改写器
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 改写器名称
+    /// * `rewrite` - 改写函数，返回 None 表示不改写
+    pub fn new(name: impl Into<String>, rewrite: F) -> Self {
+        Self { name: name.into(), rewrite }
+    }
+}
+
+#[async_trait]
+impl<F> QueryRewriter for FnRewriter<F>
+where
+    F: Fn(&SearchQuery) -> Option<String> + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn rewrite(&self, query: &SearchQuery) -> Option<String> {
+        (self.rewrite)(query)
+    }
+}
+
+/// 查询改写器列表
+#[derive(Default, Clone)]
+pub struct QueryRewriters {
+    rewriters: Vec<Arc<dyn QueryRewriter>>,
+}
+
+impl QueryRewriters {
+    /// 创建空列表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册改写器（追加到末尾）
+    pub fn register(&mut self, rewriter: Arc<dyn QueryRewriter>) {
+        self.rewriters.push(rewriter);
+    }
+
+    /// 已注册的改写器名称
+    pub fn names(&self) -> Vec<&str> {
+        self.rewriters.iter().map(|r| r.name()).collect()
+    }
+
+    /// 是否没有注册任何改写器
+    pub fn is_empty(&self) -> bool {
+        self.rewriters.is_empty()
+    }
+
+    /// 依次执行所有改写器
+    ///
+    /// 改写结果为空白时忽略，保留原查询
+    ///
+    /// # Returns
+    ///
+    /// 返回实际修改了查询的改写器名称
+    pub async fn apply(&self, query: &mut SearchQuery) -> Vec<String> {
+        let mut applied = Vec::new();
+        for rewriter in &self.rewriters {
+            let Some(rewritten) = rewriter.rewrite(query).await else {
+                continue;
+            };
+            let rewritten = rewritten.trim();
+            if rewritten.is_empty() || rewritten == query.query {
+                continue;
+            }
+            query.query = rewritten.to_string();
+            applied.push(rewriter.name().to_string());
+        }
+        applied
+    }
+}
+
+/// 查询是否包含短语或高级语法（这类查询不做自动改写）
+fn has_operators(query: &str) -> bool {
+    query.contains('"')
+        || query.split_whitespace().any(|term| {
+            term.contains(':') || term.starts_with('-') || term.starts_with('(') || term == "OR" || term == "AND"
+        })
+}
+
+/// 同义词扩展改写器
+///
+/// 把命中同义词表的词替换为 `(词 OR 同义词...)`，包含短语或高级语法的查询保持不变
+pub struct SynonymRewriter {
+    synonyms: HashMap<String, Vec<String>>,
+}
+
+impl SynonymRewriter {
+    /// 使用自定义同义词表创建改写器（键不区分大小写）
+    pub fn new(synonyms: HashMap<String, Vec<String>>) -> Self {
+        let synonyms = synonyms
+            .into_iter()
+            .map(|(term, alternatives)| (term.to_lowercase(), alternatives))
+            .filter(|(_, alternatives)| !alternatives.is_empty())
+            .collect();
+        Self { synonyms }
+    }
+}
+
+impl Default for SynonymRewriter {
+    fn default() -> Self {
+        let mut synonyms: HashMap<String, Vec<String>> = HashMap::new();
+        for (term, synonym) in DEFAULT_SYNONYMS {
+            synonyms.entry((*term).to_string()).or_default().push((*synonym).to_string());
+        }
+        Self { synonyms }
+    }
+}
+
+#[async_trait]
+impl QueryRewriter for SynonymRewriter {
+    fn name(&self) -> &str {
+        "synonyms"
+    }
+
+    async fn rewrite(&self, query: &SearchQuery) -> Option<String> {
+        if has_operators(&query.query) {
+            return None;
+        }
+
+        let mut expanded = false;
+        let terms: Vec<String> = query
+            .query
+            .split_whitespace()
+            .map(|term| match self.synonyms.get(&term.to_lowercase()) {
+                Some(alternatives) => {
+                    expanded = true;
+                    let alternatives: Vec<String> = alternatives
+                        .iter()
+                        .map(|alt| if alt.contains(' ') { format!("\"{}\"", alt) } else { alt.clone() })
+                        .collect();
+                    format!("({} OR {})", term, alternatives.join(" OR "))
+                }
+                None => term.to_string(),
+            })
+            .collect();
+
+        expanded.then(|| terms.join(" "))
+    }
+}
+
+/// 停用词过滤改写器
+///
+/// 只处理至少 4 个词的查询，过滤后至少保留 2 个词；短语查询与高级语法保持不变
+pub struct StopWordRewriter {
+    stop_words: HashSet<String>,
+}
+
+impl StopWordRewriter {
+    /// 使用自定义停用词创建改写器（不区分大小写）
+    pub fn new<I, S>(stop_words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            stop_words: stop_words.into_iter().map(|w| w.as_ref().to_lowercase()).collect(),
+        }
+    }
+}
+
+impl Default for StopWordRewriter {
+    fn default() -> Self {
+        Self::new(DEFAULT_STOP_WORDS)
+    }
+}
+
+#[async_trait]
+impl QueryRewriter for StopWordRewriter {
+    fn name(&self) -> &str {
+        "stop_words"
+    }
+
+    async fn rewrite(&self, query: &SearchQuery) -> Option<String> {
+        if has_operators(&query.query) {
+            return None;
+        }
+        let terms: Vec<&str> = query.query.split_whitespace().collect();
+        if terms.len() < STOP_WORD_MIN_TERMS {
+            return None;
+        }
+
+        let kept: Vec<&str> = terms
+            .iter()
+            .copied()
+            .filter(|term| !self.stop_words.contains(&term.to_lowercase()))
+            .collect();
+        if kept.len() == terms.len() || kept.len() < STOP_WORD_MIN_REMAINING {
+            return None;
+        }
+        Some(kept.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery {
+            query: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_synonym_rewriter() {
+        let rewriter = SynonymRewriter::default();
+        assert_eq!(
+            rewriter.rewrite(&query("js closures")).await.as_deref(),
+            Some("(js OR javascript) closures")
+        );
+        assert_eq!(
+            rewriter.rewrite(&query("ml basics")).await.as_deref(),
+            Some("(ml OR \"machine learning\") basics")
+        );
+        assert_eq!(rewriter.rewrite(&query("rust closures")).await, None);
+        assert_eq!(rewriter.rewrite(&query("\"js closures\"")).await, None);
+        assert_eq!(rewriter.rewrite(&query("js site:example.com")).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_stop_word_rewriter() {
+        let rewriter = StopWordRewriter::default();
+        assert_eq!(
+            rewriter.rewrite(&query("how to install rust on windows")).await.as_deref(),
+            Some("install rust windows")
+        );
+        // 短查询与过滤后过短的查询保持不变
+        assert_eq!(rewriter.rewrite(&query("the rust book")).await, None);
+        assert_eq!(rewriter.rewrite(&query("what is the rust")).await, None);
+        assert_eq!(rewriter.rewrite(&query("\"the state of the art\" ai")).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_rewriters_chain() {
+        let mut rewriters = QueryRewriters::new();
+        rewriters.register(Arc::new(StopWordRewriter::default()));
+        rewriters.register(Arc::new(SynonymRewriter::default()));
+        rewriters.register(Arc::new(FnRewriter::new("noop", |_: &SearchQuery| Some("   ".to_string()))));
+        assert_eq!(rewriters.names(), vec!["stop_words", "synonyms", "noop"]);
+
+        let mut q = query("how to debug js in the browser");
+        let applied = rewriters.apply(&mut q).await;
+        assert_eq!(q.query, "debug (js OR javascript) browser");
+        assert_eq!(applied, vec!["stop_words", "synonyms"]);
+    }
+}
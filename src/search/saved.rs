@@ -0,0 +1,146 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 保存的搜索执行
+//!
+//! 按各自的间隔重新执行保存的搜索（绕过结果缓存），与上次见过的结果比较，
+//! 新结果保存为告警或以 Webhook 投递
+
+use std::sync::Arc;
+
+use crate::cache::alerts::now_secs;
+use crate::cache::{Alert, AlertResult, AlertStore, SavedSearch};
+use crate::derive::SearchQuery;
+use crate::webhook::WebhookEvent;
+
+use super::on::SearchInterface;
+use super::types::{SearchRequest, SearchResponse};
+
+/// 保存的搜索执行器
+pub struct SavedSearchRunner {
+    /// 搜索接口
+    search: Arc<SearchInterface>,
+    /// 保存的搜索与告警存储
+    store: AlertStore,
+}
+
+impl SavedSearchRunner {
+    /// 创建执行器
+    ///
+    /// # 参数
+    ///
+    /// * `search` - 搜索接口（其 Webhook 分发器用于投递新结果）
+    /// * `store` - 保存的搜索与告警存储
+    pub fn new(search: Arc<SearchInterface>, store: AlertStore) -> Self {
+        Self { search, store }
+    }
+
+    /// 保存的搜索与告警存储
+    pub fn store(&self) -> &AlertStore {
+        &self.store
+    }
+
+    /// 执行所有到期的保存的搜索
+    ///
+    /// 单个搜索失败只记录日志，不影响其余搜索
+    ///
+    /// # 返回值
+    ///
+    /// 本轮产生的告警数
+    pub async fn run_due(&self) -> usize {
+        let searches = match self.store.list_searches_async().await {
+            Ok(searches) => searches,
+            Err(e) => {
+                tracing::warn!("读取保存的搜索失败: {}", e);
+                return 0;
+            }
+        };
+
+        let now = now_secs();
+        let mut alerts = 0;
+        for saved in searches.into_iter().filter(|saved| saved.is_due(now)) {
+            let id = saved.id.clone();
+            match self.run(saved).await {
+                Ok(alert) => alerts += alert.is_some() as usize,
+                Err(e) => tracing::warn!("执行保存的搜索 {} 失败: {}", id, e),
+            }
+        }
+        alerts
+    }
+
+    /// 执行一个保存的搜索
+    ///
+    /// # 返回值
+    ///
+    /// 出现新结果时返回告警（首次执行只建立基线，不产生告警）
+    pub async fn run(
+        &self,
+        mut saved: SavedSearch,
+    ) -> Result<Option<Alert>, Box<dyn std::error::Error + Send + Sync>> {
+        let request = SearchRequest {
+            query: SearchQuery {
+                query: saved.query.clone(),
+                ..Default::default()
+            },
+            engines: saved.engines.clone(),
+            force: true,
+            ..Default::default()
+        };
+        let response = self.search.search(&request).await?;
+
+        let fresh = saved.record_run(collect_results(&response), now_secs());
+        self.store
+            .save_search_async(saved.clone())
+            .await
+            .map_err(|e| format!("保存执行记录失败: {}", e))?;
+        if fresh.is_empty() {
+            return Ok(None);
+        }
+
+        let alert = Alert::new(&saved, fresh);
+        if saved.delivery.stores() {
+            self.store
+                .push_alert_async(alert.clone())
+                .await
+                .map_err(|e| format!("保存告警失败: {}", e))?;
+        }
+        if saved.delivery.notifies() {
+            match self.search.webhooks() {
+                Some(webhooks) => {
+                    webhooks.dispatch(WebhookEvent::SavedSearchMatched {
+                        saved_search_id: saved.id.clone(),
+                        query: saved.query.clone(),
+                        results: alert.results.clone(),
+                    });
+                }
+                None => tracing::warn!("保存的搜索 {} 要求 Webhook 投递，但未启用 Webhook", saved.id),
+            }
+        }
+        Ok(Some(alert))
+    }
+}
+
+/// 提取响应中的结果（按链接去重）
+fn collect_results(response: &SearchResponse) -> Vec<AlertResult> {
+    response
+        .results
+        .iter()
+        .flat_map(|result| &result.items)
+        .map(|item| AlertResult {
+            title: item.title.clone(),
+            url: item.url.clone(),
+            content: item.content.clone(),
+        })
+        .collect()
+}
@@ -16,11 +16,22 @@
 //!
 //! 基于 BM25 算法和其他启发式规则进行评分
 
-use crate::derive::{SearchResultItem, SearchQuery};
+use crate::derive::{EngineType, ResultType, SearchResultItem, SearchQuery};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// 学术结果标题与查询完全一致时的最低评分
+///
+/// 论文检索中用户常直接输入论文标题，完全一致的结果应排在最前
+pub const EXACT_TITLE_MIN_SCORE: f64 = 0.95;
+
+/// 时效性评分的半衰期（天）：发布满该天数的结果时效性评分为 0.5
+pub const RECENCY_HALF_LIFE_DAYS: f64 = 7.0;
 
 /// BM25 参数
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BM25Params {
     /// k1 参数：控制词频饱和度 (通常 1.2-2.0)
     pub k1: f64,
@@ -38,7 +49,8 @@ impl Default for BM25Params {
 }
 
 /// 评分权重配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ScoringWeights {
     /// 标题 BM25 权重
     pub title_bm25: f64,
@@ -50,6 +62,10 @@ pub struct ScoringWeights {
     pub engine_authority: f64,
     /// 位置权重
     pub position_weight: f64,
+    /// 时效性权重（按发布时间衰减，无发布时间的结果不得分）
+    pub recency: f64,
+    /// 精确匹配权重（完整查询出现在标题或内容中时额外加分）
+    pub exact_match: f64,
 }
 
 impl Default for ScoringWeights {
@@ -60,7 +76,160 @@ impl Default for ScoringWeights {
             url_match: 0.10,        // URL 匹配
             engine_authority: 0.15, // 引擎权威度
             position_weight: 0.05,  // 原始排名位置
+            recency: 0.0,
+            exact_match: 0.0,
+        }
+    }
+}
+
+impl ScoringWeights {
+    /// 检查权重是否有效（均为非负的有限数）
+    fn validate(&self) -> Result<(), String> {
+        let weights = [
+            ("title_bm25", self.title_bm25),
+            ("content_bm25", self.content_bm25),
+            ("url_match", self.url_match),
+            ("engine_authority", self.engine_authority),
+            ("position_weight", self.position_weight),
+            ("recency", self.recency),
+            ("exact_match", self.exact_match),
+        ];
+        match weights.iter().find(|(_, weight)| !weight.is_finite() || *weight < 0.0) {
+            Some((name, weight)) => Err(format!("评分权重 {} 必须为非负数，当前为 {}", name, weight)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl BM25Params {
+    /// 检查参数是否有效（k1 > 0，0 <= b <= 1）
+    fn validate(&self) -> Result<(), String> {
+        if !self.k1.is_finite() || self.k1 <= 0.0 {
+            return Err(format!("BM25 参数 k1 必须大于 0，当前为 {}", self.k1));
+        }
+        if !(0.0..=1.0).contains(&self.b) {
+            return Err(format!("BM25 参数 b 必须在 0.0-1.0 之间，当前为 {}", self.b));
         }
+        Ok(())
+    }
+}
+
+/// 单个搜索类型的评分参数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CategoryScoring {
+    /// BM25 参数
+    pub bm25: BM25Params,
+    /// 评分权重
+    pub weights: ScoringWeights,
+}
+
+/// 评分配置档
+///
+/// 默认的 BM25 参数与评分权重，可按搜索类型（`news`、`code` 等）整体覆盖。
+/// 内置覆盖：新闻偏重时效性，代码偏重精确匹配；配置 `categories` 时替换全部内置覆盖
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringProfile {
+    /// 默认 BM25 参数
+    pub bm25: BM25Params,
+    /// 默认评分权重
+    pub weights: ScoringWeights,
+    /// 按搜索类型覆盖的评分参数
+    pub categories: HashMap<EngineType, CategoryScoring>,
+}
+
+impl Default for ScoringProfile {
+    fn default() -> Self {
+        let news = CategoryScoring {
+            bm25: BM25Params::default(),
+            weights: ScoringWeights {
+                title_bm25: 0.30,
+                content_bm25: 0.20,
+                url_match: 0.05,
+                engine_authority: 0.10,
+                position_weight: 0.05,
+                recency: 0.30,
+                exact_match: 0.0,
+            },
+        };
+        // 代码搜索中标识符需要原样出现：降低长度归一化，提高精确匹配权重
+        let code = CategoryScoring {
+            bm25: BM25Params { k1: 1.2, b: 0.5 },
+            weights: ScoringWeights {
+                title_bm25: 0.30,
+                content_bm25: 0.20,
+                url_match: 0.10,
+                engine_authority: 0.10,
+                position_weight: 0.05,
+                recency: 0.0,
+                exact_match: 0.25,
+            },
+        };
+        Self {
+            bm25: BM25Params::default(),
+            weights: ScoringWeights::default(),
+            categories: HashMap::from([(EngineType::News, news), (EngineType::Code, code)]),
+        }
+    }
+}
+
+impl ScoringProfile {
+    /// 检查所有 BM25 参数与评分权重
+    pub fn validate(&self) -> Result<(), String> {
+        self.bm25.validate()?;
+        self.weights.validate()?;
+        for (category, scoring) in &self.categories {
+            scoring
+                .bm25
+                .validate()
+                .and_then(|_| scoring.weights.validate())
+                .map_err(|e| format!("{:?}: {}", category, e))?;
+        }
+        Ok(())
+    }
+
+    /// 指定搜索类型使用的评分参数（没有覆盖时使用默认值）
+    pub fn for_category(&self, category: EngineType) -> (&BM25Params, &ScoringWeights) {
+        match self.categories.get(&category) {
+            Some(scoring) => (&scoring.bm25, &scoring.weights),
+            None => (&self.bm25, &self.weights),
+        }
+    }
+}
+
+/// 可在运行时替换的评分配置档
+///
+/// 由搜索接口与聚合器共享，替换后之后的评分立即使用新参数
+#[derive(Debug, Default)]
+pub struct SharedScoringProfile {
+    /// 当前配置档
+    profile: RwLock<Arc<ScoringProfile>>,
+}
+
+impl SharedScoringProfile {
+    /// 创建共享配置档
+    ///
+    /// 参数无效时返回错误
+    pub fn new(profile: ScoringProfile) -> Result<Self, String> {
+        profile.validate()?;
+        Ok(Self {
+            profile: RwLock::new(Arc::new(profile)),
+        })
+    }
+
+    /// 当前配置档
+    pub fn get(&self) -> Arc<ScoringProfile> {
+        Arc::clone(&self.profile.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// 替换配置档
+    ///
+    /// 参数无效时返回错误并保留原有配置档
+    pub fn set(&self, profile: ScoringProfile) -> Result<(), String> {
+        profile.validate()?;
+        *self.profile.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(profile);
+        Ok(())
     }
 }
 
@@ -91,6 +260,9 @@ pub fn get_engine_authority(engine_name: &str) -> f64 {
         "github" => 0.92,
         "stackoverflow" => 0.93,
         "unsplash" => 0.85,
+        "arxiv" => 0.90,
+        "semantic scholar" | "semantic_scholar" => 0.90,
+        "crossref" => 0.88,
         
         _ => 0.70,
     }
@@ -105,6 +277,99 @@ pub(crate) fn tokenize(text: &str) -> Vec<String> {
         .collect()
 }
 
+/// 是否为中日韩文字（汉字、假名、谚文）
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30ff}'   // 平假名、片假名
+        | '\u{3400}'..='\u{4dbf}' // 汉字扩展 A
+        | '\u{4e00}'..='\u{9fff}' // 汉字
+        | '\u{ac00}'..='\u{d7af}' // 谚文音节
+        | '\u{f900}'..='\u{faff}' // 兼容汉字
+        | '\u{ff66}'..='\u{ff9f}' // 半角片假名
+    )
+}
+
+/// 分词方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tokenizer {
+    /// 按非字母数字字符切分（适用于以空格分词的语言）
+    Simple,
+    /// 中日韩文字按相邻二元组切分（单字保留为一元组），其余文字同 [`Tokenizer::Simple`]
+    ///
+    /// 中文、日文没有空格分词，整段文字会被 `Simple` 当作一个词，导致 BM25 无法匹配
+    Cjk,
+}
+
+impl Tokenizer {
+    /// 按语言选择分词方式
+    ///
+    /// 语言为中文、日文或韩文，或文本中含有中日韩文字时使用 [`Tokenizer::Cjk`]
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - 请求或检测到的语言代码（如 `zh`、`zh-CN`、`en`）
+    /// * `text` - 查询文本
+    pub fn for_language(language: Option<&str>, text: &str) -> Self {
+        let cjk_language = language
+            .and_then(|lang| lang.split(['-', '_']).next())
+            .is_some_and(|lang| ["zh", "ja", "ko"].iter().any(|cjk| lang.eq_ignore_ascii_case(cjk)));
+        if cjk_language || text.chars().any(is_cjk) {
+            Self::Cjk
+        } else {
+            Self::Simple
+        }
+    }
+
+    /// 按查询的语言与文本选择分词方式
+    pub fn for_query(query: &SearchQuery) -> Self {
+        Self::for_language(query.language.as_deref(), &query.query)
+    }
+
+    /// 分词（统一转为小写）
+    pub fn tokenize(self, text: &str) -> Vec<String> {
+        match self {
+            Self::Simple => tokenize(text),
+            Self::Cjk => tokenize_cjk(text),
+        }
+    }
+}
+
+/// 中日韩文字按二元组切分的分词
+fn tokenize_cjk(text: &str) -> Vec<String> {
+    fn flush_word(word: &mut String, tokens: &mut Vec<String>) {
+        if !word.is_empty() {
+            tokens.push(std::mem::take(word));
+        }
+    }
+    fn flush_run(run: &mut Vec<char>, tokens: &mut Vec<String>) {
+        match run.len() {
+            0 => {}
+            1 => tokens.push(run[0].to_string()),
+            _ => tokens.extend(run.windows(2).map(|pair| pair.iter().collect::<String>())),
+        }
+        run.clear();
+    }
+
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut run = Vec::new();
+    for c in text.to_lowercase().chars() {
+        if is_cjk(c) {
+            flush_word(&mut word, &mut tokens);
+            run.push(c);
+        } else if c.is_alphanumeric() || c == '_' {
+            flush_run(&mut run, &mut tokens);
+            word.push(c);
+        } else {
+            flush_word(&mut word, &mut tokens);
+            flush_run(&mut run, &mut tokens);
+        }
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_run(&mut run, &mut tokens);
+    tokens
+}
+
 /// 计算词频 (Term Frequency)
 fn term_frequency(tokens: &[String]) -> HashMap<String, usize> {
     let mut tf = HashMap::new();
@@ -128,14 +393,17 @@ fn term_frequency(tokens: &[String]) -> HashMap<String, usize> {
 /// - |D|: 文档长度
 /// - avgdl: 平均文档长度
 /// - k1, b: 调节参数
-pub(crate) fn bm25_score(
+///
+/// 文档与查询使用同一 `tokenizer` 分词
+pub(crate) fn bm25_score_with(
+    tokenizer: Tokenizer,
     document: &str,
     query: &str,
     avg_doc_length: f64,
     params: &BM25Params,
 ) -> f64 {
-    let doc_tokens = tokenize(document);
-    let query_tokens = tokenize(query);
+    let doc_tokens = tokenizer.tokenize(document);
+    let query_tokens = tokenizer.tokenize(query);
     
     if doc_tokens.is_empty() || query_tokens.is_empty() {
         return 0.0;
@@ -170,6 +438,17 @@ pub(crate) fn bm25_score(
     }
 }
 
+/// BM25 评分（分词方式按查询文本自动选择，见 [`Tokenizer::for_language`]）
+#[cfg(test)]
+pub(crate) fn bm25_score(
+    document: &str,
+    query: &str,
+    avg_doc_length: f64,
+    params: &BM25Params,
+) -> f64 {
+    bm25_score_with(Tokenizer::for_language(None, query), document, query, avg_doc_length, params)
+}
+
 /// 精确匹配加分
 pub(crate) fn exact_match_bonus(text: &str, query: &str) -> f64 {
     let text_lower = text.to_lowercase();
@@ -189,6 +468,12 @@ pub(crate) fn exact_match_bonus(text: &str, query: &str) -> f64 {
     0.0
 }
 
+/// 标题是否与查询完全一致（忽略大小写与标点）
+pub(crate) fn is_exact_title_match(title: &str, query: &str) -> bool {
+    let query_tokens = tokenize(query);
+    !query_tokens.is_empty() && tokenize(title) == query_tokens
+}
+
 /// URL 相关性评分
 pub(crate) fn url_relevance(url: &str, query: &str) -> f64 {
     let url_lower = url.to_lowercase();
@@ -208,6 +493,17 @@ pub(crate) fn url_relevance(url: &str, query: &str) -> f64 {
     }
 }
 
+/// 时效性评分
+///
+/// 按发布时间以 [`RECENCY_HALF_LIFE_DAYS`] 为半衰期衰减到 0，无发布时间时为 0
+pub(crate) fn recency_score(published: Option<chrono::DateTime<chrono::Utc>>) -> f64 {
+    let Some(published) = published else {
+        return 0.0;
+    };
+    let age_days = (chrono::Utc::now() - published).num_seconds().max(0) as f64 / 86400.0;
+    1.0 / (1.0 + age_days / RECENCY_HALF_LIFE_DAYS)
+}
+
 /// 位置评分（原始搜索引擎排名）
 pub(crate) fn position_score(position: usize) -> f64 {
     // 对数衰减：前几个结果分数明显更高
@@ -226,24 +522,90 @@ pub(crate) fn calculate_score(
     weights: &ScoringWeights,
     bm25_params: &BM25Params,
 ) -> f64 {
+    calculate_score_with_authority(
+        item,
+        query,
+        get_engine_authority(engine_name),
+        position,
+        (avg_title_length, avg_content_length),
+        weights,
+        bm25_params,
+    )
+}
+
+/// 单个结果的评分明细
+///
+/// 各分项为加权前的 0-1 分值，`score` 为按权重求和后的综合评分
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    /// 标题 BM25 评分
+    pub title_bm25: f64,
+    /// 标题精确匹配加分
+    pub title_exact: f64,
+    /// 内容 BM25 评分
+    pub content_bm25: f64,
+    /// 内容精确匹配加分
+    pub content_exact: f64,
+    /// URL 相关性
+    pub url_match: f64,
+    /// 引擎权威度（自适应权重启用时为引擎质量评分）
+    pub engine_authority: f64,
+    /// 原始排名位置评分
+    pub position: f64,
+    /// 时效性评分
+    pub recency: f64,
+    /// 学术结果标题完全匹配而提升到 [`EXACT_TITLE_MIN_SCORE`]
+    pub exact_title_boost: bool,
+    /// 综合评分
+    pub score: f64,
+}
+
+/// 使用给定的引擎权威度计算综合评分
+///
+/// `avg_lengths` 为（平均标题长度，平均内容长度）
+pub(crate) fn calculate_score_with_authority(
+    item: &SearchResultItem,
+    query: &SearchQuery,
+    authority_score: f64,
+    position: usize,
+    avg_lengths: (f64, f64),
+    weights: &ScoringWeights,
+    bm25_params: &BM25Params,
+) -> f64 {
+    score_breakdown(item, query, authority_score, position, avg_lengths, weights, bm25_params).score
+}
+
+/// 计算综合评分及其各分项
+pub(crate) fn score_breakdown(
+    item: &SearchResultItem,
+    query: &SearchQuery,
+    authority_score: f64,
+    position: usize,
+    avg_lengths: (f64, f64),
+    weights: &ScoringWeights,
+    bm25_params: &BM25Params,
+) -> ScoreBreakdown {
+    let (avg_title_length, avg_content_length) = avg_lengths;
+    let tokenizer = Tokenizer::for_query(query);
     // 1. 标题 BM25 评分
-    let title_bm25 = bm25_score(&item.title, &query.query, avg_title_length, bm25_params);
+    let title_bm25 = bm25_score_with(tokenizer, &item.title, &query.query, avg_title_length, bm25_params);
     let title_exact = exact_match_bonus(&item.title, &query.query);
     let title_score = (title_bm25 * 0.7 + title_exact * 0.3).min(1.0);
     
     // 2. 内容 BM25 评分
-    let content_bm25 = bm25_score(&item.content, &query.query, avg_content_length, bm25_params);
+    let content_bm25 = bm25_score_with(tokenizer, &item.content, &query.query, avg_content_length, bm25_params);
     let content_exact = exact_match_bonus(&item.content, &query.query);
     let content_score = (content_bm25 * 0.8 + content_exact * 0.2).min(1.0);
     
     // 3. URL 相关性
     let url_score = url_relevance(&item.url, &query.query);
     
-    // 4. 引擎权威度
-    let authority_score = get_engine_authority(engine_name);
-    
-    // 5. 位置评分
+    // 4. 位置评分
     let pos_score = position_score(position);
+
+    // 5. 时效性与精确匹配（默认权重为 0，由评分配置档按搜索类型启用）
+    let recency = recency_score(item.published_date);
+    let exact = title_exact.max(content_exact);
     
     // 加权求和
     let final_score = 
@@ -251,10 +613,29 @@ pub(crate) fn calculate_score(
         content_score * weights.content_bm25 +
         url_score * weights.url_match +
         authority_score * weights.engine_authority +
-        pos_score * weights.position_weight;
+        pos_score * weights.position_weight +
+        recency * weights.recency +
+        exact * weights.exact_match;
     
-    // 确保在 [0, 1] 范围内
-    final_score.max(0.0).min(1.0)
+    // 学术结果的标题完全匹配时置顶
+    let exact_title_boost = item.result_type == ResultType::Academic
+        && is_exact_title_match(&item.title, &query.query)
+        && final_score < EXACT_TITLE_MIN_SCORE;
+    let final_score = if exact_title_boost { EXACT_TITLE_MIN_SCORE } else { final_score };
+
+    ScoreBreakdown {
+        title_bm25,
+        title_exact,
+        content_bm25,
+        content_exact,
+        url_match: url_score,
+        engine_authority: authority_score,
+        position: pos_score,
+        recency,
+        exact_title_boost,
+        // 确保在 [0, 1] 范围内
+        score: final_score.clamp(0.0, 1.0),
+    }
 }
 
 /// 批量评分
@@ -273,12 +654,13 @@ pub fn score_results(
     let bm25_params = bm25_params.unwrap_or_default();
     
     // 计算平均文档长度
+    let tokenizer = Tokenizer::for_query(query);
     let avg_title_length = items.iter()
-        .map(|i| tokenize(&i.title).len())
+        .map(|i| tokenizer.tokenize(&i.title).len())
         .sum::<usize>() as f64 / items.len() as f64;
     
     let avg_content_length = items.iter()
-        .map(|i| tokenize(&i.content).len())
+        .map(|i| tokenizer.tokenize(&i.content).len())
         .sum::<usize>() as f64 / items.len() as f64;
     
     // 计算每个结果的评分
@@ -296,6 +678,108 @@ pub fn score_results(
     }
 }
 
+/// 按结果逐项决定引擎权威度的批量评分并排序
+///
+/// `authority` 为每个结果返回其来源引擎的权威度，
+/// 用于以历史质量替代静态权威度表
+pub fn score_and_sort_results_with_authority<F>(
+    items: &mut [SearchResultItem],
+    query: &SearchQuery,
+    authority: F,
+    weights: Option<ScoringWeights>,
+) where
+    F: Fn(&SearchResultItem) -> f64,
+{
+    score_and_sort_results_with_params(
+        items,
+        query,
+        authority,
+        &weights.unwrap_or_default(),
+        &BM25Params::default(),
+    );
+}
+
+/// 使用给定的评分权重与 BM25 参数批量评分并排序
+///
+/// `authority` 为每个结果返回其来源引擎的权威度
+pub fn score_and_sort_results_with_params<F>(
+    items: &mut [SearchResultItem],
+    query: &SearchQuery,
+    authority: F,
+    weights: &ScoringWeights,
+    bm25_params: &BM25Params,
+) where
+    F: Fn(&SearchResultItem) -> f64,
+{
+    score_and_sort_with(items, query, authority, weights, bm25_params, |_, _| {});
+}
+
+/// 与 [`score_and_sort_results_with_params`] 相同，同时返回每个结果的评分明细
+///
+/// # Returns
+///
+/// 返回结果 URL 到评分明细的映射
+pub fn score_and_explain_results<F>(
+    items: &mut [SearchResultItem],
+    query: &SearchQuery,
+    authority: F,
+    weights: &ScoringWeights,
+    bm25_params: &BM25Params,
+) -> HashMap<String, ScoreBreakdown>
+where
+    F: Fn(&SearchResultItem) -> f64,
+{
+    let mut breakdowns = HashMap::with_capacity(items.len());
+    score_and_sort_with(items, query, authority, weights, bm25_params, |item, breakdown| {
+        breakdowns.insert(item.url.clone(), breakdown);
+    });
+    breakdowns
+}
+
+/// 批量评分并排序，每个结果评分后调用 `on_scored`
+fn score_and_sort_with<F, G>(
+    items: &mut [SearchResultItem],
+    query: &SearchQuery,
+    authority: F,
+    weights: &ScoringWeights,
+    bm25_params: &BM25Params,
+    mut on_scored: G,
+) where
+    F: Fn(&SearchResultItem) -> f64,
+    G: FnMut(&SearchResultItem, ScoreBreakdown),
+{
+    if items.is_empty() {
+        return;
+    }
+
+    let tokenizer = Tokenizer::for_query(query);
+    let avg_title_length = items.iter()
+        .map(|i| tokenizer.tokenize(&i.title).len())
+        .sum::<usize>() as f64 / items.len() as f64;
+
+    let avg_content_length = items.iter()
+        .map(|i| tokenizer.tokenize(&i.content).len())
+        .sum::<usize>() as f64 / items.len() as f64;
+
+    for (position, item) in items.iter_mut().enumerate() {
+        let breakdown = score_breakdown(
+            item,
+            query,
+            authority(item),
+            position,
+            (avg_title_length, avg_content_length),
+            weights,
+            bm25_params,
+        );
+        item.score = breakdown.score;
+        on_scored(item, breakdown);
+    }
+
+    items.sort_by(|a, b| {
+        b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
 /// 评分并排序
 pub fn score_and_sort_results(
     items: &mut [SearchResultItem],
@@ -338,6 +822,35 @@ mod tests {
         assert!(score3 > score1);
     }
 
+    #[test]
+    fn test_tokenize_cjk_bigrams() {
+        assert_eq!(Tokenizer::Cjk.tokenize("人工智能"), vec!["人工", "工智", "智能"]);
+        assert_eq!(
+            Tokenizer::Cjk.tokenize("Rust 编程语言, 好"),
+            vec!["rust", "编程", "程语", "语言", "好"]
+        );
+        assert_eq!(Tokenizer::Cjk.tokenize("東京タワー2024"), vec!["東京", "京タ", "タワ", "ワー", "2024"]);
+        assert_eq!(Tokenizer::Cjk.tokenize("Hello, World!"), tokenize("Hello, World!"));
+    }
+
+    #[test]
+    fn test_tokenizer_selection() {
+        assert_eq!(Tokenizer::for_language(Some("zh-CN"), "rust"), Tokenizer::Cjk);
+        assert_eq!(Tokenizer::for_language(Some("en"), "rust"), Tokenizer::Simple);
+        assert_eq!(Tokenizer::for_language(None, "人工智能"), Tokenizer::Cjk);
+        assert_eq!(Tokenizer::for_language(None, "rust"), Tokenizer::Simple);
+    }
+
+    #[test]
+    fn test_bm25_cjk_partial_match() {
+        let params = BM25Params::default();
+        let related = bm25_score("人工智能的发展历史", "人工智能", 6.0, &params);
+        let unrelated = bm25_score("今天天气很好", "人工智能", 6.0, &params);
+        assert!(related > 0.0);
+        assert_eq!(unrelated, 0.0);
+        assert_eq!(bm25_score_with(Tokenizer::Simple, "人工智能的发展历史", "人工智能", 6.0, &params), 0.0);
+    }
+
     #[test]
     fn test_exact_match() {
         assert_eq!(exact_match_bonus("rust programming", "rust programming"), 1.0);
@@ -367,6 +880,93 @@ mod tests {
         assert_eq!(get_engine_authority("baidu"), 0.95); // 中国模式
         assert!(get_engine_authority("unknown") < 1.0);
     }
+
+    #[test]
+    fn test_recency_score() {
+        let now = chrono::Utc::now();
+        assert_eq!(recency_score(None), 0.0);
+        assert!((recency_score(Some(now)) - 1.0).abs() < 1e-3);
+        let half_life = now - chrono::Duration::days(RECENCY_HALF_LIFE_DAYS as i64);
+        assert!((recency_score(Some(half_life)) - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_scoring_profile_categories() {
+        let profile = ScoringProfile::default();
+        let (_, news) = profile.for_category(EngineType::News);
+        assert!(news.recency > 0.0);
+        let (code_bm25, code) = profile.for_category(EngineType::Code);
+        assert!(code.exact_match > 0.0);
+        assert!(code_bm25.b < BM25Params::default().b);
+        let (bm25, general) = profile.for_category(EngineType::General);
+        assert_eq!((bm25, general), (&BM25Params::default(), &ScoringWeights::default()));
+
+        // 按搜索类型覆盖的配置可从 TOML 读取
+        let parsed: ScoringProfile = toml::from_str(
+            "[weights]\ntitle_bm25 = 0.5\n\n[categories.news.weights]\nrecency = 0.6\n",
+        )
+        .unwrap();
+        assert_eq!(parsed.weights.title_bm25, 0.5);
+        assert_eq!(parsed.for_category(EngineType::News).1.recency, 0.6);
+        assert!(!parsed.categories.contains_key(&EngineType::Code));
+    }
+
+    #[test]
+    fn test_news_profile_favors_recent_results() {
+        let profile = ScoringProfile::default();
+        let (bm25, weights) = profile.for_category(EngineType::News);
+        let query = SearchQuery {
+            query: "election results".to_string(),
+            engine_type: EngineType::News,
+            ..Default::default()
+        };
+        let mut old = crate::derive::SearchResultItem {
+            title: "Election results".to_string(),
+            url: "https://news.example.com/old".to_string(),
+            content: "Latest election results".to_string(),
+            display_url: None,
+            site_name: None,
+            score: 0.0,
+            result_type: ResultType::News,
+            thumbnail: None,
+            published_date: Some(chrono::Utc::now() - chrono::Duration::days(60)),
+            template: None,
+            metadata: HashMap::new(),
+        };
+        let mut recent = old.clone();
+        recent.url = "https://news.example.com/recent".to_string();
+        recent.published_date = Some(chrono::Utc::now());
+
+        // 旧结果的原始排名靠前，新闻配置档按时效性使较新的结果领先
+        let mut items = vec![old.clone(), recent.clone()];
+        score_and_sort_results_with_params(&mut items, &query, |_| 0.7, weights, bm25);
+        assert_eq!(items[0].url, "https://news.example.com/recent");
+
+        // 默认权重不计时效性，保持原始排名
+        old.published_date = None;
+        let mut items = vec![old, recent];
+        score_and_sort_results_with_params(&mut items, &query, |_| 0.7, &ScoringWeights::default(), &BM25Params::default());
+        assert_eq!(items[0].url, "https://news.example.com/old");
+    }
+
+    #[test]
+    fn test_shared_scoring_profile_rejects_invalid() {
+        let shared = SharedScoringProfile::new(ScoringProfile::default()).unwrap();
+        let mut invalid = ScoringProfile::default();
+        invalid.bm25.b = 1.5;
+        assert!(invalid.validate().is_err());
+        assert!(shared.set(invalid).is_err());
+        assert_eq!(*shared.get(), ScoringProfile::default());
+
+        let mut negative = ScoringProfile::default();
+        negative.categories.get_mut(&EngineType::News).unwrap().weights.recency = -1.0;
+        assert!(negative.validate().is_err());
+
+        let mut tuned = ScoringProfile::default();
+        tuned.weights.url_match = 0.3;
+        shared.set(tuned).unwrap();
+        assert_eq!(shared.get().weights.url_match, 0.3);
+    }
 }
 
 // Include comprehensive scoring tests
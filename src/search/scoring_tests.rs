@@ -281,3 +281,53 @@ fn test_tokenize_empty_string() {
         score_results(&mut items, &query, "google", None, None);
         assert_eq!(items.len(), 0);
     }
+
+#[test]
+fn test_exact_title_match_ranks_academic_results_first() {
+    let query = SearchQuery {
+        query: "attention is all you need".to_string(),
+        ..Default::default()
+    };
+
+    let mut other = create_test_item(
+        "Attention mechanisms: a survey of attention is all you need follow-ups",
+        "attention is all you need attention is all you need",
+        "https://example.com/attention-is-all-you-need",
+    );
+    other.result_type = ResultType::Academic;
+    let mut exact = create_test_item("Attention Is All You Need", "", "https://arxiv.org/abs/1706.03762");
+    exact.result_type = ResultType::Academic;
+
+    let mut items = vec![other, exact];
+    score_and_sort_results(&mut items, &query, "arxiv", None);
+    assert_eq!(items[0].title, "Attention Is All You Need");
+    assert!(items[0].score >= EXACT_TITLE_MIN_SCORE);
+}
+
+#[test]
+fn test_is_exact_title_match() {
+    assert!(is_exact_title_match("Deep Learning.", "deep learning"));
+    assert!(!is_exact_title_match("Deep Learning Book", "deep learning"));
+    assert!(!is_exact_title_match("Anything", "   "));
+}
+
+#[test]
+fn test_cjk_query_ranks_matching_content_first() {
+    let query = SearchQuery {
+        query: "机器学习 入门".to_string(),
+        language: Some("zh".to_string()),
+        ..Default::default()
+    };
+
+    let unrelated = create_test_item("今日天气预报", "明天多云转晴，气温回升", "https://example.com/weather");
+    let related = create_test_item(
+        "机器学习入门教程",
+        "从零开始学习机器学习的基础知识",
+        "https://example.com/ml",
+    );
+
+    let mut items = vec![unrelated, related];
+    score_and_sort_results(&mut items, &query, "baidu", None);
+    assert_eq!(items[0].title, "机器学习入门教程");
+    assert!(items[0].score > items[1].score);
+}
@@ -0,0 +1,133 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 搜索会话上下文
+//!
+//! 从会话中之前的查询（见 `cache::SearchSession`）提取上下文词，
+//! 经查询参数传给聚合器提升同时包含这些词的结果
+
+use std::collections::HashSet;
+
+use super::rewrite::DEFAULT_STOP_WORDS;
+use super::scoring::Tokenizer;
+use crate::derive::{SearchQuery, SearchResultItem};
+
+/// 查询参数中会话上下文词的键（以空格分隔）
+pub const SESSION_CONTEXT_PARAM_KEY: &str = "session_context";
+
+/// 从会话中之前的查询提取上下文词
+///
+/// 跳过当前查询已包含的词、停用词与单字符词
+///
+/// # Arguments
+///
+/// * `previous` - 之前的查询（最近的在前）
+/// * `query` - 当前查询
+/// * `limit` - 上下文词数上限
+pub fn context_terms<'a>(previous: impl IntoIterator<Item = &'a str>, query: &SearchQuery, limit: usize) -> Vec<String> {
+    let tokenizer = Tokenizer::for_query(query);
+    let mut seen: HashSet<String> = tokenizer.tokenize(&query.query).into_iter().collect();
+    let mut terms = Vec::new();
+    for previous in previous {
+        for term in Tokenizer::for_language(query.language.as_deref(), previous).tokenize(previous) {
+            if terms.len() >= limit {
+                return terms;
+            }
+            if term.chars().count() < 2 || DEFAULT_STOP_WORDS.contains(&term.as_str()) || !seen.insert(term.clone()) {
+                continue;
+            }
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// 读取查询携带的会话上下文词
+pub fn requested_context(query: &SearchQuery) -> Vec<String> {
+    query
+        .params
+        .get(SESSION_CONTEXT_PARAM_KEY)
+        .map(|terms| terms.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// 按会话上下文提升结果
+///
+/// 标题或摘要包含上下文词的结果评分乘以 `1 + boost × 包含的比例`，再按评分稳定排序
+///
+/// # Returns
+///
+/// 返回被提升的结果数
+pub fn apply_session_context(items: &mut [SearchResultItem], terms: &[String], boost: f64) -> usize {
+    if terms.is_empty() || boost <= 0.0 {
+        return 0;
+    }
+    let mut boosted = 0;
+    for item in items.iter_mut() {
+        let text = format!("{} {}", item.title, item.content).to_lowercase();
+        let matched = terms.iter().filter(|term| text.contains(term.as_str())).count();
+        if matched > 0 {
+            item.score *= 1.0 + boost * matched as f64 / terms.len() as f64;
+            boosted += 1;
+        }
+    }
+    // 稳定排序：同分结果保持原有顺序
+    items.sort_by(|a, b| b.score.total_cmp(&a.score));
+    boosted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derive::ResultType;
+    use std::collections::HashMap;
+
+    fn item(title: &str, score: f64) -> SearchResultItem {
+        SearchResultItem {
+            title: title.to_string(),
+            url: format!("https://example.com/{}", title.replace(' ', "-")),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score,
+            result_type: ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_context_terms() {
+        let previous = ["rust async handling", "what is the tokio runtime"];
+        let query = SearchQuery {
+            query: "Error Handling".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(context_terms(previous, &query, 8), ["rust", "async", "tokio", "runtime"]);
+        assert_eq!(context_terms(previous, &query, 2), ["rust", "async"]);
+        assert!(context_terms([], &query, 8).is_empty());
+    }
+
+    #[test]
+    fn test_apply_session_context() {
+        let mut items = vec![item("error handling in go", 0.5), item("error handling in rust async", 0.45)];
+        let terms = vec!["rust".to_string(), "async".to_string()];
+        assert_eq!(apply_session_context(&mut items, &terms, 0.2), 1);
+        assert_eq!(items[0].title, "error handling in rust async");
+        assert!((items[0].score - 0.54).abs() < 1e-9);
+        assert_eq!(apply_session_context(&mut items, &[], 0.2), 0);
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 垃圾结果过滤模块
+//!
+//! 聚合去重后按启发式信号为每条结果打分，移除采集站、内容农场等低质量结果：
+//!
+//! - 低信誉域名：命中 [`SpamFilterConfig::low_reputation_domains`]（含子域名）
+//! - 关键词堆砌：标题与摘要中同一个词占比过高
+//! - 模板摘要：不同站点的摘要在去掉数字与站点名后完全相同（批量生成的页面）
+//!
+//! 各信号按权重累加为 0.0 - 1.0 的评分，达到阈值的结果被移除
+
+use std::collections::{HashMap, HashSet};
+
+use crate::config::engines::SpamFilterConfig;
+use crate::derive::SearchResultItem;
+
+use super::filtering::{domain_suffixes, item_host};
+use super::scoring::Tokenizer;
+
+/// 低信誉域名信号的权重（单独即可超过默认阈值）
+const DOMAIN_WEIGHT: f64 = 0.7;
+
+/// 关键词堆砌信号的权重
+const STUFFING_WEIGHT: f64 = 0.4;
+
+/// 模板摘要信号的权重
+const TEMPLATE_WEIGHT: f64 = 0.4;
+
+/// 检测关键词堆砌所需的最少词数
+const MIN_STUFFING_TOKENS: usize = 8;
+
+/// 同一个词至少出现多少次才可能视为堆砌
+const MIN_STUFFING_REPEATS: usize = 4;
+
+/// 词占比达到该值时开始计入堆砌信号
+const STUFFING_RATIO_START: f64 = 0.15;
+
+/// 词占比超过起点该值时堆砌信号达到满分
+const STUFFING_RATIO_SPAN: f64 = 0.2;
+
+/// 参与模板比对的摘要最少词数（过短的摘要容易偶然相同）
+const MIN_TEMPLATE_TOKENS: usize = 6;
+
+/// 不计入堆砌检测的常见虚词
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "you", "your", "are", "this", "that", "from", "les", "des", "der", "die", "und",
+];
+
+/// 垃圾结果过滤器
+#[derive(Debug, Clone)]
+pub struct SpamFilter {
+    /// 移除阈值
+    threshold: f64,
+    /// 低信誉域名（小写）
+    low_reputation_domains: HashSet<String>,
+}
+
+impl SpamFilter {
+    /// 根据配置创建过滤器
+    pub fn new(config: &SpamFilterConfig) -> Self {
+        Self {
+            threshold: config.threshold,
+            low_reputation_domains: config
+                .low_reputation_domains
+                .iter()
+                .map(|domain| domain.trim().trim_start_matches('.').to_ascii_lowercase())
+                .filter(|domain| !domain.is_empty())
+                .collect(),
+        }
+    }
+
+    /// 计算每条结果的垃圾评分（0.0 - 1.0），顺序与 `items` 一致
+    pub fn score(&self, items: &[SearchResultItem]) -> Vec<f64> {
+        let hosts: Vec<Option<String>> = items.iter().map(|item| item_host(&item.url)).collect();
+        let templates: Vec<Option<String>> = items
+            .iter()
+            .zip(&hosts)
+            .map(|(item, host)| template_key(&item.content, host.as_deref()))
+            .collect();
+
+        // 每个模板出现在多少个不同站点
+        let mut template_sites: HashMap<&str, HashSet<&str>> = HashMap::new();
+        for (template, host) in templates.iter().zip(&hosts) {
+            if let (Some(template), Some(host)) = (template, host) {
+                template_sites.entry(template).or_default().insert(host);
+            }
+        }
+
+        items
+            .iter()
+            .zip(hosts.iter().zip(&templates))
+            .map(|(item, (host, template))| {
+                let mut score = 0.0;
+                if host.as_deref().is_some_and(|host| self.is_low_reputation(host)) {
+                    score += DOMAIN_WEIGHT;
+                }
+                score += STUFFING_WEIGHT * keyword_stuffing(&format!("{} {}", item.title, item.content));
+                if template
+                    .as_deref()
+                    .and_then(|template| template_sites.get(template))
+                    .is_some_and(|sites| sites.len() > 1)
+                {
+                    score += TEMPLATE_WEIGHT;
+                }
+                score.min(1.0)
+            })
+            .collect()
+    }
+
+    /// 移除评分达到阈值的结果
+    ///
+    /// # 返回值
+    ///
+    /// 返回移除的结果数
+    pub fn apply(&self, items: &mut Vec<SearchResultItem>) -> usize {
+        let scores = self.score(items);
+        let before = items.len();
+        let mut scores = scores.into_iter();
+        items.retain(|_| scores.next().is_some_and(|score| score < self.threshold));
+        before - items.len()
+    }
+
+    /// 主机名或其父域名是否在低信誉列表中
+    fn is_low_reputation(&self, host: &str) -> bool {
+        domain_suffixes(host).any(|domain| self.low_reputation_domains.contains(domain))
+    }
+}
+
+/// 关键词堆砌程度（0.0 - 1.0）：出现最多的词占全部词的比例超出正常范围的程度
+fn keyword_stuffing(text: &str) -> f64 {
+    let tokens: Vec<String> = Tokenizer::for_language(None, text)
+        .tokenize(text)
+        .into_iter()
+        .filter(|token| token.chars().count() > 1 && !STOP_WORDS.contains(&token.as_str()))
+        .collect();
+    if tokens.len() < MIN_STUFFING_TOKENS {
+        return 0.0;
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for token in &tokens {
+        *counts.entry(token).or_insert(0) += 1;
+    }
+    let max = counts.values().copied().max().unwrap_or(0);
+    if max < MIN_STUFFING_REPEATS {
+        return 0.0;
+    }
+    let ratio = max as f64 / tokens.len() as f64;
+    ((ratio - STUFFING_RATIO_START) / STUFFING_RATIO_SPAN).clamp(0.0, 1.0)
+}
+
+/// 摘要的模板键：去掉站点名中出现的词，数字统一替换为 `#`
+fn template_key(content: &str, host: Option<&str>) -> Option<String> {
+    let site_words: HashSet<&str> = host
+        .map(|host| host.split(['.', '-']).filter(|part| part.len() > 2).collect())
+        .unwrap_or_default();
+    let tokens: Vec<String> = Tokenizer::for_language(None, content)
+        .tokenize(content)
+        .into_iter()
+        .filter(|token| !site_words.contains(token.as_str()))
+        .map(|token| if token.chars().all(|c| c.is_ascii_digit()) { "#".to_string() } else { token })
+        .collect();
+    (tokens.len() >= MIN_TEMPLATE_TOKENS).then(|| tokens.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(url: &str, title: &str, content: &str) -> SearchResultItem {
+        SearchResultItem {
+            title: title.to_string(),
+            url: url.to_string(),
+            content: content.to_string(),
+            display_url: None,
+            site_name: None,
+            score: 1.0,
+            result_type: crate::derive::ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn filter() -> SpamFilter {
+        SpamFilter::new(&SpamFilterConfig {
+            enabled: true,
+            low_reputation_domains: vec!["scraper.example".to_string()],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_low_reputation_domain() {
+        let items = vec![
+            item("https://www.scraper.example/a", "Rust 入门", "一篇介绍所有权与借用的文章"),
+            item("https://docs.rs/serde", "serde", "Serialization framework for Rust"),
+        ];
+        let scores = filter().score(&items);
+        assert!(scores[0] >= 0.6);
+        assert_eq!(scores[1], 0.0);
+    }
+
+    #[test]
+    fn test_keyword_stuffing() {
+        assert_eq!(keyword_stuffing("Learn Rust ownership and borrowing with practical examples today"), 0.0);
+        let stuffed = "cheap shoes cheap shoes buy cheap shoes best cheap shoes online cheap shoes";
+        assert_eq!(keyword_stuffing(stuffed), 1.0);
+        assert!(keyword_stuffing("便宜手机便宜手机便宜手机便宜手机便宜手机") > 0.5);
+    }
+
+    #[test]
+    fn test_duplicate_template_across_sites() {
+        let mut items = vec![
+            item(
+                "https://farm-one.example/rust",
+                "Rust",
+                "Find 12 best rust tutorials at farm one. Compare prices and reviews now",
+            ),
+            item(
+                "https://farmtwo.example/rust",
+                "Rust",
+                "Find 34 best rust tutorials at farmtwo. Compare prices and reviews now",
+            ),
+            item(
+                "https://stuffed.example/x",
+                "cheap cheap cheap cheap deals cheap cheap",
+                "Find 56 best rust tutorials at stuffed. Compare prices and reviews now",
+            ),
+            item("https://doc.rust-lang.org/book", "The Rust Book", "An introductory book about Rust"),
+        ];
+        let scores = filter().score(&items);
+        // 模板信号单独不足以移除，与关键词堆砌叠加后才移除
+        assert_eq!(scores[0], TEMPLATE_WEIGHT);
+        assert_eq!(scores[1], TEMPLATE_WEIGHT);
+        assert!(scores[2] >= 0.6);
+        assert_eq!(scores[3], 0.0);
+
+        assert_eq!(filter().apply(&mut items), 1);
+        assert_eq!(items.len(), 3);
+        assert!(items.iter().all(|item| !item.url.contains("stuffed")));
+    }
+
+    #[test]
+    fn test_same_site_is_not_a_template() {
+        let content = "Find the best rust tutorials and compare prices and reviews now";
+        let items = vec![
+            item("https://a.example/1", "one", content),
+            item("https://a.example/2", "two", content),
+        ];
+        assert_eq!(filter().score(&items), vec![0.0, 0.0]);
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 拼写纠错
+//!
+//! SymSpell 风格的对称删除算法：词典中每个词预先生成编辑距离内的删除变体，
+//! 查询词同样生成删除变体后查表，再用 Damerau-Levenshtein 距离筛选候选
+
+use std::collections::{HashMap, HashSet};
+
+/// 最大编辑距离
+const MAX_EDIT_DISTANCE: usize = 2;
+
+/// 只对词的前缀生成删除变体，控制索引大小
+const PREFIX_LENGTH: usize = 7;
+
+/// 参与纠错的最短词长
+const MIN_WORD_LENGTH: usize = 3;
+
+/// 参与纠错的最长词长
+const MAX_WORD_LENGTH: usize = 24;
+
+/// 词典最大词数，超过后只更新已有词的词频
+const MAX_DICTIONARY_WORDS: usize = 50_000;
+
+/// 拼写纠错词典
+#[derive(Debug, Default)]
+pub struct SpellCorrector {
+    /// 词频
+    words: HashMap<String, u64>,
+    /// 删除变体 -> 原词
+    deletes: HashMap<String, Vec<String>>,
+}
+
+impl SpellCorrector {
+    /// 创建空词典
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 词典词数
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// 词典是否为空
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// 是否为可纠错的词（纯字母且长度合适）
+    fn is_candidate_word(word: &str) -> bool {
+        let length = word.chars().count();
+        (MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&length) && word.chars().all(char::is_alphabetic)
+    }
+
+    /// 从文本中学习词汇（如结果标题）
+    pub fn add_text(&mut self, text: &str) {
+        for word in text.split(|c: char| !c.is_alphanumeric()) {
+            let word = word.to_lowercase();
+            if Self::is_candidate_word(&word) {
+                self.add_word(&word, 1);
+            }
+        }
+    }
+
+    /// 添加词及词频
+    pub fn add_word(&mut self, word: &str, count: u64) {
+        if let Some(frequency) = self.words.get_mut(word) {
+            *frequency += count;
+            return;
+        }
+        if self.words.len() >= MAX_DICTIONARY_WORDS {
+            return;
+        }
+
+        self.words.insert(word.to_string(), count);
+        for delete in Self::deletes_of(word) {
+            self.deletes.entry(delete).or_default().push(word.to_string());
+        }
+    }
+
+    /// 生成前缀在编辑距离内的所有删除变体（含前缀本身）
+    fn deletes_of(word: &str) -> HashSet<String> {
+        let prefix: String = word.chars().take(PREFIX_LENGTH).collect();
+        let mut deletes = HashSet::new();
+        let mut frontier = vec![prefix.clone()];
+        deletes.insert(prefix);
+
+        for _ in 0..MAX_EDIT_DISTANCE {
+            let mut next = Vec::new();
+            for candidate in &frontier {
+                let chars: Vec<char> = candidate.chars().collect();
+                if chars.len() <= 1 {
+                    continue;
+                }
+                for i in 0..chars.len() {
+                    let delete: String = chars[..i].iter().chain(&chars[i + 1..]).collect();
+                    if deletes.insert(delete.clone()) {
+                        next.push(delete);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        deletes
+    }
+
+    /// 查找最接近的词典词
+    ///
+    /// # Returns
+    ///
+    /// 词已在词典中或找不到编辑距离内的候选时返回 None；
+    /// 多个候选时取距离最小、词频最高者
+    pub fn lookup(&self, word: &str) -> Option<String> {
+        let word = word.to_lowercase();
+        if !Self::is_candidate_word(&word) || self.words.contains_key(&word) {
+            return None;
+        }
+
+        let mut best: Option<(usize, u64, &str)> = None;
+        let mut checked = HashSet::new();
+        for delete in Self::deletes_of(&word) {
+            let Some(candidates) = self.deletes.get(&delete) else {
+                continue;
+            };
+            for candidate in candidates {
+                if !checked.insert(candidate.as_str()) {
+                    continue;
+                }
+                let distance = edit_distance(&word, candidate);
+                if distance > MAX_EDIT_DISTANCE {
+                    continue;
+                }
+                let frequency = self.words[candidate];
+                let better = match best {
+                    None => true,
+                    Some((best_distance, best_frequency, _)) => {
+                        distance < best_distance || (distance == best_distance && frequency > best_frequency)
+                    }
+                };
+                if better {
+                    best = Some((distance, frequency, candidate));
+                }
+            }
+        }
+
+        best.map(|(_, _, candidate)| candidate.to_string())
+    }
+
+    /// 纠正整条查询
+    ///
+    /// # Returns
+    ///
+    /// 至少有一个词被纠正时返回纠正后的查询
+    pub fn correct(&self, query: &str) -> Option<String> {
+        let mut changed = false;
+        let corrected: Vec<String> = query
+            .split_whitespace()
+            .map(|token| match self.lookup(token) {
+                Some(correction) => {
+                    changed = true;
+                    correction
+                }
+                None => token.to_string(),
+            })
+            .collect();
+
+        changed.then(|| corrected.join(" "))
+    }
+}
+
+/// Damerau-Levenshtein 距离（最优字符串对齐版本，相邻换位计为一次编辑）
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut matrix = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut value = (matrix[i - 1][j] + 1)
+                .min(matrix[i][j - 1] + 1)
+                .min(matrix[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                value = value.min(matrix[i - 2][j - 2] + 1);
+            }
+            matrix[i][j] = value;
+        }
+    }
+    matrix[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corrector() -> SpellCorrector {
+        let mut corrector = SpellCorrector::new();
+        corrector.add_text("The Rust Programming Language");
+        corrector.add_text("Programming Rust: Fast, Safe Systems Development");
+        corrector.add_text("Python programming for beginners");
+        corrector
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("rust", "rust"), 0);
+        assert_eq!(edit_distance("rsut", "rust"), 1);
+        assert_eq!(edit_distance("programing", "programming"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let corrector = corrector();
+        assert_eq!(corrector.lookup("programing").as_deref(), Some("programming"));
+        assert_eq!(corrector.lookup("Pyhton").as_deref(), Some("python"));
+        assert_eq!(corrector.lookup("rust"), None);
+        assert_eq!(corrector.lookup("xylophone"), None);
+    }
+
+    #[test]
+    fn test_correct_query() {
+        let corrector = corrector();
+        assert_eq!(corrector.correct("rsut programing").as_deref(), Some("rust programming"));
+        assert_eq!(corrector.correct("rust programming"), None);
+        assert_eq!(corrector.correct("rust 2024"), None);
+    }
+}
@@ -16,63 +16,481 @@
 //!
 //! 对搜索结果进行基本的清理和标准化
 
-use crate::derive::{SearchResultItem, SearchResult};
-use std::collections::HashSet;
+use crate::derive::{
+    MagnetLink, ResultType, SearchResultItem, SearchResult, INFOHASH_METADATA_KEY,
+    LEECHERS_METADATA_KEY, MAGNET_METADATA_KEY, SEEDERS_METADATA_KEY,
+};
+use super::query::detect_language;
+use crate::text::truncate_in_place;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// 价格元数据键（标准化后为数值字符串）
+pub const PRICE_METADATA_KEY: &str = "price";
+
+/// 货币元数据键（ISO 4217 代码）
+pub const CURRENCY_METADATA_KEY: &str = "currency";
+
+/// 原始价格文本元数据键
+pub const PRICE_RAW_METADATA_KEY: &str = "price_raw";
+
+/// 检测到的结果语言元数据键（ISO 639-1 代码）
+///
+/// 不使用 `language`：部分引擎（如 GitHub）用它记录编程语言
+pub const LANGUAGE_METADATA_KEY: &str = "detected_language";
+
+/// 货币符号与代码（较长的符号在前，避免 `$` 抢先匹配 `US $`）
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+    ("US $", "USD"),
+    ("US$", "USD"),
+    ("C $", "CAD"),
+    ("C$", "CAD"),
+    ("AU $", "AUD"),
+    ("A$", "AUD"),
+    ("HK$", "HKD"),
+    ("JP¥", "JPY"),
+    ("RMB", "CNY"),
+    ("元", "CNY"),
+    ("$", "USD"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("¥", "CNY"),
+    ("￥", "CNY"),
+    ("₹", "INR"),
+    ("₽", "RUB"),
+    ("₩", "KRW"),
+];
 
 /// 清理文本
+///
+/// 合并空白、解码 HTML 实体，并按字素簇数截断到 `max_length`（含末尾的 `...`，见 [`truncate`](crate::text::truncate)）
 pub fn clean_text(text: &str, max_length: usize) -> String {
+    clean_text_cow(text, max_length).into_owned()
+}
+
+/// 清理文本，文本已是清理后的形式时不分配内存
+///
+/// 规则同 [`clean_text`]
+pub fn clean_text_cow(text: &str, max_length: usize) -> Cow<'_, str> {
+    if is_clean(text, max_length) {
+        return Cow::Borrowed(text);
+    }
+    let mut buffer = String::with_capacity(text.len());
+    clean_text_into(text, max_length, &mut buffer);
+    Cow::Owned(buffer)
+}
+
+/// 判断文本是否无需清理：无首尾空白、仅有单个空格分隔、无实体且不超长
+fn is_clean(text: &str, max_length: usize) -> bool {
+    let mut previous_space = true;
+    for (count, c) in text.chars().enumerate() {
+        if count >= max_length || c == '&' {
+            return false;
+        }
+        if c.is_whitespace() {
+            if c != ' ' || previous_space {
+                return false;
+            }
+            previous_space = true;
+        } else {
+            previous_space = false;
+        }
+    }
+    !previous_space || text.is_empty()
+}
+
+/// 将清理后的文本写入 `out`（先清空），复用其已分配的容量
+fn clean_text_into(text: &str, max_length: usize, out: &mut String) {
+    out.clear();
+
     // 1. 移除多余空白
-    let cleaned: String = text
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ");
-    
-    // 2. HTML 实体解码
-    let cleaned = html_escape::decode_html_entities(&cleaned).to_string();
-    
-    // 3. 截断
-    if cleaned.len() > max_length {
-        let truncated: String = cleaned.chars().take(max_length - 3).collect();
-        format!("{}...", truncated)
-    } else {
-        cleaned
+    for (i, word) in text.split_whitespace().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        out.push_str(word);
     }
+
+    // 2. HTML 实体解码（仅在确有实体时分配）
+    if out.contains('&') {
+        let decoded = match html_escape::decode_html_entities(out.as_str()) {
+            Cow::Owned(decoded) => Some(decoded),
+            Cow::Borrowed(_) => None,
+        };
+        if let Some(decoded) = decoded {
+            *out = decoded;
+        }
+    }
+
+    // 3. 按字素簇截断，不会切断多字节字符与组合字符
+    truncate_in_place(out, max_length);
+}
+
+/// 原地清理字段，`scratch` 为可复用的缓冲区
+fn clean_field(field: &mut String, max_length: usize, scratch: &mut String) {
+    if is_clean(field, max_length) {
+        return;
+    }
+    clean_text_into(field, max_length, scratch);
+    std::mem::swap(field, scratch);
+}
+
+/// 解析价格数字（兼容 `1,234.56`、`1.234,56`、`12,50`）
+fn parse_amount(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let last_dot = text.rfind('.');
+    let last_comma = text.rfind(',');
+    let normalized = match (last_dot, last_comma) {
+        // 两者都有：靠后的是小数点
+        (Some(dot), Some(comma)) if comma > dot => text.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => text.replace(',', ""),
+        // 只有逗号：后面恰好两位时视为小数点，否则为千分位
+        (None, Some(comma)) if text.len() - comma - 1 == 2 => text.replace(',', "."),
+        (None, Some(_)) => text.replace(',', ""),
+        _ => text.to_string(),
+    };
+    normalized.parse().ok()
+}
+
+/// 从价格文本中提取金额与货币
+///
+/// # Arguments
+///
+/// * `text` - 价格文本，如 `"$1,299.00"`、`"EUR 12,50"`、`"¥ 99 元"`；
+///   价格区间（如 `"$10.00 to $20.00"`）取最低价
+///
+/// # Returns
+///
+/// 成功时返回 (金额, ISO 4217 货币代码)，无法识别货币或金额时返回 None
+pub fn parse_price(text: &str) -> Option<(f64, String)> {
+    let text = text.trim();
+
+    let currency = CURRENCY_SYMBOLS
+        .iter()
+        .find(|(symbol, _)| text.contains(symbol))
+        .map(|(_, code)| code.to_string())
+        .or_else(|| {
+            // 形如 "EUR 12.50" 或 "12.50 USD" 的 ISO 代码
+            text.split(|c: char| !c.is_ascii_alphabetic())
+                .find(|word| word.len() == 3 && word.chars().all(|c| c.is_ascii_uppercase()))
+                .map(|code| code.to_string())
+        })?;
+
+    // 取第一个数字片段
+    let start = text.find(|c: char| c.is_ascii_digit())?;
+    let number: String = text[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+    let amount = parse_amount(number.trim_end_matches(['.', ',']))?;
+
+    Some((amount, currency))
+}
+
+/// 货币换算配置
+///
+/// `rates` 表示 1 单位该货币折合多少 `target` 货币
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CurrencyConverter {
+    /// 目标货币（ISO 4217 代码）
+    pub target: String,
+    /// 汇率表
+    pub rates: HashMap<String, f64>,
+}
+
+impl CurrencyConverter {
+    /// 创建换算器
+    pub fn new(target: &str, rates: HashMap<String, f64>) -> Self {
+        Self {
+            target: target.to_uppercase(),
+            rates: rates.into_iter().map(|(code, rate)| (code.to_uppercase(), rate)).collect(),
+        }
+    }
+
+    /// 换算金额，缺少汇率时返回 None
+    pub fn convert(&self, amount: f64, currency: &str) -> Option<f64> {
+        if currency.eq_ignore_ascii_case(&self.target) {
+            return Some(amount);
+        }
+        self.rates.get(&currency.to_uppercase()).map(|rate| amount * rate)
+    }
+
+    /// 在任意两种货币之间换算（经由目标货币中转），缺少汇率时返回 None
+    pub fn convert_between(&self, amount: f64, from: &str, to: &str) -> Option<f64> {
+        let in_target = self.convert(amount, from)?;
+        if to.eq_ignore_ascii_case(&self.target) {
+            return Some(in_target);
+        }
+        self.rates
+            .get(&to.to_uppercase())
+            .filter(|rate| **rate > 0.0)
+            .map(|rate| in_target / rate)
+    }
+}
+
+/// 将结果中的价格标准化为 `price`/`currency` 元数据对
+///
+/// 引擎在 `price` 中写入原始价格文本；解析成功后原文保存到 `price_raw`，
+/// `price` 改为两位小数的数值字符串。提供换算器且汇率可用时换算为目标货币，
+/// 否则保留原币种
+pub fn normalize_price(item: &mut SearchResultItem, converter: Option<&CurrencyConverter>) {
+    let Some(raw) = item.metadata.get(PRICE_METADATA_KEY) else {
+        return;
+    };
+
+    let parsed = match item.metadata.get(CURRENCY_METADATA_KEY) {
+        // 已经是结构化价格
+        Some(currency) => raw.parse::<f64>().ok().map(|amount| (amount, currency.clone())),
+        None => parse_price(raw),
+    };
+    let Some((mut amount, mut currency)) = parsed else {
+        return;
+    };
+
+    if let Some(converter) = converter
+        && let Some(converted) = converter.convert(amount, &currency)
+    {
+        amount = converted;
+        currency = converter.target.clone();
+    }
+
+    let raw = item.metadata.insert(PRICE_METADATA_KEY.to_string(), format!("{:.2}", amount));
+    if let Some(raw) = raw {
+        item.metadata.entry(PRICE_RAW_METADATA_KEY.to_string()).or_insert(raw);
+    }
+    item.metadata.insert(CURRENCY_METADATA_KEY.to_string(), currency);
+}
+
+/// 读取标准化后的价格
+pub fn item_price(item: &SearchResultItem) -> Option<f64> {
+    item.metadata.get(PRICE_METADATA_KEY).and_then(|p| p.parse().ok())
 }
 
 /// 标准化单个结果项
 pub fn standardize_item(item: &mut SearchResultItem) {
+    standardize_item_with(item, &mut String::new());
+}
+
+/// 标准化单个结果项，复用 `scratch` 缓冲区
+fn standardize_item_with(item: &mut SearchResultItem, scratch: &mut String) {
     // 清理标题（最多200字符）
-    item.title = clean_text(&item.title, 200);
-    
+    clean_field(&mut item.title, 200, scratch);
+
     // 清理内容（最多500字符）
-    item.content = clean_text(&item.content, 500);
+    clean_field(&mut item.content, 500, scratch);
     
     // 确保 URL 不为空
     if item.url.trim().is_empty() {
         item.url = "#".to_string();
     }
+
+    // 解析价格
+    normalize_price(item, None);
+
+    // 解析磁力链接
+    normalize_magnet(item);
+
+    // 检测结果语言
+    detect_item_language(item);
+}
+
+/// 检测结果的标题与摘要语言，写入 [`LANGUAGE_METADATA_KEY`] 元数据
+///
+/// 已有该元数据时保留（引擎可以直接给出语言）；无法检测时不写入
+pub fn detect_item_language(item: &mut SearchResultItem) {
+    if item.metadata.contains_key(LANGUAGE_METADATA_KEY) {
+        return;
+    }
+    let text = format!("{} {}", item.title, item.content);
+    if let Some(language) = detect_language(text.trim()) {
+        item.metadata.insert(LANGUAGE_METADATA_KEY.to_string(), language);
+    }
+}
+
+/// 读取检测到的结果语言
+pub fn item_language(item: &SearchResultItem) -> Option<&str> {
+    item.metadata.get(LANGUAGE_METADATA_KEY).map(String::as_str)
+}
+
+/// 语言代码的主语言部分（小写），`zh-CN`、`zh_TW` 都得到 `zh`
+pub fn primary_language(code: &str) -> String {
+    code.split(['-', '_']).next().unwrap_or(code).trim().to_lowercase()
+}
+
+/// 标准化种子结果的磁力链接元数据
+///
+/// URL 或 `magnet` 元数据为磁力链接时提取 `infohash`（统一为小写十六进制），
+/// `seeders`/`leechers` 去除千位分隔符等非数字字符
+pub fn normalize_magnet(item: &mut SearchResultItem) {
+    if item.url.starts_with("magnet:") && !item.metadata.contains_key(MAGNET_METADATA_KEY) {
+        item.metadata.insert(MAGNET_METADATA_KEY.to_string(), item.url.clone());
+    }
+
+    let infohash = item
+        .metadata
+        .get(INFOHASH_METADATA_KEY)
+        .and_then(|hash| MagnetLink::normalize_infohash(hash))
+        .or_else(|| {
+            item.metadata
+                .get(MAGNET_METADATA_KEY)
+                .and_then(|uri| MagnetLink::parse(uri))
+                .map(|magnet| magnet.infohash)
+        });
+    let Some(infohash) = infohash else {
+        return;
+    };
+    item.metadata.insert(INFOHASH_METADATA_KEY.to_string(), infohash);
+    if item.result_type == ResultType::Web {
+        item.result_type = ResultType::Torrent;
+    }
+
+    for key in [SEEDERS_METADATA_KEY, LEECHERS_METADATA_KEY] {
+        if let Some(count) = item.metadata.get_mut(key) {
+            count.retain(|c| c.is_ascii_digit());
+        }
+    }
 }
 
-/// 简单去重（基于 URL）
+/// 简单去重（基于 URL，种子结果基于 infohash）
 pub fn deduplicate_by_url(items: &mut Vec<SearchResultItem>) {
     let mut seen = HashSet::new();
-    items.retain(|item| {
-        let url_lower = item.url.to_lowercase().trim().to_string();
-        seen.insert(url_lower)
-    });
+    items.retain(|item| seen.insert(dedup_key(item)));
+}
+
+/// 去重使用的键（种子结果为 infohash，其余为规范化的 URL）
+pub(crate) fn dedup_key(item: &SearchResultItem) -> String {
+    match item.metadata.get(INFOHASH_METADATA_KEY) {
+        Some(infohash) => format!("btih:{}", infohash),
+        None => item.url.trim().to_lowercase(),
+    }
 }
 
 /// 标准化搜索结果
 pub fn standardize_results(result: &mut SearchResult) {
     // 1. 标准化每个项
+    let mut scratch = String::new();
     for item in &mut result.items {
-        standardize_item(item);
+        standardize_item_with(item, &mut scratch);
     }
     
     // 2. 去重
     deduplicate_by_url(&mut result.items);
 }
 
+/// 查询词高亮标记
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighlightMarkers {
+    /// 开始标记，如 `<em>`
+    pub open: String,
+    /// 结束标记，如 `</em>`
+    pub close: String,
+    /// 对标记之外的文本做 HTML 转义（HTML 标记时应开启，避免摘要中的 `<` 被当作标签）
+    #[serde(default)]
+    pub escape_html: bool,
+}
+
+impl HighlightMarkers {
+    /// 创建自定义标记（不做 HTML 转义，适合终端颜色码等）
+    pub fn new(open: &str, close: &str) -> Self {
+        Self {
+            open: open.to_string(),
+            close: close.to_string(),
+            escape_html: false,
+        }
+    }
+}
+
+impl Default for HighlightMarkers {
+    fn default() -> Self {
+        Self {
+            open: "<em>".to_string(),
+            close: "</em>".to_string(),
+            escape_html: true,
+        }
+    }
+}
+
+/// 从查询中提取需要高亮的词
+///
+/// 去掉首尾标点与引号，忽略排除词（`-term`）和搜索语法（`site:` 等）
+pub fn highlight_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = query
+        .split_whitespace()
+        .filter(|token| !token.starts_with('-') && !token.contains(':'))
+        .map(|token| token.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|term| !term.is_empty())
+        .collect();
+    // 长词优先，避免 "rust" 抢先匹配 "rustacean"
+    terms.sort_by(|a, b| b.chars().count().cmp(&a.chars().count()).then_with(|| a.cmp(b)));
+    terms.dedup();
+    terms
+}
+
+/// 在文本中用标记包裹查询词（不区分大小写）
+///
+/// # Arguments
+///
+/// * `text` - 原文本
+/// * `terms` - 由 [`highlight_terms`] 提取的查询词
+/// * `markers` - 高亮标记
+///
+/// # Returns
+///
+/// 高亮后的文本；以字母数字开头的词只在词首匹配，中文等无分词语言按子串匹配
+pub fn highlight_text(text: &str, terms: &[String], markers: &HighlightMarkers) -> String {
+    let escape = |s: &str| -> String {
+        if markers.escape_html {
+            html_escape::encode_text(s).into_owned()
+        } else {
+            s.to_string()
+        }
+    };
+    let terms: Vec<Vec<char>> = terms.iter().map(|term| term.chars().collect()).collect();
+    if terms.is_empty() {
+        return escape(text);
+    }
+
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let is_word_char = |c: char| c.is_ascii_alphanumeric();
+
+    let mut output = String::with_capacity(text.len());
+    let mut plain_start = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let at_word_start = i == 0 || !is_word_char(chars[i - 1].1);
+        let matched = terms.iter().find(|term| {
+            (at_word_start || !is_word_char(term[0]))
+                && i + term.len() <= chars.len()
+                && term.iter().zip(&chars[i..]).all(|(t, (_, c))| *t == fold(*c))
+        });
+
+        let Some(term) = matched else {
+            i += 1;
+            continue;
+        };
+        let start = chars[i].0;
+        let end = chars.get(i + term.len()).map(|(idx, _)| *idx).unwrap_or(text.len());
+        output.push_str(&escape(&text[plain_start..start]));
+        output.push_str(&markers.open);
+        output.push_str(&escape(&text[start..end]));
+        output.push_str(&markers.close);
+        plain_start = end;
+        i += term.len();
+    }
+    output.push_str(&escape(&text[plain_start..]));
+    output
+}
+
+/// 高亮搜索结果摘要中的查询词
+pub fn highlight_results(result: &mut SearchResult, query: &str, markers: &HighlightMarkers) {
+    let terms = highlight_terms(query);
+    for item in &mut result.items {
+        item.content = highlight_text(&item.content, &terms, markers);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,5 +502,191 @@ mod tests {
         let long = "a".repeat(300);
         let cleaned = clean_text(&long, 100);
         assert!(cleaned.len() <= 103); // 100 + "..."
+
+        // 按字符而不是字节计算长度
+        assert_eq!(clean_text("搜索引擎", 4), "搜索引擎");
+        assert_eq!(clean_text("搜索引擎聚合", 5), "搜索...");
+        assert_eq!(clean_text("hello", 2), "he");
+        assert_eq!(clean_text("hello", 0), "");
+    }
+
+    #[test]
+    fn test_clean_text_cow() {
+        assert!(matches!(clean_text_cow("hello world", 100), Cow::Borrowed("hello world")));
+        assert!(matches!(clean_text_cow("", 0), Cow::Borrowed("")));
+        assert_eq!(clean_text_cow("hello  world", 100), "hello world");
+        assert_eq!(clean_text_cow(" hello", 100), "hello");
+        assert_eq!(clean_text_cow("a\tb", 100), "a b");
+        assert_eq!(clean_text_cow("Tom &amp; Jerry", 100), "Tom & Jerry");
+        assert_eq!(clean_text_cow("hello world", 8), "hello...");
+
+        let mut item = SearchResultItem {
+            title: "  Rust &lt;3 ".to_string(),
+            url: "https://example.com".to_string(),
+            content: "already clean".to_string(),
+            display_url: None,
+            site_name: None,
+            score: 0.0,
+            result_type: ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        };
+        standardize_item(&mut item);
+        assert_eq!(item.title, "Rust <3");
+        assert_eq!(item.content, "already clean");
+    }
+
+    #[test]
+    fn test_detect_item_language() {
+        let mut item = SearchResultItem {
+            title: "Rust 编程语言".to_string(),
+            url: "https://example.com".to_string(),
+            content: "一门注重安全与性能的系统编程语言".to_string(),
+            display_url: None,
+            site_name: None,
+            score: 0.0,
+            result_type: ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        };
+        standardize_item(&mut item);
+        assert_eq!(item_language(&item), Some("zh"));
+
+        item.title = "The Rust Programming Language".to_string();
+        item.content = "A language empowering everyone to build reliable and efficient software".to_string();
+        item.metadata.clear();
+        standardize_item(&mut item);
+        assert_eq!(item_language(&item), Some("en"));
+
+        // 引擎给出的语言优先
+        item.metadata.insert(LANGUAGE_METADATA_KEY.to_string(), "fr".to_string());
+        standardize_item(&mut item);
+        assert_eq!(item_language(&item), Some("fr"));
+
+        assert_eq!(primary_language("zh-CN"), "zh");
+        assert_eq!(primary_language("EN_us"), "en");
+    }
+
+    #[test]
+    fn test_parse_price() {
+        assert_eq!(parse_price("$1,299.00"), Some((1299.0, "USD".to_string())));
+        assert_eq!(parse_price("US $12.99 to US $20.00"), Some((12.99, "USD".to_string())));
+        assert_eq!(parse_price("EUR 12,50"), Some((12.5, "EUR".to_string())));
+        assert_eq!(parse_price("1.234,56 €"), Some((1234.56, "EUR".to_string())));
+        assert_eq!(parse_price("¥ 99 元"), Some((99.0, "CNY".to_string())));
+        assert_eq!(parse_price("C $5"), Some((5.0, "CAD".to_string())));
+        assert_eq!(parse_price("free shipping"), None);
+        assert_eq!(parse_price("42"), None);
+    }
+
+    fn priced_item(price: &str) -> SearchResultItem {
+        let mut item = SearchResultItem {
+            title: "item".to_string(),
+            url: "https://shop.example.com/1".to_string(),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score: 1.0,
+            result_type: crate::derive::ResultType::Shopping,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        };
+        item.metadata.insert(PRICE_METADATA_KEY.to_string(), price.to_string());
+        item
+    }
+
+    #[test]
+    fn test_normalize_price() {
+        let mut item = priced_item("£10");
+        normalize_price(&mut item, None);
+        assert_eq!(item.metadata[PRICE_METADATA_KEY], "10.00");
+        assert_eq!(item.metadata[CURRENCY_METADATA_KEY], "GBP");
+        assert_eq!(item.metadata[PRICE_RAW_METADATA_KEY], "£10");
+        assert_eq!(item_price(&item), Some(10.0));
+
+        // 再次标准化（带换算）不会丢失原文
+        let converter = CurrencyConverter::new("usd", HashMap::from([("gbp".to_string(), 1.25)]));
+        normalize_price(&mut item, Some(&converter));
+        assert_eq!(item.metadata[PRICE_METADATA_KEY], "12.50");
+        assert_eq!(item.metadata[CURRENCY_METADATA_KEY], "USD");
+        assert_eq!(item.metadata[PRICE_RAW_METADATA_KEY], "£10");
+    }
+
+    #[test]
+    fn test_normalize_price_without_rate_keeps_currency() {
+        let converter = CurrencyConverter::new("USD", HashMap::new());
+        let mut item = priced_item("€8,00");
+        normalize_price(&mut item, Some(&converter));
+        assert_eq!(item.metadata[PRICE_METADATA_KEY], "8.00");
+        assert_eq!(item.metadata[CURRENCY_METADATA_KEY], "EUR");
+    }
+
+    fn torrent(url: &str) -> SearchResultItem {
+        SearchResultItem {
+            title: "ubuntu-24.04-desktop-amd64.iso".to_string(),
+            url: url.to_string(),
+            content: String::new(),
+            display_url: None,
+            site_name: None,
+            score: 1.0,
+            result_type: ResultType::Web,
+            thumbnail: None,
+            published_date: None,
+            template: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_magnet() {
+        let mut item = torrent("magnet:?xt=urn:btih:3B245504CF5F11BBDBE1201CEA6A6BF45AEE1BC0&dn=ubuntu");
+        item.metadata.insert(SEEDERS_METADATA_KEY.to_string(), "1,234".to_string());
+        normalize_magnet(&mut item);
+
+        assert_eq!(item.metadata[INFOHASH_METADATA_KEY], "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0");
+        assert_eq!(item.metadata[SEEDERS_METADATA_KEY], "1234");
+        assert_eq!(item.result_type, ResultType::Torrent);
+        assert!(item.metadata[MAGNET_METADATA_KEY].starts_with("magnet:"));
+    }
+
+    #[test]
+    fn test_deduplicate_torrents_by_infohash() {
+        // 同一 infohash 的十六进制与 Base32 形式
+        let mut items = vec![
+            torrent("magnet:?xt=urn:btih:3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0&tr=udp%3A%2F%2Fa"),
+            torrent("magnet:?xt=urn:btih:HMSFKBGPL4I3XW7BEAOOU2TL6RNO4G6A&tr=udp%3A%2F%2Fb"),
+            torrent("https://example.com/page"),
+        ];
+        for item in &mut items {
+            normalize_magnet(item);
+        }
+        deduplicate_by_url(&mut items);
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn test_highlight_terms() {
+        assert_eq!(highlight_terms("\"Rust\" async -tokio site:github.com"), vec!["async", "rust"]);
+    }
+
+    #[test]
+    fn test_highlight_text() {
+        let markers = HighlightMarkers::default();
+        let terms = highlight_terms("rust async");
+        assert_eq!(
+            highlight_text("Async Rust <book>, trusty rustacean", &terms, &markers),
+            "<em>Async</em> <em>Rust</em> &lt;book&gt;, trusty <em>rust</em>acean"
+        );
+
+        let terms = highlight_terms("搜索");
+        let markers = HighlightMarkers::new("[", "]");
+        assert_eq!(highlight_text("元搜索引擎", &terms, &markers), "元[搜索]引擎");
+        assert_eq!(highlight_text("a < b", &[], &markers), "a < b");
     }
 }
@@ -0,0 +1,345 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 结果翻译
+//!
+//! 可选的后处理阶段：按请求的 `translate_to` 将结果标题与摘要翻译为目标语言。
+//! 翻译后端可插拔，内置 LibreTranslate HTTP API 与本地命令（如 argos-translate）两种
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::error::Error;
+use std::process::Stdio;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+
+use crate::derive::SearchResultItem;
+use crate::net::client::HttpClient;
+use crate::net::types::RequestOptions;
+
+/// 翻译后原标题保存的元数据键
+pub const ORIGINAL_TITLE_METADATA_KEY: &str = "original_title";
+
+/// 翻译后原摘要保存的元数据键
+pub const ORIGINAL_CONTENT_METADATA_KEY: &str = "original_content";
+
+/// 记录翻译源语言的元数据键
+pub const TRANSLATED_FROM_METADATA_KEY: &str = "translated_from";
+
+/// 翻译后端 trait
+#[async_trait]
+pub trait Translator: Send + Sync {
+    /// 后端名称
+    fn name(&self) -> &str;
+
+    /// 批量翻译
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - 待翻译文本
+    /// * `source` - 源语言，None 表示自动检测
+    /// * `target` - 目标语言代码
+    ///
+    /// # Returns
+    ///
+    /// 与输入一一对应的译文
+    async fn translate(
+        &self,
+        texts: &[String],
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<Vec<String>, Box<dyn Error + Send + Sync>>;
+}
+
+/// 翻译后端配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum TranslationBackend {
+    /// LibreTranslate HTTP API
+    LibreTranslate {
+        /// 服务地址，如 `http://localhost:5000`
+        url: String,
+        /// API key（公共实例需要）
+        #[serde(default)]
+        api_key: Option<String>,
+    },
+    /// 本地命令，从标准输入读取文本、向标准输出写出译文
+    ///
+    /// 参数中的 `{source}`、`{target}` 会被替换为语言代码
+    Command {
+        /// 可执行文件
+        program: String,
+        /// 参数
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+impl TranslationBackend {
+    /// 构建翻译后端
+    pub fn build(&self, client: Arc<HttpClient>) -> Arc<dyn Translator> {
+        match self {
+            Self::LibreTranslate { url, api_key } => {
+                Arc::new(LibreTranslateTranslator::new(client, url, api_key.clone()))
+            }
+            Self::Command { program, args } => Arc::new(CommandTranslator::new(program, args.clone())),
+        }
+    }
+}
+
+/// 翻译配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslationConfig {
+    /// 翻译后端
+    pub backend: TranslationBackend,
+    /// 每次搜索最多翻译的结果数（按排序靠前的优先）
+    #[serde(default = "default_max_items")]
+    pub max_items: usize,
+}
+
+fn default_max_items() -> usize {
+    20
+}
+
+impl TranslationConfig {
+    /// 使用指定后端创建配置
+    pub fn new(backend: TranslationBackend) -> Self {
+        Self {
+            backend,
+            max_items: default_max_items(),
+        }
+    }
+}
+
+/// LibreTranslate 后端
+pub struct LibreTranslateTranslator {
+    client: Arc<HttpClient>,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl LibreTranslateTranslator {
+    /// 创建 LibreTranslate 后端
+    pub fn new(client: Arc<HttpClient>, url: &str, api_key: Option<String>) -> Self {
+        Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            api_key,
+        }
+    }
+
+    /// 构建请求体
+    fn request_body(&self, texts: &[String], source: Option<&str>, target: &str) -> Value {
+        let mut body = json!({
+            "q": texts,
+            "source": source.unwrap_or("auto"),
+            "target": target,
+            "format": "text",
+        });
+        if let Some(ref key) = self.api_key {
+            body["api_key"] = json!(key);
+        }
+        body
+    }
+
+    /// 解析响应（批量请求时 `translatedText` 为数组）
+    fn parse_response(json_str: &str, expected: usize) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+        let response: Value = serde_json::from_str(json_str)?;
+        if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+            return Err(format!("LibreTranslate error: {}", error).into());
+        }
+
+        let translated: Vec<String> = match response.get("translatedText") {
+            Some(Value::Array(texts)) => texts.iter().map(|t| t.as_str().unwrap_or("").to_string()).collect(),
+            Some(Value::String(text)) => vec![text.clone()],
+            _ => return Err("Invalid LibreTranslate response: missing translatedText".into()),
+        };
+        if translated.len() != expected {
+            return Err(format!(
+                "LibreTranslate returned {} translations for {} texts",
+                translated.len(),
+                expected
+            )
+            .into());
+        }
+        Ok(translated)
+    }
+}
+
+#[async_trait]
+impl Translator for LibreTranslateTranslator {
+    fn name(&self) -> &str {
+        "libretranslate"
+    }
+
+    async fn translate(
+        &self,
+        texts: &[String],
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = format!("{}/translate", self.url);
+        let body = self.request_body(texts, source, target);
+        let response = self
+            .client
+            .post_json(&url, &body, Some(RequestOptions::default()))
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+        let text = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
+        Self::parse_response(&text, texts.len())
+    }
+}
+
+/// 本地命令后端
+///
+/// 每段文本单独调用一次命令，适合 argos-translate 等本地模型
+pub struct CommandTranslator {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandTranslator {
+    /// 创建本地命令后端
+    pub fn new(program: &str, args: Vec<String>) -> Self {
+        Self {
+            program: program.to_string(),
+            args,
+        }
+    }
+
+    /// 替换参数中的语言占位符
+    fn render_args(&self, source: Option<&str>, target: &str) -> Vec<String> {
+        self.args
+            .iter()
+            .map(|arg| arg.replace("{source}", source.unwrap_or("auto")).replace("{target}", target))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Translator for CommandTranslator {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    async fn translate(
+        &self,
+        texts: &[String],
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+        let args = self.render_args(source, target);
+        let mut translated = Vec::with_capacity(texts.len());
+
+        for text in texts {
+            let mut child = tokio::process::Command::new(&self.program)
+                .args(&args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| format!("Failed to run {}: {}", self.program, e))?;
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(text.as_bytes()).await?;
+            }
+
+            let output = child.wait_with_output().await?;
+            if !output.status.success() {
+                return Err(format!("{} exited with {}", self.program, output.status).into());
+            }
+            translated.push(String::from_utf8_lossy(&output.stdout).trim().to_string());
+        }
+
+        Ok(translated)
+    }
+}
+
+/// 将译文写回结果，原文保存到元数据
+///
+/// `translations` 依次为每个结果的标题与摘要
+pub fn apply_translations(items: &mut [&mut SearchResultItem], translations: Vec<String>, source: Option<&str>) {
+    let mut translations = translations.into_iter();
+    for item in items.iter_mut() {
+        let (Some(title), Some(content)) = (translations.next(), translations.next()) else {
+            break;
+        };
+        let original_title = std::mem::replace(&mut item.title, title);
+        let original_content = std::mem::replace(&mut item.content, content);
+        item.metadata.insert(ORIGINAL_TITLE_METADATA_KEY.to_string(), original_title);
+        item.metadata.insert(ORIGINAL_CONTENT_METADATA_KEY.to_string(), original_content);
+        if let Some(source) = source {
+            item.metadata.insert(TRANSLATED_FROM_METADATA_KEY.to_string(), source.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::types::NetworkConfig;
+
+    #[test]
+    fn test_backend_config_deserialize() {
+        let toml_str = r#"
+            max_items = 5
+            [backend]
+            backend = "libre_translate"
+            url = "http://localhost:5000/"
+        "#;
+        let config: TranslationConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.max_items, 5);
+        assert_eq!(
+            config.backend,
+            TranslationBackend::LibreTranslate { url: "http://localhost:5000/".to_string(), api_key: None }
+        );
+    }
+
+    #[test]
+    fn test_libretranslate_request_and_response() {
+        let client = Arc::new(HttpClient::new(NetworkConfig::default()).unwrap());
+        let translator = LibreTranslateTranslator::new(client, "http://localhost:5000/", Some("key".to_string()));
+        let body = translator.request_body(&["Hallo".to_string()], None, "en");
+        assert_eq!(body["source"], "auto");
+        assert_eq!(body["api_key"], "key");
+        assert_eq!(translator.url, "http://localhost:5000");
+
+        let translated = LibreTranslateTranslator::parse_response(r#"{"translatedText":["Hello","World"]}"#, 2).unwrap();
+        assert_eq!(translated, vec!["Hello", "World"]);
+        assert!(LibreTranslateTranslator::parse_response(r#"{"translatedText":["Hello"]}"#, 2).is_err());
+        assert!(LibreTranslateTranslator::parse_response(r#"{"error":"Invalid API key"}"#, 1).is_err());
+    }
+
+    #[test]
+    fn test_command_args() {
+        let translator = CommandTranslator::new(
+            "argos-translate",
+            vec!["--from".to_string(), "{source}".to_string(), "--to".to_string(), "{target}".to_string()],
+        );
+        assert_eq!(translator.render_args(Some("de"), "en"), vec!["--from", "de", "--to", "en"]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_translator_runs_program() {
+        // `cat` 原样输出，验证标准输入/输出管道
+        let translator = CommandTranslator::new("cat", Vec::new());
+        let translated = translator.translate(&["Bonjour".to_string()], Some("fr"), "en").await.unwrap();
+        assert_eq!(translated, vec!["Bonjour"]);
+    }
+}
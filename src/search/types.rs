@@ -16,8 +16,22 @@
 //!
 //! 定义搜索模块使用的核心类型和数据结构
 
+use super::aggregator::AggregationStrategy;
+use super::scoring::ScoringProfile;
+use super::answers::InstantAnswer;
+use super::chinese::ChineseMatching;
+use super::intent::{default_intent_rules, IntentRule};
+use super::translation::TranslationConfig;
+use crate::config::search::{EnrichmentConfig, SearchSessionConfig};
+use super::engine_stats::AdaptiveTimeoutConfig;
+use super::engine_manager::DEFAULT_SELECTOR_ROT_THRESHOLD;
+use super::standardization::{CurrencyConverter, HighlightMarkers};
+use crate::config::engines::{CategoryConfig, EngineDependencies, EngineGeoConfig, EnginePolitenessConfig, EngineSessionConfig, EngineSpecificConfig, EnginesConfig, ResponseConfig, LanguageFilterConfig, ResultFilteringConfig, SpamFilterConfig};
+use crate::net::client::body::ResponseLimits;
 use crate::derive::{SearchQuery, SearchResult};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// 搜索请求
@@ -35,6 +49,25 @@ pub struct SearchRequest {
     pub force: bool,
     /// 缓存刷新时间线（秒），超过此时间强制刷新
     pub cache_timeline: Option<u64>,
+    /// 将结果标题与摘要翻译为该语言（需配置翻译后端）
+    #[serde(default)]
+    pub translate_to: Option<String>,
+    /// 用高亮标记包裹摘要中的查询词
+    #[serde(default)]
+    pub highlight: bool,
+    /// 记录各引擎的原始响应（截断）、解析结果数与解析警告，写入结果元数据
+    /// （配置了 `debug_capture_dir` 时同时保存到磁盘）
+    #[serde(default)]
+    pub debug_capture: bool,
+    /// 延迟预算（毫秒）：到期时不再等待未完成的引擎，直接返回已到达的结果并标记 `partial`
+    #[serde(default)]
+    pub max_latency_ms: Option<u64>,
+    /// 在结果元数据中附加评分解释（BM25 分项、引擎权威度、时效性与去重合并），用于排查排序
+    #[serde(default)]
+    pub explain: bool,
+    /// 搜索会话令牌：相同令牌的查询共享上下文（之前查询的词参与重排，之前的查询作为相关查询返回）
+    #[serde(default)]
+    pub session: Option<String>,
 }
 
 impl Default for SearchRequest {
@@ -46,6 +79,12 @@ impl Default for SearchRequest {
             max_results: Some(100),
             force: false,
             cache_timeline: Some(3600), // 默认1小时刷新
+            translate_to: None,
+            highlight: false,
+            debug_capture: false,
+            max_latency_ms: None,
+            explain: false,
+            session: None,
         }
     }
 }
@@ -65,6 +104,59 @@ pub struct SearchResponse {
     pub query: SearchQuery,
     /// 是否从缓存获取
     pub cached: bool,
+    /// 即时答案（计算、换算、释义等）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<InstantAnswer>,
+    /// 拼写纠正建议（"您是不是要找"），仅在结果较少时给出
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+    /// 各引擎的执行明细（耗时、结果数、错误等），用于定位拖慢查询的引擎
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub per_engine: Vec<EngineTiming>,
+    /// 延迟预算到期时仍有引擎未完成，结果只包含已到达的部分
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+    /// 搜索会话中之前的查询（最近的在前，请求携带会话令牌时给出）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_queries: Vec<String>,
+    /// 请求 ID（由 API、CLI 或 Python 入口生成，用于关联日志）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// 单个引擎在一次搜索中的执行明细
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineTiming {
+    /// 引擎名称
+    pub engine: String,
+    /// 耗时（毫秒，不含并发排队时间）
+    pub elapsed_ms: u64,
+    /// 返回的结果数
+    pub result_count: usize,
+    /// 是否完全由页面缓存提供
+    pub cache_hit: bool,
+    /// 错误信息（失败、超时或被跳过时）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 重复发送相同请求的次数
+    pub retries: u32,
+    /// 请求是否被目标站点拒绝（HTTP 403/429）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub blocked: bool,
+    /// 实际使用的镜像源（使用主服务源时为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+}
+
+impl EngineTiming {
+    /// 未执行的引擎（被临时禁用、配额用尽或创建失败）
+    pub fn skipped(engine: &str, reason: impl Into<String>) -> Self {
+        Self {
+            engine: engine.to_string(),
+            error: Some(reason.into()),
+            ..Default::default()
+        }
+    }
 }
 
 /// 搜索配置
@@ -76,6 +168,107 @@ pub struct SearchConfig {
     pub enable_cache: bool,
     /// 最大并发引擎数
     pub max_concurrent_engines: usize,
+    /// 在发送到引擎前清洗查询中的敏感信息（邮箱、电话等）
+    pub scrub_query_pii: bool,
+    /// 用户自定义的敏感信息正则表达式
+    pub query_scrub_patterns: Vec<String>,
+    /// 发送到引擎前把常见缩写扩展为同义词（内置 `SynonymRewriter`）
+    pub expand_synonyms: bool,
+    /// 发送到引擎前去掉较长查询中的停用词（内置 `StopWordRewriter`）
+    pub trim_stop_words: bool,
+    /// 按引擎历史质量自适应调整权重（替代静态权威度表）
+    pub adaptive_engine_weighting: bool,
+    /// 按查询语言优先使用的引擎（语言代码 -> 引擎列表）
+    pub language_engine_priority: HashMap<String, Vec<String>>,
+    /// 根据查询意图选择引擎分类（未指定引擎时生效）
+    pub enable_intent_routing: bool,
+    /// 意图分类规则
+    pub intent_rules: Vec<IntentRule>,
+    /// 引擎特定配置（引擎名 -> 令牌、自定义参数等）
+    pub engine_settings: HashMap<String, EngineSpecificConfig>,
+    /// 价格换算（设置后购物结果的价格统一换算为目标货币，同时用于货币换算即时答案）
+    pub currency_conversion: Option<CurrencyConverter>,
+    /// 启用即时答案
+    pub enable_instant_answers: bool,
+    /// 开放文件/种子分类引擎（对应配置 `engines.enable_files_category`）
+    pub enable_files_category: bool,
+    /// 结果翻译后端（未配置时忽略请求中的 `translate_to`）
+    pub translation: Option<TranslationConfig>,
+    /// 结果页面元数据补全（None 时不获取结果页面）
+    pub enrichment: Option<EnrichmentConfig>,
+    /// 启用拼写纠错（词典由缓存结果标题与搜索结果构建）
+    pub enable_spell_correction: bool,
+    /// 结果数少于该值时给出拼写纠正建议
+    pub spell_correction_threshold: usize,
+    /// 中文繁简统一与拼音匹配（查询解析与缓存全文搜索）
+    pub chinese_matching: ChineseMatching,
+    /// 摘要高亮标记（请求 `highlight` 时使用）
+    pub highlight_markers: HighlightMarkers,
+    /// 合并相同的并发查询（只向引擎发起一次请求）
+    pub coalesce_requests: bool,
+    /// 各引擎的并发请求上限（未列出的引擎只受 `max_concurrent_engines` 约束）
+    pub engine_concurrency: HashMap<String, usize>,
+    /// 按历史延迟分位数自适应调整各引擎超时（None 时统一使用 `default_timeout`）
+    pub adaptive_timeout: Option<AdaptiveTimeoutConfig>,
+    /// 未单独配置的引擎使用的响应限制（大小、Content-Type 与解压比例）
+    pub response_limits: ResponseLimits,
+    /// 各引擎的响应限制（未列出的引擎使用 `response_limits`）
+    pub engine_response_limits: HashMap<String, ResponseLimits>,
+    /// 每个引擎的默认每日下载流量配额（字节，None 表示不限制）
+    pub daily_bandwidth_quota: Option<u64>,
+    /// 各引擎的每日下载流量配额（字节，覆盖 `daily_bandwidth_quota`）
+    pub engine_bandwidth_quotas: HashMap<String, u64>,
+    /// 礼貌访问（None 时不限速）
+    pub politeness: Option<crate::config::PolitenessConfig>,
+    /// 各引擎的礼貌访问覆盖
+    pub engine_politeness: HashMap<String, EnginePolitenessConfig>,
+    /// 各引擎的地域配置（服务地区与地区镜像，只含已配置的引擎）
+    pub engine_geo: HashMap<String, EngineGeoConfig>,
+    /// 各引擎的会话保持配置（只含已启用的引擎）
+    pub engine_sessions: HashMap<String, EngineSessionConfig>,
+    /// 各引擎的依赖（启动时检查，不满足的引擎标记为不可用）
+    pub engine_dependencies: HashMap<String, EngineDependencies>,
+    /// 搜索分类（默认引擎与权重，只含已启用的分类，对应配置 `engines.categories`）
+    pub categories: HashMap<String, CategoryConfig>,
+    /// 搜索结果归档（None 时不归档）
+    pub archive: Option<crate::config::ArchiveConfig>,
+    /// 事件 Webhook（None 时不投递）
+    pub webhooks: Option<crate::config::WebhookConfig>,
+    /// 调试捕获文件的保存目录（None 时只写入结果元数据）
+    pub debug_capture_dir: Option<PathBuf>,
+    /// 抓取引擎连续多少个不同查询返回零结果（HTTP 200）时标记为降级（0 表示不检测）
+    pub selector_rot_threshold: u32,
+    /// 负面结果（零结果、验证码、HTTP 403/429）的缓存时长，期间相同查询跳过该引擎（None 时不缓存，需启用缓存）
+    pub negative_cache_ttl: Option<Duration>,
+    /// 结果过滤（域名黑白名单、远程域名列表与按分类覆盖，对应配置 `engines.result_filtering`）
+    pub result_filtering: ResultFilteringConfig,
+    /// 垃圾/内容农场结果过滤（对应配置 `engines.spam_filter`）
+    pub spam_filter: SpamFilterConfig,
+    /// 按请求语言提升或移除结果（对应配置 `engines.language_filter`）
+    pub language_filter: LanguageFilterConfig,
+    /// 结果聚合策略（对应配置 `search.aggregation.strategy`）
+    pub aggregation: AggregationStrategy,
+    /// 结果评分参数（对应配置 `search.scoring`，可通过 `SearchInterface::set_scoring_profile` 在运行时替换）
+    pub scoring: ScoringProfile,
+    /// 搜索会话（None 时忽略请求中的会话令牌，需启用缓存）
+    pub sessions: Option<SearchSessionConfig>,
+    /// WASM 插件引擎的发现路径（对应配置 `engines.discovery.discovery_paths`，需启用 `wasm-plugins` 特性）
+    pub plugin_paths: Vec<PathBuf>,
+}
+
+/// 默认的按语言引擎优先级
+fn default_language_engine_priority() -> HashMap<String, Vec<String>> {
+    let mut priority = HashMap::new();
+    priority.insert(
+        "zh".to_string(),
+        vec![
+            "baidu".to_string(),
+            "sogou".to_string(),
+            "sogou_wechat".to_string(),
+            "bilibili".to_string(),
+        ],
+    );
+    priority
 }
 
 impl Default for SearchConfig {
@@ -84,10 +277,151 @@ impl Default for SearchConfig {
             default_timeout: Duration::from_secs(60),  // 增加到60秒
             enable_cache: true,
             max_concurrent_engines: 20,          // 拉满并发数
+            scrub_query_pii: false,
+            query_scrub_patterns: Vec::new(),
+            expand_synonyms: false,
+            trim_stop_words: false,
+            adaptive_engine_weighting: false,
+            language_engine_priority: default_language_engine_priority(),
+            enable_intent_routing: false,
+            intent_rules: default_intent_rules(),
+            engine_settings: HashMap::new(),
+            currency_conversion: None,
+            enable_instant_answers: true,
+            enable_files_category: false,
+            translation: None,
+            enrichment: None,
+            enable_spell_correction: true,
+            spell_correction_threshold: 5,
+            chinese_matching: ChineseMatching::default(),
+            highlight_markers: HighlightMarkers::default(),
+            coalesce_requests: true,
+            engine_concurrency: HashMap::new(),
+            adaptive_timeout: None,
+            response_limits: ResponseLimits::from(&ResponseConfig::default()),
+            engine_response_limits: HashMap::new(),
+            daily_bandwidth_quota: None,
+            engine_bandwidth_quotas: HashMap::new(),
+            politeness: None,
+            engine_politeness: HashMap::new(),
+            engine_geo: HashMap::new(),
+            engine_sessions: HashMap::new(),
+            engine_dependencies: HashMap::new(),
+            categories: HashMap::new(),
+            archive: None,
+            webhooks: None,
+            debug_capture_dir: None,
+            selector_rot_threshold: DEFAULT_SELECTOR_ROT_THRESHOLD,
+            negative_cache_ttl: Some(Duration::from_secs(300)),
+            result_filtering: ResultFilteringConfig::default(),
+            spam_filter: SpamFilterConfig::default(),
+            language_filter: LanguageFilterConfig::default(),
+            aggregation: AggregationStrategy::Merged,
+            scoring: ScoringProfile::default(),
+            sessions: Some(SearchSessionConfig::default()),
+            plugin_paths: Vec::new(),
         }
     }
 }
 
+impl SearchConfig {
+    /// 应用配置文件中的引擎配置
+    ///
+    /// 复制文件分类开关、结果过滤配置、插件发现路径与各引擎的特定配置（令牌、端点、地域镜像等）
+    ///
+    /// # Arguments
+    ///
+    /// * `engines` - 配置文件中的 `engines` 段
+    ///
+    /// # Returns
+    ///
+    /// 返回更新后的搜索配置
+    pub fn with_engines_config(mut self, engines: &EnginesConfig) -> Self {
+        self.enable_files_category = engines.enable_files_category;
+        self.categories = engines
+            .categories
+            .iter()
+            .filter(|(_, category)| category.enabled)
+            .map(|(name, category)| (name.clone(), category.clone()))
+            .collect();
+        self.result_filtering = engines.result_filtering.clone();
+        self.spam_filter = engines.spam_filter.clone();
+        self.language_filter = engines.language_filter.clone();
+        if engines.discovery.enabled {
+            self.plugin_paths = engines.discovery.discovery_paths.iter().map(PathBuf::from).collect();
+        }
+        for (name, engine) in &engines.engines {
+            self.engine_settings.insert(name.clone(), engine.specific.clone());
+            self.engine_dependencies.insert(name.clone(), engine.dependencies.clone());
+            self.engine_concurrency
+                .insert(name.clone(), engine.performance.concurrency.max_concurrent_requests);
+            self.engine_response_limits
+                .insert(name.clone(), ResponseLimits::from(&engine.network.response));
+            self.engine_politeness
+                .insert(name.clone(), engine.network.politeness.clone());
+            if engine.network.geo.is_configured() {
+                self.engine_geo.insert(name.clone(), engine.network.geo.clone());
+            }
+            if engine.network.session.enabled {
+                self.engine_sessions.insert(name.clone(), engine.network.session.clone());
+            }
+            if let Some(quota) = engine.performance.daily_bandwidth_quota_bytes {
+                self.engine_bandwidth_quotas.insert(name.clone(), quota);
+            }
+        }
+        self
+    }
+
+    /// 从应用主配置构建搜索配置
+    ///
+    /// 映射超时、并发、结果缓存、查询处理开关与 `engines` 段，其余字段保持默认
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - 加载后的 SeeSea 主配置
+    ///
+    /// # Returns
+    ///
+    /// 返回搜索配置
+    pub fn from_app_config(config: &crate::config::SeeSeaConfig) -> Self {
+        let processing = &config.search.query_processing;
+        Self {
+            default_timeout: Duration::from_secs(config.search.search_timeout),
+            enable_cache: config.cache.enable_result_cache,
+            max_concurrent_engines: config.search.max_concurrent_engines,
+            scrub_query_pii: processing.enable_pii_scrubbing,
+            query_scrub_patterns: processing.pii_patterns.clone(),
+            expand_synonyms: processing.enable_synonyms,
+            trim_stop_words: processing.enable_stop_words,
+            enable_spell_correction: processing.enable_correction,
+            chinese_matching: ChineseMatching {
+                normalize_script: processing.enable_script_normalization,
+                pinyin: processing.enable_pinyin_matching,
+            },
+            daily_bandwidth_quota: config.search.daily_bandwidth_quota_bytes,
+            debug_capture_dir: config.search.debug_capture_dir.as_ref().map(PathBuf::from),
+            selector_rot_threshold: config.search.selector_rot_threshold,
+            negative_cache_ttl: match config.search.negative_cache_ttl_secs {
+                0 => None,
+                secs => Some(Duration::from_secs(secs)),
+            },
+            politeness: config.politeness.enabled.then(|| config.politeness.clone()),
+            archive: config.archive.enabled.then(|| config.archive.clone()),
+            enrichment: config.search.enrichment.enabled.then(|| config.search.enrichment.clone()),
+            webhooks: config
+                .integrations
+                .webhooks
+                .enabled
+                .then(|| config.integrations.webhooks.clone()),
+            aggregation: AggregationStrategy::from_config(&config.search.aggregation),
+            scoring: config.search.scoring.clone(),
+            sessions: config.search.sessions.enabled.then(|| config.search.sessions.clone()),
+            ..Self::default()
+        }
+        .with_engines_config(&config.engines)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,6 +438,37 @@ mod tests {
         let config = SearchConfig::default();
         assert_eq!(config.default_timeout, Duration::from_secs(60));
         assert!(config.enable_cache);
+        assert!(!config.scrub_query_pii);
+        assert!(!config.expand_synonyms && !config.trim_stop_words);
+        assert_eq!(config.language_engine_priority["zh"][0], "baidu");
+        assert!(!config.enable_intent_routing);
+        assert!(!config.intent_rules.is_empty());
+        assert!(config.enable_instant_answers);
+        assert!(!config.enable_files_category);
+        assert!(config.plugin_paths.is_empty());
+    }
+
+    #[test]
+    fn test_search_config_from_app_config() {
+        let mut app_config = crate::config::SeeSeaConfig::default();
+        app_config.search.search_timeout = 15;
+        app_config.search.max_concurrent_engines = 3;
+        app_config.cache.enable_result_cache = false;
+        app_config.engines.enable_files_category = true;
+        app_config.search.aggregation.strategy = crate::config::search::AggregationMode::RoundRobin;
+
+        let config = SearchConfig::from_app_config(&app_config);
+        assert_eq!(config.default_timeout, Duration::from_secs(15));
+        assert_eq!(config.max_concurrent_engines, 3);
+        assert!(!config.enable_cache);
+        assert!(config.enable_files_category);
+        assert_eq!(config.language_engine_priority["zh"][0], "baidu");
+        assert_eq!(config.aggregation, AggregationStrategy::RoundRobin);
+        assert_eq!(config.plugin_paths, vec![PathBuf::from("./engines")]);
+
+        // 关闭引擎发现时不加载插件
+        app_config.engines.discovery.enabled = false;
+        assert!(SearchConfig::from_app_config(&app_config).plugin_paths.is_empty());
     }
 
     #[test]
@@ -115,6 +480,12 @@ mod tests {
             query_time_ms: 100,
             query: SearchQuery::default(),
             cached: false,
+            answer: None,
+            suggestions: Vec::new(),
+            per_engine: Vec::new(),
+            partial: false,
+            related_queries: Vec::new(),
+            request_id: None,
         };
         assert_eq!(response.engines_used.len(), 1);
     }
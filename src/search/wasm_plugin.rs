@@ -0,0 +1,763 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WASM 插件引擎
+//!
+//! 从引擎发现路径加载编译为 WebAssembly 的第三方引擎。插件只负责构造请求与解析响应，
+//! 网络请求由宿主通过引擎的 HTTP 客户端发出（代理/Tor、隐私请求头、礼貌访问与响应限制照常生效）。
+//!
+//! 插件约定（数据均为客户内存中的 UTF-8 JSON）：
+//! - `memory`：导出的线性内存
+//! - `alloc(len: i32) -> i32`：分配宿主写入输入的缓冲区
+//! - `prepare_request(ptr: i32, len: i32) -> i64`：输入查询，输出 `{ "method", "url", "headers", "body" }`
+//! - `parse_response(ptr: i32, len: i32) -> i64`：输入 `{ "status", "headers", "body" }`，输出 `{ "results": [...] }`
+//!
+//! 输出以 `(ptr << 32) | len` 返回。插件不能有任何导入，每次调用受燃料与内存上限约束
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wasmtime::{Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc};
+
+use crate::derive::types::AboutInfo;
+use crate::derive::{
+    EngineCapabilities, EngineInfo, EngineStatus, EngineType, ResultType, SearchEngine, SearchQuery,
+    SearchResult, SearchResultItem, TimeRange,
+};
+use crate::net::client::HttpClient;
+use crate::net::types::RequestOptions;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// 每次调用的默认燃料（约等于执行的指令数）
+pub const DEFAULT_PLUGIN_FUEL: u64 = 500_000_000;
+
+/// 插件线性内存的默认上限（字节）
+pub const DEFAULT_PLUGIN_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+/// 插件执行限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginLimits {
+    /// 每次调用的燃料，耗尽时调用失败
+    pub fuel: u64,
+    /// 线性内存上限（字节）
+    pub max_memory_bytes: usize,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        Self {
+            fuel: DEFAULT_PLUGIN_FUEL,
+            max_memory_bytes: DEFAULT_PLUGIN_MEMORY_BYTES,
+        }
+    }
+}
+
+/// 插件清单（与模块同名的 `<name>.json`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// 引擎名称（搜索请求的 `engines` 参数中使用）
+    pub name: String,
+    /// 引擎类型
+    #[serde(default)]
+    pub engine_type: EngineType,
+    /// 引擎描述
+    #[serde(default)]
+    pub description: String,
+    /// 分类列表
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// 能力声明
+    #[serde(default)]
+    pub capabilities: PluginCapabilities,
+}
+
+/// 插件能力声明（键与 Python `register_engine` 的 `capabilities` 相同）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PluginCapabilities {
+    /// 支持分页
+    pub pagination: bool,
+    /// 每页最大结果数
+    pub max_page_size: usize,
+    /// 最大页码
+    pub max_page: usize,
+    /// 每分钟请求上限
+    pub rate_limit: Option<usize>,
+    /// 支持时间范围
+    pub time_range: bool,
+    /// 支持地区过滤
+    pub region: bool,
+    /// 支持安全搜索
+    pub safe_search: bool,
+    /// 请求超时（秒）
+    pub timeout: Option<u64>,
+}
+
+impl Default for PluginCapabilities {
+    fn default() -> Self {
+        Self {
+            pagination: true,
+            max_page_size: 50,
+            max_page: 50,
+            rate_limit: None,
+            time_range: false,
+            region: false,
+            safe_search: false,
+            timeout: Some(30),
+        }
+    }
+}
+
+impl PluginManifest {
+    /// 转换为引擎信息
+    fn engine_info(&self) -> EngineInfo {
+        let caps = &self.capabilities;
+        EngineInfo {
+            name: self.name.clone(),
+            engine_type: self.engine_type,
+            description: self.description.clone(),
+            status: EngineStatus::Active,
+            categories: self.categories.clone(),
+            capabilities: EngineCapabilities {
+                result_types: vec![ResultType::default()],
+                supported_params: vec![],
+                max_page_size: caps.max_page_size,
+                supports_pagination: caps.pagination,
+                supports_time_range: caps.time_range,
+                supports_language_filter: false,
+                supports_region_filter: caps.region,
+                supports_safe_search: caps.safe_search,
+                rate_limit: caps.rate_limit.filter(|limit| *limit > 0),
+            },
+            about: AboutInfo::default(),
+            shortcut: None,
+            timeout: caps.timeout,
+            disabled: false,
+            inactive: false,
+            version: None,
+            last_checked: None,
+            using_tor_proxy: false,
+            display_error_messages: true,
+            tokens: Vec::new(),
+            max_page: caps.max_page,
+        }
+    }
+}
+
+/// 传给 `prepare_request` 的查询
+#[derive(Debug, Serialize)]
+struct PluginQuery<'a> {
+    query: &'a str,
+    page: usize,
+    page_size: usize,
+    language: Option<&'a str>,
+    region: Option<&'a str>,
+    time_range: Option<TimeRange>,
+}
+
+/// `prepare_request` 返回的请求
+#[derive(Debug, Deserialize)]
+struct PluginRequest {
+    #[serde(default = "default_method")]
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// 传给 `parse_response` 的响应
+#[derive(Debug, Serialize)]
+struct PluginResponse {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+/// `parse_response` 返回的结果列表
+#[derive(Debug, Deserialize)]
+struct PluginResults {
+    results: Vec<PluginResult>,
+}
+
+/// 插件返回的单条结果
+#[derive(Debug, Deserialize)]
+struct PluginResult {
+    title: String,
+    url: String,
+    content: String,
+    score: f64,
+    #[serde(default)]
+    display_url: Option<String>,
+    #[serde(default)]
+    site_name: Option<String>,
+    #[serde(default)]
+    result_type: ResultType,
+    #[serde(default)]
+    thumbnail: Option<String>,
+    #[serde(default)]
+    published_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+impl From<PluginResult> for SearchResultItem {
+    fn from(result: PluginResult) -> Self {
+        Self {
+            title: result.title,
+            url: result.url,
+            content: result.content,
+            display_url: result.display_url,
+            site_name: result.site_name,
+            score: result.score,
+            result_type: result.result_type,
+            thumbnail: result.thumbnail,
+            published_date: result.published_date,
+            template: None,
+            metadata: result.metadata,
+        }
+    }
+}
+
+/// 一个插件实例（每次搜索新建，燃料在每次调用前重置）
+struct GuestInstance {
+    store: Store<StoreLimits>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    prepare_request: TypedFunc<(i32, i32), i64>,
+    parse_response: TypedFunc<(i32, i32), i64>,
+    fuel: u64,
+}
+
+impl GuestInstance {
+    /// 实例化模块并检查导出
+    fn new(engine: &Engine, module: &Module, limits: PluginLimits) -> Result<Self> {
+        if let Some(import) = module.imports().next() {
+            return Err(format!("imports are not allowed ({}::{})", import.module(), import.name()).into());
+        }
+
+        let store_limits = StoreLimitsBuilder::new()
+            .memory_size(limits.max_memory_bytes)
+            .instances(1)
+            .build();
+        let mut store = Store::new(engine, store_limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(limits.fuel)?;
+
+        let instance: Instance = Linker::new(engine).instantiate(&mut store, module)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or("missing exported memory")?;
+        Ok(Self {
+            alloc: instance.get_typed_func(&mut store, "alloc")?,
+            prepare_request: instance.get_typed_func(&mut store, "prepare_request")?,
+            parse_response: instance.get_typed_func(&mut store, "parse_response")?,
+            memory,
+            store,
+            fuel: limits.fuel,
+        })
+    }
+
+    /// 把输入写入客户内存，调用导出函数并读出输出
+    fn call(&mut self, func: TypedFunc<(i32, i32), i64>, input: &[u8]) -> Result<Vec<u8>> {
+        self.store.set_fuel(self.fuel)?;
+        let len = i32::try_from(input.len())?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        self.memory.write(&mut self.store, ptr as u32 as usize, input)?;
+
+        let packed = func.call(&mut self.store, (ptr, len))? as u64;
+        let start = (packed >> 32) as usize;
+        let end = start + (packed & 0xffff_ffff) as usize;
+        let output = self
+            .memory
+            .data(&self.store)
+            .get(start..end)
+            .ok_or("output is outside guest memory")?;
+        Ok(output.to_vec())
+    }
+
+    fn prepare_request(&mut self, query: &PluginQuery) -> Result<PluginRequest> {
+        let output = self.call(self.prepare_request.clone(), &serde_json::to_vec(query)?)?;
+        Ok(serde_json::from_slice(&output)?)
+    }
+
+    fn parse_response(&mut self, response: &PluginResponse) -> Result<Vec<SearchResultItem>> {
+        let output = self.call(self.parse_response.clone(), &serde_json::to_vec(response)?)?;
+        let results: PluginResults = serde_json::from_slice(&output)?;
+        Ok(results.results.into_iter().map(SearchResultItem::from).collect())
+    }
+}
+
+/// 已加载的插件
+struct LoadedPlugin {
+    module: Module,
+    info: EngineInfo,
+    path: PathBuf,
+}
+
+/// WASM 插件宿主
+///
+/// 持有编译后的插件模块，按名称为搜索创建引擎实例
+pub struct WasmPluginHost {
+    engine: Engine,
+    plugins: HashMap<String, Arc<LoadedPlugin>>,
+    limits: PluginLimits,
+}
+
+impl std::fmt::Debug for WasmPluginHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmPluginHost")
+            .field("plugins", &self.names())
+            .field("limits", &self.limits)
+            .finish()
+    }
+}
+
+impl WasmPluginHost {
+    /// 创建空的插件宿主
+    ///
+    /// # Returns
+    ///
+    /// 成功返回宿主，WASM 运行时初始化失败返回错误
+    pub fn new() -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        Ok(Self {
+            engine: Engine::new(&config)?,
+            plugins: HashMap::new(),
+            limits: PluginLimits::default(),
+        })
+    }
+
+    /// 设置执行限制（对之后创建的引擎实例生效）
+    pub fn with_limits(mut self, limits: PluginLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 从发现路径加载插件
+    ///
+    /// 不存在的目录被忽略；缺少清单、无法编译或不符合插件约定的模块记录警告后跳过，
+    /// 与已加载插件同名的模块同样跳过
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - 发现路径（对应配置 `engines.discovery.discovery_paths`）
+    ///
+    /// # Returns
+    ///
+    /// 本次加载的插件名称
+    pub fn load_dirs<P: AsRef<Path>>(&mut self, paths: &[P]) -> Vec<String> {
+        let mut loaded = Vec::new();
+        for dir in paths {
+            let Ok(entries) = std::fs::read_dir(dir.as_ref()) else {
+                continue;
+            };
+            let mut modules: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+                .collect();
+            modules.sort();
+            for path in modules {
+                match self.load_plugin(&path) {
+                    Ok(name) => loaded.push(name),
+                    Err(e) => tracing::warn!("Skipping WASM plugin {}: {}", path.display(), e),
+                }
+            }
+        }
+        loaded
+    }
+
+    /// 加载单个插件模块（清单为同目录下同名的 `.json` 文件）
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - `.wasm` 模块路径
+    ///
+    /// # Returns
+    ///
+    /// 成功返回插件名称
+    pub fn load_plugin(&mut self, path: &Path) -> Result<String> {
+        let manifest_path = path.with_extension("json");
+        let manifest: PluginManifest = serde_json::from_slice(&std::fs::read(&manifest_path).map_err(|e| {
+            format!("cannot read manifest {}: {}", manifest_path.display(), e)
+        })?)?;
+        if manifest.name.trim().is_empty() {
+            return Err("manifest name is empty".into());
+        }
+        if let Some(existing) = self.plugins.get(&manifest.name) {
+            return Err(format!(
+                "plugin '{}' is already loaded from {}",
+                manifest.name,
+                existing.path.display()
+            )
+            .into());
+        }
+
+        let module = Module::from_file(&self.engine, path)?;
+        GuestInstance::new(&self.engine, &module, self.limits)?;
+
+        let name = manifest.name.clone();
+        self.plugins.insert(
+            name.clone(),
+            Arc::new(LoadedPlugin {
+                module,
+                info: manifest.engine_info(),
+                path: path.to_path_buf(),
+            }),
+        );
+        tracing::info!("Loaded WASM plugin {} from {}", name, path.display());
+        Ok(name)
+    }
+
+    /// 已加载的插件名称（按名称排序）
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.plugins.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// 是否加载了指定名称的插件
+    pub fn contains(&self, name: &str) -> bool {
+        self.plugins.contains_key(name)
+    }
+
+    /// 创建插件引擎
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 插件名称
+    /// * `client` - 发送插件请求的 HTTP 客户端（应为该引擎配置的客户端）
+    ///
+    /// # Returns
+    ///
+    /// 未加载该插件时返回 None
+    pub fn create_engine(&self, name: &str, client: Arc<HttpClient>) -> Option<WasmPluginEngine> {
+        let plugin = self.plugins.get(name)?;
+        Some(WasmPluginEngine {
+            engine: self.engine.clone(),
+            plugin: Arc::clone(plugin),
+            limits: self.limits,
+            client,
+        })
+    }
+}
+
+/// 由 WASM 插件实现的搜索引擎
+pub struct WasmPluginEngine {
+    engine: Engine,
+    plugin: Arc<LoadedPlugin>,
+    limits: PluginLimits,
+    client: Arc<HttpClient>,
+}
+
+impl WasmPluginEngine {
+    /// 发送插件构造的请求
+    async fn fetch(&self, request: PluginRequest) -> Result<PluginResponse> {
+        let url = url::Url::parse(&request.url)?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("unsupported URL scheme: {}", url.scheme()).into());
+        }
+
+        let mut options = RequestOptions::default();
+        if let Some(timeout) = self.plugin.info.timeout {
+            options.timeout = Duration::from_secs(timeout);
+        }
+        options.headers.extend(request.headers);
+
+        let response = match request.method.to_ascii_uppercase().as_str() {
+            "GET" => self.client.get(url.as_str(), Some(options)).await,
+            "POST" => {
+                let body = request.body.unwrap_or_default().into_bytes();
+                self.client.post(url.as_str(), body, Some(options)).await
+            }
+            method => return Err(format!("unsupported HTTP method: {}", method).into()),
+        }
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = self.client.read_text(response).await.map_err(|e| e.to_string())?;
+        Ok(PluginResponse { status, headers, body })
+    }
+}
+
+#[async_trait]
+impl SearchEngine for WasmPluginEngine {
+    fn info(&self) -> &EngineInfo {
+        &self.plugin.info
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult> {
+        self.validate_query(query)?;
+        let start_time = Instant::now();
+        let name = &self.plugin.info.name;
+
+        let mut guest = GuestInstance::new(&self.engine, &self.plugin.module, self.limits)
+            .map_err(|e| format!("WASM plugin '{}' failed to start: {}", name, e))?;
+        let request = guest
+            .prepare_request(&PluginQuery {
+                query: &query.query,
+                page: query.page,
+                page_size: query.page_size.min(self.plugin.info.capabilities.max_page_size),
+                language: query.language.as_deref(),
+                region: query.region.as_deref(),
+                time_range: query.time_range,
+            })
+            .map_err(|e| format!("WASM plugin '{}' prepare_request failed: {}", name, e))?;
+        let response = self.fetch(request).await?;
+        let items = guest
+            .parse_response(&response)
+            .map_err(|e| format!("WASM plugin '{}' parse_response failed: {}", name, e))?;
+
+        Ok(SearchResult {
+            engine_name: name.clone(),
+            total_results: Some(items.len()),
+            elapsed_ms: start_time.elapsed().as_millis() as u64,
+            items,
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::types::NetworkConfig;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// 客户内存中响应输出的偏移
+    const RESPONSE_OFFSET: u64 = 4096;
+
+    fn wat_string(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    /// 生成返回固定请求与结果的插件（文本格式，wasmtime 可直接编译）
+    fn guest_wat(request: &str, results: &str) -> String {
+        format!(
+            r#"(module
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 16384))
+  (data (i32.const 0) "{request}")
+  (data (i32.const {offset}) "{results}")
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    global.get $next
+    local.set $ptr
+    global.get $next
+    local.get $len
+    i32.add
+    global.set $next
+    local.get $ptr)
+  (func (export "prepare_request") (param i32 i32) (result i64)
+    i64.const {request_packed})
+  (func (export "parse_response") (param i32 i32) (result i64)
+    i64.const {results_packed}))"#,
+            request = wat_string(request),
+            results = wat_string(results),
+            offset = RESPONSE_OFFSET,
+            request_packed = request.len(),
+            results_packed = (RESPONSE_OFFSET << 32) | results.len() as u64,
+        )
+    }
+
+    fn write_plugin(dir: &Path, file: &str, name: &str, wat: &str) {
+        std::fs::write(dir.join(format!("{}.wasm", file)), wat).unwrap();
+        std::fs::write(
+            dir.join(format!("{}.json", file)),
+            format!(r#"{{"name": "{}", "engine_type": "code", "categories": ["it"]}}"#, name),
+        )
+        .unwrap();
+    }
+
+    fn client() -> Arc<HttpClient> {
+        Arc::new(HttpClient::new(NetworkConfig::default()).unwrap())
+    }
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery {
+            query: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// 接受一个连接，返回收到的请求并回复固定页面
+    async fn spawn_server() -> (u16, tokio::sync::oneshot::Receiver<String>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).await.unwrap();
+            let _ = tx.send(String::from_utf8_lossy(&buf[..n]).to_string());
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/html\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+                .await;
+            let _ = stream.shutdown().await;
+        });
+        (port, rx)
+    }
+
+    #[test]
+    fn test_manifest_capabilities() {
+        let manifest: PluginManifest = serde_json::from_str(
+            r#"{"name": "demo", "capabilities": {"max_page_size": 20, "time_range": true, "rate_limit": 0}}"#,
+        )
+        .unwrap();
+        let info = manifest.engine_info();
+        assert_eq!(info.engine_type, EngineType::General);
+        assert_eq!(info.capabilities.max_page_size, 20);
+        assert!(info.capabilities.supports_time_range);
+        assert!(info.capabilities.supports_pagination);
+        assert_eq!(info.capabilities.rate_limit, None);
+
+        // 与 Python 注册一样拒绝未知的能力
+        assert!(serde_json::from_str::<PluginManifest>(r#"{"name": "demo", "capabilities": {"paging": true}}"#).is_err());
+    }
+
+    #[test]
+    fn test_load_dirs_skips_invalid_plugins() {
+        let dir = tempfile::tempdir().unwrap();
+        let valid = guest_wat(r#"{"url": "http://127.0.0.1/"}"#, r#"{"results": []}"#);
+        write_plugin(dir.path(), "valid", "demo", &valid);
+        // 缺少清单
+        std::fs::write(dir.path().join("orphan.wasm"), &valid).unwrap();
+        // 导入宿主函数
+        write_plugin(
+            dir.path(),
+            "imports",
+            "imports",
+            r#"(module (import "env" "socket" (func)) (memory (export "memory") 1))"#,
+        );
+        // 缺少导出
+        write_plugin(dir.path(), "exports", "exports", r#"(module (memory (export "memory") 1))"#);
+        // 与已加载插件同名
+        write_plugin(dir.path(), "zz_duplicate", "demo", &valid);
+
+        let mut host = WasmPluginHost::new().unwrap();
+        let loaded = host.load_dirs(&[dir.path().to_path_buf(), dir.path().join("missing")]);
+        assert_eq!(loaded, vec!["demo".to_string()]);
+        assert_eq!(host.names(), vec!["demo".to_string()]);
+        assert!(host.contains("demo"));
+        assert!(host.create_engine("imports", client()).is_none());
+
+        let engine = host.create_engine("demo", client()).unwrap();
+        assert_eq!(engine.info().name, "demo");
+        assert_eq!(engine.info().engine_type, EngineType::Code);
+        assert_eq!(engine.info().categories, vec!["it".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_plugin_search_goes_through_host_client() {
+        let (port, request) = spawn_server().await;
+        let dir = tempfile::tempdir().unwrap();
+        let wat = guest_wat(
+            &format!(
+                r#"{{"url": "http://127.0.0.1:{}/search?q=wasm", "headers": {{"X-Plugin": "demo"}}}}"#,
+                port
+            ),
+            r#"{"results": [{"title": "Plugin result", "url": "https://example.com/", "content": "from wasm", "score": 0.5, "result_type": "code"}]}"#,
+        );
+        write_plugin(dir.path(), "demo", "demo", &wat);
+
+        let mut host = WasmPluginHost::new().unwrap();
+        host.load_dirs(&[dir.path()]);
+        let engine = host.create_engine("demo", client()).unwrap();
+        let result = engine.search(&query("wasm")).await.unwrap();
+
+        assert_eq!(result.engine_name, "demo");
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].title, "Plugin result");
+        assert_eq!(result.items[0].result_type, ResultType::Code);
+        assert_eq!(result.items[0].score, 0.5);
+
+        let request = request.await.unwrap().to_ascii_lowercase();
+        assert!(request.starts_with("get /search?q=wasm http/1.1"));
+        assert!(request.contains("x-plugin: demo"));
+    }
+
+    #[tokio::test]
+    async fn test_plugin_rejects_unsupported_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let results = r#"{"results": []}"#;
+        write_plugin(dir.path(), "file", "file", &guest_wat(r#"{"url": "file:///etc/passwd"}"#, results));
+        write_plugin(
+            dir.path(),
+            "delete",
+            "delete",
+            &guest_wat(r#"{"method": "DELETE", "url": "http://127.0.0.1/"}"#, results),
+        );
+
+        let mut host = WasmPluginHost::new().unwrap();
+        host.load_dirs(&[dir.path()]);
+        for name in ["file", "delete"] {
+            let engine = host.create_engine(name, client()).unwrap();
+            assert!(engine.search(&query("wasm")).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plugin_traps_and_fuel_exhaustion_fail_the_search() {
+        let dir = tempfile::tempdir().unwrap();
+        let exports = r#"
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "parse_response") (param i32 i32) (result i64) unreachable)"#;
+        write_plugin(
+            dir.path(),
+            "trap",
+            "trap",
+            &format!(r#"(module {} (func (export "prepare_request") (param i32 i32) (result i64) unreachable))"#, exports),
+        );
+        write_plugin(
+            dir.path(),
+            "spin",
+            "spin",
+            &format!(
+                r#"(module {} (func (export "prepare_request") (param i32 i32) (result i64) (loop $spin (br $spin)) i64.const 0))"#,
+                exports
+            ),
+        );
+
+        let mut host = WasmPluginHost::new().unwrap().with_limits(PluginLimits {
+            fuel: 1_000_000,
+            ..Default::default()
+        });
+        assert_eq!(host.load_dirs(&[dir.path()]), vec!["spin".to_string(), "trap".to_string()]);
+        for name in ["trap", "spin"] {
+            let engine = host.create_engine(name, client()).unwrap();
+            let error = engine.search(&query("wasm")).await.unwrap_err().to_string();
+            assert!(error.contains("prepare_request failed"), "{}", error);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unicode 安全的文本工具
+//!
+//! - 按字素簇（用户感知的字符）截断，不会切断多字节字符、组合附加符号或 emoji 序列
+//! - 按终端显示宽度截断与补齐：中日韩全角字符占两列，ANSI 颜色码不占宽度且不会被切断
+//!
+//! 截断统一在末尾追加 [`ELLIPSIS`]，省略号计入长度上限
+
+use std::borrow::Cow;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// 截断时追加的省略号
+pub const ELLIPSIS: &str = "...";
+
+/// 省略号占用的字素簇数与显示宽度
+const ELLIPSIS_LEN: usize = 3;
+
+/// 按字素簇数截断文本
+///
+/// # Arguments
+///
+/// * `text` - 原文本
+/// * `max_graphemes` - 最多保留的字素簇数（含省略号）
+///
+/// # Returns
+///
+/// 未超长时借用原文本；超长时截断并追加 [`ELLIPSIS`]，上限容纳不下省略号时直接截断
+pub fn truncate(text: &str, max_graphemes: usize) -> Cow<'_, str> {
+    match truncation_point(text, max_graphemes) {
+        None => Cow::Borrowed(text),
+        Some((end, ellipsis)) => {
+            let mut out = String::with_capacity(end + ELLIPSIS.len());
+            out.push_str(&text[..end]);
+            if ellipsis {
+                out.push_str(ELLIPSIS);
+            }
+            Cow::Owned(out)
+        }
+    }
+}
+
+/// 原地按字素簇数截断字符串，规则同 [`truncate`]
+///
+/// # Returns
+///
+/// 是否发生了截断
+pub fn truncate_in_place(text: &mut String, max_graphemes: usize) -> bool {
+    let Some((end, ellipsis)) = truncation_point(text, max_graphemes) else {
+        return false;
+    };
+    text.truncate(end);
+    if ellipsis {
+        text.push_str(ELLIPSIS);
+    }
+    true
+}
+
+/// 计算截断位置：返回保留部分的字节长度与是否追加省略号，未超长时返回 None
+fn truncation_point(text: &str, max_graphemes: usize) -> Option<(usize, bool)> {
+    // 第 max_graphemes + 1 个字素簇存在才需要截断
+    grapheme_offset(text, max_graphemes)?;
+    if max_graphemes < ELLIPSIS_LEN {
+        return Some((grapheme_offset(text, max_graphemes).unwrap_or(text.len()), false));
+    }
+    let end = grapheme_offset(text, max_graphemes - ELLIPSIS_LEN).unwrap_or(text.len());
+    Some((end, true))
+}
+
+/// 第 `n` 个字素簇（从 0 开始）的字节偏移
+fn grapheme_offset(text: &str, n: usize) -> Option<usize> {
+    text.grapheme_indices(true).nth(n).map(|(offset, _)| offset)
+}
+
+/// 文本片段：ANSI 转义序列或普通文本
+enum Segment<'a> {
+    Escape(&'a str),
+    Text(&'a str),
+}
+
+/// 将文本拆分为 ANSI 转义序列（`ESC [ ... 终止字节` 或 `ESC` 加单个字符）与普通文本
+fn segments(text: &str) -> impl Iterator<Item = Segment<'_>> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        if rest.starts_with('\x1b') {
+            let bytes = rest.as_bytes();
+            let end = if bytes.get(1) == Some(&b'[') {
+                bytes[2..]
+                    .iter()
+                    .position(|b| (0x40..=0x7e).contains(b))
+                    .map_or(rest.len(), |i| i + 3)
+            } else {
+                rest[1..].chars().next().map_or(1, |c| 1 + c.len_utf8())
+            };
+            let (escape, tail) = rest.split_at(end);
+            rest = tail;
+            return Some(Segment::Escape(escape));
+        }
+        let end = rest.find('\x1b').unwrap_or(rest.len());
+        let (plain, tail) = rest.split_at(end);
+        rest = tail;
+        Some(Segment::Text(plain))
+    })
+}
+
+/// 文本在终端中的显示宽度（全角字符占两列，ANSI 转义序列不占宽度）
+pub fn display_width(text: &str) -> usize {
+    segments(text)
+        .map(|segment| match segment {
+            Segment::Escape(_) => 0,
+            Segment::Text(plain) => plain.width(),
+        })
+        .sum()
+}
+
+/// 按显示宽度截断文本
+///
+/// # Arguments
+///
+/// * `text` - 原文本，可以包含 ANSI 颜色码
+/// * `max_width` - 最大显示宽度（含省略号）
+///
+/// # Returns
+///
+/// 未超宽时借用原文本；超宽时在字素簇边界截断并追加 [`ELLIPSIS`]，
+/// 截断点之前的颜色码保留、之后的丢弃，不会切断转义序列
+pub fn truncate_width(text: &str, max_width: usize) -> Cow<'_, str> {
+    if display_width(text) <= max_width {
+        return Cow::Borrowed(text);
+    }
+    let (budget, ellipsis) = if max_width < ELLIPSIS_LEN {
+        (max_width, false)
+    } else {
+        (max_width - ELLIPSIS_LEN, true)
+    };
+
+    let mut out = String::with_capacity(text.len().min(max_width * 4) + ELLIPSIS.len());
+    let mut width = 0;
+    'outer: for segment in segments(text) {
+        match segment {
+            Segment::Escape(escape) => out.push_str(escape),
+            Segment::Text(plain) => {
+                for grapheme in plain.graphemes(true) {
+                    let grapheme_width = grapheme.width();
+                    if width + grapheme_width > budget {
+                        break 'outer;
+                    }
+                    width += grapheme_width;
+                    out.push_str(grapheme);
+                }
+            }
+        }
+    }
+    if ellipsis {
+        out.push_str(ELLIPSIS);
+    }
+    Cow::Owned(out)
+}
+
+/// 在文本末尾补空格到指定显示宽度（左对齐），已达到宽度时原样返回
+///
+/// 用于替代按字符数补齐的 `{:20}`，使中日韩文本在终端中对齐
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    let mut out = String::with_capacity(text.len() + padding);
+    out.push_str(text);
+    out.extend(std::iter::repeat_n(' ', padding));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_graphemes() {
+        assert!(matches!(truncate("hello", 5), Cow::Borrowed("hello")));
+        assert_eq!(truncate("hello world", 8), "hello...");
+        assert_eq!(truncate("搜索引擎聚合", 5), "搜索...");
+        assert_eq!(truncate("hello", 2), "he");
+        assert_eq!(truncate("hello", 0), "");
+
+        // 组合附加符号与 emoji 序列不会被拆开
+        assert_eq!(truncate("cafe\u{301}s and more", 4), "c...");
+        assert_eq!(truncate("e\u{301}e\u{301}e\u{301}", 2), "e\u{301}e\u{301}");
+        let family = "👨\u{200d}👩\u{200d}👧";
+        assert_eq!(truncate(&format!("{family}{family}{family}{family}{family}"), 4), format!("{family}..."));
+
+        let mut text = "搜索引擎聚合".to_string();
+        assert!(truncate_in_place(&mut text, 5));
+        assert_eq!(text, "搜索...");
+        assert!(!truncate_in_place(&mut text, 5));
+    }
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("rust"), 4);
+        assert_eq!(display_width("搜索"), 4);
+        assert_eq!(display_width("\x1b[1;33mrust\x1b[0m 搜索"), 9);
+    }
+
+    #[test]
+    fn test_truncate_width() {
+        assert!(matches!(truncate_width("搜索", 4), Cow::Borrowed(_)));
+        assert_eq!(truncate_width("搜索引擎聚合", 8), "搜索...");
+        // 全角字符放不下时不补半个
+        assert_eq!(truncate_width("a搜索引擎", 7), "a搜...");
+        assert_eq!(
+            truncate_width("\x1b[1mrust\x1b[0m language", 8),
+            "\x1b[1mrust\x1b[0m ..."
+        );
+        assert_eq!(truncate_width("\x1b[1mrustacean\x1b[0m", 5), "\x1b[1mru...");
+    }
+
+    #[test]
+    fn test_pad_to_width() {
+        assert_eq!(pad_to_width("总键数", 10), "总键数    ");
+        assert_eq!(pad_to_width("keys", 6), "keys  ");
+        assert_eq!(pad_to_width("longer than width", 4), "longer than width");
+        assert_eq!(display_width(&pad_to_width("缓存命中率", 20)), 20);
+    }
+}
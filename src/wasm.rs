@@ -0,0 +1,306 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WebAssembly 绑定
+//!
+//! 导出查询解析、评分、标准化、聚合与结果格式化，用于浏览器端重排序和离线演示。
+//! 不包含网络与缓存，构建方式：
+//!
+//! ```bash
+//! cargo build --lib --target wasm32-unknown-unknown --no-default-features --features wasm32
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::derive::{ResultType, SearchQuery, SearchResult, SearchResultItem};
+use crate::search::{
+    clean_text, deduplicate_by_url, get_engine_authority, highlight_terms, highlight_text,
+    score_and_sort_results_with_authority, standardize_item, AggregationStrategy, HighlightMarkers,
+    QueryParser, SearchAggregator, SortBy, ENGINE_METADATA_KEY,
+};
+
+/// 输入的搜索结果项（除标题与 URL 外均可省略）
+#[derive(Debug, Clone, Deserialize)]
+struct ItemInput {
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    display_url: Option<String>,
+    #[serde(default)]
+    site_name: Option<String>,
+    #[serde(default)]
+    score: f64,
+    #[serde(default)]
+    result_type: ResultType,
+    #[serde(default)]
+    thumbnail: Option<String>,
+    #[serde(default)]
+    published_date: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    template: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+impl From<ItemInput> for SearchResultItem {
+    fn from(item: ItemInput) -> Self {
+        Self {
+            title: item.title,
+            url: item.url,
+            content: item.content,
+            display_url: item.display_url,
+            site_name: item.site_name,
+            score: item.score,
+            result_type: item.result_type,
+            thumbnail: item.thumbnail,
+            published_date: item.published_date,
+            template: item.template,
+            metadata: item.metadata,
+        }
+    }
+}
+
+/// 输入的单个引擎结果
+#[derive(Debug, Clone, Deserialize)]
+struct EngineResultInput {
+    engine_name: String,
+    #[serde(default)]
+    items: Vec<ItemInput>,
+}
+
+impl From<EngineResultInput> for SearchResult {
+    fn from(result: EngineResultInput) -> Self {
+        let items: Vec<SearchResultItem> = result.items.into_iter().map(Into::into).collect();
+        Self {
+            engine_name: result.engine_name,
+            total_results: Some(items.len()),
+            elapsed_ms: 0,
+            items,
+            pagination: None,
+            suggestions: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+}
+
+/// 查询解析结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ParsedQueryOutput {
+    original: String,
+    normalized: String,
+    intent: String,
+    language: Option<String>,
+    region: Option<String>,
+    expanded_terms: Vec<String>,
+    warnings: Vec<String>,
+}
+
+/// 构造只含查询文本的搜索查询
+fn search_query(query: &str) -> SearchQuery {
+    SearchQuery {
+        query: query.to_string(),
+        ..Default::default()
+    }
+}
+
+/// 按查询重新评分并排序
+///
+/// 结果来源引擎取自元数据（聚合结果中已记录），缺失时使用 `engine`
+fn rerank(items: Vec<ItemInput>, query: &str, engine: &str) -> Vec<SearchResultItem> {
+    let mut items: Vec<SearchResultItem> = items.into_iter().map(Into::into).collect();
+    score_and_sort_results_with_authority(
+        &mut items,
+        &search_query(query),
+        |item| {
+            let engine = item.metadata.get(ENGINE_METADATA_KEY).map_or(engine, String::as_str);
+            get_engine_authority(engine)
+        },
+        None,
+    );
+    items
+}
+
+/// 标准化结果项并按 URL 去重
+fn standardize(items: Vec<ItemInput>) -> Vec<SearchResultItem> {
+    let mut items: Vec<SearchResultItem> = items
+        .into_iter()
+        .map(|item| {
+            let mut item = item.into();
+            standardize_item(&mut item);
+            item
+        })
+        .collect();
+    deduplicate_by_url(&mut items);
+    items
+}
+
+/// 聚合多个引擎的结果
+fn aggregate_results(
+    results: Vec<EngineResultInput>,
+    query: &str,
+    sort_by: SortBy,
+    engine_weights: Option<HashMap<String, f64>>,
+) -> SearchResult {
+    let results = results.into_iter().map(Into::into).collect();
+    let aggregator = SearchAggregator::new(AggregationStrategy::Merged, sort_by);
+    let query = search_query(query);
+    match engine_weights {
+        Some(weights) => aggregator.aggregate_with_engine_weights(results, &query, &weights),
+        None => aggregator.aggregate_with_scoring(results, &query),
+    }
+}
+
+/// 将 JS 值反序列化为 Rust 类型
+fn from_js<T: for<'de> Deserialize<'de>>(value: JsValue) -> Result<T, JsError> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// 将 Rust 值序列化为普通 JS 对象（映射转换为对象而不是 Map）
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// 解析查询（意图、语言、规范化文本）
+#[wasm_bindgen(js_name = parseQuery)]
+pub fn parse_query(query: &str) -> Result<JsValue, JsError> {
+    let parsed = QueryParser::new().parse(query);
+    to_js(&ParsedQueryOutput {
+        original: parsed.original,
+        normalized: parsed.normalized,
+        intent: format!("{:?}", parsed.intent).to_lowercase(),
+        language: parsed.language,
+        region: parsed.region,
+        expanded_terms: parsed.expanded_terms,
+        warnings: parsed.warnings,
+    })
+}
+
+/// 按查询对结果重新评分并排序
+///
+/// # Arguments
+///
+/// * `items` - 结果项数组
+/// * `query` - 查询文本
+/// * `engine` - 元数据中没有来源引擎时使用的引擎名
+#[wasm_bindgen(js_name = scoreResults)]
+pub fn score_results(items: JsValue, query: &str, engine: Option<String>) -> Result<JsValue, JsError> {
+    let items = rerank(from_js(items)?, query, engine.as_deref().unwrap_or_default());
+    to_js(&items)
+}
+
+/// 标准化结果项（清理文本、规范 URL）并按 URL 去重
+#[wasm_bindgen(js_name = standardizeResults)]
+pub fn standardize_results(items: JsValue) -> Result<JsValue, JsError> {
+    to_js(&standardize(from_js(items)?))
+}
+
+/// 聚合多个引擎的结果
+///
+/// # Arguments
+///
+/// * `results` - `[{ engine_name, items }]` 数组
+/// * `query` - 查询文本
+/// * `sort` - 排序方式（relevance / time / source / price，默认 relevance）
+/// * `engine_weights` - 引擎名到质量评分（0.0 - 1.0）的映射，替代静态权威度
+#[wasm_bindgen]
+pub fn aggregate(
+    results: JsValue,
+    query: &str,
+    sort: Option<String>,
+    engine_weights: JsValue,
+) -> Result<JsValue, JsError> {
+    let sort_by = match sort.as_deref() {
+        Some(value) => SortBy::from_param(value)
+            .ok_or_else(|| JsError::new(&format!("Unknown sort: {}", value)))?,
+        None => SortBy::Relevance,
+    };
+    let engine_weights = if engine_weights.is_undefined() || engine_weights.is_null() {
+        None
+    } else {
+        Some(from_js(engine_weights)?)
+    };
+    to_js(&aggregate_results(from_js(results)?, query, sort_by, engine_weights))
+}
+
+/// 高亮文本中的查询词
+///
+/// # Arguments
+///
+/// * `text` - 待高亮的文本
+/// * `query` - 查询文本
+/// * `open` / `close` - 高亮标记（默认 `<em>` / `</em>`，并对其余文本做 HTML 转义）
+#[wasm_bindgen]
+pub fn highlight(text: &str, query: &str, open: Option<String>, close: Option<String>) -> String {
+    let markers = match (open, close) {
+        (Some(open), Some(close)) => HighlightMarkers::new(&open, &close),
+        _ => HighlightMarkers::default(),
+    };
+    highlight_text(text, &highlight_terms(query), &markers)
+}
+
+/// 清理文本（去除 HTML 与多余空白并截断）
+#[wasm_bindgen(js_name = cleanText)]
+pub fn clean(text: &str, max_length: usize) -> String {
+    clean_text(text, max_length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(title: &str, url: &str, engine: &str) -> ItemInput {
+        let mut item: ItemInput =
+            serde_json::from_value(serde_json::json!({ "title": title, "url": url })).unwrap();
+        item.metadata.insert(ENGINE_METADATA_KEY.to_string(), engine.to_string());
+        item
+    }
+
+    #[test]
+    fn test_rerank_prefers_matching_titles() {
+        let items = vec![
+            item("Cooking pasta", "https://example.com/pasta", "bing"),
+            item("Rust async programming", "https://example.com/rust", "bing"),
+        ];
+        let ranked = rerank(items, "rust async", "");
+        assert_eq!(ranked[0].url, "https://example.com/rust");
+        assert!(ranked[0].score > ranked[1].score);
+    }
+
+    #[test]
+    fn test_aggregate_deduplicates_across_engines() {
+        let results = vec![
+            EngineResultInput {
+                engine_name: "bing".to_string(),
+                items: vec![item("Rust", "https://www.rust-lang.org/", "bing")],
+            },
+            EngineResultInput {
+                engine_name: "yandex".to_string(),
+                items: vec![
+                    item("Rust", "https://www.rust-lang.org/", "yandex"),
+                    item("Rust book", "https://doc.rust-lang.org/book/", "yandex"),
+                ],
+            },
+        ];
+        let aggregated = aggregate_results(results, "rust", SortBy::Relevance, None);
+        assert_eq!(aggregated.items.len(), 2);
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 内置网页搜索界面
+//!
+//! 在 `/` 提供服务端渲染、不依赖 JavaScript 的搜索页面，内置模板与样式嵌入在二进制中，
+//! 可由配置目录下的 `templates/` 覆盖。偏好设置保存在浏览器 Cookie 中，服务端不存储；
+//! 可通过 `web.enabled = false` 关闭
+
+mod render;
+mod templates;
+
+pub use render::{escape_html, Page};
+pub use templates::{render_template, Templates, TEMPLATE_DIRECTORY};
+
+use crate::api::middleware::ApiClient;
+use crate::api::{execute_search, ApiSearchRequest, ApiState};
+use crate::cache::preferences::{Theme, UserPreferences};
+use axum::{
+    extract::{Extension, Form, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+    Router,
+};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::Deserialize;
+
+/// 搜索页面路径
+pub const INDEX_PATH: &str = "/";
+
+/// 保存偏好设置的路径
+pub const PREFERENCES_PATH: &str = "/preferences";
+
+/// 样式表路径
+pub const STYLESHEET_PATH: &str = "/static/style.css";
+
+/// 保存偏好设置的 Cookie 名称
+const PREFERENCES_COOKIE: &str = "seesea_prefs";
+
+/// 偏好设置 Cookie 有效期（秒）
+const PREFERENCES_COOKIE_MAX_AGE: u64 = 365 * 24 * 3600;
+
+/// 页面的内容安全策略（不加载脚本与第三方资源）
+const CONTENT_SECURITY_POLICY: &str =
+    "default-src 'none'; style-src 'self'; img-src 'self'; form-action 'self'; base-uri 'none'; frame-ancestors 'none'";
+
+/// 构建网页界面路由
+pub fn router() -> Router<ApiState> {
+    Router::new()
+        .route(INDEX_PATH, get(handle_index))
+        .route(PREFERENCES_PATH, post(handle_preferences_save))
+        .route(STYLESHEET_PATH, get(handle_stylesheet))
+}
+
+/// 处理搜索页面请求（没有查询时显示首页）
+pub async fn handle_index(
+    State(state): State<ApiState>,
+    Extension(client): Extension<ApiClient>,
+    headers: HeaderMap,
+    Query(params): Query<ApiSearchRequest>,
+) -> Response {
+    let preferences = read_preferences(&headers);
+    let engines = state.search.list_engines();
+    let page = Page {
+        title: &state.web.title,
+        version: &state.version,
+        preferences: &preferences,
+        engines: &engines,
+        templates: &state.web_templates,
+    };
+
+    let query = params.get_query().unwrap_or_default();
+    if query.trim().is_empty() {
+        return html_page(StatusCode::OK, page.home());
+    }
+
+    let request = apply_preferences(params.clone(), &preferences);
+    match execute_search(&state, &client, request).await {
+        Ok(response) => html_page(StatusCode::OK, page.results(&params, &response)),
+        Err(e) => html_page(StatusCode::INTERNAL_SERVER_ERROR, page.error(&query, &format!("搜索失败：{}", e))),
+    }
+}
+
+/// 偏好设置表单
+#[derive(Debug, Default, Deserialize)]
+pub struct PreferencesForm {
+    /// 保存后返回的查询
+    #[serde(default)]
+    pub q: String,
+    /// 偏好语言（空表示不限）
+    #[serde(default)]
+    pub language: String,
+    /// 偏好引擎（逗号分隔，空表示默认引擎）
+    #[serde(default)]
+    pub engines: String,
+    /// 每页结果数（空表示默认）
+    #[serde(default)]
+    pub results_per_page: String,
+    /// 界面主题
+    #[serde(default)]
+    pub theme: Theme,
+    /// 恢复默认设置
+    #[serde(default)]
+    pub reset: Option<String>,
+}
+
+impl PreferencesForm {
+    /// 转换为用户偏好并校验
+    pub fn to_preferences(&self) -> Result<UserPreferences, String> {
+        let language = self.language.trim();
+        let results_per_page = match self.results_per_page.trim() {
+            "" => None,
+            n => Some(n.parse::<u32>().map_err(|_| format!("无效的每页结果数: {}", n))?),
+        };
+        let preferences = UserPreferences {
+            language: (!language.is_empty()).then(|| language.to_string()),
+            engines: self
+                .engines
+                .split(',')
+                .map(str::trim)
+                .filter(|e| !e.is_empty())
+                .map(str::to_string)
+                .collect(),
+            results_per_page,
+            theme: self.theme,
+            updated_at: 0,
+        };
+        preferences.validate()?;
+        Ok(preferences)
+    }
+}
+
+/// 处理保存偏好设置请求（写入 Cookie 后返回搜索页面）
+pub async fn handle_preferences_save(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Form(form): Form<PreferencesForm>,
+) -> Response {
+    let location = if form.q.trim().is_empty() {
+        INDEX_PATH.to_string()
+    } else {
+        render::search_link(&form.q, 1, None)
+    };
+
+    if form.reset.is_some() {
+        return redirect_with_cookie(&location, &format!("{}=; Path=/; Max-Age=0; HttpOnly; SameSite=Lax", PREFERENCES_COOKIE));
+    }
+    match form.to_preferences() {
+        Ok(preferences) => redirect_with_cookie(&location, &preferences_cookie(&preferences)),
+        Err(e) => {
+            let preferences = read_preferences(&headers);
+            let engines = state.search.list_engines();
+            let page = Page {
+                title: &state.web.title,
+                version: &state.version,
+                preferences: &preferences,
+                engines: &engines,
+                templates: &state.web_templates,
+            };
+            html_page(StatusCode::BAD_REQUEST, page.error(&form.q, &format!("偏好设置无效：{}", e)))
+        }
+    }
+}
+
+/// 返回样式表（已配置覆盖时返回覆盖后的样式表）
+pub async fn handle_stylesheet(State(state): State<ApiState>) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "text/css; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        state.web_templates.stylesheet.clone(),
+    )
+        .into_response()
+}
+
+/// 用偏好设置补全请求中未指定的语言、引擎与每页结果数
+pub fn apply_preferences(mut params: ApiSearchRequest, preferences: &UserPreferences) -> ApiSearchRequest {
+    if params.language.is_none() {
+        params.language = preferences.language.clone();
+    }
+    if params.engines.is_none() && !params.china_mode && !preferences.engines.is_empty() {
+        params.engines = Some(preferences.engines.join(","));
+    }
+    if let Some(results_per_page) = preferences.results_per_page {
+        params.page_size = results_per_page;
+    }
+    params
+}
+
+/// 从请求 Cookie 读取偏好设置（缺失或无效时使用默认值）
+pub fn read_preferences(headers: &HeaderMap) -> UserPreferences {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().strip_prefix(PREFERENCES_COOKIE)?.strip_prefix('='))
+        .find_map(decode_preferences)
+        .unwrap_or_default()
+}
+
+/// 解码偏好设置 Cookie 的值
+fn decode_preferences(value: &str) -> Option<UserPreferences> {
+    let bytes = URL_SAFE_NO_PAD.decode(value).ok()?;
+    let preferences: UserPreferences = serde_json::from_slice(&bytes).ok()?;
+    preferences.validate().ok()?;
+    Some(preferences)
+}
+
+/// 生成保存偏好设置的 `Set-Cookie` 值
+fn preferences_cookie(preferences: &UserPreferences) -> String {
+    let json = serde_json::to_vec(preferences).unwrap_or_default();
+    format!(
+        "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax",
+        PREFERENCES_COOKIE,
+        URL_SAFE_NO_PAD.encode(json),
+        PREFERENCES_COOKIE_MAX_AGE
+    )
+}
+
+/// 返回带安全响应头的 HTML 页面
+fn html_page(status: StatusCode, body: String) -> Response {
+    let mut response = (status, Html(body)).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_SECURITY_POLICY, HeaderValue::from_static(CONTENT_SECURITY_POLICY));
+    headers.insert(header::REFERRER_POLICY, HeaderValue::from_static("no-referrer"));
+    headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    response
+}
+
+/// 设置 Cookie 并以 303 跳转
+fn redirect_with_cookie(location: &str, cookie: &str) -> Response {
+    let mut response = StatusCode::SEE_OTHER.into_response();
+    let headers = response.headers_mut();
+    if let Ok(location) = HeaderValue::from_str(location) {
+        headers.insert(header::LOCATION, location);
+    }
+    if let Ok(cookie) = HeaderValue::from_str(cookie) {
+        headers.insert(header::SET_COOKIE, cookie);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preferences_cookie_roundtrip() {
+        let preferences = UserPreferences {
+            language: Some("zh".to_string()),
+            engines: vec!["bing".to_string(), "baidu".to_string()],
+            results_per_page: Some(20),
+            theme: Theme::Dark,
+            updated_at: 0,
+        };
+        let cookie = preferences_cookie(&preferences);
+        let value = cookie.split(';').next().unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, HeaderValue::from_str(&format!("other=1; {}", value)).unwrap());
+        assert_eq!(read_preferences(&headers), preferences);
+
+        // 无效的 Cookie 回退到默认偏好
+        headers.insert(header::COOKIE, HeaderValue::from_static("seesea_prefs=!!!"));
+        assert_eq!(read_preferences(&headers), UserPreferences::default());
+    }
+
+    #[test]
+    fn test_preferences_form() {
+        let form = PreferencesForm {
+            language: " en ".to_string(),
+            engines: "bing, ,yandex".to_string(),
+            results_per_page: "30".to_string(),
+            ..Default::default()
+        };
+        let preferences = form.to_preferences().unwrap();
+        assert_eq!(preferences.language.as_deref(), Some("en"));
+        assert_eq!(preferences.engines, vec!["bing", "yandex"]);
+        assert_eq!(preferences.results_per_page, Some(30));
+
+        let form = PreferencesForm {
+            results_per_page: "1000".to_string(),
+            ..Default::default()
+        };
+        assert!(form.to_preferences().is_err());
+    }
+
+    #[test]
+    fn test_apply_preferences() {
+        let preferences = UserPreferences {
+            language: Some("zh".to_string()),
+            engines: vec!["baidu".to_string()],
+            results_per_page: Some(25),
+            ..Default::default()
+        };
+        let params: ApiSearchRequest = serde_json::from_value(serde_json::json!({ "q": "rust", "language": "en" })).unwrap();
+        let applied = apply_preferences(params, &preferences);
+        assert_eq!(applied.language.as_deref(), Some("en"));
+        assert_eq!(applied.engines.as_deref(), Some("baidu"));
+        assert_eq!(applied.page_size, 25);
+    }
+}
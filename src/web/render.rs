@@ -0,0 +1,393 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 网页模板渲染
+//!
+//! 所有插入模板的文本都经过 HTML 转义
+
+use crate::api::{ApiSearchRequest, ApiSearchResponse, ApiSearchResultItem};
+use crate::cache::preferences::{Theme, UserPreferences, MAX_RESULTS_PER_PAGE};
+use crate::search::InstantAnswer;
+use super::templates::{render_template, Templates};
+
+/// 未设置偏好语言时页面使用的语言
+const DEFAULT_PAGE_LANGUAGE: &str = "zh";
+
+/// 转义 HTML 文本与属性值中的特殊字符
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 只允许 http(s) 与站内链接，避免 `javascript:` 等链接进入页面
+fn safe_href(url: &str) -> Option<&str> {
+    let lower = url.trim_start().to_ascii_lowercase();
+    (lower.starts_with("http://") || lower.starts_with("https://") || lower.starts_with('/')).then_some(url)
+}
+
+/// 构建搜索页面链接
+///
+/// # 参数
+///
+/// * `query` - 查询字符串
+/// * `page` - 页码
+/// * `params` - 原始请求参数（保留其中显式指定的语言与引擎）
+pub fn search_link(query: &str, page: u32, params: Option<&ApiSearchRequest>) -> String {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    serializer.append_pair("q", query);
+    if page > 1 {
+        serializer.append_pair("page", &page.to_string());
+    }
+    if let Some(language) = params.and_then(|p| p.language.as_deref()) {
+        serializer.append_pair("language", language);
+    }
+    if let Some(engines) = params.and_then(|p| p.engines.as_deref()) {
+        serializer.append_pair("engines", engines);
+    }
+    format!("/?{}", serializer.finish())
+}
+
+/// 主题在页面上的取值
+fn theme_name(theme: Theme) -> &'static str {
+    match theme {
+        Theme::Auto => "auto",
+        Theme::Light => "light",
+        Theme::Dark => "dark",
+    }
+}
+
+/// 网页渲染上下文
+pub struct Page<'a> {
+    /// 站点名称
+    pub title: &'a str,
+    /// 版本号
+    pub version: &'a str,
+    /// 当前用户偏好
+    pub preferences: &'a UserPreferences,
+    /// 可用引擎（在偏好面板中提示）
+    pub engines: &'a [String],
+    /// 页面模板
+    pub templates: &'a Templates,
+}
+
+impl Page<'_> {
+    /// 渲染首页
+    pub fn home(&self) -> String {
+        self.layout("", "home", "")
+    }
+
+    /// 渲染搜索结果页
+    ///
+    /// # 参数
+    ///
+    /// * `params` - 原始请求参数（用于生成翻页与建议链接）
+    /// * `response` - 搜索响应
+    pub fn results(&self, params: &ApiSearchRequest, response: &ApiSearchResponse) -> String {
+        let query = response.query.as_str();
+        let mut content = String::new();
+
+        for warning in &response.warnings {
+            content.push_str(&format!("<p class=\"warning\">{}</p>\n", escape_html(warning)));
+        }
+        if !response.suggestions.is_empty() {
+            let links: Vec<String> = response
+                .suggestions
+                .iter()
+                .map(|s| format!("<a href=\"{}\">{}</a>", escape_html(&search_link(s, 1, Some(params))), escape_html(s)))
+                .collect();
+            content.push_str(&format!("<p class=\"meta\">您是不是要找：{}</p>\n", links.join("、")));
+        }
+        if let Some(ref answer) = response.answer {
+            content.push_str(&self.render_answer(answer));
+        }
+
+        if response.results.is_empty() {
+            content.push_str(&format!("<p class=\"meta\">没有找到与“{}”相关的结果</p>\n", escape_html(query)));
+        } else {
+            content.push_str(&format!(
+                "<p class=\"meta\">约 {} 条结果（{} 毫秒{}）· {}</p>\n<ol class=\"results\">\n",
+                response.total_count,
+                response.query_time_ms,
+                if response.cached { "，来自缓存" } else { "" },
+                escape_html(&response.engines_used.join(", ")),
+            ));
+            for item in &response.results {
+                content.push_str(&self.render_result(item));
+            }
+            content.push_str("</ol>\n");
+        }
+
+        let has_previous = response.page > 1;
+        let has_next = response.results.len() >= response.page_size as usize && response.page_size > 0;
+        if has_previous || has_next {
+            content.push_str("<nav class=\"pagination\">");
+            if has_previous {
+                content.push_str(&format!(
+                    "<a rel=\"prev\" href=\"{}\">上一页</a>",
+                    escape_html(&search_link(query, response.page - 1, Some(params)))
+                ));
+            } else {
+                content.push_str("<span></span>");
+            }
+            if has_next {
+                content.push_str(&format!(
+                    "<a rel=\"next\" href=\"{}\">下一页</a>",
+                    escape_html(&search_link(query, response.page + 1, Some(params)))
+                ));
+            }
+            content.push_str("</nav>\n");
+        }
+
+        self.layout(query, "results", &content)
+    }
+
+    /// 渲染错误页
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 查询字符串（回填到搜索框）
+    /// * `message` - 错误信息
+    pub fn error(&self, query: &str, message: &str) -> String {
+        let content = format!("<p class=\"warning\">{}</p>\n", escape_html(message));
+        self.layout(query, "error", &content)
+    }
+
+    /// 套用页面框架
+    fn layout(&self, query: &str, body_class: &str, content: &str) -> String {
+        let page_title = if query.is_empty() {
+            escape_html(self.title)
+        } else {
+            format!("{} - {}", escape_html(query), escape_html(self.title))
+        };
+        let language = self.preferences.language.as_deref().unwrap_or(DEFAULT_PAGE_LANGUAGE);
+        let preferences = self.preferences_panel(query);
+        render_template(
+            &self.templates.layout,
+            &[
+                ("lang", &escape_html(language)),
+                ("theme", theme_name(self.preferences.theme)),
+                ("page_title", &page_title),
+                ("body_class", body_class),
+                ("title", &escape_html(self.title)),
+                ("query", &escape_html(query)),
+                ("autofocus", if query.is_empty() { " autofocus" } else { "" }),
+                ("content", content),
+                ("preferences", &preferences),
+                ("version", &escape_html(self.version)),
+            ],
+        )
+    }
+
+    /// 渲染偏好设置面板（保存到浏览器 Cookie，不在服务端存储）
+    fn preferences_panel(&self, query: &str) -> String {
+        let preferences = self.preferences;
+        let themes: String = [(Theme::Auto, "跟随系统"), (Theme::Light, "浅色"), (Theme::Dark, "深色")]
+            .iter()
+            .map(|(theme, label)| {
+                format!(
+                    "<option value=\"{}\"{}>{}</option>",
+                    theme_name(*theme),
+                    if *theme == preferences.theme { " selected" } else { "" },
+                    label
+                )
+            })
+            .collect();
+        format!(
+            concat!(
+                "<details class=\"preferences\">\n<summary>偏好设置</summary>\n",
+                "<form action=\"/preferences\" method=\"post\">\n",
+                "<input type=\"hidden\" name=\"q\" value=\"{query}\">\n",
+                "<label>语言 <input name=\"language\" value=\"{language}\" placeholder=\"如 zh、en，留空不限\"></label>\n",
+                "<label>引擎 <input name=\"engines\" value=\"{engines}\" placeholder=\"逗号分隔，留空使用默认引擎\"></label>\n",
+                "<p class=\"meta\">可用引擎：{available}</p>\n",
+                "<label>每页结果数 <input type=\"number\" name=\"results_per_page\" min=\"1\" max=\"{max}\" value=\"{per_page}\"></label>\n",
+                "<label>主题 <select name=\"theme\">{themes}</select></label>\n",
+                "<button type=\"submit\">保存</button> <button type=\"submit\" name=\"reset\" value=\"1\">恢复默认</button>\n",
+                "</form>\n</details>",
+            ),
+            query = escape_html(query),
+            language = escape_html(preferences.language.as_deref().unwrap_or_default()),
+            engines = escape_html(&preferences.engines.join(",")),
+            available = escape_html(&self.engines.join(", ")),
+            max = MAX_RESULTS_PER_PAGE,
+            per_page = preferences.results_per_page.map(|n| n.to_string()).unwrap_or_default(),
+            themes = themes,
+        )
+    }
+
+    /// 渲染单条结果
+    fn render_result(&self, item: &ApiSearchResultItem) -> String {
+        let title = escape_html(&item.title);
+        let href = safe_href(&item.url).map(escape_html);
+        let title_html = match href {
+            Some(ref href) => format!("<a class=\"title\" href=\"{}\" rel=\"noreferrer noopener\">{}</a>", href, title),
+            None => format!("<span class=\"title\">{}</span>", title),
+        };
+        let description = escape_html(item.description.as_deref().unwrap_or_default());
+        let description_html = if description.is_empty() {
+            String::new()
+        } else {
+            format!("<p>{}</p>", description)
+        };
+        let thumbnail = item.thumbnail.as_deref().and_then(safe_href).map(escape_html).unwrap_or_default();
+        render_template(
+            &self.templates.result,
+            &[
+                ("title", &title),
+                ("title_html", &title_html),
+                ("url", &escape_html(&item.url)),
+                ("href", href.as_deref().unwrap_or_default()),
+                ("description", &description),
+                ("description_html", &description_html),
+                ("engine", &escape_html(&item.engine)),
+                ("thumbnail", &thumbnail),
+            ],
+        )
+    }
+
+    /// 渲染即时答案
+    fn render_answer(&self, answer: &InstantAnswer) -> String {
+        let mut details_html = String::new();
+        if !answer.details.is_empty() {
+            let mut details: Vec<_> = answer.details.iter().collect();
+            details.sort();
+            details_html.push_str("<dl>");
+            for (key, value) in details {
+                details_html.push_str(&format!("<dt>{}</dt><dd>{}</dd>", escape_html(key), escape_html(value)));
+            }
+            details_html.push_str("</dl>");
+        }
+        let source_html = answer
+            .url
+            .as_deref()
+            .and_then(safe_href)
+            .map(|href| format!("<a href=\"{}\" rel=\"noreferrer noopener\">来源</a>", escape_html(href)))
+            .unwrap_or_default();
+        render_template(
+            &self.templates.answer,
+            &[
+                ("answer", &escape_html(&answer.answer)),
+                ("answerer", &escape_html(&answer.answerer)),
+                ("details_html", &details_html),
+                ("source_html", &source_html),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(query: &str) -> ApiSearchRequest {
+        serde_json::from_value(serde_json::json!({ "q": query })).unwrap()
+    }
+
+    fn response(results: Vec<ApiSearchResultItem>, page_size: u32) -> ApiSearchResponse {
+        serde_json::from_value(serde_json::json!({
+            "query": "rust <lang>",
+            "results": results,
+            "total_count": results.len(),
+            "page": 1,
+            "page_size": page_size,
+            "engines_used": ["bing"],
+            "query_time_ms": 12,
+            "cached": false,
+        }))
+        .unwrap()
+    }
+
+    fn item(title: &str, url: &str) -> ApiSearchResultItem {
+        ApiSearchResultItem {
+            title: title.to_string(),
+            url: url.to_string(),
+            description: Some("a & b".to_string()),
+            thumbnail: None,
+            engine: "bing".to_string(),
+            score: None,
+            explain: None,
+        }
+    }
+
+    fn page<'a>(preferences: &'a UserPreferences, templates: &'a Templates) -> Page<'a> {
+        Page {
+            title: "SeeSea",
+            version: "0.0.0",
+            preferences,
+            engines: &[],
+            templates,
+        }
+    }
+
+    #[test]
+    fn test_results_page_escapes_and_filters_links() {
+        let preferences = UserPreferences::default();
+        let templates = Templates::builtin();
+        let html = page(&preferences, &templates).results(
+            &params("rust <lang>"),
+            &response(
+                vec![item("<script>x</script>", "https://a.com/?a=1&b=2"), item("bad", "javascript:alert(1)")],
+                10,
+            ),
+        );
+        assert!(html.contains("&lt;script&gt;x&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("href=\"https://a.com/?a=1&amp;b=2\""));
+        assert!(!html.contains("href=\"javascript:"));
+        assert!(html.contains("<span class=\"title\">bad</span>"));
+        assert!(html.contains("value=\"rust &lt;lang&gt;\""));
+        // 结果不足一页时没有下一页
+        assert!(!html.contains("rel=\"next\""));
+    }
+
+    #[test]
+    fn test_results_page_pagination() {
+        let preferences = UserPreferences {
+            theme: Theme::Dark,
+            ..Default::default()
+        };
+        let templates = Templates::builtin();
+        let html = page(&preferences, &templates).results(&params("rust"), &response(vec![item("a", "https://a.com")], 1));
+        assert!(html.contains("data-theme=\"dark\""));
+        assert!(html.contains("href=\"/?q=rust+%3Clang%3E&amp;page=2\""));
+    }
+
+    #[test]
+    fn test_custom_result_template() {
+        let preferences = UserPreferences::default();
+        let templates = Templates {
+            result: "<div class=\"hit\" data-engine=\"{{engine}}\">{{ title }}</div>".to_string(),
+            ..Templates::builtin()
+        };
+        let html = page(&preferences, &templates).results(&params("rust"), &response(vec![item("a<b", "https://a.com")], 10));
+        assert!(html.contains("<div class=\"hit\" data-engine=\"bing\">a&lt;b</div>"));
+    }
+
+    #[test]
+    fn test_search_link_keeps_explicit_filters() {
+        let mut request = params("北京");
+        request.engines = Some("bing,baidu".to_string());
+        assert_eq!(search_link("北京", 1, Some(&request)), "/?q=%E5%8C%97%E4%BA%AC&engines=bing%2Cbaidu");
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 可覆盖的页面模板
+//!
+//! 内置模板嵌入在二进制中；`<config_directory>/templates/` 下的同名文件会替换对应模板，
+//! 实例运营者无需修改源码即可更换页面外观。模板使用 `{{name}}` 占位符，插入的值均已转义：
+//!
+//! - `layout.html`：`lang`、`theme`、`page_title`、`body_class`、`title`、`query`、`autofocus`、
+//!   `content`、`preferences`、`version`
+//! - `result.html`：`title`、`title_html`、`url`、`href`、`description`、`description_html`、`engine`、`thumbnail`
+//! - `answer.html`：`answer`、`answerer`、`details_html`、`source_html`
+//! - `style.css`：样式表，原样返回
+
+use std::path::Path;
+
+/// 覆盖模板所在的子目录（相对于配置目录）
+pub const TEMPLATE_DIRECTORY: &str = "templates";
+
+/// 页面模板集合
+#[derive(Debug, Clone)]
+pub struct Templates {
+    /// 页面框架
+    pub layout: String,
+    /// 单条搜索结果
+    pub result: String,
+    /// 即时答案
+    pub answer: String,
+    /// 样式表
+    pub stylesheet: String,
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl Templates {
+    /// 内置模板
+    pub fn builtin() -> Self {
+        Self {
+            layout: include_str!("assets/layout.html").to_string(),
+            result: include_str!("assets/result.html").to_string(),
+            answer: include_str!("assets/answer.html").to_string(),
+            stylesheet: include_str!("assets/style.css").to_string(),
+        }
+    }
+
+    /// 加载模板，目录中存在的同名文件覆盖内置模板
+    ///
+    /// 目录不存在时全部使用内置模板；单个文件读取失败时记录警告并使用该文件的内置模板
+    ///
+    /// # 参数
+    ///
+    /// * `dir` - 覆盖模板目录
+    pub fn load(dir: &Path) -> Self {
+        let mut templates = Self::builtin();
+        if !dir.is_dir() {
+            return templates;
+        }
+        for (name, template) in [
+            ("layout.html", &mut templates.layout),
+            ("result.html", &mut templates.result),
+            ("answer.html", &mut templates.answer),
+            ("style.css", &mut templates.stylesheet),
+        ] {
+            let path = dir.join(name);
+            if !path.is_file() {
+                continue;
+            }
+            match std::fs::read_to_string(&path) {
+                Ok(content) => {
+                    tracing::info!("Using custom template {}", path.display());
+                    *template = content;
+                }
+                Err(e) => tracing::warn!("Failed to read template {}, using built-in: {}", path.display(), e),
+            }
+        }
+        templates
+    }
+}
+
+/// 填充模板中的 `{{name}}` 占位符
+///
+/// 单遍替换，插入的内容中即使包含占位符也不会被再次展开；未提供的占位符替换为空
+///
+/// # 参数
+///
+/// * `template` - 模板
+/// * `vars` - 占位符名称与（已转义的）取值
+pub fn render_template(template: &str, vars: &[(&str, &str)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let Some(end) = rest[start + 2..].find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = rest[start + 2..start + 2 + end].trim();
+        if let Some((_, value)) = vars.iter().find(|(key, _)| *key == name) {
+            output.push_str(value);
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template_single_pass() {
+        let rendered = render_template("<p>{{a}}</p>{{ b }}{{missing}}", &[("a", "{{b}}"), ("b", "x")]);
+        assert_eq!(rendered, "<p>{{b}}</p>x");
+        assert_eq!(render_template("open {{ only", &[]), "open {{ only");
+    }
+
+    #[test]
+    fn test_load_overrides() {
+        let dir = std::env::temp_dir().join(format!("seesea_templates_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("style.css"), "body { color: red; }").unwrap();
+
+        let templates = Templates::load(&dir);
+        assert_eq!(templates.stylesheet, "body { color: red; }");
+        assert_eq!(templates.layout, Templates::builtin().layout);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(Templates::load(&dir).stylesheet, Templates::builtin().stylesheet);
+    }
+}
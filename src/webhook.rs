@@ -0,0 +1,345 @@
+// Copyright 2025 nostalgiatan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Webhook 事件投递
+//!
+//! 将 RSS 新条目匹配、引擎被熔断禁用或降级等事件以 JSON POST 到配置的端点；
+//! 配置了密钥的端点附带 HMAC-SHA256 签名，投递失败时按指数退避重试
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, KeyInit, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::cache::AlertResult;
+use crate::config::{WebhookConfig, WebhookEndpointConfig, WebhookEventKind};
+use crate::net::{HttpClient, NetworkConfig, RequestOptions};
+
+/// 事件类型请求头
+pub const EVENT_HEADER: &str = "X-SeeSea-Event";
+/// 投递 ID 请求头（同一事件的重试使用相同 ID）
+pub const DELIVERY_HEADER: &str = "X-SeeSea-Delivery";
+/// 签名请求头，值为 `sha256=<hex>`
+pub const SIGNATURE_HEADER: &str = "X-SeeSea-Signature";
+
+/// Webhook 事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// 订阅的 RSS feed 出现匹配保存查询的新条目
+    RssItemsMatched {
+        /// feed URL
+        feed_url: String,
+        /// 匹配的保存查询
+        query: String,
+        /// 匹配的新条目
+        items: Vec<MatchedRssItem>,
+    },
+    /// 引擎因连续零结果被临时禁用
+    EngineDisabled {
+        /// 引擎名称
+        engine: String,
+        /// 连续失败次数
+        consecutive_failures: u32,
+        /// 禁用时长（秒）
+        disabled_for_secs: u64,
+        /// 禁用原因
+        reason: String,
+    },
+    /// 抓取引擎连续多个不同查询在成功响应下返回零结果（疑似选择器失效）
+    EngineDegraded {
+        /// 引擎名称
+        engine: String,
+        /// 连续返回零结果的不同查询数
+        zero_result_queries: usize,
+        /// 降级原因
+        reason: String,
+    },
+    /// 保存的搜索出现新结果
+    SavedSearchMatched {
+        /// 保存的搜索 ID
+        saved_search_id: String,
+        /// 查询文本
+        query: String,
+        /// 新结果
+        results: Vec<AlertResult>,
+    },
+}
+
+/// 匹配保存查询的 RSS 条目
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchedRssItem {
+    /// 标题
+    pub title: String,
+    /// 链接
+    pub link: String,
+    /// 发布时间
+    pub pub_date: Option<String>,
+}
+
+impl WebhookEvent {
+    /// 事件类型
+    pub fn kind(&self) -> WebhookEventKind {
+        match self {
+            Self::RssItemsMatched { .. } => WebhookEventKind::RssItemsMatched,
+            Self::EngineDisabled { .. } => WebhookEventKind::EngineDisabled,
+            Self::EngineDegraded { .. } => WebhookEventKind::EngineDegraded,
+            Self::SavedSearchMatched { .. } => WebhookEventKind::SavedSearchMatched,
+        }
+    }
+}
+
+/// 投递的 JSON 负载
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    /// 投递 ID
+    id: &'a str,
+    /// 事件发生时间
+    timestamp: DateTime<Utc>,
+    /// 事件内容
+    #[serde(flatten)]
+    event: &'a WebhookEvent,
+}
+
+/// 计算负载签名
+///
+/// # 参数
+///
+/// * `secret` - 端点密钥
+/// * `body` - 请求体
+///
+/// # 返回值
+///
+/// HMAC-SHA256 的小写十六进制表示
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 响应状态是否值得重试（限流与服务端错误）
+fn should_retry(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+/// 第 `attempt` 次重试前的等待时间
+fn backoff(initial_ms: u64, attempt: u32) -> Duration {
+    Duration::from_millis(initial_ms.saturating_mul(1u64 << attempt.min(16)))
+}
+
+/// Webhook 分发器
+///
+/// 事件在后台任务中投递，调用方不等待投递结果
+pub struct WebhookDispatcher {
+    /// Webhook 配置
+    config: Arc<WebhookConfig>,
+    /// HTTP 客户端
+    client: Arc<HttpClient>,
+}
+
+impl WebhookDispatcher {
+    /// 创建分发器
+    ///
+    /// # 参数
+    ///
+    /// * `config` - Webhook 配置
+    pub fn new(config: WebhookConfig) -> crate::Result<Self> {
+        let client = HttpClient::new(NetworkConfig::default())?;
+        Ok(Self {
+            config: Arc::new(config),
+            client: Arc::new(client),
+        })
+    }
+
+    /// Webhook 配置
+    pub fn config(&self) -> &WebhookConfig {
+        &self.config
+    }
+
+    /// 分发事件
+    ///
+    /// 向订阅该事件的每个端点启动一个投递任务；不在 Tokio 运行时中调用时丢弃事件
+    ///
+    /// # 返回值
+    ///
+    /// 启动的投递任务数
+    pub fn dispatch(&self, event: WebhookEvent) -> usize {
+        let endpoints: Vec<_> = self
+            .config
+            .endpoints
+            .iter()
+            .filter(|endpoint| endpoint.subscribes_to(event.kind()))
+            .cloned()
+            .collect();
+        if endpoints.is_empty() {
+            return 0;
+        }
+
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!("不在异步运行时中，丢弃 Webhook 事件 {}", event.kind().as_str());
+            return 0;
+        };
+
+        let id = format!("{:032x}", rand::random::<u128>());
+        let payload = WebhookPayload {
+            id: &id,
+            timestamp: Utc::now(),
+            event: &event,
+        };
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => Arc::new(body),
+            Err(e) => {
+                tracing::warn!("序列化 Webhook 事件失败: {}", e);
+                return 0;
+            }
+        };
+
+        for endpoint in &endpoints {
+            let client = Arc::clone(&self.client);
+            let config = Arc::clone(&self.config);
+            let endpoint = endpoint.clone();
+            let body = Arc::clone(&body);
+            let id = id.clone();
+            let kind = event.kind();
+            handle.spawn(async move {
+                deliver(&client, &config, &endpoint, kind, &id, &body).await;
+            });
+        }
+        endpoints.len()
+    }
+}
+
+/// 向单个端点投递，失败时按指数退避重试
+///
+/// # 返回值
+///
+/// 端点返回 2xx 时为 true
+async fn deliver(
+    client: &HttpClient,
+    config: &WebhookConfig,
+    endpoint: &WebhookEndpointConfig,
+    kind: WebhookEventKind,
+    id: &str,
+    body: &[u8],
+) -> bool {
+    let mut headers = vec![
+        ("Content-Type".to_string(), "application/json".to_string()),
+        (EVENT_HEADER.to_string(), kind.as_str().to_string()),
+        (DELIVERY_HEADER.to_string(), id.to_string()),
+    ];
+    if let Some(secret) = &endpoint.secret {
+        headers.push((SIGNATURE_HEADER.to_string(), format!("sha256={}", sign_payload(secret, body))));
+    }
+
+    for attempt in 0..=config.max_retries {
+        if attempt > 0 {
+            tokio::time::sleep(backoff(config.initial_backoff_ms, attempt - 1)).await;
+        }
+
+        let options = RequestOptions {
+            timeout: Duration::from_secs(config.timeout_secs),
+            headers: headers.clone(),
+            ..Default::default()
+        };
+        match client.post(&endpoint.url, body.to_vec(), Some(options)).await {
+            Ok(response) if response.status().is_success() => return true,
+            Ok(response) => {
+                let status = response.status().as_u16();
+                if !should_retry(status) {
+                    tracing::warn!("Webhook {} 拒绝事件 {}: HTTP {}", endpoint.url, id, status);
+                    return false;
+                }
+                tracing::debug!("Webhook {} 返回 HTTP {}，第 {} 次尝试", endpoint.url, status, attempt + 1);
+            }
+            Err(e) => {
+                tracing::debug!("Webhook {} 投递失败（第 {} 次尝试）: {}", endpoint.url, attempt + 1, e);
+            }
+        }
+    }
+
+    tracing::warn!("Webhook {} 投递事件 {} 失败，已重试 {} 次", endpoint.url, id, config.max_retries);
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        assert_eq!(
+            sign_payload("key", b"The quick brown fox jumps over the lazy dog"),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_payload_serialization() {
+        let event = WebhookEvent::EngineDisabled {
+            engine: "bing".to_string(),
+            consecutive_failures: 2,
+            disabled_for_secs: 1500,
+            reason: "zero results".to_string(),
+        };
+        let payload = WebhookPayload {
+            id: "abc",
+            timestamp: Utc::now(),
+            event: &event,
+        };
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["event"], "engine_disabled");
+        assert_eq!(json["id"], "abc");
+        assert_eq!(json["engine"], "bing");
+        assert_eq!(json["disabled_for_secs"], 1500);
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        assert!(should_retry(429));
+        assert!(should_retry(503));
+        assert!(!should_retry(404));
+        assert_eq!(backoff(500, 0), Duration::from_millis(500));
+        assert_eq!(backoff(500, 2), Duration::from_millis(2000));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_filters_endpoints() {
+        let config = WebhookConfig {
+            enabled: true,
+            endpoints: vec![WebhookEndpointConfig {
+                url: "http://127.0.0.1:9/hook".to_string(),
+                secret: None,
+                events: vec![WebhookEventKind::RssItemsMatched],
+            }],
+            max_retries: 0,
+            ..Default::default()
+        };
+        let dispatcher = WebhookDispatcher::new(config).unwrap();
+        let event = WebhookEvent::EngineDisabled {
+            engine: "bing".to_string(),
+            consecutive_failures: 1,
+            disabled_for_secs: 300,
+            reason: "zero results".to_string(),
+        };
+        assert_eq!(dispatcher.dispatch(event), 0);
+    }
+}
@@ -0,0 +1,289 @@
+//! Shared helpers for integration tests
+//!
+//! [`MockServer`] is a small wiremock-style HTTP server for end-to-end engine tests:
+//! mount [`Mock`]s matching method, path and query parameters, point an engine's
+//! `HttpClient` at the server with `HttpClient::with_origin_override` (or
+//! [`MockServer::client_for`]), then assert on the requests the engine sent.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::body::{Body, Bytes};
+use axum::extract::State;
+use axum::http::{HeaderMap, Method, StatusCode, Uri};
+use axum::response::Response;
+use axum::Router;
+use tokio::sync::oneshot;
+
+use seesea_core::net::{HttpClient, NetworkConfig};
+
+/// Read a file from `tests/fixtures/`
+pub fn fixture(path: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(path);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e))
+}
+
+/// A request received by the mock server
+#[derive(Debug, Clone)]
+pub struct ReceivedRequest {
+    /// HTTP method
+    pub method: String,
+    /// Request path
+    pub path: String,
+    /// Decoded query parameters
+    pub query: HashMap<String, String>,
+    /// Request headers (lowercase names)
+    pub headers: HashMap<String, String>,
+    /// Request body
+    pub body: Vec<u8>,
+}
+
+impl ReceivedRequest {
+    /// Value of a query parameter
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.get(name).map(String::as_str)
+    }
+
+    /// Value of a header (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+}
+
+/// Canned response returned by a [`Mock`]
+#[derive(Debug, Clone)]
+pub struct ResponseTemplate {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    delay: Option<Duration>,
+}
+
+impl ResponseTemplate {
+    /// Empty response with the given status
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+            delay: None,
+        }
+    }
+
+    /// Set the response body
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Set a JSON body and content type
+    pub fn json(self, value: &serde_json::Value) -> Self {
+        self.header("Content-Type", "application/json")
+            .body(serde_json::to_vec(value).unwrap())
+    }
+
+    /// Add a response header
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Delay the response (for timeout tests)
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+/// Request matcher with its response
+///
+/// Mocks are tried in mount order; the first matching mock with remaining uses responds
+#[derive(Debug, Clone)]
+pub struct Mock {
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    response: ResponseTemplate,
+    remaining: Option<usize>,
+}
+
+impl Mock {
+    /// Match requests with the given method and path
+    pub fn given(method: &str, path: &str) -> Self {
+        Self {
+            method: method.parse().expect("valid HTTP method"),
+            path: path.to_string(),
+            query: Vec::new(),
+            response: ResponseTemplate::new(200),
+            remaining: None,
+        }
+    }
+
+    /// Also require a query parameter value
+    pub fn query_param(mut self, name: &str, value: &str) -> Self {
+        self.query.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Response to return when matched
+    pub fn respond_with(mut self, response: ResponseTemplate) -> Self {
+        self.response = response;
+        self
+    }
+
+    /// Stop matching after `n` requests (later mocks take over)
+    pub fn up_to_n_times(mut self, n: usize) -> Self {
+        self.remaining = Some(n);
+        self
+    }
+
+    fn matches(&self, request: &ReceivedRequest) -> bool {
+        self.remaining != Some(0)
+            && self.method.as_str() == request.method
+            && self.path == request.path
+            && self
+                .query
+                .iter()
+                .all(|(name, value)| request.query_param(name) == Some(value.as_str()))
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    mocks: Vec<Mock>,
+    requests: Vec<ReceivedRequest>,
+}
+
+/// Local HTTP server answering with mounted mocks
+///
+/// Unmatched requests get `404`. The server stops when dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Start a server on a random local port
+    pub async fn start() -> Self {
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (shutdown, signal) = oneshot::channel();
+
+        let router = Router::new().fallback(handle).with_state(Arc::clone(&state));
+        tokio::spawn(async move {
+            axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = signal.await;
+                })
+                .await
+                .unwrap();
+        });
+
+        Self {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    /// Base URI of the server, e.g. `http://127.0.0.1:38211`
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Register a mock
+    pub fn mount(&self, mock: Mock) {
+        self.state.lock().unwrap().mocks.push(mock);
+    }
+
+    /// Requests received so far, in arrival order
+    pub fn received_requests(&self) -> Vec<ReceivedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Wait until at least `count` requests arrived (for background deliveries)
+    pub async fn wait_for_requests(&self, count: usize, timeout: Duration) -> Vec<ReceivedRequest> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let requests = self.received_requests();
+            if requests.len() >= count || tokio::time::Instant::now() >= deadline {
+                return requests;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /// HTTP client sending requests for each of `origins` to this server
+    pub fn client_for(&self, origins: &[&str]) -> Arc<HttpClient> {
+        let uri = self.uri();
+        let client = origins.iter().fold(
+            HttpClient::new(NetworkConfig::default()).unwrap(),
+            |client, origin| client.with_origin_override(origin, &uri),
+        );
+        Arc::new(client)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+async fn handle(
+    State(state): State<Arc<Mutex<MockState>>>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let request = ReceivedRequest {
+        method: method.to_string(),
+        path: uri.path().to_string(),
+        query: url::form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect(),
+        headers: headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str().to_string(), value.to_str().ok()?.to_string())))
+            .collect(),
+        body: body.to_vec(),
+    };
+
+    let template = {
+        let mut state = state.lock().unwrap();
+        let template = state.mocks.iter_mut().find(|mock| mock.matches(&request)).map(|mock| {
+            if let Some(remaining) = mock.remaining.as_mut() {
+                *remaining -= 1;
+            }
+            mock.response.clone()
+        });
+        state.requests.push(request);
+        template
+    };
+
+    let Some(template) = template else {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("no mock matched"))
+            .unwrap();
+    };
+    if let Some(delay) = template.delay {
+        tokio::time::sleep(delay).await;
+    }
+
+    let mut response = Response::builder().status(template.status);
+    for (name, value) in &template.headers {
+        response = response.header(name, value);
+    }
+    response.body(Body::from(template.body)).unwrap()
+}
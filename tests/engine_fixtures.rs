@@ -0,0 +1,176 @@
+//! Golden-file tests for engine response parsers
+//!
+//! Every engine with fixtures has a directory `tests/fixtures/<engine>/` holding
+//! sanitized responses (`*.html`, `*.json`, `*.xml`). Each fixture is fed to the
+//! engine's `response()` parser and the structured output is compared with the
+//! `<fixture>.snap.json` snapshot stored next to it.
+//!
+//! After an intentional parser change, regenerate the snapshots and review the diff:
+//!
+//! ```bash
+//! SEESEA_UPDATE_FIXTURES=1 cargo test --test engine_fixtures
+//! ```
+
+#[cfg(test)]
+mod engine_fixture_tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use serde_json::{json, Value};
+
+    use seesea_core::derive::{RequestParams, RequestResponseEngine};
+    use seesea_core::search::engines::*;
+
+    /// Environment variable that rewrites snapshots instead of comparing them
+    const UPDATE_ENV: &str = "SEESEA_UPDATE_FIXTURES";
+    /// Suffix of snapshot files (`web.html` -> `web.snap.json`)
+    const SNAPSHOT_SUFFIX: &str = ".snap.json";
+    /// Extensions treated as engine responses
+    const FIXTURE_EXTENSIONS: &[&str] = &["html", "json", "xml"];
+
+    /// Root directory of the fixtures
+    fn fixtures_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures")
+    }
+
+    /// Run a parser over one response and capture items, next-page cursor or error
+    fn run<E: RequestResponseEngine>(engine: &E, resp: E::Response) -> Value {
+        let next_cursor = engine.next_cursor(&resp, &RequestParams::default());
+        match engine.response(resp) {
+            Ok(items) => {
+                let mut snapshot = json!({ "items": items });
+                if let Some(cursor) = next_cursor {
+                    snapshot["next_cursor"] = Value::String(cursor);
+                }
+                snapshot
+            }
+            Err(e) => json!({ "error": e.to_string() }),
+        }
+    }
+
+    /// Parse a fixture with the engine named by its directory
+    ///
+    /// Returns `None` for directories that do not map to an engine
+    fn parse(engine: &str, body: String) -> Option<Value> {
+        let snapshot = match engine {
+            "arxiv" => run(&ArxivEngine::new(), body),
+            "baidu" => run(&BaiduEngine::new(), (body, None)),
+            "bilibili" => run(&BilibiliEngine::new(), body),
+            "bing" => run(&BingEngine::new(), body),
+            "bing_images" => run(&BingImagesEngine::new(), body),
+            "bing_news" => run(&BingNewsEngine::new(), body),
+            "bing_videos" => run(&BingVideosEngine::new(), body),
+            "crossref" => run(&CrossrefEngine::new(), body),
+            "ebay" => run(&EbayEngine::new(), body),
+            "github" => run(&GitHubEngine::new(), body),
+            "nominatim" => run(&NominatimEngine::new(), body),
+            "piratebay" => run(&PirateBayEngine::new(), body),
+            "semantic_scholar" => run(&SemanticScholarEngine::new(), body),
+            "sogou" => run(&SogouEngine::new(), body),
+            "sogou_images" => run(&SogouImagesEngine::new(), body),
+            "sogou_videos" => run(&SogouVideosEngine::new(), body),
+            "sogou_wechat" => run(&SogouWeChatEngine::new(), body),
+            "stackoverflow" => run(&StackOverflowEngine::new(), body),
+            "unsplash" => run(&UnsplashEngine::new(), body),
+            "yandex" => run(&YandexEngine::new(), (body, None)),
+            _ => return None,
+        };
+        Some(canonical(snapshot))
+    }
+
+    /// Sort object keys recursively so metadata maps produce stable snapshots
+    fn canonical(value: Value) -> Value {
+        match value {
+            Value::Object(map) => {
+                let sorted: BTreeMap<String, Value> =
+                    map.into_iter().map(|(k, v)| (k, canonical(v))).collect();
+                Value::Object(sorted.into_iter().collect())
+            }
+            Value::Array(values) => Value::Array(values.into_iter().map(canonical).collect()),
+            other => other,
+        }
+    }
+
+    /// Fixture files of one engine directory, sorted by name
+    fn fixture_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+                let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+                !name.ends_with(SNAPSHOT_SUFFIX) && FIXTURE_EXTENSIONS.contains(&extension)
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Snapshot path for a fixture
+    fn snapshot_path(fixture: &Path) -> PathBuf {
+        let stem = fixture.file_stem().and_then(|s| s.to_str()).unwrap();
+        fixture.with_file_name(format!("{}{}", stem, SNAPSHOT_SUFFIX))
+    }
+
+    #[test]
+    fn test_engine_fixtures_match_snapshots() {
+        let update = std::env::var_os(UPDATE_ENV).is_some();
+        let mut engine_dirs: Vec<PathBuf> = fs::read_dir(fixtures_dir())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.is_dir())
+            .collect();
+        engine_dirs.sort();
+        assert!(!engine_dirs.is_empty(), "no engine fixtures found");
+
+        let mut checked = 0;
+        let mut failures = Vec::new();
+        for dir in engine_dirs {
+            let engine = dir.file_name().and_then(|n| n.to_str()).unwrap().to_string();
+            for fixture in fixture_files(&dir) {
+                let body = fs::read_to_string(&fixture).unwrap();
+                let Some(actual) = parse(&engine, body) else {
+                    failures.push(format!("{}: no parser registered for engine '{}'", dir.display(), engine));
+                    break;
+                };
+                checked += 1;
+
+                let snapshot = snapshot_path(&fixture);
+                let rendered = format!("{}\n", serde_json::to_string_pretty(&actual).unwrap());
+                if update {
+                    fs::write(&snapshot, rendered).unwrap();
+                    continue;
+                }
+
+                match fs::read_to_string(&snapshot) {
+                    Ok(expected) => {
+                        let expected: Value = serde_json::from_str(&expected).unwrap();
+                        if expected != actual {
+                            failures.push(format!(
+                                "{} does not match {}; actual output:\n{}",
+                                fixture.display(),
+                                snapshot.display(),
+                                rendered
+                            ));
+                        }
+                    }
+                    Err(_) => failures.push(format!(
+                        "{} has no snapshot; run with {}=1 to create it",
+                        fixture.display(),
+                        UPDATE_ENV
+                    )),
+                }
+            }
+        }
+
+        assert!(checked > 0, "no fixture files found");
+        assert!(
+            failures.is_empty(),
+            "{} fixture(s) failed (set {}=1 to accept changes):\n\n{}",
+            failures.len(),
+            UPDATE_ENV,
+            failures.join("\n\n")
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! End-to-end engine tests against a local mock HTTP server
+
+mod common;
+
+#[cfg(test)]
+mod engine_mock_server_tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::common::{fixture, Mock, MockServer, ResponseTemplate};
+    use seesea_core::config::engines::{AuthenticationConfig, EngineAuthType, EngineSessionConfig, TokenRefreshConfig};
+    use seesea_core::config::{WebhookConfig, WebhookEndpointConfig};
+    use seesea_core::derive::{SearchEngine, SearchQuery, CURSOR_PARAM_KEY, NEXT_CURSOR_METADATA_KEY};
+    use seesea_core::search::engines::{BaiduEngine, BingEngine, CrossrefEngine};
+    use seesea_core::net::{EngineAuth, EngineSession, HttpClient, NetworkConfig};
+    use seesea_core::webhook::{DELIVERY_HEADER, EVENT_HEADER};
+    use seesea_core::{WebhookDispatcher, WebhookEvent};
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery {
+            query: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bing_search_end_to_end() {
+        let server = MockServer::start().await;
+        server.mount(
+            Mock::given("GET", "/search")
+                .respond_with(ResponseTemplate::new(200).body(fixture("bing/web.html"))),
+        );
+        let engine = BingEngine::with_client(server.client_for(&["https://www.bing.com"]));
+
+        let result = engine.search(&query("rust programming")).await.unwrap();
+        assert_eq!(result.items.len(), 3);
+        assert_eq!(result.items[0].url, "https://www.rust-lang.org/learn");
+
+        let requests = server.received_requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].query_param("q"), Some("rust programming"));
+    }
+
+    #[tokio::test]
+    async fn test_bing_rate_limited() {
+        let server = MockServer::start().await;
+        server.mount(Mock::given("GET", "/search").respond_with(ResponseTemplate::new(429)));
+        let engine = BingEngine::with_client(server.client_for(&["https://www.bing.com"]));
+
+        let error = engine.search(&query("rust")).await.unwrap_err();
+        assert!(error.to_string().contains("请求过于频繁"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_baidu_captcha_page() {
+        let server = MockServer::start().await;
+        server.mount(
+            Mock::given("GET", "/s")
+                .respond_with(ResponseTemplate::new(200).body(fixture("baidu/captcha.html"))),
+        );
+        let engine = BaiduEngine::with_client(server.client_for(&["https://www.baidu.com"]));
+
+        let error = engine.search(&query("rust")).await.unwrap_err();
+        assert!(error.to_string().contains("反爬虫"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_crossref_cursor_pagination() {
+        let server = MockServer::start().await;
+        server.mount(
+            Mock::given("GET", "/works")
+                .query_param("cursor", "*")
+                .respond_with(ResponseTemplate::new(200).body(fixture("crossref/works.json"))),
+        );
+        server.mount(
+            Mock::given("GET", "/works")
+                .query_param("cursor", "DnF1ZXJ5VGhlbkZldGNo")
+                .respond_with(ResponseTemplate::new(200).json(&json!({
+                    "status": "ok",
+                    "message": { "items": [] }
+                }))),
+        );
+        let engine = CrossrefEngine::with_client(server.client_for(&["https://api.crossref.org"]));
+
+        let first = engine.search(&query("deep learning")).await.unwrap();
+        assert_eq!(first.items.len(), 1);
+        let cursor = first.metadata.get(NEXT_CURSOR_METADATA_KEY).cloned().unwrap();
+
+        let second_query = SearchQuery {
+            page: 2,
+            params: HashMap::from([(CURSOR_PARAM_KEY.to_string(), cursor)]),
+            ..query("deep learning")
+        };
+        let second = engine.search(&second_query).await.unwrap();
+        assert!(second.items.is_empty());
+        assert!(!second.metadata.contains_key(NEXT_CURSOR_METADATA_KEY));
+
+        let requests = server.received_requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].query_param("cursor"), Some("DnF1ZXJ5VGhlbkZldGNo"));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retries_after_server_error() {
+        let server = MockServer::start().await;
+        server.mount(
+            Mock::given("POST", "/hook")
+                .up_to_n_times(1)
+                .respond_with(ResponseTemplate::new(503)),
+        );
+        server.mount(Mock::given("POST", "/hook").respond_with(ResponseTemplate::new(204)));
+
+        let dispatcher = WebhookDispatcher::new(WebhookConfig {
+            enabled: true,
+            endpoints: vec![WebhookEndpointConfig {
+                url: format!("{}/hook", server.uri()),
+                secret: None,
+                events: Vec::new(),
+            }],
+            max_retries: 3,
+            initial_backoff_ms: 10,
+            ..Default::default()
+        })
+        .unwrap();
+        let sent = dispatcher.dispatch(WebhookEvent::EngineDisabled {
+            engine: "bing".to_string(),
+            consecutive_failures: 1,
+            disabled_for_secs: 300,
+            reason: "zero results".to_string(),
+        });
+        assert_eq!(sent, 1);
+
+        let requests = server.wait_for_requests(2, Duration::from_secs(5)).await;
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].header(EVENT_HEADER), Some("engine_disabled"));
+        assert_eq!(requests[0].header(DELIVERY_HEADER), requests[1].header(DELIVERY_HEADER));
+
+        // 成功后不再重试
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(server.received_requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_oauth_token_refreshed_on_unauthorized() {
+        let server = MockServer::start().await;
+        server.mount(
+            Mock::given("POST", "/token")
+                .up_to_n_times(1)
+                .respond_with(ResponseTemplate::new(200).json(&json!({"access_token": "t1", "expires_in": 3600, "refresh_token": "r2"}))),
+        );
+        server.mount(
+            Mock::given("POST", "/token").respond_with(ResponseTemplate::new(200).json(&json!({"access_token": "t2"}))),
+        );
+        server.mount(Mock::given("GET", "/search").up_to_n_times(1).respond_with(ResponseTemplate::new(401)));
+        server.mount(Mock::given("GET", "/search").respond_with(ResponseTemplate::new(200).body("ok")));
+
+        let auth = EngineAuth::from_config(&AuthenticationConfig {
+            auth_type: EngineAuthType::OAuth2,
+            credentials: HashMap::new(),
+            token_refresh: Some(TokenRefreshConfig {
+                token_endpoint: format!("{}/token", server.uri()),
+                client_id: "seesea".to_string(),
+                client_secret: "secret".to_string(),
+                refresh_token: "r1".to_string(),
+                refresh_before_expiry: 60,
+            }),
+        })
+        .unwrap();
+        let client = HttpClient::new(NetworkConfig::default()).unwrap().with_auth(std::sync::Arc::new(auth));
+
+        let response = client.get(&format!("{}/search?q=rust", server.uri()), None).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        let requests = server.received_requests();
+        let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, vec!["/token", "/search", "/token", "/search"]);
+        assert!(String::from_utf8_lossy(&requests[0].body).contains("refresh_token=r1"));
+        assert_eq!(requests[1].header("authorization"), Some("Bearer t1"));
+        // 轮换后的刷新令牌用于下一次刷新
+        assert!(String::from_utf8_lossy(&requests[2].body).contains("refresh_token=r2"));
+        assert_eq!(requests[3].header("authorization"), Some("Bearer t2"));
+    }
+
+    #[tokio::test]
+    async fn test_session_cookie_replayed() {
+        let server = MockServer::start().await;
+        server.mount(
+            Mock::given("GET", "/consent")
+                .respond_with(ResponseTemplate::new(200).header("set-cookie", "CONSENT=YES; Path=/; Max-Age=3600").body("ok")),
+        );
+        server.mount(Mock::given("GET", "/search").respond_with(ResponseTemplate::new(200).body("ok")));
+
+        let config = EngineSessionConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let session = std::sync::Arc::new(EngineSession::new("mock", &config, None));
+        let client = HttpClient::new(NetworkConfig::default()).unwrap().with_session(session);
+
+        client.get(&format!("{}/consent", server.uri()), None).await.unwrap();
+        client.get(&format!("{}/search?q=rust", server.uri()), None).await.unwrap();
+
+        let requests = server.received_requests();
+        assert_eq!(requests[0].header("cookie"), None);
+        assert_eq!(requests[1].header("cookie"), Some("CONSENT=YES"));
+    }
+}
@@ -11,6 +11,7 @@ use seesea_core::derive::types::{SearchQuery, SearchResultItem, EngineType, Resu
 use seesea_core::derive::SearchResult;
 use seesea_core::config::common::SafeSearchLevel;
 use seesea_core::derive::rss::{RssFeed, RssFeedItem, RssFeedMeta};
+use seesea_core::search::{ChineseMatching, KeywordMatcher};
 
 /// 创建临时缓存配置用于测试
 fn temp_cache_config() -> CacheImplConfig {
@@ -47,6 +48,9 @@ fn sample_query(query_text: &str) -> SearchQuery {
         safe_search: SafeSearchLevel::Moderate,
         time_range: None,
         params: HashMap::new(),
+        lat: None,
+        lon: None,
+        bbox: None,
     }
 }
 
@@ -138,6 +142,44 @@ fn test_result_cache_fulltext_search() {
     assert_eq!(limited_results.len(), 1, "应该只返回一个结果");
 }
 
+#[test]
+#[serial]
+fn test_result_cache_fulltext_script_and_pinyin() {
+    let config = temp_cache_config();
+    let cache_interface = CacheInterface::new(config).expect("创建缓存接口失败");
+    let result_cache = cache_interface.results();
+
+    let items = vec![
+        sample_result_item("北京天气预报", "https://weather.example.com/beijing", "今日多云"),
+        sample_result_item("機器學習入門", "https://ml.example.com", "從零開始"),
+    ];
+    let result = sample_search_result("TestEngine", items);
+    result_cache.set(&sample_query("天气"), "TestEngine", &result, None).expect("缓存失败");
+
+    // 简体关键词匹配繁体内容
+    let found = result_cache.search_fulltext(&["机器学习".to_string()], false, None)
+        .expect("全文搜索失败");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].url, "https://ml.example.com");
+
+    // 繁体关键词匹配简体内容
+    let found = result_cache.search_fulltext(&["天氣".to_string()], false, None)
+        .expect("全文搜索失败");
+    assert_eq!(found.len(), 1);
+
+    // 拼音关键词匹配中文内容
+    let found = result_cache.search_fulltext(&["jiqi".to_string()], false, None)
+        .expect("全文搜索失败");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].title, "機器學習入門");
+
+    // 关闭拼音匹配后不再命中
+    let matcher = KeywordMatcher::new(["jiqi"], ChineseMatching { pinyin: false, ..ChineseMatching::default() });
+    let found = result_cache.search_fulltext_with(&matcher, false, None)
+        .expect("全文搜索失败");
+    assert!(found.is_empty());
+}
+
 #[test]
 #[serial]
 fn test_result_cache_search_with_stale() {
@@ -0,0 +1,143 @@
+//! Property tests for query parsing and result standardization on adversarial input
+//!
+//! Inputs are proptest strategies (huge queries, mixed scripts, combining marks,
+//! HTML entities, lossy-decoded invalid UTF-8, odd URLs), so failures shrink to a
+//! minimal case and are recorded under `proptest-regressions/`. Set `PROPTEST_CASES`
+//! to run more cases. The `fuzz/` directory holds matching cargo-fuzz targets for
+//! open-ended fuzzing.
+
+#[cfg(test)]
+mod input_robustness_tests {
+    use proptest::prelude::*;
+    use proptest::sample::select;
+    use seesea_core::api::handlers::redirect::strip_tracking_params;
+    use seesea_core::derive::MagnetLink;
+    use seesea_core::search::{clean_text, highlight_terms, highlight_text, HighlightMarkers, QueryParser};
+    use unicode_segmentation::UnicodeSegmentation;
+
+    /// Fragments that tend to break text handling
+    const FRAGMENTS: &[&str] = &[
+        " ", "  ", "\t", "\n", "\r\n", "\u{a0}", "\u{3000}", "\u{200b}", "\u{feff}",
+        "a", "Rust", "site:", "-", "\"", "'", ":", "&", "&amp;", "&lt;", "&#", "&#xD800;",
+        "&#x110000;", "&#128512;", "&nbsp;", "&unknown;", "<em>", "</em>", "<", ">",
+        "é", "e\u{301}", "ß", "İ", "ﬀ", "搜索", "视频", "附近", "日本語", "한국어",
+        "Привет", "مرحبا", "😀", "👨‍👩‍👧", "\u{0}", "\u{1b}[31m", "\u{fffd}", "...",
+        "buy", "near me", "code:", "user@example.com", "+1 555 123 4567", "%", "%FF",
+    ];
+
+    /// One piece of text: a fragment, an arbitrary char or lossy-decoded bytes
+    fn part() -> impl Strategy<Value = String> {
+        prop_oneof![
+            2 => select(FRAGMENTS).prop_map(str::to_string),
+            1 => any::<char>().prop_map(String::from),
+            1 => prop::collection::vec(any::<u8>(), 1..8).prop_map(|bytes| String::from_utf8_lossy(&bytes).into_owned()),
+        ]
+    }
+
+    /// Random text mixing fragments, arbitrary chars and lossy-decoded bytes
+    fn text() -> impl Strategy<Value = String> {
+        // 偶尔生成超长输入
+        prop_oneof![
+            31 => prop::collection::vec(part(), 0..24),
+            1 => prop::collection::vec(part(), 1000..5000),
+        ]
+        .prop_map(|parts| parts.concat())
+    }
+
+    /// Random URL-like string built from plausible and hostile parts
+    fn url() -> impl Strategy<Value = String> {
+        const SCHEMES: &[&str] = &["http://", "https://", "HTTPS://", "ftp://", "magnet:?", "", "//", "javascript:"];
+        const HOSTS: &[&str] = &[
+            "example.com", "EXAMPLE.com.", "例子.测试", "xn--fsqu00a.xn--0zwm56d", "[::1]", "127.0.0.1:8080",
+            "user:pass@host", "host:99999", "a..b", "", "%41", "😀.example",
+        ];
+        const PARAMS: &[&str] = &[
+            "utm_source=x", "UTM_Medium=y", "fbclid=abc", "id=7", "q=rust%20lang", "a=%FF", "=", "&&", "x",
+            "redirect=http://evil", "k=v#frag", "%zz=1",
+        ];
+        (
+            select(SCHEMES),
+            select(HOSTS),
+            text(),
+            prop::collection::vec(select(PARAMS), 0..6),
+        )
+            .prop_map(|(scheme, host, path, params)| {
+                let mut url = format!("{}{}/{}", scheme, host, path.chars().take(20).collect::<String>());
+                for (i, param) in params.iter().enumerate() {
+                    url.push(if i == 0 { '?' } else { '&' });
+                    url.push_str(param);
+                }
+                url
+            })
+    }
+
+    /// Infohash candidates: valid hex, valid base32 or arbitrary chars
+    fn infohash() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "[0-9a-fA-F]{40}",
+            "[A-Z2-7]{32}",
+            prop::collection::vec(any::<char>(), 0..48).prop_map(|chars| chars.into_iter().collect()),
+        ]
+    }
+
+    fn tracking_rules() -> Vec<String> {
+        vec!["utm_*".to_string(), "fbclid".to_string(), "gclid".to_string()]
+    }
+
+    proptest! {
+        #[test]
+        fn test_query_parser_never_panics(query in text()) {
+            let parser = QueryParser::new().with_pii_scrubbing(true);
+            let parsed = parser.parse(&query);
+            prop_assert_eq!(&parsed.original, &query);
+            prop_assert_eq!(parsed.normalized.as_str(), parsed.normalized.trim());
+            let _ = parser.detect_language(&query);
+        }
+
+        #[test]
+        fn test_clean_text_respects_max_length(input in text(), max_length in 0usize..300) {
+            let cleaned = clean_text(&input, max_length);
+            prop_assert!(cleaned.graphemes(true).count() <= max_length, "output: {:?}", cleaned);
+        }
+
+        #[test]
+        fn test_highlight_only_inserts_markers(input in text(), query in text()) {
+            // 私有区字符不会出现在生成的文本片段中，可安全地作为标记剥离
+            let markers = HighlightMarkers::new("\u{f8f0}", "\u{f8f1}");
+            let is_marker = |c: &char| ('\u{f8f0}'..='\u{f8f1}').contains(c);
+            let input: String = input.chars().filter(|c| !is_marker(c)).collect();
+            let terms = highlight_terms(&query);
+            let highlighted = highlight_text(&input, &terms, &markers);
+            let stripped: String = highlighted.chars().filter(|c| !is_marker(c)).collect();
+            prop_assert_eq!(stripped, input, "terms: {:?}", terms);
+        }
+
+        #[test]
+        fn test_strip_tracking_params_is_idempotent(input in url()) {
+            let rules = tracking_rules();
+            if let Some(stripped) = strip_tracking_params(&input, &rules) {
+                prop_assert!(stripped.starts_with("http://") || stripped.starts_with("https://"), "{:?}", stripped);
+                let parsed = url::Url::parse(&stripped).unwrap();
+                prop_assert!(
+                    parsed.query_pairs().all(|(key, _)| {
+                        let key = key.to_ascii_lowercase();
+                        !key.starts_with("utm_") && key != "fbclid" && key != "gclid"
+                    }),
+                    "output: {:?}",
+                    stripped
+                );
+                prop_assert_eq!(strip_tracking_params(&stripped, &rules), Some(stripped));
+            }
+        }
+
+        #[test]
+        fn test_magnet_parse_never_panics(hash in infohash(), params in url()) {
+            let uri = format!("magnet:?xt=urn:btih:{}&{}", hash, params);
+            if let Some(magnet) = MagnetLink::parse(&uri) {
+                prop_assert_eq!(magnet.infohash.len(), 40);
+                prop_assert_eq!(MagnetLink::parse(&magnet.to_uri()).map(|m| m.infohash), Some(magnet.infohash));
+            }
+            let _ = MagnetLink::normalize_infohash(&hash);
+        }
+    }
+}